@@ -1,23 +1,32 @@
 use std::convert::Infallible;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
-use axum::response::Response;
-use axum::routing::{post, IntoMakeService};
+use axum::extract::connect_info::IntoMakeServiceWithConnectInfo;
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
 use axum::{Extension, Router};
+use hyper::body::HttpBody;
+use hyper::header::HeaderValue;
 use hyper::server::conn::AddrIncoming;
-use hyper::{header, Method, Request, Server};
+use hyper::{header, Body, Method, Request, Server, StatusCode};
 use starknet_core::starknet::StarknetConfig;
 use tower::Service;
-use tower_http::cors::CorsLayer;
+use tower_http::catch_panic::CatchPanicLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
 use tower_http::timeout::TimeoutLayer;
 use tower_http::trace::TraceLayer;
 
+use crate::error_responses::{self, panic_response};
+use crate::rate_limit::{self, RateLimiter};
 use crate::rpc_handler::{self, RpcHandler};
-use crate::ServerConfig;
+use crate::{HeaderValueWrapper, ServerConfig};
 
 /// Helper type for naming the [`Server`]
-pub type StarknetDevnetServer = Server<AddrIncoming, IntoMakeService<Router>>;
+pub type StarknetDevnetServer =
+    Server<AddrIncoming, IntoMakeServiceWithConnectInfo<Router, SocketAddr>>;
 
 /// Helper for constructing a [`Server`].
 /// [`Builder`] is a convenience wrapper around [`Router`] with added support for JSON-RPC and HTTP
@@ -30,6 +39,7 @@ pub type StarknetDevnetServer = Server<AddrIncoming, IntoMakeService<Router>>;
 pub struct Builder<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send + Sync + 'static> {
     address: SocketAddr,
     routes: Router,
+    json_rpc_path: Option<String>,
     json_rpc_handler: TJsonRpcHandler,
     http_api_handler: THttpApiHandler,
     config: Option<ServerConfig>,
@@ -46,6 +56,7 @@ impl<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send + Sync + 'static
         Builder {
             address: addr,
             routes: Router::<hyper::Body>::new(),
+            json_rpc_path: None,
             json_rpc_handler,
             http_api_handler,
             config: None,
@@ -78,6 +89,7 @@ impl<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send + Sync + 'static
     pub fn json_rpc_route(self, path: &str) -> Self {
         Self {
             routes: self.routes.route(path, post(rpc_handler::handle::<TJsonRpcHandler>)),
+            json_rpc_path: Some(path.to_string()),
             ..self
         }
     }
@@ -91,26 +103,100 @@ impl<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send + Sync + 'static
     /// [`ServerConfig`] and all handlers that have Some value. If TJsonRpcHandler and/or
     /// THttpApiHandler are set each methods that serves the route will be able to use it.
     /// https://docs.rs/axum/latest/axum/#using-request-extensions
-    pub fn build(self, starknet_config: &StarknetConfig) -> StarknetDevnetServer {
+    /// Fails if the address can't be bound to, e.g. if the port is already taken
+    pub fn build(
+        self,
+        starknet_config: &StarknetConfig,
+    ) -> Result<StarknetDevnetServer, hyper::Error> {
         let mut svc = self.routes;
 
+        let request_body_limit = starknet_config.request_body_limit;
         svc = svc
+            .layer(CatchPanicLayer::custom(panic_response))
             .layer(Extension(self.json_rpc_handler))
             .layer(Extension(self.http_api_handler))
+            .layer(middleware::from_fn(move |request, next| {
+                limit_request_body(request, next, request_body_limit)
+            }));
+
+        if let Some(requests_per_sec) = starknet_config.rate_limit {
+            // rejected before the body is read, so that limited clients cost as little as possible
+            let rate_limiter = Arc::new(RateLimiter::new(requests_per_sec));
+            svc = svc.layer(middleware::from_fn(move |request, next| {
+                rate_limit::limit_request_rate(request, next, rate_limiter.clone())
+            }));
+        }
+
+        svc = svc
             .layer(TraceLayer::new_for_http())
             .layer(TimeoutLayer::new(Duration::from_secs(starknet_config.timeout.into())));
 
-        if let Some(ServerConfig { allow_origin }) = self.config {
-            svc = svc.layer(
-                // see https://docs.rs/tower-http/latest/tower_http/cors/index.html
-                // for more details
-                CorsLayer::new()
-                    .allow_origin(allow_origin.0)
-                    .allow_headers(vec![header::CONTENT_TYPE])
-                    .allow_methods(vec![Method::GET, Method::POST]),
-            )
+        // every error response, including those of the layers above, gets the shape of its endpoint
+        let json_rpc_path = self.json_rpc_path;
+        svc = svc.layer(middleware::from_fn(move |request, next| {
+            error_responses::format_error_response(request, next, json_rpc_path.clone())
+        }));
+
+        if let Some(ServerConfig { allow_origins }) = self.config {
+            svc = svc.layer(cors_layer(allow_origins));
         }
 
-        Server::bind(&self.address).serve(svc.into_make_service())
+        // the address of the client is needed for limiting the request rate
+        let make_service = svc.into_make_service_with_connect_info::<SocketAddr>();
+        Ok(Server::try_bind(&self.address)?.serve(make_service))
+    }
+}
+
+/// Answers preflight requests and adds the cors headers to responses to requests from
+/// `allow_origins`. Browsers refuse credentials from servers allowing any origin, so credentials
+/// are allowed only if the origins are listed explicitly.
+/// See https://docs.rs/tower-http/latest/tower_http/cors/index.html for more details
+fn cors_layer(allow_origins: Vec<HeaderValueWrapper>) -> CorsLayer {
+    let cors = CorsLayer::new()
+        .allow_headers(vec![header::CONTENT_TYPE])
+        .allow_methods(vec![Method::GET, Method::POST]);
+
+    if allow_origins.iter().any(|origin| **origin == "*") {
+        cors.allow_origin(AllowOrigin::any())
+    } else {
+        cors.allow_origin(AllowOrigin::list(allow_origins.into_iter().map(HeaderValue::from)))
+            .allow_credentials(true)
     }
 }
+
+/// Responds with 413 Payload Too Large to requests whose body exceeds `limit` bytes. The declared
+/// content length is checked first, so that such requests are rejected without reading their body,
+/// but the body is still counted while being read, since the declared length may be missing.
+async fn limit_request_body(request: Request<Body>, next: Next<Body>, limit: usize) -> Response {
+    let content_length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if matches!(content_length, Some(length) if length > limit) {
+        return payload_too_large(limit);
+    }
+
+    let (parts, mut body) = request.into_parts();
+    let mut buffered = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                return (StatusCode::BAD_REQUEST, format!("Failed to read request body: {err}"))
+                    .into_response();
+            }
+        };
+        if buffered.len() + chunk.len() > limit {
+            return payload_too_large(limit);
+        }
+        buffered.extend_from_slice(&chunk);
+    }
+
+    next.run(Request::from_parts(parts, Body::from(buffered))).await
+}
+
+fn payload_too_large(limit: usize) -> Response {
+    (StatusCode::PAYLOAD_TOO_LARGE, format!("Request body exceeds the limit of {limit} bytes"))
+        .into_response()
+}
@@ -6,13 +6,13 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// Additional server options.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
-    /// The cors `allow_origin` header
-    pub allow_origin: HeaderValueWrapper,
+    /// Origins allowed by the cors `allow_origin` header; `*` allows any origin
+    pub allow_origins: Vec<HeaderValueWrapper>,
 }
 
 impl Default for ServerConfig {
     fn default() -> Self {
-        Self { allow_origin: "*".parse::<HeaderValue>().unwrap().into() }
+        Self { allow_origins: vec!["*".parse::<HeaderValue>().unwrap().into()] }
     }
 }
 
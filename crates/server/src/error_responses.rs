@@ -0,0 +1,61 @@
+use std::any::Any;
+
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use hyper::{header, Body, Request, StatusCode};
+use rpc_core::error::{ErrorCode, RpcError};
+use serde_json::json;
+use tracing::error;
+
+/// Converts the error responses that don't carry JSON, e.g. the rejections of extractors, unknown
+/// paths, timeouts and caught panics, into the shape of the endpoint they were returned from. A
+/// JSON-RPC error with a null id is returned from `json_rpc_path`, and
+/// `{ "error": "...", "details": {} }` from the other paths. The status code is kept.
+pub(crate) async fn format_error_response(
+    request: Request<Body>,
+    next: Next<Body>,
+    json_rpc_path: Option<String>,
+) -> Response {
+    let is_json_rpc = json_rpc_path.as_deref() == Some(request.uri().path());
+    let response = next.run(request).await;
+
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map_or(false, |content_type| content_type.starts_with("application/json"));
+    if !(status.is_client_error() || status.is_server_error()) || is_json {
+        return response;
+    }
+
+    let message = match hyper::body::to_bytes(response.into_body()).await {
+        Ok(bytes) if !bytes.is_empty() => String::from_utf8_lossy(&bytes).into_owned(),
+        _ => status.canonical_reason().unwrap_or("Unknown error").to_string(),
+    };
+
+    if is_json_rpc {
+        let code = match status.is_server_error() {
+            true => ErrorCode::InternalError,
+            false => ErrorCode::InvalidRequest,
+        };
+        let error = RpcError { code, message: message.into(), data: None };
+        (status, Json(rpc_core::response::Response::error(error))).into_response()
+    } else {
+        (status, Json(json!({ "error": message, "details": {} }))).into_response()
+    }
+}
+
+/// Responds with 500 Internal Server Error to a request whose handling panicked, instead of
+/// dropping the connection. The response is given its final shape by [`format_error_response`].
+pub(crate) fn panic_response(panic: Box<dyn Any + Send + 'static>) -> Response {
+    let reason = panic
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| panic.downcast_ref::<&str>().copied())
+        .unwrap_or("unknown reason");
+    error!(reason, "handling of request panicked");
+
+    (StatusCode::INTERNAL_SERVER_ERROR, format!("Internal error: {reason}")).into_response()
+}
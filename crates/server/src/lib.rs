@@ -1,6 +1,8 @@
 pub mod builder;
 mod config;
+mod error_responses;
+pub mod rate_limit;
 /// handlers for axum server
 pub mod rpc_handler;
-pub use config::ServerConfig;
+pub use config::{HeaderValueWrapper, ServerConfig};
 pub use rpc_core;
@@ -0,0 +1,151 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::ConnectInfo;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use hyper::{header, Body, Request, StatusCode};
+use rpc_core::error::{ErrorCode, RpcError};
+use tracing::warn;
+
+/// JSON-RPC error code of requests rejected for exceeding the rate limit
+pub const LIMIT_EXCEEDED_RPC_ERROR_CODE: i64 = -32005;
+
+/// Number of independently locked parts the buckets are split into, so that requests of different
+/// clients rarely wait for each other
+const SHARDS: usize = 16;
+
+/// Number of buckets a shard holds before the ones of idle clients are dropped
+const MAX_BUCKETS_PER_SHARD: usize = 1024;
+
+/// Token bucket of a single client. It holds at most a second worth of requests, which is the
+/// largest burst accepted, and is refilled at the rate limit.
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn full(capacity: f64, now: Instant) -> Self {
+        Self { tokens: capacity, refilled_at: now }
+    }
+
+    fn refill(&mut self, capacity: f64, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * capacity).min(capacity);
+        self.refilled_at = now;
+    }
+}
+
+/// Limits the number of requests per second accepted from every client IP
+pub struct RateLimiter {
+    requests_per_sec: f64,
+    shards: Vec<Mutex<HashMap<IpAddr, TokenBucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_sec: u32) -> Self {
+        Self {
+            requests_per_sec: requests_per_sec.into(),
+            shards: (0..SHARDS).map(|_| Mutex::new(HashMap::new())).collect(),
+        }
+    }
+
+    /// Takes a token from the bucket of `ip`. If it's empty, returns how long to wait until a
+    /// token is available.
+    fn acquire(&self, ip: IpAddr) -> Result<(), Duration> {
+        let capacity = self.requests_per_sec;
+        let now = Instant::now();
+
+        let mut buckets = self.shard_of(ip).lock().unwrap_or_else(|err| err.into_inner());
+        if buckets.len() >= MAX_BUCKETS_PER_SHARD && !buckets.contains_key(&ip) {
+            // a bucket refilled to capacity is no different from a new one
+            buckets.retain(|_, bucket| {
+                bucket.refill(capacity, now);
+                bucket.tokens < capacity
+            });
+        }
+
+        let bucket = buckets.entry(ip).or_insert_with(|| TokenBucket::full(capacity, now));
+        bucket.refill(capacity, now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / capacity))
+        }
+    }
+
+    fn shard_of(&self, ip: IpAddr) -> &Mutex<HashMap<IpAddr, TokenBucket>> {
+        let mut hasher = DefaultHasher::new();
+        ip.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+}
+
+/// Responds with 429 Too Many Requests to clients exceeding the rate limit. The response holds a
+/// JSON-RPC error and the number of seconds to wait before retrying in the `Retry-After` header.
+/// Requests whose client address is unknown are not limited.
+pub(crate) async fn limit_request_rate(
+    request: Request<Body>,
+    next: Next<Body>,
+    rate_limiter: Arc<RateLimiter>,
+) -> Response {
+    let Some(ConnectInfo(client_addr)) = request.extensions().get::<ConnectInfo<SocketAddr>>()
+    else {
+        return next.run(request).await;
+    };
+
+    match rate_limiter.acquire(client_addr.ip()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            warn!(client = %client_addr.ip(), "rate limit exceeded");
+            too_many_requests(retry_after)
+        }
+    }
+}
+
+fn too_many_requests(retry_after: Duration) -> Response {
+    let error = RpcError {
+        code: ErrorCode::ServerError(LIMIT_EXCEEDED_RPC_ERROR_CODE),
+        message: "Rate limit exceeded".into(),
+        data: None,
+    };
+    // Retry-After is given in whole seconds
+    let retry_after_secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, retry_after_secs.to_string())],
+        Json(rpc_core::response::Response::error(error)),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::RateLimiter;
+
+    #[test]
+    fn requests_above_limit_are_rejected_per_client() {
+        let rate_limiter = RateLimiter::new(3);
+        let client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let other_client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+
+        for _ in 0..3 {
+            assert!(rate_limiter.acquire(client).is_ok());
+        }
+        let retry_after = rate_limiter.acquire(client).unwrap_err();
+        assert!(retry_after.as_secs_f64() <= 1.0 / 3.0);
+
+        // every client has its own bucket
+        assert!(rate_limiter.acquire(other_client).is_ok());
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::{self};
+use std::time::Instant;
 
 use axum::extract::rejection::JsonRejection;
 use axum::extract::Extension;
@@ -8,7 +9,7 @@ use rpc_core::error::RpcError;
 use rpc_core::request::{Request, RpcCall, RpcMethodCall};
 use rpc_core::response::{Response, ResponseResult, RpcResponse};
 use serde::de::DeserializeOwned;
-use tracing::{error, trace, warn};
+use tracing::{error, info, info_span, trace, warn, Instrument};
 
 /// Helper trait that is used to execute starknet rpc calls
 #[async_trait::async_trait]
@@ -89,7 +90,14 @@ pub async fn handle_request<THandler: RpcHandler>(
 
     match req {
         Request::Single(call) => handle_call(call, handler).await.map(Response::Single),
+        // an empty batch is not a valid request
+        Request::Batch(calls) if calls.is_empty() => {
+            warn!(target: "rpc", "received empty batch");
+            Some(Response::error(RpcError::invalid_request()))
+        }
         Request::Batch(calls) => {
+            // every call is handled independently and the order of responses matches the order of
+            // calls
             future::join_all(calls.into_iter().map(move |call| handle_call(call, handler.clone())))
                 .map(responses_as_batch)
                 .await
@@ -105,7 +113,20 @@ async fn handle_call<THandler: RpcHandler>(
     match call {
         RpcCall::MethodCall(call) => {
             trace!(target: "rpc", id = ?call.id , method = ?call.method,  "handling call");
-            Some(handler.on_call(call).await)
+            // everything logged while handling the call is attributed to its method and id
+            let span = info_span!(target: "rpc", "rpc_call", method = %call.method, id = ?call.id);
+            async move {
+                let started_at = Instant::now();
+                let response = handler.on_call(call).await;
+                info!(
+                    target: "rpc",
+                    elapsed_ms = started_at.elapsed().as_millis() as u64,
+                    "handled method call"
+                );
+                Some(response)
+            }
+            .instrument(span)
+            .await
         }
         RpcCall::Notification(notification) => {
             trace!(target: "rpc", method = ?notification.method, "received rpc notification");
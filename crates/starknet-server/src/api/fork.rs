@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{mpsc, Arc, RwLock};
+use std::time::Duration;
+
+use starknet_core::starknet::fork::{ForkError, ForkedState};
+use starknet_rs_core::types::{BlockId, FieldElement, StarknetError};
+use starknet_rs_providers::jsonrpc::HttpTransport;
+use starknet_rs_providers::{JsonRpcClient, Provider, ProviderError};
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::ContractClass;
+use starknet_types::felt::{ClassHash, Felt};
+use starknet_types::patricia_key::PatriciaKey;
+use tokio::runtime::Handle;
+use url::Url;
+
+use crate::api::models::contract_class::ContractClass as RpcContractClass;
+
+fn to_fork_error<E: std::error::Error>(err: ProviderError<E>) -> ForkError {
+    match err {
+        ProviderError::StarknetError(StarknetError::ContractNotFound) => {
+            ForkError::ContractNotFound
+        }
+        ProviderError::StarknetError(StarknetError::ClassHashNotFound) => {
+            ForkError::ClassHashNotFound
+        }
+        other => ForkError::Upstream { msg: other.to_string() },
+    }
+}
+
+/// Missing contracts and classes are not an error of the forked network
+fn found<T>(result: Result<T, ForkError>) -> Result<Option<T>, ForkError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(ForkError::ContractNotFound | ForkError::ClassHashNotFound) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Client of the JSON-RPC node of the forked network. Every request is made at the block the
+/// network was forked at, so values fetched from it never change and classes can be cached.
+///
+/// The state is read while executing transactions, which doesn't happen in async context, so
+/// requests are made on a runtime of their own and awaited by blocking.
+pub struct ForkClient {
+    client: Arc<JsonRpcClient<HttpTransport>>,
+    url: Url,
+    block_number: u64,
+    timeout: Duration,
+    classes: RwLock<HashMap<ClassHash, ContractClass>>,
+    runtime: Handle,
+}
+
+impl ForkClient {
+    /// Connects to the forked network; if `block_number` is not provided, the latest block is used
+    pub async fn connect(
+        url: Url,
+        block_number: Option<u64>,
+        timeout: Duration,
+    ) -> Result<Self, ForkError> {
+        let client = JsonRpcClient::new(HttpTransport::new(url.clone()));
+        let block_number = match block_number {
+            Some(block_number) => block_number,
+            None => with_timeout(timeout, client.block_number()).await?,
+        };
+
+        Ok(Self {
+            client: Arc::new(client),
+            url,
+            block_number,
+            timeout,
+            classes: RwLock::new(HashMap::new()),
+            runtime: spawn_runtime()?,
+        })
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    fn block_id(&self) -> BlockId {
+        BlockId::Number(self.block_number)
+    }
+
+    /// Makes the request built by `request` on the runtime of the client and waits for its
+    /// response
+    fn block_on<T, E, F>(
+        &self,
+        request: impl FnOnce(Arc<JsonRpcClient<HttpTransport>>, BlockId) -> F,
+    ) -> Result<T, ForkError>
+    where
+        T: Send + 'static,
+        E: std::error::Error + Send + 'static,
+        F: Future<Output = Result<T, ProviderError<E>>> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let response = with_timeout(self.timeout, request(self.client.clone(), self.block_id()));
+        self.runtime.spawn(async move {
+            // the receiver waits until the response is sent
+            let _ = sender.send(response.await);
+        });
+
+        receiver.recv().map_err(|_| ForkError::Upstream {
+            msg: "The client of the forked network has stopped".to_string(),
+        })?
+    }
+}
+
+impl ForkedState for ForkClient {
+    fn get_class_hash_at(
+        &self,
+        contract_address: ContractAddress,
+    ) -> Result<Option<ClassHash>, ForkError> {
+        let contract_address = FieldElement::from(Felt::from(contract_address));
+        found(self.block_on(|client, block_id| async move {
+            client.get_class_hash_at(block_id, contract_address).await
+        }))
+        .map(|class_hash| class_hash.map(Felt::from))
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> Result<Option<Felt>, ForkError> {
+        let contract_address = FieldElement::from(Felt::from(contract_address));
+        found(self.block_on(|client, block_id| async move {
+            client.get_nonce(block_id, contract_address).await
+        }))
+        .map(|nonce| nonce.map(Felt::from))
+    }
+
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: PatriciaKey,
+    ) -> Result<Option<Felt>, ForkError> {
+        let contract_address = FieldElement::from(Felt::from(contract_address));
+        let key = FieldElement::from(key.to_felt());
+        found(self.block_on(|client, block_id| async move {
+            client.get_storage_at(contract_address, key, block_id).await
+        }))
+        .map(|value| value.map(Felt::from))
+    }
+
+    /// Fetches the class from the forked network only if it hasn't been fetched before
+    fn get_class(&self, class_hash: ClassHash) -> Result<Option<ContractClass>, ForkError> {
+        let classes = &self.classes;
+        if let Some(contract_class) =
+            classes.read().expect("Classes should not be poisoned").get(&class_hash)
+        {
+            return Ok(Some(contract_class.clone()));
+        }
+
+        let class_hash_element = FieldElement::from(class_hash);
+        let contract_class = match found(self.block_on(|client, block_id| async move {
+            client.get_class(block_id, class_hash_element).await
+        }))? {
+            Some(contract_class) => contract_class,
+            None => return Ok(None),
+        };
+
+        // both representations follow the JSON-RPC specification
+        let to_upstream_error = |msg: String| ForkError::Upstream { msg };
+        let contract_class = match serde_json::to_value(contract_class)
+            .and_then(serde_json::from_value)
+            .map_err(|err| to_upstream_error(err.to_string()))?
+        {
+            RpcContractClass::Cairo0(deprecated_contract_class) => {
+                ContractClass::try_from(deprecated_contract_class)
+                    .map_err(|err| to_upstream_error(err.to_string()))?
+            }
+            RpcContractClass::Sierra(sierra_contract_class) => {
+                ContractClass::Cairo1(sierra_contract_class)
+            }
+        };
+
+        classes
+            .write()
+            .expect("Classes should not be poisoned")
+            .insert(class_hash, contract_class.clone());
+
+        Ok(Some(contract_class))
+    }
+}
+
+/// Runtime on a thread of its own, which runs the requests to the forked network until the
+/// process exits
+fn spawn_runtime() -> Result<Handle, ForkError> {
+    let to_upstream_error = |err: std::io::Error| ForkError::Upstream { msg: err.to_string() };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(to_upstream_error)?;
+    let handle = runtime.handle().clone();
+    std::thread::Builder::new()
+        .name("fork-client".to_string())
+        .spawn(move || runtime.block_on(std::future::pending::<()>()))
+        .map_err(to_upstream_error)?;
+
+    Ok(handle)
+}
+
+async fn with_timeout<T, E: std::error::Error>(
+    timeout: Duration,
+    request: impl Future<Output = Result<T, ProviderError<E>>>,
+) -> Result<T, ForkError> {
+    match tokio::time::timeout(timeout, request).await {
+        Ok(result) => result.map_err(to_fork_error),
+        Err(_) => Err(ForkError::Timeout),
+    }
+}
@@ -1,35 +1,96 @@
 use axum::extract::Query;
 use axum::{Extension, Json};
+use starknet_core::starknet::FeeUnit;
 use starknet_types::traits::ToDecimalString;
 
 use crate::api::http::error::HttpApiError;
-use crate::api::http::models::{Balance, ContractAddress, SerializableAccount};
+use crate::api::http::models::{
+    AccountBalance, Balance, BalanceQuery, ImpersonatedAccount, PredeployedAccountsQuery,
+    SerializableAccount,
+};
 use crate::api::http::{HttpApiHandler, HttpApiResult};
-use crate::api::models::{ContractAddressHex, FeltHex};
+use crate::api::models::{BlockId, ContractAddressHex, FeltHex, Tag};
 
+/// Lists the predeployed accounts. With `detailed=true`, the balance of each account is reported
+/// in both ETH and STRK instead of only in ETH.
 pub(crate) async fn get_predeployed_accounts(
+    Query(query): Query<PredeployedAccountsQuery>,
     Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<Json<Vec<SerializableAccount>>> {
-    let predeployed_accounts = state
-        .api
-        .starknet
-        .read()
-        .await
+    let starknet = state.api.starknet.read().await;
+    let hide_predeployed_keys = starknet.config.hide_predeployed_keys;
+    let token_balance = |address, unit| {
+        starknet
+            .get_token_balance(BlockId::Tag(Tag::Latest).into(), address, unit)
+            .map(|amount| amount.to_str_radix(10))
+            .map_err(|err| HttpApiError::BalanceError { msg: err.to_string() })
+    };
+    let predeployed_accounts = starknet
         .get_predeployed_accounts()
         .into_iter()
-        .map(|acc| SerializableAccount {
-            initial_balance: acc.initial_balance.to_decimal_string(),
-            address: ContractAddressHex(acc.account_address),
-            public_key: FeltHex(acc.public_key),
-            private_key: FeltHex(acc.private_key),
+        .map(|acc| {
+            let balance = if query.detailed {
+                AccountBalance::Detailed {
+                    eth: token_balance(acc.account_address, FeeUnit::Wei)?,
+                    strk: token_balance(acc.account_address, FeeUnit::Fri)?,
+                }
+            } else {
+                AccountBalance::Eth(
+                    starknet
+                        .get_account_balance(&acc)
+                        .map_err(|_| HttpApiError::GeneralError)?
+                        .to_decimal_string(),
+                )
+            };
+
+            Ok(SerializableAccount {
+                initial_balance: acc.initial_balance.to_decimal_string(),
+                balance,
+                address: ContractAddressHex(acc.account_address),
+                public_key: FeltHex(acc.public_key),
+                private_key: if hide_predeployed_keys {
+                    None
+                } else {
+                    acc.private_key.map(FeltHex)
+                },
+                class_hash: FeltHex(acc.class_hash),
+            })
         })
-        .collect();
+        .collect::<HttpApiResult<Vec<SerializableAccount>>>()?;
 
     Ok(Json(predeployed_accounts))
 }
 
 pub(crate) async fn get_account_balance(
-    Query(_contract_address): Query<ContractAddress>,
+    Query(query): Query<BalanceQuery>,
+    Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<Json<Balance>> {
-    Err(HttpApiError::GeneralError)
+    let block_id = BlockId::Tag(query.block_tag.unwrap_or(Tag::Latest));
+    let amount = state
+        .api
+        .starknet
+        .read()
+        .await
+        .get_token_balance(block_id.into(), query.address.0, query.unit)
+        .map_err(|err| HttpApiError::BalanceError { msg: err.to_string() })?;
+
+    Ok(Json(Balance { amount: amount.to_str_radix(10), unit: query.unit }))
+}
+
+pub(crate) async fn impersonate_account(
+    Json(data): Json<ImpersonatedAccount>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<()> {
+    state.api.starknet.write().await.impersonate_account(data.account_address.0);
+
+    Ok(())
+}
+
+pub(crate) async fn stop_impersonating_account(
+    Json(data): Json<ImpersonatedAccount>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<()> {
+    state.api.starknet.write().await.stop_impersonating_account(&data.account_address.0);
+
+    Ok(())
 }
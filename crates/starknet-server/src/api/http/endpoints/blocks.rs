@@ -1,15 +1,61 @@
-use axum::Json;
+use axum::{Extension, Json};
+use starknet_core::error::Error;
 
 use crate::api::http::error::HttpApiError;
-use crate::api::http::models::{AbortedBlocks, AbortingBlocks, CreatedBlock};
-use crate::api::http::HttpApiResult;
+use crate::api::http::models::{AbortedBlocks, AbortingBlocks, CreatedBlock, PendingTransaction};
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::models::{ContractAddressHex, FeltHex};
 
-pub(crate) async fn create_block() -> HttpApiResult<Json<CreatedBlock>> {
-    Err(HttpApiError::GeneralError)
+pub(crate) async fn create_block(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<CreatedBlock>> {
+    let block_hash = state
+        .api
+        .starknet
+        .write()
+        .await
+        .create_block()
+        .map_err(|err| HttpApiError::BlockCreationError { msg: err.to_string() })?;
+
+    Ok(Json(CreatedBlock { block_hash: FeltHex(block_hash) }))
 }
 
 pub(crate) async fn abort_blocks(
-    Json(_data): Json<AbortingBlocks>,
+    Json(data): Json<AbortingBlocks>,
+    Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<Json<AbortedBlocks>> {
-    Err(HttpApiError::GeneralError)
+    let mut starknet = state.api.starknet.write().await;
+    let aborted = starknet.abort_blocks(data.starting_block_hash.0).map_err(|err| match err {
+        Error::NoBlock => {
+            HttpApiError::BlockAbortError { msg: "No block found with the given hash".to_string() }
+        }
+        err => HttpApiError::BlockAbortError { msg: err.to_string() },
+    })?;
+
+    Ok(Json(AbortedBlocks { aborted: aborted.into_iter().map(FeltHex).collect() }))
+}
+
+/// Lists the transactions that will be part of the next block created on demand, in the order they
+/// were accepted. Empty if blocks are generated on transactions.
+pub(crate) async fn get_pending_transactions(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<Vec<PendingTransaction>>> {
+    let starknet = state.api.starknet.read().await;
+    let pending_transactions = starknet
+        .pending_transactions()
+        .into_iter()
+        .map(|transaction| {
+            Ok(PendingTransaction {
+                transaction_hash: FeltHex(
+                    transaction.get_hash().ok_or(HttpApiError::GeneralError)?,
+                ),
+                sender_address: ContractAddressHex(
+                    transaction.sender_address().map_err(|_| HttpApiError::GeneralError)?,
+                ),
+                nonce: FeltHex(*transaction.nonce()),
+            })
+        })
+        .collect::<HttpApiResult<Vec<PendingTransaction>>>()?;
+
+    Ok(Json(pending_transactions))
 }
@@ -0,0 +1,34 @@
+use axum::{Extension, Json};
+
+use crate::api::http::error::HttpApiError;
+use crate::api::http::models::{DevDeclareRequest, DevDeclareResponse};
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::models::FeltHex;
+
+/// Declares the class on behalf of a predeployed account, which signs the declaration. Only
+/// available if Devnet was started with `--allow-dev-declare`.
+pub(crate) async fn declare(
+    Json(request): Json<DevDeclareRequest>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<DevDeclareResponse>> {
+    let mut starknet = state.api.starknet.write().await;
+    if !starknet.config.allow_dev_declare {
+        return Err(HttpApiError::DeclarationError {
+            msg: "POST /declare is disabled; start Devnet with --allow-dev-declare".into(),
+        });
+    }
+
+    let (transaction_hash, class_hash) = starknet
+        .declare_with_predeployed_account(
+            request.account_address.map(|address| address.0),
+            request.sierra,
+            &request.casm,
+        )
+        .await
+        .map_err(|err| HttpApiError::DeclarationError { msg: err.to_string() })?;
+
+    Ok(Json(DevDeclareResponse {
+        class_hash: FeltHex(class_hash),
+        transaction_hash: FeltHex(transaction_hash),
+    }))
+}
@@ -0,0 +1,83 @@
+use axum::{Extension, Json};
+use starknet_core::error::Error;
+use starknet_core::starknet::calculate_deployment_address;
+use starknet_types::felt::Felt;
+use starknet_types::traits::ToHexString;
+
+use crate::api::http::error::HttpApiError;
+use crate::api::http::models::{
+    DeploymentType, DevDeployRequest, DevDeployResponse, PredictAddressRequest,
+    PredictAddressResponse,
+};
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::models::{ContractAddressHex, FeltHex};
+
+/// Deploys an instance of a declared class through the UDC on behalf of the first predeployed
+/// account, which signs the deployment. Only available if Devnet was started with
+/// `--allow-dev-deploy`.
+pub(crate) async fn deploy(
+    Json(request): Json<DevDeployRequest>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<DevDeployResponse>> {
+    let mut starknet = state.api.starknet.write().await;
+    if !starknet.config.allow_dev_deploy {
+        return Err(HttpApiError::DeploymentError {
+            msg: "POST /deploy is disabled; start Devnet with --allow-dev-deploy".into(),
+        });
+    }
+
+    let class_hash = request.class_hash.0;
+    let (transaction_hash, contract_address) = starknet
+        .deploy_with_predeployed_account(
+            class_hash,
+            request.constructor_calldata.into_iter().map(|felt| felt.0).collect(),
+            request.salt.0,
+        )
+        .await
+        .map_err(|err| match err {
+            Error::ClassHashNotFound => HttpApiError::DeploymentError {
+                msg: format!(
+                    "Class with hash {} is not declared",
+                    class_hash.to_prefixed_hex_str()
+                ),
+            },
+            err => HttpApiError::DeploymentError { msg: err.to_string() },
+        })?;
+
+    Ok(Json(DevDeployResponse {
+        contract_address: ContractAddressHex(contract_address),
+        transaction_hash: FeltHex(transaction_hash),
+    }))
+}
+
+/// Computes the address an instance of a class would be deployed at, without deploying it or
+/// checking that the class is declared
+pub(crate) async fn predict_address(
+    Json(request): Json<PredictAddressRequest>,
+) -> HttpApiResult<Json<PredictAddressResponse>> {
+    let deployer_address = request
+        .deployer_address
+        .map(|address| address.0)
+        .filter(|address| Felt::from(*address) != Felt::from(0u128));
+    let unique_deployer = match request.deployment_type {
+        DeploymentType::Udc => deployer_address,
+        DeploymentType::DeployAccount if deployer_address.is_some() => {
+            return Err(HttpApiError::AddressPredictionError {
+                msg: "deployer_address must be 0 or not set for DEPLOY_ACCOUNT".into(),
+            });
+        }
+        DeploymentType::DeployAccount => None,
+    };
+
+    let constructor_calldata: Vec<Felt> =
+        request.constructor_calldata.into_iter().map(|felt| felt.0).collect();
+    let contract_address = calculate_deployment_address(
+        request.class_hash.0,
+        &constructor_calldata,
+        request.salt.0,
+        unique_deployer,
+    )
+    .map_err(|err| HttpApiError::AddressPredictionError { msg: err.to_string() })?;
+
+    Ok(Json(PredictAddressResponse { contract_address: ContractAddressHex(contract_address) }))
+}
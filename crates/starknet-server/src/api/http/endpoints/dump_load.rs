@@ -1,19 +1,42 @@
 use axum::{Extension, Json};
+use starknet_core::error::Error;
+use starknet_core::starknet::dump::Dump;
 
 use crate::api::http::error::HttpApiError;
-use crate::api::http::models::Path;
+use crate::api::http::models::{LoadPath, Path};
 use crate::api::http::{HttpApiHandler, HttpApiResult};
 
+/// Dumps the state to the file at the provided path, or returns the dump if no path is provided
 pub(crate) async fn dump(
-    Json(_path): Json<Path>,
-    Extension(_state): Extension<HttpApiHandler>,
-) -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+    path: Option<Json<Path>>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<Option<Dump>>> {
+    // holding the lock while dumping prevents transactions from being added in the meantime
+    let starknet = state.api.starknet.read().await;
+
+    match path {
+        Some(Json(Path { path })) => {
+            starknet
+                .dump_to_file(&path)
+                .map_err(|err| HttpApiError::DumpError { msg: err.to_string() })?;
+            Ok(Json(None))
+        }
+        None => {
+            let dump =
+                starknet.dump().map_err(|err| HttpApiError::DumpError { msg: err.to_string() })?;
+            Ok(Json(Some(dump)))
+        }
+    }
 }
 
+/// Replays the dump from the file at the provided path
 pub(crate) async fn load(
-    Json(_path): Json<Path>,
-    Extension(_state): Extension<HttpApiHandler>,
+    Json(data): Json<LoadPath>,
+    Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<()> {
-    Err(HttpApiError::PathNotFound)
+    state.api.starknet.write().await.load_from_file(&data.path, data.force).map_err(|err| match err
+    {
+        Error::ReadFileError { .. } => HttpApiError::PathNotFound,
+        err => HttpApiError::LoadError { msg: err.to_string() },
+    })
 }
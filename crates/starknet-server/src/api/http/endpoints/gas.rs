@@ -0,0 +1,25 @@
+use axum::{Extension, Json};
+
+use crate::api::http::error::HttpApiError;
+use crate::api::http::models::{GasPrice, GasPriceResponse};
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::models::FeltHex;
+
+pub(crate) async fn set_gas_price(
+    Json(data): Json<GasPrice>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<GasPriceResponse>> {
+    let block_hash = state
+        .api
+        .starknet
+        .write()
+        .await
+        .set_gas_price(data.gas_price_wei, data.data_gas_price_wei, data.generate_block)
+        .map_err(|err| HttpApiError::GasPriceError { msg: err.to_string() })?;
+
+    Ok(Json(GasPriceResponse {
+        gas_price_wei: data.gas_price_wei,
+        data_gas_price_wei: data.data_gas_price_wei,
+        block_hash: block_hash.map(FeltHex),
+    }))
+}
@@ -0,0 +1,24 @@
+use axum::response::IntoResponse;
+use axum::Extension;
+use hyper::header;
+
+use crate::api::http::error::HttpApiError;
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+
+/// Content type of the Prometheus text exposition format
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Metrics in the Prometheus text format, to be scraped. Only available if Devnet was started with
+/// `--enable-metrics`.
+pub(crate) async fn get_metrics(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<impl IntoResponse> {
+    let starknet = state.api.starknet.read().await;
+    if !starknet.config.enable_metrics {
+        return Err(HttpApiError::MetricsError {
+            msg: "GET /metrics is disabled; start Devnet with --enable-metrics".into(),
+        });
+    }
+
+    Ok(([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], starknet.render_metrics()))
+}
@@ -1,13 +1,14 @@
 use axum::{Extension, Json};
-use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+use starknet_core::starknet::FeeUnit;
 use starknet_rs_core::types::{BlockId, BlockTag};
-use starknet_types::felt::Felt;
-use starknet_types::num_bigint::BigUint;
 
 use crate::api::http::error::HttpApiError;
-use crate::api::http::models::{FeeToken, MintTokensRequest, MintTokensResponse};
+use crate::api::http::models::{
+    FeeToken, MintBatchRequest, MintBatchResponse, MintTokensRequest, MintTokensResponse,
+    MintedBalance,
+};
 use crate::api::http::{HttpApiHandler, HttpApiResult};
-use crate::api::models::FeltHex;
+use crate::api::models::{ContractAddressHex, FeltHex};
 
 pub(crate) async fn get_fee_token() -> HttpApiResult<Json<FeeToken>> {
     Err(HttpApiError::GeneralError)
@@ -20,32 +21,61 @@ pub(crate) async fn mint(
     // increase balance
     let mut starknet = state.api.starknet.write().await;
     let tx_hash = starknet
-        .mint(request.address.0, request.amount)
+        .mint(request.address.0, request.amount, request.unit)
         .await
         .map_err(|err| HttpApiError::MintingError { msg: err.to_string() })?;
 
     // get new balance
-    let erc20_address = Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap();
-    let balance_selector =
-        starknet_rs_core::utils::get_selector_from_name("balanceOf").unwrap().into();
-    let new_balance_raw = starknet
-        .call(
-            BlockId::Tag(BlockTag::Pending),
-            erc20_address,
-            balance_selector,
-            vec![Felt::from(request.address.0)], // calldata = the incremented address
-        )
+    let new_balance = starknet
+        .get_token_balance(BlockId::Tag(BlockTag::Pending), request.address.0, request.unit)
         .map_err(|err| HttpApiError::MintingError { msg: err.to_string() })?;
 
-    // format new balance for output - initially it is a 2-member vector (low, high)
-    assert_eq!(new_balance_raw.len(), 2);
-    let new_balance_low: BigUint = (*new_balance_raw.get(0).unwrap()).into();
-    let new_balance_high: BigUint = (*new_balance_raw.get(1).unwrap()).into();
-    let new_balance: BigUint = (new_balance_high << 128) + new_balance_low;
-
     Ok(Json(MintTokensResponse {
         new_balance: new_balance.to_str_radix(10),
-        unit: "WEI".to_string(),
+        unit: request.unit,
         tx_hash: FeltHex(tx_hash),
     }))
 }
+
+/// Same as [`mint`], but always mints STRK
+pub(crate) async fn mint_strk(
+    Json(request): Json<MintTokensRequest>,
+    state: Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<MintTokensResponse>> {
+    mint(Json(MintTokensRequest { unit: FeeUnit::Fri, ..request }), state).await
+}
+
+pub(crate) async fn mint_batch(
+    Json(request): Json<MintBatchRequest>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<MintBatchResponse>> {
+    let mints: Vec<_> =
+        request.mints.into_iter().map(|mint| (mint.address.0, mint.amount, mint.unit)).collect();
+
+    let mut starknet = state.api.starknet.write().await;
+    let tx_hash = starknet
+        .mint_batch(&mints)
+        .await
+        .map_err(|err| HttpApiError::MintingError { msg: err.to_string() })?;
+    if request.seal_block {
+        starknet
+            .commit_pending_transactions()
+            .map_err(|err| HttpApiError::BlockCreationError { msg: err.to_string() })?;
+    }
+
+    let balances = mints
+        .into_iter()
+        .map(|(address, _, unit)| {
+            let new_balance = starknet
+                .get_token_balance(BlockId::Tag(BlockTag::Pending), address, unit)
+                .map_err(|err| HttpApiError::MintingError { msg: err.to_string() })?;
+            Ok(MintedBalance {
+                address: ContractAddressHex(address),
+                new_balance: new_balance.to_str_radix(10),
+                unit,
+            })
+        })
+        .collect::<HttpApiResult<Vec<MintedBalance>>>()?;
+
+    Ok(Json(MintBatchResponse { balances, tx_hash: FeltHex(tx_hash) }))
+}
@@ -1,8 +1,19 @@
-use axum::Json;
+use std::time::Duration;
+
+use axum::{Extension, Json};
+use starknet_core::constants::UDC_CONTRACT_ADDRESS;
+use starknet_core::starknet::FeeUnit;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::Felt;
+use starknet_types::traits::ToDecimalString;
 
 use super::error::HttpApiError;
-use super::models::ForkStatus;
-use super::HttpApiResult;
+use super::models::{DevnetConfig, ForkStatus, HealthStatus};
+use super::{HttpApiHandler, HttpApiResult};
+use crate::api::models::{ContractAddressHex, FeltHex};
+
+/// How long the health check waits for the state to become readable
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(1);
 
 /// Dumping and loading
 pub(crate) mod dump_load;
@@ -13,26 +24,144 @@ pub(crate) mod postman;
 /// Blocks
 pub(crate) mod blocks;
 
+/// Snapshots
+pub(crate) mod snapshots;
+
 /// Time
 pub(crate) mod time;
 
+/// Gas price
+pub(crate) mod gas;
+
 /// Accounts
 pub(crate) mod accounts;
 
 /// Mint token - Local faucet
 pub(crate) mod mint_token;
 
+/// Declaring without a signed transaction
+pub(crate) mod declare;
+
+/// Deploying without a signed transaction and predicting addresses of deployments
+pub(crate) mod deploy;
+
+/// Writing contract storage without a transaction
+pub(crate) mod storage;
+
+/// Prometheus metrics
+pub(crate) mod metrics;
+
 /// Is alive
 pub(crate) async fn is_alive() -> HttpApiResult<String> {
     Ok("Alive!!!".to_string())
 }
 
+/// Health
+/// Unlike `is_alive`, confirms that the state can be read. Devnet only starts listening once its
+/// state is built, so this fails with 503 only if other requests keep the state locked for too
+/// long, e.g. while a large dump is loaded
+pub(crate) async fn health(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<HealthStatus>> {
+    let starknet = tokio::time::timeout(HEALTH_CHECK_TIMEOUT, state.api.starknet.read())
+        .await
+        .map_err(|_| HttpApiError::HealthCheckError { msg: "state is not readable".to_string() })?;
+
+    let block_number = starknet
+        .get_latest_block()
+        .map_err(|err| HttpApiError::HealthCheckError { msg: err.to_string() })?
+        .block_number()
+        .0;
+
+    Ok(Json(HealthStatus {
+        status: "ok",
+        block_number,
+        uptime_secs: state.api.started_at.elapsed().as_secs(),
+    }))
+}
+
+/// Configuration the devnet was started with
+pub(crate) async fn get_config(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<DevnetConfig>> {
+    let starknet = state.api.starknet.read().await;
+    let udc_address = Felt::from_prefixed_hex_str(UDC_CONTRACT_ADDRESS)
+        .and_then(ContractAddress::new)
+        .map_err(|_| HttpApiError::GeneralError)?;
+
+    Ok(Json(DevnetConfig {
+        host: starknet.config.host.clone(),
+        port: starknet.config.port,
+        rpc_path: starknet.config.rpc_path.clone(),
+        devnet_path: starknet.config.devnet_path.clone(),
+        seed: starknet.config.seed,
+        total_accounts: starknet.config.total_accounts,
+        predeployed_accounts_initial_balance: starknet
+            .config
+            .predeployed_accounts_initial_balance
+            .to_decimal_string(),
+        udc_address: ContractAddressHex(udc_address),
+        eth_erc20_address: ContractAddressHex(starknet.fee_token_address(FeeUnit::Wei)),
+        strk_erc20_address: ContractAddressHex(starknet.fee_token_address(FeeUnit::Fri)),
+        account_class_hash: FeltHex(starknet.account_class_hash()),
+        predeclared_classes: starknet
+            .predeclared_classes()
+            .iter()
+            .map(|(name, class_hash)| (*name, FeltHex(*class_hash)))
+            .collect(),
+        chain_id: FeltHex(starknet.chain_id().to_felt()),
+        gas_price: starknet.gas_price(),
+    }))
+}
+
 /// Restart
-pub(crate) async fn restart() -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+/// Discards all blocks and transactions, regenerating the genesis state
+pub(crate) async fn restart(Extension(state): Extension<HttpApiHandler>) -> HttpApiResult<()> {
+    state
+        .api
+        .starknet
+        .write()
+        .await
+        .restart()
+        .map_err(|err| HttpApiError::RestartError { msg: err.to_string() })
 }
 
 /// Fork
-pub(crate) async fn get_fork_status() -> HttpApiResult<Json<ForkStatus>> {
-    Err(HttpApiError::GeneralError)
+/// Both fields are omitted if Devnet is not forked
+pub(crate) async fn get_fork_status(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<ForkStatus>> {
+    Ok(Json(match &state.api.fork {
+        Some(fork) => {
+            ForkStatus { url: Some(fork.url().to_string()), block: Some(fork.block_number()) }
+        }
+        None => ForkStatus { url: None, block: None },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use axum::Extension;
+    use starknet_core::starknet::{Starknet, StarknetConfig};
+
+    use super::health;
+    use crate::api::http::error::HttpApiError;
+    use crate::api::http::HttpApiHandler;
+    use crate::api::Api;
+
+    #[tokio::test]
+    async fn health_fails_while_state_is_locked() {
+        let starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+        let handler = HttpApiHandler { api: Api::new(starknet) };
+
+        let starknet_guard = handler.api.starknet.write().await;
+        let err = health(Extension(handler.clone())).await.err().unwrap();
+        assert!(matches!(err, HttpApiError::HealthCheckError { .. }));
+        assert_eq!(err.into_response().status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        drop(starknet_guard);
+        assert_eq!(health(Extension(handler)).await.unwrap().0.block_number, 0);
+    }
 }
@@ -1,26 +1,69 @@
-use axum::Json;
+use axum::{Extension, Json};
+use starknet_core::starknet::messaging::MessageToL2 as CoreMessageToL2;
 
 use crate::api::http::error::HttpApiError;
 use crate::api::http::models::{
-    MessageFromL2, MessageHash, MessageToL2, PostmanLoadL1MessagingContract,
+    L1MessagingContract, MessageFromL2, MessageHash, MessageToL2, PostmanLoadL1MessagingContract,
+    SentMessageToL2,
 };
-use crate::api::http::HttpApiResult;
-use crate::api::models::transaction::TransactionHashHex;
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::models::transaction::MessageToL1;
+use crate::api::models::FeltHex;
 
 pub(crate) async fn postman_load(
-    Json(_l1_contract): Json<PostmanLoadL1MessagingContract>,
-) -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+    Json(l1_contract): Json<PostmanLoadL1MessagingContract>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<L1MessagingContract>> {
+    state.api.starknet.write().await.load_l1_messaging_contract(l1_contract.address.0);
+
+    Ok(Json(L1MessagingContract { messaging_contract_address: l1_contract.address }))
 }
 
-pub(crate) async fn postman_flush() -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+/// Returns the messages sent to L1 since the last flush, in the order of their transactions. If
+/// Devnet was started with an L1 node, they are first submitted to the loaded messaging contract
+pub(crate) async fn postman_flush(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<Vec<MessageToL1>>> {
+    let mut starknet = state.api.starknet.write().await;
+
+    if let Some(l1) = state.api.l1.as_ref() {
+        if !starknet.messages_to_l1().is_empty() {
+            let messaging_contract_address =
+                starknet.l1_messaging_contract_address().ok_or(HttpApiError::MessagingError {
+                    msg: "L1 messaging contract is not loaded".to_string(),
+                })?;
+            // messages are kept if submitting fails, so that flushing can be retried
+            l1.send_messages_to_l1(messaging_contract_address, starknet.messages_to_l1())
+                .await
+                .map_err(|err| HttpApiError::MessagingError { msg: err.to_string() })?;
+        }
+    }
+
+    Ok(Json(starknet.flush_messages_to_l1().into_iter().map(MessageToL1::from).collect()))
 }
 
 pub(crate) async fn postman_send_message_to_l2(
-    Json(_data): Json<MessageToL2>,
-) -> HttpApiResult<Json<TransactionHashHex>> {
-    Err(HttpApiError::GeneralError)
+    Json(data): Json<MessageToL2>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<SentMessageToL2>> {
+    let message = CoreMessageToL2 {
+        l1_contract_address: data.l1_contract_address.0,
+        l2_contract_address: data.l2_contract_address.0,
+        entry_point_selector: data.entry_point_selector.0,
+        payload: data.payload.into_iter().map(|felt| felt.0).collect(),
+        nonce: data.nonce.0,
+        paid_fee_on_l1: data.fee.0,
+    };
+
+    let (transaction_hash, message_hash) = state
+        .api
+        .starknet
+        .write()
+        .await
+        .send_message_to_l2(message)
+        .map_err(|err| HttpApiError::MessagingError { msg: err.to_string() })?;
+
+    Ok(Json(SentMessageToL2 { transaction_hash: FeltHex(transaction_hash), message_hash }))
 }
 
 pub(crate) async fn postman_consume_message_from_l2(
@@ -0,0 +1,45 @@
+use axum::{Extension, Json};
+use starknet_core::error::Error;
+use starknet_types::felt::Felt;
+use starknet_types::traits::ToHexString;
+
+use crate::api::http::error::HttpApiError;
+use crate::api::http::models::SnapshotId;
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::models::FeltHex;
+
+pub(crate) async fn take_snapshot(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<SnapshotId>> {
+    let snapshot_id = state
+        .api
+        .starknet
+        .write()
+        .await
+        .take_snapshot()
+        .map_err(|err| HttpApiError::SnapshotError { msg: err.to_string() })?;
+
+    Ok(Json(SnapshotId { snapshot_id: FeltHex(Felt::from(snapshot_id as u128)) }))
+}
+
+pub(crate) async fn revert(
+    Json(data): Json<SnapshotId>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<()> {
+    let no_snapshot = || HttpApiError::SnapshotError {
+        msg: format!("No snapshot with id {}", data.snapshot_id.0.to_prefixed_hex_str()),
+    };
+
+    // ids are assigned from a counter, so larger values can't identify a snapshot
+    let bytes = data.snapshot_id.0.bytes();
+    let (high, low) = bytes.split_at(bytes.len() - 8);
+    if high.iter().any(|byte| *byte != 0) {
+        return Err(no_snapshot());
+    }
+    let snapshot_id = u64::from_be_bytes(low.try_into().map_err(|_| no_snapshot())?);
+
+    state.api.starknet.write().await.revert_to_snapshot(snapshot_id).map_err(|err| match err {
+        Error::NoSnapshot { .. } => no_snapshot(),
+        err => HttpApiError::SnapshotError { msg: err.to_string() },
+    })
+}
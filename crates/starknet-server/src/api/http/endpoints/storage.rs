@@ -0,0 +1,37 @@
+use axum::{Extension, Json};
+use starknet_core::error::Error;
+use starknet_types::traits::ToHexString;
+
+use crate::api::http::error::HttpApiError;
+use crate::api::http::models::{SetStorageRequest, SetStorageResponse};
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::models::FeltHex;
+
+/// Writes a value to contract storage without a transaction and generates a block recording the
+/// write. Only available if Devnet was started with `--allow-dev-state`.
+pub(crate) async fn set_storage_at(
+    Json(request): Json<SetStorageRequest>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<SetStorageResponse>> {
+    let mut starknet = state.api.starknet.write().await;
+    if !starknet.config.allow_dev_state {
+        return Err(HttpApiError::StorageError {
+            msg: "POST /set_storage_at is disabled; start Devnet with --allow-dev-state".into(),
+        });
+    }
+
+    let contract_address = request.contract_address.0;
+    let block_hash = starknet
+        .set_storage_at(contract_address, request.key.0, request.value.0)
+        .map_err(|err| match err {
+            Error::ContractNotFound => HttpApiError::StorageError {
+                msg: format!(
+                    "No contract is deployed at address {}",
+                    contract_address.to_prefixed_hex_str()
+                ),
+            },
+            err => HttpApiError::StorageError { msg: err.to_string() },
+        })?;
+
+    Ok(Json(SetStorageResponse { block_hash: FeltHex(block_hash) }))
+}
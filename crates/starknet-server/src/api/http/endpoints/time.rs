@@ -1,13 +1,47 @@
-use axum::Json;
+use axum::{Extension, Json};
+use starknet_core::error::Error;
 
 use crate::api::http::error::HttpApiError;
-use crate::api::http::models::Time;
-use crate::api::http::HttpApiResult;
+use crate::api::http::models::{Time, TimeResponse};
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::models::FeltHex;
 
-pub(crate) async fn set_time(Json(_data): Json<Time>) -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+/// Out of range times are an error of the request
+fn to_time_error(err: Error) -> HttpApiError {
+    match err {
+        err @ Error::TimeOutOfRange { .. } => {
+            HttpApiError::InvalidTimeError { msg: err.to_string() }
+        }
+        err => HttpApiError::TimeError { msg: err.to_string() },
+    }
 }
 
-pub(crate) async fn increase_time(Json(_data): Json<Time>) -> HttpApiResult<()> {
-    Err(HttpApiError::GeneralError)
+pub(crate) async fn set_time(
+    Json(data): Json<Time>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<TimeResponse>> {
+    let block_hash = state
+        .api
+        .starknet
+        .write()
+        .await
+        .set_time(data.time, data.generate_block)
+        .map_err(to_time_error)?;
+
+    Ok(Json(TimeResponse { block_hash: block_hash.map(FeltHex) }))
+}
+
+pub(crate) async fn increase_time(
+    Json(data): Json<Time>,
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<TimeResponse>> {
+    let block_hash = state
+        .api
+        .starknet
+        .write()
+        .await
+        .increase_time(data.time, data.generate_block)
+        .map_err(to_time_error)?;
+
+    Ok(Json(TimeResponse { block_hash: block_hash.map(FeltHex) }))
 }
@@ -12,10 +12,72 @@ pub enum HttpApiError {
     GeneralError,
     #[error("Minting error: {msg}")]
     MintingError { msg: String },
+    #[error("Block creation error: {msg}")]
+    BlockCreationError { msg: String },
+    #[error("Block abortion error: {msg}")]
+    BlockAbortError { msg: String },
+    #[error("Snapshot error: {msg}")]
+    SnapshotError { msg: String },
+    #[error("Time setting error: {msg}")]
+    TimeError { msg: String },
+    #[error("Invalid time: {msg}")]
+    InvalidTimeError { msg: String },
+    #[error("Dumping error: {msg}")]
+    DumpError { msg: String },
+    #[error("Loading error: {msg}")]
+    LoadError { msg: String },
+    #[error("Restart error: {msg}")]
+    RestartError { msg: String },
+    #[error("Gas price setting error: {msg}")]
+    GasPriceError { msg: String },
+    #[error("Messaging error: {msg}")]
+    MessagingError { msg: String },
+    #[error("Health check error: {msg}")]
+    HealthCheckError { msg: String },
+    #[error("Balance error: {msg}")]
+    BalanceError { msg: String },
+    #[error("Declaration error: {msg}")]
+    DeclarationError { msg: String },
+    #[error("Deployment error: {msg}")]
+    DeploymentError { msg: String },
+    #[error("Address prediction error: {msg}")]
+    AddressPredictionError { msg: String },
+    #[error("Storage setting error: {msg}")]
+    StorageError { msg: String },
+    #[error("Metrics error: {msg}")]
+    MetricsError { msg: String },
+}
+
+impl HttpApiError {
+    /// Fields of the error, returned next to its message
+    fn details(&self) -> serde_json::Value {
+        match self {
+            HttpApiError::PathNotFound | HttpApiError::GeneralError => json!({}),
+            HttpApiError::MintingError { msg }
+            | HttpApiError::BlockCreationError { msg }
+            | HttpApiError::BlockAbortError { msg }
+            | HttpApiError::SnapshotError { msg }
+            | HttpApiError::TimeError { msg }
+            | HttpApiError::InvalidTimeError { msg }
+            | HttpApiError::DumpError { msg }
+            | HttpApiError::LoadError { msg }
+            | HttpApiError::RestartError { msg }
+            | HttpApiError::GasPriceError { msg }
+            | HttpApiError::MessagingError { msg }
+            | HttpApiError::HealthCheckError { msg }
+            | HttpApiError::BalanceError { msg }
+            | HttpApiError::DeclarationError { msg }
+            | HttpApiError::DeploymentError { msg }
+            | HttpApiError::AddressPredictionError { msg }
+            | HttpApiError::StorageError { msg }
+            | HttpApiError::MetricsError { msg } => json!({ "msg": msg }),
+        }
+    }
 }
 
 impl IntoResponse for HttpApiError {
     fn into_response(self) -> axum::response::Response {
+        let details = self.details();
         let (status, error_message) = match self {
             HttpApiError::PathNotFound => {
                 (StatusCode::BAD_REQUEST, String::from("path is missing"))
@@ -26,10 +88,58 @@ impl IntoResponse for HttpApiError {
             err @ HttpApiError::MintingError { msg: _ } => {
                 (StatusCode::BAD_REQUEST, err.to_string())
             }
+            err @ HttpApiError::BlockCreationError { msg: _ } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+            }
+            err @ HttpApiError::BlockAbortError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
+            err @ HttpApiError::SnapshotError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
+            err @ HttpApiError::TimeError { msg: _ } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+            }
+            err @ HttpApiError::InvalidTimeError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
+            err @ HttpApiError::DumpError { msg: _ } => (StatusCode::BAD_REQUEST, err.to_string()),
+            err @ HttpApiError::LoadError { msg: _ } => (StatusCode::BAD_REQUEST, err.to_string()),
+            err @ HttpApiError::RestartError { msg: _ } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+            }
+            err @ HttpApiError::GasPriceError { msg: _ } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+            }
+            err @ HttpApiError::MessagingError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
+            err @ HttpApiError::HealthCheckError { msg: _ } => {
+                (StatusCode::SERVICE_UNAVAILABLE, err.to_string())
+            }
+            err @ HttpApiError::BalanceError { msg: _ } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+            }
+            err @ HttpApiError::DeclarationError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
+            err @ HttpApiError::DeploymentError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
+            err @ HttpApiError::AddressPredictionError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
+            err @ HttpApiError::StorageError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
+            err @ HttpApiError::MetricsError { msg: _ } => {
+                (StatusCode::BAD_REQUEST, err.to_string())
+            }
         };
 
         let body = Json(json!({
             "error": error_message,
+            "details": details,
         }));
 
         (status, body).into_response()
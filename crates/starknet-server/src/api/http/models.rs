@@ -1,30 +1,58 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use starknet_core::starknet::FeeUnit;
+use starknet_types::num_bigint::BigUint;
 use starknet_types::starknet_api::transaction::Fee;
 
 use crate::api::models::block::BlockHashHex;
-use crate::api::models::transaction::{Calldata, EntryPointSelectorHex, Nonce, TransactionHashHex};
-use crate::api::models::{ContractAddressHex, FeltHex};
+use crate::api::models::transaction::{
+    Calldata, ClassHashHex, EntryPointSelectorHex, Nonce, TransactionHashHex,
+};
+use crate::api::models::{ContractAddressHex, FeltHex, PatriciaKeyHex, Tag};
+use crate::api::serde_helpers::rpc_sierra_contract_class_to_sierra_contract_class::deserialize_to_sierra_contract_class;
+use crate::api::serde_helpers::u256_amount::deserialize_to_u256;
 
 #[derive(Deserialize, Debug)]
 pub(crate) struct Path {
-    path: String,
+    pub(crate) path: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct LoadPath {
+    pub(crate) path: String,
+    /// If set, Devnet is restarted before loading in case it already contains blocks
+    #[serde(default)]
+    pub(crate) force: bool,
 }
 
 #[derive(Deserialize, Debug)]
 pub(crate) struct PostmanLoadL1MessagingContract {
-    #[serde(rename = "networkUrl")]
-    network_url: String,
-    address: ContractAddressHex,
+    pub(crate) address: FeltHex,
+}
+
+#[derive(Serialize)]
+pub(crate) struct L1MessagingContract {
+    pub(crate) messaging_contract_address: FeltHex,
 }
 
 #[derive(Deserialize)]
 pub(crate) struct MessageToL2 {
-    l2_contract_address: ContractAddressHex,
-    entry_point_selector: EntryPointSelectorHex,
-    l1_contract_addresss: ContractAddressHex,
-    payload: Calldata,
-    paid_fee_on_l1: Fee,
-    nonce: Nonce,
+    pub(crate) l2_contract_address: ContractAddressHex,
+    pub(crate) entry_point_selector: EntryPointSelectorHex,
+    pub(crate) l1_contract_address: FeltHex,
+    pub(crate) payload: Calldata,
+    /// Fee paid on L1 for executing the L1 handler
+    #[serde(alias = "paid_fee_on_l1")]
+    pub(crate) fee: Fee,
+    pub(crate) nonce: Nonce,
+}
+
+#[derive(Serialize)]
+pub(crate) struct SentMessageToL2 {
+    pub(crate) transaction_hash: TransactionHashHex,
+    /// Keccak hash of the message, which doesn't necessarily fit into a felt
+    pub(crate) message_hash: String,
 }
 
 #[derive(Deserialize)]
@@ -41,42 +69,114 @@ pub(crate) struct MessageHash {
 
 #[derive(Serialize)]
 pub(crate) struct CreatedBlock {
-    block_hash: BlockHashHex,
+    pub(crate) block_hash: BlockHashHex,
 }
 
 #[derive(Deserialize)]
 pub(crate) struct AbortingBlocks {
-    #[serde(rename = "startingBlockHash")]
-    starting_block_hash: BlockHashHex,
+    #[serde(alias = "startingBlockHash")]
+    pub(crate) starting_block_hash: BlockHashHex,
 }
 
 #[derive(Serialize)]
 pub(crate) struct AbortedBlocks {
-    aborted: Vec<BlockHashHex>,
+    pub(crate) aborted: Vec<BlockHashHex>,
+}
+
+/// Transaction accepted in demand mode that is not yet part of a block
+#[derive(Serialize)]
+pub(crate) struct PendingTransaction {
+    pub(crate) transaction_hash: TransactionHashHex,
+    pub(crate) sender_address: ContractAddressHex,
+    pub(crate) nonce: Nonce,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SnapshotId {
+    pub(crate) snapshot_id: FeltHex,
 }
 
 #[derive(Deserialize)]
 pub(crate) struct Time {
-    time: u64,
+    pub(crate) time: u64,
+    /// If set, a new empty block is generated so that the new time takes effect immediately
+    #[serde(default)]
+    pub(crate) generate_block: bool,
+}
+
+#[derive(Serialize)]
+pub(crate) struct TimeResponse {
+    /// Hash of the generated block, if one was generated
+    pub(crate) block_hash: Option<BlockHashHex>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct GasPrice {
+    pub(crate) gas_price_wei: u64,
+    pub(crate) data_gas_price_wei: u64,
+    /// If set, a new block is generated so that the new prices appear in a block immediately
+    #[serde(default)]
+    pub(crate) generate_block: bool,
+}
+
+#[derive(Serialize)]
+pub(crate) struct GasPriceResponse {
+    pub(crate) gas_price_wei: u64,
+    pub(crate) data_gas_price_wei: u64,
+    /// Hash of the generated block, if one was generated
+    pub(crate) block_hash: Option<BlockHashHex>,
 }
 
 #[derive(Serialize)]
 pub(crate) struct SerializableAccount {
     pub(crate) initial_balance: String,
+    pub(crate) balance: AccountBalance,
     pub(crate) address: ContractAddressHex,
     pub(crate) public_key: FeltHex,
-    pub(crate) private_key: FeltHex,
+    /// Omitted if Devnet was started with `--hide-predeployed-keys` or if the account was
+    /// listed in the file given with `--predeploy-account`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) private_key: Option<FeltHex>,
+    pub(crate) class_hash: FeltHex,
+}
+
+/// Balance of a predeployed account at the latest block, decimal repr
+#[derive(Serialize)]
+#[serde(untagged)]
+pub(crate) enum AccountBalance {
+    /// In WEI
+    Eth(String),
+    /// In both fee tokens, if the accounts were requested with `detailed=true`
+    Detailed { eth: String, strk: String },
 }
 
 #[derive(Deserialize)]
-pub(crate) struct ContractAddress {
-    contract_address: ContractAddressHex,
+pub(crate) struct PredeployedAccountsQuery {
+    /// If set, the balance of each account is reported in both fee tokens
+    #[serde(default)]
+    pub(crate) detailed: bool,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct BalanceQuery {
+    pub(crate) address: ContractAddressHex,
+    /// WEI for ETH, FRI for STRK
+    #[serde(default)]
+    pub(crate) unit: FeeUnit,
+    /// Block at which the balance is read; latest if not set
+    pub(crate) block_tag: Option<Tag>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ImpersonatedAccount {
+    pub(crate) account_address: ContractAddressHex,
 }
 
 #[derive(Serialize)]
 pub(crate) struct Balance {
-    amount: u128,
-    unit: String,
+    /// decimal repr
+    pub(crate) amount: String,
+    pub(crate) unit: FeeUnit,
 }
 
 #[derive(Serialize)]
@@ -88,19 +188,158 @@ pub(crate) struct FeeToken {
 #[derive(Debug, Deserialize)]
 pub(crate) struct MintTokensRequest {
     pub(crate) address: ContractAddressHex,
-    pub(crate) amount: u128,
+    /// Decimal or prefixed hex string of up to 256 bits; small amounts may also be numbers
+    #[serde(deserialize_with = "deserialize_to_u256")]
+    pub(crate) amount: BigUint,
+    /// WEI for ETH, FRI for STRK
+    #[serde(default)]
+    pub(crate) unit: FeeUnit,
 }
 
 #[derive(Serialize)]
 pub(crate) struct MintTokensResponse {
     /// decimal repr
     pub(crate) new_balance: String,
-    pub(crate) unit: String,
+    pub(crate) unit: FeeUnit,
+    pub(crate) tx_hash: TransactionHashHex,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct MintBatchRequest {
+    pub(crate) mints: Vec<MintTokensRequest>,
+    /// If set, a block holding the batch is created right away in demand mode
+    #[serde(default)]
+    pub(crate) seal_block: bool,
+}
+
+#[derive(Serialize)]
+pub(crate) struct MintedBalance {
+    pub(crate) address: ContractAddressHex,
+    /// decimal repr
+    pub(crate) new_balance: String,
+    pub(crate) unit: FeeUnit,
+}
+
+#[derive(Serialize)]
+pub(crate) struct MintBatchResponse {
+    /// New balance for each mint of the batch, in the order of the request
+    pub(crate) balances: Vec<MintedBalance>,
     pub(crate) tx_hash: TransactionHashHex,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct DevDeclareRequest {
+    /// Sierra class as compiled by Scarb; the ABI may also be a JSON string
+    #[serde(deserialize_with = "deserialize_to_sierra_contract_class")]
+    pub(crate) sierra: starknet_in_rust::SierraContractClass,
+    /// CASM compiled from `sierra`
+    pub(crate) casm: starknet_in_rust::CasmContractClass,
+    /// Predeployed account declaring the class; the first predeployed account if not set
+    pub(crate) account_address: Option<ContractAddressHex>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct DevDeclareResponse {
+    pub(crate) class_hash: ClassHashHex,
+    pub(crate) transaction_hash: TransactionHashHex,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct DevDeployRequest {
+    /// Hash of a declared class
+    pub(crate) class_hash: ClassHashHex,
+    #[serde(default)]
+    pub(crate) constructor_calldata: Vec<FeltHex>,
+    /// 0 if not set
+    #[serde(default)]
+    pub(crate) salt: FeltHex,
+}
+
+#[derive(Serialize)]
+pub(crate) struct DevDeployResponse {
+    pub(crate) contract_address: ContractAddressHex,
+    pub(crate) transaction_hash: TransactionHashHex,
+}
+
+/// How the instance whose address is predicted is deployed
+#[derive(Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub(crate) enum DeploymentType {
+    /// Through the UDC, e.g. with `POST /deploy`; unique if `deployer_address` is set
+    Udc,
+    /// With a deploy_account transaction, whose deployer is 0
+    DeployAccount,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PredictAddressRequest {
+    pub(crate) class_hash: ClassHashHex,
+    #[serde(default)]
+    pub(crate) constructor_calldata: Vec<FeltHex>,
+    /// 0 if not set
+    #[serde(default)]
+    pub(crate) salt: FeltHex,
+    /// Account calling the UDC with `unique` set; not set if the instance is not unique
+    pub(crate) deployer_address: Option<ContractAddressHex>,
+    pub(crate) deployment_type: DeploymentType,
+}
+
+#[derive(Serialize)]
+pub(crate) struct PredictAddressResponse {
+    pub(crate) contract_address: ContractAddressHex,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SetStorageRequest {
+    pub(crate) contract_address: ContractAddressHex,
+    pub(crate) key: PatriciaKeyHex,
+    pub(crate) value: FeltHex,
+}
+
+#[derive(Serialize)]
+pub(crate) struct SetStorageResponse {
+    /// Block recording the write in its state diff
+    pub(crate) block_hash: BlockHashHex,
+}
+
+#[derive(Serialize)]
+pub(crate) struct DevnetConfig {
+    pub(crate) host: String,
+    /// The port Devnet is actually bound to, also if started with port 0
+    pub(crate) port: u16,
+    /// Path the JSON-RPC handler is served at
+    pub(crate) rpc_path: String,
+    /// Prefix of the Devnet-specific endpoints; empty if they are served at the root
+    pub(crate) devnet_path: String,
+    pub(crate) seed: u32,
+    pub(crate) total_accounts: u8,
+    /// decimal repr
+    pub(crate) predeployed_accounts_initial_balance: String,
+    pub(crate) udc_address: ContractAddressHex,
+    pub(crate) eth_erc20_address: ContractAddressHex,
+    pub(crate) strk_erc20_address: ContractAddressHex,
+    /// Class of the generated predeployed accounts
+    pub(crate) account_class_hash: ClassHashHex,
+    /// Hashes of the standard classes declared at genesis, by class name; empty unless Devnet was
+    /// started with `--predeclare`
+    pub(crate) predeclared_classes: BTreeMap<&'static str, ClassHashHex>,
+    pub(crate) chain_id: FeltHex,
+    /// Current price of L1 gas in wei
+    pub(crate) gas_price: u64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct HealthStatus {
+    pub(crate) status: &'static str,
+    /// Number of the latest block
+    pub(crate) block_number: u64,
+    pub(crate) uptime_secs: u64,
+}
+
 #[derive(Serialize)]
 pub(crate) struct ForkStatus {
-    url: String,
-    block: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) block: Option<u64>,
 }
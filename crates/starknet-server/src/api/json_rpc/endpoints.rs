@@ -1,52 +1,82 @@
+use starknet_core::constants::RPC_SPEC_VERSION;
 use starknet_core::error::Error;
+use starknet_core::starknet::events::{ContinuationToken, EventFilter as CoreEventFilter};
+use starknet_core::starknet::Starknet;
+use starknet_core::transactions::{StarknetTransaction, Transaction as CoreTransaction};
+use starknet_core::StarknetBlock;
 use starknet_in_rust::core::errors::state_errors::StateError;
 use starknet_in_rust::transaction::error::TransactionError;
 use starknet_in_rust::utils::Address;
+use starknet_rs_core::types::{
+    BlockId as ImportedBlockId, TransactionStatus as ImportedTransactionStatus,
+};
 use starknet_types::felt::Felt;
-use starknet_types::starknet_api::block::BlockNumber;
+use starknet_types::starknet_api::block::{BlockNumber, BlockStatus as StarknetBlockStatus};
+use starknet_types::starknet_api::transaction::Fee;
 use starknet_types::traits::ToHexString;
 
 use super::error::{self, ApiError};
-use super::models::{BlockHashAndNumberOutput, EstimateFeeOutput, SyncingOutput};
+use super::models::{
+    BlockHashAndNumberOutput, EstimateFeeOutput, SimulatedTransaction, SyncingOutput,
+    TransactionStatusOutput,
+};
+use super::write_endpoints::convert_to_transaction;
 use super::{JsonRpcHandler, RpcResult};
-use crate::api::models::block::{Block, BlockHeader};
+use crate::api::models::block::{
+    Block, BlockHeader, BlockWithReceipts, MaybePendingBlock, PendingBlock, PendingBlockHeader,
+    SyncStatus,
+};
 use crate::api::models::contract_class::ContractClass;
 use crate::api::models::state::{
-    ClassHashes, ContractNonce, DeployedContract, StateUpdate, StorageDiff, StorageEntry,
-    ThinStateDiff,
+    ClassHashes, ContractNonce, DeployedContract, MaybePendingStateUpdate, PendingStateUpdate,
+    ReplacedClasses, StateUpdate, StorageDiff, StorageEntry, StorageProof, ThinStateDiff,
 };
+use crate::api::models::trace::{BlockTransactionTrace, TransactionTrace};
 use crate::api::models::transaction::{
-    BroadcastedTransactionWithType, ClassHashHex, EventFilter, EventsChunk, FunctionCall,
-    Transaction, TransactionHashHex, TransactionReceipt, TransactionWithType, Transactions,
+    BroadcastedTransactionWithType, ClassHashHex, CommonTransactionReceipt, EmittedEvent, Event,
+    EventContent, EventFilter, EventsChunk, FeePayment, FunctionCall, L1HandlerTransactionReceipt,
+    MessageToL1, SimulationFlag, Transaction, TransactionExecutionStatus,
+    TransactionFinalityStatus, TransactionHashHex, TransactionOutput, TransactionReceipt,
+    TransactionReceiptWithStatus, TransactionStatus, TransactionWithReceipt, TransactionWithType,
+    Transactions,
 };
-use crate::api::models::{BlockId, ContractAddressHex, FeltHex, PatriciaKeyHex};
+use crate::api::models::{BlockId, ContractAddressHex, FeltHex, PatriciaKeyHex, Tag};
+
+/// Maximum number of events returned in one chunk by starknet_getEvents
+const EVENTS_MAX_CHUNK_SIZE: usize = 1000;
+/// Maximum number of key positions in a starknet_getEvents filter
+const EVENTS_MAX_KEYS_IN_FILTER: usize = 100;
 
 /// here are the definitions and stub implementations of all JSON-RPC read endpoints
 impl JsonRpcHandler {
     /// starknet_getBlockWithTxHashes
-    pub(crate) async fn get_block_with_tx_hashes(&self, block_id: BlockId) -> RpcResult<Block> {
+    pub(crate) async fn get_block_with_tx_hashes(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<MaybePendingBlock> {
         let block =
             self.api.starknet.read().await.get_block(block_id.into()).map_err(|err| match err {
                 Error::NoBlock => ApiError::BlockNotFound,
                 unknown_error => ApiError::StarknetDevnetError(unknown_error),
             })?;
 
-        Ok(Block {
-            status: *block.status(),
-            header: BlockHeader::from(&block),
-            transactions: crate::api::models::transaction::Transactions::Hashes(
-                block
-                    .get_transactions()
-                    .iter()
-                    // We shouldnt get in the situation where tx hash is None
-                    .map(|tx| FeltHex(tx.get_hash().unwrap_or_default()))
-                    .collect(),
-            ),
-        })
+        let transactions = crate::api::models::transaction::Transactions::Hashes(
+            block
+                .get_transactions()
+                .iter()
+                // We shouldnt get in the situation where tx hash is None
+                .map(|tx| FeltHex(tx.get_hash().unwrap_or_default()))
+                .collect(),
+        );
+
+        Ok(maybe_pending_block(block_id, &block, transactions))
     }
 
     /// starknet_getBlockWithTxs
-    pub(crate) async fn get_block_with_txs(&self, block_id: BlockId) -> RpcResult<Block> {
+    pub(crate) async fn get_block_with_txs(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<MaybePendingBlock> {
         let block =
             self.api.starknet.read().await.get_block(block_id.into()).map_err(|err| match err {
                 Error::NoBlock => ApiError::BlockNotFound,
@@ -60,15 +90,60 @@ impl JsonRpcHandler {
 
             transactions.push(txn_to_add);
         }
-        Ok(Block {
+
+        Ok(maybe_pending_block(block_id, &block, Transactions::Full(transactions)))
+    }
+
+    /// starknet_getBlockWithReceipts
+    pub(crate) async fn get_block_with_receipts(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<BlockWithReceipts> {
+        let starknet = self.api.starknet.read().await;
+        let block = starknet.get_block(block_id.into()).map_err(|err| match err {
+            Error::NoBlock => ApiError::BlockNotFound,
+            unknown_error => ApiError::StarknetDevnetError(unknown_error),
+        })?;
+
+        let status = match block.status() {
+            StarknetBlockStatus::Pending => TransactionStatus::Pending,
+            _ => TransactionStatus::AcceptedOnL2,
+        };
+
+        let mut transactions = Vec::<TransactionWithReceipt>::new();
+        for txn in block.get_transactions() {
+            let transaction_hash = txn.get_hash().ok_or(ApiError::TransactionNotFound)?;
+            let transaction =
+                starknet.get_transaction_by_hash(transaction_hash).map_err(|err| match err {
+                    Error::TransactionNotFound => ApiError::TransactionNotFound,
+                    unknown_error => ApiError::StarknetDevnetError(unknown_error),
+                })?;
+            let receipt = transaction_receipt(
+                &starknet,
+                transaction,
+                block.block_hash(),
+                block.block_number(),
+            )?;
+
+            transactions.push(TransactionWithReceipt {
+                transaction: TransactionWithType::try_from(txn)?,
+                receipt: TransactionReceiptWithStatus { status, receipt },
+            });
+        }
+
+        Ok(BlockWithReceipts {
             status: *block.status(),
             header: BlockHeader::from(&block),
-            transactions: Transactions::Full(transactions),
+            transactions,
         })
     }
 
     /// starknet_getStateUpdate
-    pub(crate) async fn get_state_update(&self, block_id: BlockId) -> RpcResult<StateUpdate> {
+    pub(crate) async fn get_state_update(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<MaybePendingStateUpdate> {
+        let is_pending = matches!(block_id, BlockId::Tag(Tag::Pending));
         let state_update =
             self.api.starknet.read().await.block_state_update(block_id.into()).map_err(|err| {
                 match err {
@@ -121,15 +196,26 @@ impl JsonRpcHandler {
                         .collect(),
                 })
                 .collect(),
-            replaced_classes: vec![],
+            replaced_classes: state_update
+                .replaced_classes
+                .into_iter()
+                .map(|(contract_address, class_hash)| ReplacedClasses {
+                    contract_address: ContractAddressHex(contract_address),
+                    class_hash: FeltHex(class_hash),
+                })
+                .collect(),
         };
 
-        Ok(StateUpdate {
+        if is_pending {
+            return Ok(MaybePendingStateUpdate::PendingUpdate(PendingStateUpdate { state_diff }));
+        }
+
+        Ok(MaybePendingStateUpdate::Update(StateUpdate {
             block_hash: FeltHex(state_update.block_hash),
             new_root: FeltHex(state_update.new_root),
             old_root: FeltHex(state_update.old_root),
             state_diff,
-        })
+        }))
     }
 
     /// starknet_getStorageAt
@@ -149,12 +235,41 @@ impl JsonRpcHandler {
                 Error::NoBlock => ApiError::BlockNotFound,
                 Error::StateError(StateError::NoneStorage((_, _)))
                 | Error::NoStateAtBlock { block_number: _ } => ApiError::ContractNotFound,
+                Error::ForkError(err) => ApiError::ForkError(err),
                 unknown_error => ApiError::StarknetDevnetError(unknown_error),
             })?;
 
         Ok(FeltHex(felt))
     }
 
+    /// starknet_getStorageProof
+    pub(crate) async fn get_storage_proof(
+        &self,
+        block_id: BlockId,
+        contract_address: ContractAddressHex,
+        keys: Vec<PatriciaKeyHex>,
+    ) -> RpcResult<StorageProof> {
+        let proof = self
+            .api
+            .starknet
+            .read()
+            .await
+            .get_storage_proof(
+                block_id.into(),
+                contract_address.0,
+                keys.into_iter().map(|key| key.0).collect(),
+            )
+            .map_err(|err| match err {
+                Error::NoBlock => ApiError::BlockNotFound,
+                Error::StorageProofForPendingBlock
+                | Error::StorageProofInLiteMode
+                | Error::NoStateAtBlock { block_number: _ } => ApiError::StorageProofNotSupported,
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            })?;
+
+        Ok(StorageProof::from(proof))
+    }
+
     /// starknet_getTransactionByHash
     pub(crate) async fn get_transaction_by_hash(
         &self,
@@ -166,27 +281,91 @@ impl JsonRpcHandler {
     /// starknet_getTransactionByBlockIdAndIndex
     pub(crate) async fn get_transaction_by_block_id_and_index(
         &self,
-        _block_id: BlockId,
-        _index: BlockNumber,
+        block_id: BlockId,
+        index: BlockNumber,
     ) -> RpcResult<TransactionWithType> {
-        Err(error::ApiError::InvalidTransactionIndexInBlock)
+        let block =
+            self.api.starknet.read().await.get_block(block_id.into()).map_err(|err| match err {
+                Error::NoBlock => ApiError::BlockNotFound,
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            })?;
+
+        let transaction = usize::try_from(index.0)
+            .ok()
+            .and_then(|index| block.get_transactions().get(index))
+            .ok_or(ApiError::InvalidTransactionIndexInBlock)?;
+
+        TransactionWithType::try_from(transaction)
     }
 
     /// starknet_getTransactionReceipt
     pub(crate) async fn get_transaction_receipt_by_hash(
         &self,
-        _transaction_hash: TransactionHashHex,
+        transaction_hash: TransactionHashHex,
     ) -> RpcResult<TransactionReceipt> {
-        Err(error::ApiError::TransactionNotFound)
+        let starknet = self.api.starknet.read().await;
+        let to_api_error = |err| match err {
+            Error::TransactionNotFound => ApiError::TransactionNotFound,
+            unknown_error => ApiError::StarknetDevnetError(unknown_error),
+        };
+        let transaction =
+            starknet.get_transaction_by_hash(transaction_hash.0).map_err(to_api_error)?;
+
+        // rejected transactions and transactions of the pending block have no receipt
+        if *transaction.status() == ImportedTransactionStatus::Rejected {
+            return Err(ApiError::TransactionNotFound);
+        }
+        let (block_hash, block_number) =
+            match (transaction.block_hash(), transaction.block_number()) {
+                (Some(block_hash), Some(block_number)) => (block_hash, block_number),
+                _ => return Err(ApiError::TransactionNotFound),
+            };
+
+        transaction_receipt(&starknet, transaction, block_hash, block_number)
+    }
+
+    /// starknet_getTransactionStatus
+    pub(crate) async fn get_transaction_status_by_hash(
+        &self,
+        transaction_hash: TransactionHashHex,
+    ) -> RpcResult<TransactionStatusOutput> {
+        let starknet = self.api.starknet.read().await;
+        let transaction =
+            starknet.get_transaction_by_hash(transaction_hash.0).map_err(|err| match err {
+                Error::TransactionNotFound => ApiError::TransactionNotFound,
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            })?;
+
+        let finality_status = match transaction.status() {
+            ImportedTransactionStatus::Pending => TransactionFinalityStatus::Received,
+            ImportedTransactionStatus::AcceptedOnL2 => TransactionFinalityStatus::AcceptedOnL2,
+            ImportedTransactionStatus::AcceptedOnL1 => TransactionFinalityStatus::AcceptedOnL1,
+            ImportedTransactionStatus::Rejected => TransactionFinalityStatus::Rejected,
+        };
+        // transactions of aborted blocks were executed, but are rejected nevertheless
+        let execution_status = match finality_status {
+            TransactionFinalityStatus::Rejected => None,
+            _ => transaction.execution_status().map(TransactionExecutionStatus::from),
+        };
+
+        Ok(TransactionStatusOutput { finality_status, execution_status })
     }
 
     /// starknet_getClass
     pub(crate) async fn get_class(
         &self,
-        _block_id: BlockId,
-        _class_hash: ClassHashHex,
+        block_id: BlockId,
+        class_hash: ClassHashHex,
     ) -> RpcResult<ContractClass> {
-        Err(error::ApiError::ClassHashNotFound)
+        let result = self.api.starknet.read().await.get_class(block_id.into(), class_hash.0);
+
+        match result {
+            Ok(contract_class) => ContractClass::try_from(contract_class),
+            Err(Error::NoBlock) => Err(ApiError::BlockNotFound),
+            Err(Error::ClassHashNotFound) => Err(ApiError::ClassHashNotFound),
+            Err(Error::ForkError(err)) => Err(ApiError::ForkError(err)),
+            Err(unknown_error) => Err(ApiError::StarknetDevnetError(unknown_error)),
+        }
     }
 
     /// starknet_getClassHashAt
@@ -195,13 +374,16 @@ impl JsonRpcHandler {
         block_id: BlockId,
         contract_address: ContractAddressHex,
     ) -> RpcResult<ClassHashHex> {
-        let starknet = self.api.starknet.read().await;
-        match starknet.get_class_hash_at(&block_id.into(), &contract_address.0) {
+        let result =
+            self.api.starknet.read().await.get_class_hash_at(&block_id.into(), &contract_address.0);
+
+        match result {
             Ok(class_hash) => Ok(FeltHex(class_hash)),
             Err(Error::NoBlock) => Err(ApiError::BlockNotFound),
             Err(Error::ContractNotFound | Error::NoStateAtBlock { block_number: _ }) => {
                 Err(ApiError::ContractNotFound)
             }
+            Err(Error::ForkError(err)) => Err(ApiError::ForkError(err)),
             Err(unknown_error) => Err(ApiError::StarknetDevnetError(unknown_error)),
         }
     }
@@ -209,19 +391,21 @@ impl JsonRpcHandler {
     /// starknet_getClassAt
     pub(crate) async fn get_class_at(
         &self,
-        _block_id: BlockId,
-        _contract_address: ContractAddressHex,
+        block_id: BlockId,
+        contract_address: ContractAddressHex,
     ) -> RpcResult<ContractClass> {
-        Err(error::ApiError::ContractNotFound)
+        let class_hash = self.get_class_hash_at(block_id, contract_address).await?;
+
+        self.get_class(block_id, class_hash).await
     }
 
     /// starknet_getBlockTransactionCount
     pub(crate) async fn get_block_txs_count(&self, block_id: BlockId) -> RpcResult<u64> {
-        let num_trans_count = self.api.starknet.read().await.get_block_txs_count(block_id.into());
-        match num_trans_count {
-            Ok(count) => Ok(count),
-            Err(_) => Err(ApiError::NoBlocks),
-        }
+        let starknet = self.api.starknet.read().await;
+        starknet.get_block_txs_count(block_id.into()).map_err(|err| match err {
+            Error::NoBlock => ApiError::BlockNotFound,
+            unknown_error => ApiError::StarknetDevnetError(unknown_error),
+        })
     }
 
     /// starknet_call
@@ -241,17 +425,57 @@ impl JsonRpcHandler {
             Err(Error::TransactionError(TransactionError::State(
                 StateError::NoneContractState(Address(_address)),
             ))) => Err(ApiError::ContractNotFound),
-            Err(_) => Err(ApiError::ContractError),
+            Err(Error::NoBlock) => Err(ApiError::BlockNotFound),
+            Err(err) => Err(ApiError::ContractError {
+                revert_error: starknet.explain_failure(err.to_string()),
+            }),
         }
     }
 
     /// starknet_estimateFee
+    /// Transactions are estimated one after another, each one seeing the effects of the previous
     pub(crate) async fn estimate_fee(
         &self,
-        _block_id: BlockId,
-        _request: Vec<BroadcastedTransactionWithType>,
+        block_id: BlockId,
+        request: Vec<BroadcastedTransactionWithType>,
+        simulation_flags: Option<Vec<SimulationFlag>>,
     ) -> RpcResult<Vec<EstimateFeeOutput>> {
-        Err(error::ApiError::ContractError)
+        let starknet = self.api.starknet.read().await;
+        let transactions = convert_to_transactions(&starknet, request)?;
+        let simulation_flags = simulation_flags_or_default(&starknet, simulation_flags);
+        let skip_validate = simulation_flags.contains(&SimulationFlag::SkipValidate);
+
+        let estimations = starknet
+            .estimate_fee(block_id.into(), &transactions, skip_validate)
+            .map_err(into_simulation_error)?;
+
+        Ok(estimations.into_iter().map(EstimateFeeOutput::from).collect())
+    }
+
+    /// starknet_simulateTransactions
+    pub(crate) async fn simulate_transactions(
+        &self,
+        block_id: BlockId,
+        transactions: Vec<BroadcastedTransactionWithType>,
+        simulation_flags: Option<Vec<SimulationFlag>>,
+    ) -> RpcResult<Vec<SimulatedTransaction>> {
+        let starknet = self.api.starknet.read().await;
+        let transactions = convert_to_transactions(&starknet, transactions)?;
+        let simulation_flags = simulation_flags_or_default(&starknet, simulation_flags);
+        let skip_validate = simulation_flags.contains(&SimulationFlag::SkipValidate);
+        let skip_fee_charge = simulation_flags.contains(&SimulationFlag::SkipFeeCharge);
+
+        let simulations = starknet
+            .simulate_transactions(block_id.into(), &transactions, skip_validate, skip_fee_charge)
+            .map_err(into_simulation_error)?;
+
+        Ok(simulations
+            .into_iter()
+            .map(|simulation| SimulatedTransaction {
+                transaction_trace: simulation.transaction_trace.into(),
+                fee_estimation: simulation.fee_estimation.into(),
+            })
+            .collect())
     }
 
     /// starknet_blockNumber
@@ -277,7 +501,12 @@ impl JsonRpcHandler {
     pub(crate) async fn chain_id(&self) -> RpcResult<String> {
         let chain_id = self.api.starknet.read().await.chain_id();
 
-        Ok(Felt::from(chain_id.to_felt()).to_prefixed_hex_str())
+        Ok(chain_id.to_felt().to_prefixed_hex_str())
+    }
+
+    /// starknet_specVersion
+    pub(crate) async fn spec_version(&self) -> RpcResult<String> {
+        Ok(RPC_SPEC_VERSION.to_string())
     }
 
     /// starknet_pendingTransactions
@@ -286,13 +515,83 @@ impl JsonRpcHandler {
     }
 
     /// starknet_syncing
+    /// Devnet is always synced, unless configured to fake syncing between two blocks. Progress is
+    /// reported from the moment the starting block is mined until the latest block is the highest
+    /// one; the hash of the highest block is known only once it is mined.
     pub(crate) async fn syncing(&self) -> RpcResult<SyncingOutput> {
-        Ok(SyncingOutput::False(false))
+        let starknet = self.api.starknet.read().await;
+        let (starting_block_num, highest_block_num) =
+            match (starknet.config.fake_sync_from, starknet.config.fake_sync_until) {
+                (Some(from), Some(until)) => (from, until),
+                _ => return Ok(SyncingOutput::False(false)),
+            };
+
+        let (current_block_hash, current_block_num) = match starknet.get_latest_block() {
+            Ok(block) => (block.block_hash(), block.block_number()),
+            Err(Error::NoBlock) => return Ok(SyncingOutput::False(false)),
+            Err(unknown_error) => return Err(ApiError::StarknetDevnetError(unknown_error)),
+        };
+        if current_block_num.0 < starting_block_num || current_block_num.0 > highest_block_num {
+            return Ok(SyncingOutput::False(false));
+        }
+
+        let block_hash =
+            |block_number: u64| match starknet.get_block(ImportedBlockId::Number(block_number)) {
+                Ok(block) => Ok(block.block_hash()),
+                Err(Error::NoBlock) => Ok(Felt::default()),
+                Err(unknown_error) => Err(ApiError::StarknetDevnetError(unknown_error)),
+            };
+
+        Ok(SyncingOutput::True(SyncStatus {
+            starting_block_hash: FeltHex(block_hash(starting_block_num)?),
+            starting_block_num: BlockNumber(starting_block_num),
+            current_block_hash: FeltHex(current_block_hash),
+            current_block_num,
+            highest_block_hash: FeltHex(block_hash(highest_block_num)?),
+            highest_block_num: BlockNumber(highest_block_num),
+        }))
     }
 
     /// starknet_getEvents
-    pub(crate) async fn get_events(&self, _filter: EventFilter) -> RpcResult<EventsChunk> {
-        Err(error::ApiError::InvalidContinuationToken)
+    pub(crate) async fn get_events(&self, filter: EventFilter) -> RpcResult<EventsChunk> {
+        if filter.chunk_size > EVENTS_MAX_CHUNK_SIZE {
+            return Err(ApiError::RequestPageSizeTooBig);
+        }
+        if filter.keys.len() > EVENTS_MAX_KEYS_IN_FILTER {
+            return Err(ApiError::TooManyKeysInFilter);
+        }
+
+        let continuation_token = filter
+            .continuation_token
+            .map(|token| token.parse::<ContinuationToken>())
+            .transpose()
+            .map_err(|_| ApiError::InvalidContinuationToken)?;
+        let core_filter = CoreEventFilter {
+            from_block: filter.from_block.map(ImportedBlockId::from),
+            to_block: filter.to_block.map(ImportedBlockId::from),
+            address: filter.address.map(|address| address.0),
+            keys: filter
+                .keys
+                .into_iter()
+                .map(|alternatives| alternatives.into_iter().map(|key| key.0).collect())
+                .collect(),
+        };
+
+        let (events, continuation_token) = self
+            .api
+            .starknet
+            .read()
+            .await
+            .get_events(&core_filter, continuation_token, filter.chunk_size)
+            .map_err(|err| match err {
+                Error::NoBlock => ApiError::BlockNotFound,
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            })?;
+
+        Ok(EventsChunk {
+            events: events.into_iter().map(EmittedEvent::from).collect(),
+            continuation_token: continuation_token.map(|token| token.to_string()),
+        })
     }
 
     /// starknet_getNonce
@@ -312,9 +611,470 @@ impl JsonRpcHandler {
                 Error::NoStateAtBlock { block_number: _ } | Error::ContractNotFound => {
                     ApiError::ContractNotFound
                 }
+                Error::ForkError(err) => ApiError::ForkError(err),
                 unknown_error => ApiError::StarknetDevnetError(unknown_error),
             })?;
 
         Ok(FeltHex(nonce))
     }
+
+    /// starknet_traceTransaction
+    pub(crate) async fn trace_transaction(
+        &self,
+        transaction_hash: TransactionHashHex,
+    ) -> RpcResult<TransactionTrace> {
+        let trace = self.api.starknet.read().await.trace_transaction(&transaction_hash.0).map_err(
+            |err| match err {
+                Error::TransactionNotFound => ApiError::TransactionNotFound,
+                err @ Error::TraceForPendingTransaction => {
+                    ApiError::NoTraceAvailable { msg: err.to_string() }
+                }
+                unknown_error => ApiError::StarknetDevnetError(unknown_error),
+            },
+        )?;
+
+        Ok(trace.into())
+    }
+
+    /// starknet_traceBlockTransactions
+    pub(crate) async fn trace_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Vec<BlockTransactionTrace>> {
+        let traces =
+            self.api.starknet.read().await.trace_block_transactions(block_id.into()).map_err(
+                |err| match err {
+                    Error::NoBlock => ApiError::BlockNotFound,
+                    unknown_error => ApiError::StarknetDevnetError(unknown_error),
+                },
+            )?;
+
+        Ok(traces
+            .into_iter()
+            .map(|(transaction_hash, trace)| BlockTransactionTrace {
+                transaction_hash: FeltHex(transaction_hash),
+                trace_root: trace.into(),
+            })
+            .collect())
+    }
+}
+
+/// Converts the broadcasted transactions of an estimation or a simulation request
+fn convert_to_transactions(
+    starknet: &Starknet,
+    transactions: Vec<BroadcastedTransactionWithType>,
+) -> RpcResult<Vec<starknet_core::transactions::Transaction>> {
+    let chain_id = starknet.config.chain_id.to_felt();
+    transactions
+        .into_iter()
+        .map(|broadcasted| convert_to_transaction(broadcasted.transaction, chain_id))
+        .collect()
+}
+
+fn into_simulation_error(err: Error) -> ApiError {
+    match err {
+        Error::NoBlock => ApiError::BlockNotFound,
+        Error::ContractNotFound => ApiError::ContractNotFound,
+        Error::TransactionFailed { index, source } => {
+            ApiError::TransactionExecutionError { index, msg: source.to_string() }
+        }
+        unknown_error => ApiError::StarknetDevnetError(unknown_error),
+    }
+}
+
+/// Flags of an estimation or simulation. Explicitly given flags, even none, take precedence over
+/// the default ones, which include skipping validation if so configured
+fn simulation_flags_or_default(
+    starknet: &Starknet,
+    simulation_flags: Option<Vec<SimulationFlag>>,
+) -> Vec<SimulationFlag> {
+    simulation_flags.unwrap_or_else(|| {
+        if starknet.config.estimate_skip_validate {
+            vec![SimulationFlag::SkipValidate]
+        } else {
+            vec![]
+        }
+    })
+}
+
+/// Builds the receipt of an executed `transaction`, included in the block of `block_hash` and
+/// `block_number`
+fn transaction_receipt(
+    starknet: &Starknet,
+    transaction: &StarknetTransaction,
+    block_hash: Felt,
+    block_number: BlockNumber,
+) -> RpcResult<TransactionReceipt> {
+    let transaction_hash =
+        transaction.get_transaction().get_hash().ok_or(ApiError::TransactionNotFound)?;
+    let events = starknet
+        .get_transaction_events(transaction_hash)
+        .map_err(|err| match err {
+            Error::TransactionNotFound => ApiError::TransactionNotFound,
+            unknown_error => ApiError::StarknetDevnetError(unknown_error),
+        })?
+        .into_iter()
+        .map(|event| Event {
+            from_address: ContractAddressHex(event.from_address),
+            content: EventContent {
+                keys: event.keys.into_iter().map(FeltHex).collect(),
+                data: event.data.into_iter().map(FeltHex).collect(),
+            },
+        })
+        .collect();
+    let messages_sent = starknet
+        .get_transaction_messages_to_l1(transaction_hash)
+        .map_err(|err| match err {
+            Error::TransactionNotFound => ApiError::TransactionNotFound,
+            unknown_error => ApiError::StarknetDevnetError(unknown_error),
+        })?
+        .into_iter()
+        .map(MessageToL1::from)
+        .collect();
+
+    let common = CommonTransactionReceipt {
+        transaction_hash: FeltHex(transaction_hash),
+        r#type: TransactionWithType::try_from(transaction.get_transaction())?.r#type,
+        block_hash: FeltHex(block_hash),
+        block_number,
+        execution_status: transaction
+            .execution_status()
+            .map_or(TransactionExecutionStatus::Succeeded, TransactionExecutionStatus::from),
+        revert_reason: transaction.revert_reason().map(|reason| starknet.explain_failure(reason)),
+        output: TransactionOutput {
+            actual_fee: FeePayment {
+                amount: Fee(transaction.actual_fee()),
+                unit: transaction.get_transaction().fee_unit(),
+            },
+            messages_sent,
+            events,
+        },
+        execution_resources: transaction.execution_resources().into(),
+    };
+
+    Ok(match transaction.get_transaction() {
+        CoreTransaction::L1Handler(l1_handler_transaction) => {
+            TransactionReceipt::L1Handler(L1HandlerTransactionReceipt {
+                common,
+                message_hash: l1_handler_transaction.message_hash(),
+            })
+        }
+        _ => TransactionReceipt::Common(common),
+    })
+}
+
+/// The pending block has no hash, number nor root yet, so it's returned without them
+fn maybe_pending_block(
+    block_id: BlockId,
+    block: &StarknetBlock,
+    transactions: Transactions,
+) -> MaybePendingBlock {
+    if matches!(block_id, BlockId::Tag(Tag::Pending)) {
+        return MaybePendingBlock::PendingBlock(PendingBlock {
+            header: PendingBlockHeader::from(block),
+            transactions,
+        });
+    }
+
+    MaybePendingBlock::Block(Block {
+        status: *block.status(),
+        header: BlockHeader::from(block),
+        transactions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_core::constants::DEVNET_DEFAULT_GAS_PRICE;
+    use starknet_core::starknet::{BlockGenerationOn, FeeUnit, Starknet, StarknetConfig};
+    use starknet_rs_core::types::BlockId as ImportedBlockId;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+    use starknet_types::starknet_api::block::BlockNumber;
+
+    use crate::api::json_rpc::error::ApiError;
+    use crate::api::json_rpc::models::SyncingOutput;
+    use crate::api::json_rpc::JsonRpcHandler;
+    use crate::api::models::block::{MaybePendingBlock, PendingBlock};
+    use crate::api::models::state::MaybePendingStateUpdate;
+    use crate::api::models::transaction::{TransactionWithType, Transactions};
+    use crate::api::models::{BlockHashOrNumber, BlockId, FeltHex, Tag};
+    use crate::api::Api;
+
+    fn setup() -> JsonRpcHandler {
+        setup_with_config(StarknetConfig::default())
+    }
+
+    fn setup_with_config(config: StarknetConfig) -> JsonRpcHandler {
+        let config = StarknetConfig { gas_price: DEVNET_DEFAULT_GAS_PRICE, ..config };
+        let starknet = Starknet::new(&config).unwrap();
+        JsonRpcHandler { api: Api::new(starknet) }
+    }
+
+    async fn mine_block(handler: &JsonRpcHandler) {
+        let address = ContractAddress::new(Felt::from(1)).unwrap();
+        handler.api.starknet.write().await.mint(address, 1u32.into(), FeeUnit::Wei).await.unwrap();
+    }
+
+    async fn latest_block_number(handler: &JsonRpcHandler) -> Option<u64> {
+        let starknet = handler.api.starknet.read().await;
+        starknet.get_latest_block().ok().map(|block| block.block_number().0)
+    }
+
+    async fn block_hash(handler: &JsonRpcHandler, block_number: u64) -> FeltHex {
+        let starknet = handler.api.starknet.read().await;
+        FeltHex(starknet.get_block(ImportedBlockId::Number(block_number)).unwrap().block_hash())
+    }
+
+    #[tokio::test]
+    async fn syncing_is_false_by_default() {
+        let handler = setup();
+        assert_eq!(handler.syncing().await.unwrap(), SyncingOutput::False(false));
+
+        mine_block(&handler).await;
+        assert_eq!(handler.syncing().await.unwrap(), SyncingOutput::False(false));
+    }
+
+    #[tokio::test]
+    async fn fake_syncing_progresses_from_starting_until_highest_block() {
+        let handler = setup_with_config(StarknetConfig {
+            fake_sync_from: Some(2),
+            fake_sync_until: Some(4),
+            ..StarknetConfig::default()
+        });
+        // syncing starts once the starting block is mined
+        while latest_block_number(&handler).await < Some(2) {
+            assert_eq!(handler.syncing().await.unwrap(), SyncingOutput::False(false));
+            mine_block(&handler).await;
+        }
+
+        for current_block_num in 2..=4 {
+            let status = match handler.syncing().await.unwrap() {
+                SyncingOutput::True(status) => status,
+                SyncingOutput::False(_) => panic!("Expected sync status"),
+            };
+            assert_eq!(status.starting_block_num, BlockNumber(2));
+            assert_eq!(status.starting_block_hash, block_hash(&handler, 2).await);
+            assert_eq!(status.current_block_num, BlockNumber(current_block_num));
+            assert_eq!(status.current_block_hash, block_hash(&handler, current_block_num).await);
+            assert_eq!(status.highest_block_num, BlockNumber(4));
+            // the hash of the highest block is known once it is mined
+            if current_block_num == 4 {
+                assert_eq!(status.highest_block_hash, block_hash(&handler, 4).await);
+            } else {
+                assert_eq!(status.highest_block_hash, FeltHex(Felt::default()));
+            }
+            mine_block(&handler).await;
+        }
+
+        assert_eq!(handler.syncing().await.unwrap(), SyncingOutput::False(false));
+    }
+
+    fn full_transactions(block: MaybePendingBlock) -> Vec<TransactionWithType> {
+        let transactions = match block {
+            MaybePendingBlock::Block(block) => block.transactions,
+            MaybePendingBlock::PendingBlock(block) => block.transactions,
+        };
+        match transactions {
+            Transactions::Full(transactions) => transactions,
+            Transactions::Hashes(_) => panic!("Expected full transactions"),
+        }
+    }
+
+    #[tokio::test]
+    async fn transaction_by_block_id_and_index_found() {
+        let handler = setup();
+        mine_block(&handler).await;
+        mine_block(&handler).await;
+
+        let block_id = BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(1)));
+        let expected = full_transactions(handler.get_block_with_txs(block_id).await.unwrap());
+        assert_eq!(
+            handler.get_transaction_by_block_id_and_index(block_id, BlockNumber(0)).await.unwrap(),
+            expected[0]
+        );
+    }
+
+    #[tokio::test]
+    async fn transaction_by_block_id_and_index_out_of_range_or_in_unknown_block() {
+        let handler = setup();
+        mine_block(&handler).await;
+
+        let latest = BlockId::Tag(Tag::Latest);
+        assert!(matches!(
+            handler.get_transaction_by_block_id_and_index(latest, BlockNumber(1)).await,
+            Err(ApiError::InvalidTransactionIndexInBlock)
+        ));
+        assert!(matches!(
+            handler.get_transaction_by_block_id_and_index(latest, BlockNumber(u64::MAX)).await,
+            Err(ApiError::InvalidTransactionIndexInBlock)
+        ));
+
+        let unknown_block = BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(42)));
+        assert!(matches!(
+            handler.get_transaction_by_block_id_and_index(unknown_block, BlockNumber(0)).await,
+            Err(ApiError::BlockNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn transaction_by_block_id_and_index_in_pending_block() {
+        let handler = setup_with_config(StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..StarknetConfig::default()
+        });
+        mine_block(&handler).await;
+        mine_block(&handler).await;
+
+        let pending = BlockId::Tag(Tag::Pending);
+        let expected = full_transactions(handler.get_block_with_txs(pending).await.unwrap());
+        assert_eq!(expected.len(), 2);
+        for (index, expected_transaction) in expected.iter().enumerate() {
+            let transaction = handler
+                .get_transaction_by_block_id_and_index(pending, BlockNumber(index as u64))
+                .await
+                .unwrap();
+            assert_eq!(&transaction, expected_transaction);
+        }
+        assert!(matches!(
+            handler.get_transaction_by_block_id_and_index(pending, BlockNumber(2)).await,
+            Err(ApiError::InvalidTransactionIndexInBlock)
+        ));
+    }
+
+    async fn pending_block(handler: &JsonRpcHandler) -> PendingBlock {
+        match handler.get_block_with_tx_hashes(BlockId::Tag(Tag::Pending)).await.unwrap() {
+            MaybePendingBlock::PendingBlock(block) => block,
+            MaybePendingBlock::Block(_) => panic!("Expected pending block"),
+        }
+    }
+
+    #[tokio::test]
+    async fn pending_block_empty_in_transaction_mode() {
+        let handler = setup();
+        mine_block(&handler).await;
+        let latest_block =
+            match handler.get_block_with_tx_hashes(BlockId::Tag(Tag::Latest)).await.unwrap() {
+                MaybePendingBlock::Block(block) => block,
+                MaybePendingBlock::PendingBlock(_) => panic!("Expected block"),
+            };
+        assert!(
+            matches!(&latest_block.transactions, Transactions::Hashes(hashes) if hashes.len() == 1)
+        );
+
+        let pending_block = pending_block(&handler).await;
+        assert_eq!(pending_block.transactions, Transactions::Hashes(vec![]));
+        assert_eq!(pending_block.header.parent_hash, latest_block.header.block_hash);
+        assert!(pending_block.header.timestamp >= latest_block.header.timestamp);
+        assert_eq!(pending_block.header.l1_gas_price, latest_block.header.l1_gas_price);
+
+        let serialized =
+            serde_json::to_value(MaybePendingBlock::PendingBlock(pending_block)).unwrap();
+        for committed_field in ["status", "block_hash", "block_number", "new_root"] {
+            assert!(serialized.get(committed_field).is_none(), "{serialized}");
+        }
+    }
+
+    #[tokio::test]
+    async fn pending_block_holds_uncommitted_transactions_in_demand_mode() {
+        let handler = setup_with_config(StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..StarknetConfig::default()
+        });
+        let block_hash = handler.api.starknet.write().await.create_block().unwrap();
+        mine_block(&handler).await;
+        mine_block(&handler).await;
+
+        let pending_transactions = match pending_block(&handler).await.transactions {
+            Transactions::Hashes(hashes) => hashes,
+            Transactions::Full(_) => panic!("Expected transaction hashes"),
+        };
+        assert_eq!(pending_transactions.len(), 2);
+        assert_eq!(pending_block(&handler).await.header.parent_hash, FeltHex(block_hash));
+
+        // the pending transactions are the ones of the next block
+        handler.api.starknet.write().await.create_block().unwrap();
+        match handler.get_block_with_tx_hashes(BlockId::Tag(Tag::Latest)).await.unwrap() {
+            MaybePendingBlock::Block(block) => {
+                assert_eq!(block.transactions, Transactions::Hashes(pending_transactions))
+            }
+            MaybePendingBlock::PendingBlock(_) => panic!("Expected block"),
+        }
+        assert_eq!(pending_block(&handler).await.transactions, Transactions::Hashes(vec![]));
+    }
+
+    #[tokio::test]
+    async fn block_transaction_count_of_populated_and_empty_blocks() {
+        let handler = setup_with_config(StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..StarknetConfig::default()
+        });
+        // the genesis block, created on startup, is empty
+        let genesis = BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(0)));
+        assert_eq!(handler.get_block_txs_count(genesis).await.unwrap(), 0);
+
+        mine_block(&handler).await;
+        mine_block(&handler).await;
+        let pending = BlockId::Tag(Tag::Pending);
+        assert_eq!(handler.get_block_txs_count(pending).await.unwrap(), 2);
+
+        handler.api.starknet.write().await.create_block().unwrap();
+        let latest = BlockId::Tag(Tag::Latest);
+        assert_eq!(handler.get_block_txs_count(latest).await.unwrap(), 2);
+        assert_eq!(handler.get_block_txs_count(pending).await.unwrap(), 0);
+        assert_eq!(handler.get_block_txs_count(genesis).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn block_transaction_count_of_unknown_block() {
+        let handler = setup();
+        mine_block(&handler).await;
+
+        let unknown_hash = BlockId::HashOrNumber(BlockHashOrNumber::Hash(FeltHex(Felt::from(42))));
+        assert!(matches!(
+            handler.get_block_txs_count(unknown_hash).await,
+            Err(ApiError::BlockNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn state_update_of_pending_block_has_no_block_hash_nor_roots() {
+        let handler = setup_with_config(StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..StarknetConfig::default()
+        });
+        mine_block(&handler).await;
+
+        let pending = BlockId::Tag(Tag::Pending);
+        let pending_state_diff = match handler.get_state_update(pending).await.unwrap() {
+            MaybePendingStateUpdate::PendingUpdate(update) => update.state_diff,
+            MaybePendingStateUpdate::Update(_) => panic!("Expected pending state update"),
+        };
+        assert!(!pending_state_diff.storage_diffs.is_empty());
+
+        let block_hash = handler.api.starknet.write().await.create_block().unwrap();
+        match handler.get_state_update(BlockId::Tag(Tag::Latest)).await.unwrap() {
+            MaybePendingStateUpdate::Update(update) => {
+                assert_eq!(update.block_hash, FeltHex(block_hash));
+                // diffs of the same changes, possibly listed in another order
+                assert_eq!(
+                    update.state_diff.storage_diffs.len(),
+                    pending_state_diff.storage_diffs.len()
+                );
+                assert_eq!(update.state_diff.nonces.len(), pending_state_diff.nonces.len());
+            }
+            MaybePendingStateUpdate::PendingUpdate(_) => panic!("Expected state update"),
+        }
+        match handler.get_state_update(pending).await.unwrap() {
+            MaybePendingStateUpdate::PendingUpdate(update) => {
+                assert!(update.state_diff.storage_diffs.is_empty())
+            }
+            MaybePendingStateUpdate::Update(_) => panic!("Expected pending state update"),
+        }
+    }
+
+    #[test]
+    fn syncing_output_serialization() {
+        assert_eq!(serde_json::to_string(&SyncingOutput::False(false)).unwrap(), "false");
+    }
 }
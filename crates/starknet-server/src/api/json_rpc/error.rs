@@ -1,5 +1,8 @@
 use server::rpc_core::error::RpcError;
+use starknet_core::starknet::fork::ForkError;
 use starknet_types;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::ClassHash;
 use thiserror::Error;
 use tracing::error;
 
@@ -23,7 +26,7 @@ pub enum ApiError {
     #[error("Class hash not found")]
     ClassHashNotFound,
     #[error("Contract error")]
-    ContractError,
+    ContractError { revert_error: String },
     #[error("There are no blocks")]
     NoBlocks,
     #[error("Requested page size is too big")]
@@ -33,20 +36,46 @@ pub enum ApiError {
     #[error("Too many keys provided in a filter")]
     TooManyKeysInFilter,
     #[error("Class already declared")]
-    ClassAlreadyDeclared,
+    ClassAlreadyDeclared { class_hash: ClassHash },
+    #[error("Compiled class hash mismatch")]
+    CompiledClassHashMismatch { expected: ClassHash, provided: ClassHash },
     #[error("Invalid contract class")]
     InvalidContractClass,
+    #[error("Contract already deployed")]
+    ContractAlreadyDeployed { contract_address: ContractAddress },
+    #[error("Invalid transaction nonce")]
+    InvalidTransactionNonce,
+    #[error("Transaction {field} has {length} elements, more than the allowed {max_length}")]
+    TransactionTooLong { field: &'static str, length: usize, max_length: usize },
+    #[error("The contract class version is not supported")]
+    UnsupportedContractClassVersion { version: String },
     #[error("Only latest/pending block is supported")]
     OnlyLatestBlock,
     #[error("Unsupported version")]
     UnsupportedVersion,
+    #[error("Storage proofs are not supported for the requested block")]
+    StorageProofNotSupported,
+    #[error("No trace available for transaction: {msg}")]
+    NoTraceAvailable { msg: String },
+    #[error("Transaction at index {index} failed: {msg}")]
+    TransactionExecutionError { index: usize, msg: String },
+    #[error("The transaction's resources don't cover validation or the minimal transaction fee")]
+    InsufficientResources,
+    #[error(transparent)]
+    ForkError(#[from] ForkError),
 }
 
 #[cfg(test)]
 mod tests {
 
+    use starknet_core::starknet::fork::ForkError;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+
     use crate::api::json_rpc::error::ApiError;
-    use crate::api::json_rpc::{RpcResult, ToRpcResponseResult};
+    use crate::api::json_rpc::{
+        FORK_TIMEOUT_RPC_ERROR_CODE, RpcResult, ToRpcResponseResult, WILDCARD_RPC_ERROR_CODE,
+    };
 
     #[test]
     fn contract_not_found_error() {
@@ -81,6 +110,121 @@ mod tests {
         error_expected_code_and_message(ApiError::ClassHashNotFound, 28, "Class hash not found");
     }
 
+    #[test]
+    fn class_already_declared_error() {
+        let error_result =
+            RpcResult::<()>::Err(ApiError::ClassAlreadyDeclared { class_hash: Felt::from(42) })
+                .to_rpc_result();
+        match error_result {
+            server::rpc_core::response::ResponseResult::Success(_) => panic!("Expected error"),
+            server::rpc_core::response::ResponseResult::Error(err) => {
+                assert_eq!(err.message, "Class already declared");
+                assert_eq!(err.code, server::rpc_core::error::ErrorCode::ServerError(51));
+                assert_eq!(err.data, Some(serde_json::json!({ "class_hash": "0x2a" })));
+            }
+        }
+    }
+
+    #[test]
+    fn contract_already_deployed_error() {
+        let error_result = RpcResult::<()>::Err(ApiError::ContractAlreadyDeployed {
+            contract_address: ContractAddress::new(Felt::from(42)).unwrap(),
+        })
+        .to_rpc_result();
+        match error_result {
+            server::rpc_core::response::ResponseResult::Success(_) => panic!("Expected error"),
+            server::rpc_core::response::ResponseResult::Error(err) => {
+                assert_eq!(err.message, "Contract already deployed");
+                assert_eq!(
+                    err.code,
+                    server::rpc_core::error::ErrorCode::ServerError(WILDCARD_RPC_ERROR_CODE)
+                );
+                assert_eq!(err.data, Some(serde_json::json!({ "contract_address": "0x2a" })));
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_transaction_nonce_error() {
+        error_expected_code_and_message(
+            ApiError::InvalidTransactionNonce,
+            52,
+            "Invalid transaction nonce",
+        );
+    }
+
+    #[test]
+    fn unsupported_contract_class_version_error() {
+        let error_result = RpcResult::<()>::Err(ApiError::UnsupportedContractClassVersion {
+            version: "1.99.0".to_string(),
+        })
+        .to_rpc_result();
+
+        match error_result {
+            server::rpc_core::response::ResponseResult::Success(_) => panic!("Expected error"),
+            server::rpc_core::response::ResponseResult::Error(err) => {
+                assert_eq!(err.message, "The contract class version is not supported");
+                assert_eq!(err.code, server::rpc_core::error::ErrorCode::ServerError(62));
+                let data = err.data.unwrap();
+                assert_eq!(data["sierra_version"], "1.99.0");
+                assert_eq!(data["supported_sierra_versions"][0], "1.1.0");
+            }
+        }
+    }
+
+    #[test]
+    fn transaction_too_long_error() {
+        let error_result = RpcResult::<()>::Err(ApiError::TransactionTooLong {
+            field: "calldata",
+            length: 11,
+            max_length: 10,
+        })
+        .to_rpc_result();
+
+        match error_result {
+            server::rpc_core::response::ResponseResult::Success(_) => panic!("Expected error"),
+            server::rpc_core::response::ResponseResult::Error(err) => {
+                assert_eq!(
+                    err.message,
+                    "Transaction calldata has 11 elements, more than the allowed 10"
+                );
+                assert_eq!(
+                    err.code,
+                    server::rpc_core::error::ErrorCode::ServerError(WILDCARD_RPC_ERROR_CODE)
+                );
+                assert_eq!(
+                    err.data,
+                    Some(
+                        serde_json::json!({ "field": "calldata", "length": 11, "max_length": 10 })
+                    )
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn compiled_class_hash_mismatch_error() {
+        let error_result = RpcResult::<()>::Err(ApiError::CompiledClassHashMismatch {
+            expected: Felt::from(42),
+            provided: Felt::from(43),
+        })
+        .to_rpc_result();
+        match error_result {
+            server::rpc_core::response::ResponseResult::Success(_) => panic!("Expected error"),
+            server::rpc_core::response::ResponseResult::Error(err) => {
+                assert_eq!(err.message, "Compiled class hash mismatch");
+                assert_eq!(err.code, server::rpc_core::error::ErrorCode::ServerError(60));
+                assert_eq!(
+                    err.data,
+                    Some(serde_json::json!({
+                        "expected_compiled_class_hash": "0x2a",
+                        "provided_compiled_class_hash": "0x2b",
+                    }))
+                );
+            }
+        }
+    }
+
     #[test]
     fn page_size_too_big_error() {
         error_expected_code_and_message(
@@ -115,7 +259,97 @@ mod tests {
 
     #[test]
     fn contract_error() {
-        error_expected_code_and_message(ApiError::ContractError, 40, "Contract error");
+        error_expected_code_and_message(
+            ApiError::ContractError { revert_error: "Execution failed".to_string() },
+            40,
+            "Contract error",
+        );
+    }
+
+    #[test]
+    fn storage_proof_not_supported_error() {
+        error_expected_code_and_message(
+            ApiError::StorageProofNotSupported,
+            42,
+            "Storage proofs are not supported for the requested block",
+        );
+    }
+
+    #[test]
+    fn no_trace_available_error() {
+        error_expected_code_and_message(
+            ApiError::NoTraceAvailable { msg: "transaction is pending".to_string() },
+            10,
+            "No trace available for transaction: transaction is pending",
+        );
+    }
+
+    #[test]
+    fn insufficient_resources_error() {
+        error_expected_code_and_message(
+            ApiError::InsufficientResources,
+            53,
+            "The transaction's resources don't cover validation or the minimal transaction fee",
+        );
+    }
+
+    #[test]
+    fn transaction_execution_error() {
+        let error_result = RpcResult::<()>::Err(ApiError::TransactionExecutionError {
+            index: 1,
+            msg: "Class not declared".to_string(),
+        })
+        .to_rpc_result();
+        match error_result {
+            server::rpc_core::response::ResponseResult::Success(_) => panic!("Expected error"),
+            server::rpc_core::response::ResponseResult::Error(err) => {
+                assert_eq!(err.message, "Transaction execution error");
+                assert_eq!(err.code, server::rpc_core::error::ErrorCode::ServerError(41));
+                assert_eq!(
+                    err.data,
+                    Some(serde_json::json!({
+                        "transaction_index": 1,
+                        "execution_error": "Class not declared",
+                    }))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fork_timeout_error() {
+        error_expected_code_and_message(
+            ApiError::ForkError(ForkError::Timeout),
+            FORK_TIMEOUT_RPC_ERROR_CODE,
+            "Request to the forked network timed out",
+        );
+    }
+
+    #[test]
+    fn fork_contract_not_found_error() {
+        error_expected_code_and_message(
+            ApiError::ForkError(ForkError::ContractNotFound),
+            20,
+            "Contract not found",
+        );
+    }
+
+    #[test]
+    fn fork_class_hash_not_found_error() {
+        error_expected_code_and_message(
+            ApiError::ForkError(ForkError::ClassHashNotFound),
+            28,
+            "Class hash not found",
+        );
+    }
+
+    #[test]
+    fn fork_upstream_error() {
+        error_expected_code_and_message(
+            ApiError::ForkError(ForkError::Upstream { msg: "unreachable".to_string() }),
+            WILDCARD_RPC_ERROR_CODE,
+            "Forked network error: unreachable",
+        );
     }
 
     fn error_expected_code_and_message(err: ApiError, expected_code: i64, expected_message: &str) {
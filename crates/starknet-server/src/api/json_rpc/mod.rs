@@ -1,16 +1,20 @@
 mod endpoints;
 pub mod error;
-mod models;
+pub(crate) mod models;
 mod write_endpoints;
 
 use models::{
     BlockAndClassHashInput, BlockAndContractAddressInput, BlockAndIndexInput, CallInput,
-    EstimateFeeInput, EventsInput, GetStorageInput, TransactionHashInput,
+    EstimateFeeInput, EventsInput, GetStorageInput, GetStorageProofInput,
+    SimulateTransactionsInput, TransactionHashInput,
 };
 use serde::{Deserialize, Serialize};
 use server::rpc_core::error::RpcError;
 use server::rpc_core::response::ResponseResult;
 use server::rpc_handler::RpcHandler;
+use starknet_core::constants::SUPPORTED_SIERRA_VERSIONS;
+use starknet_core::starknet::fork::ForkError;
+use starknet_types::traits::ToHexString;
 use tracing::{error, info, trace};
 
 use self::error::ApiError;
@@ -31,6 +35,9 @@ pub(crate) trait ToRpcResponseResult {
 /// Used when there is no defined code to use
 pub const WILDCARD_RPC_ERROR_CODE: i64 = -1;
 
+/// Used when a request to the forked network doesn't complete within the server timeout
+pub const FORK_TIMEOUT_RPC_ERROR_CODE: i64 = -32001;
+
 /// Converts a serializable value into a `ResponseResult`
 pub fn to_rpc_result<T: Serialize>(val: T) -> ResponseResult {
     match serde_json::to_value(val) {
@@ -73,10 +80,10 @@ impl<T: Serialize> ToRpcResponseResult for RpcResult<T> {
                     message: err.to_string().into(),
                     data: None,
                 },
-                err @ ApiError::ContractError => RpcError {
+                ApiError::ContractError { revert_error } => RpcError {
                     code: server::rpc_core::error::ErrorCode::ServerError(40),
-                    message: err.to_string().into(),
-                    data: None,
+                    message: "Contract error".into(),
+                    data: Some(serde_json::json!({ "revert_error": revert_error })),
                 },
                 err @ ApiError::NoBlocks => RpcError {
                     code: server::rpc_core::error::ErrorCode::ServerError(32),
@@ -98,11 +105,52 @@ impl<T: Serialize> ToRpcResponseResult for RpcResult<T> {
                     message: err.to_string().into(),
                     data: None,
                 },
-                err @ ApiError::ClassAlreadyDeclared => RpcError {
+                err @ ApiError::ClassAlreadyDeclared { class_hash } => RpcError {
                     code: server::rpc_core::error::ErrorCode::ServerError(51),
                     message: err.to_string().into(),
+                    data: Some(serde_json::json!({
+                        "class_hash": class_hash.to_prefixed_hex_str(),
+                    })),
+                },
+                err @ ApiError::CompiledClassHashMismatch { expected, provided } => RpcError {
+                    code: server::rpc_core::error::ErrorCode::ServerError(60),
+                    message: err.to_string().into(),
+                    data: Some(serde_json::json!({
+                        "expected_compiled_class_hash": expected.to_prefixed_hex_str(),
+                        "provided_compiled_class_hash": provided.to_prefixed_hex_str(),
+                    })),
+                },
+                err @ ApiError::ContractAlreadyDeployed { contract_address } => RpcError {
+                    // the specification has no dedicated code for an occupied address
+                    code: server::rpc_core::error::ErrorCode::ServerError(WILDCARD_RPC_ERROR_CODE),
+                    message: err.to_string().into(),
+                    data: Some(serde_json::json!({
+                        "contract_address": contract_address.to_prefixed_hex_str(),
+                    })),
+                },
+                err @ ApiError::InvalidTransactionNonce => RpcError {
+                    code: server::rpc_core::error::ErrorCode::ServerError(52),
+                    message: err.to_string().into(),
                     data: None,
                 },
+                err @ ApiError::TransactionTooLong { field, length, max_length } => RpcError {
+                    // the specification has no dedicated code for oversized transactions
+                    code: server::rpc_core::error::ErrorCode::ServerError(WILDCARD_RPC_ERROR_CODE),
+                    message: err.to_string().into(),
+                    data: Some(serde_json::json!({
+                        "field": field,
+                        "length": length,
+                        "max_length": max_length,
+                    })),
+                },
+                err @ ApiError::UnsupportedContractClassVersion { version } => RpcError {
+                    code: server::rpc_core::error::ErrorCode::ServerError(62),
+                    message: err.to_string().into(),
+                    data: Some(serde_json::json!({
+                        "sierra_version": version,
+                        "supported_sierra_versions": SUPPORTED_SIERRA_VERSIONS,
+                    })),
+                },
                 err @ ApiError::InvalidContractClass => RpcError {
                     code: server::rpc_core::error::ErrorCode::ServerError(50),
                     message: err.to_string().into(),
@@ -128,6 +176,39 @@ impl<T: Serialize> ToRpcResponseResult for RpcResult<T> {
                     message: err.to_string().into(),
                     data: None,
                 },
+                err @ ApiError::StorageProofNotSupported => RpcError {
+                    code: server::rpc_core::error::ErrorCode::ServerError(42),
+                    message: err.to_string().into(),
+                    data: None,
+                },
+                err @ ApiError::NoTraceAvailable { msg: _ } => RpcError {
+                    code: server::rpc_core::error::ErrorCode::ServerError(10),
+                    message: err.to_string().into(),
+                    data: None,
+                },
+                err @ ApiError::InsufficientResources => RpcError {
+                    code: server::rpc_core::error::ErrorCode::ServerError(53),
+                    message: err.to_string().into(),
+                    data: None,
+                },
+                ApiError::TransactionExecutionError { index, msg } => RpcError {
+                    code: server::rpc_core::error::ErrorCode::ServerError(41),
+                    message: "Transaction execution error".into(),
+                    data: Some(serde_json::json!({
+                        "transaction_index": index,
+                        "execution_error": msg,
+                    })),
+                },
+                ApiError::ForkError(err) => RpcError {
+                    code: server::rpc_core::error::ErrorCode::ServerError(match err {
+                        ForkError::Timeout => FORK_TIMEOUT_RPC_ERROR_CODE,
+                        ForkError::ContractNotFound => 20,
+                        ForkError::ClassHashNotFound => 28,
+                        ForkError::Upstream { msg: _ } => WILDCARD_RPC_ERROR_CODE,
+                    }),
+                    message: err.to_string().into(),
+                    data: None,
+                },
             }
             .into(),
         }
@@ -164,12 +245,20 @@ impl JsonRpcHandler {
             StarknetRequest::BlockWithFullTransactions(block) => {
                 self.get_block_with_txs(block.block_id).await.to_rpc_result()
             }
+            StarknetRequest::BlockWithReceipts(block) => {
+                self.get_block_with_receipts(block.block_id).await.to_rpc_result()
+            }
             StarknetRequest::StateUpdate(block) => {
                 self.get_state_update(block.block_id).await.to_rpc_result()
             }
             StarknetRequest::StorageAt(GetStorageInput { contract_address, key, block_id }) => {
                 self.get_storage_at(contract_address, key, block_id).await.to_rpc_result()
             }
+            StarknetRequest::StorageProof(GetStorageProofInput {
+                block_id,
+                contract_address,
+                keys,
+            }) => self.get_storage_proof(block_id, contract_address, keys).await.to_rpc_result(),
             StarknetRequest::TransactionByHash(TransactionHashInput { transaction_hash }) => {
                 self.get_transaction_by_hash(transaction_hash).await.to_rpc_result()
             }
@@ -179,6 +268,9 @@ impl JsonRpcHandler {
             StarknetRequest::TransactionReceiptByTransactionHash(TransactionHashInput {
                 transaction_hash,
             }) => self.get_transaction_receipt_by_hash(transaction_hash).await.to_rpc_result(),
+            StarknetRequest::TransactionStatusByHash(TransactionHashInput { transaction_hash }) => {
+                self.get_transaction_status_by_hash(transaction_hash).await.to_rpc_result()
+            }
             StarknetRequest::ClassByHash(BlockAndClassHashInput { block_id, class_hash }) => {
                 self.get_class(block_id, class_hash).await.to_rpc_result()
             }
@@ -196,14 +288,31 @@ impl JsonRpcHandler {
             StarknetRequest::Call(CallInput { request, block_id }) => {
                 self.call(block_id, request).await.to_rpc_result()
             }
-            StarknetRequest::EsimateFee(EstimateFeeInput { request, block_id }) => {
-                self.estimate_fee(block_id, request).await.to_rpc_result()
+            StarknetRequest::EsimateFee(EstimateFeeInput {
+                request,
+                block_id,
+                simulation_flags,
+            }) => self.estimate_fee(block_id, request, simulation_flags).await.to_rpc_result(),
+            StarknetRequest::TraceTransaction(TransactionHashInput { transaction_hash }) => {
+                self.trace_transaction(transaction_hash).await.to_rpc_result()
             }
+            StarknetRequest::TraceBlockTransactions(BlockIdInput { block_id }) => {
+                self.trace_block_transactions(block_id).await.to_rpc_result()
+            }
+            StarknetRequest::SimulateTransactions(SimulateTransactionsInput {
+                block_id,
+                transactions,
+                simulation_flags,
+            }) => self
+                .simulate_transactions(block_id, transactions, simulation_flags)
+                .await
+                .to_rpc_result(),
             StarknetRequest::BlockNumber => self.block_number().await.to_rpc_result(),
             StarknetRequest::BlockHashAndNumber => {
                 self.block_hash_and_number().await.to_rpc_result()
             }
             StarknetRequest::ChainId => self.chain_id().await.to_rpc_result(),
+            StarknetRequest::SpecVersion => self.spec_version().await.to_rpc_result(),
             StarknetRequest::PendingTransactions => {
                 self.pending_transactions().await.to_rpc_result()
             }
@@ -238,16 +347,22 @@ pub enum StarknetRequest {
     BlockWithTransactionHashes(BlockIdInput),
     #[serde(rename = "starknet_getBlockWithTxs")]
     BlockWithFullTransactions(BlockIdInput),
+    #[serde(rename = "starknet_getBlockWithReceipts")]
+    BlockWithReceipts(BlockIdInput),
     #[serde(rename = "starknet_getStateUpdate")]
     StateUpdate(BlockIdInput),
     #[serde(rename = "starknet_getStorageAt")]
     StorageAt(GetStorageInput),
+    #[serde(rename = "starknet_getStorageProof")]
+    StorageProof(GetStorageProofInput),
     #[serde(rename = "starknet_getTransactionByHash")]
     TransactionByHash(TransactionHashInput),
     #[serde(rename = "starknet_getTransactionByBlockIdAndIndex")]
     TransactionByBlockAndIndex(BlockAndIndexInput),
     #[serde(rename = "starknet_getTransactionReceipt")]
     TransactionReceiptByTransactionHash(TransactionHashInput),
+    #[serde(rename = "starknet_getTransactionStatus")]
+    TransactionStatusByHash(TransactionHashInput),
     #[serde(rename = "starknet_getClass")]
     ClassByHash(BlockAndClassHashInput),
     #[serde(rename = "starknet_getClassHashAt")]
@@ -260,12 +375,20 @@ pub enum StarknetRequest {
     Call(CallInput),
     #[serde(rename = "starknet_estimateFee")]
     EsimateFee(EstimateFeeInput),
+    #[serde(rename = "starknet_simulateTransactions")]
+    SimulateTransactions(SimulateTransactionsInput),
+    #[serde(rename = "starknet_traceTransaction")]
+    TraceTransaction(TransactionHashInput),
+    #[serde(rename = "starknet_traceBlockTransactions")]
+    TraceBlockTransactions(BlockIdInput),
     #[serde(rename = "starknet_blockNumber", with = "empty_params")]
     BlockNumber,
     #[serde(rename = "starknet_blockHashAndNumber", with = "empty_params")]
     BlockHashAndNumber,
     #[serde(rename = "starknet_chainId", with = "empty_params")]
     ChainId,
+    #[serde(rename = "starknet_specVersion", with = "empty_params")]
+    SpecVersion,
     #[serde(rename = "starknet_pendingTransactions", with = "empty_params")]
     PendingTransactions,
     #[serde(rename = "starknet_syncing", with = "empty_params")]
@@ -305,6 +428,16 @@ mod requests_tests {
         assert_deserialization_fails(json_str.replace("pending", "0x134134").as_str());
     }
 
+    #[test]
+    fn deserialize_get_block_with_receipts_request() {
+        let json_str =
+            r#"{"method":"starknet_getBlockWithReceipts","params":{"block_id":"pending"}}"#;
+        assert_deserialization_succeeds(json_str);
+        assert_deserialization_succeeds(&json_str.replace("\"pending\"", r#"{"block_number":1}"#));
+
+        assert_deserialization_fails(&json_str.replace("pending", "0x134134"));
+    }
+
     #[test]
     fn deserialize_get_state_update_request() {
         let json_str = r#"{"method":"starknet_getStateUpdate","params":{"block_id":"latest"}}"#;
@@ -321,6 +454,16 @@ mod requests_tests {
         assert_deserialization_fails(&json_str.replace("0x134134", "134134"));
     }
 
+    #[test]
+    fn deserialize_get_storage_proof_request() {
+        let json_str = r#"{"method":"starknet_getStorageProof","params":{"block_id":"latest","contract_address":"0x134134","keys":["0x1","0x2"]}}"#;
+        assert_deserialization_succeeds(json_str);
+        assert_deserialization_succeeds(&json_str.replace(r#"["0x1","0x2"]"#, "[]"));
+
+        assert_deserialization_fails(&json_str.replace("0x134134", "134134"));
+        assert_deserialization_fails(&json_str.replace(r#","keys":["0x1","0x2"]"#, ""));
+    }
+
     #[test]
     fn deserialize_get_transaction_by_hash_request() {
         let json_str = r#"{"method":"starknet_getTransactionByHash","params":{"transaction_hash":"0x134134"}}"#;
@@ -442,8 +585,18 @@ mod requests_tests {
         }"#;
 
         assert_deserialization_succeeds(json_str);
+        assert_deserialization_succeeds(
+            json_str
+                .replace(r#""block_id""#, r#""simulation_flags":["SKIP_VALIDATE"],"block_id""#)
+                .as_str(),
+        );
 
         assert_deserialization_fails(json_str.replace("estimateFee", "estimate_fee").as_str());
+        assert_deserialization_fails(
+            json_str
+                .replace(r#""block_id""#, r#""simulation_flags":["SKIP_EXECUTE"],"block_id""#)
+                .as_str(),
+        );
     }
 
     #[test]
@@ -1,16 +1,22 @@
 use serde::{Deserialize, Serialize};
+use starknet_core::starknet::estimations::FeeEstimation;
+use starknet_core::starknet::FeeUnit;
 use starknet_types::starknet_api::block::BlockNumber;
 
 use crate::api::models::block::{BlockHashHex, SyncStatus};
 use crate::api::models::transaction::{
     BroadcastedDeclareTransaction, BroadcastedDeployAccountTransaction,
     BroadcastedInvokeTransaction, BroadcastedTransactionWithType, ClassHashHex, EventFilter,
-    FunctionCall, TransactionHashHex,
+    FunctionCall, SimulationFlag, TransactionExecutionStatus, TransactionFinalityStatus,
+    TransactionHashHex,
 };
+use crate::api::models::trace::TransactionTrace;
 use crate::api::models::{BlockId, ContractAddressHex, PatriciaKeyHex};
+use crate::api::serde_helpers::block_id;
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct BlockIdInput {
+    #[serde(with = "block_id")]
     pub(crate) block_id: BlockId,
 }
 
@@ -21,25 +27,37 @@ pub struct TransactionHashInput {
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct GetStorageInput {
+    #[serde(with = "block_id")]
     pub(crate) block_id: BlockId,
     pub(crate) contract_address: ContractAddressHex,
     pub(crate) key: PatriciaKeyHex,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetStorageProofInput {
+    #[serde(with = "block_id")]
+    pub(crate) block_id: BlockId,
+    pub(crate) contract_address: ContractAddressHex,
+    pub(crate) keys: Vec<PatriciaKeyHex>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct BlockAndIndexInput {
+    #[serde(with = "block_id")]
     pub(crate) block_id: BlockId,
     pub(crate) index: BlockNumber,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct BlockAndClassHashInput {
+    #[serde(with = "block_id")]
     pub(crate) block_id: BlockId,
     pub(crate) class_hash: ClassHashHex,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct BlockAndContractAddressInput {
+    #[serde(with = "block_id")]
     pub(crate) block_id: BlockId,
     pub(crate) contract_address: ContractAddressHex,
 }
@@ -47,20 +65,55 @@ pub struct BlockAndContractAddressInput {
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct CallInput {
     pub request: FunctionCall,
+    #[serde(with = "block_id")]
     pub block_id: BlockId,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct EstimateFeeInput {
     pub request: Vec<BroadcastedTransactionWithType>,
+    #[serde(with = "block_id")]
     pub block_id: BlockId,
+    #[serde(default)]
+    pub simulation_flags: Option<Vec<SimulationFlag>>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct EstimateFeeOutput {
     pub gas_consumed: String,
     pub gas_price: String,
+    pub data_gas_consumed: String,
+    pub data_gas_price: String,
     pub overall_fee: String,
+    pub unit: FeeUnit,
+}
+
+impl From<FeeEstimation> for EstimateFeeOutput {
+    fn from(estimation: FeeEstimation) -> Self {
+        Self {
+            gas_consumed: format!("{:#x}", estimation.gas_consumed),
+            gas_price: format!("{:#x}", estimation.gas_price),
+            data_gas_consumed: format!("{:#x}", estimation.data_gas_consumed),
+            data_gas_price: format!("{:#x}", estimation.data_gas_price),
+            overall_fee: format!("{:#x}", estimation.overall_fee),
+            unit: estimation.unit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SimulateTransactionsInput {
+    #[serde(with = "block_id")]
+    pub block_id: BlockId,
+    pub transactions: Vec<BroadcastedTransactionWithType>,
+    #[serde(default)]
+    pub simulation_flags: Option<Vec<SimulationFlag>>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SimulatedTransaction {
+    pub transaction_trace: TransactionTrace,
+    pub fee_estimation: EstimateFeeOutput,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
@@ -70,6 +123,15 @@ pub struct BlockHashAndNumberOutput {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TransactionStatusOutput {
+    pub finality_status: TransactionFinalityStatus,
+    /// Absent for rejected transactions, which were not executed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_status: Option<TransactionExecutionStatus>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
 pub enum SyncingOutput {
     True(SyncStatus),
     False(bool),
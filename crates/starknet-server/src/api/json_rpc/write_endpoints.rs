@@ -2,8 +2,15 @@ use serde_json::json;
 use server::rpc_core::error::RpcError;
 use starknet_core::transactions::declare_transaction::DeclareTransactionV1;
 use starknet_core::transactions::declare_transaction_v2::DeclareTransactionV2;
+use starknet_core::transactions::declare_transaction_v3::DeclareTransactionV3;
 use starknet_core::transactions::deploy_account_transaction::DeployAccountTransaction;
+use starknet_core::transactions::deploy_account_transaction_v3::DeployAccountTransactionV3;
 use starknet_core::transactions::invoke_transaction::InvokeTransactionV1;
+use starknet_core::transactions::invoke_transaction_v3::InvokeTransactionV3;
+use starknet_core::transactions::v3::{
+    ResourceBounds as CoreResourceBounds, ResourceBoundsMapping, V3Fields,
+};
+use starknet_core::transactions::Transaction;
 use starknet_types::contract_class::ContractClass;
 use starknet_types::felt::Felt;
 
@@ -16,8 +23,11 @@ use crate::api::json_rpc::JsonRpcHandler;
 use crate::api::models::contract_class::DeprecatedContractClass;
 use crate::api::models::transaction::{
     BroadcastedDeclareTransaction, BroadcastedDeclareTransactionV1,
-    BroadcastedDeclareTransactionV2, BroadcastedDeployAccountTransaction,
-    BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV1,
+    BroadcastedDeclareTransactionV2, BroadcastedDeclareTransactionV3,
+    BroadcastedDeployAccountTransaction, BroadcastedDeployAccountTransactionV1,
+    BroadcastedDeployAccountTransactionV3, BroadcastedInvokeTransaction,
+    BroadcastedInvokeTransactionV1, BroadcastedInvokeTransactionV3, BroadcastedTransaction,
+    ResourceBounds, TransactionV3Fields,
 };
 use crate::api::models::{ContractAddressHex, FeltHex};
 
@@ -26,17 +36,50 @@ impl JsonRpcHandler {
         &self,
         request: BroadcastedDeclareTransaction,
     ) -> RpcResult<DeclareTransactionOutput> {
+        let signature = match &request {
+            BroadcastedDeclareTransaction::V1(declare_transaction) => {
+                &declare_transaction.common.signature
+            }
+            BroadcastedDeclareTransaction::V2(declare_transaction) => {
+                &declare_transaction.common.signature
+            }
+            BroadcastedDeclareTransaction::V3(declare_transaction) => {
+                &declare_transaction.common.signature
+            }
+        };
+        self.check_lengths(&[], signature).await?;
+
         let chain_id = self.api.starknet.read().await.config.chain_id.to_felt();
         let (transaction_hash, class_hash) = match request {
-            BroadcastedDeclareTransaction::V1(broadcasted_declare_txn) => {
-                self.api.starknet.write().await.add_declare_transaction_v1(
-                    (convert_to_declare_transaction_v1(*broadcasted_declare_txn, chain_id.into()))?,
-                )?
-            }
-            BroadcastedDeclareTransaction::V2(broadcasted_declare_txn) => {
-                self.api.starknet.write().await.add_declare_transaction_v2(
-                    convert_to_declare_transaction_v2(*broadcasted_declare_txn, chain_id.into())?,
-                )?
+            BroadcastedDeclareTransaction::V1(broadcasted_declare_txn) => self
+                .api
+                .starknet
+                .write()
+                .await
+                .add_declare_transaction_v1(convert_to_declare_transaction_v1(
+                    *broadcasted_declare_txn,
+                    chain_id,
+                )?)
+                .map_err(to_api_error)?,
+            BroadcastedDeclareTransaction::V2(broadcasted_declare_txn) => self
+                .api
+                .starknet
+                .write()
+                .await
+                .add_declare_transaction_v2(convert_to_declare_transaction_v2(
+                    *broadcasted_declare_txn,
+                    chain_id,
+                )?)
+                .map_err(to_api_error)?,
+            BroadcastedDeclareTransaction::V3(broadcasted_declare_txn) => {
+                let declare_transaction =
+                    convert_to_declare_transaction_v3(*broadcasted_declare_txn, chain_id)?;
+                self.api
+                    .starknet
+                    .write()
+                    .await
+                    .add_declare_transaction_v3(declare_transaction)
+                    .map_err(to_api_error)?
             }
         };
 
@@ -50,22 +93,47 @@ impl JsonRpcHandler {
         &self,
         request: BroadcastedDeployAccountTransaction,
     ) -> RpcResult<DeployAccountTransactionOutput> {
+        let (calldata, signature) = match &request {
+            BroadcastedDeployAccountTransaction::V1(deploy_account_transaction) => (
+                &deploy_account_transaction.constructor_calldata,
+                &deploy_account_transaction.common.signature,
+            ),
+            BroadcastedDeployAccountTransaction::V3(deploy_account_transaction) => (
+                &deploy_account_transaction.constructor_calldata,
+                &deploy_account_transaction.common.signature,
+            ),
+        };
+        self.check_lengths(calldata, signature).await?;
+
         let chain_id = self.api.starknet.read().await.config.chain_id.to_felt();
-        let (transaction_hash, contract_address) = self
-            .api
-            .starknet
-            .write()
-            .await
-            .add_deploy_account_transaction(convert_to_deploy_account_transaction(
-                request,
-                chain_id.into(),
-            )?)
-            .map_err(|err| match err {
-                starknet_core::error::Error::StateError(
-                    starknet_in_rust::core::errors::state_errors::StateError::MissingClassHash(),
-                ) => ApiError::ClassHashNotFound,
-                unknown_error => ApiError::StarknetDevnetError(unknown_error),
-            })?;
+        let result = match request {
+            BroadcastedDeployAccountTransaction::V1(deploy_account_transaction) => {
+                let deploy_account_transaction =
+                    convert_to_deploy_account_transaction(deploy_account_transaction, chain_id)?;
+                self.api
+                    .starknet
+                    .write()
+                    .await
+                    .add_deploy_account_transaction(deploy_account_transaction)
+            }
+            BroadcastedDeployAccountTransaction::V3(deploy_account_transaction) => {
+                let deploy_account_transaction = convert_to_deploy_account_transaction_v3(
+                    *deploy_account_transaction,
+                    chain_id,
+                )?;
+                self.api
+                    .starknet
+                    .write()
+                    .await
+                    .add_deploy_account_transaction_v3(deploy_account_transaction)
+            }
+        };
+        let (transaction_hash, contract_address) = result.map_err(|err| match err {
+            starknet_core::error::Error::StateError(
+                starknet_in_rust::core::errors::state_errors::StateError::MissingClassHash(),
+            ) => ApiError::ClassHashNotFound,
+            other_error => to_api_error(other_error),
+        })?;
 
         Ok(DeployAccountTransactionOutput {
             transaction_hash: FeltHex(transaction_hash),
@@ -77,22 +145,101 @@ impl JsonRpcHandler {
         &self,
         request: BroadcastedInvokeTransaction,
     ) -> RpcResult<InvokeTransactionOutput> {
+        let (calldata, signature) = match &request {
+            BroadcastedInvokeTransaction::V0(invoke_transaction) => {
+                (&invoke_transaction.calldata, &invoke_transaction.common.signature)
+            }
+            BroadcastedInvokeTransaction::V1(invoke_transaction) => {
+                (&invoke_transaction.calldata, &invoke_transaction.common.signature)
+            }
+            BroadcastedInvokeTransaction::V3(invoke_transaction) => {
+                (&invoke_transaction.calldata, &invoke_transaction.common.signature)
+            }
+        };
+        self.check_lengths(calldata, signature).await?;
+
         let hash = match request {
             BroadcastedInvokeTransaction::V0(_) => Err(ApiError::UnsupportedVersion),
             BroadcastedInvokeTransaction::V1(invoke_transaction) => {
-                let chain_id: Felt =
-                    self.api.starknet.read().await.config.chain_id.to_felt().into();
+                let chain_id = self.api.starknet.read().await.config.chain_id.to_felt();
                 let invoke_request =
                     convert_to_invoke_transaction_v1(invoke_transaction, chain_id)?;
-                let res =
-                    self.api.starknet.write().await.add_invoke_transaction_v1(invoke_request)?;
+                let mut starknet = self.api.starknet.write().await;
+                starknet
+                    .fund_impersonated_sender(&Transaction::Invoke(Box::new(
+                        invoke_request.clone(),
+                    )))
+                    .await?;
+                let res = starknet.add_invoke_transaction_v1(invoke_request)?;
 
                 Ok(res)
             }
+            BroadcastedInvokeTransaction::V3(invoke_transaction) => {
+                let chain_id = self.api.starknet.read().await.config.chain_id.to_felt();
+                let invoke_transaction =
+                    convert_to_invoke_transaction_v3(invoke_transaction, chain_id)?;
+
+                let mut starknet = self.api.starknet.write().await;
+                starknet
+                    .fund_impersonated_sender(&Transaction::InvokeV3(Box::new(
+                        invoke_transaction.clone(),
+                    )))
+                    .await
+                    .map_err(to_api_error)?;
+                starknet.add_invoke_transaction_v3(invoke_transaction).map_err(to_api_error)
+            }
         }?;
 
         Ok(InvokeTransactionOutput { transaction_hash: FeltHex(hash) })
     }
+
+    /// Rejects a transaction whose calldata or signature is longer than allowed by
+    /// `max_calldata_len` and `max_signature_len`, before it's converted and executed
+    async fn check_lengths(&self, calldata: &[FeltHex], signature: &[FeltHex]) -> RpcResult<()> {
+        let starknet = self.api.starknet.read().await;
+        if calldata.len() > starknet.config.max_calldata_len {
+            return Err(ApiError::TransactionTooLong {
+                field: "calldata",
+                length: calldata.len(),
+                max_length: starknet.config.max_calldata_len,
+            });
+        }
+        if signature.len() > starknet.config.max_signature_len {
+            return Err(ApiError::TransactionTooLong {
+                field: "signature",
+                length: signature.len(),
+                max_length: starknet.config.max_signature_len,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Maps errors of adding a transaction, distinguishing transactions rejected for their resource
+/// bounds
+fn to_api_error(err: starknet_core::error::Error) -> ApiError {
+    match err {
+        starknet_core::error::Error::InsufficientResources { msg: _ } => {
+            ApiError::InsufficientResources
+        }
+        starknet_core::error::Error::ClassAlreadyDeclared { class_hash } => {
+            ApiError::ClassAlreadyDeclared { class_hash }
+        }
+        starknet_core::error::Error::CompiledClassHashMismatch { expected, provided } => {
+            ApiError::CompiledClassHashMismatch { expected, provided }
+        }
+        starknet_core::error::Error::ContractAlreadyDeployed { contract_address } => {
+            ApiError::ContractAlreadyDeployed { contract_address }
+        }
+        starknet_core::error::Error::InvalidTransactionNonce { .. } => {
+            ApiError::InvalidTransactionNonce
+        }
+        starknet_core::error::Error::UnsupportedSierraVersion { version } => {
+            ApiError::UnsupportedContractClassVersion { version }
+        }
+        unknown_error => ApiError::StarknetDevnetError(unknown_error),
+    }
 }
 
 impl TryFrom<DeprecatedContractClass> for ContractClass {
@@ -118,6 +265,60 @@ impl TryFrom<DeprecatedContractClass> for ContractClass {
     }
 }
 
+/// Converts a broadcasted transaction of any type into one executable by Devnet
+pub(crate) fn convert_to_transaction(
+    value: BroadcastedTransaction,
+    chain_id: Felt,
+) -> RpcResult<Transaction> {
+    match value {
+        BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V0(_)) => {
+            Err(ApiError::UnsupportedVersion)
+        }
+        BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(invoke_transaction)) => {
+            Ok(Transaction::Invoke(Box::new(convert_to_invoke_transaction_v1(
+                invoke_transaction,
+                chain_id,
+            )?)))
+        }
+        BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V3(invoke_transaction)) => {
+            Ok(Transaction::InvokeV3(Box::new(convert_to_invoke_transaction_v3(
+                invoke_transaction,
+                chain_id,
+            )?)))
+        }
+        BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V1(declare_transaction)) => {
+            Ok(Transaction::Declare(Box::new(convert_to_declare_transaction_v1(
+                *declare_transaction,
+                chain_id,
+            )?)))
+        }
+        BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V2(declare_transaction)) => {
+            Ok(Transaction::DeclareV2(Box::new(convert_to_declare_transaction_v2(
+                *declare_transaction,
+                chain_id,
+            )?)))
+        }
+        BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V3(declare_transaction)) => {
+            Ok(Transaction::DeclareV3(Box::new(convert_to_declare_transaction_v3(
+                *declare_transaction,
+                chain_id,
+            )?)))
+        }
+        BroadcastedTransaction::DeployAccount(BroadcastedDeployAccountTransaction::V1(
+            deploy_account_transaction,
+        )) => Ok(Transaction::DeployAccount(Box::new(convert_to_deploy_account_transaction(
+            deploy_account_transaction,
+            chain_id,
+        )?))),
+        BroadcastedTransaction::DeployAccount(BroadcastedDeployAccountTransaction::V3(
+            deploy_account_transaction,
+        )) => Ok(Transaction::DeployAccountV3(Box::new(convert_to_deploy_account_transaction_v3(
+            *deploy_account_transaction,
+            chain_id,
+        )?))),
+    }
+}
+
 fn convert_to_declare_transaction_v1(
     value: BroadcastedDeclareTransactionV1,
     chain_id: Felt,
@@ -134,7 +335,7 @@ fn convert_to_declare_transaction_v1(
 }
 
 fn convert_to_deploy_account_transaction(
-    broadcasted_txn: BroadcastedDeployAccountTransaction,
+    broadcasted_txn: BroadcastedDeployAccountTransactionV1,
     chain_id: Felt,
 ) -> RpcResult<DeployAccountTransaction> {
     DeployAccountTransaction::new(
@@ -155,6 +356,66 @@ fn convert_to_deploy_account_transaction(
     })
 }
 
+fn convert_to_deploy_account_transaction_v3(
+    broadcasted_txn: BroadcastedDeployAccountTransactionV3,
+    chain_id: Felt,
+) -> RpcResult<DeployAccountTransactionV3> {
+    DeployAccountTransactionV3::new(
+        broadcasted_txn.constructor_calldata.iter().map(|felt_hex| felt_hex.0).collect(),
+        broadcasted_txn.common.signature.iter().map(|felt_hex| felt_hex.0).collect(),
+        broadcasted_txn.common.nonce.0,
+        broadcasted_txn.class_hash.0,
+        broadcasted_txn.contract_address_salt.0,
+        chain_id,
+        convert_to_v3_fields(&broadcasted_txn.common.v3_fields)?,
+    )
+    .map_err(|err| {
+        ApiError::RpcError(RpcError::invalid_params(format!(
+            "Unable to create DeployAccountTransactionV3: {}",
+            err
+        )))
+    })
+}
+
+/// Converts the fields of a transaction of version 3, checking that the resource bounds and the
+/// tip fit in their types
+fn convert_to_v3_fields(v3_fields: &TransactionV3Fields) -> RpcResult<V3Fields> {
+    let resource_bounds = |bounds: &ResourceBounds, name: &str| -> RpcResult<CoreResourceBounds> {
+        Ok(CoreResourceBounds {
+            max_amount: felt_to_uint(&bounds.max_amount, &format!("{name}.max_amount"))?,
+            max_price_per_unit: felt_to_uint(
+                &bounds.max_price_per_unit,
+                &format!("{name}.max_price_per_unit"),
+            )?,
+        })
+    };
+
+    Ok(V3Fields {
+        resource_bounds: ResourceBoundsMapping {
+            l1_gas: resource_bounds(&v3_fields.resource_bounds.l1_gas, "l1_gas")?,
+            l2_gas: resource_bounds(&v3_fields.resource_bounds.l2_gas, "l2_gas")?,
+        },
+        tip: felt_to_uint(&v3_fields.tip, "tip")?,
+        paymaster_data: v3_fields.paymaster_data.iter().map(|felt_hex| felt_hex.0).collect(),
+        nonce_data_availability_mode: v3_fields.nonce_data_availability_mode,
+        fee_data_availability_mode: v3_fields.fee_data_availability_mode,
+    })
+}
+
+/// Converts `value` to an unsigned integer, failing if it doesn't fit in it
+fn felt_to_uint<T: TryFrom<u128>>(value: &FeltHex, name: &str) -> RpcResult<T> {
+    let bytes = value.0.bytes();
+    let out_of_range =
+        || ApiError::RpcError(RpcError::invalid_params(format!("{name}: value out of range")));
+    if bytes[..16].iter().any(|byte| *byte != 0) {
+        return Err(out_of_range());
+    }
+
+    let mut low_bytes = [0u8; 16];
+    low_bytes.copy_from_slice(&bytes[16..]);
+    T::try_from(u128::from_be_bytes(low_bytes)).map_err(|_| out_of_range())
+}
+
 fn convert_to_declare_transaction_v2(
     value: BroadcastedDeclareTransactionV2,
     chain_id: Felt,
@@ -171,6 +432,23 @@ fn convert_to_declare_transaction_v2(
     .map_err(ApiError::StarknetDevnetError)
 }
 
+fn convert_to_declare_transaction_v3(
+    value: BroadcastedDeclareTransactionV3,
+    chain_id: Felt,
+) -> RpcResult<DeclareTransactionV3> {
+    DeclareTransactionV3::new(
+        ContractClass::from(value.contract_class),
+        value.compiled_class_hash.0,
+        value.sender_address.0,
+        value.common.signature.iter().map(|x| x.0).collect(),
+        value.common.nonce.0,
+        chain_id,
+        convert_to_v3_fields(&value.common.v3_fields)?,
+        value.account_deployment_data.iter().map(|x| x.0).collect(),
+    )
+    .map_err(ApiError::StarknetDevnetError)
+}
+
 fn convert_to_invoke_transaction_v1(
     value: BroadcastedInvokeTransactionV1,
     chain_id: Felt,
@@ -185,20 +463,47 @@ fn convert_to_invoke_transaction_v1(
     )
     .map_err(ApiError::StarknetDevnetError)
 }
+
+fn convert_to_invoke_transaction_v3(
+    value: BroadcastedInvokeTransactionV3,
+    chain_id: Felt,
+) -> RpcResult<InvokeTransactionV3> {
+    InvokeTransactionV3::new(
+        value.sender_address.0,
+        value.common.signature.iter().map(|felt_hex| felt_hex.0).collect(),
+        value.common.nonce.0,
+        value.calldata.iter().map(|felt_hex| felt_hex.0).collect(),
+        chain_id,
+        convert_to_v3_fields(&value.common.v3_fields)?,
+        value.account_deployment_data.iter().map(|felt_hex| felt_hex.0).collect(),
+    )
+    .map_err(ApiError::StarknetDevnetError)
+}
+
 #[cfg(test)]
 mod tests {
     use starknet_core::constants::{
-        DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_GAS_PRICE, DEVNET_DEFAULT_HOST,
-        DEVNET_DEFAULT_INITIAL_BALANCE, DEVNET_DEFAULT_PORT, DEVNET_DEFAULT_TEST_SEED,
+        DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_DATA_GAS_PRICE, DEVNET_DEFAULT_ETH_STRK_RATE,
+        DEVNET_DEFAULT_HOST, DEVNET_DEFAULT_INITIAL_BALANCE, DEVNET_DEFAULT_MAX_CALLDATA_LEN,
+        DEVNET_DEFAULT_MAX_SIGNATURE_LEN, DEVNET_DEFAULT_MAX_STEPS, DEVNET_DEFAULT_PORT,
+        DEVNET_DEFAULT_REQUEST_BODY_LIMIT, DEVNET_DEFAULT_RPC_PATH, DEVNET_DEFAULT_TEST_SEED,
         DEVNET_DEFAULT_TIMEOUT, DEVNET_DEFAULT_TOTAL_ACCOUNTS,
+        DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL, ERC20_DECIMALS, ETH_ERC20_NAME,
+        ETH_ERC20_SYMBOL, STRK_ERC20_NAME, STRK_ERC20_SYMBOL,
     };
-    use starknet_core::starknet::{Starknet, StarknetConfig};
+    use starknet_core::starknet::{
+        default_sequencer_address, AccountClass, BlockGenerationOn, CalldataValidation, FeeUnit,
+        PendingOverflow, Starknet, StarknetConfig, UndeployedStorageWrite,
+    };
+    use starknet_types::felt::Felt;
     use starknet_types::traits::ToHexString;
 
     use crate::api::json_rpc::JsonRpcHandler;
     use crate::api::models::transaction::{
         BroadcastedDeclareTransactionV1, BroadcastedDeployAccountTransaction,
+        BroadcastedInvokeTransaction,
     };
+    use crate::api::models::FeltHex;
     use crate::api::Api;
 
     #[tokio::test]
@@ -225,7 +530,90 @@ mod tests {
 
     #[test]
     fn check_correct_deserialization_of_deploy_account_transaction_request() {
-        test_deploy_account_transaction();
+        assert!(matches!(
+            test_deploy_account_transaction(),
+            BroadcastedDeployAccountTransaction::V1(_)
+        ));
+    }
+
+    #[test]
+    fn deploy_account_transaction_v3_request_deserialized_and_converted() {
+        let json_string = r#"{
+            "type": "DEPLOY_ACCOUNT",
+            "version": "0x3",
+            "signature": [],
+            "nonce": "0x0",
+            "contract_address_salt": "0xd",
+            "constructor_calldata": ["0x1"],
+            "class_hash": "0x1fac3074c9d5282f0acc5c69a4781a1c711efea5e73c550c5d9fb253cf7fd3d",
+            "resource_bounds": {
+                "l1_gas": { "max_amount": "0x3e8", "max_price_per_unit": "0x5" },
+                "l2_gas": { "max_amount": "0x0", "max_price_per_unit": "0x0" }
+            },
+            "tip": "0x0",
+            "paymaster_data": [],
+            "nonce_data_availability_mode": "L1",
+            "fee_data_availability_mode": "L1"
+        }"#;
+
+        let deploy_account_transaction = match serde_json::from_str(json_string).unwrap() {
+            BroadcastedDeployAccountTransaction::V3(deploy_account_transaction) => {
+                *deploy_account_transaction
+            }
+            other => panic!("Expected a transaction of version 3, got {other:?}"),
+        };
+
+        let converted = super::convert_to_deploy_account_transaction_v3(
+            deploy_account_transaction,
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap();
+        assert_eq!(converted.max_fee(), 5000);
+        assert_eq!(converted.v3_fields().resource_bounds.l1_gas.max_amount, 1000);
+    }
+
+    #[test]
+    fn invoke_transaction_v3_request_deserialized_and_converted() {
+        let json_string = r#"{
+            "type": "INVOKE",
+            "version": "0x3",
+            "signature": [],
+            "nonce": "0x0",
+            "sender_address": "0x1",
+            "calldata": ["0x2"],
+            "resource_bounds": {
+                "l1_gas": { "max_amount": "0x64", "max_price_per_unit": "0x2" },
+                "l2_gas": { "max_amount": "0x0", "max_price_per_unit": "0x0" }
+            },
+            "tip": "0x0",
+            "paymaster_data": [],
+            "account_deployment_data": [],
+            "nonce_data_availability_mode": "L1",
+            "fee_data_availability_mode": "L1"
+        }"#;
+
+        let invoke_transaction = match serde_json::from_str(json_string).unwrap() {
+            BroadcastedInvokeTransaction::V3(invoke_transaction) => invoke_transaction,
+            other => panic!("Expected a transaction of version 3, got {other:?}"),
+        };
+
+        let converted = super::convert_to_invoke_transaction_v3(
+            invoke_transaction,
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap();
+        assert_eq!(converted.max_fee(), 200);
+        assert_eq!(converted.calldata(), &vec![Felt::from(2)]);
+    }
+
+    #[test]
+    fn resource_bound_exceeding_its_type_rejected() {
+        let too_large_amount = FeltHex(Felt::from(u64::MAX as u128 + 1));
+        assert!(super::felt_to_uint::<u64>(&too_large_amount, "max_amount").is_err());
+        assert_eq!(
+            super::felt_to_uint::<u128>(&too_large_amount, "max_amount").unwrap(),
+            u64::MAX as u128 + 1
+        );
     }
 
     fn setup() -> JsonRpcHandler {
@@ -233,11 +621,65 @@ mod tests {
             seed: DEVNET_DEFAULT_TEST_SEED,
             total_accounts: DEVNET_DEFAULT_TOTAL_ACCOUNTS,
             predeployed_accounts_initial_balance: DEVNET_DEFAULT_INITIAL_BALANCE.into(),
+            predeployed_accounts_path: None,
+            no_auto_accounts: false,
             host: DEVNET_DEFAULT_HOST.into(),
             port: DEVNET_DEFAULT_PORT,
+            port_file: None,
+            rpc_path: DEVNET_DEFAULT_RPC_PATH.to_string(),
+            devnet_path: String::new(),
             timeout: DEVNET_DEFAULT_TIMEOUT,
-            gas_price: DEVNET_DEFAULT_GAS_PRICE,
+            request_body_limit: DEVNET_DEFAULT_REQUEST_BODY_LIMIT,
+            rate_limit: None,
+            transaction_subscription_ttl: DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL,
+            // the sender of the example declaration is not funded on Devnet
+            gas_price: 0,
+            data_gas_price: DEVNET_DEFAULT_DATA_GAS_PRICE,
+            eth_strk_rate: DEVNET_DEFAULT_ETH_STRK_RATE,
+            max_steps: DEVNET_DEFAULT_MAX_STEPS,
+            max_calldata_len: DEVNET_DEFAULT_MAX_CALLDATA_LEN,
+            max_signature_len: DEVNET_DEFAULT_MAX_SIGNATURE_LEN,
             chain_id: DEVNET_DEFAULT_CHAIN_ID,
+            initial_block_number: 0,
+            initial_block_timestamp: None,
+            fake_sync_from: None,
+            fake_sync_until: None,
+            dump_on: None,
+            dump_path: None,
+            commit_on_shutdown: false,
+            state_dir: None,
+            fork_network: None,
+            fork_block: None,
+            auto_impersonate: false,
+            impersonate_auto_fund: None,
+            disable_fee_charge: false,
+            estimate_skip_validate: false,
+            allow_dev_declare: false,
+            allow_dev_deploy: false,
+            allow_dev_state: false,
+            undeployed_storage_write: UndeployedStorageWrite::Warn,
+            calldata_validation: CalldataValidation::Strict,
+            predeclare: None,
+            lite_mode: false,
+            block_generation_on: BlockGenerationOn::Transaction,
+            block_time_secs: 0,
+            parallel_execution: false,
+            max_pending_transactions: None,
+            pending_overflow: PendingOverflow::Seal,
+            account_class: AccountClass::Cairo0,
+            account_class_custom: None,
+            hide_predeployed_keys: false,
+            l1_node_url: None,
+            eth_fee_token_address: FeeUnit::Wei.default_erc20_address(),
+            strk_fee_token_address: FeeUnit::Fri.default_erc20_address(),
+            eth_token_name: ETH_ERC20_NAME.to_string(),
+            eth_token_symbol: ETH_ERC20_SYMBOL.to_string(),
+            eth_token_decimals: ERC20_DECIMALS,
+            strk_token_name: STRK_ERC20_NAME.to_string(),
+            strk_token_symbol: STRK_ERC20_SYMBOL.to_string(),
+            strk_token_decimals: ERC20_DECIMALS,
+            sequencer_address: default_sequencer_address(),
+            enable_metrics: false,
         };
         let starknet = Starknet::new(&config).unwrap();
         let api = Api::new(starknet);
@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use hyper::client::HttpConnector;
+use hyper::{Body, Client, Request};
+use serde_json::json;
+use starknet_core::starknet::messaging::MessageToL1;
+use starknet_types::felt::Felt;
+use thiserror::Error;
+use url::Url;
+
+/// Selector of `mockSendMessageFromL2(uint256,uint256,uint256[])` of the mock Starknet messaging
+/// contract: the first 4 bytes of the keccak256 of the signature
+const MOCK_SEND_MESSAGE_FROM_L2_SELECTOR: &str = "d1fb1509";
+
+#[derive(Error, Debug)]
+pub enum L1Error {
+    #[error("Request to the L1 node timed out")]
+    Timeout,
+    #[error("L1 node error: {msg}")]
+    Upstream { msg: String },
+}
+
+/// Client of the JSON-RPC node of L1. Messages sent to L1 are submitted to it through the mock
+/// Starknet messaging contract, from the first account of the node.
+pub struct L1Client {
+    client: Client<HttpConnector>,
+    url: Url,
+    timeout: Duration,
+}
+
+impl L1Client {
+    pub fn new(url: Url, timeout: Duration) -> Self {
+        Self { client: Client::new(), url, timeout }
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Submits `messages` in order to the messaging contract at `messaging_contract_address`
+    pub async fn send_messages_to_l1(
+        &self,
+        messaging_contract_address: Felt,
+        messages: &[MessageToL1],
+    ) -> Result<(), L1Error> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+
+        let accounts = self.request("eth_accounts", json!([])).await?;
+        let sender = accounts
+            .get(0)
+            .and_then(|account| account.as_str())
+            .ok_or(L1Error::Upstream { msg: "The L1 node has no accounts".to_string() })?
+            .to_string();
+
+        for message in messages {
+            let transaction = json!({
+                "from": sender,
+                "to": eth_address(&messaging_contract_address),
+                "data": mock_send_message_from_l2_calldata(message),
+            });
+            self.request("eth_sendTransaction", json!([transaction])).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, L1Error> {
+        let upstream_error =
+            |err: &dyn std::error::Error| L1Error::Upstream { msg: err.to_string() };
+
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let request = Request::post(self.url.as_str())
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .map_err(|err| upstream_error(&err))?;
+
+        let response = match tokio::time::timeout(self.timeout, self.client.request(request)).await
+        {
+            Ok(response) => response.map_err(|err| upstream_error(&err))?,
+            Err(_) => return Err(L1Error::Timeout),
+        };
+        let bytes = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|err| upstream_error(&err))?;
+        let mut response_body: serde_json::Value =
+            serde_json::from_slice(&bytes).map_err(|err| upstream_error(&err))?;
+
+        if let Some(error) = response_body.get("error") {
+            return Err(L1Error::Upstream { msg: error.to_string() });
+        }
+
+        Ok(response_body["result"].take())
+    }
+}
+
+/// The 20 lowest bytes of `felt` as a 0x-prefixed hex string
+fn eth_address(felt: &Felt) -> String {
+    format!("0x{}", hex_of(&felt.bytes()[12..]))
+}
+
+/// ABI-encoded call of `mockSendMessageFromL2(from_address, to_address, payload)`
+fn mock_send_message_from_l2_calldata(message: &MessageToL1) -> String {
+    let mut words = vec![
+        Felt::from(message.from_address),
+        message.to_address,
+        // offset of the dynamic payload, following the three head words
+        Felt::from(3 * 32),
+        Felt::from(message.payload.len() as u128),
+    ];
+    words.extend(message.payload.iter().copied());
+
+    let encoded_words: String = words.iter().map(|word| hex_of(&word.bytes())).collect();
+    format!("0x{MOCK_SEND_MESSAGE_FROM_L2_SELECTOR}{encoded_words}")
+}
+
+fn hex_of(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_core::starknet::messaging::MessageToL1;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+
+    use super::{eth_address, mock_send_message_from_l2_calldata};
+
+    #[test]
+    fn eth_address_has_20_bytes() {
+        assert_eq!(eth_address(&Felt::from(0xE7)), format!("0x{:040x}", 0xE7));
+    }
+
+    #[test]
+    fn message_encoded_as_call_of_mock_messaging_contract() {
+        let message = MessageToL1 {
+            from_address: ContractAddress::new(Felt::from(1)).unwrap(),
+            to_address: Felt::from(2),
+            payload: vec![Felt::from(3), Felt::from(4)],
+        };
+
+        let expected_words: String =
+            [1, 2, 0x60, 2, 3, 4].iter().map(|word| format!("{word:064x}")).collect();
+        assert_eq!(
+            mock_send_message_from_l2_calldata(&message),
+            format!("0xd1fb1509{expected_words}")
+        );
+    }
+}
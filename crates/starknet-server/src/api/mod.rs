@@ -1,24 +1,50 @@
+pub(crate) mod fork;
 pub(crate) mod http;
 pub(crate) mod json_rpc;
+pub(crate) mod l1;
 pub(crate) mod models;
 pub(crate) mod serde_helpers;
 pub(crate) mod utils;
+pub(crate) mod ws;
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use starknet_core::starknet::Starknet;
 use tokio::sync::RwLock;
 
+use self::fork::ForkClient;
+use self::l1::L1Client;
+
 /// Data that can be shared between threads with read write lock access
 /// Whatever needs to be accessed as information outside of Starknet could be added to this struct
 #[derive(Clone)]
 pub struct Api {
     // maybe the config should be added here next to the starknet instance
     pub starknet: Arc<RwLock<Starknet>>,
+    /// Network Devnet is forked from, if any; its state is read through the Starknet instance
+    pub fork: Option<Arc<ForkClient>>,
+    /// Node to which messages sent to L1 are submitted when flushed
+    pub l1: Option<Arc<L1Client>>,
+    /// When Devnet started serving, for reporting its uptime
+    pub started_at: Instant,
 }
 
 impl Api {
     pub fn new(starknet: Starknet) -> Self {
-        Self { starknet: Arc::new(RwLock::new(starknet)) }
+        Self {
+            starknet: Arc::new(RwLock::new(starknet)),
+            fork: None,
+            l1: None,
+            started_at: Instant::now(),
+        }
+    }
+
+    pub fn with_fork(starknet: Starknet, fork: Arc<ForkClient>) -> Self {
+        Self { fork: Some(fork), ..Self::new(starknet) }
+    }
+
+    pub fn with_l1(self, l1: L1Client) -> Self {
+        Self { l1: Some(Arc::new(l1)), ..self }
     }
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use starknet_types::starknet_api::block::{BlockNumber, BlockStatus, BlockTimestamp};
 
-use super::transaction::Transactions;
+use super::transaction::{TransactionWithReceipt, Transactions};
 use super::{ContractAddressHex, FeltHex};
 
 pub type BlockHashHex = FeltHex;
@@ -15,6 +15,30 @@ pub struct Block {
     pub transactions: Transactions,
 }
 
+/// Block the next transactions are added to. Its transactions are not committed yet, so it has no
+/// hash, number nor root.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PendingBlock {
+    #[serde(flatten)]
+    pub header: PendingBlockHeader,
+    pub transactions: Transactions,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum MaybePendingBlock {
+    Block(Block),
+    PendingBlock(PendingBlock),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BlockWithReceipts {
+    pub status: BlockStatus,
+    #[serde(flatten)]
+    pub header: BlockHeader,
+    pub transactions: Vec<TransactionWithReceipt>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct BlockHeader {
     pub block_hash: BlockHashHex,
@@ -23,6 +47,25 @@ pub struct BlockHeader {
     pub sequencer_address: ContractAddressHex,
     pub new_root: GlobalRootHex,
     pub timestamp: BlockTimestamp,
+    pub l1_gas_price: ResourcePrice,
+    pub l1_data_gas_price: ResourcePrice,
+    pub transaction_commitment: FeltHex,
+    pub event_commitment: FeltHex,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PendingBlockHeader {
+    pub parent_hash: BlockHashHex,
+    pub sequencer_address: ContractAddressHex,
+    pub timestamp: BlockTimestamp,
+    pub l1_gas_price: ResourcePrice,
+    pub l1_data_gas_price: ResourcePrice,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ResourcePrice {
+    pub price_in_wei: FeltHex,
+    pub price_in_fri: FeltHex,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
@@ -44,6 +87,38 @@ impl From<&starknet_core::StarknetBlock> for BlockHeader {
             sequencer_address: ContractAddressHex(value.sequencer_address()),
             new_root: FeltHex(value.new_root()),
             timestamp: value.timestamp(),
+            l1_gas_price: ResourcePrice::l1_gas_price_of(value),
+            l1_data_gas_price: ResourcePrice::l1_data_gas_price_of(value),
+            transaction_commitment: FeltHex(value.transaction_commitment()),
+            event_commitment: FeltHex(value.event_commitment()),
+        }
+    }
+}
+
+impl From<&starknet_core::StarknetBlock> for PendingBlockHeader {
+    fn from(value: &starknet_core::StarknetBlock) -> Self {
+        Self {
+            parent_hash: FeltHex(value.parent_hash()),
+            sequencer_address: ContractAddressHex(value.sequencer_address()),
+            timestamp: value.timestamp(),
+            l1_gas_price: ResourcePrice::l1_gas_price_of(value),
+            l1_data_gas_price: ResourcePrice::l1_data_gas_price_of(value),
+        }
+    }
+}
+
+impl ResourcePrice {
+    fn l1_gas_price_of(block: &starknet_core::StarknetBlock) -> Self {
+        Self {
+            price_in_wei: FeltHex(block.l1_gas_price().0.into()),
+            price_in_fri: FeltHex(block.l1_gas_price_in_fri().0.into()),
+        }
+    }
+
+    fn l1_data_gas_price_of(block: &starknet_core::StarknetBlock) -> Self {
+        Self {
+            price_in_wei: FeltHex(block.l1_data_gas_price().0.into()),
+            price_in_fri: FeltHex(block.l1_data_gas_price_in_fri().0.into()),
         }
     }
 }
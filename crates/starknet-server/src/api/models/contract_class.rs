@@ -4,13 +4,25 @@ use serde::{Deserialize, Serialize};
 
 use super::abi_entry::{AbiEntry, AbiEntryType};
 use super::FeltHex;
-use crate::api::serde_helpers::base_64_gzipped_json_string::deserialize_to_serde_json_value_with_keys_ordered_in_alphabetical_order;
+use crate::api::serde_helpers::base_64_gzipped_json_string::{
+    deserialize_to_serde_json_value_with_keys_ordered_in_alphabetical_order,
+    serialize_to_base_64_gzipped_json_string,
+};
+use crate::api::serde_helpers::rpc_sierra_contract_class_to_sierra_contract_class::{
+    deserialize_to_sierra_contract_class, serialize_to_rpc_sierra_contract_class,
+};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum ContractClass {
     Cairo0(DeprecatedContractClass),
-    Sierra(starknet_in_rust::SierraContractClass),
+    Sierra(
+        #[serde(
+            deserialize_with = "deserialize_to_sierra_contract_class",
+            serialize_with = "serialize_to_rpc_sierra_contract_class"
+        )]
+        starknet_in_rust::SierraContractClass,
+    ),
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
@@ -29,7 +41,8 @@ pub struct DeprecatedContractClass {
     pub abi: Vec<ContractClassAbiEntryWithType>,
     /// A base64 encoding of the gzip-compressed JSON representation of program.
     #[serde(
-        deserialize_with = "deserialize_to_serde_json_value_with_keys_ordered_in_alphabetical_order"
+        deserialize_with = "deserialize_to_serde_json_value_with_keys_ordered_in_alphabetical_order",
+        serialize_with = "serialize_to_base_64_gzipped_json_string"
     )]
     pub program: serde_json::Value,
     /// The selector of each entry point is a unique identifier in the program.
@@ -2,6 +2,7 @@ pub(crate) mod abi_entry;
 pub(crate) mod block;
 pub(crate) mod contract_class;
 pub(crate) mod state;
+pub(crate) mod trace;
 pub(crate) mod transaction;
 
 use serde::{Deserialize, Serialize};
@@ -35,8 +36,8 @@ pub enum BlockHashOrNumber {
     Number(BlockNumber),
 }
 
-#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(untagged)]
+/// (De)serialized with `serde_helpers::block_id`, so that all methods accept the same forms
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum BlockId {
     HashOrNumber(BlockHashOrNumber),
     Tag(Tag),
@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use starknet_core::starknet::storage_proof::{
+    ProofNode as CoreProofNode, StorageProof as CoreStorageProof,
+};
 
 use super::block::{BlockHashHex, GlobalRootHex};
 use super::transaction::{ClassHashHex, Nonce};
@@ -14,6 +17,19 @@ pub struct StateUpdate {
     pub state_diff: ThinStateDiff,
 }
 
+/// State update of the pending block, whose changes are not committed yet
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PendingStateUpdate {
+    pub state_diff: ThinStateDiff,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum MaybePendingStateUpdate {
+    Update(StateUpdate),
+    PendingUpdate(PendingStateUpdate),
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct ThinStateDiff {
     pub deployed_contracts: Vec<DeployedContract>,
@@ -64,3 +80,60 @@ pub struct ContractNonce {
     pub contract_address: ContractAddressHex,
     pub nonce: Nonce,
 }
+
+/// A node of a Merkle-Patricia trie proof
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum MerkleNode {
+    Binary { left: FeltHex, right: FeltHex },
+    Edge { child: FeltHex, path: FeltHex, length: u8 },
+}
+
+impl From<CoreProofNode> for MerkleNode {
+    fn from(node: CoreProofNode) -> Self {
+        match node {
+            CoreProofNode::Binary { left, right } => {
+                MerkleNode::Binary { left: FeltHex(left), right: FeltHex(right) }
+            }
+            CoreProofNode::Edge { child, path, length } => {
+                MerkleNode::Edge { child: FeltHex(child), path: FeltHex(path), length }
+            }
+        }
+    }
+}
+
+/// Proof of the contract in the global contracts trie and of the requested keys in its storage
+/// trie. Paths are ordered from the root towards the leaf.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct StorageProof {
+    pub contracts_tree_root: GlobalRootHex,
+    pub contract_proof: Vec<MerkleNode>,
+    pub contract_data: ContractData,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ContractData {
+    pub class_hash: ClassHashHex,
+    pub nonce: Nonce,
+    pub root: FeltHex,
+    pub storage_proofs: Vec<Vec<MerkleNode>>,
+}
+
+impl From<CoreStorageProof> for StorageProof {
+    fn from(proof: CoreStorageProof) -> Self {
+        Self {
+            contracts_tree_root: FeltHex(proof.contracts_tree_root),
+            contract_proof: proof.contract_proof.into_iter().map(MerkleNode::from).collect(),
+            contract_data: ContractData {
+                class_hash: FeltHex(proof.class_hash),
+                nonce: FeltHex(proof.nonce),
+                root: FeltHex(proof.contract_storage_root),
+                storage_proofs: proof
+                    .storage_proofs
+                    .into_iter()
+                    .map(|path| path.into_iter().map(MerkleNode::from).collect())
+                    .collect(),
+            },
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use starknet_core::traces::{
+    CallType as CoreCallType, FunctionInvocation as CoreFunctionInvocation,
+    OrderedEvent as CoreOrderedEvent, TransactionTrace as CoreTransactionTrace,
+};
+use starknet_in_rust::EntryPointType as CoreEntryPointType;
+
+use super::transaction::{
+    Calldata, ClassHashHex, EntryPointSelectorHex, EventData, EventKeyHex, TransactionHashHex,
+};
+use super::{ContractAddressHex, FeltHex};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EntryPointType {
+    External,
+    L1Handler,
+    Constructor,
+}
+
+impl From<CoreEntryPointType> for EntryPointType {
+    fn from(entry_point_type: CoreEntryPointType) -> Self {
+        match entry_point_type {
+            CoreEntryPointType::External => EntryPointType::External,
+            CoreEntryPointType::L1Handler => EntryPointType::L1Handler,
+            CoreEntryPointType::Constructor => EntryPointType::Constructor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CallType {
+    Call,
+    LibraryCall,
+}
+
+impl From<CoreCallType> for CallType {
+    fn from(call_type: CoreCallType) -> Self {
+        match call_type {
+            CoreCallType::Call => CallType::Call,
+            CoreCallType::LibraryCall => CallType::LibraryCall,
+        }
+    }
+}
+
+/// An event emitted during an invocation; `order` is its position among all events of the
+/// transaction
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct OrderedEvent {
+    pub order: u64,
+    pub keys: Vec<EventKeyHex>,
+    pub data: EventData,
+}
+
+impl From<CoreOrderedEvent> for OrderedEvent {
+    fn from(event: CoreOrderedEvent) -> Self {
+        Self {
+            order: event.order,
+            keys: event.keys.into_iter().map(FeltHex).collect(),
+            data: event.data.into_iter().map(FeltHex).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct FunctionInvocation {
+    pub contract_address: ContractAddressHex,
+    pub entry_point_selector: EntryPointSelectorHex,
+    pub calldata: Calldata,
+    pub caller_address: ContractAddressHex,
+    pub class_hash: ClassHashHex,
+    pub entry_point_type: EntryPointType,
+    pub call_type: CallType,
+    pub result: Vec<FeltHex>,
+    /// Invocations made by this invocation
+    pub calls: Vec<FunctionInvocation>,
+    pub events: Vec<OrderedEvent>,
+}
+
+impl From<CoreFunctionInvocation> for FunctionInvocation {
+    fn from(invocation: CoreFunctionInvocation) -> Self {
+        Self {
+            contract_address: ContractAddressHex(invocation.contract_address),
+            entry_point_selector: FeltHex(invocation.entry_point_selector),
+            calldata: invocation.calldata.into_iter().map(FeltHex).collect(),
+            caller_address: ContractAddressHex(invocation.caller_address),
+            class_hash: FeltHex(invocation.class_hash),
+            entry_point_type: invocation.entry_point_type.into(),
+            call_type: invocation.call_type.into(),
+            result: invocation.result.into_iter().map(FeltHex).collect(),
+            calls: invocation.calls.into_iter().map(FunctionInvocation::from).collect(),
+            events: invocation.events.into_iter().map(OrderedEvent::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ExecuteInvocation {
+    Succeeded(FunctionInvocation),
+    Reverted { revert_reason: String },
+}
+
+/// Trace of a transaction. Only the invocations made by the type of the traced transaction are
+/// present.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TransactionTrace {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validate_invocation: Option<FunctionInvocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execute_invocation: Option<ExecuteInvocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constructor_invocation: Option<FunctionInvocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_transfer_invocation: Option<FunctionInvocation>,
+}
+
+impl From<CoreTransactionTrace> for TransactionTrace {
+    fn from(trace: CoreTransactionTrace) -> Self {
+        let execute_invocation = match trace.revert_reason {
+            Some(revert_reason) => Some(ExecuteInvocation::Reverted { revert_reason }),
+            None => trace
+                .execute_invocation
+                .map(|invocation| ExecuteInvocation::Succeeded(invocation.into())),
+        };
+
+        Self {
+            validate_invocation: trace.validate_invocation.map(FunctionInvocation::from),
+            execute_invocation,
+            constructor_invocation: trace.constructor_invocation.map(FunctionInvocation::from),
+            fee_transfer_invocation: trace.fee_transfer_invocation.map(FunctionInvocation::from),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BlockTransactionTrace {
+    pub transaction_hash: TransactionHashHex,
+    pub trace_root: TransactionTrace,
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_core::traces::TransactionTrace as CoreTransactionTrace;
+
+    use super::TransactionTrace;
+
+    #[test]
+    fn reverted_trace_holds_revert_reason_in_place_of_execute_invocation() {
+        let trace = TransactionTrace::from(CoreTransactionTrace {
+            validate_invocation: None,
+            execute_invocation: None,
+            constructor_invocation: None,
+            fee_transfer_invocation: None,
+            revert_reason: Some("Out of gas".to_string()),
+        });
+
+        assert_eq!(
+            serde_json::to_value(trace).unwrap(),
+            serde_json::json!({ "execute_invocation": { "revert_reason": "Out of gas" } })
+        );
+    }
+}
@@ -1,13 +1,18 @@
 use std::collections::HashSet;
 
 use serde::{Deserialize, Serialize};
-use starknet_rs_core::types::BlockId;
+use starknet_core::starknet::events::EmittedEvent as CoreEmittedEvent;
+use starknet_core::starknet::messaging::MessageToL1 as CoreMessageToL1;
+use starknet_core::starknet::FeeUnit;
+use starknet_core::transactions::v3::DataAvailabilityMode;
+use starknet_core::transactions::{ExecutionResources as CoreExecutionResources, ExecutionStatus};
 use starknet_types::starknet_api::block::BlockNumber;
-use starknet_types::starknet_api::transaction::{EthAddress, Fee};
+use starknet_types::starknet_api::transaction::Fee;
 
 use super::block::BlockHashHex;
 use super::contract_class::DeprecatedContractClass;
-use super::{ContractAddressHex, FeltHex};
+use super::{BlockId, ContractAddressHex, FeltHex};
+use crate::api::serde_helpers::block_id;
 use crate::api::serde_helpers::rpc_sierra_contract_class_to_sierra_contract_class::deserialize_to_sierra_contract_class;
 
 pub type TransactionHashHex = FeltHex;
@@ -82,12 +87,27 @@ pub struct DeclareTransactionV2 {
     pub signature: TransactionSignature,
 }
 
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct DeclareTransactionV3 {
+    pub class_hash: ClassHashHex,
+    pub compiled_class_hash: CompiledClassHashHex,
+    pub sender_address: ContractAddressHex,
+    pub nonce: Nonce,
+    pub version: TransactionVersionHex,
+    pub transaction_hash: TransactionHashHex,
+    pub signature: TransactionSignature,
+    #[serde(flatten)]
+    pub v3_fields: TransactionV3Fields,
+    pub account_deployment_data: Vec<FeltHex>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum DeclareTransaction {
     Version0(DeclareTransactionV0V1),
     Version1(DeclareTransactionV0V1),
     Version2(DeclareTransactionV2),
+    Version3(DeclareTransactionV3),
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
@@ -113,15 +133,29 @@ pub struct InvokeTransactionV1 {
     pub calldata: Calldata,
 }
 
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct InvokeTransactionV3 {
+    pub transaction_hash: TransactionHashHex,
+    pub version: TransactionVersionHex,
+    pub signature: TransactionSignature,
+    pub nonce: Nonce,
+    pub sender_address: ContractAddressHex,
+    pub calldata: Calldata,
+    #[serde(flatten)]
+    pub v3_fields: TransactionV3Fields,
+    pub account_deployment_data: Vec<FeltHex>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum InvokeTransaction {
     Version0(InvokeTransactionV0),
     Version1(InvokeTransactionV1),
+    Version3(InvokeTransactionV3),
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
-pub struct DeployAccountTransaction {
+pub struct DeployAccountTransactionV1 {
     pub transaction_hash: TransactionHashHex,
     pub max_fee: Fee,
     pub version: TransactionVersionHex,
@@ -132,6 +166,50 @@ pub struct DeployAccountTransaction {
     pub constructor_calldata: Calldata,
 }
 
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct DeployAccountTransactionV3 {
+    pub transaction_hash: TransactionHashHex,
+    pub version: TransactionVersionHex,
+    pub signature: TransactionSignature,
+    pub nonce: Nonce,
+    pub class_hash: ClassHashHex,
+    pub contract_address_salt: ContractAddressSaltHex,
+    pub constructor_calldata: Calldata,
+    #[serde(flatten)]
+    pub v3_fields: TransactionV3Fields,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DeployAccountTransaction {
+    Version1(DeployAccountTransactionV1),
+    Version3(DeployAccountTransactionV3),
+}
+
+/// Max amount of a resource and max price per unit of it, both as hex strings
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ResourceBounds {
+    pub max_amount: FeltHex,
+    pub max_price_per_unit: FeltHex,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ResourceBoundsMapping {
+    pub l1_gas: ResourceBounds,
+    pub l2_gas: ResourceBounds,
+}
+
+/// Fields shared by transactions of version 3, whose fee is paid in STRK within the resource
+/// bounds
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TransactionV3Fields {
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: FeltHex,
+    pub paymaster_data: Vec<FeltHex>,
+    pub nonce_data_availability_mode: DataAvailabilityMode,
+    pub fee_data_availability_mode: DataAvailabilityMode,
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub struct DeployTransaction {
     pub transaction_hash: TransactionHashHex,
@@ -169,6 +247,35 @@ pub enum TransactionStatus {
     Rejected,
 }
 
+/// How final a transaction is, reported by `starknet_getTransactionStatus`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionFinalityStatus {
+    /// The transaction was executed, but not included in a block yet
+    Received,
+    AcceptedOnL2,
+    AcceptedOnL1,
+    /// The transaction failed validation or its block was aborted
+    Rejected,
+}
+
+/// Outcome of the execution of an accepted transaction
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionExecutionStatus {
+    Succeeded,
+    Reverted,
+}
+
+impl From<ExecutionStatus> for TransactionExecutionStatus {
+    fn from(value: ExecutionStatus) -> Self {
+        match value {
+            ExecutionStatus::Succeeded => TransactionExecutionStatus::Succeeded,
+            ExecutionStatus::Reverted => TransactionExecutionStatus::Reverted,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct TransactionReceiptWithStatus {
     pub status: TransactionStatus,
@@ -176,10 +283,18 @@ pub struct TransactionReceiptWithStatus {
     pub receipt: TransactionReceipt,
 }
 
+/// Transaction of a block paired with its receipt
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TransactionWithReceipt {
+    pub transaction: TransactionWithType,
+    pub receipt: TransactionReceiptWithStatus,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum TransactionReceipt {
     Deploy(DeployTransactionReceipt),
+    L1Handler(L1HandlerTransactionReceipt),
     Common(CommonTransactionReceipt),
 }
 
@@ -190,33 +305,124 @@ pub struct DeployTransactionReceipt {
     pub contract_address: ContractAddressHex,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct L1HandlerTransactionReceipt {
+    #[serde(flatten)]
+    pub common: CommonTransactionReceipt,
+    /// Hash of the consumed L1 to L2 message, which doesn't necessarily fit into a felt
+    pub message_hash: String,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct CommonTransactionReceipt {
     pub transaction_hash: TransactionHashHex,
     pub r#type: TransactionType,
     pub block_hash: BlockHashHex,
     pub block_number: BlockNumber,
+    pub execution_status: TransactionExecutionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
     #[serde(flatten)]
     pub output: TransactionOutput,
+    pub execution_resources: ExecutionResources,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct TransactionOutput {
-    pub actual_fee: Fee,
+    pub actual_fee: FeePayment,
     pub messages_sent: Vec<MessageToL1>,
     pub events: Vec<Event>,
 }
 
+/// Fee paid for a transaction, in the unit of the token it was paid with
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct FeePayment {
+    pub amount: Fee,
+    pub unit: FeeUnit,
+}
+
+/// Resources consumed by the execution of a transaction. Builtins that were not used are omitted.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ExecutionResources {
+    pub steps: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub memory_holes: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub range_check_builtin_applications: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub pedersen_builtin_applications: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub poseidon_builtin_applications: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub ec_op_builtin_applications: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub ecdsa_builtin_applications: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub bitwise_builtin_applications: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub keccak_builtin_applications: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub segment_arena_builtin: u64,
+    pub data_availability: DataAvailabilityResources,
+}
+
+/// Gas needed to publish the state diff of a transaction on L1, either in calldata or in blobs
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct DataAvailabilityResources {
+    pub l1_gas: u128,
+    pub l1_data_gas: u128,
+}
+
+fn is_zero(amount: &u64) -> bool {
+    *amount == 0
+}
+
+impl From<CoreExecutionResources> for ExecutionResources {
+    fn from(resources: CoreExecutionResources) -> Self {
+        let builtin =
+            |name: &str| resources.builtin_instance_counter.get(name).copied().unwrap_or_default();
+
+        Self {
+            steps: resources.steps,
+            memory_holes: resources.memory_holes,
+            range_check_builtin_applications: builtin("range_check_builtin"),
+            pedersen_builtin_applications: builtin("pedersen_builtin"),
+            poseidon_builtin_applications: builtin("poseidon_builtin"),
+            ec_op_builtin_applications: builtin("ec_op_builtin"),
+            ecdsa_builtin_applications: builtin("ecdsa_builtin"),
+            bitwise_builtin_applications: builtin("bitwise_builtin"),
+            keccak_builtin_applications: builtin("keccak_builtin"),
+            segment_arena_builtin: builtin("segment_arena_builtin"),
+            // state diffs are published in blobs
+            data_availability: DataAvailabilityResources {
+                l1_gas: 0,
+                l1_data_gas: resources.data_gas_consumed,
+            },
+        }
+    }
+}
+
 pub type L2ToL1Payload = Vec<FeltHex>;
 
 /// An L2 to L1 message.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct MessageToL1 {
     pub from_address: ContractAddressHex,
-    pub to_address: EthAddress,
+    /// Address of the L1 recipient
+    pub to_address: FeltHex,
     pub payload: L2ToL1Payload,
 }
 
+impl From<CoreMessageToL1> for MessageToL1 {
+    fn from(message: CoreMessageToL1) -> Self {
+        Self {
+            from_address: ContractAddressHex(message.from_address),
+            to_address: FeltHex(message.to_address),
+            payload: message.payload.into_iter().map(FeltHex).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Event {
     pub from_address: ContractAddressHex,
@@ -236,7 +442,9 @@ pub struct EventContent {
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct EventFilter {
+    #[serde(default, with = "block_id::optional")]
     pub from_block: Option<BlockId>,
+    #[serde(default, with = "block_id::optional")]
     pub to_block: Option<BlockId>,
     pub continuation_token: Option<String>,
     pub chunk_size: usize,
@@ -245,9 +453,39 @@ pub struct EventFilter {
     pub keys: Vec<HashSet<FeltHex>>,
 }
 
+/// An event together with the transaction and the block it was emitted in. Block hash and number
+/// are omitted for events of the pending block.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EmittedEvent {
+    #[serde(flatten)]
+    pub event: Event,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<BlockHashHex>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<BlockNumber>,
+    pub transaction_hash: TransactionHashHex,
+}
+
+impl From<CoreEmittedEvent> for EmittedEvent {
+    fn from(emitted_event: CoreEmittedEvent) -> Self {
+        Self {
+            event: Event {
+                from_address: ContractAddressHex(emitted_event.from_address),
+                content: EventContent {
+                    keys: emitted_event.keys.into_iter().map(FeltHex).collect(),
+                    data: emitted_event.data.into_iter().map(FeltHex).collect(),
+                },
+            },
+            block_hash: emitted_event.block_hash.map(FeltHex),
+            block_number: emitted_event.block_number,
+            transaction_hash: FeltHex(emitted_event.transaction_hash),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct EventsChunk {
-    pub events: Vec<Event>,
+    pub events: Vec<EmittedEvent>,
     pub continuation_token: Option<String>,
 }
 
@@ -266,6 +504,25 @@ pub struct BroadcastedTransactionCommon {
     pub nonce: Nonce,
 }
 
+/// Fields shared by broadcasted transactions of version 3, which have resource bounds in place of
+/// the max fee
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BroadcastedTransactionCommonV3 {
+    pub version: TransactionVersionHex,
+    pub signature: TransactionSignature,
+    pub nonce: Nonce,
+    #[serde(flatten)]
+    pub v3_fields: TransactionV3Fields,
+}
+
+/// Flags altering the execution of transactions that are estimated, without being added
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SimulationFlag {
+    SkipValidate,
+    SkipFeeCharge,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct BroadcastedTransactionWithType {
     pub r#type: TransactionType,
@@ -286,6 +543,7 @@ pub enum BroadcastedTransaction {
 pub enum BroadcastedInvokeTransaction {
     V0(BroadcastedInvokeTransactionV0),
     V1(BroadcastedInvokeTransactionV1),
+    V3(BroadcastedInvokeTransactionV3),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
@@ -293,6 +551,7 @@ pub enum BroadcastedInvokeTransaction {
 pub enum BroadcastedDeclareTransaction {
     V1(Box<BroadcastedDeclareTransactionV1>),
     V2(Box<BroadcastedDeclareTransactionV2>),
+    V3(Box<BroadcastedDeclareTransactionV3>),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
@@ -312,6 +571,16 @@ pub struct BroadcastedInvokeTransactionV1 {
     pub calldata: Calldata,
 }
 
+/// Invoke transaction of version 3, whose fee is paid in STRK within the resource bounds
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BroadcastedInvokeTransactionV3 {
+    #[serde(flatten)]
+    pub common: BroadcastedTransactionCommonV3,
+    pub sender_address: ContractAddressHex,
+    pub calldata: Calldata,
+    pub account_deployment_data: Vec<FeltHex>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct BroadcastedDeclareTransactionV1 {
     #[serde(flatten)]
@@ -330,11 +599,40 @@ pub struct BroadcastedDeclareTransactionV2 {
     pub compiled_class_hash: CompiledClassHashHex,
 }
 
+/// Declare transaction of version 3, whose fee is paid in STRK within the resource bounds
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BroadcastedDeclareTransactionV3 {
+    #[serde(flatten)]
+    pub common: BroadcastedTransactionCommonV3,
+    #[serde(deserialize_with = "deserialize_to_sierra_contract_class")]
+    pub contract_class: starknet_in_rust::SierraContractClass,
+    pub sender_address: ContractAddressHex,
+    pub compiled_class_hash: CompiledClassHashHex,
+    pub account_deployment_data: Vec<FeltHex>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum BroadcastedDeployAccountTransaction {
+    V1(BroadcastedDeployAccountTransactionV1),
+    V3(Box<BroadcastedDeployAccountTransactionV3>),
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
-pub struct BroadcastedDeployAccountTransaction {
+pub struct BroadcastedDeployAccountTransactionV1 {
     #[serde(flatten)]
     pub common: BroadcastedTransactionCommon,
     pub contract_address_salt: ContractAddressSaltHex,
     pub constructor_calldata: Calldata,
     pub class_hash: ClassHashHex,
 }
+
+/// Deploy account transaction of version 3, whose fee is paid in STRK within the resource bounds
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BroadcastedDeployAccountTransactionV3 {
+    #[serde(flatten)]
+    pub common: BroadcastedTransactionCommonV3,
+    pub contract_address_salt: ContractAddressSaltHex,
+    pub constructor_calldata: Calldata,
+    pub class_hash: ClassHashHex,
+}
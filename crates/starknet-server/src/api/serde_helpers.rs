@@ -18,7 +18,7 @@ pub mod empty_params {
 }
 
 pub mod rpc_sierra_contract_class_to_sierra_contract_class {
-    use serde::{Deserialize, Deserializer};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
     pub fn deserialize_to_sierra_contract_class<'de, D>(
         deserializer: D,
@@ -42,11 +42,37 @@ pub mod rpc_sierra_contract_class_to_sierra_contract_class {
         serde_json::from_value(json_obj).map_err(serde::de::Error::custom)
     }
 
+    /// Serializes the class in the format of the JSON-RPC specification, in which the ABI is a
+    /// JSON string and there is no debug info
+    pub fn serialize_to_rpc_sierra_contract_class<S>(
+        contract_class: &starknet_in_rust::ContractClass,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut json_obj =
+            serde_json::to_value(contract_class).map_err(serde::ser::Error::custom)?;
+        let fields = json_obj
+            .as_object_mut()
+            .ok_or(serde::ser::Error::custom("Expected to be an object"))?;
+
+        fields.remove("sierra_program_debug_info");
+        if let Some(abi) = fields.get("abi").filter(|abi| !abi.is_null()) {
+            let abi_string = serde_json::to_string(abi).map_err(serde::ser::Error::custom)?;
+            fields.insert("abi".to_string(), serde_json::Value::String(abi_string));
+        }
+
+        json_obj.serialize(serializer)
+    }
+
     #[cfg(test)]
     mod tests {
-        use serde::Deserialize;
+        use serde::{Deserialize, Serialize};
 
-        use crate::api::serde_helpers::rpc_sierra_contract_class_to_sierra_contract_class::deserialize_to_sierra_contract_class;
+        use crate::api::serde_helpers::rpc_sierra_contract_class_to_sierra_contract_class::{
+            deserialize_to_sierra_contract_class, serialize_to_rpc_sierra_contract_class,
+        };
 
         #[test]
         fn correct_deserialzation_from_sierra_contract_class_with_abi_field_as_string() {
@@ -66,12 +92,45 @@ pub mod rpc_sierra_contract_class_to_sierra_contract_class {
 
             serde_json::from_str::<TestDeserialization>(&json_str).unwrap();
         }
+
+        #[test]
+        fn serialized_sierra_contract_class_has_abi_as_string() {
+            #[derive(Deserialize, Serialize)]
+            struct TestSerialization(
+                #[serde(
+                    deserialize_with = "deserialize_to_sierra_contract_class",
+                    serialize_with = "serialize_to_rpc_sierra_contract_class"
+                )]
+                starknet_in_rust::ContractClass,
+            );
+
+            let path = concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/test_data/sierra_contract_class_with_abi_as_string.json"
+            );
+            let json_str = std::fs::read_to_string(path).unwrap();
+            let contract_class = serde_json::from_str::<TestSerialization>(&json_str).unwrap();
+
+            let serialized = serde_json::to_value(&contract_class).unwrap();
+            let abi_string = serialized["abi"].as_str().unwrap();
+            assert!(serde_json::from_str::<serde_json::Value>(abi_string).unwrap().is_array());
+            assert!(serialized.get("sierra_program_debug_info").is_none());
+
+            // serialized class can be deserialized again
+            let reserialized = serde_json::to_value(
+                serde_json::from_value::<TestSerialization>(serialized.clone()).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(reserialized, serialized);
+        }
     }
 }
 
 pub mod base_64_gzipped_json_string {
+    use std::io::Write;
+
     use base64::Engine;
-    use serde::{Deserialize, Deserializer};
+    use serde::{Deserialize, Deserializer, Serializer};
     use starknet_rs_core::types::contract::legacy::LegacyProgram;
 
     pub fn deserialize_to_serde_json_value_with_keys_ordered_in_alphabetical_order<'de, D>(
@@ -98,11 +157,40 @@ pub mod base_64_gzipped_json_string {
             .map_err(|_| serde::de::Error::custom("program: Unable to parse to JSON"))
     }
 
+    /// Serializes the program as the base64 encoding of its gzip-compressed JSON representation.
+    /// A missing program is serialized as an empty string
+    pub fn serialize_to_base_64_gzipped_json_string<S>(
+        program: &serde_json::Value,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if program.is_null() {
+            return serializer.serialize_str("");
+        }
+
+        let json_bytes = serde_json::to_vec(program)
+            .map_err(|_| serde::ser::Error::custom("program: Unable to serialize to JSON"))?;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(&json_bytes)
+            .map_err(|_| serde::ser::Error::custom("program: Unable to gzip JSON"))?;
+        let gzipped_bytes = encoder
+            .finish()
+            .map_err(|_| serde::ser::Error::custom("program: Unable to gzip JSON"))?;
+
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(gzipped_bytes))
+    }
+
     #[cfg(test)]
     mod tests {
-        use serde::Deserialize;
+        use serde::{Deserialize, Serialize};
 
-        use crate::api::serde_helpers::base_64_gzipped_json_string::deserialize_to_serde_json_value_with_keys_ordered_in_alphabetical_order;
+        use crate::api::serde_helpers::base_64_gzipped_json_string::{
+            deserialize_to_serde_json_value_with_keys_ordered_in_alphabetical_order,
+            serialize_to_base_64_gzipped_json_string,
+        };
 
         #[test]
         fn deserialize_successfully_starknet_api_program() {
@@ -123,6 +211,31 @@ pub mod base_64_gzipped_json_string {
 
             serde_json::from_str::<TestDeserialization>(&json_str).unwrap();
         }
+
+        #[test]
+        fn serialized_program_is_deserialized_to_the_same_program() {
+            #[derive(Deserialize, Serialize)]
+            struct TestSerialization {
+                #[serde(
+                    serialize_with = "serialize_to_base_64_gzipped_json_string",
+                    deserialize_with = "deserialize_to_serde_json_value_with_keys_ordered_in_alphabetical_order"
+                )]
+                program: serde_json::Value,
+            }
+
+            let json_str = std::fs::read_to_string(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/test_data/rpc/cairo_0_base64_gzipped_program.json"
+            ))
+            .unwrap();
+            let deserialized = serde_json::from_str::<TestSerialization>(&json_str).unwrap();
+
+            let serialized = serde_json::to_value(&deserialized).unwrap();
+            assert!(serialized["program"].is_string());
+            let deserialized_again =
+                serde_json::from_value::<TestSerialization>(serialized).unwrap();
+            assert_eq!(deserialized_again.program, deserialized.program);
+        }
     }
 }
 
@@ -348,3 +461,352 @@ pub mod hex_string {
         }
     }
 }
+
+/// Token amounts of up to 256 bits, given as a JSON number, a decimal string or a prefixed hex
+/// string. JSON numbers above u64 lose precision in the parser, so such amounts have to be passed
+/// as strings.
+pub mod u256_amount {
+    use std::fmt;
+
+    use serde::de::{self, Visitor};
+    use serde::{Deserializer, Serializer};
+    use starknet_types::num_bigint::BigUint;
+
+    pub fn deserialize_to_u256<'de, D>(deserializer: D) -> Result<BigUint, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct U256Visitor;
+
+        impl<'de> Visitor<'de> for U256Visitor {
+            type Value = BigUint;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter
+                    .write_str("an unsigned 256-bit integer as a decimal or prefixed hex string")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(BigUint::from(value))
+            }
+
+            fn visit_u128<E: de::Error>(self, value: u128) -> Result<Self::Value, E> {
+                Ok(BigUint::from(value))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                Err(E::custom(format!(
+                    "amount {value} is not an integer or is too big to be a JSON number; pass it \
+                     as a decimal or prefixed hex string"
+                )))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                let parsed = match value.strip_prefix("0x") {
+                    Some(hex_digits) => BigUint::parse_bytes(hex_digits.as_bytes(), 16),
+                    None => BigUint::parse_bytes(value.as_bytes(), 10),
+                }
+                .ok_or_else(|| {
+                    E::custom(format!(
+                        "amount {value} is not a decimal or prefixed hex string of an unsigned \
+                         integer"
+                    ))
+                })?;
+
+                if parsed.bits() > 256 {
+                    return Err(E::custom(format!("amount {value} doesn't fit in 256 bits")));
+                }
+
+                Ok(parsed)
+            }
+        }
+
+        deserializer.deserialize_any(U256Visitor)
+    }
+
+    #[allow(unused)]
+    pub fn serialize_to_decimal_string<S>(value: &BigUint, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(&value.to_str_radix(10))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use serde::{Deserialize, Serialize};
+        use serde_json::json;
+        use starknet_types::num_bigint::BigUint;
+
+        use crate::api::serde_helpers::u256_amount::{
+            deserialize_to_u256, serialize_to_decimal_string,
+        };
+
+        #[derive(Deserialize, Serialize)]
+        struct TestAmount {
+            #[serde(
+                deserialize_with = "deserialize_to_u256",
+                serialize_with = "serialize_to_decimal_string"
+            )]
+            amount: BigUint,
+        }
+
+        fn deserialize_amount(amount: serde_json::Value) -> Result<BigUint, serde_json::Error> {
+            serde_json::from_value::<TestAmount>(json!({ "amount": amount }))
+                .map(|deserialized| deserialized.amount)
+        }
+
+        #[test]
+        fn deserialization_of_decimal_string() {
+            let max_u256 = (BigUint::from(1u8) << 256) - 1u8;
+            assert_eq!(deserialize_amount(json!(max_u256.to_string())).unwrap(), max_u256);
+            assert_eq!(deserialize_amount(json!("42")).unwrap(), BigUint::from(42u8));
+        }
+
+        #[test]
+        fn deserialization_of_prefixed_hex_string() {
+            let max_u256 = (BigUint::from(1u8) << 256) - 1u8;
+            assert_eq!(deserialize_amount(json!(format!("0x{max_u256:x}"))).unwrap(), max_u256);
+            assert_eq!(deserialize_amount(json!("0x2a")).unwrap(), BigUint::from(42u8));
+        }
+
+        #[test]
+        fn deserialization_of_number() {
+            assert_eq!(deserialize_amount(json!(42)).unwrap(), BigUint::from(42u8));
+            assert!(deserialize_amount(json!(1.5)).is_err());
+        }
+
+        #[test]
+        fn deserialization_of_amount_above_256_bits_should_return_error() {
+            let above_u256 = BigUint::from(1u8) << 256;
+            assert!(deserialize_amount(json!(above_u256.to_string())).is_err());
+            assert!(deserialize_amount(json!(format!("0x{above_u256:x}"))).is_err());
+        }
+
+        #[test]
+        fn deserialization_of_non_numeric_input_should_return_error() {
+            for garbage in [json!("abc"), json!("0xzz"), json!(""), json!("-1"), json!(null)] {
+                assert!(deserialize_amount(garbage).is_err());
+            }
+        }
+
+        #[test]
+        fn serialization_to_decimal_string() {
+            let amount = TestAmount { amount: BigUint::from(1u8) << 128 };
+            assert_eq!(
+                serde_json::to_string(&amount).unwrap(),
+                r#"{"amount":"340282366920938463463374607431768211456"}"#
+            );
+        }
+    }
+}
+
+/// Block ids, accepted in all of their forms by every method taking one: the tags `"latest"` and
+/// `"pending"`, `{ "block_number": N }` and `{ "block_hash": "0x..." }`. Objects with both the
+/// hash and the number are rejected as ambiguous.
+pub mod block_id {
+    use serde::ser::SerializeMap;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use starknet_types::starknet_api::block::BlockNumber;
+
+    use crate::api::models::{BlockHashOrNumber, BlockId, FeltHex, Tag};
+
+    const BLOCK_HASH_KEY: &str = "block_hash";
+    const BLOCK_NUMBER_KEY: &str = "block_number";
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BlockId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let object = match value {
+            serde_json::Value::String(tag) => {
+                return match tag.as_str() {
+                    "latest" => Ok(BlockId::Tag(Tag::Latest)),
+                    "pending" => Ok(BlockId::Tag(Tag::Pending)),
+                    _ => Err(serde::de::Error::custom(format!(
+                        "invalid block tag {tag}: expected \"latest\" or \"pending\""
+                    ))),
+                };
+            }
+            serde_json::Value::Object(object) => object,
+            other => {
+                return Err(serde::de::Error::custom(format!(
+                    "invalid block id {other}: expected a block tag or an object with a \
+                     {BLOCK_HASH_KEY} or a {BLOCK_NUMBER_KEY}"
+                )));
+            }
+        };
+
+        if let Some(unknown_key) =
+            object.keys().find(|key| *key != BLOCK_HASH_KEY && *key != BLOCK_NUMBER_KEY)
+        {
+            return Err(serde::de::Error::custom(format!(
+                "unknown key {unknown_key} of block id: expected {BLOCK_HASH_KEY} or \
+                 {BLOCK_NUMBER_KEY}"
+            )));
+        }
+
+        match (object.get(BLOCK_HASH_KEY), object.get(BLOCK_NUMBER_KEY)) {
+            (Some(_), Some(_)) => Err(serde::de::Error::custom(format!(
+                "ambiguous block id: only one of {BLOCK_HASH_KEY} and {BLOCK_NUMBER_KEY} can be \
+                 given"
+            ))),
+            (Some(hash), None) => FeltHex::deserialize(hash)
+                .map(|hash| BlockId::HashOrNumber(BlockHashOrNumber::Hash(hash)))
+                .map_err(serde::de::Error::custom),
+            (None, Some(number)) => BlockNumber::deserialize(number)
+                .map(|number| BlockId::HashOrNumber(BlockHashOrNumber::Number(number)))
+                .map_err(serde::de::Error::custom),
+            (None, None) => Err(serde::de::Error::custom(format!(
+                "block id has neither a {BLOCK_HASH_KEY} nor a {BLOCK_NUMBER_KEY}"
+            ))),
+        }
+    }
+
+    pub fn serialize<S>(block_id: &BlockId, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match block_id {
+            BlockId::Tag(Tag::Latest) => s.serialize_str("latest"),
+            BlockId::Tag(Tag::Pending) => s.serialize_str("pending"),
+            BlockId::HashOrNumber(BlockHashOrNumber::Hash(hash)) => {
+                let mut map = s.serialize_map(Some(1))?;
+                map.serialize_entry(BLOCK_HASH_KEY, hash)?;
+                map.end()
+            }
+            BlockId::HashOrNumber(BlockHashOrNumber::Number(number)) => {
+                let mut map = s.serialize_map(Some(1))?;
+                map.serialize_entry(BLOCK_NUMBER_KEY, number)?;
+                map.end()
+            }
+        }
+    }
+
+    /// Same as the parent module, for block ids that can be omitted
+    pub mod optional {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        use crate::api::models::BlockId;
+
+        #[derive(Deserialize, Serialize)]
+        struct BlockIdWrapper(#[serde(with = "crate::api::serde_helpers::block_id")] BlockId);
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<BlockId>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Ok(Option::<BlockIdWrapper>::deserialize(deserializer)?.map(|wrapper| wrapper.0))
+        }
+
+        pub fn serialize<S>(block_id: &Option<BlockId>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            block_id.map(BlockIdWrapper).serialize(s)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use serde::{Deserialize, Serialize};
+        use serde_json::json;
+        use starknet_types::felt::Felt;
+        use starknet_types::starknet_api::block::BlockNumber;
+
+        use crate::api::models::{BlockHashOrNumber, BlockId, FeltHex, Tag};
+
+        #[derive(Debug, Deserialize, Serialize)]
+        struct TestBlockId {
+            #[serde(with = "crate::api::serde_helpers::block_id")]
+            block_id: BlockId,
+        }
+
+        fn deserialize_block_id(block_id: serde_json::Value) -> Result<BlockId, serde_json::Error> {
+            serde_json::from_value::<TestBlockId>(json!({ "block_id": block_id }))
+                .map(|deserialized| deserialized.block_id)
+        }
+
+        fn assert_serialization_round_trip(block_id: BlockId, expected_json: serde_json::Value) {
+            let serialized = serde_json::to_value(TestBlockId { block_id }).unwrap();
+            assert_eq!(serialized, json!({ "block_id": expected_json }));
+            assert_eq!(deserialize_block_id(expected_json).unwrap(), block_id);
+        }
+
+        #[test]
+        fn deserialization_of_tags() {
+            assert_eq!(deserialize_block_id(json!("latest")).unwrap(), BlockId::Tag(Tag::Latest));
+            assert_eq!(deserialize_block_id(json!("pending")).unwrap(), BlockId::Tag(Tag::Pending));
+            assert!(deserialize_block_id(json!("earliest")).is_err());
+        }
+
+        #[test]
+        fn deserialization_of_block_number() {
+            assert_eq!(
+                deserialize_block_id(json!({ "block_number": 10 })).unwrap(),
+                BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(10)))
+            );
+            assert!(deserialize_block_id(json!({ "block_number": "0xa" })).is_err());
+            assert!(deserialize_block_id(json!({ "block_number": -1 })).is_err());
+        }
+
+        #[test]
+        fn deserialization_of_block_hash() {
+            assert_eq!(
+                deserialize_block_id(json!({ "block_hash": "0x1" })).unwrap(),
+                BlockId::HashOrNumber(BlockHashOrNumber::Hash(FeltHex(Felt::from(1))))
+            );
+            assert!(deserialize_block_id(json!({ "block_hash": "1" })).is_err());
+        }
+
+        #[test]
+        fn deserialization_of_object_with_both_keys_should_return_error() {
+            let err = deserialize_block_id(json!({ "block_hash": "0x1", "block_number": 1 }))
+                .unwrap_err();
+            assert!(err.to_string().contains("ambiguous block id"));
+        }
+
+        #[test]
+        fn deserialization_of_invalid_objects_should_return_error() {
+            assert!(deserialize_block_id(json!({})).is_err());
+            assert!(deserialize_block_id(json!({ "block_numberr": 1 })).is_err());
+            assert!(deserialize_block_id(json!({ "block_number": 1, "tag": "latest" })).is_err());
+            assert!(deserialize_block_id(json!(1)).is_err());
+        }
+
+        #[test]
+        fn serialization_of_all_variants() {
+            assert_serialization_round_trip(BlockId::Tag(Tag::Latest), json!("latest"));
+            assert_serialization_round_trip(BlockId::Tag(Tag::Pending), json!("pending"));
+            assert_serialization_round_trip(
+                BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(3))),
+                json!({ "block_number": 3 }),
+            );
+            assert_serialization_round_trip(
+                BlockId::HashOrNumber(BlockHashOrNumber::Hash(FeltHex(Felt::from(0x10)))),
+                json!({ "block_hash": "0x10" }),
+            );
+        }
+
+        #[test]
+        fn optional_block_id_omitted_or_given() {
+            #[derive(Deserialize)]
+            struct TestOptionalBlockId {
+                #[serde(default, with = "crate::api::serde_helpers::block_id::optional")]
+                block_id: Option<BlockId>,
+            }
+
+            let omitted = serde_json::from_value::<TestOptionalBlockId>(json!({})).unwrap();
+            assert_eq!(omitted.block_id, None);
+
+            let given =
+                serde_json::from_value::<TestOptionalBlockId>(json!({ "block_id": "latest" }))
+                    .unwrap();
+            assert_eq!(given.block_id, Some(BlockId::Tag(Tag::Latest)));
+
+            let ambiguous = json!({ "block_id": { "block_hash": "0x1", "block_number": 1 } });
+            assert!(serde_json::from_value::<TestOptionalBlockId>(ambiguous).is_err());
+        }
+    }
+}
@@ -1,11 +1,18 @@
+use starknet_core::transactions::v3::{ResourceBounds as CoreResourceBounds, V3Fields};
 use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::Cairo0ContractClass;
+use starknet_types::error::{ConversionError, JsonError};
 use starknet_types::felt::Felt;
 use starknet_types::starknet_api::transaction::Fee;
 
 use super::json_rpc::error::ApiError;
+use super::models::contract_class::{ContractClass, DeprecatedContractClass};
 use super::models::transaction::{
-    DeclareTransaction, DeclareTransactionV0V1, DeclareTransactionV2, DeployAccountTransaction,
-    InvokeTransactionV1, Transaction, TransactionType, TransactionWithType,
+    DeclareTransaction, DeclareTransactionV0V1, DeclareTransactionV2, DeclareTransactionV3,
+    DeployAccountTransaction, DeployAccountTransactionV1, DeployAccountTransactionV3,
+    InvokeTransaction, InvokeTransactionV1, InvokeTransactionV3, L1HandlerTransaction,
+    ResourceBounds, ResourceBoundsMapping, Transaction, TransactionType, TransactionV3Fields,
+    TransactionWithType,
 };
 use super::models::{ContractAddressHex, FeltHex};
 
@@ -21,6 +28,30 @@ impl From<Felt> for FeltHex {
     }
 }
 
+impl From<&CoreResourceBounds> for ResourceBounds {
+    fn from(value: &CoreResourceBounds) -> Self {
+        Self {
+            max_amount: Felt::from(value.max_amount as u128).into(),
+            max_price_per_unit: Felt::from(value.max_price_per_unit).into(),
+        }
+    }
+}
+
+impl From<&V3Fields> for TransactionV3Fields {
+    fn from(value: &V3Fields) -> Self {
+        Self {
+            resource_bounds: ResourceBoundsMapping {
+                l1_gas: (&value.resource_bounds.l1_gas).into(),
+                l2_gas: (&value.resource_bounds.l2_gas).into(),
+            },
+            tip: Felt::from(value.tip as u128).into(),
+            paymaster_data: into_vec(&value.paymaster_data),
+            nonce_data_availability_mode: value.nonce_data_availability_mode,
+            fee_data_availability_mode: value.fee_data_availability_mode,
+        }
+    }
+}
+
 impl From<ContractAddress> for ContractAddressHex {
     fn from(value: ContractAddress) -> Self {
         Self(value)
@@ -76,8 +107,26 @@ impl TryFrom<&starknet_core::transactions::Transaction> for TransactionWithType
                     transaction: Transaction::Declare(DeclareTransaction::Version2(declare_txn)),
                 }
             }
+            starknet_core::transactions::Transaction::DeclareV3(declare_v3) => {
+                let declare_txn = DeclareTransactionV3 {
+                    class_hash: declare_v3.class_hash().unwrap_or(&Felt::default()).into(),
+                    compiled_class_hash: declare_v3.compiled_class_hash().into(),
+                    sender_address: declare_v3.sender_address().into(),
+                    nonce: txn.nonce().into(),
+                    version: txn.version().into(),
+                    transaction_hash: txn.get_hash().unwrap_or_default().into(),
+                    signature: into_vec(txn.signature()),
+                    v3_fields: declare_v3.v3_fields().into(),
+                    account_deployment_data: into_vec(declare_v3.account_deployment_data()),
+                };
+
+                TransactionWithType {
+                    r#type: TransactionType::Declare,
+                    transaction: Transaction::Declare(DeclareTransaction::Version3(declare_txn)),
+                }
+            }
             starknet_core::transactions::Transaction::DeployAccount(deploy_account) => {
-                let deploy_account_txn = DeployAccountTransaction {
+                let deploy_account_txn = DeployAccountTransactionV1 {
                     nonce: txn.nonce().into(),
                     max_fee: Fee(txn.max_fee()),
                     version: txn.version().into(),
@@ -93,7 +142,31 @@ impl TryFrom<&starknet_core::transactions::Transaction> for TransactionWithType
 
                 TransactionWithType {
                     r#type: TransactionType::DeployAccount,
-                    transaction: Transaction::DeployAccount(deploy_account_txn),
+                    transaction: Transaction::DeployAccount(DeployAccountTransaction::Version1(
+                        deploy_account_txn,
+                    )),
+                }
+            }
+            starknet_core::transactions::Transaction::DeployAccountV3(deploy_account) => {
+                let deploy_account_txn = DeployAccountTransactionV3 {
+                    nonce: txn.nonce().into(),
+                    version: txn.version().into(),
+                    transaction_hash: txn.get_hash().unwrap_or_default().into(),
+                    signature: into_vec(txn.signature()),
+                    class_hash: deploy_account
+                        .class_hash()
+                        .map_err(ApiError::StarknetDevnetError)?
+                        .into(),
+                    contract_address_salt: deploy_account.contract_address_salt().into(),
+                    constructor_calldata: into_vec(&deploy_account.constructor_calldata()),
+                    v3_fields: deploy_account.v3_fields().into(),
+                };
+
+                TransactionWithType {
+                    r#type: TransactionType::DeployAccount,
+                    transaction: Transaction::DeployAccount(DeployAccountTransaction::Version3(
+                        deploy_account_txn,
+                    )),
                 }
             }
             starknet_core::transactions::Transaction::Invoke(invoke_v1) => {
@@ -112,9 +185,42 @@ impl TryFrom<&starknet_core::transactions::Transaction> for TransactionWithType
 
                 TransactionWithType {
                     r#type: TransactionType::Invoke,
-                    transaction: Transaction::Invoke(
-                        crate::api::models::transaction::InvokeTransaction::Version1(invoke_txn),
-                    ),
+                    transaction: Transaction::Invoke(InvokeTransaction::Version1(invoke_txn)),
+                }
+            }
+            starknet_core::transactions::Transaction::InvokeV3(invoke_v3) => {
+                let invoke_txn = InvokeTransactionV3 {
+                    sender_address: invoke_v3
+                        .sender_address()
+                        .map_err(ApiError::StarknetDevnetError)?
+                        .into(),
+                    nonce: txn.nonce().into(),
+                    version: txn.version().into(),
+                    transaction_hash: txn.get_hash().unwrap_or_default().into(),
+                    signature: into_vec(txn.signature()),
+                    calldata: into_vec(invoke_v3.calldata()),
+                    v3_fields: invoke_v3.v3_fields().into(),
+                    account_deployment_data: into_vec(invoke_v3.account_deployment_data()),
+                };
+
+                TransactionWithType {
+                    r#type: TransactionType::Invoke,
+                    transaction: Transaction::Invoke(InvokeTransaction::Version3(invoke_txn)),
+                }
+            }
+            starknet_core::transactions::Transaction::L1Handler(l1_handler) => {
+                let l1_handler_txn = L1HandlerTransaction {
+                    transaction_hash: txn.get_hash().unwrap_or_default().into(),
+                    version: txn.version().into(),
+                    nonce: txn.nonce().into(),
+                    contract_address: l1_handler.contract_address().into(),
+                    entry_point_selector: l1_handler.entry_point_selector().into(),
+                    calldata: into_vec(l1_handler.calldata()),
+                };
+
+                TransactionWithType {
+                    r#type: TransactionType::L1Handler,
+                    transaction: Transaction::L1Handler(l1_handler_txn),
                 }
             }
         };
@@ -122,3 +228,33 @@ impl TryFrom<&starknet_core::transactions::Transaction> for TransactionWithType
         Ok(transaction_with_type)
     }
 }
+
+impl TryFrom<starknet_types::contract_class::ContractClass> for ContractClass {
+    type Error = ApiError;
+    fn try_from(value: starknet_types::contract_class::ContractClass) -> Result<Self, Self::Error> {
+        match value {
+            starknet_types::contract_class::ContractClass::Cairo1(sierra_contract_class) => {
+                Ok(ContractClass::Sierra(sierra_contract_class))
+            }
+            starknet_types::contract_class::ContractClass::Cairo0(Cairo0ContractClass::Json(
+                mut json_obj,
+            )) => {
+                let to_types_error =
+                    |err: serde_json::Error| ApiError::TypesError(JsonError::from(err).into());
+
+                Ok(ContractClass::Cairo0(DeprecatedContractClass {
+                    abi: serde_json::from_value(json_obj["abi"].take()).map_err(to_types_error)?,
+                    program: json_obj["program"].take(),
+                    entry_points_by_type: serde_json::from_value(
+                        json_obj["entry_points_by_type"].take(),
+                    )
+                    .map_err(to_types_error)?,
+                }))
+            }
+            // classes deserialized from their JSON artifact keep it, so this shouldn't happen
+            starknet_types::contract_class::ContractClass::Cairo0(Cairo0ContractClass::Obj(_)) => {
+                Err(ApiError::TypesError(ConversionError::InvalidFormat.into()))
+            }
+        }
+    }
+}
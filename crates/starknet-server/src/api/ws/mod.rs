@@ -0,0 +1,494 @@
+pub(crate) mod models;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::IntoResponse;
+use axum::Extension;
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use server::rpc_core::error::{ErrorCode, RpcError};
+use server::rpc_core::request::Request;
+use server::rpc_core::response::Response;
+use starknet_core::transactions::Transaction;
+use starknet_core::StarknetBlock;
+use starknet_types::felt::TransactionHash;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::{error, trace, warn};
+
+use self::models::{
+    PendingTransactionsInput, SubscriptionId, SubscriptionNotification,
+    SubscriptionNotificationParams, SubscriptionNotificationResult, SubscriptionRequest,
+    TransactionStatusInput, TransactionStatusUpdate, UnsubscribeInput,
+};
+use crate::api::json_rpc::models::TransactionStatusOutput;
+use crate::api::json_rpc::JsonRpcHandler;
+use crate::api::models::block::BlockHeader;
+use crate::api::models::transaction::{
+    TransactionFinalityStatus, TransactionHashHex, TransactionWithType,
+};
+use crate::api::models::FeltHex;
+
+/// Subscription ids are unique across all connections
+static NEXT_SUBSCRIPTION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Error code defined by the specification for unknown subscriptions
+const INVALID_SUBSCRIPTION_ID_ERROR_CODE: i64 = 66;
+
+/// Subscription to blocks and transactions of the chain
+enum ChainSubscription {
+    NewHeads,
+    PendingTransactions(PendingTransactionsInput),
+}
+
+type ChainSubscriptions = Arc<Mutex<HashMap<SubscriptionId, ChainSubscription>>>;
+
+/// Subscriptions of a single connection
+#[derive(Default)]
+struct Subscriptions {
+    /// Subscriptions served by a task of their own
+    tasks: HashMap<SubscriptionId, JoinHandle<()>>,
+    /// Subscriptions to new heads and pending transactions. They are all served by a single task,
+    /// so that a pending transaction is always notified before the block including it
+    chain: ChainSubscriptions,
+    chain_task: Option<JoinHandle<()>>,
+}
+
+impl Subscriptions {
+    async fn subscribe_to_chain(
+        &mut self,
+        subscription: ChainSubscription,
+        handler: &JsonRpcHandler,
+        message_sender: &mpsc::UnboundedSender<String>,
+    ) -> SubscriptionId {
+        let subscription_id = NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::Relaxed);
+        self.chain
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(subscription_id, subscription);
+
+        if self.chain_task.is_none() {
+            // subscribing before responding, so that no event following the response is missed
+            let receivers = subscribe_to_chain_events(handler).await;
+            self.chain_task = Some(tokio::spawn(forward_chain_events(
+                self.chain.clone(),
+                receivers,
+                handler.clone(),
+                message_sender.clone(),
+            )));
+        }
+
+        subscription_id
+    }
+
+    /// Returns false if there is no active subscription with `subscription_id`
+    fn unsubscribe(&mut self, subscription_id: SubscriptionId) -> bool {
+        // subscriptions closed by Devnet can't be unsubscribed from
+        match self.tasks.remove(&subscription_id).filter(|task| !task.is_finished()) {
+            Some(task) => {
+                task.abort();
+                true
+            }
+            None => self
+                .chain
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .remove(&subscription_id)
+                .is_some(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.tasks.len() + self.chain.lock().unwrap_or_else(|err| err.into_inner()).len()
+    }
+
+    fn stop(self) {
+        for (_, task) in self.tasks {
+            task.abort();
+        }
+        if let Some(chain_task) = self.chain_task {
+            chain_task.abort();
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct IncomingCall {
+    #[serde(default)]
+    id: serde_json::Value,
+    method: String,
+    #[serde(default)]
+    params: Option<serde_json::Value>,
+}
+
+/// Upgrades the connection to a WebSocket, over which subscriptions and regular JSON-RPC calls are
+/// handled
+pub(crate) async fn ws_handler(
+    ws: WebSocketUpgrade,
+    Extension(handler): Extension<JsonRpcHandler>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(socket, handler))
+}
+
+async fn handle_socket(socket: WebSocket, handler: JsonRpcHandler) {
+    let (mut socket_sender, mut socket_receiver) = socket.split();
+    let (message_sender, mut message_receiver) = mpsc::unbounded_channel::<String>();
+
+    // responses and notifications are all written to the socket by a single task
+    let writer = tokio::spawn(async move {
+        while let Some(message) = message_receiver.recv().await {
+            if socket_sender.send(Message::Text(message)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut subscriptions = Subscriptions::default();
+    while let Some(Ok(message)) = socket_receiver.next().await {
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            // pings are answered by axum, other messages are not expected
+            _ => continue,
+        };
+
+        if let Some(response) =
+            handle_message(&text, &handler, &mut subscriptions, &message_sender).await
+        {
+            if message_sender.send(response).is_err() {
+                break;
+            }
+        }
+    }
+
+    trace!(target: "ws", "client disconnected, stopping {} subscriptions", subscriptions.len());
+    subscriptions.stop();
+    writer.abort();
+}
+
+/// Returns the serialized response to the incoming message, if there should be one
+async fn handle_message(
+    text: &str,
+    handler: &JsonRpcHandler,
+    subscriptions: &mut Subscriptions,
+    message_sender: &mpsc::UnboundedSender<String>,
+) -> Option<String> {
+    let subscription_request = serde_json::from_str::<IncomingCall>(text).ok().and_then(|call| {
+        let method_with_params = json!({
+            "method": call.method,
+            "params": call.params.unwrap_or_else(|| json!([])),
+        });
+        serde_json::from_value::<SubscriptionRequest>(method_with_params)
+            .ok()
+            .map(|request| (call.id, request))
+    });
+
+    let response = match subscription_request {
+        Some((id, SubscriptionRequest::NewHeads)) => {
+            let subscription_id = subscriptions
+                .subscribe_to_chain(ChainSubscription::NewHeads, handler, message_sender)
+                .await;
+
+            json!({ "jsonrpc": "2.0", "id": id, "result": subscription_id })
+        }
+        Some((id, SubscriptionRequest::PendingTransactions(input))) => {
+            let subscription_id = subscriptions
+                .subscribe_to_chain(
+                    ChainSubscription::PendingTransactions(input),
+                    handler,
+                    message_sender,
+                )
+                .await;
+
+            json!({ "jsonrpc": "2.0", "id": id, "result": subscription_id })
+        }
+        Some((
+            id,
+            SubscriptionRequest::TransactionStatus(TransactionStatusInput { transaction_hash }),
+        )) => {
+            let subscription_id = NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::Relaxed);
+            let (receiver, ttl) = {
+                let starknet = handler.api.starknet.read().await;
+                (
+                    starknet.subscribe_to_transaction_status_changes(),
+                    Duration::from_secs(starknet.config.transaction_subscription_ttl),
+                )
+            };
+            subscriptions.tasks.insert(
+                subscription_id,
+                tokio::spawn(forward_transaction_status(
+                    subscription_id,
+                    transaction_hash,
+                    handler.clone(),
+                    receiver,
+                    ttl,
+                    message_sender.clone(),
+                )),
+            );
+
+            json!({ "jsonrpc": "2.0", "id": id, "result": subscription_id })
+        }
+        Some((id, SubscriptionRequest::Unsubscribe(UnsubscribeInput { subscription_id }))) => {
+            if subscriptions.unsubscribe(subscription_id) {
+                json!({ "jsonrpc": "2.0", "id": id, "result": true })
+            } else {
+                let error = RpcError {
+                    code: ErrorCode::ServerError(INVALID_SUBSCRIPTION_ID_ERROR_CODE),
+                    message: "Invalid subscription id".into(),
+                    data: None,
+                };
+                json!({ "jsonrpc": "2.0", "id": id, "error": error })
+            }
+        }
+        // not a subscription method, so it is handled as a regular JSON-RPC call
+        None => {
+            let response = match serde_json::from_str::<Request>(text) {
+                Ok(request) => {
+                    server::rpc_handler::handle_request(request, handler.clone()).await?
+                }
+                Err(err) => {
+                    warn!(target: "ws", ?err, "invalid request");
+                    Response::error(RpcError::parse_error())
+                }
+            };
+
+            return serde_json::to_string(&response).ok();
+        }
+    };
+
+    Some(response.to_string())
+}
+
+/// Notifies the chain subscriptions of a connection of new blocks and pending transactions. When
+/// Devnet is restarted, the subscriptions are closed, as blocks of the previous chain are not
+/// followed by blocks of the restarted one.
+async fn forward_chain_events(
+    subscriptions: ChainSubscriptions,
+    (mut transactions, mut blocks): (
+        broadcast::Receiver<Transaction>,
+        broadcast::Receiver<StarknetBlock>,
+    ),
+    handler: JsonRpcHandler,
+    message_sender: mpsc::UnboundedSender<String>,
+) {
+    loop {
+        // a transaction is sent to subscribers before the block including it, so preferring
+        // transactions over blocks keeps the notifications in the order of the events
+        let notifications = tokio::select! {
+            biased;
+            transaction = transactions.recv() => transaction.map(|transaction| {
+                pending_transaction_notifications(&subscriptions, &transaction)
+            }),
+            block = blocks.recv() => {
+                block.map(|block| new_heads_notifications(&subscriptions, &block))
+            }
+        };
+
+        let notifications = match notifications {
+            Ok(notifications) => notifications,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(target: "ws", skipped, "subscriber is lagging behind");
+                continue;
+            }
+            Err(RecvError::Closed) => {
+                let closed = std::mem::take(
+                    &mut *subscriptions.lock().unwrap_or_else(|err| err.into_inner()),
+                );
+                let notifications = closed.into_keys().map(|subscription_id| {
+                    (
+                        "starknet_subscriptionClosed",
+                        SubscriptionNotificationParams {
+                            subscription_id,
+                            result: SubscriptionNotificationResult::Closed {
+                                reason: "Devnet was restarted".to_string(),
+                            },
+                        },
+                    )
+                });
+                // later subscriptions follow the restarted chain
+                (transactions, blocks) = subscribe_to_chain_events(&handler).await;
+                notifications.collect()
+            }
+        };
+
+        for (method, params) in notifications {
+            // the socket is closed, so there is no one to notify
+            if !send_notification(&message_sender, method, params) {
+                return;
+            }
+        }
+    }
+}
+
+async fn subscribe_to_chain_events(
+    handler: &JsonRpcHandler,
+) -> (broadcast::Receiver<Transaction>, broadcast::Receiver<StarknetBlock>) {
+    let starknet = handler.api.starknet.read().await;
+    (starknet.subscribe_to_pending_transactions(), starknet.subscribe_to_new_blocks())
+}
+
+fn new_heads_notifications(
+    subscriptions: &ChainSubscriptions,
+    block: &StarknetBlock,
+) -> Vec<(&'static str, SubscriptionNotificationParams)> {
+    let header = BlockHeader::from(block);
+    let subscriptions = subscriptions.lock().unwrap_or_else(|err| err.into_inner());
+    subscriptions
+        .iter()
+        .filter(|(_, subscription)| matches!(subscription, ChainSubscription::NewHeads))
+        .map(|(subscription_id, _)| {
+            (
+                "starknet_subscriptionNewHeads",
+                SubscriptionNotificationParams {
+                    subscription_id: *subscription_id,
+                    result: SubscriptionNotificationResult::NewHeads(header.clone()),
+                },
+            )
+        })
+        .collect()
+}
+
+fn pending_transaction_notifications(
+    subscriptions: &ChainSubscriptions,
+    transaction: &Transaction,
+) -> Vec<(&'static str, SubscriptionNotificationParams)> {
+    let Some(transaction_hash) = transaction.get_hash() else {
+        error!(target: "ws", "pending transaction has no generated hash");
+        return vec![];
+    };
+    let sender_address = transaction.sender_address().ok();
+    let subscriptions = subscriptions.lock().unwrap_or_else(|err| err.into_inner());
+
+    let mut notifications = vec![];
+    for (subscription_id, subscription) in subscriptions.iter() {
+        let ChainSubscription::PendingTransactions(input) = subscription else {
+            continue;
+        };
+        if let Some(sender_addresses) = &input.sender_address {
+            if !sender_addresses.iter().any(|address| Some(address.0) == sender_address) {
+                continue;
+            }
+        }
+
+        let result = if input.transaction_details {
+            match TransactionWithType::try_from(transaction) {
+                Ok(transaction) => {
+                    SubscriptionNotificationResult::PendingTransaction(Box::new(transaction))
+                }
+                Err(err) => {
+                    error!(target: "ws", ?err, "failed to convert pending transaction");
+                    continue;
+                }
+            }
+        } else {
+            SubscriptionNotificationResult::PendingTransactionHash(FeltHex(transaction_hash))
+        };
+        notifications.push((
+            "starknet_subscriptionPendingTransactions",
+            SubscriptionNotificationParams { subscription_id: *subscription_id, result },
+        ));
+    }
+
+    notifications
+}
+
+/// Notifies of the status of the transaction whenever it changes, until it is final. If the
+/// transaction is not known to Devnet within `ttl`, the subscription is closed.
+async fn forward_transaction_status(
+    subscription_id: SubscriptionId,
+    transaction_hash: TransactionHashHex,
+    handler: JsonRpcHandler,
+    mut receiver: broadcast::Receiver<TransactionHash>,
+    ttl: Duration,
+    message_sender: mpsc::UnboundedSender<String>,
+) {
+    let deadline = tokio::time::Instant::now() + ttl;
+    let mut last_status: Option<TransactionStatusOutput> = None;
+    let close_reason = loop {
+        // unknown transactions have no status yet
+        if let Ok(status) = handler.get_transaction_status_by_hash(transaction_hash).await {
+            if last_status.as_ref() != Some(&status) {
+                let is_final = status.finality_status != TransactionFinalityStatus::Received;
+                let sent = send_notification(
+                    &message_sender,
+                    "starknet_subscriptionTransactionStatus",
+                    SubscriptionNotificationParams {
+                        subscription_id,
+                        result: SubscriptionNotificationResult::TransactionStatus(
+                            TransactionStatusUpdate { transaction_hash, status: status.clone() },
+                        ),
+                    },
+                );
+                // the socket is closed, so there is no one to notify
+                if !sent {
+                    return;
+                }
+                if is_final {
+                    break "Transaction is final".to_string();
+                }
+                last_status = Some(status);
+            }
+        }
+
+        let status_change = wait_for_status_change(&mut receiver, transaction_hash.0);
+        let changed = match last_status {
+            Some(_) => status_change.await,
+            None => match tokio::time::timeout_at(deadline, status_change).await {
+                Ok(changed) => changed,
+                Err(_) => {
+                    break format!("Transaction not received within {}s", ttl.as_secs());
+                }
+            },
+        };
+        if !changed {
+            break "Devnet was restarted".to_string();
+        }
+    };
+
+    send_notification(
+        &message_sender,
+        "starknet_subscriptionClosed",
+        SubscriptionNotificationParams {
+            subscription_id,
+            result: SubscriptionNotificationResult::Closed { reason: close_reason },
+        },
+    );
+}
+
+/// Waits until the status of the transaction with `transaction_hash` may have changed. Returns
+/// false if no more changes are coming, because Devnet was restarted.
+async fn wait_for_status_change(
+    receiver: &mut broadcast::Receiver<TransactionHash>,
+    transaction_hash: TransactionHash,
+) -> bool {
+    loop {
+        match receiver.recv().await {
+            Ok(changed_hash) if changed_hash == transaction_hash => return true,
+            Ok(_) => continue,
+            // the change of the transaction may be among the skipped ones
+            Err(RecvError::Lagged(_)) => return true,
+            Err(RecvError::Closed) => return false,
+        }
+    }
+}
+
+/// Returns false if the socket is closed
+fn send_notification(
+    message_sender: &mpsc::UnboundedSender<String>,
+    method: &'static str,
+    params: SubscriptionNotificationParams,
+) -> bool {
+    match serde_json::to_string(&SubscriptionNotification::new(method, params)) {
+        Ok(notification) => message_sender.send(notification).is_ok(),
+        Err(err) => {
+            error!(target: "ws", ?err, "failed to serialize notification");
+            true
+        }
+    }
+}
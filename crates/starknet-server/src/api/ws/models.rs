@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::json_rpc::models::TransactionStatusOutput;
+use crate::api::models::block::BlockHeader;
+use crate::api::models::transaction::{TransactionHashHex, TransactionWithType};
+use crate::api::models::ContractAddressHex;
+
+pub type SubscriptionId = u64;
+
+/// Subscription related methods, all other methods are handled as regular JSON-RPC calls
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(tag = "method", content = "params")]
+pub enum SubscriptionRequest {
+    #[serde(
+        rename = "starknet_subscribeNewHeads",
+        with = "crate::api::serde_helpers::empty_params"
+    )]
+    NewHeads,
+    #[serde(rename = "starknet_subscribeTransactionStatus")]
+    TransactionStatus(TransactionStatusInput),
+    #[serde(rename = "starknet_subscribePendingTransactions")]
+    PendingTransactions(PendingTransactionsInput),
+    #[serde(rename = "starknet_unsubscribe")]
+    Unsubscribe(UnsubscribeInput),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct TransactionStatusInput {
+    pub transaction_hash: TransactionHashHex,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct PendingTransactionsInput {
+    /// Whether whole transactions are notified instead of their hashes
+    #[serde(default)]
+    pub transaction_details: bool,
+    /// If present, only transactions sent by one of these addresses are notified
+    #[serde(default)]
+    pub sender_address: Option<Vec<ContractAddressHex>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct UnsubscribeInput {
+    pub subscription_id: SubscriptionId,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum SubscriptionNotificationResult {
+    NewHeads(BlockHeader),
+    TransactionStatus(TransactionStatusUpdate),
+    PendingTransaction(Box<TransactionWithType>),
+    PendingTransactionHash(TransactionHashHex),
+    /// The subscription was ended by Devnet, not by the subscriber
+    Closed {
+        reason: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TransactionStatusUpdate {
+    pub transaction_hash: TransactionHashHex,
+    pub status: TransactionStatusOutput,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SubscriptionNotificationParams {
+    pub subscription_id: SubscriptionId,
+    pub result: SubscriptionNotificationResult,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SubscriptionNotification {
+    pub jsonrpc: &'static str,
+    pub method: &'static str,
+    pub params: SubscriptionNotificationParams,
+}
+
+impl SubscriptionNotification {
+    pub fn new(method: &'static str, params: SubscriptionNotificationParams) -> Self {
+        Self { jsonrpc: "2.0", method, params }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+
+    use super::{
+        PendingTransactionsInput, SubscriptionRequest, TransactionStatusInput, UnsubscribeInput,
+    };
+    use crate::api::models::{ContractAddressHex, FeltHex};
+
+    #[test]
+    fn deserialize_subscription_requests() {
+        let request = serde_json::from_str::<SubscriptionRequest>(
+            r#"{"method":"starknet_subscribeNewHeads","params":[]}"#,
+        )
+        .unwrap();
+        assert_eq!(request, SubscriptionRequest::NewHeads);
+
+        let request = serde_json::from_str::<SubscriptionRequest>(
+            r#"{"method":"starknet_unsubscribe","params":{"subscription_id":7}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SubscriptionRequest::Unsubscribe(UnsubscribeInput { subscription_id: 7 })
+        );
+
+        let request = serde_json::from_str::<SubscriptionRequest>(
+            r#"{"method":"starknet_subscribeTransactionStatus","params":{"transaction_hash":"0x7"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SubscriptionRequest::TransactionStatus(TransactionStatusInput {
+                transaction_hash: FeltHex(Felt::from(7))
+            })
+        );
+
+        let request = serde_json::from_str::<SubscriptionRequest>(
+            r#"{"method":"starknet_subscribePendingTransactions","params":[]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SubscriptionRequest::PendingTransactions(PendingTransactionsInput::default())
+        );
+
+        let request = serde_json::from_str::<SubscriptionRequest>(
+            r#"{"method":"starknet_subscribePendingTransactions","params":{"transaction_details":true,"sender_address":["0x3"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            SubscriptionRequest::PendingTransactions(PendingTransactionsInput {
+                transaction_details: true,
+                sender_address: Some(vec![ContractAddressHex(
+                    ContractAddress::new(Felt::from(3)).unwrap()
+                )]),
+            })
+        );
+
+        assert!(
+            serde_json::from_str::<SubscriptionRequest>(
+                r#"{"method":"starknet_chainId","params":[]}"#
+            )
+            .is_err()
+        );
+    }
+}
@@ -1,11 +1,28 @@
+use std::str::FromStr;
+
+use ::server::{HeaderValueWrapper, ServerConfig};
 use clap::Parser;
 use starknet_core::constants::{
-    DEVNET_DEFAULT_GAS_PRICE, DEVNET_DEFAULT_HOST, DEVNET_DEFAULT_INITIAL_BALANCE,
-    DEVNET_DEFAULT_PORT, DEVNET_DEFAULT_TIMEOUT, DEVNET_DEFAULT_TOTAL_ACCOUNTS,
+    DEVNET_DEFAULT_DATA_GAS_PRICE, DEVNET_DEFAULT_ETH_STRK_RATE, DEVNET_DEFAULT_GAS_PRICE,
+    DEVNET_DEFAULT_HOST, DEVNET_DEFAULT_INITIAL_BALANCE, DEVNET_DEFAULT_MAX_CALLDATA_LEN,
+    DEVNET_DEFAULT_MAX_SIGNATURE_LEN, DEVNET_DEFAULT_MAX_STEPS, DEVNET_DEFAULT_PORT,
+    DEVNET_DEFAULT_REQUEST_BODY_LIMIT, DEVNET_DEFAULT_RPC_PATH, DEVNET_DEFAULT_SEQUENCER_ADDRESS,
+    DEVNET_DEFAULT_TIMEOUT, DEVNET_DEFAULT_TOTAL_ACCOUNTS,
+    DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL, ERC20_CONTRACT_ADDRESS, ERC20_DECIMALS,
+    ETH_ERC20_NAME, ETH_ERC20_SYMBOL, STRK_ERC20_CONTRACT_ADDRESS, STRK_ERC20_NAME,
+    STRK_ERC20_SYMBOL,
+};
+use starknet_core::starknet::dump::DumpOn;
+use starknet_core::starknet::{
+    AccountClass, BlockGenerationOn, CalldataValidation, PendingOverflow, PredeclaredClassSet,
+    StarknetConfig, UndeployedStorageWrite,
 };
-use starknet_core::starknet::StarknetConfig;
-use starknet_in_rust::definitions::block_context::StarknetChainId;
+use starknet_rs_core::utils::cairo_short_string_to_felt;
+use starknet_types::chain_id::ChainId;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::Felt;
 use starknet_types::num_bigint::BigUint;
+use url::Url;
 
 /// Run a local instance of Starknet Devnet
 #[derive(Parser, Debug)]
@@ -16,7 +33,8 @@ pub(crate) struct Args {
     #[arg(long = "accounts")]
     #[arg(value_name = "ACCOUNTS")]
     #[arg(default_value_t = DEVNET_DEFAULT_TOTAL_ACCOUNTS)]
-    #[arg(help = "Specify the number of accounts to be predeployed;")]
+    #[arg(value_parser = clap::value_parser!(u8).range(1..))]
+    #[arg(help = "Specify the number of accounts to be predeployed; must be at least 1;")]
     accounts_count: u8,
 
     /// Initial balance of predeployed accounts
@@ -31,7 +49,8 @@ pub(crate) struct Args {
     #[arg(long = "seed")]
     #[arg(value_name = "SEED")]
     #[arg(help = "Specify the seed for randomness of accounts to be predeployed; if not \
-                  provided, it is randomly generated")]
+                  provided, it is randomly generated and logged at startup, so that the same \
+                  accounts can be reproduced by passing it;")]
     seed: Option<u32>,
 
     // Host address
@@ -45,16 +64,74 @@ pub(crate) struct Args {
     #[arg(long = "port")]
     #[arg(value_name = "PORT")]
     #[arg(default_value_t = DEVNET_DEFAULT_PORT)]
-    #[arg(help = "Specify the port to listen at;")]
+    #[arg(help = "Specify the port to listen at; if 0, a free port is assigned by the OS;")]
     port: u16,
 
+    // File the bound port is written to
+    #[arg(long = "port-file")]
+    #[arg(value_name = "PATH")]
+    #[arg(help = "Specify the path of a file the port is written to once Devnet listens;")]
+    port_file: Option<String>,
+
+    // Path of the JSON-RPC handler
+    #[arg(long = "rpc-path")]
+    #[arg(value_name = "PATH")]
+    #[arg(default_value = DEVNET_DEFAULT_RPC_PATH)]
+    #[arg(value_parser = parse_rpc_path)]
+    #[arg(help = "Specify the path the JSON-RPC handler is served at, e.g. /rpc/v0_6; requests \
+                  to other paths are not handled as JSON-RPC;")]
+    rpc_path: String,
+
+    // Prefix of the Devnet-specific endpoints
+    #[arg(long = "devnet-path")]
+    #[arg(value_name = "PATH")]
+    #[arg(default_value = "")]
+    #[arg(value_parser = parse_devnet_path)]
+    #[arg(help = "Specify the prefix of the paths of Devnet-specific endpoints, such as /mint \
+                  or /dump; by default they are served at the root;")]
+    devnet_path: String,
+
     // Server timeout in seconds
     #[arg(long = "timeout")]
+    #[arg(visible_alias = "request-timeout-secs")]
     #[arg(value_name = "TIMEOUT")]
     #[arg(default_value_t = DEVNET_DEFAULT_TIMEOUT)]
     #[arg(help = "Specify the server timeout in seconds;")]
     timeout: u16,
 
+    // Max request body size in bytes
+    #[arg(long = "request-body-limit")]
+    #[arg(value_name = "BYTES")]
+    #[arg(default_value_t = DEVNET_DEFAULT_REQUEST_BODY_LIMIT)]
+    #[arg(help = "Specify the max size of a request body in bytes;")]
+    request_body_limit: usize,
+
+    // Max requests per second per client IP
+    #[arg(long = "rate-limit")]
+    #[arg(value_name = "REQUESTS_PER_SEC")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    #[arg(help = "Specify the max number of requests per second accepted from a single client \
+                  IP; unlimited if not provided;")]
+    rate_limit: Option<u32>,
+
+    // Origins allowed to send cross-origin requests
+    #[arg(long = "cors-origins")]
+    #[arg(value_name = "ORIGINS")]
+    #[arg(value_delimiter = ',')]
+    #[arg(help = "Specify the comma-separated origins allowed to send cross-origin requests, or \
+                  * to allow any origin; credentialed requests are allowed only from listed \
+                  origins; any origin is allowed if not provided;")]
+    cors_origins: Option<Vec<HeaderValueWrapper>>,
+
+    // Lifetime of subscriptions to unknown transactions in seconds
+    #[arg(long = "transaction-subscription-ttl")]
+    #[arg(value_name = "SECONDS")]
+    #[arg(default_value_t = DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL)]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(help = "Specify for how many seconds a WebSocket subscription to the status of a \
+                  transaction is kept open while the transaction is unknown to Devnet;")]
+    transaction_subscription_ttl: u64,
+
     // Gas price in wei
     #[arg(long = "gas-price")]
     #[arg(value_name = "GAS_PRICE")]
@@ -62,12 +139,436 @@ pub(crate) struct Args {
     #[arg(help = "Specify the gas price in wei per gas unit;")]
     gas_price: u64,
 
-    // Chain id as string
+    // Data gas price in wei
+    #[arg(long = "data-gas-price")]
+    #[arg(value_name = "DATA_GAS_PRICE")]
+    #[arg(default_value_t = DEVNET_DEFAULT_DATA_GAS_PRICE)]
+    #[arg(help = "Specify the data gas price in wei per data gas unit;")]
+    data_gas_price: u64,
+
+    // Conversion rate between wei and fri
+    #[arg(long = "eth-strk-rate")]
+    #[arg(value_name = "RATE")]
+    #[arg(default_value_t = DEVNET_DEFAULT_ETH_STRK_RATE)]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(help = "Specify how many fri one wei is worth; gas prices in fri are the gas prices \
+                  in wei multiplied by this rate;")]
+    eth_strk_rate: u64,
+
+    // Max Cairo steps of a transaction or a call
+    #[arg(long = "max-steps")]
+    #[arg(value_name = "MAX_STEPS")]
+    #[arg(default_value_t = DEVNET_DEFAULT_MAX_STEPS)]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(help = "Specify the max number of Cairo steps of executing a transaction or a call, \
+                  nested calls included; exceeding it fails the execution;")]
+    max_steps: u64,
+
+    // Max calldata length of a submitted transaction
+    #[arg(long = "max-calldata-len")]
+    #[arg(value_name = "LENGTH")]
+    #[arg(default_value_t = DEVNET_DEFAULT_MAX_CALLDATA_LEN)]
+    #[arg(help = "Specify the max number of calldata elements of a submitted invoke or \
+                  deploy_account transaction; longer transactions are rejected before execution;")]
+    max_calldata_len: usize,
+
+    // Max signature length of a submitted transaction
+    #[arg(long = "max-signature-len")]
+    #[arg(value_name = "LENGTH")]
+    #[arg(default_value_t = DEVNET_DEFAULT_MAX_SIGNATURE_LEN)]
+    #[arg(help = "Specify the max number of signature elements of a submitted transaction; \
+                  longer transactions are rejected before execution;")]
+    max_signature_len: usize,
+
+    // Chain id as a network name or a raw value
     #[arg(long = "chain-id")]
     #[arg(value_name = "CHAIN_ID")]
     #[arg(default_value = "TESTNET")]
-    #[arg(help = "Specify the chain id as one of: {MAINNET, TESTNET, TESTNET2};")]
-    chain_id: String,
+    #[arg(help = "Specify the chain id as one of: {MAINNET, TESTNET, TESTNET2, SEPOLIA, \
+                  SEPOLIA_INTEGRATION}, or as a 0x-prefixed hex value or an ASCII string of at \
+                  most 31 characters;")]
+    chain_id: ChainId,
+
+    // Number of the genesis block
+    #[arg(long = "initial-block-number")]
+    #[arg(value_name = "NUMBER")]
+    #[arg(default_value_t = 0)]
+    #[arg(
+        help = "Specify the number of the genesis block; subsequent blocks are numbered from it;"
+    )]
+    initial_block_number: u64,
+
+    // Timestamp of the genesis block
+    #[arg(long = "initial-block-timestamp")]
+    #[arg(value_name = "SECONDS")]
+    #[arg(help = "Specify the UNIX timestamp of the genesis block; subsequent blocks advance \
+                  from it; by default the genesis block is timestamped 0 and subsequent blocks \
+                  take the time of the system clock;")]
+    initial_block_timestamp: Option<u64>,
+
+    // Block number from which syncing is faked
+    #[arg(long = "fake-sync-from")]
+    #[arg(value_name = "BLOCK")]
+    #[arg(requires = "fake_sync_until")]
+    #[arg(help = "Make starknet_syncing report progress starting from block number BLOCK; by \
+                  default Devnet always reports being synced;")]
+    fake_sync_from: Option<u64>,
+
+    // Block number until which syncing is faked
+    #[arg(long = "fake-sync-until")]
+    #[arg(value_name = "BLOCK")]
+    #[arg(requires = "fake_sync_from")]
+    #[arg(help = "Specify the highest block reported while faking syncing; once block number \
+                  BLOCK is mined, Devnet reports being synced again;")]
+    fake_sync_until: Option<u64>,
+
+    // When to dump the state
+    #[arg(long = "dump-on")]
+    #[arg(value_name = "WHEN")]
+    #[arg(requires = "dump_path")]
+    #[arg(help = "Specify when to dump the state of Devnet to DUMP_PATH; one of: {exit, \
+                  transaction};")]
+    dump_on: Option<DumpOn>,
+
+    // Path of the dump file
+    #[arg(long = "dump-path")]
+    #[arg(value_name = "DUMP_PATH")]
+    #[arg(help = "Specify the path of the file the state is dumped to;")]
+    dump_path: Option<String>,
+
+    // Commit pending transactions on shutdown
+    #[arg(long = "commit-on-shutdown")]
+    #[arg(help = "On SIGTERM or SIGINT, commit pending transactions to a new block before \
+                  dumping on exit;")]
+    commit_on_shutdown: bool,
+
+    // Directory of persisted blocks
+    #[arg(long = "state-dir")]
+    #[arg(value_name = "PATH")]
+    #[arg(help = "Specify the directory where committed blocks are persisted; blocks already \
+                  persisted there are loaded on startup;")]
+    state_dir: Option<String>,
+
+    // URL of the forked network
+    #[arg(long = "fork-network")]
+    #[arg(value_name = "URL")]
+    #[arg(help = "Specify the URL of the JSON-RPC node of the network to fork from; state \
+                  missing locally is fetched from it;")]
+    fork_network: Option<Url>,
+
+    // Block number of the forked network
+    #[arg(long = "fork-block")]
+    #[arg(value_name = "BLOCK_NUMBER")]
+    #[arg(requires = "fork_network")]
+    #[arg(help = "Specify the number of the block to fork from; defaults to the latest block of \
+                  the forked network;")]
+    fork_block: Option<u64>,
+
+    // Impersonation of all accounts
+    #[arg(long = "auto-impersonate")]
+    #[arg(help = "Execute transactions of all accounts without validating their signatures, as \
+                  if every account was impersonated;")]
+    auto_impersonate: bool,
+
+    // Funding of impersonated accounts
+    #[arg(long = "impersonate-auto-fund")]
+    #[arg(value_name = "AMOUNT")]
+    #[arg(help = "Before executing an invoke transaction of an impersonated account, top up its \
+                  balance in the fee token of the transaction to AMOUNT, in WEI or FRI, by \
+                  minting the difference; not done if not provided;")]
+    impersonate_auto_fund: Option<u128>,
+
+    // Execution without fee charging
+    #[arg(long = "disable-fee-charge")]
+    #[arg(help = "Execute invoke, declare and deploy account transactions without checking max \
+                  fee and without deducting the fee from the sender's balance; actual fee is \
+                  still reported;")]
+    disable_fee_charge: bool,
+
+    // Fee estimation without validation by default
+    #[arg(long = "estimate-skip-validate")]
+    #[arg(help = "Skip validation in starknet_estimateFee and starknet_simulateTransactions \
+                  requests that don't specify simulation_flags; flags given explicitly, even an \
+                  empty list, are used as they are; transactions are still validated when \
+                  executed;")]
+    estimate_skip_validate: bool,
+
+    // Declaring without a signed transaction
+    #[arg(long = "allow-dev-declare")]
+    #[arg(help = "Enable POST /declare, which declares a Sierra class and its CASM on behalf of \
+                  a predeployed account, signing the transaction on the user's behalf;")]
+    allow_dev_declare: bool,
+
+    // Deploying without a signed transaction
+    #[arg(long = "allow-dev-deploy")]
+    #[arg(help = "Enable POST /deploy, which deploys a declared class through the UDC on behalf \
+                  of a predeployed account, signing the transaction on the user's behalf;")]
+    allow_dev_deploy: bool,
+
+    // Writing storage without a transaction
+    #[arg(long = "allow-dev-state")]
+    #[arg(help = "Enable POST /set_storage_at, which writes a value to contract storage without \
+                  a transaction and generates a block recording the write;")]
+    allow_dev_state: bool,
+
+    // Handling of storage writes to undeployed contracts
+    #[arg(long = "undeployed-storage-write")]
+    #[arg(value_name = "HANDLING")]
+    #[arg(default_value = "warn")]
+    #[arg(help = "Specify what happens when POST /set_storage_at writes to an address without a \
+                  deployed contract; one of: {warn, reject}; warn writes the value and logs a \
+                  warning;")]
+    undeployed_storage_write: UndeployedStorageWrite,
+
+    // Reporting of calldata not matching the parameters of entry points
+    #[arg(long = "validation")]
+    #[arg(value_name = "MODE")]
+    #[arg(default_value = "strict")]
+    #[arg(help = "Specify how calls and invoke transactions failing because their calldata \
+                  doesn't match the parameters of a Cairo 1 entry point are reported; one of: \
+                  {strict, permissive}; strict reports the failure as on mainnet, permissive \
+                  adds guidance on fixing the calldata;")]
+    calldata_validation: CalldataValidation,
+
+    // Standard classes declared at genesis
+    #[arg(long = "predeclare")]
+    #[arg(value_name = "SET")]
+    #[arg(help = "Expose the class hashes of a set of standard classes declared at genesis in \
+                  GET /config, so that they can be deployed without declaring them; one of: \
+                  {standard}; standard holds the OpenZeppelin ERC20 and the UDC;")]
+    predeclare: Option<PredeclaredClassSet>,
+
+    // Skipping state root computation
+    #[arg(long = "lite-mode")]
+    #[arg(help = "Skip computing the global state root of generated blocks, reporting it as \
+                  zero, to speed up block generation; disables starknet_getStorageProof;")]
+    lite_mode: bool,
+
+    // When to generate blocks
+    #[arg(long = "block-generation-on")]
+    #[arg(value_name = "WHEN")]
+    #[arg(default_value = "transaction")]
+    #[arg(help = "Specify when to generate a new block; one of: {transaction, demand}; in \
+                  demand mode, blocks are only generated through POST /create_block;")]
+    block_generation_on: BlockGenerationOn,
+
+    // Interval of timed block generation
+    #[arg(long = "block-time-secs")]
+    #[arg(value_name = "SECONDS")]
+    #[arg(default_value_t = 0)]
+    #[arg(help = "Specify the interval in seconds at which a new block is generated, even if it \
+                  holds no transactions; in demand mode, POST /create_block still works between \
+                  the generated blocks; 0 disables timed block generation;")]
+    block_time_secs: u64,
+
+    // Parallel execution of invoke transactions
+    #[arg(long = "parallel-execution")]
+    #[arg(help = "In demand mode, queue invoke transactions of version 1 and execute them in \
+                  parallel when the block is created; the resulting state is the same as if \
+                  they were executed one by one;")]
+    parallel_execution: bool,
+
+    // Limit of pending transactions in demand mode
+    #[arg(long = "max-pending-transactions")]
+    #[arg(value_name = "NUMBER")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(help = "In demand mode, specify how many transactions can be pending, queued ones \
+                  included; what happens to further transactions is set with \
+                  --pending-overflow; unlimited if not provided;")]
+    max_pending_transactions: Option<u64>,
+
+    // Handling of a full pending block
+    #[arg(long = "pending-overflow")]
+    #[arg(value_name = "HANDLING")]
+    #[arg(default_value = "seal")]
+    #[arg(help = "Specify what happens once --max-pending-transactions are pending; one of: \
+                  {seal, reject}; seal creates a block right away, reject rejects new \
+                  transactions until POST /create_block is called;")]
+    pending_overflow: PendingOverflow,
+
+    // Class of predeployed accounts
+    #[arg(long = "account-class")]
+    #[arg(value_name = "CLASS")]
+    #[arg(default_value = "cairo0")]
+    #[arg(help = "Specify the class used by predeployed accounts; one of: {cairo0, cairo1};")]
+    account_class: AccountClass,
+
+    // Custom class of predeployed accounts
+    #[arg(long = "account-class-custom")]
+    #[arg(value_name = "PATH")]
+    #[arg(conflicts_with = "account_class")]
+    #[arg(help = "Specify the path to a Sierra artifact to be used by predeployed accounts;")]
+    account_class_custom: Option<String>,
+
+    // Accounts predeployed at given addresses
+    #[arg(long = "predeploy-account")]
+    #[arg(value_name = "PATH")]
+    #[arg(help = "Specify the path to a JSON file listing accounts to be predeployed besides \
+                  the generated ones; every entry has an address, public_key, balance and \
+                  class_hash;")]
+    predeployed_accounts_path: Option<String>,
+
+    // Skip generating accounts
+    #[arg(long = "no-auto-accounts")]
+    #[arg(requires = "predeployed_accounts_path")]
+    #[arg(help = "Don't generate accounts; only the ones listed with --predeploy-account are \
+                  predeployed;")]
+    no_auto_accounts: bool,
+
+    // Hide private keys of predeployed accounts
+    #[arg(long = "hide-predeployed-keys")]
+    #[arg(help = "Don't print nor return private keys of predeployed accounts;")]
+    hide_predeployed_keys: bool,
+
+    // URL of the L1 node
+    #[arg(long = "l1-node-url")]
+    #[arg(value_name = "URL")]
+    #[arg(help = "Specify the URL of the L1 node to which messages sent to L1 are submitted on \
+                  POST /postman/flush;")]
+    l1_node_url: Option<Url>,
+
+    // Address of the ETH fee token
+    #[arg(long = "eth-fee-token-address")]
+    #[arg(value_name = "ADDRESS")]
+    #[arg(default_value = ERC20_CONTRACT_ADDRESS)]
+    #[arg(value_parser = parse_contract_address)]
+    #[arg(help = "Specify the address at which the ETH fee token is deployed; fees are charged \
+                  and WEI is minted in it; defaults to its address on mainnet and testnets;")]
+    eth_fee_token_address: ContractAddress,
+
+    // Address of the STRK fee token
+    #[arg(long = "strk-fee-token-address")]
+    #[arg(value_name = "ADDRESS")]
+    #[arg(default_value = STRK_ERC20_CONTRACT_ADDRESS)]
+    #[arg(value_parser = parse_contract_address)]
+    #[arg(help = "Specify the address at which the STRK fee token is deployed; FRI is minted in \
+                  it; defaults to its address on mainnet and testnets;")]
+    strk_fee_token_address: ContractAddress,
+
+    // Metadata of the ETH fee token
+    #[arg(long = "eth-token-name")]
+    #[arg(value_name = "NAME")]
+    #[arg(default_value = ETH_ERC20_NAME)]
+    #[arg(value_parser = parse_short_string)]
+    #[arg(help = "Specify the name of the ETH fee token, returned by its `name` entry point; up \
+                  to 31 ASCII characters;")]
+    eth_token_name: String,
+
+    #[arg(long = "eth-token-symbol")]
+    #[arg(value_name = "SYMBOL")]
+    #[arg(default_value = ETH_ERC20_SYMBOL)]
+    #[arg(value_parser = parse_short_string)]
+    #[arg(help = "Specify the symbol of the ETH fee token, returned by its `symbol` entry \
+                  point; up to 31 ASCII characters;")]
+    eth_token_symbol: String,
+
+    #[arg(long = "eth-token-decimals")]
+    #[arg(value_name = "DECIMALS")]
+    #[arg(default_value_t = ERC20_DECIMALS)]
+    #[arg(help = "Specify the decimals of the ETH fee token;")]
+    eth_token_decimals: u8,
+
+    // Metadata of the STRK fee token
+    #[arg(long = "strk-token-name")]
+    #[arg(value_name = "NAME")]
+    #[arg(default_value = STRK_ERC20_NAME)]
+    #[arg(value_parser = parse_short_string)]
+    #[arg(help = "Specify the name of the STRK fee token, returned by its `name` entry point; \
+                  up to 31 ASCII characters;")]
+    strk_token_name: String,
+
+    #[arg(long = "strk-token-symbol")]
+    #[arg(value_name = "SYMBOL")]
+    #[arg(default_value = STRK_ERC20_SYMBOL)]
+    #[arg(value_parser = parse_short_string)]
+    #[arg(help = "Specify the symbol of the STRK fee token, returned by its `symbol` entry \
+                  point; up to 31 ASCII characters;")]
+    strk_token_symbol: String,
+
+    #[arg(long = "strk-token-decimals")]
+    #[arg(value_name = "DECIMALS")]
+    #[arg(default_value_t = ERC20_DECIMALS)]
+    #[arg(help = "Specify the decimals of the STRK fee token;")]
+    strk_token_decimals: u8,
+
+    // Address of the sequencer
+    #[arg(long = "sequencer-address")]
+    #[arg(value_name = "ADDRESS")]
+    #[arg(default_value = DEVNET_DEFAULT_SEQUENCER_ADDRESS)]
+    #[arg(value_parser = parse_contract_address)]
+    #[arg(help = "Specify the address of the sequencer in block headers, which contracts get \
+                  from get_sequencer_address and to which fees are transferred;")]
+    sequencer_address: ContractAddress,
+
+    // Collection of metrics
+    #[arg(long = "enable-metrics")]
+    #[arg(help = "Collect metrics of the processed transactions and expose them, with the \
+                  current block number and pending pool size, via GET /metrics in the \
+                  Prometheus format;")]
+    enable_metrics: bool,
+
+    // Format of the logs
+    #[arg(long = "log-format")]
+    #[arg(value_name = "FORMAT")]
+    #[arg(default_value = "text")]
+    #[arg(help = "Specify the format of the logs; one of: {text, json}; in json format, every \
+                  log line is a JSON object;")]
+    pub(crate) log_format: LogFormat,
+}
+
+/// Format in which Devnet writes its logs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, for log aggregators
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!("Invalid log format: {other}; expected one of: text, json")),
+        }
+    }
+}
+
+fn parse_contract_address(address: &str) -> Result<ContractAddress, String> {
+    Felt::from_prefixed_hex_str(address)
+        .and_then(ContractAddress::new)
+        .map_err(|err| format!("Invalid contract address: {err}"))
+}
+
+/// Accepts absolute paths, dropping a trailing slash unless the path is the root
+fn parse_rpc_path(path: &str) -> Result<String, String> {
+    if !path.starts_with('/') {
+        return Err(format!("Invalid path: {path}; expected a path starting with /"));
+    }
+    match path.trim_end_matches('/') {
+        "" => Ok("/".to_string()),
+        trimmed => Ok(trimmed.to_string()),
+    }
+}
+
+/// Like `parse_rpc_path`, but the root is represented by an empty prefix, so that the paths of
+/// the endpoints can be appended to it
+fn parse_devnet_path(path: &str) -> Result<String, String> {
+    if path.is_empty() {
+        return Ok(String::new());
+    }
+    parse_rpc_path(path).map(|path| path.trim_end_matches('/').to_string())
+}
+
+/// Accepts strings that fit in a felt as Cairo short strings
+fn parse_short_string(value: &str) -> Result<String, String> {
+    cairo_short_string_to_felt(value)
+        .map(|_| value.to_string())
+        .map_err(|err| format!("Invalid short string: {err}"))
 }
 
 impl Args {
@@ -83,16 +584,758 @@ impl Args {
                 .clone()
                 .try_into()
                 .expect("Invalid value for initial balance"), // TODO: Doesn't exit nicely.
+            predeployed_accounts_path: self.predeployed_accounts_path.clone(),
+            no_auto_accounts: self.no_auto_accounts,
             host: self.host.to_string(),
             port: self.port, // TODO: Unification of parsing messages for host and port.
+            port_file: self.port_file.clone(),
+            rpc_path: self.rpc_path.clone(),
+            devnet_path: self.devnet_path.clone(),
             timeout: self.timeout,
+            request_body_limit: self.request_body_limit,
+            rate_limit: self.rate_limit,
+            transaction_subscription_ttl: self.transaction_subscription_ttl,
             gas_price: self.gas_price,
-            chain_id: match self.chain_id.as_str() {
-                "MAINNET" => StarknetChainId::MainNet,
-                "TESTNET" => StarknetChainId::TestNet,
-                "TESTNET2" => StarknetChainId::TestNet2,
-                _ => panic!("Invalid value for chain-id"),
-            },
+            data_gas_price: self.data_gas_price,
+            eth_strk_rate: self.eth_strk_rate,
+            max_steps: self.max_steps,
+            max_calldata_len: self.max_calldata_len,
+            max_signature_len: self.max_signature_len,
+            chain_id: self.chain_id,
+            initial_block_number: self.initial_block_number,
+            initial_block_timestamp: self.initial_block_timestamp,
+            fake_sync_from: self.fake_sync_from,
+            fake_sync_until: self.fake_sync_until,
+            dump_on: self.dump_on,
+            dump_path: self.dump_path.clone(),
+            commit_on_shutdown: self.commit_on_shutdown,
+            state_dir: self.state_dir.clone(),
+            fork_network: self.fork_network.as_ref().map(Url::to_string),
+            fork_block: self.fork_block,
+            auto_impersonate: self.auto_impersonate,
+            impersonate_auto_fund: self.impersonate_auto_fund,
+            disable_fee_charge: self.disable_fee_charge,
+            estimate_skip_validate: self.estimate_skip_validate,
+            allow_dev_declare: self.allow_dev_declare,
+            allow_dev_deploy: self.allow_dev_deploy,
+            allow_dev_state: self.allow_dev_state,
+            undeployed_storage_write: self.undeployed_storage_write,
+            calldata_validation: self.calldata_validation,
+            predeclare: self.predeclare,
+            lite_mode: self.lite_mode,
+            block_generation_on: self.block_generation_on,
+            block_time_secs: self.block_time_secs,
+            parallel_execution: self.parallel_execution,
+            max_pending_transactions: self.max_pending_transactions,
+            pending_overflow: self.pending_overflow,
+            account_class: self.account_class,
+            account_class_custom: self.account_class_custom.clone(),
+            hide_predeployed_keys: self.hide_predeployed_keys,
+            l1_node_url: self.l1_node_url.as_ref().map(Url::to_string),
+            eth_fee_token_address: self.eth_fee_token_address,
+            strk_fee_token_address: self.strk_fee_token_address,
+            eth_token_name: self.eth_token_name.clone(),
+            eth_token_symbol: self.eth_token_symbol.clone(),
+            eth_token_decimals: self.eth_token_decimals,
+            strk_token_name: self.strk_token_name.clone(),
+            strk_token_symbol: self.strk_token_symbol.clone(),
+            strk_token_decimals: self.strk_token_decimals,
+            sequencer_address: self.sequencer_address,
+            enable_metrics: self.enable_metrics,
+        }
+    }
+
+    pub(crate) fn to_server_config(&self) -> ServerConfig {
+        match &self.cors_origins {
+            Some(allow_origins) => ServerConfig { allow_origins: allow_origins.clone() },
+            None => ServerConfig::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use starknet_core::starknet::dump::DumpOn;
+    use starknet_core::starknet::{
+        default_sequencer_address, AccountClass, BlockGenerationOn, CalldataValidation, FeeUnit,
+        PendingOverflow, PredeclaredClassSet, UndeployedStorageWrite,
+    };
+    use starknet_types::chain_id::ChainId;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+
+    use super::{
+        Args, LogFormat, DEVNET_DEFAULT_ETH_STRK_RATE, DEVNET_DEFAULT_MAX_CALLDATA_LEN,
+        DEVNET_DEFAULT_MAX_SIGNATURE_LEN, DEVNET_DEFAULT_MAX_STEPS,
+        DEVNET_DEFAULT_REQUEST_BODY_LIMIT, DEVNET_DEFAULT_TIMEOUT,
+        DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL,
+    };
+
+    #[test]
+    fn valid_number_of_accounts_accepted() {
+        let args = Args::try_parse_from(["starknet-devnet", "--accounts", "3"]).unwrap();
+        assert_eq!(args.to_starknet_config().total_accounts, 3);
+    }
+
+    #[test]
+    fn dump_on_parsed_together_with_dump_path() {
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--dump-on",
+            "exit",
+            "--dump-path",
+            "dump.json",
+        ])
+        .unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.dump_on, Some(DumpOn::Exit));
+        assert_eq!(config.dump_path, Some("dump.json".to_string()));
+    }
+
+    #[test]
+    fn initial_block_number_and_timestamp_parsed() {
+        let config = Args::try_parse_from(["starknet-devnet"]).unwrap().to_starknet_config();
+        assert_eq!(config.initial_block_number, 0);
+        assert_eq!(config.initial_block_timestamp, None);
+
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--initial-block-number",
+            "1000",
+            "--initial-block-timestamp",
+            "1700000000",
+        ])
+        .unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.initial_block_number, 1000);
+        assert_eq!(config.initial_block_timestamp, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn port_zero_and_port_file_parsed() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert_eq!(args.to_starknet_config().port_file, None);
+
+        let args =
+            Args::try_parse_from(["starknet-devnet", "--port", "0", "--port-file", "port.txt"])
+                .unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.port, 0);
+        assert_eq!(config.port_file, Some("port.txt".to_string()));
+    }
+
+    #[test]
+    fn commit_on_shutdown_parsed() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert!(!args.to_starknet_config().commit_on_shutdown);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--commit-on-shutdown"]).unwrap();
+        assert!(args.to_starknet_config().commit_on_shutdown);
+    }
+
+    #[test]
+    fn dump_on_without_dump_path_rejected() {
+        match Args::try_parse_from(["starknet-devnet", "--dump-on", "transaction"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn invalid_dump_on_rejected() {
+        match Args::try_parse_from(["starknet-devnet", "--dump-on", "block", "--dump-path", "d"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn state_dir_parsed() {
+        let args =
+            Args::try_parse_from(["starknet-devnet", "--state-dir", "devnet-state"]).unwrap();
+        assert_eq!(args.to_starknet_config().state_dir, Some("devnet-state".to_string()));
+        assert_eq!(Args::parse_from(["starknet-devnet"]).to_starknet_config().state_dir, None);
+    }
+
+    #[test]
+    fn log_format_parsed() {
+        assert_eq!(Args::parse_from(["starknet-devnet"]).log_format, LogFormat::Text);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--log-format", "json"]).unwrap();
+        assert_eq!(args.log_format, LogFormat::Json);
+
+        match Args::try_parse_from(["starknet-devnet", "--log-format", "xml"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn request_limits_parsed() {
+        let config = Args::parse_from(["starknet-devnet"]).to_starknet_config();
+        assert_eq!(config.request_body_limit, DEVNET_DEFAULT_REQUEST_BODY_LIMIT);
+        assert_eq!(config.timeout, DEVNET_DEFAULT_TIMEOUT);
+        assert_eq!(config.rate_limit, None);
+
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--request-body-limit",
+            "1000",
+            "--request-timeout-secs",
+            "5",
+            "--rate-limit",
+            "20",
+        ])
+        .unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.request_body_limit, 1000);
+        assert_eq!(config.timeout, 5);
+        assert_eq!(config.rate_limit, Some(20));
+
+        assert!(Args::try_parse_from(["starknet-devnet", "--rate-limit", "0"]).is_err());
+    }
+
+    #[test]
+    fn route_paths_parsed() {
+        let config = Args::parse_from(["starknet-devnet"]).to_starknet_config();
+        assert_eq!(config.rpc_path, "/rpc");
+        assert_eq!(config.devnet_path, "");
+
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--rpc-path",
+            "/rpc/v0_6/",
+            "--devnet-path",
+            "/devnet",
+        ])
+        .unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.rpc_path, "/rpc/v0_6");
+        assert_eq!(config.devnet_path, "/devnet");
+
+        let config = Args::parse_from(["starknet-devnet", "--rpc-path", "/", "--devnet-path", "/"])
+            .to_starknet_config();
+        assert_eq!(config.rpc_path, "/");
+        assert_eq!(config.devnet_path, "");
+
+        assert!(Args::try_parse_from(["starknet-devnet", "--rpc-path", "rpc"]).is_err());
+        assert!(Args::try_parse_from(["starknet-devnet", "--devnet-path", "devnet"]).is_err());
+    }
+
+    #[test]
+    fn cors_origins_parsed() {
+        let config = Args::parse_from(["starknet-devnet"]).to_server_config();
+        assert_eq!(config.allow_origins.len(), 1);
+        assert_eq!(*config.allow_origins[0], "*");
+
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--cors-origins",
+            "http://localhost:3000,https://example.com",
+        ])
+        .unwrap();
+        let config = args.to_server_config();
+        assert_eq!(config.allow_origins.len(), 2);
+        assert_eq!(*config.allow_origins[0], "http://localhost:3000");
+        assert_eq!(*config.allow_origins[1], "https://example.com");
+
+        assert!(Args::try_parse_from(["starknet-devnet", "--cors-origins", "http://a\nb"]).is_err());
+    }
+
+    #[test]
+    fn transaction_subscription_ttl_parsed() {
+        let config = Args::parse_from(["starknet-devnet"]).to_starknet_config();
+        assert_eq!(
+            config.transaction_subscription_ttl,
+            DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL
+        );
+
+        let config = Args::parse_from(["starknet-devnet", "--transaction-subscription-ttl", "5"])
+            .to_starknet_config();
+        assert_eq!(config.transaction_subscription_ttl, 5);
+
+        assert!(
+            Args::try_parse_from(["starknet-devnet", "--transaction-subscription-ttl", "0"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn fork_network_parsed_together_with_fork_block() {
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--fork-network",
+            "http://localhost:9545",
+            "--fork-block",
+            "42",
+        ])
+        .unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.fork_network, Some("http://localhost:9545/".to_string()));
+        assert_eq!(config.fork_block, Some(42));
+    }
+
+    #[test]
+    fn fork_block_without_fork_network_rejected() {
+        match Args::try_parse_from(["starknet-devnet", "--fork-block", "42"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn invalid_fork_network_rejected() {
+        match Args::try_parse_from(["starknet-devnet", "--fork-network", "not a url"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn l1_node_url_parsed() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert_eq!(args.to_starknet_config().l1_node_url, None);
+
+        let args =
+            Args::try_parse_from(["starknet-devnet", "--l1-node-url", "http://localhost:8545"])
+                .unwrap();
+        assert_eq!(
+            args.to_starknet_config().l1_node_url,
+            Some("http://localhost:8545/".to_string())
+        );
+
+        match Args::try_parse_from(["starknet-devnet", "--l1-node-url", "not a url"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn fee_token_addresses_default_to_known_addresses() {
+        let config = Args::try_parse_from(["starknet-devnet"]).unwrap().to_starknet_config();
+        assert_eq!(config.eth_fee_token_address, FeeUnit::Wei.default_erc20_address());
+        assert_eq!(config.strk_fee_token_address, FeeUnit::Fri.default_erc20_address());
+
+        let config = Args::try_parse_from(["starknet-devnet", "--eth-fee-token-address", "0xe7"])
+            .unwrap()
+            .to_starknet_config();
+        assert_eq!(config.eth_fee_token_address, ContractAddress::new(Felt::from(0xe7)).unwrap());
+        assert_eq!(config.strk_fee_token_address, FeeUnit::Fri.default_erc20_address());
+    }
+
+    #[test]
+    fn fee_token_metadata_defaults_to_mainnet() {
+        let config = Args::try_parse_from(["starknet-devnet"]).unwrap().to_starknet_config();
+        assert_eq!(
+            (config.eth_token_name.as_str(), config.eth_token_symbol.as_str()),
+            ("Ether", "ETH")
+        );
+        assert_eq!(
+            (config.strk_token_name.as_str(), config.strk_token_symbol.as_str()),
+            ("Starknet Token", "STRK")
+        );
+        assert_eq!((config.eth_token_decimals, config.strk_token_decimals), (18, 18));
+
+        let config = Args::try_parse_from([
+            "starknet-devnet",
+            "--eth-token-symbol",
+            "WETH",
+            "--strk-token-decimals",
+            "6",
+        ])
+        .unwrap()
+        .to_starknet_config();
+        assert_eq!(config.eth_token_symbol, "WETH");
+        assert_eq!(config.strk_token_decimals, 6);
+    }
+
+    #[test]
+    fn fee_token_symbol_longer_than_short_string_rejected() {
+        match Args::try_parse_from(["starknet-devnet", "--eth-token-symbol", &"A".repeat(32)]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn sequencer_address_parsed() {
+        let config = Args::try_parse_from(["starknet-devnet"]).unwrap().to_starknet_config();
+        assert_eq!(config.sequencer_address, default_sequencer_address());
+
+        let config = Args::try_parse_from(["starknet-devnet", "--sequencer-address", "0x5e9"])
+            .unwrap()
+            .to_starknet_config();
+        assert_eq!(config.sequencer_address, ContractAddress::new(Felt::from(0x5e9)).unwrap());
+
+        match Args::try_parse_from(["starknet-devnet", "--sequencer-address", "5e9"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn invalid_fee_token_address_rejected() {
+        match Args::try_parse_from(["starknet-devnet", "--strk-fee-token-address", "e7"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn auto_impersonate_disabled_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert!(!args.to_starknet_config().auto_impersonate);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--auto-impersonate"]).unwrap();
+        assert!(args.to_starknet_config().auto_impersonate);
+    }
+
+    #[test]
+    fn impersonate_auto_fund_parsed() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert_eq!(args.to_starknet_config().impersonate_auto_fund, None);
+
+        let args =
+            Args::try_parse_from(["starknet-devnet", "--impersonate-auto-fund", "1000"]).unwrap();
+        assert_eq!(args.to_starknet_config().impersonate_auto_fund, Some(1000));
+    }
+
+    #[test]
+    fn estimate_skip_validate_disabled_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert!(!args.to_starknet_config().estimate_skip_validate);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--estimate-skip-validate"]).unwrap();
+        assert!(args.to_starknet_config().estimate_skip_validate);
+    }
+
+    #[test]
+    fn dev_declare_disabled_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert!(!args.to_starknet_config().allow_dev_declare);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--allow-dev-declare"]).unwrap();
+        assert!(args.to_starknet_config().allow_dev_declare);
+    }
+
+    #[test]
+    fn dev_state_disabled_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        let config = args.to_starknet_config();
+        assert!(!config.allow_dev_state);
+        assert_eq!(config.undeployed_storage_write, UndeployedStorageWrite::Warn);
+
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--allow-dev-state",
+            "--undeployed-storage-write",
+            "reject",
+        ])
+        .unwrap();
+        let config = args.to_starknet_config();
+        assert!(config.allow_dev_state);
+        assert_eq!(config.undeployed_storage_write, UndeployedStorageWrite::Reject);
+
+        assert!(
+            Args::try_parse_from(["starknet-devnet", "--undeployed-storage-write", "ignore"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn calldata_validation_strict_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert_eq!(args.to_starknet_config().calldata_validation, CalldataValidation::Strict);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--validation", "permissive"]).unwrap();
+        assert_eq!(args.to_starknet_config().calldata_validation, CalldataValidation::Permissive);
+
+        assert!(Args::try_parse_from(["starknet-devnet", "--validation", "lenient"]).is_err());
+    }
+
+    #[test]
+    fn dev_deploy_disabled_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert!(!args.to_starknet_config().allow_dev_deploy);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--allow-dev-deploy"]).unwrap();
+        assert!(args.to_starknet_config().allow_dev_deploy);
+    }
+
+    #[test]
+    fn nothing_predeclared_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert_eq!(args.to_starknet_config().predeclare, None);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--predeclare", "standard"]).unwrap();
+        assert_eq!(args.to_starknet_config().predeclare, Some(PredeclaredClassSet::Standard));
+
+        assert!(Args::try_parse_from(["starknet-devnet", "--predeclare", "all"]).is_err());
+    }
+
+    #[test]
+    fn lite_mode_disabled_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert!(!args.to_starknet_config().lite_mode);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--lite-mode"]).unwrap();
+        assert!(args.to_starknet_config().lite_mode);
+    }
+
+    #[test]
+    fn metrics_disabled_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert!(!args.to_starknet_config().enable_metrics);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--enable-metrics"]).unwrap();
+        assert!(args.to_starknet_config().enable_metrics);
+    }
+
+    #[test]
+    fn fake_sync_from_and_until_block() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!((config.fake_sync_from, config.fake_sync_until), (None, None));
+
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--fake-sync-from",
+            "2",
+            "--fake-sync-until",
+            "5",
+        ])
+        .unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!((config.fake_sync_from, config.fake_sync_until), (Some(2), Some(5)));
+
+        for flag in ["--fake-sync-from", "--fake-sync-until"] {
+            assert!(Args::try_parse_from(["starknet-devnet", flag, "5"]).is_err());
+        }
+    }
+
+    #[test]
+    fn fee_charge_enabled_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert!(!args.to_starknet_config().disable_fee_charge);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--disable-fee-charge"]).unwrap();
+        assert!(args.to_starknet_config().disable_fee_charge);
+    }
+
+    #[test]
+    fn block_generation_on_parsed() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert_eq!(args.to_starknet_config().block_generation_on, BlockGenerationOn::Transaction);
+
+        let args =
+            Args::try_parse_from(["starknet-devnet", "--block-generation-on", "demand"]).unwrap();
+        assert_eq!(args.to_starknet_config().block_generation_on, BlockGenerationOn::Demand);
+    }
+
+    #[test]
+    fn block_time_secs_parsed() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert_eq!(args.to_starknet_config().block_time_secs, 0);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--block-time-secs", "10"]).unwrap();
+        assert_eq!(args.to_starknet_config().block_time_secs, 10);
+    }
+
+    #[test]
+    fn parallel_execution_disabled_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert!(!args.to_starknet_config().parallel_execution);
+
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--block-generation-on",
+            "demand",
+            "--parallel-execution",
+        ])
+        .unwrap();
+        assert!(args.to_starknet_config().parallel_execution);
+    }
+
+    #[test]
+    fn pending_transactions_unlimited_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.max_pending_transactions, None);
+        assert_eq!(config.pending_overflow, PendingOverflow::Seal);
+
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--block-generation-on",
+            "demand",
+            "--max-pending-transactions",
+            "100",
+            "--pending-overflow",
+            "reject",
+        ])
+        .unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.max_pending_transactions, Some(100));
+        assert_eq!(config.pending_overflow, PendingOverflow::Reject);
+
+        assert!(
+            Args::try_parse_from(["starknet-devnet", "--max-pending-transactions", "0"]).is_err()
+        );
+        assert!(Args::try_parse_from(["starknet-devnet", "--pending-overflow", "drop"]).is_err());
+    }
+
+    #[test]
+    fn invalid_block_generation_on_rejected() {
+        match Args::try_parse_from(["starknet-devnet", "--block-generation-on", "block"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn chain_id_parsed_from_name_or_raw_value() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert_eq!(args.to_starknet_config().chain_id, ChainId::TESTNET);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--chain-id", "SEPOLIA"]).unwrap();
+        assert_eq!(args.to_starknet_config().chain_id, ChainId::SEPOLIA);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--chain-id", "0x42"]).unwrap();
+        assert_eq!(args.to_starknet_config().chain_id, ChainId::from(Felt::from(0x42)));
+    }
+
+    #[test]
+    fn empty_or_ambiguous_chain_id_rejected() {
+        for chain_id in ["", "1234"] {
+            match Args::try_parse_from(["starknet-devnet", "--chain-id", chain_id]) {
+                Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation),
+                Ok(_) => panic!("Should have failed"),
+            }
+        }
+    }
+
+    #[test]
+    fn account_class_parsed() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert_eq!(args.to_starknet_config().account_class, AccountClass::Cairo0);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--account-class", "cairo1"]).unwrap();
+        assert_eq!(args.to_starknet_config().account_class, AccountClass::Cairo1);
+
+        let args =
+            Args::try_parse_from(["starknet-devnet", "--account-class-custom", "account.sierra"])
+                .unwrap();
+        assert_eq!(args.to_starknet_config().account_class_custom, Some("account.sierra".into()));
+    }
+
+    #[test]
+    fn account_class_together_with_custom_class_rejected() {
+        match Args::try_parse_from([
+            "starknet-devnet",
+            "--account-class",
+            "cairo1",
+            "--account-class-custom",
+            "account.sierra",
+        ]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn predeployed_accounts_file_parsed() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.predeployed_accounts_path, None);
+        assert!(!config.no_auto_accounts);
+
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--predeploy-account",
+            "accounts.json",
+            "--no-auto-accounts",
+        ])
+        .unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.predeployed_accounts_path, Some("accounts.json".into()));
+        assert!(config.no_auto_accounts);
+    }
+
+    #[test]
+    fn no_auto_accounts_without_accounts_file_rejected() {
+        match Args::try_parse_from(["starknet-devnet", "--no-auto-accounts"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn predeployed_keys_shown_by_default() {
+        let args = Args::try_parse_from(["starknet-devnet"]).unwrap();
+        assert!(!args.to_starknet_config().hide_predeployed_keys);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--hide-predeployed-keys"]).unwrap();
+        assert!(args.to_starknet_config().hide_predeployed_keys);
+    }
+
+    #[test]
+    fn gas_prices_parsed() {
+        let args = Args::try_parse_from([
+            "starknet-devnet",
+            "--gas-price",
+            "10",
+            "--data-gas-price",
+            "20",
+        ])
+        .unwrap();
+        let config = args.to_starknet_config();
+        assert_eq!(config.gas_price, 10);
+        assert_eq!(config.data_gas_price, 20);
+        assert_eq!(config.eth_strk_rate, DEVNET_DEFAULT_ETH_STRK_RATE);
+    }
+
+    #[test]
+    fn eth_strk_rate_parsed() {
+        let args = Args::try_parse_from(["starknet-devnet", "--eth-strk-rate", "1000"]).unwrap();
+        assert_eq!(args.to_starknet_config().eth_strk_rate, 1000);
+
+        assert!(Args::try_parse_from(["starknet-devnet", "--eth-strk-rate", "0"]).is_err());
+    }
+
+    #[test]
+    fn max_steps_parsed() {
+        let config = Args::parse_from(["starknet-devnet"]).to_starknet_config();
+        assert_eq!(config.max_steps, DEVNET_DEFAULT_MAX_STEPS);
+
+        let args = Args::try_parse_from(["starknet-devnet", "--max-steps", "1000"]).unwrap();
+        assert_eq!(args.to_starknet_config().max_steps, 1000);
+
+        assert!(Args::try_parse_from(["starknet-devnet", "--max-steps", "0"]).is_err());
+    }
+
+    #[test]
+    fn transaction_length_limits_parsed() {
+        let config = Args::parse_from(["starknet-devnet"]).to_starknet_config();
+        assert_eq!(config.max_calldata_len, DEVNET_DEFAULT_MAX_CALLDATA_LEN);
+        assert_eq!(config.max_signature_len, DEVNET_DEFAULT_MAX_SIGNATURE_LEN);
+
+        let config = Args::parse_from([
+            "starknet-devnet",
+            "--max-calldata-len",
+            "100",
+            "--max-signature-len",
+            "2",
+        ])
+        .to_starknet_config();
+        assert_eq!(config.max_calldata_len, 100);
+        assert_eq!(config.max_signature_len, 2);
+    }
+
+    #[test]
+    fn zero_accounts_rejected() {
+        match Args::try_parse_from(["starknet-devnet", "--accounts", "0"]) {
+            Err(err) => assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation),
+            Ok(_) => panic!("Should have failed"),
         }
     }
 }
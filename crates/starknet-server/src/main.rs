@@ -1,15 +1,22 @@
 use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
-use ::server::ServerConfig;
+use api::fork::ForkClient;
+use api::l1::L1Client;
 use clap::Parser;
-use cli::Args;
+use cli::{Args, LogFormat};
 use starknet_core::account::Account;
+use starknet_core::starknet::dump::DumpOn;
 use starknet_core::starknet::Starknet;
 use starknet_types::felt::Felt;
 use starknet_types::traits::{ToDecimalString, ToHexString};
-use tracing::info;
+use tracing::{error, info, warn, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
+use url::Url;
 
 mod api;
 mod cli;
@@ -17,22 +24,50 @@ mod server;
 
 /// Configures tracing with default level INFO,
 /// If the environment variable `RUST_LOG` is set, it will be used instead.
-fn configure_tracing() {
+fn configure_tracing(log_format: LogFormat) {
     let level_filter_layer =
         EnvFilter::builder().with_default_directive(tracing::Level::INFO.into()).from_env_lossy();
 
-    tracing_subscriber::fmt().with_env_filter(level_filter_layer).init();
+    match log_format {
+        LogFormat::Text => tracing_subscriber::fmt().with_env_filter(level_filter_layer).init(),
+        LogFormat::Json => json_subscriber(level_filter_layer, std::io::stdout).init(),
+    }
+}
+
+/// Subscriber writing every event as a JSON object to `writer`. The fields of the span the event
+/// happened in, e.g. the method of the RPC call being handled, are listed under `span`.
+fn json_subscriber<W>(env_filter: EnvFilter, writer: W) -> impl Subscriber + Send + Sync
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt()
+        .json()
+        .with_current_span(true)
+        .with_span_list(false)
+        .with_env_filter(env_filter)
+        .with_writer(writer)
+        .finish()
 }
 
-fn log_predeployed_accounts(predeployed_accounts: &Vec<Account>, seed: u32, initial_balance: Felt) {
+fn log_predeployed_accounts(
+    predeployed_accounts: &Vec<Account>,
+    seed: u32,
+    initial_balance: Felt,
+    hide_keys: bool,
+) {
     for account in predeployed_accounts {
+        let private_key = match account.private_key {
+            _ if hide_keys => "<hidden>".to_string(),
+            Some(private_key) => private_key.to_prefixed_hex_str(),
+            None => "<unknown>".to_string(),
+        };
         let formatted_str = format!(
             r"
 | Account address |  {} 
 | Private key     |  {}
 | Public key      |  {}",
             account.account_address.to_prefixed_hex_str(),
-            account.private_key.to_prefixed_hex_str(),
+            private_key,
             account.public_key.to_prefixed_hex_str()
         );
 
@@ -48,36 +83,248 @@ fn log_predeployed_accounts(predeployed_accounts: &Vec<Account>, seed: u32, init
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
-    configure_tracing();
-
     // parse arguments
     let args = Args::parse();
+    configure_tracing(args.log_format);
+
     let starknet_config = args.to_starknet_config();
+    // the seed is logged also if randomly generated, so that a run can be reproduced with it
+    info!(
+        seed = starknet_config.seed,
+        "Predeployed accounts are generated from seed {0}; start with --seed {0} to reproduce them",
+        starknet_config.seed
+    );
     let host =
         IpAddr::from_str(starknet_config.host.as_str()).expect("Invalid value for host IP address");
     let mut addr = SocketAddr::new(host, starknet_config.port);
 
-    let api = api::Api::new(Starknet::new(&starknet_config)?);
+    let mut starknet = Starknet::new(&starknet_config)?;
+    // the forked network is the baseline of the replayed blocks as well
+    let fork = match starknet_config.fork_network.as_ref() {
+        Some(fork_network) => {
+            let fork = Arc::new(
+                ForkClient::connect(
+                    Url::parse(fork_network)?,
+                    starknet_config.fork_block,
+                    // upstream requests time out well before the server does, so that a slow
+                    // forked network is reported as such instead of as a server timeout
+                    Duration::from_secs(starknet_config.timeout.into()) / 2,
+                )
+                .await?,
+            );
+            info!("Forking from {} at block {}", fork.url(), fork.block_number());
+            starknet.set_fork(fork.clone());
+
+            Some(fork)
+        }
+        None => None,
+    };
+    if let Some(state_dir) = starknet_config.state_dir.as_ref() {
+        starknet.load_state_dir()?;
+        info!("Loaded {} blocks persisted to {}", starknet.block_number().0, state_dir);
+    }
+    let mut api = match fork {
+        Some(fork) => api::Api::with_fork(starknet, fork),
+        None => api::Api::new(starknet),
+    };
+    if let Some(l1_node_url) = starknet_config.l1_node_url.as_ref() {
+        let l1 = L1Client::new(
+            Url::parse(l1_node_url)?,
+            Duration::from_secs(starknet_config.timeout.into()) / 2,
+        );
+        info!("Messages sent to L1 are submitted to {}", l1.url());
+
+        api = api.with_l1(l1);
+    }
 
     let predeployed_accounts = api.starknet.read().await.get_predeployed_accounts();
     log_predeployed_accounts(
         &predeployed_accounts,
         starknet_config.seed,
         starknet_config.predeployed_accounts_initial_balance,
+        starknet_config.hide_predeployed_keys,
     );
 
     let server = server::serve_http_api_json_rpc(
         addr,
-        ServerConfig::default(),
+        args.to_server_config(),
         api.clone(),
         &starknet_config,
-    );
+    )
+    .map_err(|err| anyhow::anyhow!("Failed to bind to {addr}: {err}"))?;
+    // with port 0, the OS assigns the port
     addr = server.local_addr();
+    api.starknet.write().await.config.port = addr.port();
+    if let Some(port_file) = starknet_config.port_file.as_ref() {
+        write_port_file(port_file, addr.port())?;
+    }
 
     info!("Starknet Devnet listening on {}", addr);
 
+    // the server stops accepting connections once a shutdown signal arrives, but lets the
+    // requests in flight finish
+    let (stop_accepting_sender, stop_accepting_receiver) = tokio::sync::oneshot::channel::<()>();
+    let server = server.with_graceful_shutdown(async {
+        stop_accepting_receiver.await.ok();
+    });
+
     // spawn the server on a new task
-    let serve = tokio::task::spawn(server);
+    let mut serve = tokio::task::spawn(server);
+    let block_timer = (starknet_config.block_time_secs > 0).then(|| {
+        let block_time = Duration::from_secs(starknet_config.block_time_secs);
+        info!("Generating a new block every {}s", block_time.as_secs());
+        tokio::task::spawn(generate_blocks_periodically(api.clone(), block_time))
+    });
+
+    tokio::select! {
+        result = &mut serve => return Ok(result??),
+        signal = shutdown_signal() => info!("Received {signal}, shutting down"),
+    }
+
+    stop_accepting_sender.send(()).ok();
+    // no block is generated anymore once shutting down, so that the state dumped on exit is final
+    if let Some(block_timer) = block_timer {
+        block_timer.abort();
+        block_timer.await.ok();
+    }
+    // requests time out anyway after the configured timeout
+    let grace_period = Duration::from_secs(starknet_config.timeout.into());
+    match tokio::time::timeout(grace_period, serve).await {
+        Ok(result) => result??,
+        Err(_) => warn!("Requests still in flight after {}s are dropped", grace_period.as_secs()),
+    }
 
-    Ok(serve.await??)
+    let mut starknet = api.starknet.write().await;
+    if starknet_config.commit_on_shutdown {
+        if let Some(block_hash) = starknet.commit_pending_transactions()? {
+            info!("Committed pending transactions to block {}", block_hash.to_prefixed_hex_str());
+        }
+    }
+    if let (Some(DumpOn::Exit), Some(path)) =
+        (starknet_config.dump_on, starknet_config.dump_path.as_ref())
+    {
+        info!("Dumping state to {}", path);
+        starknet.dump_to_file(path)?;
+    }
+
+    Ok(())
+}
+
+/// Writes `port` to the file at `path`. The file is written under a temporary name first and then
+/// renamed, so that readers polling for it never see it partially written
+fn write_port_file(path: &str, port: u16) -> Result<(), anyhow::Error> {
+    let temporary_path = format!("{path}.tmp");
+    std::fs::write(&temporary_path, port.to_string())
+        .and_then(|_| std::fs::rename(&temporary_path, path))
+        .map_err(|err| anyhow::anyhow!("Failed to write port to {path}: {err}"))
+}
+
+/// Generates a block every `block_time`, holding the pending transactions if there are any. The
+/// timer is independent of blocks created otherwise, e.g. through POST /create_block. Runs until
+/// aborted
+async fn generate_blocks_periodically(api: api::Api, block_time: Duration) {
+    let mut interval = tokio::time::interval(block_time);
+    // a late tick, e.g. because the state was locked by a long request, doesn't cause a burst
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // the first tick completes immediately
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        match api.starknet.write().await.create_block() {
+            Ok(block_hash) => {
+                info!("Generated block {} on timer", block_hash.to_prefixed_hex_str())
+            }
+            Err(err) => error!("Failed to generate block on timer: {err}"),
+        }
+    }
+}
+
+/// Resolves with the name of the first received signal that Devnet shuts down on: SIGINT
+/// (Ctrl+C), or SIGTERM on Unix
+async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => "SIGINT",
+            _ = sigterm.recv() => "SIGTERM",
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
+        "SIGINT"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use ::server::rpc_core::request::Request;
+    use ::server::rpc_core::response::ResponseResult;
+    use ::server::rpc_handler::{self, RpcHandler};
+    use tracing_subscriber::EnvFilter;
+
+    use super::json_subscriber;
+
+    /// Collects everything written by the subscriber
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct EchoHandler;
+
+    #[async_trait::async_trait]
+    impl RpcHandler for EchoHandler {
+        type Request = serde_json::Value;
+
+        async fn on_request(&self, request: Self::Request) -> ResponseResult {
+            ResponseResult::Success(request)
+        }
+    }
+
+    #[tokio::test]
+    async fn json_logs_of_rpc_calls_carry_method_name() {
+        let captured_logs = CapturedLogs::default();
+        let writer = captured_logs.clone();
+        let _guard =
+            tracing::subscriber::set_default(json_subscriber(EnvFilter::new("info"), move || {
+                writer.clone()
+            }));
+
+        let request: Request = serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_chainId",
+            "params": []
+        }))
+        .unwrap();
+        rpc_handler::handle_request(request, EchoHandler).await.unwrap();
+
+        let logs = String::from_utf8(captured_logs.0.lock().unwrap().clone()).unwrap();
+        let handled_call_log = logs
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .find(|log| log["fields"]["message"] == "handled method call")
+            .unwrap_or_else(|| panic!("No log of the handled call in: {logs}"));
+        assert_eq!(handled_call_log["span"]["method"], "starknet_chainId");
+        assert_eq!(handled_call_log["span"]["name"], "rpc_call");
+        assert!(handled_call_log["fields"]["elapsed_ms"].is_u64());
+    }
 }
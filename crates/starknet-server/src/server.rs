@@ -7,43 +7,78 @@ use starknet_core::starknet::StarknetConfig;
 
 use crate::api::http::{endpoints as http, HttpApiHandler};
 use crate::api::json_rpc::JsonRpcHandler;
-use crate::api::Api;
+use crate::api::{ws, Api};
 
-/// Configures an [axum::Server] that handles related JSON-RPC calls and WEB API calls via HTTP
+/// Configures an [axum::Server] that handles related JSON-RPC calls and WEB API calls via HTTP.
+/// Fails if `addr` can't be bound to
 pub fn serve_http_api_json_rpc(
     addr: SocketAddr,
     config: ServerConfig,
     api: Api,
     starknet_config: &StarknetConfig,
-) -> StarknetDevnetServer {
+) -> Result<StarknetDevnetServer, hyper::Error> {
     let http = HttpApiHandler { api: api.clone() };
     let json_rpc = JsonRpcHandler { api };
+    // the Devnet-specific endpoints share a configurable prefix, so that they can be mounted
+    // next to the JSON-RPC handler behind a gateway
+    let devnet_route = |path: &str| format!("{}{path}", starknet_config.devnet_path);
 
     server::builder::Builder::<JsonRpcHandler, HttpApiHandler>::new(addr, json_rpc, http)
         .set_config(config)
-        .json_rpc_route("/rpc")
-        .http_api_route("/is_alive", get(http::is_alive))
-        .http_api_route("/dump", post(http::dump_load::dump))
-        .http_api_route("/load", post(http::dump_load::load))
-        .http_api_route("/postman/load_l1_messaging_contract", post(http::postman::postman_load))
-        .http_api_route("/postman/flush", post(http::postman::postman_flush))
+        .json_rpc_route(&starknet_config.rpc_path)
+        .http_api_route(&devnet_route("/ws"), get(ws::ws_handler))
+        .http_api_route(&devnet_route("/is_alive"), get(http::is_alive))
+        .http_api_route(&devnet_route("/health"), get(http::health))
+        .http_api_route(&devnet_route("/dump"), post(http::dump_load::dump))
+        .http_api_route(&devnet_route("/load"), post(http::dump_load::load))
         .http_api_route(
-            "/postman/send_message_to_l2",
+            &devnet_route("/postman/load_l1_messaging_contract"),
+            post(http::postman::postman_load),
+        )
+        .http_api_route(&devnet_route("/postman/flush"), post(http::postman::postman_flush))
+        .http_api_route(
+            &devnet_route("/postman/send_message_to_l2"),
             post(http::postman::postman_send_message_to_l2),
         )
         .http_api_route(
-            "/postman/consume_message_from_l2",
+            &devnet_route("/postman/consume_message_from_l2"),
             post(http::postman::postman_consume_message_from_l2),
         )
-        .http_api_route("/create_block", post(http::blocks::create_block))
-        .http_api_route("/abort_blocks", post(http::blocks::abort_blocks))
-        .http_api_route("/restart", post(http::restart))
-        .http_api_route("/set_time", post(http::time::set_time))
-        .http_api_route("/increase_time", post(http::time::increase_time))
-        .http_api_route("/predeployed_accounts", get(http::accounts::get_predeployed_accounts))
-        .http_api_route("/account_balance", get(http::accounts::get_account_balance))
-        .http_api_route("/fee_token", get(http::mint_token::get_fee_token))
-        .http_api_route("/mint", post(http::mint_token::mint))
-        .http_api_route("/fork_status", get(http::get_fork_status))
+        .http_api_route(&devnet_route("/create_block"), post(http::blocks::create_block))
+        .http_api_route(&devnet_route("/abort_blocks"), post(http::blocks::abort_blocks))
+        .http_api_route(
+            &devnet_route("/pending_transactions"),
+            get(http::blocks::get_pending_transactions),
+        )
+        .http_api_route(&devnet_route("/snapshot"), post(http::snapshots::take_snapshot))
+        .http_api_route(&devnet_route("/revert"), post(http::snapshots::revert))
+        .http_api_route(&devnet_route("/restart"), post(http::restart))
+        .http_api_route(&devnet_route("/set_time"), post(http::time::set_time))
+        .http_api_route(&devnet_route("/increase_time"), post(http::time::increase_time))
+        .http_api_route(&devnet_route("/set_gas_price"), post(http::gas::set_gas_price))
+        .http_api_route(
+            &devnet_route("/predeployed_accounts"),
+            get(http::accounts::get_predeployed_accounts),
+        )
+        .http_api_route(&devnet_route("/account_balance"), get(http::accounts::get_account_balance))
+        .http_api_route(
+            &devnet_route("/impersonate_account"),
+            post(http::accounts::impersonate_account),
+        )
+        .http_api_route(
+            &devnet_route("/stop_impersonate_account"),
+            post(http::accounts::stop_impersonating_account),
+        )
+        .http_api_route(&devnet_route("/fee_token"), get(http::mint_token::get_fee_token))
+        .http_api_route(&devnet_route("/mint"), post(http::mint_token::mint))
+        .http_api_route(&devnet_route("/mint_strk"), post(http::mint_token::mint_strk))
+        .http_api_route(&devnet_route("/mint_batch"), post(http::mint_token::mint_batch))
+        .http_api_route(&devnet_route("/declare"), post(http::declare::declare))
+        .http_api_route(&devnet_route("/deploy"), post(http::deploy::deploy))
+        .http_api_route(&devnet_route("/predict_address"), post(http::deploy::predict_address))
+        .http_api_route(&devnet_route("/set_storage_at"), post(http::storage::set_storage_at))
+        .http_api_route(&devnet_route("/fork_status"), get(http::get_fork_status))
+        .http_api_route(&devnet_route("/config"), get(http::get_config))
+        .http_api_route(&devnet_route("/metrics"), get(http::metrics::get_metrics))
         .build(starknet_config)
 }
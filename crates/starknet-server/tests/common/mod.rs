@@ -12,19 +12,26 @@ pub mod constants {
         "0x34ba56f92265f0868c57d3fe72ecab144fc96f97954bbbc4252cef8e8a979ba";
     // half the default value - sanity check
     pub const PREDEPLOYED_ACCOUNT_INITIAL_BALANCE: u128 = DEVNET_DEFAULT_INITIAL_BALANCE / 2;
+
+    pub const DUMMY_ADDRESS: &str = "0x42";
+    pub const DUMMY_AMOUNT: u128 = 42;
 }
 
 pub mod util {
     use std::net::TcpListener;
-    use std::process::{Child, Command, Stdio};
+    use std::process::{Child, Command, ExitStatus, Stdio};
     use std::{thread, time};
 
     use hyper::client::HttpConnector;
     use hyper::http::request;
     use hyper::{Body, Client, Response, StatusCode, Uri};
     use lazy_static::lazy_static;
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+    use starknet_rs_core::utils::get_selector_from_name;
     use starknet_rs_providers::jsonrpc::HttpTransport;
-    use starknet_rs_providers::JsonRpcClient;
+    use starknet_rs_providers::{JsonRpcClient, Provider};
     use thiserror::Error;
     use tokio::sync::Mutex;
     use url::Url;
@@ -77,40 +84,92 @@ pub mod util {
         pub http_client: Client<HttpConnector>,
         pub json_rpc_client: JsonRpcClient<HttpTransport>,
         process: Child,
+        port: u16,
         url: String,
     }
 
+    /// Starts Devnet with the default arguments and `args` on top of them
+    fn start_process(args: &[&str]) -> Child {
+        Command::new("cargo")
+            .arg("run")
+            .arg("--release")
+            .arg("--")
+            .arg("--seed")
+            .arg(SEED.to_string())
+            .arg("--accounts")
+            .arg(ACCOUNTS.to_string())
+            .arg("--initial-balance")
+            .arg(PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string())
+            .args(args)
+            .stdout(Stdio::piped()) // comment this out for complete devnet stdout
+            .spawn()
+            .expect("Could not start background devnet")
+    }
+
+    /// The value following `name` in `args`, if any
+    fn arg_value<'a>(args: &[&'a str], name: &str) -> Option<&'a str> {
+        args.iter().position(|arg| *arg == name).and_then(|i| args.get(i + 1)).copied()
+    }
+
     impl BackgroundDevnet {
         /// Ensures the background instance spawns at a free port, checks at most `MAX_RETRIES`
         /// times
         pub(crate) async fn spawn() -> Result<Self, TestError> {
+            BackgroundDevnet::spawn_with_additional_args(&[]).await
+        }
+
+        /// Like `spawn`, but passes `args` to Devnet on top of the default arguments
+        pub(crate) async fn spawn_with_additional_args(args: &[&str]) -> Result<Self, TestError> {
             // we keep the reference, otherwise the mutex unlocks immediately
             let _mutex_guard = BACKGROUND_DEVNET_MUTEX.lock().await;
 
             let free_port = get_free_port().expect("No free ports");
+            let port_arg = free_port.to_string();
+            let process = start_process(&[&["--port", port_arg.as_str()][..], args].concat());
+
+            // custom paths of the handlers are needed for reaching the started instance
+            let rpc_path = arg_value(args, "--rpc-path").unwrap_or("/rpc");
+            let devnet_path = arg_value(args, "--devnet-path").unwrap_or("");
+            BackgroundDevnet::wait_until_alive(process, free_port, rpc_path, devnet_path).await
+        }
+
+        /// Spawns Devnet with `--port 0`, so that the OS assigns it a free port, which is read
+        /// from the file given with `--port-file`
+        pub(crate) async fn spawn_on_os_assigned_port(port_file: &str) -> Result<Self, TestError> {
+            // a port written by a previous run must not be mistaken for the new one
+            std::fs::remove_file(port_file).ok();
+            let process = start_process(&["--port", "0", "--port-file", port_file]);
 
-            let devnet_url = format!("http://{HOST}:{free_port}");
-            let devnet_rpc_url = Url::parse(format!("{}/rpc", devnet_url.as_str()).as_str())?;
+            let mut retries = 0;
+            let max_retries = 30;
+            while retries < max_retries {
+                if let Some(port) = std::fs::read_to_string(port_file)
+                    .ok()
+                    .and_then(|port| port.parse::<u16>().ok())
+                {
+                    return BackgroundDevnet::wait_until_alive(process, port, "/rpc", "").await;
+                }
+
+                retries += 1;
+                thread::sleep(time::Duration::from_millis(500));
+            }
+
+            Err(TestError::DevnetNotStartable)
+        }
+
+        /// Waits until the Devnet `process` listening at `port` responds; the JSON-RPC client
+        /// is pointed at `rpc_path` and the healthcheck is prefixed with `devnet_path`
+        async fn wait_until_alive(
+            process: Child,
+            port: u16,
+            rpc_path: &str,
+            devnet_path: &str,
+        ) -> Result<Self, TestError> {
+            let devnet_url = format!("http://{HOST}:{port}");
+            let devnet_rpc_url = Url::parse(format!("{devnet_url}{rpc_path}").as_str())?;
             let json_rpc_client = JsonRpcClient::new(HttpTransport::new(devnet_rpc_url));
 
-            let process = Command::new("cargo")
-                .arg("run")
-                .arg("--release")
-                .arg("--")
-                .arg("--seed")
-                .arg(SEED.to_string())
-                .arg("--accounts")
-                .arg(ACCOUNTS.to_string())
-                .arg("--port")
-                .arg(free_port.to_string())
-                .arg("--initial-balance")
-                .arg(PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string())
-                .stdout(Stdio::piped()) // comment this out for complete devnet stdout
-                .spawn()
-                .expect("Could not start background devnet");
-
-            let healthcheck_uri =
-                format!("{}/is_alive", devnet_url.as_str()).as_str().parse::<Uri>()?;
+            let healthcheck_uri = format!("{devnet_url}{devnet_path}/is_alive").parse::<Uri>()?;
 
             let mut retries = 0;
             let max_retries = 30; // limit the number of times we check if devnet is spawned
@@ -118,11 +177,12 @@ pub mod util {
             while retries < max_retries {
                 if let Ok(alive_resp) = http_client.get(healthcheck_uri.clone()).await {
                     assert_eq!(alive_resp.status(), StatusCode::OK);
-                    println!("Spawned background devnet at port {free_port}");
+                    println!("Spawned background devnet at port {port}");
                     return Ok(BackgroundDevnet {
                         http_client,
                         json_rpc_client,
                         process,
+                        port,
                         url: devnet_url,
                     });
                 }
@@ -136,6 +196,10 @@ pub mod util {
             Err(TestError::DevnetNotStartable)
         }
 
+        pub fn port(&self) -> u16 {
+            self.port
+        }
+
         pub async fn post_json(
             &self,
             path: String,
@@ -149,13 +213,73 @@ pub mod util {
                 .unwrap();
             self.http_client.request(req).await
         }
+
+        /// Mints `amount` to `address` through an invoke transaction of the chargeable account and
+        /// returns the response body, holding the new balance and the transaction hash
+        pub async fn mint(&self, address: &str, amount: u128) -> serde_json::Value {
+            let req_body = Body::from(json!({ "address": address, "amount": amount }).to_string());
+            let resp = self.post_json("/mint".into(), req_body).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+            get_json_body(resp).await
+        }
+
+        /// Calls the JSON-RPC `method` with `params` and returns the response body
+        pub async fn rpc_call(&self, method: &str, params: serde_json::Value) -> serde_json::Value {
+            let req_body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+            let resp =
+                self.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+            get_json_body(resp).await
+        }
+
+        /// Returns the low 128 bits of the ETH balance of `address` at the latest block
+        pub async fn get_balance(&self, address: &str) -> FieldElement {
+            let balance = self
+                .json_rpc_client
+                .call(
+                    FunctionCall {
+                        contract_address: FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS)
+                            .unwrap(),
+                        entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                        calldata: vec![FieldElement::from_hex_be(address).unwrap()],
+                    },
+                    BlockId::Tag(BlockTag::Latest),
+                )
+                .await
+                .unwrap();
+
+            balance[0]
+        }
+
+        pub fn ws_url(&self) -> String {
+            format!("{}/ws", self.url.replacen("http", "ws", 1))
+        }
+
+        pub async fn get(&self, path: &str) -> Result<Response<hyper::Body>, hyper::Error> {
+            let uri = format!("{}{}", self.url.as_str(), path).parse::<Uri>().unwrap();
+            self.http_client.get(uri).await
+        }
+
+        /// Sends SIGTERM to the Devnet process and waits until it exits
+        #[cfg(unix)]
+        pub fn terminate(&mut self) -> ExitStatus {
+            let kill_status = Command::new("kill")
+                .args(["-TERM", &self.process.id().to_string()])
+                .status()
+                .expect("Could not send SIGTERM");
+            assert!(kill_status.success());
+
+            self.process.wait().expect("Could not wait for Devnet to exit")
+        }
     }
 
     /// By implementing Drop, we ensure there are no zombie background Devnet processes
     /// in case of an early test failure
     impl Drop for BackgroundDevnet {
         fn drop(&mut self) {
-            self.process.kill().expect("Cannot kill process");
+            // the process may have already exited, e.g. if it was terminated by the test
+            if let Ok(None) = self.process.try_wait() {
+                self.process.kill().expect("Cannot kill process");
+            }
         }
     }
 }
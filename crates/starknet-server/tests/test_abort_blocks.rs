@@ -0,0 +1,82 @@
+pub mod common;
+
+mod abort_blocks_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    async fn get_block_hash(devnet: &BackgroundDevnet, block_number: u64) -> String {
+        let block = devnet
+            .rpc_call(
+                "starknet_getBlockWithTxHashes",
+                json!({ "block_id": { "block_number": block_number } }),
+            )
+            .await;
+        block["result"]["block_hash"].as_str().unwrap().to_string()
+    }
+
+    async fn abort_blocks(
+        devnet: &BackgroundDevnet,
+        starting_block_hash: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let req_body =
+            Body::from(json!({ "starting_block_hash": starting_block_hash }).to_string());
+        let resp = devnet.post_json("/abort_blocks".into(), req_body).await.unwrap();
+        (resp.status(), get_json_body(resp).await)
+    }
+
+    #[tokio::test]
+    async fn abort_blocks_reverts_to_parent_block() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let mut mint_hashes = vec![];
+        for _ in 0..3 {
+            mint_hashes.push(devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await["tx_hash"].take());
+        }
+        let second_block_hash = get_block_hash(&devnet, 2).await;
+        let third_block_hash = get_block_hash(&devnet, 3).await;
+
+        let (status, aborted) = abort_blocks(&devnet, &second_block_hash).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(aborted, json!({ "aborted": [second_block_hash, third_block_hash] }));
+
+        let block_number = devnet.rpc_call("starknet_blockNumber", json!([])).await;
+        assert_eq!(block_number["result"], 2);
+
+        let aborted_block = devnet
+            .rpc_call(
+                "starknet_getBlockWithTxHashes",
+                json!({ "block_id": { "block_hash": second_block_hash } }),
+            )
+            .await;
+        assert_eq!(aborted_block["error"]["code"], 24);
+
+        let transaction_status = |transaction_hash: serde_json::Value| {
+            devnet.rpc_call(
+                "starknet_getTransactionStatus",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+        };
+        assert_eq!(
+            transaction_status(mint_hashes[0].clone()).await["result"],
+            json!({ "finality_status": "ACCEPTED_ON_L2", "execution_status": "SUCCEEDED" })
+        );
+        assert_eq!(
+            transaction_status(mint_hashes[1].clone()).await["result"],
+            json!({ "finality_status": "REJECTED" })
+        );
+    }
+
+    #[tokio::test]
+    async fn abort_genesis_block_is_rejected() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let genesis_block_hash = get_block_hash(&devnet, 0).await;
+
+        let (status, resp_body) = abort_blocks(&devnet, &genesis_block_hash).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(resp_body["error"].as_str().unwrap().contains("Genesis block can't be aborted"));
+    }
+}
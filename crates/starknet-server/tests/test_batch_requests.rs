@@ -0,0 +1,45 @@
+pub mod common;
+
+mod batch_requests_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    #[tokio::test]
+    async fn batch_with_malformed_member_returns_result_per_member() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let batch = json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "starknet_chainId" },
+            // malformed: no method specified
+            { "jsonrpc": "2.0", "id": 2 },
+            { "jsonrpc": "2.0", "id": 3, "method": "starknet_syncing" },
+        ]);
+        let resp = devnet.post_json("/rpc".into(), Body::from(batch.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp_body = get_json_body(resp).await;
+        let responses = resp_body.as_array().expect("Batch response should be an array");
+        assert_eq!(responses.len(), 3);
+
+        assert_eq!(responses[0]["id"], 1);
+        assert!(responses[0]["result"].as_str().unwrap().starts_with("0x"));
+
+        assert_eq!(responses[1]["error"]["code"], -32600);
+
+        assert_eq!(responses[2]["id"], 3);
+        assert_eq!(responses[2]["result"], false);
+    }
+
+    #[tokio::test]
+    async fn empty_batch_is_invalid_request() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet.post_json("/rpc".into(), Body::from("[]")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(resp_body["error"]["code"], -32600);
+    }
+}
@@ -0,0 +1,338 @@
+pub mod common;
+
+mod blocks_generation_tests {
+    use futures::future::join_all;
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::{BLOCK_HASH_CONTRACT_ADDRESS, CHARGEABLE_ACCOUNT_ADDRESS};
+    use starknet_rs_core::types::FieldElement;
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    /// Returns the hash of the minting transaction
+    async fn mint(devnet: &BackgroundDevnet) -> String {
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await["tx_hash"].as_str().unwrap().to_string()
+    }
+
+    async fn get_block(
+        devnet: &BackgroundDevnet,
+        block_id: serde_json::Value,
+    ) -> serde_json::Value {
+        let req_body = Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "starknet_getBlockWithTxHashes",
+                "params": { "block_id": block_id }
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/rpc".into(), req_body).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    #[tokio::test]
+    async fn blocks_generated_on_demand_hold_all_pending_transactions() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+                .await
+                .expect("Could not start Devnet");
+
+        let mut transaction_hashes = vec![];
+        for _ in 0..3 {
+            transaction_hashes.push(mint(&devnet).await);
+        }
+
+        let pending_block = get_block(&devnet, json!("pending")).await;
+        assert_eq!(pending_block["result"]["transactions"], json!(transaction_hashes));
+        // no block has been generated since the genesis block
+        let latest_block = get_block(&devnet, json!("latest")).await;
+        assert_eq!(latest_block["result"]["block_number"], 0);
+        assert_eq!(latest_block["result"]["transactions"], json!([]));
+
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let block_hash = get_json_body(resp).await["block_hash"].clone();
+
+        let latest_block = get_block(&devnet, json!("latest")).await;
+        assert_eq!(latest_block["result"]["block_hash"], block_hash);
+        assert_eq!(latest_block["result"]["block_number"], 1);
+        assert_eq!(latest_block["result"]["transactions"], json!(transaction_hashes));
+
+        let pending_block = get_block(&devnet, json!("pending")).await;
+        assert_eq!(pending_block["result"]["transactions"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn pending_block_empty_in_transaction_mode() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        mint(&devnet).await;
+
+        let latest_block = get_block(&devnet, json!("latest")).await["result"].clone();
+        let pending_block = get_block(&devnet, json!("pending")).await["result"].clone();
+        assert_eq!(pending_block["transactions"], json!([]));
+        assert_eq!(pending_block["parent_hash"], latest_block["block_hash"]);
+        for committed_field in ["status", "block_hash", "block_number", "new_root"] {
+            assert!(pending_block.get(committed_field).is_none(), "{pending_block}");
+        }
+    }
+
+    #[tokio::test]
+    async fn block_headers_hold_commitments() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let empty_block = get_block(&devnet, json!("latest")).await["result"].clone();
+        assert_eq!(to_felt(&empty_block["transaction_commitment"]), FieldElement::ZERO);
+        assert_eq!(to_felt(&empty_block["event_commitment"]), FieldElement::ZERO);
+
+        mint(&devnet).await;
+        let minting_block = get_block(&devnet, json!("latest")).await["result"].clone();
+        assert_ne!(to_felt(&minting_block["transaction_commitment"]), FieldElement::ZERO);
+        assert_ne!(to_felt(&minting_block["event_commitment"]), FieldElement::ZERO);
+
+        // the pending block has no commitments yet
+        let pending_block = get_block(&devnet, json!("pending")).await["result"].clone();
+        assert!(pending_block.get("transaction_commitment").is_none(), "{pending_block}");
+    }
+
+    async fn get_pending_transactions(devnet: &BackgroundDevnet) -> Vec<serde_json::Value> {
+        let resp = devnet.get("/pending_transactions").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        get_json_body(resp).await.as_array().unwrap().clone()
+    }
+
+    fn to_felt(value: &serde_json::Value) -> FieldElement {
+        FieldElement::from_hex_be(value.as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn pending_transactions_listed_until_block_created_on_demand() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+                .await
+                .expect("Could not start Devnet");
+
+        let first_hash = mint(&devnet).await;
+        let second_hash = mint(&devnet).await;
+
+        let pending_transactions = get_pending_transactions(&devnet).await;
+        assert_eq!(pending_transactions.len(), 2);
+        assert_eq!(pending_transactions[0]["transaction_hash"], first_hash);
+        assert_eq!(pending_transactions[1]["transaction_hash"], second_hash);
+        for pending_transaction in &pending_transactions {
+            assert_eq!(
+                to_felt(&pending_transaction["sender_address"]),
+                FieldElement::from_hex_be(CHARGEABLE_ACCOUNT_ADDRESS).unwrap()
+            );
+        }
+        assert_eq!(
+            to_felt(&pending_transactions[1]["nonce"]),
+            to_felt(&pending_transactions[0]["nonce"]) + FieldElement::ONE
+        );
+
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        assert!(get_pending_transactions(&devnet).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn no_pending_transactions_listed_in_transaction_mode() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        mint(&devnet).await;
+        assert!(get_pending_transactions(&devnet).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn full_pending_block_sealed_in_demand_mode() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--block-generation-on",
+            "demand",
+            "--max-pending-transactions",
+            "2",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let transaction_hashes = vec![mint(&devnet).await, mint(&devnet).await];
+
+        let latest_block = get_block(&devnet, json!("latest")).await;
+        assert_eq!(latest_block["result"]["transactions"], json!(transaction_hashes));
+        let pending_block = get_block(&devnet, json!("pending")).await;
+        assert_eq!(pending_block["result"]["transactions"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn concurrent_transactions_beyond_pending_limit_rejected() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--block-generation-on",
+            "demand",
+            "--max-pending-transactions",
+            "3",
+            "--pending-overflow",
+            "reject",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let responses = join_all((0..5).map(|_| {
+            let req_body =
+                Body::from(json!({ "address": DUMMY_ADDRESS, "amount": DUMMY_AMOUNT }).to_string());
+            devnet.post_json("/mint".into(), req_body)
+        }))
+        .await;
+        let statuses: Vec<StatusCode> =
+            responses.into_iter().map(|resp| resp.unwrap().status()).collect();
+        assert_eq!(statuses.iter().filter(|status| **status == StatusCode::OK).count(), 3);
+        assert_eq!(statuses.iter().filter(|status| **status == StatusCode::BAD_REQUEST).count(), 2);
+
+        let pending_block = get_block(&devnet, json!("pending")).await;
+        assert_eq!(pending_block["result"]["transactions"].as_array().unwrap().len(), 3);
+
+        // creating a block makes room for new transactions
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        mint(&devnet).await;
+    }
+
+    #[tokio::test]
+    async fn create_block_in_transaction_mode_generates_empty_block() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        mint(&devnet).await;
+
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let latest_block = get_block(&devnet, json!("latest")).await;
+        assert_eq!(latest_block["result"]["block_number"], 2);
+        assert_eq!(latest_block["result"]["transactions"], json!([]));
+    }
+
+    async fn get_stored_block_hash(
+        devnet: &BackgroundDevnet,
+        block_number: u64,
+    ) -> serde_json::Value {
+        let req_body = Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "starknet_getStorageAt",
+                "params": {
+                    "contract_address": BLOCK_HASH_CONTRACT_ADDRESS,
+                    "key": format!("{block_number:#x}"),
+                    "block_id": "latest"
+                }
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/rpc".into(), req_body).await.unwrap();
+        get_json_body(resp).await["result"].take()
+    }
+
+    #[tokio::test]
+    async fn hashes_of_old_blocks_stored_for_get_block_hash_syscall() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        for _ in 0..15 {
+            let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        }
+        assert_eq!(get_block(&devnet, json!("latest")).await["result"]["block_number"], 15);
+
+        let block_3 = get_block(&devnet, json!({ "block_number": 3 })).await;
+        let block_3_hash = block_3["result"]["block_hash"].as_str().unwrap();
+        let stored_hash = get_stored_block_hash(&devnet, 3).await;
+        assert_eq!(
+            FieldElement::from_hex_be(stored_hash.as_str().unwrap()).unwrap(),
+            FieldElement::from_hex_be(block_3_hash).unwrap()
+        );
+
+        // hashes of the last 10 blocks are not available
+        let stored_hash = get_stored_block_hash(&devnet, 10).await;
+        assert_eq!(
+            FieldElement::from_hex_be(stored_hash.as_str().unwrap()).unwrap(),
+            FieldElement::ZERO
+        );
+    }
+
+    #[tokio::test]
+    async fn blocks_numbered_from_initial_block_number() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--initial-block-number", "1000"])
+                .await
+                .expect("Could not start Devnet");
+
+        // the genesis block is created on startup
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 1001);
+        let genesis_block = get_block(&devnet, json!({ "block_number": 1000 })).await;
+        assert_eq!(genesis_block["result"]["block_number"], 1000);
+
+        mint(&devnet).await;
+        let latest_block = get_block(&devnet, json!("latest")).await;
+        assert_eq!(latest_block["result"]["block_number"], 1001);
+        assert_eq!(latest_block["result"]["parent_hash"], genesis_block["result"]["block_hash"]);
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 1002);
+    }
+
+    /// Starts Devnet with `args`, returning its genesis block and its pending block
+    async fn spawn_and_get_genesis_block(args: &[&str]) -> (serde_json::Value, serde_json::Value) {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(args)
+            .await
+            .expect("Could not start Devnet");
+
+        let genesis_block = get_block(&devnet, json!({ "block_number": 0 })).await["result"].take();
+        let pending_block = get_block(&devnet, json!("pending")).await["result"].take();
+        (genesis_block, pending_block)
+    }
+
+    #[tokio::test]
+    async fn genesis_block_hash_reproducible_with_same_config() {
+        let (genesis_block, pending_block) = spawn_and_get_genesis_block(&[]).await;
+        assert_eq!(genesis_block["timestamp"], 0);
+        assert_eq!(genesis_block["transactions"], json!([]));
+        // blocks after the genesis block take the time of the system clock
+        assert!(pending_block["timestamp"].as_u64().unwrap() > 0);
+        // the instances are started at different times
+        let (other_genesis_block, _) = spawn_and_get_genesis_block(&[]).await;
+        assert_eq!(genesis_block["block_hash"], other_genesis_block["block_hash"]);
+
+        let timestamp_args = ["--initial-block-timestamp", "1000000"];
+        let (genesis_block, pending_block) = spawn_and_get_genesis_block(&timestamp_args).await;
+        assert_eq!(genesis_block["timestamp"], 1000000);
+        assert!(pending_block["timestamp"].as_u64().unwrap() >= 1000000);
+        let (other_genesis_block, _) = spawn_and_get_genesis_block(&timestamp_args).await;
+        assert_eq!(genesis_block["block_hash"], other_genesis_block["block_hash"]);
+    }
+
+    #[tokio::test]
+    async fn blocks_generated_on_timer_without_transactions() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--block-generation-on",
+            "demand",
+            "--block-time-secs",
+            "1",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        tokio::time::sleep(std::time::Duration::from_millis(2500)).await;
+        let first_block = get_block(&devnet, json!({ "block_number": 0 })).await;
+        let latest_block = get_block(&devnet, json!("latest")).await;
+        let latest_block_number = latest_block["result"]["block_number"].as_u64().unwrap();
+        assert!(latest_block_number >= 1, "Unexpected block number {latest_block_number}");
+        assert_eq!(latest_block["result"]["transactions"], json!([]));
+        assert!(
+            latest_block["result"]["timestamp"].as_u64().unwrap()
+                > first_block["result"]["timestamp"].as_u64().unwrap()
+        );
+
+        // blocks can still be created on demand between the timed ones
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let created_block_hash = get_json_body(resp).await["block_hash"].clone();
+        let created_block = get_block(&devnet, json!({ "block_hash": created_block_hash })).await;
+        assert!(created_block["result"]["block_number"].as_u64().unwrap() > latest_block_number);
+    }
+}
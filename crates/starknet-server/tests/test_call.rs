@@ -1,8 +1,12 @@
 pub mod common;
 
 mod call {
-    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use serde_json::json;
+    use starknet_core::constants::{
+        CAIRO_0_ACCOUNT_CONTRACT_HASH, ERC20_CONTRACT_ADDRESS, UDC_CONTRACT_ADDRESS,
+    };
     use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall, StarknetError};
+    use starknet_rs_core::utils::{get_contract_address, get_selector_from_name};
     use starknet_rs_providers::{Provider, ProviderError};
 
     use crate::common::constants::{
@@ -88,4 +92,124 @@ mod call {
         let expected_result = vec![expected_balance, FieldElement::ZERO]; // uint256
         assert_eq!(retrieved_result, expected_result);
     }
+
+    #[tokio::test]
+    async fn calling_contract_deployed_in_pending_block() {
+        // the account is impersonated, so that the deployment needs no signature
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--auto-impersonate",
+            "--block-generation-on",
+            "demand",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        // an instance of the account class is deployed through the UDC, not unique, with the
+        // public key as its only constructor argument
+        let class_hash = FieldElement::from_hex_be(CAIRO_0_ACCOUNT_CONTRACT_HASH).unwrap();
+        let salt = FieldElement::from(42u32);
+        let public_key = FieldElement::from(0x1234u32);
+        let deploy_selector = get_selector_from_name("deployContract").unwrap();
+        let invoke_transaction = json!({
+            "type": "INVOKE",
+            "version": "0x1",
+            "max_fee": "0x2386f26fc10000",
+            "signature": [],
+            "nonce": "0x0",
+            "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+            "calldata": [
+                "0x1",
+                UDC_CONTRACT_ADDRESS,
+                format!("{deploy_selector:#x}"),
+                "0x0",
+                "0x5",
+                "0x5",
+                format!("{class_hash:#x}"),
+                format!("{salt:#x}"),
+                "0x0",
+                "0x1",
+                format!("{public_key:#x}")
+            ]
+        });
+        let added = devnet
+            .rpc_call(
+                "starknet_addInvokeTransaction",
+                json!({ "invoke_transaction": invoke_transaction }),
+            )
+            .await;
+        assert!(added["result"]["transaction_hash"].is_string(), "Unexpected response: {added}");
+
+        let contract_address =
+            get_contract_address(salt, class_hash, &[public_key], FieldElement::ZERO);
+        let function_call = FunctionCall {
+            contract_address,
+            entry_point_selector: get_selector_from_name("getPublicKey").unwrap(),
+            calldata: vec![],
+        };
+
+        let pending_result = devnet
+            .json_rpc_client
+            .call(function_call.clone(), BlockId::Tag(BlockTag::Pending))
+            .await
+            .expect("Failed to call contract at pending");
+        assert_eq!(pending_result, vec![public_key]);
+
+        let err = devnet
+            .json_rpc_client
+            .call(function_call, BlockId::Tag(BlockTag::Latest))
+            .await
+            .expect_err("Should have failed at latest");
+        match err {
+            ProviderError::StarknetError(StarknetError::ContractNotFound) => (),
+            _ => panic!("Invalid error: {err:?}"),
+        }
+
+        // failing calls carry the reason of the failure
+        let failed = devnet
+            .rpc_call(
+                "starknet_call",
+                json!({
+                    "request": {
+                        "contract_address": format!("{contract_address:#x}"),
+                        "entry_point_selector":
+                            format!("{:#x}", get_selector_from_name("nonExistentMethod").unwrap()),
+                        "calldata": []
+                    },
+                    "block_id": "pending"
+                }),
+            )
+            .await;
+        assert_eq!(failed["error"]["code"], 40, "Unexpected response: {failed}");
+        assert!(failed["error"]["data"]["revert_error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn call_exceeding_max_steps_fails_with_out_of_resources_error() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--max-steps", "10"])
+            .await
+            .expect("Could not start Devnet");
+
+        // the call is cut off after the allowed number of steps instead of running until the
+        // server times out
+        let failed = devnet
+            .rpc_call(
+                "starknet_call",
+                json!({
+                    "request": {
+                        "contract_address": ERC20_CONTRACT_ADDRESS,
+                        "entry_point_selector":
+                            format!("{:#x}", get_selector_from_name("balanceOf").unwrap()),
+                        "calldata": [PREDEPLOYED_ACCOUNT_ADDRESS]
+                    },
+                    "block_id": "latest"
+                }),
+            )
+            .await;
+        assert_eq!(failed["error"]["code"], 40, "Unexpected response: {failed}");
+        let revert_error = failed["error"]["data"]["revert_error"].as_str().unwrap();
+        assert!(
+            revert_error.contains("RunResources has no remaining steps"),
+            "Unexpected error: {revert_error}"
+        );
+    }
 }
@@ -0,0 +1,178 @@
+pub mod common;
+
+mod calldata_validation_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_in_rust::{CasmContractClass, SierraContractClass};
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    /// Sierra artifact of a contract whose `test_send_message_to_l1` takes an address and an array
+    const TEST_CONTRACT_PATH: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../starknet/test_artifacts/cairo_1_test.json");
+    /// Part of the guidance given in permissive mode
+    const GUIDANCE: &str = "Compare the calldata with the ABI of the contract";
+
+    /// Spawns Devnet with the test contract deployed and returns the address of the contract
+    async fn spawn_with_test_contract(validation: &str) -> (BackgroundDevnet, String) {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--allow-dev-declare",
+            "--allow-dev-deploy",
+            "--auto-impersonate",
+            "--validation",
+            validation,
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let sierra: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(TEST_CONTRACT_PATH).unwrap()).unwrap();
+        let sierra_contract_class: SierraContractClass =
+            serde_json::from_value(sierra.clone()).unwrap();
+        let casm = CasmContractClass::from_contract_class(sierra_contract_class, true).unwrap();
+        let resp = devnet
+            .post_json(
+                "/declare".into(),
+                Body::from(json!({ "sierra": sierra, "casm": casm }).to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let class_hash = get_json_body(resp).await["class_hash"].clone();
+
+        let deploy_body =
+            json!({ "class_hash": class_hash, "constructor_calldata": ["0x1", "0x2"] });
+        let resp =
+            devnet.post_json("/deploy".into(), Body::from(deploy_body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let contract_address =
+            get_json_body(resp).await["contract_address"].as_str().unwrap().to_string();
+
+        (devnet, contract_address)
+    }
+
+    fn send_message_selector() -> String {
+        format!("{:#x}", get_selector_from_name("test_send_message_to_l1").unwrap())
+    }
+
+    /// Calls `test_send_message_to_l1` with the L1 address only, leaving out the payload, and
+    /// returns the reported error
+    async fn call_with_missing_payload(
+        devnet: &BackgroundDevnet,
+        contract_address: &str,
+    ) -> String {
+        let resp_body = devnet
+            .rpc_call(
+                "starknet_call",
+                json!({
+                    "request": {
+                        "contract_address": contract_address,
+                        "entry_point_selector": send_message_selector(),
+                        "calldata": ["0xe1"]
+                    },
+                    "block_id": "latest"
+                }),
+            )
+            .await;
+        assert_eq!(resp_body["error"]["code"], 40, "Unexpected response: {resp_body}");
+        resp_body["error"]["data"]["revert_error"].as_str().unwrap().to_string()
+    }
+
+    /// Invokes `test_send_message_to_l1` with the L1 address only, leaving out the payload, and
+    /// returns the revert reason of the transaction
+    async fn invoke_with_missing_payload(
+        devnet: &BackgroundDevnet,
+        contract_address: &str,
+    ) -> String {
+        let nonce = devnet
+            .rpc_call(
+                "starknet_getNonce",
+                json!({ "block_id": "latest", "contract_address": PREDEPLOYED_ACCOUNT_ADDRESS }),
+            )
+            .await["result"]
+            .clone();
+        let resp_body = devnet
+            .rpc_call(
+                "starknet_addInvokeTransaction",
+                json!({
+                    "invoke_transaction": {
+                        "type": "INVOKE",
+                        "version": "0x1",
+                        "max_fee": "0xde0b6b3a7640000",
+                        "signature": [],
+                        "nonce": nonce,
+                        "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                        "calldata": [
+                            "0x1",
+                            contract_address,
+                            send_message_selector(),
+                            "0x0",
+                            "0x1",
+                            "0x1",
+                            "0xe1"
+                        ]
+                    }
+                }),
+            )
+            .await;
+        let transaction_hash = resp_body["result"]["transaction_hash"].clone();
+        assert!(transaction_hash.is_string(), "Unexpected response: {resp_body}");
+
+        let receipt = devnet
+            .rpc_call(
+                "starknet_getTransactionReceipt",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await["result"]
+            .clone();
+        assert_eq!(receipt["execution_status"], "REVERTED", "{receipt}");
+        receipt["revert_reason"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn wrong_calldata_length_reported_as_on_mainnet_in_strict_mode() {
+        let (devnet, contract_address) = spawn_with_test_contract("strict").await;
+
+        let revert_error = call_with_missing_payload(&devnet, &contract_address).await;
+        assert!(!revert_error.contains(GUIDANCE), "Unexpected error: {revert_error}");
+
+        let revert_reason = invoke_with_missing_payload(&devnet, &contract_address).await;
+        assert!(!revert_reason.contains(GUIDANCE), "Unexpected reason: {revert_reason}");
+    }
+
+    #[tokio::test]
+    async fn wrong_calldata_length_reported_with_guidance_in_permissive_mode() {
+        let (devnet, contract_address) = spawn_with_test_contract("permissive").await;
+
+        let revert_error = call_with_missing_payload(&devnet, &contract_address).await;
+        assert!(revert_error.contains(GUIDANCE), "Unexpected error: {revert_error}");
+
+        let revert_reason = invoke_with_missing_payload(&devnet, &contract_address).await;
+        assert!(revert_reason.contains(GUIDANCE), "Unexpected reason: {revert_reason}");
+    }
+
+    #[tokio::test]
+    async fn unrelated_failures_reported_without_guidance_in_permissive_mode() {
+        let (devnet, contract_address) = spawn_with_test_contract("permissive").await;
+
+        let resp_body = devnet
+            .rpc_call(
+                "starknet_call",
+                json!({
+                    "request": {
+                        "contract_address": contract_address,
+                        "entry_point_selector":
+                            format!("{:#x}", get_selector_from_name("nonExistentMethod").unwrap()),
+                        "calldata": []
+                    },
+                    "block_id": "latest"
+                }),
+            )
+            .await;
+        assert_eq!(resp_body["error"]["code"], 40, "Unexpected response: {resp_body}");
+        let revert_error = resp_body["error"]["data"]["revert_error"].as_str().unwrap();
+        assert!(!revert_error.contains(GUIDANCE), "Unexpected error: {revert_error}");
+    }
+}
@@ -0,0 +1,41 @@
+pub mod common;
+
+mod chain_id_tests {
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::cairo_short_string_to_felt;
+    use starknet_rs_providers::Provider;
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::util::BackgroundDevnet;
+
+    /// Mints on a fresh devnet started with the given chain id and returns the transaction hash
+    async fn mint_on_chain(chain_id: &str) -> String {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--chain-id", chain_id])
+            .await
+            .expect("Could not start Devnet");
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await["tx_hash"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn configured_chain_id_returned() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--chain-id", "SEPOLIA"])
+            .await
+            .expect("Could not start Devnet");
+        let chain_id = devnet.json_rpc_client.chain_id().await.unwrap();
+        assert_eq!(chain_id, cairo_short_string_to_felt("SN_SEPOLIA").unwrap());
+
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--chain-id", "0x42"])
+            .await
+            .expect("Could not start Devnet");
+        let chain_id = devnet.json_rpc_client.chain_id().await.unwrap();
+        assert_eq!(chain_id, FieldElement::from(0x42_u32));
+    }
+
+    #[tokio::test]
+    async fn same_transaction_hashed_differently_on_different_chains() {
+        let sepolia_hash = mint_on_chain("SEPOLIA").await;
+        let custom_chain_hash = mint_on_chain("MY_CHAIN").await;
+
+        assert_ne!(sepolia_hash, custom_chain_hash);
+    }
+}
@@ -0,0 +1,142 @@
+pub mod common;
+
+mod config_tests {
+    use hyper::StatusCode;
+    use serde_json::json;
+    use starknet_core::constants::{
+        CAIRO_0_ACCOUNT_CONTRACT_HASH, DEVNET_DEFAULT_GAS_PRICE, DEVNET_DEFAULT_HOST,
+        ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS, UDC_CONTRACT_ADDRESS,
+    };
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement};
+    use starknet_rs_providers::Provider;
+    use starknet_types::chain_id::ChainId;
+
+    use crate::common::constants::{ACCOUNTS, PREDEPLOYED_ACCOUNT_INITIAL_BALANCE, SEED};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    fn felt_field(config: &serde_json::Value, field: &str) -> FieldElement {
+        FieldElement::from_hex_be(config[field].as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn config_reflects_cli_arguments() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet.get("/config").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let config = get_json_body(resp).await;
+        assert_eq!(config["host"], DEVNET_DEFAULT_HOST);
+        assert_eq!(config["port"], devnet.port());
+        assert_eq!(config["seed"], SEED);
+        assert_eq!(config["total_accounts"], ACCOUNTS);
+        assert_eq!(
+            config["predeployed_accounts_initial_balance"],
+            json!(PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string())
+        );
+        assert_eq!(
+            felt_field(&config, "udc_address"),
+            FieldElement::from_hex_be(UDC_CONTRACT_ADDRESS).unwrap()
+        );
+        assert_eq!(
+            felt_field(&config, "eth_erc20_address"),
+            FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap()
+        );
+        assert_eq!(
+            felt_field(&config, "strk_erc20_address"),
+            FieldElement::from_hex_be(STRK_ERC20_CONTRACT_ADDRESS).unwrap()
+        );
+        assert_eq!(
+            felt_field(&config, "account_class_hash"),
+            FieldElement::from_hex_be(CAIRO_0_ACCOUNT_CONTRACT_HASH).unwrap()
+        );
+        assert_eq!(felt_field(&config, "chain_id"), FieldElement::from(ChainId::TESTNET.to_felt()));
+        assert_eq!(config["gas_price"], DEVNET_DEFAULT_GAS_PRICE);
+    }
+
+    #[tokio::test]
+    async fn config_reflects_overridden_addresses_and_network() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--eth-fee-token-address",
+            "0xe7",
+            "--chain-id",
+            "MAINNET",
+            "--gas-price",
+            "123",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let config = get_json_body(devnet.get("/config").await.unwrap()).await;
+        assert_eq!(felt_field(&config, "eth_erc20_address"), FieldElement::from(0xe7_u32));
+        assert_eq!(felt_field(&config, "chain_id"), FieldElement::from(ChainId::MAINNET.to_felt()));
+        assert_eq!(config["gas_price"], 123);
+    }
+
+    #[tokio::test]
+    async fn reported_udc_address_has_code() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let config = get_json_body(devnet.get("/config").await.unwrap()).await;
+        let class_hash = devnet
+            .json_rpc_client
+            .get_class_hash_at(BlockId::Tag(BlockTag::Latest), felt_field(&config, "udc_address"))
+            .await
+            .unwrap();
+        assert_ne!(class_hash, FieldElement::ZERO);
+    }
+
+    #[tokio::test]
+    async fn predeployed_accounts_count_matches_cli_argument() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet.get("/predeployed_accounts").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let accounts = get_json_body(resp).await;
+        assert_eq!(accounts.as_array().unwrap().len(), ACCOUNTS);
+    }
+
+    #[tokio::test]
+    async fn predeployed_accounts_listed_with_class_hash() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--account-class", "cairo0"])
+            .await
+            .expect("Could not start Devnet");
+
+        let resp = devnet.get("/predeployed_accounts").await.unwrap();
+        let accounts = get_json_body(resp).await;
+        for account in accounts.as_array().unwrap() {
+            assert_eq!(
+                FieldElement::from_hex_be(account["class_hash"].as_str().unwrap()).unwrap(),
+                FieldElement::from_hex_be(CAIRO_0_ACCOUNT_CONTRACT_HASH).unwrap()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn devnets_on_port_zero_get_distinct_ports() {
+        let port_files = ["devnet_port_zero_first.txt", "devnet_port_zero_second.txt"]
+            .map(|file_name| std::env::temp_dir().join(file_name).display().to_string());
+        let first = BackgroundDevnet::spawn_on_os_assigned_port(&port_files[0])
+            .await
+            .expect("Could not start Devnet");
+        let second = BackgroundDevnet::spawn_on_os_assigned_port(&port_files[1])
+            .await
+            .expect("Could not start Devnet");
+
+        assert_ne!(first.port(), 0);
+        assert_ne!(second.port(), 0);
+        assert_ne!(first.port(), second.port());
+
+        // both accept requests and report the port they are bound to
+        for devnet in [&first, &second] {
+            let resp = devnet.get("/config").await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+            assert_eq!(get_json_body(resp).await["port"], devnet.port());
+        }
+
+        for port_file in port_files {
+            std::fs::remove_file(port_file).unwrap();
+        }
+    }
+}
@@ -0,0 +1,75 @@
+pub mod common;
+
+mod cors_tests {
+    use hyper::{header, Body, Method, Request, Response, StatusCode};
+
+    use crate::common::constants::HOST;
+    use crate::common::util::BackgroundDevnet;
+
+    static ALLOWED_ORIGIN: &str = "http://localhost:3000";
+
+    /// Sends the preflight request a browser sends before a cross-origin JSON POST to `path`
+    async fn send_preflight(devnet: &BackgroundDevnet, path: &str, origin: &str) -> Response<Body> {
+        let req = Request::builder()
+            .method(Method::OPTIONS)
+            .uri(format!("http://{HOST}:{}{path}", devnet.port()))
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type")
+            .body(Body::empty())
+            .unwrap();
+        devnet.http_client.request(req).await.unwrap()
+    }
+
+    fn header_value(resp: &Response<Body>, name: header::HeaderName) -> Option<&str> {
+        resp.headers().get(name).map(|value| value.to_str().unwrap())
+    }
+
+    #[tokio::test]
+    async fn any_origin_allowed_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = send_preflight(&devnet, "/rpc", ALLOWED_ORIGIN).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(header_value(&resp, header::ACCESS_CONTROL_ALLOW_ORIGIN), Some("*"));
+        assert_eq!(header_value(&resp, header::ACCESS_CONTROL_ALLOW_CREDENTIALS), None);
+    }
+
+    #[tokio::test]
+    async fn preflight_of_listed_origin_answered() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--cors-origins",
+            &format!("{ALLOWED_ORIGIN},https://example.com"),
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        for path in ["/rpc", "/mint"] {
+            let resp = send_preflight(&devnet, path, ALLOWED_ORIGIN).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(
+                header_value(&resp, header::ACCESS_CONTROL_ALLOW_ORIGIN),
+                Some(ALLOWED_ORIGIN)
+            );
+            assert_eq!(header_value(&resp, header::ACCESS_CONTROL_ALLOW_CREDENTIALS), Some("true"));
+
+            let allowed_methods =
+                header_value(&resp, header::ACCESS_CONTROL_ALLOW_METHODS).unwrap().to_uppercase();
+            assert!(allowed_methods.contains("POST"), "Allowed methods: {allowed_methods}");
+            let allowed_headers =
+                header_value(&resp, header::ACCESS_CONTROL_ALLOW_HEADERS).unwrap().to_lowercase();
+            assert!(allowed_headers.contains("content-type"), "Allowed headers: {allowed_headers}");
+        }
+    }
+
+    #[tokio::test]
+    async fn unlisted_origin_not_allowed() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--cors-origins", ALLOWED_ORIGIN])
+                .await
+                .expect("Could not start Devnet");
+
+        let resp = send_preflight(&devnet, "/rpc", "http://unknown.com").await;
+        assert_eq!(header_value(&resp, header::ACCESS_CONTROL_ALLOW_ORIGIN), None);
+    }
+}
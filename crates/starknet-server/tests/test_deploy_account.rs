@@ -0,0 +1,80 @@
+pub mod common;
+
+mod deploy_account_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::{CAIRO_0_ACCOUNT_CONTRACT_HASH, DEVNET_DEFAULT_CHAIN_ID};
+    use starknet_core::transactions::deploy_account_transaction::DeployAccountTransaction;
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_signers::SigningKey;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::{HashProducer, ToHexString};
+
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static MAX_FEE: u128 = 1_000_000_000_000_000;
+
+    /// Returns the body of a signed deploy account request of the default account class and the
+    /// address it deploys to
+    fn signed_deploy_account_request() -> (serde_json::Value, String) {
+        let signing_key = SigningKey::from_secret_scalar(FieldElement::from(0x123_u32));
+        let public_key = Felt::from(signing_key.verifying_key().scalar());
+        let class_hash = Felt::from_prefixed_hex_str(CAIRO_0_ACCOUNT_CONTRACT_HASH).unwrap();
+        let salt = Felt::from(0x42);
+
+        let transaction = DeployAccountTransaction::new(
+            vec![public_key],
+            MAX_FEE,
+            vec![],
+            Felt::from(0),
+            class_hash,
+            salt,
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+            Felt::from(1),
+        )
+        .unwrap();
+        let address =
+            ContractAddress::try_from(transaction.inner.contract_address().clone()).unwrap();
+        let transaction_hash = transaction.generate_hash().unwrap();
+        let signature = signing_key.sign(&transaction_hash.into()).unwrap();
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_addDeployAccountTransaction",
+            "params": {
+                "deploy_account_transaction": {
+                    "type": "DEPLOY_ACCOUNT",
+                    "version": "0x1",
+                    "max_fee": format!("{MAX_FEE:#x}"),
+                    "signature": [format!("{:#x}", signature.r), format!("{:#x}", signature.s)],
+                    "nonce": "0x0",
+                    "class_hash": CAIRO_0_ACCOUNT_CONTRACT_HASH,
+                    "contract_address_salt": salt.to_prefixed_hex_str(),
+                    "constructor_calldata": [public_key.to_prefixed_hex_str()]
+                }
+            }
+        });
+        (request, address.to_prefixed_hex_str())
+    }
+
+    #[tokio::test]
+    async fn deploying_same_account_twice_rejected_with_explicit_error() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (request, address) = signed_deploy_account_request();
+
+        let mint_body = json!({ "address": address, "amount": MAX_FEE }).to_string();
+        let resp = devnet.post_json("/mint".into(), Body::from(mint_body)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = devnet.post_json("/rpc".into(), Body::from(request.to_string())).await.unwrap();
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(resp_body["result"]["contract_address"], address, "Response: {resp_body}");
+
+        let resp = devnet.post_json("/rpc".into(), Body::from(request.to_string())).await.unwrap();
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(resp_body["error"]["message"], "Contract already deployed");
+        assert_eq!(resp_body["error"]["data"]["contract_address"], address);
+    }
+}
@@ -0,0 +1,190 @@
+pub mod common;
+
+mod dev_declare_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::UDC_CONTRACT_ADDRESS;
+    use starknet_in_rust::{CasmContractClass, SierraContractClass};
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_providers::Provider;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static INITIAL_BALANCE: u128 = 42;
+
+    /// Sierra class of a contract holding a balance, together with its compilation
+    fn sierra_and_casm() -> (serde_json::Value, serde_json::Value) {
+        let sierra: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/test_data/sierra_contract_class_with_abi_as_string.json"
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut sierra_with_abi_array = sierra.clone();
+        sierra_with_abi_array["abi"] =
+            serde_json::from_str(sierra["abi"].as_str().unwrap()).unwrap();
+        let sierra_contract_class: SierraContractClass =
+            serde_json::from_value(sierra_with_abi_array).unwrap();
+        let casm = CasmContractClass::from_contract_class(sierra_contract_class, true).unwrap();
+
+        (sierra, serde_json::to_value(casm).unwrap())
+    }
+
+    /// Deploys an instance of `class_hash` through the UDC with an unsigned invoke of the
+    /// impersonated predeployed account and returns the address of the instance
+    async fn deploy_through_udc(
+        devnet: &BackgroundDevnet,
+        class_hash: &str,
+        nonce: u128,
+    ) -> String {
+        let deploy_selector = get_selector_from_name("deployContract").unwrap();
+        let resp = devnet
+            .rpc_call(
+                "starknet_addInvokeTransaction",
+                json!({
+                    "invoke_transaction": {
+                        "type": "INVOKE",
+                        "version": "0x1",
+                        "max_fee": "0x2386f26fc10000",
+                        "signature": [],
+                        "nonce": format!("{nonce:#x}"),
+                        "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                        "calldata": [
+                            "0x1",
+                            UDC_CONTRACT_ADDRESS,
+                            format!("{deploy_selector:#x}"),
+                            "0x0",
+                            "0x5",
+                            "0x5",
+                            class_hash,
+                            "0x0", // salt
+                            "0x0", // unique
+                            "0x1", // constructor calldata len
+                            format!("{INITIAL_BALANCE:#x}")
+                        ]
+                    }
+                }),
+            )
+            .await;
+        let transaction_hash = resp["result"]["transaction_hash"].clone();
+
+        let receipt = devnet
+            .rpc_call(
+                "starknet_getTransactionReceipt",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await;
+        assert_eq!(receipt["result"]["execution_status"], "SUCCEEDED");
+
+        // the UDC emits the address of the deployed contract
+        let udc_address = FieldElement::from_hex_be(UDC_CONTRACT_ADDRESS).unwrap();
+        receipt["result"]["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|event| {
+                FieldElement::from_hex_be(event["from_address"].as_str().unwrap()).unwrap()
+                    == udc_address
+            })
+            .unwrap()["data"][0]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn declared_class_deployable() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--allow-dev-declare",
+            "--auto-impersonate",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let (sierra, casm) = sierra_and_casm();
+        let resp = devnet
+            .post_json(
+                "/declare".into(),
+                Body::from(
+                    json!({
+                        "sierra": sierra,
+                        "casm": casm,
+                        "account_address": PREDEPLOYED_ACCOUNT_ADDRESS
+                    })
+                    .to_string(),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp_body = get_json_body(resp).await;
+        let class_hash = resp_body["class_hash"].as_str().unwrap().to_string();
+
+        let receipt = devnet
+            .rpc_call(
+                "starknet_getTransactionReceipt",
+                json!({ "transaction_hash": resp_body["transaction_hash"] }),
+            )
+            .await;
+        assert_eq!(receipt["result"]["execution_status"], "SUCCEEDED");
+
+        // the declaration used nonce 0 of the account
+        let contract_address = deploy_through_udc(&devnet, &class_hash, 1).await;
+
+        let balance = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address: FieldElement::from_hex_be(&contract_address).unwrap(),
+                    entry_point_selector: get_selector_from_name("get_balance").unwrap(),
+                    calldata: vec![],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap();
+        assert_eq!(balance, vec![FieldElement::from(INITIAL_BALANCE)]);
+    }
+
+    #[tokio::test]
+    async fn declaring_disabled_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let (sierra, casm) = sierra_and_casm();
+        let resp = devnet
+            .post_json(
+                "/declare".into(),
+                Body::from(json!({ "sierra": sierra, "casm": casm }).to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn class_of_unsupported_sierra_version_rejected() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--allow-dev-declare"])
+            .await
+            .expect("Could not start Devnet");
+
+        let (mut sierra, casm) = sierra_and_casm();
+        // the program starts with the major, minor and patch version of Sierra
+        sierra["sierra_program"][1] = json!("0x63");
+        let resp = devnet
+            .post_json(
+                "/declare".into(),
+                Body::from(json!({ "sierra": sierra, "casm": casm }).to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let error = get_json_body(resp).await["error"].as_str().unwrap().to_string();
+        assert!(error.contains("Sierra version 1.99.0 is not supported"), "{error}");
+        assert!(error.contains("1.2.0"), "{error}");
+    }
+}
@@ -0,0 +1,116 @@
+pub mod common;
+
+mod dev_deploy_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_in_rust::{CasmContractClass, SierraContractClass};
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+    use starknet_rs_core::utils::{get_contract_address, get_selector_from_name};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static INITIAL_BALANCE: u128 = 42;
+
+    /// Declares the class of a contract holding a balance through POST /declare and returns its
+    /// hash
+    async fn declare_balance_contract(devnet: &BackgroundDevnet) -> String {
+        let sierra: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/test_data/sierra_contract_class_with_abi_as_string.json"
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut sierra_with_abi_array = sierra.clone();
+        sierra_with_abi_array["abi"] =
+            serde_json::from_str(sierra["abi"].as_str().unwrap()).unwrap();
+        let sierra_contract_class: SierraContractClass =
+            serde_json::from_value(sierra_with_abi_array).unwrap();
+        let casm = CasmContractClass::from_contract_class(sierra_contract_class, true).unwrap();
+
+        let resp = devnet
+            .post_json(
+                "/declare".into(),
+                Body::from(json!({ "sierra": sierra, "casm": casm }).to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        get_json_body(resp).await["class_hash"].as_str().unwrap().to_string()
+    }
+
+    async fn deploy(devnet: &BackgroundDevnet, body: serde_json::Value) -> hyper::Response<Body> {
+        devnet.post_json("/deploy".into(), Body::from(body.to_string())).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn deployed_contract_callable_at_predicted_address() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--allow-dev-declare",
+            "--allow-dev-deploy",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let class_hash = declare_balance_contract(&devnet).await;
+        let resp = deploy(
+            &devnet,
+            json!({
+                "class_hash": class_hash,
+                "constructor_calldata": [format!("{INITIAL_BALANCE:#x}")],
+                "salt": "0x123"
+            }),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp_body = get_json_body(resp).await;
+        let contract_address =
+            FieldElement::from_hex_be(resp_body["contract_address"].as_str().unwrap()).unwrap();
+
+        let expected_address = get_contract_address(
+            FieldElement::from_hex_be("0x123").unwrap(),
+            FieldElement::from_hex_be(&class_hash).unwrap(),
+            &[FieldElement::from(INITIAL_BALANCE)],
+            FieldElement::ZERO,
+        );
+        assert_eq!(contract_address, expected_address);
+
+        let balance = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address,
+                    entry_point_selector: get_selector_from_name("get_balance").unwrap(),
+                    calldata: vec![],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap();
+        assert_eq!(balance, vec![FieldElement::from(INITIAL_BALANCE)]);
+    }
+
+    #[tokio::test]
+    async fn deploying_undeclared_class_rejected() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--allow-dev-deploy"])
+            .await
+            .expect("Could not start Devnet");
+
+        let resp =
+            deploy(&devnet, json!({ "class_hash": "0x123", "constructor_calldata": [] })).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let error = get_json_body(resp).await["error"].as_str().unwrap().to_string();
+        assert!(error.contains("is not declared"), "Unexpected error: {error}");
+    }
+
+    #[tokio::test]
+    async fn deploying_disabled_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = deploy(&devnet, json!({ "class_hash": "0x123" })).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,143 @@
+pub mod common;
+
+mod dump_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_rs_core::types::FieldElement;
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    /// Dump whose first block declares the class of `cairo_0_test.json` from the first predeployed
+    /// account and whose second block is empty
+    const DECLARED_CLASS_DUMP_PATH: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/test_data/dumps/declared_class.json");
+    const DECLARED_CLASS_HASH: &str =
+        "0x399998c787e0a063c3ac1d2abac084dcbe09954e3b156d53a8c43a02aa27d35";
+    const GENESIS_BLOCK_HASH: &str =
+        "0xb751935b7c033082e15e41198fdedcbe07a7bc0b097f639fbfd8b239d6b56f";
+
+    #[tokio::test]
+    async fn dump_without_path_returns_dump() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let resp = devnet.post_json("/dump".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let dump = get_json_body(resp).await;
+        let blocks = dump["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0]["transactions"][0]["type"], "Invoke");
+    }
+
+    #[tokio::test]
+    async fn dump_with_path_writes_file() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let path = std::env::temp_dir().join("devnet_dump_with_path_writes_file.json");
+        let path = path.to_str().unwrap();
+        let req_body = Body::from(json!({ "path": path }).to_string());
+        let resp = devnet.post_json("/dump".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let dump: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(dump["blocks"].as_array().unwrap().len(), 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    async fn load(devnet: &BackgroundDevnet, body: serde_json::Value) -> StatusCode {
+        let resp = devnet.post_json("/load".into(), Body::from(body.to_string())).await.unwrap();
+        resp.status()
+    }
+
+    #[tokio::test]
+    async fn load_requires_force_if_devnet_contains_blocks() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let path = std::env::temp_dir().join("devnet_load_requires_force.json");
+        let path = path.to_str().unwrap();
+        let req_body = Body::from(json!({ "path": path }).to_string());
+        let resp = devnet.post_json("/dump".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        assert_eq!(load(&devnet, json!({ "path": path })).await, StatusCode::BAD_REQUEST);
+        assert_eq!(load(&devnet, json!({ "path": path, "force": true })).await, StatusCode::OK);
+        std::fs::remove_file(path).unwrap();
+
+        let block_number_req =
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_blockNumber" });
+        let resp = devnet
+            .post_json("/rpc".into(), Body::from(block_number_req.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(get_json_body(resp).await["result"], 3);
+    }
+
+    #[tokio::test]
+    async fn load_from_missing_file_fails() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let status = load(&devnet, json!({ "path": "/nonexistent/dump.json" })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    fn felt_of(value: &serde_json::Value) -> FieldElement {
+        FieldElement::from_hex_be(value.as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn declared_class_and_blocks_restored_from_dump_file() {
+        // without the state root, block hashes only depend on the blocks themselves
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--lite-mode"])
+            .await
+            .expect("Could not start Devnet");
+
+        let status = load(&devnet, json!({ "path": DECLARED_CLASS_DUMP_PATH })).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let resp_body = devnet
+            .rpc_call(
+                "starknet_getClass",
+                json!({ "block_id": "latest", "class_hash": DECLARED_CLASS_HASH }),
+            )
+            .await;
+        let abi_names: Vec<&str> = resp_body["result"]["abi"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(abi_names, vec!["increase_balance", "get_balance"]);
+
+        let expected_blocks = [
+            (1, "0x7b7a5e27a14622d1830eb71ed5304d44571e60637a9fbc64de033e7195aa29c", 1_000_000),
+            (2, "0x7f0d02ea2135f7afa93b1c0df1562b3b70a4b48e7867d711f767aaba980e9b3", 1_000_100),
+        ];
+        let mut parent_hash = GENESIS_BLOCK_HASH;
+        for (block_number, block_hash, timestamp) in expected_blocks {
+            let block = devnet
+                .rpc_call(
+                    "starknet_getBlockWithTxHashes",
+                    json!({ "block_id": { "block_number": block_number } }),
+                )
+                .await["result"]
+                .take();
+            assert_eq!(
+                felt_of(&block["block_hash"]),
+                FieldElement::from_hex_be(block_hash).unwrap()
+            );
+            assert_eq!(
+                felt_of(&block["parent_hash"]),
+                FieldElement::from_hex_be(parent_hash).unwrap()
+            );
+            assert_eq!(block["timestamp"], timestamp);
+            parent_hash = block_hash;
+        }
+    }
+}
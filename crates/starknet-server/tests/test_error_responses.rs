@@ -0,0 +1,64 @@
+pub mod common;
+
+mod error_responses_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static METHOD_NOT_FOUND_CODE: i64 = -32601;
+
+    #[tokio::test]
+    async fn unknown_rpc_method_answered_with_json_rpc_error() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let req_body = json!({ "jsonrpc": "2.0", "id": 7, "method": "starknet_nonExistent" });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(resp_body["jsonrpc"], "2.0");
+        assert_eq!(resp_body["id"], 7);
+        assert_eq!(resp_body["error"]["code"], METHOD_NOT_FOUND_CODE);
+        assert!(resp_body["error"]["message"].is_string());
+        assert!(resp_body.get("result").is_none());
+    }
+
+    #[tokio::test]
+    async fn bad_mint_requests_answered_with_error_and_details() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        // rejected while parsing the body, before reaching the handler
+        let resp = devnet
+            .post_json("/mint".into(), Body::from(json!({ "address": "0x1" }).to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let resp_body = get_json_body(resp).await;
+        assert!(resp_body["error"].as_str().unwrap().contains("amount"), "{resp_body}");
+        assert_eq!(resp_body["details"], json!({}));
+
+        // rejected by the handler
+        let resp = devnet
+            .post_json(
+                "/mint".into(),
+                Body::from(json!({ "address": "0x1", "amount": -1 }).to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let resp_body = get_json_body(resp).await;
+        assert!(resp_body["error"].is_string(), "{resp_body}");
+        assert!(resp_body["details"].is_object(), "{resp_body}");
+    }
+
+    #[tokio::test]
+    async fn unknown_path_answered_with_error_and_details() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet.get("/non_existent").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(resp_body, json!({ "error": "Not Found", "details": {} }));
+    }
+}
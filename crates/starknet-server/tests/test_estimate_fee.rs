@@ -0,0 +1,227 @@
+pub mod common;
+
+mod estimate_fee_tests {
+    use hyper::Body;
+    use serde_json::json;
+    use starknet_core::constants::{CAIRO_0_ACCOUNT_CONTRACT_HASH, ERC20_CONTRACT_ADDRESS};
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::{
+        PREDEPLOYED_ACCOUNT_ADDRESS, PREDEPLOYED_ACCOUNT_INITIAL_BALANCE,
+    };
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static RECIPIENT_ADDRESS: &str = "0x42";
+
+    /// Unsigned ERC20 transfer from the predeployed account
+    fn transfer(nonce: u128, amount: u128) -> serde_json::Value {
+        let transfer_selector = get_selector_from_name("transfer").unwrap();
+        json!({
+            "type": "INVOKE",
+            "version": "0x1",
+            "max_fee": "0x0",
+            "signature": [],
+            "nonce": format!("{nonce:#x}"),
+            "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+            "calldata": [
+                "0x1",
+                ERC20_CONTRACT_ADDRESS,
+                format!("{transfer_selector:#x}"),
+                "0x0",
+                "0x3",
+                "0x3",
+                RECIPIENT_ADDRESS,
+                format!("{amount:#x}"),
+                "0x0"
+            ]
+        })
+    }
+
+    async fn estimate_fee(
+        devnet: &BackgroundDevnet,
+        transactions: Vec<serde_json::Value>,
+        simulation_flags: Vec<&str>,
+    ) -> serde_json::Value {
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_estimateFee",
+            "params": {
+                "request": transactions,
+                "block_id": "latest",
+                "simulation_flags": simulation_flags
+            }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    /// Estimates `transaction` without specifying simulation flags
+    async fn estimate_fee_with_default_flags(
+        devnet: &BackgroundDevnet,
+        transaction: serde_json::Value,
+    ) -> serde_json::Value {
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_estimateFee",
+            "params": { "request": [transaction], "block_id": "latest" }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    fn transfer_with_bogus_signature() -> serde_json::Value {
+        let mut transaction = transfer(0, 1);
+        transaction["signature"] = json!(["0x1", "0x2"]);
+        transaction
+    }
+
+    #[tokio::test]
+    async fn bogus_signature_estimated_when_validation_skipped_by_default() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--estimate-skip-validate"])
+            .await
+            .expect("Could not start Devnet");
+
+        let resp_body =
+            estimate_fee_with_default_flags(&devnet, transfer_with_bogus_signature()).await;
+        let estimations = resp_body["result"].as_array().expect("Expected an array of estimates");
+        assert_eq!(estimations.len(), 1);
+
+        // explicitly given flags take precedence
+        let resp_body = estimate_fee(&devnet, vec![transfer_with_bogus_signature()], vec![]).await;
+        assert_eq!(resp_body["error"]["code"], 41);
+    }
+
+    #[tokio::test]
+    async fn bogus_signature_fails_estimation_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp_body =
+            estimate_fee_with_default_flags(&devnet, transfer_with_bogus_signature()).await;
+        assert_eq!(resp_body["error"]["code"], 41);
+        assert_eq!(resp_body["error"]["data"]["transaction_index"], 0);
+    }
+
+    #[tokio::test]
+    async fn bundle_estimated_sequentially_without_validation() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        // the second transfer is only valid if the nonce was incremented by the first one
+        let resp_body =
+            estimate_fee(&devnet, vec![transfer(0, 1), transfer(1, 1)], vec!["SKIP_VALIDATE"])
+                .await;
+        let estimations = resp_body["result"].as_array().expect("Expected an array of estimates");
+        assert_eq!(estimations.len(), 2);
+        for estimation in estimations {
+            assert!(estimation["overall_fee"].is_string());
+            assert!(estimation["gas_consumed"].is_string());
+            assert!(estimation["gas_price"].is_string());
+        }
+    }
+
+    fn hex_to_u128(value: &serde_json::Value) -> u128 {
+        u128::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+    }
+
+    #[tokio::test]
+    async fn data_gas_differs_between_transactions_with_different_state_diffs() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        // transfers to two recipients, so the state diff holds one more balance than the one of
+        // a single transfer
+        let transfer_selector = format!("{:#x}", get_selector_from_name("transfer").unwrap());
+        let mut double_transfer = transfer(0, 1);
+        double_transfer["calldata"] = json!([
+            "0x2",
+            ERC20_CONTRACT_ADDRESS,
+            transfer_selector,
+            "0x0",
+            "0x3",
+            ERC20_CONTRACT_ADDRESS,
+            transfer_selector,
+            "0x3",
+            "0x3",
+            "0x6",
+            RECIPIENT_ADDRESS,
+            "0x1",
+            "0x0",
+            "0x43",
+            "0x1",
+            "0x0"
+        ]);
+
+        let mut data_gas = vec![];
+        for transaction in [transfer(0, 1), double_transfer] {
+            let resp_body = estimate_fee(&devnet, vec![transaction], vec!["SKIP_VALIDATE"]).await;
+            let estimation = &resp_body["result"][0];
+
+            let data_gas_consumed = hex_to_u128(&estimation["data_gas_consumed"]);
+            assert_eq!(
+                hex_to_u128(&estimation["overall_fee"]),
+                hex_to_u128(&estimation["gas_consumed"]) * hex_to_u128(&estimation["gas_price"])
+                    + data_gas_consumed * hex_to_u128(&estimation["data_gas_price"])
+            );
+            data_gas.push(data_gas_consumed);
+        }
+
+        assert!(data_gas[0] > 0);
+        assert!(data_gas[1] > data_gas[0]);
+    }
+
+    #[tokio::test]
+    async fn unsigned_transaction_fails_without_skipping_validation() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp_body = estimate_fee(&devnet, vec![transfer(0, 1)], vec![]).await;
+        assert_eq!(resp_body["error"]["code"], 41);
+        assert_eq!(resp_body["error"]["data"]["transaction_index"], 0);
+    }
+
+    #[tokio::test]
+    async fn failure_reports_index_of_failed_transaction() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        // the second transfer exceeds the remaining balance
+        let resp_body = estimate_fee(
+            &devnet,
+            vec![transfer(0, 1), transfer(1, PREDEPLOYED_ACCOUNT_INITIAL_BALANCE)],
+            vec!["SKIP_VALIDATE"],
+        )
+        .await;
+        assert_eq!(resp_body["error"]["code"], 41);
+        assert_eq!(resp_body["error"]["data"]["transaction_index"], 1);
+    }
+
+    #[tokio::test]
+    async fn deploy_account_estimated_for_counterfactual_address() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        // the account to be deployed holds no funds and the transaction is neither signed nor
+        // given a max fee
+        let deploy_account_transaction = json!({
+            "type": "DEPLOY_ACCOUNT",
+            "version": "0x1",
+            "max_fee": "0x0",
+            "signature": [],
+            "nonce": "0x0",
+            "class_hash": CAIRO_0_ACCOUNT_CONTRACT_HASH,
+            "contract_address_salt": "0x123",
+            "constructor_calldata": ["0x42"]
+        });
+        let resp_body = estimate_fee(
+            &devnet,
+            vec![deploy_account_transaction],
+            vec!["SKIP_VALIDATE", "SKIP_FEE_CHARGE"],
+        )
+        .await;
+
+        let estimations = resp_body["result"].as_array().expect("Expected an array of estimates");
+        assert_eq!(estimations.len(), 1);
+        let overall_fee = estimations[0]["overall_fee"].as_str().unwrap();
+        assert!(
+            u128::from_str_radix(overall_fee.trim_start_matches("0x"), 16).unwrap() > 0,
+            "Unexpected estimate: {resp_body}"
+        );
+    }
+}
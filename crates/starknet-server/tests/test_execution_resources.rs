@@ -0,0 +1,69 @@
+pub mod common;
+
+mod execution_resources_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+
+    use crate::common::constants::DUMMY_AMOUNT;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    async fn get_receipt(
+        devnet: &BackgroundDevnet,
+        transaction_hash: serde_json::Value,
+    ) -> serde_json::Value {
+        let req_body = Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "starknet_getTransactionReceipt",
+                "params": { "transaction_hash": transaction_hash }
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/rpc".into(), req_body).await.unwrap();
+        get_json_body(resp).await["result"].take()
+    }
+
+    /// Mints to every address in `addresses` with a single transaction and returns its receipt
+    async fn mint_to_all(devnet: &BackgroundDevnet, addresses: &[String]) -> serde_json::Value {
+        let mints: Vec<serde_json::Value> = addresses
+            .iter()
+            .map(|address| json!({ "address": address, "amount": DUMMY_AMOUNT }))
+            .collect();
+        let resp = devnet
+            .post_json("/mint_batch".into(), Body::from(json!({ "mints": mints }).to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let tx_hash = get_json_body(resp).await["tx_hash"].take();
+        get_receipt(devnet, tx_hash).await
+    }
+
+    #[tokio::test]
+    async fn receipt_reports_resources_of_all_nested_calls() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let single_mint = mint_to_all(&devnet, &["0x42".to_string()]).await;
+        let addresses: Vec<String> = (0x100..0x120).map(|i| format!("{i:#x}")).collect();
+        let many_mints = mint_to_all(&devnet, &addresses).await;
+
+        for receipt in [&single_mint, &many_mints] {
+            let resources = &receipt["execution_resources"];
+            assert!(resources["steps"].as_u64().unwrap() > 0, "Receipt: {receipt}");
+            assert!(resources["range_check_builtin_applications"].as_u64().unwrap() > 0);
+            assert!(resources["pedersen_builtin_applications"].as_u64().unwrap() > 0);
+            assert_eq!(resources["data_availability"]["l1_gas"], 0);
+            assert!(resources["data_availability"]["l1_data_gas"].as_u64().unwrap() > 0);
+        }
+
+        // every transfer of the fee token is a nested call of the chargeable account
+        let steps =
+            |receipt: &serde_json::Value| receipt["execution_resources"]["steps"].as_u64().unwrap();
+        assert!(steps(&many_mints) > steps(&single_mint));
+        let data_gas = |receipt: &serde_json::Value| {
+            receipt["execution_resources"]["data_availability"]["l1_data_gas"].as_u64().unwrap()
+        };
+        assert!(data_gas(&many_mints) > data_gas(&single_mint));
+    }
+}
@@ -0,0 +1,74 @@
+pub mod common;
+
+mod fee_charge_tests {
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::BackgroundDevnet;
+
+    static RECIPIENT_ADDRESS: &str = "0x42";
+    static TRANSFER_AMOUNT: u128 = 42;
+
+    /// Sends an ERC20 transfer with zero max fee and returns the JSON-RPC response
+    async fn send_transfer_with_zero_max_fee(devnet: &BackgroundDevnet) -> serde_json::Value {
+        let transfer_selector = get_selector_from_name("transfer").unwrap();
+        let params = json!({
+            "invoke_transaction": {
+                "type": "INVOKE",
+                "version": "0x1",
+                "max_fee": "0x0",
+                "signature": [],
+                "nonce": "0x0",
+                "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                "calldata": [
+                    "0x1",
+                    ERC20_CONTRACT_ADDRESS,
+                    format!("{transfer_selector:#x}"),
+                    "0x0",
+                    "0x3",
+                    "0x3",
+                    RECIPIENT_ADDRESS,
+                    format!("{TRANSFER_AMOUNT:#x}"),
+                    "0x0"
+                ]
+            }
+        });
+        devnet.rpc_call("starknet_addInvokeTransaction", params).await
+    }
+
+    #[tokio::test]
+    async fn zero_max_fee_rejected_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp_body = send_transfer_with_zero_max_fee(&devnet).await;
+        assert!(resp_body["error"].is_object(), "Expected an error, got {resp_body}");
+    }
+
+    #[tokio::test]
+    async fn zero_max_fee_accepted_with_disabled_fee_charge() {
+        // impersonation allows sending the transaction without signing it
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--disable-fee-charge",
+            "--auto-impersonate",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        let sender_balance_before = devnet.get_balance(PREDEPLOYED_ACCOUNT_ADDRESS).await;
+
+        let resp_body = send_transfer_with_zero_max_fee(&devnet).await;
+        assert!(resp_body["result"]["transaction_hash"].is_string());
+
+        // only the transferred amount leaves the sender's balance
+        assert_eq!(
+            devnet.get_balance(RECIPIENT_ADDRESS).await,
+            FieldElement::from(TRANSFER_AMOUNT)
+        );
+        assert_eq!(
+            devnet.get_balance(PREDEPLOYED_ACCOUNT_ADDRESS).await,
+            sender_balance_before - FieldElement::from(TRANSFER_AMOUNT)
+        );
+    }
+}
@@ -0,0 +1,81 @@
+pub mod common;
+
+mod fee_token_metadata_tests {
+    use starknet_core::constants::{ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS};
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+    use starknet_rs_core::utils::{cairo_short_string_to_felt, get_selector_from_name};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::util::BackgroundDevnet;
+
+    async fn call_token(
+        devnet: &BackgroundDevnet,
+        token_address: &str,
+        entry_point: &str,
+    ) -> FieldElement {
+        let result = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address: FieldElement::from_hex_be(token_address).unwrap(),
+                    entry_point_selector: get_selector_from_name(entry_point).unwrap(),
+                    calldata: vec![],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap();
+
+        result[0]
+    }
+
+    #[tokio::test]
+    async fn metadata_matches_mainnet_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        for (token_address, name, symbol) in [
+            (ERC20_CONTRACT_ADDRESS, "Ether", "ETH"),
+            (STRK_ERC20_CONTRACT_ADDRESS, "Starknet Token", "STRK"),
+        ] {
+            assert_eq!(
+                call_token(&devnet, token_address, "name").await,
+                cairo_short_string_to_felt(name).unwrap()
+            );
+            assert_eq!(
+                call_token(&devnet, token_address, "symbol").await,
+                cairo_short_string_to_felt(symbol).unwrap()
+            );
+            assert_eq!(
+                call_token(&devnet, token_address, "decimals").await,
+                FieldElement::from(18_u8)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_metadata_readable_through_calls() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--eth-token-symbol",
+            "WETH",
+            "--strk-token-name",
+            "Custom Token",
+            "--strk-token-decimals",
+            "6",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        assert_eq!(
+            call_token(&devnet, ERC20_CONTRACT_ADDRESS, "symbol").await,
+            cairo_short_string_to_felt("WETH").unwrap()
+        );
+        assert_eq!(
+            call_token(&devnet, STRK_ERC20_CONTRACT_ADDRESS, "name").await,
+            cairo_short_string_to_felt("Custom Token").unwrap()
+        );
+        assert_eq!(
+            call_token(&devnet, STRK_ERC20_CONTRACT_ADDRESS, "decimals").await,
+            FieldElement::from(6_u8)
+        );
+    }
+}
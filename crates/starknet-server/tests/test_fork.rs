@@ -0,0 +1,198 @@
+pub mod common;
+
+mod fork_tests {
+    use std::net::{SocketAddr, TcpListener};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use axum::routing::post;
+    use axum::{Extension, Json, Router};
+    use hyper::StatusCode;
+    use serde_json::json;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    const FORKED_CONTRACT_ADDRESS: &str = "0x123";
+    const FORKED_CLASS_HASH: &str = "0x456";
+    const FORK_BLOCK: &str = "10";
+    /// Value of every storage variable of the forked contract
+    const FORKED_STORAGE_VALUE: &str = "0x2a";
+
+    #[derive(Clone)]
+    struct MockUpstream {
+        contract_class: serde_json::Value,
+        class_requests: Arc<AtomicUsize>,
+        delay: Duration,
+    }
+
+    /// Answers requests of the forked network: knows of a single contract at
+    /// `FORKED_CONTRACT_ADDRESS`, whose class is the one of the declare_v1 test data
+    async fn handle_rpc(
+        Extension(upstream): Extension<MockUpstream>,
+        Json(req): Json<serde_json::Value>,
+    ) -> Json<serde_json::Value> {
+        tokio::time::sleep(upstream.delay).await;
+
+        let is_forked_contract = req["params"]["contract_address"] == FORKED_CONTRACT_ADDRESS;
+        let result = match req["method"].as_str().unwrap() {
+            "starknet_getClassHashAt" if is_forked_contract => json!(FORKED_CLASS_HASH),
+            "starknet_getNonce" if is_forked_contract => json!("0x2"),
+            "starknet_getStorageAt" if is_forked_contract => json!(FORKED_STORAGE_VALUE),
+            "starknet_getClass" => {
+                upstream.class_requests.fetch_add(1, Ordering::SeqCst);
+                upstream.contract_class.clone()
+            }
+            _ => {
+                return Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": req["id"],
+                    "error": { "code": 20, "message": "Contract not found" }
+                }));
+            }
+        };
+
+        Json(json!({ "jsonrpc": "2.0", "id": req["id"], "result": result }))
+    }
+
+    fn canned_contract_class() -> serde_json::Value {
+        let declare_v1: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/test_data/rpc/declare_v1.json"
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+
+        declare_v1["contract_class"].clone()
+    }
+
+    fn spawn_mock_upstream(delay: Duration) -> (SocketAddr, Arc<AtomicUsize>) {
+        let class_requests = Arc::new(AtomicUsize::new(0));
+        let upstream = MockUpstream {
+            contract_class: canned_contract_class(),
+            class_requests: class_requests.clone(),
+            delay,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().route("/", post(handle_rpc)).layer(Extension(upstream));
+        tokio::spawn(axum::Server::from_tcp(listener).unwrap().serve(app.into_make_service()));
+
+        (addr, class_requests)
+    }
+
+    async fn spawn_forked_devnet(upstream_addr: SocketAddr, timeout: &str) -> BackgroundDevnet {
+        BackgroundDevnet::spawn_with_additional_args(&[
+            "--fork-network",
+            &format!("http://{upstream_addr}"),
+            "--fork-block",
+            FORK_BLOCK,
+            "--timeout",
+            timeout,
+        ])
+        .await
+        .expect("Could not start Devnet")
+    }
+
+    fn get_class_at_params() -> serde_json::Value {
+        json!({ "block_id": "latest", "contract_address": FORKED_CONTRACT_ADDRESS })
+    }
+
+    fn abi_names(contract_class: &serde_json::Value) -> Vec<&str> {
+        contract_class["abi"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["name"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn get_class_at_fetches_and_caches_forked_class() {
+        let (upstream_addr, class_requests) = spawn_mock_upstream(Duration::ZERO);
+        let devnet = spawn_forked_devnet(upstream_addr, "120").await;
+
+        let expected_class = canned_contract_class();
+        for _ in 0..2 {
+            let resp_body = devnet.rpc_call("starknet_getClassAt", get_class_at_params()).await;
+            let contract_class = &resp_body["result"];
+            assert_eq!(abi_names(contract_class), abi_names(&expected_class));
+            assert_eq!(
+                contract_class["entry_points_by_type"]["EXTERNAL"].as_array().unwrap().len(),
+                expected_class["entry_points_by_type"]["EXTERNAL"].as_array().unwrap().len()
+            );
+        }
+        assert_eq!(class_requests.load(Ordering::SeqCst), 1);
+
+        // the nonce of the contract is read from the forked network as well
+        let nonce = devnet
+            .rpc_call(
+                "starknet_getNonce",
+                json!({ "block_id": "latest", "contract_address": FORKED_CONTRACT_ADDRESS }),
+            )
+            .await;
+        assert_eq!(nonce["result"], "0x2");
+    }
+
+    #[tokio::test]
+    async fn contract_deployed_only_upstream_can_be_called() {
+        let (upstream_addr, _) = spawn_mock_upstream(Duration::ZERO);
+        let devnet = spawn_forked_devnet(upstream_addr, "120").await;
+
+        let resp_body = devnet
+            .rpc_call(
+                "starknet_call",
+                json!({
+                    "request": {
+                        "contract_address": FORKED_CONTRACT_ADDRESS,
+                        "entry_point_selector":
+                            format!("{:#x}", get_selector_from_name("get_balance").unwrap()),
+                        "calldata": []
+                    },
+                    "block_id": "latest"
+                }),
+            )
+            .await;
+        assert_eq!(resp_body["result"], json!([FORKED_STORAGE_VALUE]), "{resp_body}");
+    }
+
+    #[tokio::test]
+    async fn contract_missing_upstream_is_not_found() {
+        let (upstream_addr, _) = spawn_mock_upstream(Duration::ZERO);
+        let devnet = spawn_forked_devnet(upstream_addr, "120").await;
+
+        let resp_body = devnet
+            .rpc_call(
+                "starknet_getStorageAt",
+                json!({ "contract_address": "0x789", "key": "0x1", "block_id": "latest" }),
+            )
+            .await;
+        assert_eq!(resp_body["error"]["code"], 20);
+    }
+
+    #[tokio::test]
+    async fn upstream_timeout_is_reported() {
+        let (upstream_addr, _) = spawn_mock_upstream(Duration::from_secs(30));
+        let devnet = spawn_forked_devnet(upstream_addr, "2").await;
+
+        let resp_body = devnet.rpc_call("starknet_getClassAt", get_class_at_params()).await;
+        assert_eq!(resp_body["error"]["code"], -32001);
+    }
+
+    #[tokio::test]
+    async fn fork_status_reports_forked_network() {
+        let (upstream_addr, _) = spawn_mock_upstream(Duration::ZERO);
+        let devnet = spawn_forked_devnet(upstream_addr, "120").await;
+
+        let resp = devnet.get("/fork_status").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            get_json_body(resp).await,
+            json!({ "url": format!("http://{upstream_addr}/"), "block": 10 })
+        );
+    }
+}
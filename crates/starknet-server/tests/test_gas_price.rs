@@ -0,0 +1,168 @@
+pub mod common;
+
+mod gas_price_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    fn felt_of(value: &serde_json::Value) -> FieldElement {
+        FieldElement::from_hex_be(value.as_str().unwrap()).unwrap()
+    }
+
+    fn u128_of(value: &serde_json::Value) -> u128 {
+        u128::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+    }
+
+    /// Estimates the fee of an unsigned ERC20 transfer from the predeployed account
+    async fn estimate_transfer_fee(devnet: &BackgroundDevnet) -> serde_json::Value {
+        let transfer_selector = get_selector_from_name("transfer").unwrap();
+        let transfer = json!({
+            "type": "INVOKE",
+            "version": "0x1",
+            "max_fee": "0x0",
+            "signature": [],
+            "nonce": "0x0",
+            "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+            "calldata": [
+                "0x1",
+                ERC20_CONTRACT_ADDRESS,
+                format!("{transfer_selector:#x}"),
+                "0x0",
+                "0x3",
+                "0x3",
+                "0x42",
+                "0x1",
+                "0x0"
+            ]
+        });
+        let resp_body = devnet
+            .rpc_call(
+                "starknet_estimateFee",
+                json!({
+                    "request": [transfer],
+                    "block_id": "latest",
+                    "simulation_flags": ["SKIP_VALIDATE"]
+                }),
+            )
+            .await;
+        resp_body["result"][0].clone()
+    }
+
+    async fn set_gas_price(
+        devnet: &BackgroundDevnet,
+        gas_price: u64,
+        data_gas_price: u64,
+        generate_block: bool,
+    ) -> serde_json::Value {
+        let req_body = Body::from(
+            json!({
+                "gas_price_wei": gas_price,
+                "data_gas_price_wei": data_gas_price,
+                "generate_block": generate_block
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/set_gas_price".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        get_json_body(resp).await
+    }
+
+    #[tokio::test]
+    async fn estimated_fee_scales_with_gas_price() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--gas-price",
+            "1000",
+            "--data-gas-price",
+            "2000",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let low_estimation = estimate_transfer_fee(&devnet).await;
+        assert_eq!(felt_of(&low_estimation["gas_price"]), FieldElement::from(1000_u32));
+        assert_eq!(felt_of(&low_estimation["data_gas_price"]), FieldElement::from(2000_u32));
+
+        set_gas_price(&devnet, 1_000_000, 2_000_000, false).await;
+
+        let high_estimation = estimate_transfer_fee(&devnet).await;
+        assert_eq!(felt_of(&high_estimation["gas_price"]), FieldElement::from(1_000_000_u32));
+        assert_eq!(felt_of(&high_estimation["data_gas_price"]), FieldElement::from(2_000_000_u32));
+        assert_eq!(high_estimation["gas_consumed"], low_estimation["gas_consumed"]);
+        // the fee is proportional to the gas price, up to rounding
+        assert!(
+            u128_of(&high_estimation["overall_fee"])
+                >= 100 * u128_of(&low_estimation["overall_fee"])
+        );
+    }
+
+    #[tokio::test]
+    async fn new_prices_appear_in_generated_block_only() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--gas-price", "1000"])
+            .await
+            .expect("Could not start Devnet");
+        let req_body = Body::from(json!({ "address": "0x42", "amount": 1 }).to_string());
+        let resp = devnet.post_json("/mint".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let resp_body = set_gas_price(&devnet, 3000, 4000, true).await;
+        let new_block_hash = resp_body["block_hash"].clone();
+        assert!(new_block_hash.is_string(), "Unexpected response: {resp_body}");
+
+        let new_block = devnet
+            .rpc_call(
+                "starknet_getBlockWithTxHashes",
+                json!({ "block_id": { "block_hash": new_block_hash } }),
+            )
+            .await;
+        assert_eq!(
+            felt_of(&new_block["result"]["l1_gas_price"]["price_in_wei"]),
+            FieldElement::from(3000_u32)
+        );
+        assert_eq!(
+            felt_of(&new_block["result"]["l1_data_gas_price"]["price_in_wei"]),
+            FieldElement::from(4000_u32)
+        );
+
+        let mint_block = devnet
+            .rpc_call("starknet_getBlockWithTxHashes", json!({ "block_id": { "block_number": 1 } }))
+            .await;
+        assert_eq!(
+            felt_of(&mint_block["result"]["l1_gas_price"]["price_in_wei"]),
+            FieldElement::from(1000_u32)
+        );
+    }
+
+    #[tokio::test]
+    async fn block_header_reports_prices_in_wei_and_fri() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--gas-price",
+            "1000",
+            "--data-gas-price",
+            "2000",
+            "--eth-strk-rate",
+            "3",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        let req_body = Body::from(json!({ "address": "0x42", "amount": 1 }).to_string());
+        let resp = devnet.post_json("/mint".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let block =
+            devnet.rpc_call("starknet_getBlockWithTxHashes", json!({ "block_id": "latest" })).await;
+        for (price, price_in_wei) in [("l1_gas_price", 1000_u32), ("l1_data_gas_price", 2000)] {
+            let resource_price = &block["result"][price];
+            assert_eq!(felt_of(&resource_price["price_in_wei"]), FieldElement::from(price_in_wei));
+            assert_eq!(
+                felt_of(&resource_price["price_in_fri"]),
+                FieldElement::from(3 * price_in_wei),
+                "Checking {price} of {block}"
+            );
+        }
+    }
+}
@@ -0,0 +1,60 @@
+pub mod common;
+
+mod get_block_with_receipts_tests {
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::BackgroundDevnet;
+
+    #[tokio::test]
+    async fn block_with_invoke_contains_transaction_paired_with_receipt() {
+        // the account is impersonated, so that the transaction needs no signature
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--auto-impersonate"])
+            .await
+            .expect("Could not start Devnet");
+
+        let transfer_selector = get_selector_from_name("transfer").unwrap();
+        let invoke_transaction = json!({
+            "type": "INVOKE",
+            "version": "0x1",
+            "max_fee": "0xde0b6b3a7640000",
+            "signature": [],
+            "nonce": "0x0",
+            "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+            "calldata": [
+                "0x1",
+                ERC20_CONTRACT_ADDRESS,
+                format!("{transfer_selector:#x}"),
+                "0x0",
+                "0x3",
+                "0x3",
+                "0x42",
+                "0x2a",
+                "0x0"
+            ]
+        });
+        let added = devnet
+            .rpc_call(
+                "starknet_addInvokeTransaction",
+                json!({ "invoke_transaction": invoke_transaction }),
+            )
+            .await;
+        let transaction_hash = added["result"]["transaction_hash"].clone();
+        assert!(transaction_hash.is_string(), "Unexpected response: {added}");
+
+        let block = devnet
+            .rpc_call("starknet_getBlockWithReceipts", json!({ "block_id": "latest" }))
+            .await["result"]
+            .clone();
+        let transactions = block["transactions"].as_array().unwrap();
+        assert_eq!(transactions.len(), 1);
+
+        let transaction_with_receipt = &transactions[0];
+        assert_eq!(transaction_with_receipt["transaction"]["transaction_hash"], transaction_hash);
+        assert_eq!(transaction_with_receipt["receipt"]["transaction_hash"], transaction_hash);
+        assert_eq!(transaction_with_receipt["receipt"]["status"], "ACCEPTED_ON_L2");
+        assert_eq!(transaction_with_receipt["receipt"]["block_hash"], block["block_hash"]);
+    }
+}
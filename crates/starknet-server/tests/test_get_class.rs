@@ -0,0 +1,109 @@
+pub mod common;
+
+mod get_class_tests {
+    use std::io::Read;
+
+    use base64::Engine;
+    use serde_json::json;
+    use starknet_core::constants::CAIRO_0_ACCOUNT_CONTRACT_HASH;
+    use starknet_rs_core::types::{BlockId, BlockTag, ContractClass, FieldElement};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    #[tokio::test]
+    async fn get_legacy_class_with_compressed_program() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet
+            .rpc_call(
+                "starknet_getClass",
+                json!({ "block_id": "latest", "class_hash": CAIRO_0_ACCOUNT_CONTRACT_HASH }),
+            )
+            .await;
+        let contract_class = &resp["result"];
+
+        assert!(contract_class["abi"].is_array());
+        assert!(!contract_class["entry_points_by_type"]["EXTERNAL"].as_array().unwrap().is_empty());
+
+        let compressed_program = base64::engine::general_purpose::STANDARD
+            .decode(contract_class["program"].as_str().unwrap())
+            .unwrap();
+        let mut program_json = String::new();
+        flate2::read::GzDecoder::new(compressed_program.as_slice())
+            .read_to_string(&mut program_json)
+            .unwrap();
+        let program: serde_json::Value = serde_json::from_str(&program_json).unwrap();
+        assert!(program["data"].is_array());
+
+        // the class is also parsable by a spec compliant client
+        let contract_class = devnet
+            .json_rpc_client
+            .get_class(
+                BlockId::Tag(BlockTag::Latest),
+                FieldElement::from_hex_be(CAIRO_0_ACCOUNT_CONTRACT_HASH).unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(contract_class, ContractClass::Legacy(_)));
+    }
+
+    #[tokio::test]
+    async fn get_sierra_class_with_abi_as_string() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--account-class", "cairo1"])
+            .await
+            .expect("Could not start Devnet");
+
+        let resp = devnet.get("/predeployed_accounts").await.unwrap();
+        let account_address = get_json_body(resp).await[0]["address"].take();
+        let class_hash = devnet
+            .rpc_call(
+                "starknet_getClassHashAt",
+                json!({ "block_id": "latest", "contract_address": account_address }),
+            )
+            .await["result"]
+            .take();
+
+        let resp = devnet
+            .rpc_call(
+                "starknet_getClass",
+                json!({ "block_id": "latest", "class_hash": class_hash }),
+            )
+            .await;
+        let contract_class = &resp["result"];
+
+        assert!(contract_class["sierra_program"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|felt| felt.as_str().unwrap().starts_with("0x")));
+        assert!(contract_class["contract_class_version"].is_string());
+        assert!(contract_class["entry_points_by_type"]["EXTERNAL"][0]["function_idx"].is_u64());
+        assert!(contract_class.get("sierra_program_debug_info").is_none());
+        let abi: serde_json::Value =
+            serde_json::from_str(contract_class["abi"].as_str().unwrap()).unwrap();
+        assert!(abi.is_array());
+
+        // the class is also parsable by a spec compliant client
+        let contract_class = devnet
+            .json_rpc_client
+            .get_class(
+                BlockId::Tag(BlockTag::Latest),
+                FieldElement::from_hex_be(class_hash.as_str().unwrap()).unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(contract_class, ContractClass::Sierra(_)));
+    }
+
+    #[tokio::test]
+    async fn get_class_with_unknown_hash() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet
+            .rpc_call("starknet_getClass", json!({ "block_id": "latest", "class_hash": "0x123" }))
+            .await;
+
+        assert_eq!(resp["error"], json!({ "code": 28, "message": "Class hash not found" }));
+    }
+}
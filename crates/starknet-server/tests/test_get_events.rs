@@ -0,0 +1,100 @@
+pub mod common;
+
+mod get_events_tests {
+    use hyper::Body;
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    async fn get_events(devnet: &BackgroundDevnet, filter: serde_json::Value) -> serde_json::Value {
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_getEvents",
+            "params": { "filter": filter }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    fn transfer_key() -> String {
+        format!("{:#x}", get_selector_from_name("Transfer").unwrap())
+    }
+
+    #[tokio::test]
+    async fn events_filtered_by_keys_with_wildcards() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let resp_body = get_events(
+            &devnet,
+            json!({ "address": ERC20_CONTRACT_ADDRESS, "keys": [[transfer_key()]], "chunk_size": 100 }),
+        )
+        .await;
+        let transfer_events = resp_body["result"]["events"].as_array().unwrap().clone();
+        assert!(!transfer_events.is_empty(), "Expected events, got {resp_body}");
+
+        // an empty list of alternatives matches any key
+        let resp_body = get_events(
+            &devnet,
+            json!({ "address": ERC20_CONTRACT_ADDRESS, "keys": [[]], "chunk_size": 100 }),
+        )
+        .await;
+        assert_eq!(resp_body["result"]["events"].as_array().unwrap(), &transfer_events);
+
+        let resp_body = get_events(
+            &devnet,
+            json!({ "address": ERC20_CONTRACT_ADDRESS, "keys": [["0x1"]], "chunk_size": 100 }),
+        )
+        .await;
+        assert_eq!(resp_body["result"]["events"], json!([]));
+        assert!(resp_body["result"]["continuation_token"].is_null());
+    }
+
+    #[tokio::test]
+    async fn events_paginated_with_continuation_token() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        for address in ["0x1", "0x2", "0x3"] {
+            devnet.mint(address, DUMMY_AMOUNT).await;
+        }
+
+        let filter = json!({ "keys": [[transfer_key()]], "chunk_size": 100 });
+        let all_events = get_events(&devnet, filter).await["result"]["events"].clone();
+
+        let mut chunked_events = vec![];
+        let mut continuation_token = serde_json::Value::Null;
+        loop {
+            let resp_body = get_events(
+                &devnet,
+                json!({
+                    "keys": [[transfer_key()]],
+                    "chunk_size": 2,
+                    "continuation_token": continuation_token
+                }),
+            )
+            .await;
+            let events = resp_body["result"]["events"].as_array().unwrap();
+            assert!(events.len() <= 2);
+            chunked_events.extend(events.iter().cloned());
+
+            continuation_token = resp_body["result"]["continuation_token"].clone();
+            if continuation_token.is_null() {
+                break;
+            }
+        }
+
+        assert_eq!(json!(chunked_events), all_events);
+    }
+
+    #[tokio::test]
+    async fn invalid_continuation_token_rejected() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp_body =
+            get_events(&devnet, json!({ "chunk_size": 1, "continuation_token": "invalid" })).await;
+        assert_eq!(resp_body["error"]["code"], 33);
+    }
+}
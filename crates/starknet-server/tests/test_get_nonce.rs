@@ -0,0 +1,63 @@
+pub mod common;
+
+mod get_nonce_tests {
+    use starknet_core::constants::CHARGEABLE_ACCOUNT_ADDRESS;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, StarknetError};
+    use starknet_rs_providers::{Provider, ProviderError};
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT, PREDEPLOYED_ACCOUNT_ADDRESS};
+    use crate::common::util::BackgroundDevnet;
+
+    #[tokio::test]
+    async fn nonce_of_idle_account_is_zero() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let nonce = devnet
+            .json_rpc_client
+            .get_nonce(
+                BlockId::Tag(BlockTag::Latest),
+                FieldElement::from_hex_be(PREDEPLOYED_ACCOUNT_ADDRESS).unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(nonce, FieldElement::ZERO);
+    }
+
+    #[tokio::test]
+    async fn nonce_counts_transactions_up_to_given_block() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let chargeable_address = FieldElement::from_hex_be(CHARGEABLE_ACCOUNT_ADDRESS).unwrap();
+
+        for _ in 0..3 {
+            devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        }
+
+        let nonce = devnet
+            .json_rpc_client
+            .get_nonce(BlockId::Tag(BlockTag::Latest), chargeable_address)
+            .await
+            .unwrap();
+        assert_eq!(nonce, FieldElement::from(3u32));
+
+        // a block is generated for each transaction, the first one being block 1
+        let nonce =
+            devnet.json_rpc_client.get_nonce(BlockId::Number(1), chargeable_address).await.unwrap();
+        assert_eq!(nonce, FieldElement::ONE);
+    }
+
+    #[tokio::test]
+    async fn nonce_of_undeployed_contract_not_found() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let err = devnet
+            .json_rpc_client
+            .get_nonce(BlockId::Tag(BlockTag::Latest), FieldElement::from_hex_be("0x1234").unwrap())
+            .await
+            .expect_err("Should have failed");
+
+        match err {
+            ProviderError::StarknetError(StarknetError::ContractNotFound) => (),
+            _ => panic!("Invalid error: {err:?}"),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+pub mod common;
+
+mod health_tests {
+    use hyper::{Body, StatusCode};
+
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    #[tokio::test]
+    async fn is_alive_once_listening() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet.get("/is_alive").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+    }
+
+    #[tokio::test]
+    async fn health_reports_latest_block_number_and_uptime() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet.get("/health").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let health = get_json_body(resp).await;
+        assert_eq!(health["status"], "ok");
+        // the genesis block is created on startup
+        assert_eq!(health["block_number"], 0, "Unexpected health: {health}");
+        assert!(health["uptime_secs"].is_u64(), "Unexpected health: {health}");
+
+        devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+
+        let resp = devnet.get("/health").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        assert_eq!(get_json_body(resp).await["block_number"], 2);
+    }
+}
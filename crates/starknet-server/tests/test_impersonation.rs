@@ -0,0 +1,121 @@
+pub mod common;
+
+mod impersonation_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::BackgroundDevnet;
+
+    static RECIPIENT_ADDRESS: &str = "0x42";
+    static TRANSFER_AMOUNT: u128 = 42;
+
+    async fn post(devnet: &BackgroundDevnet, path: &str, body: serde_json::Value) {
+        let resp = devnet.post_json(path.into(), Body::from(body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+    }
+
+    /// Sends an ERC20 transfer from `sender_address` with a signature that is not valid for it
+    async fn send_unsigned_transfer(devnet: &BackgroundDevnet, sender_address: &str, nonce: u128) {
+        let transfer_selector = get_selector_from_name("transfer").unwrap();
+        let params = json!({
+            "invoke_transaction": {
+                "type": "INVOKE",
+                "version": "0x1",
+                "max_fee": "0x2386f26fc10000",
+                "signature": ["0x1", "0x2"],
+                "nonce": format!("{nonce:#x}"),
+                "sender_address": sender_address,
+                "calldata": [
+                    "0x1",
+                    ERC20_CONTRACT_ADDRESS,
+                    format!("{transfer_selector:#x}"),
+                    "0x0",
+                    "0x3",
+                    "0x3",
+                    RECIPIENT_ADDRESS,
+                    format!("{TRANSFER_AMOUNT:#x}"),
+                    "0x0"
+                ]
+            }
+        });
+        let resp_body = devnet.rpc_call("starknet_addInvokeTransaction", params).await;
+        assert!(resp_body["result"]["transaction_hash"].is_string());
+    }
+
+    async fn get_recipient_balance(devnet: &BackgroundDevnet) -> FieldElement {
+        devnet.get_balance(RECIPIENT_ADDRESS).await
+    }
+
+    #[tokio::test]
+    async fn impersonated_account_invokes_without_valid_signature() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        post(
+            &devnet,
+            "/impersonate_account",
+            json!({ "account_address": PREDEPLOYED_ACCOUNT_ADDRESS }),
+        )
+        .await;
+        post(&devnet, "/mint", json!({ "address": PREDEPLOYED_ACCOUNT_ADDRESS, "amount": 1000 }))
+            .await;
+
+        send_unsigned_transfer(&devnet, PREDEPLOYED_ACCOUNT_ADDRESS, 0).await;
+        assert_eq!(get_recipient_balance(&devnet).await, FieldElement::from(TRANSFER_AMOUNT));
+
+        post(
+            &devnet,
+            "/stop_impersonate_account",
+            json!({ "account_address": PREDEPLOYED_ACCOUNT_ADDRESS }),
+        )
+        .await;
+
+        // the transaction is rejected, so the balance stays the same
+        send_unsigned_transfer(&devnet, PREDEPLOYED_ACCOUNT_ADDRESS, 1).await;
+        assert_eq!(get_recipient_balance(&devnet).await, FieldElement::from(TRANSFER_AMOUNT));
+    }
+
+    #[tokio::test]
+    async fn auto_impersonate_applies_to_all_accounts() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--auto-impersonate"])
+            .await
+            .expect("Could not start Devnet");
+
+        send_unsigned_transfer(&devnet, PREDEPLOYED_ACCOUNT_ADDRESS, 0).await;
+        assert_eq!(get_recipient_balance(&devnet).await, FieldElement::from(TRANSFER_AMOUNT));
+    }
+
+    #[tokio::test]
+    async fn impersonated_account_without_balance_funded_before_invoking() {
+        let auto_fund_amount: u128 = 1_000_000_000_000_000_000;
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--initial-balance",
+            "0",
+            "--impersonate-auto-fund",
+            &auto_fund_amount.to_string(),
+        ])
+        .await
+        .expect("Could not start Devnet");
+        assert_eq!(devnet.get_balance(PREDEPLOYED_ACCOUNT_ADDRESS).await, FieldElement::ZERO);
+
+        post(
+            &devnet,
+            "/impersonate_account",
+            json!({ "account_address": PREDEPLOYED_ACCOUNT_ADDRESS }),
+        )
+        .await;
+
+        // without funds, the fee couldn't be charged and the transfer would be rejected
+        send_unsigned_transfer(&devnet, PREDEPLOYED_ACCOUNT_ADDRESS, 0).await;
+        assert_eq!(get_recipient_balance(&devnet).await, FieldElement::from(TRANSFER_AMOUNT));
+
+        // the account is topped up to the amount again rather than funded with it once more
+        send_unsigned_transfer(&devnet, PREDEPLOYED_ACCOUNT_ADDRESS, 1).await;
+        assert_eq!(get_recipient_balance(&devnet).await, FieldElement::from(2 * TRANSFER_AMOUNT));
+        let sender_balance = devnet.get_balance(PREDEPLOYED_ACCOUNT_ADDRESS).await;
+        assert!(sender_balance < FieldElement::from(auto_fund_amount), "{sender_balance}");
+    }
+}
@@ -0,0 +1,140 @@
+pub mod common;
+
+mod lite_mode_tests {
+    use std::time::{Duration, Instant};
+
+    use hyper::Body;
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, MaybePendingStateUpdate};
+    use starknet_rs_core::utils::get_storage_var_address;
+    use starknet_rs_providers::Provider;
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    async fn get_storage_proof(devnet: &BackgroundDevnet) -> serde_json::Value {
+        let req_body = Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "starknet_getStorageProof",
+                "params": {
+                    "block_id": "latest",
+                    "contract_address": ERC20_CONTRACT_ADDRESS,
+                    "keys": []
+                }
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/rpc".into(), req_body).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    async fn latest_state_root(devnet: &BackgroundDevnet) -> FieldElement {
+        match devnet.json_rpc_client.get_state_update(BlockId::Tag(BlockTag::Latest)).await.unwrap()
+        {
+            MaybePendingStateUpdate::Update(state_update) => state_update.new_root,
+            other => panic!("Unexpected state update: {other:?}"),
+        }
+    }
+
+    async fn time_mints(devnet: &BackgroundDevnet, mints: usize) -> Duration {
+        let started_at = Instant::now();
+        for _ in 0..mints {
+            devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        }
+        started_at.elapsed()
+    }
+
+    #[tokio::test]
+    async fn storage_and_state_diffs_accurate_without_state_root() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--lite-mode"])
+            .await
+            .expect("Could not start Devnet");
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let erc20_address = FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap();
+        let balance_key = get_storage_var_address(
+            "ERC20_balances",
+            &[FieldElement::from_hex_be(DUMMY_ADDRESS).unwrap()],
+        )
+        .unwrap();
+        let balance = devnet
+            .json_rpc_client
+            .get_storage_at(erc20_address, balance_key, BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap();
+        assert_eq!(balance, FieldElement::from(2 * DUMMY_AMOUNT));
+
+        let state_update = match devnet
+            .json_rpc_client
+            .get_state_update(BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap()
+        {
+            MaybePendingStateUpdate::Update(state_update) => state_update,
+            other => panic!("Unexpected state update: {other:?}"),
+        };
+        assert_eq!(state_update.new_root, FieldElement::ZERO);
+        let erc20_diff = state_update
+            .state_diff
+            .storage_diffs
+            .iter()
+            .find(|diff| diff.address == erc20_address)
+            .expect("No storage diff of the fee token");
+        let balance_entry =
+            erc20_diff.storage_entries.iter().find(|entry| entry.key == balance_key).unwrap();
+        assert_eq!(balance_entry.value, FieldElement::from(2 * DUMMY_AMOUNT));
+    }
+
+    #[tokio::test]
+    async fn storage_proof_rejected_in_lite_mode() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--lite-mode"])
+            .await
+            .expect("Could not start Devnet");
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        assert_eq!(get_storage_proof(&devnet).await["error"]["code"], 42);
+    }
+
+    #[tokio::test]
+    async fn state_root_computed_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let state_root = latest_state_root(&devnet).await;
+        assert_ne!(state_root, FieldElement::ZERO);
+
+        let proof = get_storage_proof(&devnet).await;
+        assert_eq!(
+            FieldElement::from_hex_be(proof["result"]["contracts_tree_root"].as_str().unwrap())
+                .unwrap(),
+            state_root
+        );
+    }
+
+    /// Run with `cargo test --test test_lite_mode -- --ignored` to compare throughputs
+    #[tokio::test]
+    #[ignore]
+    async fn lite_mode_increases_throughput() {
+        const MINTS: usize = 50;
+
+        let full_devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let full_elapsed = time_mints(&full_devnet, MINTS).await;
+
+        let lite_devnet = BackgroundDevnet::spawn_with_additional_args(&["--lite-mode"])
+            .await
+            .expect("Could not start Devnet");
+        let lite_elapsed = time_mints(&lite_devnet, MINTS).await;
+
+        println!(
+            "{MINTS} blocks: {:.1} blocks/s with state root, {:.1} blocks/s in lite mode",
+            MINTS as f64 / full_elapsed.as_secs_f64(),
+            MINTS as f64 / lite_elapsed.as_secs_f64()
+        );
+        assert!(lite_elapsed < full_elapsed);
+    }
+}
@@ -0,0 +1,284 @@
+pub mod common;
+
+mod messaging_tests {
+    use hyper::{Body, Response, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::DEVNET_DEFAULT_SEQUENCER_ADDRESS;
+    use starknet_in_rust::{CasmContractClass, SierraContractClass};
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    /// Sierra artifact of a contract whose `l1_handle` L1 handler returns its only argument
+    const TEST_CONTRACT_PATH: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../starknet/test_artifacts/cairo_1_test.json");
+    const L1_CONTRACT_ADDRESS: &str = "0xe7";
+    /// 1 ETH, more than enough for executing the L1 handler
+    const PAID_FEE_ON_L1: &str = "0xde0b6b3a7640000";
+
+    fn felt_of(value: &serde_json::Value) -> FieldElement {
+        FieldElement::from_hex_be(value.as_str().unwrap()).unwrap()
+    }
+
+    /// Spawns Devnet whose predeployed accounts are instances of the test contract, so that its
+    /// L1 handler can be reached without declaring and deploying it. Returns the address of one
+    async fn spawn_with_l1_handler() -> (BackgroundDevnet, String) {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--account-class-custom",
+            TEST_CONTRACT_PATH,
+        ])
+        .await
+        .expect("Could not start Devnet");
+        let resp = devnet.get("/predeployed_accounts").await.unwrap();
+        let accounts = get_json_body(resp).await;
+        let address = accounts[0]["address"].as_str().unwrap().to_string();
+
+        (devnet, address)
+    }
+
+    async fn send_message_to_l2(
+        devnet: &BackgroundDevnet,
+        l2_contract_address: &str,
+        nonce: &str,
+    ) -> Response<Body> {
+        let l1_handle_selector = get_selector_from_name("l1_handle").unwrap();
+        let req_body = json!({
+            "l2_contract_address": l2_contract_address,
+            "entry_point_selector": format!("{l1_handle_selector:#x}"),
+            "l1_contract_address": L1_CONTRACT_ADDRESS,
+            "payload": ["0x2a"],
+            "nonce": nonce,
+            "fee": PAID_FEE_ON_L1
+        });
+        devnet
+            .post_json("/postman/send_message_to_l2".into(), Body::from(req_body.to_string()))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn loaded_l1_messaging_contract_address_returned() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let req_body = Body::from(json!({ "address": L1_CONTRACT_ADDRESS }).to_string());
+        let resp =
+            devnet.post_json("/postman/load_l1_messaging_contract".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(
+            felt_of(&resp_body["messaging_contract_address"]),
+            felt_of(&json!(L1_CONTRACT_ADDRESS))
+        );
+    }
+
+    #[tokio::test]
+    async fn flushing_without_messages_to_l1_returns_empty_list() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        // a transaction that doesn't send messages
+        let req_body = Body::from(json!({ "address": "0x1", "amount": 1 }).to_string());
+        devnet.post_json("/mint".into(), req_body).await.unwrap();
+
+        for _ in 0..2 {
+            let resp = devnet.post_json("/postman/flush".into(), Body::empty()).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+            assert_eq!(get_json_body(resp).await, json!([]));
+        }
+    }
+
+    #[tokio::test]
+    async fn message_consumed_by_l1_handler_transaction() {
+        let (devnet, l2_contract_address) = spawn_with_l1_handler().await;
+
+        let resp = send_message_to_l2(&devnet, &l2_contract_address, "0x0").await;
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let resp_body = get_json_body(resp).await;
+        let transaction_hash = resp_body["transaction_hash"].clone();
+        let message_hash = resp_body["message_hash"].clone();
+
+        let block = devnet
+            .rpc_call("starknet_getBlockWithTxs", json!({ "block_id": "latest" }))
+            .await["result"]
+            .clone();
+        let transaction = &block["transactions"][0];
+        assert_eq!(transaction["type"], "L1_HANDLER");
+        assert_eq!(felt_of(&transaction["transaction_hash"]), felt_of(&transaction_hash));
+        assert_eq!(felt_of(&transaction["contract_address"]), felt_of(&json!(l2_contract_address)));
+        // the handler receives the L1 sender before the payload
+        let calldata: Vec<FieldElement> =
+            transaction["calldata"].as_array().unwrap().iter().map(felt_of).collect();
+        assert_eq!(calldata, vec![felt_of(&json!(L1_CONTRACT_ADDRESS)), FieldElement::from(42u32)]);
+
+        let receipt = devnet
+            .rpc_call(
+                "starknet_getTransactionReceipt",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await["result"]
+            .clone();
+        assert_eq!(receipt["type"], "L1_HANDLER");
+        assert_eq!(receipt["message_hash"], message_hash);
+    }
+
+    #[tokio::test]
+    async fn fee_paid_on_l1_in_receipt_without_charging_l2() {
+        let (devnet, l2_contract_address) = spawn_with_l1_handler().await;
+        // fees charged on L2 are transferred to the sequencer
+        let sequencer_address = DEVNET_DEFAULT_SEQUENCER_ADDRESS;
+        let handler_balance = devnet.get_balance(&l2_contract_address).await;
+        let sequencer_balance = devnet.get_balance(sequencer_address).await;
+
+        let resp = send_message_to_l2(&devnet, &l2_contract_address, "0x0").await;
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let transaction_hash = get_json_body(resp).await["transaction_hash"].clone();
+
+        let receipt = devnet
+            .rpc_call(
+                "starknet_getTransactionReceipt",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await["result"]
+            .clone();
+        assert_eq!(receipt["type"], "L1_HANDLER");
+        assert_eq!(receipt["execution_status"], "SUCCEEDED");
+        assert_eq!(felt_of(&receipt["actual_fee"]["amount"]), felt_of(&json!(PAID_FEE_ON_L1)));
+        assert_eq!(receipt["actual_fee"]["unit"], "WEI");
+
+        assert_eq!(devnet.get_balance(&l2_contract_address).await, handler_balance);
+        assert_eq!(devnet.get_balance(sequencer_address).await, sequencer_balance);
+    }
+
+    #[tokio::test]
+    async fn message_with_nonce_below_consumed_one_rejected() {
+        let (devnet, l2_contract_address) = spawn_with_l1_handler().await;
+
+        let resp = send_message_to_l2(&devnet, &l2_contract_address, "0x2").await;
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let resp = send_message_to_l2(&devnet, &l2_contract_address, "0x1").await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "Checking status of {resp:?}");
+        let error = get_json_body(resp).await["error"].as_str().unwrap().to_string();
+        assert!(error.contains("out of order"), "Unexpected error: {error}");
+    }
+
+    #[tokio::test]
+    async fn message_with_consumed_nonce_rejected() {
+        let (devnet, l2_contract_address) = spawn_with_l1_handler().await;
+
+        let resp = send_message_to_l2(&devnet, &l2_contract_address, "0x1").await;
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let resp = send_message_to_l2(&devnet, &l2_contract_address, "0x1").await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "Checking status of {resp:?}");
+
+        let resp = send_message_to_l2(&devnet, &l2_contract_address, "0x2").await;
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+    }
+
+    /// Declares and deploys the test contract, whose `test_send_message_to_l1` sends a message to
+    /// L1. Returns the address of the deployed contract
+    async fn deploy_test_contract(devnet: &BackgroundDevnet) -> String {
+        let sierra: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(TEST_CONTRACT_PATH).unwrap()).unwrap();
+        let sierra_contract_class: SierraContractClass =
+            serde_json::from_value(sierra.clone()).unwrap();
+        let casm = CasmContractClass::from_contract_class(sierra_contract_class, true).unwrap();
+
+        let resp = devnet
+            .post_json(
+                "/declare".into(),
+                Body::from(json!({ "sierra": sierra, "casm": casm }).to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let class_hash = get_json_body(resp).await["class_hash"].clone();
+
+        let deploy_body =
+            json!({ "class_hash": class_hash, "constructor_calldata": ["0x1", "0x2"] });
+        let resp =
+            devnet.post_json("/deploy".into(), Body::from(deploy_body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        get_json_body(resp).await["contract_address"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn messages_to_l1_of_nested_calls_in_receipt() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--allow-dev-declare",
+            "--allow-dev-deploy",
+            "--auto-impersonate",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        let contract_address = deploy_test_contract(&devnet).await;
+
+        // the account calls the contract twice, each call sending a message with a one-felt payload
+        let send_selector =
+            format!("{:#x}", get_selector_from_name("test_send_message_to_l1").unwrap());
+        let nonce = devnet
+            .rpc_call(
+                "starknet_getNonce",
+                json!({ "block_id": "latest", "contract_address": PREDEPLOYED_ACCOUNT_ADDRESS }),
+            )
+            .await["result"]
+            .clone();
+        let resp_body = devnet
+            .rpc_call(
+                "starknet_addInvokeTransaction",
+                json!({
+                    "invoke_transaction": {
+                        "type": "INVOKE",
+                        "version": "0x1",
+                        "max_fee": "0xde0b6b3a7640000",
+                        "signature": [],
+                        "nonce": nonce,
+                        "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                        "calldata": [
+                            "0x2",
+                            contract_address,
+                            send_selector,
+                            "0x0",
+                            "0x3",
+                            contract_address,
+                            send_selector,
+                            "0x3",
+                            "0x3",
+                            "0x6",
+                            "0xe1",
+                            "0x1",
+                            "0x11",
+                            "0xe2",
+                            "0x1",
+                            "0x22"
+                        ]
+                    }
+                }),
+            )
+            .await;
+        let transaction_hash = resp_body["result"]["transaction_hash"].clone();
+        assert!(transaction_hash.is_string(), "Unexpected response: {resp_body}");
+
+        let receipt = devnet
+            .rpc_call(
+                "starknet_getTransactionReceipt",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await["result"]
+            .clone();
+        assert_eq!(receipt["execution_status"], "SUCCEEDED", "{receipt}");
+        let messages_sent = receipt["messages_sent"].as_array().unwrap();
+        assert_eq!(messages_sent.len(), 2, "{receipt}");
+        for (message, (to_address, payload)) in
+            messages_sent.iter().zip([("0xe1", "0x11"), ("0xe2", "0x22")])
+        {
+            assert_eq!(felt_of(&message["from_address"]), felt_of(&json!(contract_address)));
+            assert_eq!(felt_of(&message["to_address"]), felt_of(&json!(to_address)));
+            let payload_felts: Vec<FieldElement> =
+                message["payload"].as_array().unwrap().iter().map(felt_of).collect();
+            assert_eq!(payload_felts, vec![felt_of(&json!(payload))]);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+pub mod common;
+
+mod metrics_tests {
+    use hyper::{header, StatusCode};
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static INVOKE_COUNTER: &str = "devnet_transactions_total{type=\"INVOKE\"}";
+
+    async fn scrape(devnet: &BackgroundDevnet) -> String {
+        let resp = devnet.get("/metrics").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], "text/plain; version=0.0.4");
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    /// Value of the sample named `name`, or zero if it wasn't reported yet
+    fn sample_value(metrics: &str, name: &str) -> u64 {
+        metrics
+            .lines()
+            .find_map(|line| line.strip_prefix(name)?.trim().parse().ok())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn transaction_counter_incremented_on_transaction() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--enable-metrics"])
+            .await
+            .expect("Could not start Devnet");
+
+        let metrics_before = scrape(&devnet).await;
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let metrics_after = scrape(&devnet).await;
+
+        assert_eq!(
+            sample_value(&metrics_after, INVOKE_COUNTER),
+            sample_value(&metrics_before, INVOKE_COUNTER) + 1,
+            "{metrics_after}"
+        );
+        assert_eq!(
+            sample_value(&metrics_after, "devnet_block_number"),
+            sample_value(&metrics_before, "devnet_block_number") + 1,
+            "{metrics_after}"
+        );
+        assert_eq!(
+            sample_value(&metrics_after, "devnet_transaction_execution_seconds_count"),
+            sample_value(&metrics_before, "devnet_transaction_execution_seconds_count") + 1,
+            "{metrics_after}"
+        );
+        assert_eq!(sample_value(&metrics_after, "devnet_pending_transactions"), 0);
+    }
+
+    #[tokio::test]
+    async fn metrics_unavailable_if_not_enabled() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet.get("/metrics").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let resp_body = get_json_body(resp).await;
+        assert!(
+            resp_body["error"].as_str().unwrap().contains("--enable-metrics"),
+            "Unexpected response: {resp_body}"
+        );
+    }
+}
@@ -3,6 +3,14 @@ pub mod common;
 mod minting_tests {
     use hyper::{Body, StatusCode};
     use serde_json::json;
+    use starknet_core::constants::{
+        CHARGEABLE_ACCOUNT_ADDRESS, ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS,
+    };
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, FieldElement, FunctionCall, MaybePendingBlockWithTxHashes,
+    };
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_providers::Provider;
 
     use crate::common::constants::{
         PREDEPLOYED_ACCOUNT_ADDRESS, PREDEPLOYED_ACCOUNT_INITIAL_BALANCE,
@@ -11,6 +19,7 @@ mod minting_tests {
 
     static DUMMY_ADDRESS: &str = "0x42";
     static DUMMY_AMOUNT: u128 = 42;
+    static CUSTOM_ETH_FEE_TOKEN_ADDRESS: &str = "0xe7";
 
     async fn increase_balance_happy_path(address: &str, init_amount: u128, mint_amount: u128) {
         let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
@@ -56,6 +65,213 @@ mod minting_tests {
         .await
     }
 
+    async fn get_token_balance(
+        devnet: &BackgroundDevnet,
+        token_address: &str,
+        address: &str,
+    ) -> Vec<FieldElement> {
+        devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address: FieldElement::from_hex_be(token_address).unwrap(),
+                    entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                    calldata: vec![FieldElement::from_hex_be(address).unwrap()],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn get_eth_balance(devnet: &BackgroundDevnet, address: &str) -> Vec<FieldElement> {
+        get_token_balance(devnet, ERC20_CONTRACT_ADDRESS, address).await
+    }
+
+    #[tokio::test]
+    async fn mint_receipt_holds_transfer_event_of_fee_token() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let req_body =
+            Body::from(json!({ "address": DUMMY_ADDRESS, "amount": DUMMY_AMOUNT }).to_string());
+        let resp = devnet.post_json("/mint".into(), req_body).await.unwrap();
+        let tx_hash = get_json_body(resp).await["tx_hash"].take();
+
+        let req_body = Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "starknet_getTransactionReceipt",
+                "params": { "transaction_hash": tx_hash }
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/rpc".into(), req_body).await.unwrap();
+        let receipt = get_json_body(resp).await["result"].take();
+
+        // the chargeable account calls the fee token, which is the one emitting the event
+        let transfer_selector = get_selector_from_name("Transfer").unwrap();
+        let to_felt =
+            |value: &serde_json::Value| FieldElement::from_hex_be(value.as_str().unwrap()).unwrap();
+        let transfer_event = receipt["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|event| to_felt(&event["keys"][0]) == transfer_selector)
+            .unwrap_or_else(|| panic!("No Transfer event in receipt {receipt}"));
+        assert_eq!(
+            to_felt(&transfer_event["from_address"]),
+            FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap()
+        );
+        assert_ne!(
+            to_felt(&transfer_event["from_address"]),
+            FieldElement::from_hex_be(CHARGEABLE_ACCOUNT_ADDRESS).unwrap()
+        );
+        let data: Vec<FieldElement> =
+            transfer_event["data"].as_array().unwrap().iter().map(to_felt).collect();
+        // from, to, amount as low and high part
+        assert_eq!(
+            data,
+            [
+                FieldElement::ZERO,
+                FieldElement::from_hex_be(DUMMY_ADDRESS).unwrap(),
+                FieldElement::from(DUMMY_AMOUNT),
+                FieldElement::ZERO
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn increase_strk_balance_without_changing_eth_balance() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let eth_balance_before = get_eth_balance(&devnet, PREDEPLOYED_ACCOUNT_ADDRESS).await;
+
+        let req_body = Body::from(
+            json!({
+                "address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                "amount": DUMMY_AMOUNT,
+                "unit": "FRI"
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/mint".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(resp_body["new_balance"], DUMMY_AMOUNT.to_string());
+        assert_eq!(resp_body["unit"], "FRI");
+
+        let eth_balance_after = get_eth_balance(&devnet, PREDEPLOYED_ACCOUNT_ADDRESS).await;
+        assert_eq!(eth_balance_after, eth_balance_before);
+    }
+
+    async fn get_account_balance(
+        devnet: &BackgroundDevnet,
+        address: &str,
+        unit: &str,
+        block_tag: &str,
+    ) -> serde_json::Value {
+        let resp = devnet
+            .get(&format!("/account_balance?address={address}&unit={unit}&block_tag={block_tag}"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        get_json_body(resp).await
+    }
+
+    #[tokio::test]
+    async fn minted_strk_balance_returned_by_account_balance() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+                .await
+                .expect("Could not start Devnet");
+        // more than fits in 128 bits
+        let amount = "1000000000000000000000000000000000000000000";
+
+        let req_body = Body::from(
+            json!({ "address": DUMMY_ADDRESS, "amount": amount, "unit": "FRI" }).to_string(),
+        );
+        let resp = devnet.post_json("/mint".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        assert_eq!(
+            get_account_balance(&devnet, DUMMY_ADDRESS, "FRI", "pending").await,
+            json!({ "amount": amount, "unit": "FRI" })
+        );
+        // the mint is not committed to a block yet
+        assert_eq!(
+            get_account_balance(&devnet, DUMMY_ADDRESS, "FRI", "latest").await,
+            json!({ "amount": "0", "unit": "FRI" })
+        );
+        assert_eq!(
+            get_account_balance(&devnet, DUMMY_ADDRESS, "WEI", "pending").await,
+            json!({ "amount": "0", "unit": "WEI" })
+        );
+
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        assert_eq!(
+            get_account_balance(&devnet, DUMMY_ADDRESS, "FRI", "latest").await,
+            json!({ "amount": amount, "unit": "FRI" })
+        );
+    }
+
+    #[tokio::test]
+    async fn account_balance_defaults_to_wei_at_latest_block() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet
+            .get(&format!("/account_balance?address={PREDEPLOYED_ACCOUNT_ADDRESS}"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        assert_eq!(
+            get_json_body(resp).await,
+            json!({ "amount": PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string(), "unit": "WEI" })
+        );
+
+        let resp = devnet
+            .get(&format!("/account_balance?address={DUMMY_ADDRESS}&unit=GWEI"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "Checking status of {resp:?}");
+    }
+
+    #[tokio::test]
+    async fn increase_balance_in_custom_eth_fee_token() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--eth-fee-token-address",
+            CUSTOM_ETH_FEE_TOKEN_ADDRESS,
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let req_body = Body::from(
+            json!({ "address": PREDEPLOYED_ACCOUNT_ADDRESS, "amount": DUMMY_AMOUNT }).to_string(),
+        );
+        let resp = devnet.post_json("/mint".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let expected_balance = PREDEPLOYED_ACCOUNT_INITIAL_BALANCE + DUMMY_AMOUNT;
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(resp_body["new_balance"], expected_balance.to_string());
+
+        let balance =
+            get_token_balance(&devnet, CUSTOM_ETH_FEE_TOKEN_ADDRESS, PREDEPLOYED_ACCOUNT_ADDRESS)
+                .await;
+        assert_eq!(balance, vec![FieldElement::from(expected_balance), FieldElement::ZERO]);
+    }
+
+    #[tokio::test]
+    async fn reject_unknown_unit() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        reject_bad_request(
+            &devnet,
+            json!({ "address": DUMMY_ADDRESS, "amount": DUMMY_AMOUNT, "unit": "GWEI" }),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        )
+        .await;
+    }
+
     async fn reject_bad_request(
         devnet: &BackgroundDevnet,
         json_body: serde_json::Value,
@@ -101,4 +317,111 @@ mod minting_tests {
         )
         .await;
     }
+
+    #[tokio::test]
+    async fn mint_strk_alias_mints_strk() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let req_body =
+            Body::from(json!({ "address": DUMMY_ADDRESS, "amount": DUMMY_AMOUNT }).to_string());
+        let resp = devnet.post_json("/mint_strk".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(resp_body["new_balance"], DUMMY_AMOUNT.to_string());
+        assert_eq!(resp_body["unit"], "FRI");
+
+        assert_eq!(
+            get_token_balance(&devnet, STRK_ERC20_CONTRACT_ADDRESS, DUMMY_ADDRESS).await,
+            vec![FieldElement::from(DUMMY_AMOUNT), FieldElement::ZERO]
+        );
+        assert_eq!(
+            get_eth_balance(&devnet, DUMMY_ADDRESS).await,
+            vec![FieldElement::ZERO, FieldElement::ZERO]
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_of_mints_sealed_in_one_block() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+                .await
+                .expect("Could not start Devnet");
+
+        let addresses: Vec<String> = (1..=5).map(|i| format!("{:#x}", 0x100 + i)).collect();
+        let mints: Vec<serde_json::Value> = addresses
+            .iter()
+            .enumerate()
+            .map(|(i, address)| {
+                let unit = if i % 2 == 0 { "WEI" } else { "FRI" };
+                json!({ "address": address, "amount": DUMMY_AMOUNT + i as u128, "unit": unit })
+            })
+            .collect();
+        let req_body = Body::from(json!({ "mints": mints, "seal_block": true }).to_string());
+        let resp = devnet.post_json("/mint_batch".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let resp_body = get_json_body(resp).await;
+
+        for (i, address) in addresses.iter().enumerate() {
+            let expected_amount = DUMMY_AMOUNT + i as u128;
+            let balance = &resp_body["balances"][i];
+            assert_eq!(
+                FieldElement::from_hex_be(balance["address"].as_str().unwrap()).unwrap(),
+                FieldElement::from_hex_be(address).unwrap()
+            );
+            assert_eq!(balance["new_balance"], expected_amount.to_string());
+
+            let token_address =
+                if i % 2 == 0 { ERC20_CONTRACT_ADDRESS } else { STRK_ERC20_CONTRACT_ADDRESS };
+            assert_eq!(
+                get_token_balance(&devnet, token_address, address).await,
+                vec![FieldElement::from(expected_amount), FieldElement::ZERO]
+            );
+        }
+
+        // the whole batch is a single transaction of the only block after the genesis block
+        let latest_block = match devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap()
+        {
+            MaybePendingBlockWithTxHashes::Block(block) => block,
+            other => panic!("Unexpected block: {other:?}"),
+        };
+        assert_eq!(latest_block.block_number, 1);
+        assert_eq!(
+            latest_block.transactions,
+            vec![FieldElement::from_hex_be(resp_body["tx_hash"].as_str().unwrap()).unwrap()]
+        );
+        assert!(
+            devnet.json_rpc_client.get_block_with_tx_hashes(BlockId::Number(2)).await.is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_of_mints_with_overflowing_amount_applies_nothing() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let max_amount = format!("0x{}", "f".repeat(64));
+
+        let req_body = Body::from(
+            json!({
+                "mints": [
+                    { "address": DUMMY_ADDRESS, "amount": DUMMY_AMOUNT },
+                    { "address": PREDEPLOYED_ACCOUNT_ADDRESS, "amount": max_amount }
+                ]
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/mint_batch".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "Checking status of {resp:?}");
+
+        assert_eq!(
+            get_eth_balance(&devnet, DUMMY_ADDRESS).await,
+            vec![FieldElement::ZERO, FieldElement::ZERO]
+        );
+        assert_eq!(
+            get_eth_balance(&devnet, PREDEPLOYED_ACCOUNT_ADDRESS).await,
+            vec![FieldElement::from(PREDEPLOYED_ACCOUNT_INITIAL_BALANCE), FieldElement::ZERO]
+        );
+    }
 }
@@ -0,0 +1,84 @@
+pub mod common;
+
+mod predeclared_classes_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+    use starknet_rs_core::utils::{cairo_short_string_to_felt, get_selector_from_name};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static INITIAL_SUPPLY: u128 = 1000;
+
+    #[tokio::test]
+    async fn predeclared_erc20_class_declared_and_deployable() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--predeclare",
+            "standard",
+            "--allow-dev-deploy",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let config = get_json_body(devnet.get("/config").await.unwrap()).await;
+        let class_hash = config["predeclared_classes"]["erc20"].as_str().unwrap().to_string();
+        devnet
+            .json_rpc_client
+            .get_class(
+                BlockId::Tag(BlockTag::Latest),
+                FieldElement::from_hex_be(&class_hash).unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let to_hex = |felt: FieldElement| format!("{felt:#x}");
+        let resp = devnet
+            .post_json(
+                "/deploy".into(),
+                Body::from(
+                    json!({
+                        "class_hash": class_hash,
+                        "constructor_calldata": [
+                            to_hex(cairo_short_string_to_felt("Token").unwrap()),
+                            to_hex(cairo_short_string_to_felt("TKN").unwrap()),
+                            "0x12", // decimals
+                            format!("{INITIAL_SUPPLY:#x}"), // initial supply, low
+                            "0x0", // initial supply, high
+                            PREDEPLOYED_ACCOUNT_ADDRESS, // recipient
+                            PREDEPLOYED_ACCOUNT_ADDRESS // owner
+                        ]
+                    })
+                    .to_string(),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let contract_address =
+            get_json_body(resp).await["contract_address"].as_str().unwrap().to_string();
+
+        let balance = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address: FieldElement::from_hex_be(&contract_address).unwrap(),
+                    entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                    calldata: vec![FieldElement::from_hex_be(PREDEPLOYED_ACCOUNT_ADDRESS).unwrap()],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap();
+        assert_eq!(balance, vec![FieldElement::from(INITIAL_SUPPLY), FieldElement::ZERO]);
+    }
+
+    #[tokio::test]
+    async fn nothing_reported_as_predeclared_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let config = get_json_body(devnet.get("/config").await.unwrap()).await;
+        assert_eq!(config["predeclared_classes"], json!({}));
+    }
+}
@@ -0,0 +1,130 @@
+pub mod common;
+
+mod predeployed_accounts_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::{DEVNET_DEFAULT_CHAIN_ID, ERC20_CONTRACT_ADDRESS};
+    use starknet_core::raw_execution::{Call, RawExecution};
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_signers::{LocalWallet, Signer, SigningKey};
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_INITIAL_BALANCE;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    async fn get_predeployed_accounts(devnet: &BackgroundDevnet) -> Vec<serde_json::Value> {
+        let resp = devnet.get("/predeployed_accounts").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        get_json_body(resp).await.as_array().unwrap().clone()
+    }
+
+    fn felt_of(value: &serde_json::Value) -> FieldElement {
+        FieldElement::from_hex_be(value.as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn returned_private_key_signs_accepted_transaction() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let account = get_predeployed_accounts(&devnet).await[0].clone();
+        let account_address = felt_of(&account["address"]);
+        assert_eq!(account["balance"], PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string());
+
+        let raw_execution = RawExecution {
+            calls: vec![Call {
+                to: FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap(),
+                selector: get_selector_from_name("transfer").unwrap(),
+                calldata: vec![FieldElement::from(0x42_u32), FieldElement::ONE, FieldElement::ZERO],
+            }],
+            nonce: FieldElement::ZERO,
+            max_fee: FieldElement::from(1_000_000_000_000_000_000_u128),
+        };
+        let transaction_hash = raw_execution
+            .transaction_hash(DEVNET_DEFAULT_CHAIN_ID.to_felt().into(), account_address);
+        let signature =
+            LocalWallet::from(SigningKey::from_secret_scalar(felt_of(&account["private_key"])))
+                .sign_hash(&transaction_hash)
+                .await
+                .unwrap();
+
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_addInvokeTransaction",
+            "params": {
+                "invoke_transaction": {
+                    "type": "INVOKE",
+                    "version": "0x1",
+                    "max_fee": format!("{:#x}", raw_execution.max_fee),
+                    "signature": [format!("{:#x}", signature.r), format!("{:#x}", signature.s)],
+                    "nonce": "0x0",
+                    "sender_address": format!("{account_address:#x}"),
+                    "calldata": raw_execution
+                        .raw_calldata()
+                        .iter()
+                        .map(|felt| format!("{felt:#x}"))
+                        .collect::<Vec<String>>(),
+                }
+            }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(felt_of(&resp_body["result"]["transaction_hash"]), transaction_hash);
+
+        // the transferred amount and the fee are deducted from the current balance
+        let account = get_predeployed_accounts(&devnet).await[0].clone();
+        let balance: u128 = account["balance"].as_str().unwrap().parse().unwrap();
+        assert!(balance < PREDEPLOYED_ACCOUNT_INITIAL_BALANCE);
+        assert_eq!(account["initial_balance"], PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string());
+    }
+
+    #[tokio::test]
+    async fn private_keys_hidden_on_request() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--hide-predeployed-keys"])
+            .await
+            .expect("Could not start Devnet");
+
+        for account in get_predeployed_accounts(&devnet).await {
+            assert!(account.get("private_key").is_none());
+            assert!(account["public_key"].is_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn same_seed_yields_same_accounts() {
+        // both instances are started with the same explicit seed
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let other_devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let accounts = get_predeployed_accounts(&devnet).await;
+        assert!(!accounts.is_empty());
+        assert_eq!(accounts, get_predeployed_accounts(&other_devnet).await);
+    }
+
+    #[tokio::test]
+    async fn detailed_listing_reports_balances_in_both_tokens() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let account = get_predeployed_accounts(&devnet).await[0].clone();
+        let minted_amount = 1_000_000_u128;
+
+        let mint_body =
+            json!({ "address": account["address"], "amount": minted_amount, "unit": "FRI" });
+        let resp =
+            devnet.post_json("/mint".into(), Body::from(mint_body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = devnet.get("/predeployed_accounts?detailed=true").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let accounts = get_json_body(resp).await;
+        assert_eq!(
+            accounts[0]["balance"],
+            json!({
+                "eth": PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string(),
+                "strk": minted_amount.to_string()
+            })
+        );
+
+        // without the parameter, only the balance in ETH is reported
+        let account = get_predeployed_accounts(&devnet).await[0].clone();
+        assert_eq!(account["balance"], PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string());
+    }
+}
@@ -0,0 +1,198 @@
+pub mod common;
+
+mod predict_address_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::UDC_CONTRACT_ADDRESS;
+    use starknet_in_rust::{CasmContractClass, SierraContractClass};
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    /// Constructor calldata of the test contract: its initial balance
+    const INITIAL_BALANCE: &str = "0x2a";
+
+    /// Declares the test contract, a contract holding a balance, with the first predeployed
+    /// account and returns its class hash
+    async fn declare_test_contract(devnet: &BackgroundDevnet) -> String {
+        let mut sierra: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/test_data/sierra_contract_class_with_abi_as_string.json"
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+        sierra["abi"] = serde_json::from_str(sierra["abi"].as_str().unwrap()).unwrap();
+        let sierra_contract_class: SierraContractClass =
+            serde_json::from_value(sierra.clone()).unwrap();
+        let casm = CasmContractClass::from_contract_class(sierra_contract_class, true).unwrap();
+
+        let resp = devnet
+            .post_json(
+                "/declare".into(),
+                Body::from(json!({ "sierra": sierra, "casm": casm }).to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        get_json_body(resp).await["class_hash"].as_str().unwrap().to_string()
+    }
+
+    async fn predict_address(devnet: &BackgroundDevnet, body: serde_json::Value) -> FieldElement {
+        let resp = devnet
+            .post_json("/predict_address".into(), Body::from(body.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let resp_body = get_json_body(resp).await;
+        FieldElement::from_hex_be(resp_body["contract_address"].as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn predicted_address_matches_address_of_dev_deployment() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--allow-dev-declare",
+            "--allow-dev-deploy",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        let class_hash = declare_test_contract(&devnet).await;
+
+        // POST /deploy deploys instances that are not unique, so without a deployer
+        let udc_prediction = predict_address(
+            &devnet,
+            json!({
+                "class_hash": class_hash,
+                "constructor_calldata": [INITIAL_BALANCE],
+                "salt": "0x3",
+                "deployment_type": "UDC"
+            }),
+        )
+        .await;
+        let deploy_account_prediction = predict_address(
+            &devnet,
+            json!({
+                "class_hash": class_hash,
+                "constructor_calldata": [INITIAL_BALANCE],
+                "salt": "0x3",
+                "deployer_address": "0x0",
+                "deployment_type": "DEPLOY_ACCOUNT"
+            }),
+        )
+        .await;
+        assert_eq!(udc_prediction, deploy_account_prediction);
+
+        let deploy_body = json!({
+            "class_hash": class_hash,
+            "constructor_calldata": [INITIAL_BALANCE],
+            "salt": "0x3"
+        });
+        let resp =
+            devnet.post_json("/deploy".into(), Body::from(deploy_body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let resp_body = get_json_body(resp).await;
+        let contract_address =
+            FieldElement::from_hex_be(resp_body["contract_address"].as_str().unwrap()).unwrap();
+
+        assert_eq!(udc_prediction, contract_address);
+    }
+
+    #[tokio::test]
+    async fn predicted_address_matches_address_of_unique_udc_deployment() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--allow-dev-declare",
+            "--auto-impersonate",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        let class_hash = declare_test_contract(&devnet).await;
+
+        let predicted_address = predict_address(
+            &devnet,
+            json!({
+                "class_hash": class_hash,
+                "constructor_calldata": [INITIAL_BALANCE],
+                "salt": "0x7",
+                "deployer_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                "deployment_type": "UDC"
+            }),
+        )
+        .await;
+
+        // the declaration used nonce 0 of the account
+        let deploy_selector = get_selector_from_name("deployContract").unwrap();
+        let resp_body = devnet
+            .rpc_call(
+                "starknet_addInvokeTransaction",
+                json!({
+                    "invoke_transaction": {
+                        "type": "INVOKE",
+                        "version": "0x1",
+                        "max_fee": "0x2386f26fc10000",
+                        "signature": [],
+                        "nonce": "0x1",
+                        "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                        "calldata": [
+                            "0x1",
+                            UDC_CONTRACT_ADDRESS,
+                            format!("{deploy_selector:#x}"),
+                            "0x0",
+                            "0x5",
+                            "0x5",
+                            class_hash,
+                            "0x7", // salt
+                            "0x1", // unique
+                            "0x1", // constructor calldata len
+                            INITIAL_BALANCE
+                        ]
+                    }
+                }),
+            )
+            .await;
+        let receipt = devnet
+            .rpc_call(
+                "starknet_getTransactionReceipt",
+                json!({ "transaction_hash": resp_body["result"]["transaction_hash"] }),
+            )
+            .await;
+        assert_eq!(receipt["result"]["execution_status"], "SUCCEEDED", "{receipt}");
+
+        // the UDC emits the address of the deployed contract
+        let udc_address = FieldElement::from_hex_be(UDC_CONTRACT_ADDRESS).unwrap();
+        let deployment_event = receipt["result"]["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|event| {
+                FieldElement::from_hex_be(event["from_address"].as_str().unwrap()).unwrap()
+                    == udc_address
+            })
+            .unwrap()
+            .clone();
+        let contract_address =
+            FieldElement::from_hex_be(deployment_event["data"][0].as_str().unwrap()).unwrap();
+
+        assert_eq!(predicted_address, contract_address);
+    }
+
+    #[tokio::test]
+    async fn deployer_of_deploy_account_rejected() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let body = json!({
+            "class_hash": "0x123",
+            "deployer_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+            "deployment_type": "DEPLOY_ACCOUNT"
+        });
+        let resp = devnet
+            .post_json("/predict_address".into(), Body::from(body.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let error = get_json_body(resp).await["error"].as_str().unwrap().to_string();
+        assert!(error.contains("deployer_address"), "{error}");
+    }
+}
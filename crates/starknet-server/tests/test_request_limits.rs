@@ -0,0 +1,116 @@
+pub mod common;
+
+mod request_limits_tests {
+    use std::time::Duration;
+
+    use futures::future::join_all;
+    use hyper::body::Bytes;
+    use hyper::{header, Body, Response, StatusCode};
+    use serde_json::json;
+
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    #[tokio::test]
+    async fn request_body_exceeding_limit_is_rejected() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--request-body-limit", "1000"])
+                .await
+                .expect("Could not start Devnet");
+
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_chainId",
+            "params": { "padding": "0".repeat(1000) }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // a body within the limit is still served
+        let req_body = json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_chainId" });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(get_json_body(resp).await["result"].is_string());
+    }
+
+    #[tokio::test]
+    async fn streamed_request_body_exceeding_limit_is_rejected() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--request-body-limit", "1000"])
+                .await
+                .expect("Could not start Devnet");
+
+        // sent in chunks, without declaring the content length up front
+        let (mut sender, body) = Body::channel();
+        tokio::spawn(async move {
+            for _ in 0..10 {
+                if sender.send_data(Bytes::from(vec![b' '; 200])).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let resp = devnet.post_json("/rpc".into(), body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn request_exceeding_timeout_is_rejected() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--request-timeout-secs", "1"])
+            .await
+            .expect("Could not start Devnet");
+
+        // the body is never completed, so the request is still being served when the timeout
+        // expires; the sender is kept alive until the response arrives
+        let (_sender, body) = Body::channel();
+
+        let resp = devnet.post_json("/rpc".into(), body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::REQUEST_TIMEOUT);
+    }
+
+    /// Sends `count` requests at once and returns their responses
+    async fn send_burst(devnet: &BackgroundDevnet, count: usize) -> Vec<Response<Body>> {
+        let req_body = json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_chainId" });
+        let responses = join_all(
+            (0..count).map(|_| devnet.post_json("/rpc".into(), Body::from(req_body.to_string()))),
+        )
+        .await;
+
+        responses.into_iter().map(Result::unwrap).collect()
+    }
+
+    #[tokio::test]
+    async fn requests_exceeding_rate_limit_are_rejected() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--rate-limit", "10"])
+            .await
+            .expect("Could not start Devnet");
+
+        // a burst below the limit is served entirely
+        let responses = send_burst(&devnet, 5).await;
+        assert!(responses.iter().all(|resp| resp.status() == StatusCode::OK));
+
+        // wait for the bucket to be refilled
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        // at most a second worth of requests is accepted at once, besides the tokens refilled
+        // while the burst is sent
+        let (rejected, accepted): (Vec<_>, Vec<_>) = send_burst(&devnet, 30)
+            .await
+            .into_iter()
+            .partition(|resp| resp.status() == StatusCode::TOO_MANY_REQUESTS);
+        assert!(rejected.len() >= 10, "Only {} requests rejected", rejected.len());
+        assert!(accepted.iter().all(|resp| resp.status() == StatusCode::OK));
+
+        let resp = rejected.into_iter().next().unwrap();
+        let retry_after: u64 =
+            resp.headers()[header::RETRY_AFTER].to_str().unwrap().parse().unwrap();
+        assert!(retry_after >= 1);
+        assert_eq!(get_json_body(resp).await["error"]["code"], -32005);
+
+        // served again once the client waits as instructed
+        tokio::time::sleep(Duration::from_secs(retry_after)).await;
+        let req_body = json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_chainId" });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,85 @@
+pub mod common;
+
+mod restart_tests {
+    use futures::{SinkExt, StreamExt};
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_providers::Provider;
+    use tokio_tungstenite::connect_async;
+    use tokio_tungstenite::tungstenite::Message;
+
+    use crate::common::constants::{
+        PREDEPLOYED_ACCOUNT_ADDRESS, PREDEPLOYED_ACCOUNT_INITIAL_BALANCE,
+    };
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    async fn get_predeployed_account_balance(devnet: &BackgroundDevnet) -> String {
+        let resp = devnet.get("/predeployed_accounts").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let address = FieldElement::from_hex_be(PREDEPLOYED_ACCOUNT_ADDRESS).unwrap();
+        let accounts = get_json_body(resp).await;
+        let account = accounts
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|account| {
+                FieldElement::from_hex_be(account["address"].as_str().unwrap()).unwrap() == address
+            })
+            .unwrap();
+        account["balance"].as_str().unwrap().to_string()
+    }
+
+    async fn restart(devnet: &BackgroundDevnet) {
+        let resp = devnet.post_json("/restart".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+    }
+
+    #[tokio::test]
+    async fn minted_balance_reverted_after_restart() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let req_body =
+            Body::from(json!({ "address": PREDEPLOYED_ACCOUNT_ADDRESS, "amount": 42 }).to_string());
+        let resp = devnet.post_json("/mint".into(), req_body).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        let mint_hash = get_json_body(resp).await["tx_hash"].as_str().unwrap().to_string();
+        assert_eq!(
+            get_predeployed_account_balance(&devnet).await,
+            (PREDEPLOYED_ACCOUNT_INITIAL_BALANCE + 42).to_string()
+        );
+
+        restart(&devnet).await;
+
+        assert_eq!(
+            get_predeployed_account_balance(&devnet).await,
+            PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string()
+        );
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 1);
+        devnet
+            .json_rpc_client
+            .get_transaction_by_hash(FieldElement::from_hex_be(&mint_hash).unwrap())
+            .await
+            .expect_err("Transaction should not exist after restart");
+    }
+
+    #[tokio::test]
+    async fn subscription_closed_on_restart() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (mut ws, _) = connect_async(devnet.ws_url()).await.unwrap();
+
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_subscribeNewHeads" });
+        ws.send(Message::Text(request.to_string())).await.unwrap();
+        let _subscription_resp = ws.next().await.unwrap().unwrap();
+
+        restart(&devnet).await;
+
+        let notification: serde_json::Value = loop {
+            match ws.next().await.unwrap().unwrap() {
+                Message::Text(text) => break serde_json::from_str(&text).unwrap(),
+                _ => continue,
+            }
+        };
+        assert_eq!(notification["method"], "starknet_subscriptionClosed");
+    }
+}
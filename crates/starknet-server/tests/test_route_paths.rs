@@ -0,0 +1,55 @@
+pub mod common;
+
+mod route_paths_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_providers::Provider;
+
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    fn chain_id_request() -> Body {
+        Body::from(json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_chainId" }).to_string())
+    }
+
+    #[tokio::test]
+    async fn handlers_served_only_at_custom_paths() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--rpc-path",
+            "/rpc/v0_6",
+            "--devnet-path",
+            "/devnet",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        // the client of the background instance is pointed at the custom path
+        let chain_id = devnet.json_rpc_client.chain_id().await.unwrap();
+        assert_ne!(chain_id, FieldElement::ZERO);
+
+        let resp = devnet.post_json("/rpc/v0_6".into(), chain_id_request()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get_json_body(resp).await["result"], format!("{chain_id:#x}"));
+
+        for old_path in ["/", "/rpc"] {
+            let resp = devnet.post_json(old_path.into(), chain_id_request()).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::NOT_FOUND, "Checking {old_path}");
+        }
+
+        assert_eq!(devnet.get("/config").await.unwrap().status(), StatusCode::NOT_FOUND);
+        let resp = devnet.get("/devnet/config").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let config = get_json_body(resp).await;
+        assert_eq!(config["rpc_path"], "/rpc/v0_6");
+        assert_eq!(config["devnet_path"], "/devnet");
+    }
+
+    #[tokio::test]
+    async fn default_paths_reported() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let config = get_json_body(devnet.get("/config").await.unwrap()).await;
+        assert_eq!(config["rpc_path"], "/rpc");
+        assert_eq!(config["devnet_path"], "");
+    }
+}
@@ -0,0 +1,58 @@
+pub mod common;
+
+mod sequencer_tests {
+    use hyper::Body;
+    use serde_json::json;
+    use starknet_core::constants::DEVNET_DEFAULT_SEQUENCER_ADDRESS;
+    use starknet_rs_core::types::FieldElement;
+
+    use crate::common::constants::{DUMMY_AMOUNT, PREDEPLOYED_ACCOUNT_ADDRESS};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static SEQUENCER_ADDRESS: &str = "0x5e9";
+
+    async fn get_latest_block_sequencer(devnet: &BackgroundDevnet) -> FieldElement {
+        let req_body = Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "starknet_getBlockWithTxHashes",
+                "params": { "block_id": "latest" }
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/rpc".into(), req_body).await.unwrap();
+        let block = get_json_body(resp).await;
+        FieldElement::from_hex_be(block["result"]["sequencer_address"].as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn blocks_sequenced_by_default_sequencer() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        devnet.mint(PREDEPLOYED_ACCOUNT_ADDRESS, DUMMY_AMOUNT).await;
+
+        assert_eq!(
+            get_latest_block_sequencer(&devnet).await,
+            FieldElement::from_hex_be(DEVNET_DEFAULT_SEQUENCER_ADDRESS).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn configured_sequencer_in_block_headers_and_execution_context() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--sequencer-address",
+            SEQUENCER_ADDRESS,
+        ])
+        .await
+        .expect("Could not start Devnet");
+        devnet.mint(PREDEPLOYED_ACCOUNT_ADDRESS, DUMMY_AMOUNT).await;
+
+        assert_eq!(
+            get_latest_block_sequencer(&devnet).await,
+            FieldElement::from_hex_be(SEQUENCER_ADDRESS).unwrap()
+        );
+        // the fee token transfers the fee to the sequencer it gets from the execution context
+        assert_ne!(devnet.get_balance(SEQUENCER_ADDRESS).await, FieldElement::ZERO);
+        assert_eq!(devnet.get_balance(DEVNET_DEFAULT_SEQUENCER_ADDRESS).await, FieldElement::ZERO);
+    }
+}
@@ -0,0 +1,93 @@
+pub mod common;
+
+mod set_storage_at_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+    use starknet_rs_core::utils::{get_selector_from_name, get_storage_var_address};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::constants::DUMMY_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    async fn set_storage_at(
+        devnet: &BackgroundDevnet,
+        contract_address: &str,
+        key: FieldElement,
+        value: &str,
+    ) -> hyper::Response<Body> {
+        let req_body = Body::from(
+            json!({
+                "contract_address": contract_address,
+                "key": format!("{key:#x}"),
+                "value": value
+            })
+            .to_string(),
+        );
+        devnet.post_json("/set_storage_at".into(), req_body).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn written_balance_readable_immediately() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--allow-dev-state"])
+            .await
+            .expect("Could not start Devnet");
+
+        let balance_key = get_storage_var_address(
+            "ERC20_balances",
+            &[FieldElement::from_hex_be(DUMMY_ADDRESS).unwrap()],
+        )
+        .unwrap();
+        let resp = set_storage_at(&devnet, ERC20_CONTRACT_ADDRESS, balance_key, "0x123").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(get_json_body(resp).await["block_hash"].is_string());
+
+        let erc20_address = FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap();
+        let value = devnet
+            .json_rpc_client
+            .get_storage_at(erc20_address, balance_key, BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap();
+        assert_eq!(value, FieldElement::from(0x123_u32));
+
+        // contracts see the written value as well
+        let balance = devnet
+            .json_rpc_client
+            .call(
+                FunctionCall {
+                    contract_address: erc20_address,
+                    entry_point_selector: get_selector_from_name("balanceOf").unwrap(),
+                    calldata: vec![FieldElement::from_hex_be(DUMMY_ADDRESS).unwrap()],
+                },
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap();
+        assert_eq!(balance, vec![FieldElement::from(0x123_u32), FieldElement::ZERO]);
+    }
+
+    #[tokio::test]
+    async fn writing_to_undeployed_contract_rejected_if_configured() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--allow-dev-state",
+            "--undeployed-storage-write",
+            "reject",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let resp = set_storage_at(&devnet, DUMMY_ADDRESS, FieldElement::ONE, "0x1").await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let error = get_json_body(resp).await["error"].as_str().unwrap().to_string();
+        assert!(error.contains("No contract is deployed"), "Unexpected error: {error}");
+    }
+
+    #[tokio::test]
+    async fn setting_storage_disabled_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = set_storage_at(&devnet, ERC20_CONTRACT_ADDRESS, FieldElement::ONE, "0x1").await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,50 @@
+pub mod common;
+
+#[cfg(unix)]
+mod shutdown_tests {
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::util::BackgroundDevnet;
+
+    /// Starts Devnet generating blocks on demand and dumping on exit to `path`, mints without
+    /// generating a block, terminates Devnet with SIGTERM and returns the written dump
+    async fn dump_after_sigterm(path: &str, additional_args: &[&str]) -> serde_json::Value {
+        // a dump of a previous run must not be mistaken for the new one
+        std::fs::remove_file(path).ok();
+
+        let args = [
+            &["--block-generation-on", "demand", "--dump-on", "exit", "--dump-path", path][..],
+            additional_args,
+        ]
+        .concat();
+        let mut devnet = BackgroundDevnet::spawn_with_additional_args(&args)
+            .await
+            .expect("Could not start Devnet");
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let exit_status = devnet.terminate();
+        assert!(exit_status.success(), "Devnet exited with {exit_status}");
+
+        let dump_str = std::fs::read_to_string(path).expect("Dump not written on SIGTERM");
+        std::fs::remove_file(path).unwrap();
+        serde_json::from_str(&dump_str).unwrap()
+    }
+
+    #[tokio::test]
+    async fn sigterm_dumps_state_before_exit() {
+        let path = std::env::temp_dir().join("devnet_sigterm_dumps_state_before_exit.json");
+        let dump = dump_after_sigterm(path.to_str().unwrap(), &[]).await;
+
+        // the pending transaction is not committed
+        assert_eq!(dump["blocks"].as_array().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn sigterm_commits_pending_transactions_if_configured() {
+        let path = std::env::temp_dir().join("devnet_sigterm_commits_pending_transactions.json");
+        let dump = dump_after_sigterm(path.to_str().unwrap(), &["--commit-on-shutdown"]).await;
+
+        let blocks = dump["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0]["transactions"][0]["type"], "Invoke");
+    }
+}
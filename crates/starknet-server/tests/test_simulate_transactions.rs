@@ -0,0 +1,179 @@
+pub mod common;
+
+mod simulate_transactions_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::{CAIRO_0_ACCOUNT_CONTRACT_HASH, ERC20_CONTRACT_ADDRESS};
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_providers::Provider;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static RECIPIENT_ADDRESS: &str = "0x42";
+
+    /// Unsigned ERC20 transfer from the predeployed account
+    fn transfer(nonce: u128, amount: u128) -> serde_json::Value {
+        let transfer_selector = get_selector_from_name("transfer").unwrap();
+        json!({
+            "type": "INVOKE",
+            "version": "0x1",
+            "max_fee": "0x0",
+            "signature": [],
+            "nonce": format!("{nonce:#x}"),
+            "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+            "calldata": [
+                "0x1",
+                ERC20_CONTRACT_ADDRESS,
+                format!("{transfer_selector:#x}"),
+                "0x0",
+                "0x3",
+                "0x3",
+                RECIPIENT_ADDRESS,
+                format!("{amount:#x}"),
+                "0x0"
+            ]
+        })
+    }
+
+    /// Unsigned query of the ERC20 balance of `sender`, sent by `sender` itself
+    fn balance_query(sender: &str) -> serde_json::Value {
+        let balance_of_selector = get_selector_from_name("balanceOf").unwrap();
+        json!({
+            "type": "INVOKE",
+            "version": "0x1",
+            "max_fee": "0x0",
+            "signature": [],
+            "nonce": "0x0",
+            "sender_address": sender,
+            "calldata": [
+                "0x1",
+                ERC20_CONTRACT_ADDRESS,
+                format!("{balance_of_selector:#x}"),
+                "0x0",
+                "0x1",
+                "0x1",
+                sender
+            ]
+        })
+    }
+
+    async fn simulate_transactions(
+        devnet: &BackgroundDevnet,
+        block_id: serde_json::Value,
+        transactions: Vec<serde_json::Value>,
+        simulation_flags: Vec<&str>,
+    ) -> serde_json::Value {
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_simulateTransactions",
+            "params": {
+                "block_id": block_id,
+                "transactions": transactions,
+                "simulation_flags": simulation_flags
+            }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    fn to_felt(value: &serde_json::Value) -> FieldElement {
+        FieldElement::from_hex_be(value.as_str().unwrap()).unwrap()
+    }
+
+    /// Number of nested levels of calls of `invocation`, including itself
+    fn depth(invocation: &serde_json::Value) -> usize {
+        1 + invocation["calls"].as_array().unwrap().iter().map(depth).max().unwrap_or(0)
+    }
+
+    #[tokio::test]
+    async fn trace_contains_nested_calls() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp_body = simulate_transactions(
+            &devnet,
+            json!("latest"),
+            vec![transfer(0, 1)],
+            vec!["SKIP_VALIDATE", "SKIP_FEE_CHARGE"],
+        )
+        .await;
+        let simulations = resp_body["result"].as_array().expect("Expected an array of simulations");
+        assert_eq!(simulations.len(), 1);
+
+        let trace = &simulations[0]["transaction_trace"];
+        assert!(trace.get("validate_invocation").is_none());
+        assert!(trace.get("fee_transfer_invocation").is_none());
+
+        // account's __execute__ -> ERC20 transfer
+        let execute_invocation = &trace["execute_invocation"];
+        assert_eq!(depth(execute_invocation), 2);
+        let transfer_invocation = &execute_invocation["calls"][0];
+        assert_eq!(
+            to_felt(&transfer_invocation["entry_point_selector"]),
+            get_selector_from_name("transfer").unwrap()
+        );
+        assert_eq!(
+            to_felt(&transfer_invocation["contract_address"]),
+            FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap()
+        );
+
+        assert!(simulations[0]["fee_estimation"]["overall_fee"].is_string());
+    }
+
+    #[tokio::test]
+    async fn simulation_does_not_change_state() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        // the same nonce can be simulated repeatedly
+        for _ in 0..2 {
+            let resp_body = simulate_transactions(
+                &devnet,
+                json!("latest"),
+                vec![transfer(0, 1)],
+                vec!["SKIP_VALIDATE"],
+            )
+            .await;
+            assert!(resp_body["result"].is_array(), "Expected a result, got {resp_body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn sender_not_found_at_block_preceding_its_deployment() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--allow-dev-deploy"])
+            .await
+            .expect("Could not start Devnet");
+
+        let deploy_body = json!({
+            "class_hash": CAIRO_0_ACCOUNT_CONTRACT_HASH,
+            "constructor_calldata": ["0x123"] // public key
+        });
+        let resp =
+            devnet.post_json("/deploy".into(), Body::from(deploy_body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let account_address =
+            get_json_body(resp).await["contract_address"].as_str().unwrap().to_string();
+        let deployment_block_number = devnet.json_rpc_client.block_number().await.unwrap();
+
+        let resp_body = simulate_transactions(
+            &devnet,
+            json!({ "block_number": deployment_block_number - 1 }),
+            vec![balance_query(&account_address)],
+            vec!["SKIP_VALIDATE", "SKIP_FEE_CHARGE"],
+        )
+        .await;
+        assert_eq!(resp_body["error"]["code"], 20, "Unexpected response: {resp_body}");
+        assert_eq!(resp_body["error"]["message"], "Contract not found");
+
+        let resp_body = simulate_transactions(
+            &devnet,
+            json!({ "block_number": deployment_block_number }),
+            vec![balance_query(&account_address)],
+            vec!["SKIP_VALIDATE", "SKIP_FEE_CHARGE"],
+        )
+        .await;
+        let simulations = resp_body["result"].as_array().expect("Expected an array of simulations");
+        assert!(simulations[0]["transaction_trace"].get("execute_invocation").is_some());
+    }
+}
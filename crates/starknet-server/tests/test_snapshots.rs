@@ -0,0 +1,46 @@
+pub mod common;
+
+mod snapshots_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+
+    use crate::common::constants::DUMMY_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    async fn take_snapshot(devnet: &BackgroundDevnet) -> serde_json::Value {
+        let resp = devnet.post_json("/snapshot".into(), Body::from("")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        get_json_body(resp).await["snapshot_id"].take()
+    }
+
+    async fn revert(devnet: &BackgroundDevnet, snapshot_id: &serde_json::Value) -> StatusCode {
+        let req_body = Body::from(json!({ "snapshot_id": snapshot_id }).to_string());
+        devnet.post_json("/revert".into(), req_body).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn revert_restores_balance_at_snapshot() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        assert_eq!(devnet.mint(DUMMY_ADDRESS, 10).await["new_balance"], json!("10"));
+        let snapshot_id = take_snapshot(&devnet).await;
+        assert_eq!(devnet.mint(DUMMY_ADDRESS, 32).await["new_balance"], json!("42"));
+
+        assert_eq!(revert(&devnet, &snapshot_id).await, StatusCode::OK);
+        assert_eq!(devnet.mint(DUMMY_ADDRESS, 1).await["new_balance"], json!("11"));
+    }
+
+    #[tokio::test]
+    async fn snapshots_taken_after_reverted_one_are_invalidated() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let first_snapshot_id = take_snapshot(&devnet).await;
+        devnet.mint(DUMMY_ADDRESS, 10).await;
+        let second_snapshot_id = take_snapshot(&devnet).await;
+        assert_ne!(first_snapshot_id, second_snapshot_id);
+
+        assert_eq!(revert(&devnet, &first_snapshot_id).await, StatusCode::OK);
+        assert_eq!(revert(&devnet, &second_snapshot_id).await, StatusCode::BAD_REQUEST);
+        assert_eq!(revert(&devnet, &json!("0x1234")).await, StatusCode::BAD_REQUEST);
+    }
+}
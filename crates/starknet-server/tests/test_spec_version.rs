@@ -0,0 +1,21 @@
+pub mod common;
+
+mod spec_version_tests {
+    use hyper::Body;
+    use serde_json::json;
+    use starknet_core::constants::RPC_SPEC_VERSION;
+
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    #[tokio::test]
+    async fn supported_spec_version_returned() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let req_body = Body::from(
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_specVersion" }).to_string(),
+        );
+        let resp = devnet.post_json("/rpc".into(), req_body).await.unwrap();
+
+        assert_eq!(get_json_body(resp).await["result"], json!(RPC_SPEC_VERSION));
+    }
+}
@@ -0,0 +1,338 @@
+pub mod common;
+
+mod subscriptions_tests {
+    use futures::{SinkExt, StreamExt};
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::{DEVNET_DEFAULT_CHAIN_ID, ERC20_CONTRACT_ADDRESS};
+    use starknet_core::raw_execution::{Call, RawExecution};
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::get_selector_from_name;
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::tungstenite::Message;
+    use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT, PREDEPLOYED_ACCOUNT_ADDRESS};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    async fn send_and_receive(ws: &mut WsStream, request: serde_json::Value) -> serde_json::Value {
+        ws.send(Message::Text(request.to_string())).await.unwrap();
+        receive(ws).await
+    }
+
+    async fn receive(ws: &mut WsStream) -> serde_json::Value {
+        loop {
+            match ws.next().await.unwrap().unwrap() {
+                Message::Text(text) => return serde_json::from_str(&text).unwrap(),
+                _ => continue,
+            }
+        }
+    }
+
+    /// ERC20 transfer from the predeployed account, which has to be impersonated, since the
+    /// transaction is not signed
+    fn transfer_execution() -> RawExecution {
+        RawExecution {
+            calls: vec![Call {
+                to: FieldElement::from_hex_be(ERC20_CONTRACT_ADDRESS).unwrap(),
+                selector: get_selector_from_name("transfer").unwrap(),
+                calldata: vec![FieldElement::from(0x42_u32), FieldElement::ONE, FieldElement::ZERO],
+            }],
+            nonce: FieldElement::ZERO,
+            max_fee: FieldElement::from(1_000_000_000_000_000_000_u128),
+        }
+    }
+
+    /// Returns the hash of the transfer that `send_transfer` submits
+    fn transfer_hash(raw_execution: &RawExecution) -> FieldElement {
+        raw_execution.transaction_hash(
+            DEVNET_DEFAULT_CHAIN_ID.to_felt().into(),
+            FieldElement::from_hex_be(PREDEPLOYED_ACCOUNT_ADDRESS).unwrap(),
+        )
+    }
+
+    async fn send_transfer(devnet: &BackgroundDevnet, raw_execution: &RawExecution) {
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_addInvokeTransaction",
+            "params": {
+                "invoke_transaction": {
+                    "type": "INVOKE",
+                    "version": "0x1",
+                    "max_fee": format!("{:#x}", raw_execution.max_fee),
+                    "signature": [],
+                    "nonce": format!("{:#x}", raw_execution.nonce),
+                    "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                    "calldata": raw_execution
+                        .raw_calldata()
+                        .iter()
+                        .map(|felt| format!("{felt:#x}"))
+                        .collect::<Vec<String>>(),
+                }
+            }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        let resp_body = get_json_body(resp).await;
+        assert_eq!(
+            FieldElement::from_hex_be(resp_body["result"]["transaction_hash"].as_str().unwrap())
+                .unwrap(),
+            transfer_hash(raw_execution)
+        );
+    }
+
+    async fn subscribe_to_transaction_status(
+        ws: &mut WsStream,
+        transaction_hash: FieldElement,
+    ) -> serde_json::Value {
+        let subscription_resp = send_and_receive(
+            ws,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "starknet_subscribeTransactionStatus",
+                "params": { "transaction_hash": format!("{transaction_hash:#x}") }
+            }),
+        )
+        .await;
+        let subscription_id = subscription_resp["result"].clone();
+        assert!(subscription_id.is_u64(), "Unexpected response: {subscription_resp}");
+        subscription_id
+    }
+
+    #[tokio::test]
+    async fn new_heads_notification_received_after_mint() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (mut ws, _) = connect_async(devnet.ws_url()).await.unwrap();
+
+        let subscription_resp = send_and_receive(
+            &mut ws,
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_subscribeNewHeads" }),
+        )
+        .await;
+        let subscription_id = subscription_resp["result"].clone();
+        assert!(subscription_id.is_u64(), "Unexpected response: {subscription_resp}");
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let notification = receive(&mut ws).await;
+        assert_eq!(notification["method"], "starknet_subscriptionNewHeads");
+        assert_eq!(notification["params"]["subscription_id"], subscription_id);
+
+        let header = &notification["params"]["result"];
+        assert_eq!(header["block_number"], 1);
+        for field in ["block_hash", "parent_hash", "timestamp", "sequencer_address"] {
+            assert!(!header[field].is_null(), "Missing {field} in {header}");
+        }
+    }
+
+    #[tokio::test]
+    async fn no_notifications_after_unsubscribing() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (mut ws, _) = connect_async(devnet.ws_url()).await.unwrap();
+
+        let subscription_resp = send_and_receive(
+            &mut ws,
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_subscribeNewHeads" }),
+        )
+        .await;
+        let subscription_id = subscription_resp["result"].clone();
+
+        let unsubscription_resp = send_and_receive(
+            &mut ws,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "starknet_unsubscribe",
+                "params": { "subscription_id": subscription_id }
+            }),
+        )
+        .await;
+        assert_eq!(unsubscription_resp["result"], true);
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        // the next message is the response to a regular call, not a notification
+        let block_number_resp = send_and_receive(
+            &mut ws,
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "starknet_blockNumber" }),
+        )
+        .await;
+        assert_eq!(block_number_resp["id"], 3);
+    }
+
+    #[tokio::test]
+    async fn transaction_status_notified_until_final() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--auto-impersonate",
+            "--block-generation-on",
+            "demand",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        let (mut ws, _) = connect_async(devnet.ws_url()).await.unwrap();
+
+        let raw_execution = transfer_execution();
+        let transaction_hash = transfer_hash(&raw_execution);
+        // the transaction is not known yet when subscribing
+        let subscription_id = subscribe_to_transaction_status(&mut ws, transaction_hash).await;
+
+        send_transfer(&devnet, &raw_execution).await;
+        let notification = receive(&mut ws).await;
+        assert_eq!(notification["method"], "starknet_subscriptionTransactionStatus");
+        assert_eq!(notification["params"]["subscription_id"], subscription_id);
+        let update = &notification["params"]["result"];
+        assert_eq!(
+            FieldElement::from_hex_be(update["transaction_hash"].as_str().unwrap()).unwrap(),
+            transaction_hash
+        );
+        // the transaction is already executed while it waits in the pending block
+        assert_eq!(
+            update["status"],
+            json!({ "finality_status": "RECEIVED", "execution_status": "SUCCEEDED" })
+        );
+
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let notification = receive(&mut ws).await;
+        assert_eq!(
+            notification["params"]["result"]["status"],
+            json!({ "finality_status": "ACCEPTED_ON_L2", "execution_status": "SUCCEEDED" })
+        );
+
+        let notification = receive(&mut ws).await;
+        assert_eq!(notification["method"], "starknet_subscriptionClosed");
+        assert_eq!(notification["params"]["subscription_id"], subscription_id);
+
+        // a subscription to a final transaction gets its status once
+        let subscription_id = subscribe_to_transaction_status(&mut ws, transaction_hash).await;
+        let notification = receive(&mut ws).await;
+        assert_eq!(notification["params"]["subscription_id"], subscription_id);
+        assert_eq!(notification["params"]["result"]["status"]["finality_status"], "ACCEPTED_ON_L2");
+        let notification = receive(&mut ws).await;
+        assert_eq!(notification["method"], "starknet_subscriptionClosed");
+    }
+
+    #[tokio::test]
+    async fn subscription_to_unknown_transaction_closed_after_ttl() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--transaction-subscription-ttl", "1"])
+                .await
+                .expect("Could not start Devnet");
+        let (mut ws, _) = connect_async(devnet.ws_url()).await.unwrap();
+
+        let subscription_id =
+            subscribe_to_transaction_status(&mut ws, FieldElement::from(0x123_u32)).await;
+
+        let notification = receive(&mut ws).await;
+        assert_eq!(notification["method"], "starknet_subscriptionClosed");
+        assert_eq!(notification["params"]["subscription_id"], subscription_id);
+        assert_eq!(
+            notification["params"]["result"]["reason"],
+            "Transaction not received within 1s"
+        );
+    }
+
+    #[tokio::test]
+    async fn pending_transaction_notified_before_new_heads() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--auto-impersonate"])
+            .await
+            .expect("Could not start Devnet");
+        let (mut ws, _) = connect_async(devnet.ws_url()).await.unwrap();
+
+        let new_heads_resp = send_and_receive(
+            &mut ws,
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_subscribeNewHeads" }),
+        )
+        .await;
+        let transactions_resp = send_and_receive(
+            &mut ws,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "starknet_subscribePendingTransactions",
+                "params": {
+                    "transaction_details": true,
+                    "sender_address": [PREDEPLOYED_ACCOUNT_ADDRESS]
+                }
+            }),
+        )
+        .await;
+        // transactions of other senders are filtered out
+        let filtered_resp = send_and_receive(
+            &mut ws,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "starknet_subscribePendingTransactions",
+                "params": { "sender_address": ["0x1"] }
+            }),
+        )
+        .await;
+        assert!(filtered_resp["result"].is_u64(), "Unexpected response: {filtered_resp}");
+
+        let raw_execution = transfer_execution();
+        send_transfer(&devnet, &raw_execution).await;
+
+        let notification = receive(&mut ws).await;
+        assert_eq!(notification["method"], "starknet_subscriptionPendingTransactions");
+        assert_eq!(notification["params"]["subscription_id"], transactions_resp["result"]);
+        let transaction = &notification["params"]["result"];
+        assert_eq!(transaction["type"], "INVOKE");
+        assert_eq!(
+            FieldElement::from_hex_be(transaction["transaction_hash"].as_str().unwrap()).unwrap(),
+            transfer_hash(&raw_execution)
+        );
+        assert_eq!(
+            FieldElement::from_hex_be(transaction["sender_address"].as_str().unwrap()).unwrap(),
+            FieldElement::from_hex_be(PREDEPLOYED_ACCOUNT_ADDRESS).unwrap()
+        );
+
+        let notification = receive(&mut ws).await;
+        assert_eq!(notification["method"], "starknet_subscriptionNewHeads");
+        assert_eq!(notification["params"]["subscription_id"], new_heads_resp["result"]);
+
+        // the next message is the response to a regular call, not a notification
+        let block_number_resp = send_and_receive(
+            &mut ws,
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "starknet_blockNumber" }),
+        )
+        .await;
+        assert_eq!(block_number_resp["id"], 4);
+    }
+
+    #[tokio::test]
+    async fn pending_transaction_hashes_notified_without_details() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (mut ws, _) = connect_async(devnet.ws_url()).await.unwrap();
+
+        let subscription_resp = send_and_receive(
+            &mut ws,
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "starknet_subscribePendingTransactions" }),
+        )
+        .await;
+        assert!(subscription_resp["result"].is_u64(), "Unexpected response: {subscription_resp}");
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let notification = receive(&mut ws).await;
+        assert_eq!(notification["method"], "starknet_subscriptionPendingTransactions");
+        assert_eq!(notification["params"]["subscription_id"], subscription_resp["result"]);
+        let transaction_hash = notification["params"]["result"].as_str().unwrap();
+
+        let req_body = Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "starknet_getBlockWithTxHashes",
+                "params": { "block_id": "latest" }
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/rpc".into(), req_body).await.unwrap();
+        let block = get_json_body(resp).await;
+        assert_eq!(block["result"]["transactions"], json!([transaction_hash]));
+    }
+}
@@ -0,0 +1,138 @@
+pub mod common;
+
+mod time_tests {
+    use std::time::SystemTime;
+
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    const DUMMY_TIMESTAMP: u64 = 1_000_000;
+
+    fn now() -> u64 {
+        SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    async fn post_time(
+        devnet: &BackgroundDevnet,
+        path: &str,
+        body: serde_json::Value,
+    ) -> serde_json::Value {
+        let resp = devnet.post_json(path.into(), Body::from(body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+        get_json_body(resp).await
+    }
+
+    async fn get_block_timestamp(devnet: &BackgroundDevnet, block_id: serde_json::Value) -> u64 {
+        let req_body = Body::from(
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "starknet_getBlockWithTxHashes",
+                "params": { "block_id": block_id }
+            })
+            .to_string(),
+        );
+        let resp = devnet.post_json("/rpc".into(), req_body).await.unwrap();
+        get_json_body(resp).await["result"]["timestamp"].as_u64().unwrap()
+    }
+
+    #[tokio::test]
+    async fn set_time_with_block_generation() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp_body = post_time(
+            &devnet,
+            "/set_time",
+            json!({ "time": DUMMY_TIMESTAMP, "generate_block": true }),
+        )
+        .await;
+        let block_hash = resp_body["block_hash"].as_str().unwrap();
+
+        let timestamp = get_block_timestamp(&devnet, json!({ "block_hash": block_hash })).await;
+        assert_eq!(timestamp, DUMMY_TIMESTAMP);
+
+        // subsequent blocks advance from the adjusted time
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let timestamp = get_block_timestamp(&devnet, json!("latest")).await;
+        assert!(timestamp >= DUMMY_TIMESTAMP);
+        assert!(timestamp < now());
+    }
+
+    #[tokio::test]
+    async fn set_time_without_block_generation() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp_body = post_time(&devnet, "/set_time", json!({ "time": DUMMY_TIMESTAMP })).await;
+        assert_eq!(resp_body, json!({ "block_hash": null }));
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let timestamp = get_block_timestamp(&devnet, json!("latest")).await;
+        assert_eq!(timestamp, DUMMY_TIMESTAMP);
+    }
+
+    #[tokio::test]
+    async fn increase_time_shifts_subsequent_blocks() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let time_shift = 1_000_000;
+        let timestamp_before = now();
+        let resp_body = post_time(
+            &devnet,
+            "/increase_time",
+            json!({ "time": time_shift, "generate_block": true }),
+        )
+        .await;
+        let block_hash = resp_body["block_hash"].as_str().unwrap();
+
+        let timestamp = get_block_timestamp(&devnet, json!({ "block_hash": block_hash })).await;
+        assert!(timestamp >= timestamp_before + time_shift);
+
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let timestamp = get_block_timestamp(&devnet, json!("latest")).await;
+        assert!(timestamp >= timestamp_before + time_shift);
+    }
+
+    #[tokio::test]
+    async fn time_out_of_range_rejected() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        for path in ["/set_time", "/increase_time"] {
+            let body = json!({ "time": u64::MAX, "generate_block": true });
+            let resp = devnet.post_json(path.into(), Body::from(body.to_string())).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "Checking status of {resp:?}");
+            let error = get_json_body(resp).await["error"].as_str().unwrap().to_string();
+            assert!(error.contains("out of range"), "{error}");
+        }
+    }
+
+    #[tokio::test]
+    async fn blocks_advance_from_initial_block_timestamp() {
+        let initial_timestamp = DUMMY_TIMESTAMP.to_string();
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--initial-block-timestamp",
+            initial_timestamp.as_str(),
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let timestamp = get_block_timestamp(&devnet, json!({ "block_number": 0 })).await;
+        assert_eq!(timestamp, DUMMY_TIMESTAMP);
+
+        // blocks after the genesis block advance from the initial timestamp
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let timestamp = get_block_timestamp(&devnet, json!({ "block_number": 1 })).await;
+        assert!(timestamp >= DUMMY_TIMESTAMP);
+        assert!(timestamp < now());
+
+        // shifting the time is relative to the initial timestamp, not to the system clock
+        let time_shift = 1_000;
+        post_time(&devnet, "/increase_time", json!({ "time": time_shift })).await;
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+        let timestamp = get_block_timestamp(&devnet, json!("latest")).await;
+        assert!(timestamp >= DUMMY_TIMESTAMP + time_shift);
+        assert!(timestamp < now());
+    }
+}
@@ -0,0 +1,125 @@
+pub mod common;
+
+mod trace_transaction_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    /// Returns the hash of the minting transaction
+    async fn mint(devnet: &BackgroundDevnet) -> String {
+        devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await["tx_hash"].as_str().unwrap().to_string()
+    }
+
+    async fn trace_transaction(
+        devnet: &BackgroundDevnet,
+        transaction_hash: &str,
+    ) -> serde_json::Value {
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_traceTransaction",
+            "params": { "transaction_hash": transaction_hash }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    /// Keys of the events emitted by `invocation` and by all the invocations it made
+    fn event_keys(invocation: &serde_json::Value) -> Vec<FieldElement> {
+        let mut keys: Vec<FieldElement> = invocation["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|event| event["keys"].as_array().unwrap().clone())
+            .map(|key| FieldElement::from_hex_be(key.as_str().unwrap()).unwrap())
+            .collect();
+        for call in invocation["calls"].as_array().unwrap() {
+            keys.extend(event_keys(call));
+        }
+
+        keys
+    }
+
+    #[tokio::test]
+    async fn trace_contains_emitted_event() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let transaction_hash = mint(&devnet).await;
+
+        let resp_body = trace_transaction(&devnet, &transaction_hash).await;
+        let execute_invocation = &resp_body["result"]["execute_invocation"];
+        assert!(execute_invocation.is_object(), "Expected a trace, got {resp_body}");
+        assert!(
+            event_keys(execute_invocation).contains(&get_selector_from_name("Transfer").unwrap())
+        );
+    }
+
+    async fn trace_block_transactions(
+        devnet: &BackgroundDevnet,
+        block_id: serde_json::Value,
+    ) -> serde_json::Value {
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_traceBlockTransactions",
+            "params": { "block_id": block_id }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    #[tokio::test]
+    async fn traces_of_all_block_transactions_returned_in_order() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+                .await
+                .expect("Could not start Devnet");
+        let transaction_hashes = vec![mint(&devnet).await, mint(&devnet).await];
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let resp_body = trace_block_transactions(&devnet, json!("latest")).await;
+        let traces = resp_body["result"].as_array().expect("Expected an array of traces");
+        assert_eq!(traces.len(), 2);
+        for (trace, transaction_hash) in traces.iter().zip(transaction_hashes) {
+            assert_eq!(
+                FieldElement::from_hex_be(trace["transaction_hash"].as_str().unwrap()).unwrap(),
+                FieldElement::from_hex_be(&transaction_hash).unwrap()
+            );
+            assert!(trace["trace_root"]["execute_invocation"].is_object());
+        }
+    }
+
+    #[tokio::test]
+    async fn traces_of_empty_block_are_empty() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let resp_body = trace_block_transactions(&devnet, json!("latest")).await;
+        assert_eq!(resp_body["result"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn trace_of_unknown_transaction_fails() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp_body = trace_transaction(&devnet, "0x1").await;
+        assert_eq!(resp_body["error"]["code"], 25);
+    }
+
+    #[tokio::test]
+    async fn trace_of_pending_transaction_fails() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--block-generation-on", "demand"])
+                .await
+                .expect("Could not start Devnet");
+        let transaction_hash = mint(&devnet).await;
+
+        let resp_body = trace_transaction(&devnet, &transaction_hash).await;
+        assert_eq!(resp_body["error"]["code"], 10);
+    }
+}
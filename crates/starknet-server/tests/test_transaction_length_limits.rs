@@ -0,0 +1,85 @@
+pub mod common;
+
+mod transaction_length_limits_tests {
+    use hyper::Body;
+    use serde_json::json;
+    use starknet_rs_providers::Provider;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static WILDCARD_RPC_ERROR_CODE: i64 = -1;
+
+    /// Sends an invoke transaction of the predeployed account with the given calldata and
+    /// signature lengths and returns the response body
+    async fn add_invoke_transaction(
+        devnet: &BackgroundDevnet,
+        calldata_len: usize,
+        signature_len: usize,
+    ) -> serde_json::Value {
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_addInvokeTransaction",
+            "params": {
+                "invoke_transaction": {
+                    "type": "INVOKE",
+                    "version": "0x1",
+                    "max_fee": "0xde0b6b3a7640000",
+                    "signature": vec!["0x1"; signature_len],
+                    "nonce": "0x0",
+                    "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                    "calldata": vec!["0x0"; calldata_len]
+                }
+            }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    #[tokio::test]
+    async fn oversized_calldata_rejected_before_execution() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--max-calldata-len", "10"])
+            .await
+            .expect("Could not start Devnet");
+
+        let resp_body = add_invoke_transaction(&devnet, 11, 0).await;
+        assert_eq!(resp_body["error"]["code"], WILDCARD_RPC_ERROR_CODE, "{resp_body}");
+        assert_eq!(
+            resp_body["error"]["message"],
+            "Transaction calldata has 11 elements, more than the allowed 10"
+        );
+        assert_eq!(
+            resp_body["error"]["data"],
+            json!({ "field": "calldata", "length": 11, "max_length": 10 })
+        );
+
+        // nothing was executed, so no block was generated
+        assert_eq!(devnet.json_rpc_client.block_number().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn calldata_within_limit_not_rejected_for_its_length() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--max-calldata-len", "10"])
+            .await
+            .expect("Could not start Devnet");
+
+        // the transaction may still fail once executed, but not for the length of its calldata
+        let resp_body = add_invoke_transaction(&devnet, 10, 0).await;
+        assert!(resp_body["error"]["data"]["field"].is_null(), "{resp_body}");
+    }
+
+    #[tokio::test]
+    async fn oversized_signature_rejected_before_execution() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--max-signature-len", "2"])
+            .await
+            .expect("Could not start Devnet");
+
+        let resp_body = add_invoke_transaction(&devnet, 0, 3).await;
+        assert_eq!(resp_body["error"]["code"], WILDCARD_RPC_ERROR_CODE, "{resp_body}");
+        assert_eq!(
+            resp_body["error"]["data"],
+            json!({ "field": "signature", "length": 3, "max_length": 2 })
+        );
+    }
+}
@@ -0,0 +1,104 @@
+pub mod common;
+
+mod transaction_nonce_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::{get_json_body, BackgroundDevnet};
+
+    static INVALID_TRANSACTION_NONCE_CODE: i64 = 52;
+
+    /// Sends a transfer of the predeployed account with `nonce`, without signing it, and returns
+    /// the JSON-RPC response
+    async fn send_transfer(devnet: &BackgroundDevnet, nonce: u32) -> serde_json::Value {
+        let transfer_selector = get_selector_from_name("transfer").unwrap();
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_addInvokeTransaction",
+            "params": {
+                "invoke_transaction": {
+                    "type": "INVOKE",
+                    "version": "0x1",
+                    "max_fee": "0xde0b6b3a7640000",
+                    "signature": [],
+                    "nonce": format!("{nonce:#x}"),
+                    "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+                    "calldata": [
+                        "0x1",
+                        ERC20_CONTRACT_ADDRESS,
+                        format!("{transfer_selector:#x}"),
+                        "0x0",
+                        "0x3",
+                        "0x3",
+                        "0x42",
+                        "0x1",
+                        "0x0"
+                    ]
+                }
+            }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        get_json_body(resp).await
+    }
+
+    fn transaction_hash_of(resp_body: &serde_json::Value) -> serde_json::Value {
+        let transaction_hash = resp_body["result"]["transaction_hash"].clone();
+        assert!(transaction_hash.is_string(), "Unexpected response: {resp_body}");
+        transaction_hash
+    }
+
+    fn assert_invalid_nonce(resp_body: &serde_json::Value) {
+        assert_eq!(resp_body["error"]["code"], INVALID_TRANSACTION_NONCE_CODE, "{resp_body}");
+        assert_eq!(resp_body["error"]["message"], "Invalid transaction nonce");
+    }
+
+    #[tokio::test]
+    async fn only_expected_nonce_accepted_if_blocks_generated_on_transaction() {
+        // impersonation allows sending the transactions without signing them
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--auto-impersonate"])
+            .await
+            .expect("Could not start Devnet");
+
+        transaction_hash_of(&send_transfer(&devnet, 0).await);
+        assert_invalid_nonce(&send_transfer(&devnet, 0).await);
+        assert_invalid_nonce(&send_transfer(&devnet, 2).await);
+        transaction_hash_of(&send_transfer(&devnet, 1).await);
+    }
+
+    #[tokio::test]
+    async fn transactions_after_nonce_gap_committed_in_nonce_order() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--auto-impersonate",
+            "--block-generation-on",
+            "demand",
+        ])
+        .await
+        .expect("Could not start Devnet");
+
+        let first_hash = transaction_hash_of(&send_transfer(&devnet, 0).await);
+        let third_hash = transaction_hash_of(&send_transfer(&devnet, 2).await);
+        let fourth_hash = transaction_hash_of(&send_transfer(&devnet, 3).await);
+        assert_invalid_nonce(&send_transfer(&devnet, 0).await);
+        let second_hash = transaction_hash_of(&send_transfer(&devnet, 1).await);
+
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req_body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "starknet_getBlockWithTxHashes",
+            "params": { "block_id": "latest" }
+        });
+        let resp = devnet.post_json("/rpc".into(), Body::from(req_body.to_string())).await.unwrap();
+        let block = get_json_body(resp).await;
+        assert_eq!(
+            block["result"]["transactions"],
+            json!([first_hash, second_hash, third_hash, fourth_hash])
+        );
+    }
+}
@@ -0,0 +1,83 @@
+pub mod common;
+
+mod transaction_status_tests {
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::BackgroundDevnet;
+
+    /// Sends an unsigned ETH transfer of `amount` from the predeployed account and returns the
+    /// status of the transaction
+    async fn transfer_status(devnet: &BackgroundDevnet, amount: &str) -> serde_json::Value {
+        let transfer_selector = get_selector_from_name("transfer").unwrap();
+        let invoke_transaction = json!({
+            "type": "INVOKE",
+            "version": "0x1",
+            "max_fee": "0x2386f26fc10000",
+            "signature": [],
+            "nonce": "0x0",
+            "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+            "calldata": [
+                "0x1",
+                ERC20_CONTRACT_ADDRESS,
+                format!("{transfer_selector:#x}"),
+                "0x0",
+                "0x3",
+                "0x3",
+                "0x42",
+                amount,
+                "0x0"
+            ]
+        });
+        let added = devnet
+            .rpc_call(
+                "starknet_addInvokeTransaction",
+                json!({ "invoke_transaction": invoke_transaction }),
+            )
+            .await;
+        let transaction_hash = added["result"]["transaction_hash"].clone();
+        assert!(transaction_hash.is_string(), "Unexpected response: {added}");
+
+        devnet
+            .rpc_call(
+                "starknet_getTransactionStatus",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await["result"]
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn invoke_failing_validation_is_rejected() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        // the predeployed account validates the missing signature
+        let status = transfer_status(&devnet, "0x2a").await;
+        assert_eq!(status, json!({ "finality_status": "REJECTED" }));
+    }
+
+    #[tokio::test]
+    async fn executed_invoke_is_accepted_on_l2() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--auto-impersonate"])
+            .await
+            .expect("Could not start Devnet");
+
+        let status = transfer_status(&devnet, "0x2a").await;
+        assert_eq!(
+            status,
+            json!({ "finality_status": "ACCEPTED_ON_L2", "execution_status": "SUCCEEDED" })
+        );
+    }
+
+    #[tokio::test]
+    async fn status_of_unknown_transaction_is_not_found() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let resp = devnet
+            .rpc_call("starknet_getTransactionStatus", json!({ "transaction_hash": "0x1" }))
+            .await;
+        assert_eq!(resp["error"]["code"], 25);
+    }
+}
@@ -0,0 +1,88 @@
+pub mod common;
+
+mod v3_transactions_tests {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+    use starknet_core::constants::ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_core::utils::get_selector_from_name;
+
+    use crate::common::constants::PREDEPLOYED_ACCOUNT_ADDRESS;
+    use crate::common::util::BackgroundDevnet;
+
+    static RECIPIENT_ADDRESS: &str = "0x42";
+
+    #[tokio::test]
+    async fn invoke_transaction_v3_pays_fee_in_fri() {
+        // the account is impersonated, so that the transaction needs no signature
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--auto-impersonate"])
+            .await
+            .expect("Could not start Devnet");
+
+        let mint_body = json!({
+            "address": PREDEPLOYED_ACCOUNT_ADDRESS,
+            "amount": 1_000_000_000_000_000_000u128,
+            "unit": "FRI"
+        });
+        let resp =
+            devnet.post_json("/mint".into(), Body::from(mint_body.to_string())).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "Checking status of {resp:?}");
+
+        let resource_bounds = json!({
+            "l1_gas": { "max_amount": "0x186a0", "max_price_per_unit": "0x174876e800" },
+            "l2_gas": { "max_amount": "0x0", "max_price_per_unit": "0x0" }
+        });
+        let transfer_selector = get_selector_from_name("transfer").unwrap();
+        let invoke_transaction = json!({
+            "type": "INVOKE",
+            "version": "0x3",
+            "signature": [],
+            "nonce": "0x0",
+            "sender_address": PREDEPLOYED_ACCOUNT_ADDRESS,
+            "calldata": [
+                "0x1",
+                ERC20_CONTRACT_ADDRESS,
+                format!("{transfer_selector:#x}"),
+                "0x0",
+                "0x3",
+                "0x3",
+                RECIPIENT_ADDRESS,
+                "0x2a",
+                "0x0"
+            ],
+            "resource_bounds": resource_bounds,
+            "tip": "0x0",
+            "paymaster_data": [],
+            "account_deployment_data": [],
+            "nonce_data_availability_mode": "L1",
+            "fee_data_availability_mode": "L1"
+        });
+
+        let added = devnet
+            .rpc_call(
+                "starknet_addInvokeTransaction",
+                json!({ "invoke_transaction": invoke_transaction }),
+            )
+            .await;
+        let transaction_hash = added["result"]["transaction_hash"].clone();
+        assert!(transaction_hash.is_string(), "Unexpected response: {added}");
+
+        let receipt = devnet
+            .rpc_call(
+                "starknet_getTransactionReceipt",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await["result"]
+            .clone();
+        assert_eq!(receipt["actual_fee"]["unit"], "FRI");
+
+        let transaction = devnet
+            .rpc_call(
+                "starknet_getTransactionByHash",
+                json!({ "transaction_hash": transaction_hash }),
+            )
+            .await["result"]
+            .clone();
+        assert_eq!(transaction["version"], "0x3");
+        assert_eq!(transaction["resource_bounds"], resource_bounds);
+    }
+}
@@ -19,16 +19,17 @@ use crate::traits::{Accounted, Deployed, StateChanger, StateExtractor};
 use crate::utils::get_storage_var_address;
 
 /// data taken from https://github.com/0xSpaceShard/starknet-devnet/blob/fb96e0cc3c1c31fb29892ecefd2a670cf8a32b51/starknet_devnet/account.py
-const ACCOUNT_CLASS_HASH_HEX_FOR_ADDRESS_COMPUTATION: &str =
+pub(crate) const ACCOUNT_CLASS_HASH_HEX_FOR_ADDRESS_COMPUTATION: &str =
     "0x3FCBF77B28C96F4F2FB5BD2D176AB083A12A5E123ADEB0DE955D7EE228C9854";
 
 #[derive(Clone)]
 pub struct Account {
     pub public_key: Key,
-    pub private_key: Key,
+    /// None for accounts deployed at given addresses, whose keys are held by the user
+    pub private_key: Option<Key>,
     pub account_address: ContractAddress,
     pub initial_balance: Balance,
-    pub(crate) class_hash: ClassHash,
+    pub class_hash: ClassHash,
     pub(crate) contract_class: ContractClass,
     pub(crate) fee_token_address: ContractAddress,
 }
@@ -44,7 +45,7 @@ impl Account {
         let initial_balance_hex = format!("0x{}", initial_balance.to_str_radix(16));
         Self {
             public_key: Key::from_prefixed_hex_str(CHARGEABLE_ACCOUNT_PUBLIC_KEY).unwrap(),
-            private_key: Key::from_prefixed_hex_str(CHARGEABLE_ACCOUNT_PRIVATE_KEY).unwrap(),
+            private_key: Some(Key::from_prefixed_hex_str(CHARGEABLE_ACCOUNT_PRIVATE_KEY).unwrap()),
             account_address: ContractAddress::new(
                 Felt::from_prefixed_hex_str(CHARGEABLE_ACCOUNT_ADDRESS).unwrap(),
             )
@@ -67,7 +68,7 @@ impl Account {
         Ok(Self {
             initial_balance,
             public_key,
-            private_key,
+            private_key: Some(private_key),
             class_hash,
             contract_class,
             account_address: Account::compute_account_address(&public_key)?,
@@ -75,6 +76,26 @@ impl Account {
         })
     }
 
+    /// Account deployed at the given address, whose private key is not known to Devnet
+    pub(crate) fn new_at_address(
+        initial_balance: Balance,
+        public_key: Key,
+        account_address: ContractAddress,
+        class_hash: ClassHash,
+        contract_class: ContractClass,
+        fee_token_address: ContractAddress,
+    ) -> Self {
+        Self {
+            initial_balance,
+            public_key,
+            private_key: None,
+            class_hash,
+            contract_class,
+            account_address,
+            fee_token_address,
+        }
+    }
+
     fn compute_account_address(public_key: &Key) -> Result<ContractAddress> {
         let account_address = calculate_contract_address(
             ContractAddressSalt(stark_felt!(20u32)),
@@ -87,10 +108,24 @@ impl Account {
         Ok(ContractAddress::from(account_address))
     }
 
-    fn balance_storage_key(&self) -> Result<ContractStorageKey> {
+    pub(crate) fn balance_storage_key(&self) -> Result<ContractStorageKey> {
+        self.balance_storage_key_in(self.fee_token_address)
+    }
+
+    fn balance_storage_key_in(&self, token_address: ContractAddress) -> Result<ContractStorageKey> {
         let storage_var_address =
             get_storage_var_address("ERC20_balances", &[Felt::from(self.account_address)])?;
-        Ok(ContractStorageKey::new(self.fee_token_address, storage_var_address))
+        Ok(ContractStorageKey::new(token_address, storage_var_address))
+    }
+
+    /// Sets the initial balance in the token at `token_address`, which may differ from the fee
+    /// token of the account
+    pub(crate) fn set_initial_balance_in(
+        &self,
+        state: &mut impl StateChanger,
+        token_address: ContractAddress,
+    ) -> Result<()> {
+        state.change_storage(self.balance_storage_key_in(token_address)?, self.initial_balance)
     }
 }
 
@@ -119,13 +154,7 @@ impl Deployed for Account {
 
 impl Accounted for Account {
     fn set_initial_balance(&self, state: &mut impl StateChanger) -> Result<()> {
-        let storage_var_address =
-            get_storage_var_address("ERC20_balances", &[Felt::from(self.account_address)])?;
-        let storage_key = ContractStorageKey::new(self.fee_token_address, storage_var_address);
-
-        state.change_storage(storage_key, self.initial_balance)?;
-
-        Ok(())
+        self.set_initial_balance_in(state, self.fee_token_address)
     }
 
     fn get_balance(&self, state: &mut impl StateExtractor) -> Result<Balance> {
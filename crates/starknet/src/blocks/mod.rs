@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 
-use starknet_api::block::{BlockHeader, BlockNumber, BlockStatus, BlockTimestamp};
+use starknet_api::block::{BlockHeader, BlockNumber, BlockStatus, BlockTimestamp, GasPrice};
 use starknet_api::hash::{pedersen_hash_array, StarkFelt};
 use starknet_api::stark_felt;
 use starknet_rs_core::types::BlockId;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::{BlockHash, Felt};
+use starknet_types::patricia_key::PatriciaKey;
 use starknet_types::traits::HashProducer;
+use tokio::sync::broadcast;
 
 use crate::state::state_diff::StateDiff;
 use crate::state::StarknetState;
@@ -68,6 +70,32 @@ impl StarknetBlocks {
         self.num_to_state.insert(block_number, state);
     }
 
+    /// Removes the block with the given number and all blocks after it, together with their state
+    /// diffs and states. Returns the removed blocks, ordered by block number.
+    pub fn remove_blocks_from(&mut self, block_number: BlockNumber) -> Vec<StarknetBlock> {
+        let mut removed_block_numbers: Vec<BlockNumber> =
+            self.num_to_block.keys().filter(|num| **num >= block_number).copied().collect();
+        removed_block_numbers.sort();
+
+        let mut removed_blocks = vec![];
+        for num in removed_block_numbers {
+            if let Some(block) = self.num_to_block.remove(&num) {
+                self.hash_to_num.remove(&block.block_hash());
+                removed_blocks.push(block);
+            }
+            self.num_to_state_diff.remove(&num);
+            self.num_to_state.remove(&num);
+        }
+
+        self.last_block_hash = block_number
+            .0
+            .checked_sub(1)
+            .and_then(|parent_number| self.num_to_block.get(&BlockNumber(parent_number)))
+            .map(|parent| parent.block_hash());
+
+        removed_blocks
+    }
+
     pub fn get_by_block_id(&self, block_id: BlockId) -> Option<&StarknetBlock> {
         match block_id {
             BlockId::Hash(hash) => self.get_by_hash(Felt::from(hash)),
@@ -84,10 +112,55 @@ impl StarknetBlocks {
     }
 }
 
+/// Number of blocks kept for subscribers that have not received them yet
+const NEW_BLOCKS_CHANNEL_CAPACITY: usize = 100;
+
+/// Notifies all subscribers about every newly created block
+pub(crate) struct NewBlocksNotifier {
+    sender: broadcast::Sender<StarknetBlock>,
+}
+
+impl Default for NewBlocksNotifier {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(NEW_BLOCKS_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+}
+
+impl NewBlocksNotifier {
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<StarknetBlock> {
+        self.sender.subscribe()
+    }
+
+    pub(crate) fn notify(&self, block: &StarknetBlock) {
+        // sending fails only if there are no subscribers, which is not an error
+        let _ = self.sender.send(block.clone());
+    }
+}
+
+/// Write to the storage of a contract made without a transaction
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct StorageWrite {
+    pub contract_address: ContractAddress,
+    pub key: PatriciaKey,
+    pub value: Felt,
+}
+
 #[derive(Clone, Eq, PartialEq)]
 pub struct StarknetBlock {
     pub(crate) header: BlockHeader,
+    /// The header only holds the price of L1 gas in wei
+    pub(crate) l1_data_gas_price: GasPrice,
+    /// Prices of L1 gas and L1 data gas in fri, converted from the prices in wei
+    pub(crate) l1_gas_price_in_fri: GasPrice,
+    pub(crate) l1_data_gas_price_in_fri: GasPrice,
+    /// Roots of the tries of the hashes of the block's transactions and events, set when the
+    /// block is generated
+    pub(crate) transaction_commitment: Felt,
+    pub(crate) event_commitment: Felt,
     pub(crate) transactions: Vec<Transaction>,
+    /// Storage write generating the block, made after its transactions
+    pub(crate) storage_write: Option<StorageWrite>,
     pub(crate) status: BlockStatus,
 }
 
@@ -100,6 +173,10 @@ impl StarknetBlock {
         &self.transactions
     }
 
+    pub fn storage_write(&self) -> Option<&StorageWrite> {
+        self.storage_write.as_ref()
+    }
+
     pub fn status(&self) -> &BlockStatus {
         &self.status
     }
@@ -120,10 +197,34 @@ impl StarknetBlock {
         self.header.timestamp
     }
 
+    pub fn l1_gas_price(&self) -> GasPrice {
+        self.header.gas_price
+    }
+
+    pub fn l1_data_gas_price(&self) -> GasPrice {
+        self.l1_data_gas_price
+    }
+
+    pub fn l1_gas_price_in_fri(&self) -> GasPrice {
+        self.l1_gas_price_in_fri
+    }
+
+    pub fn l1_data_gas_price_in_fri(&self) -> GasPrice {
+        self.l1_data_gas_price_in_fri
+    }
+
     pub fn new_root(&self) -> Felt {
         self.header.state_root.0.into()
     }
 
+    pub fn transaction_commitment(&self) -> Felt {
+        self.transaction_commitment
+    }
+
+    pub fn event_commitment(&self) -> Felt {
+        self.event_commitment
+    }
+
     pub(crate) fn set_block_hash(&mut self, block_hash: BlockHash) {
         self.header.block_hash = block_hash.into();
     }
@@ -135,7 +236,13 @@ impl StarknetBlock {
     pub(crate) fn create_pending_block() -> Self {
         Self {
             header: BlockHeader::default(),
+            l1_data_gas_price: GasPrice::default(),
+            l1_gas_price_in_fri: GasPrice::default(),
+            l1_data_gas_price_in_fri: GasPrice::default(),
+            transaction_commitment: Felt::default(),
+            event_commitment: Felt::default(),
             transactions: Vec::new(),
+            storage_write: None,
             status: BlockStatus::Pending,
         }
     }
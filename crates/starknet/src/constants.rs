@@ -1,5 +1,5 @@
-// use starknet_types::felt::Felt;
-use starknet_in_rust::definitions::block_context::StarknetChainId;
+use starknet_in_rust::definitions::constants::DEFAULT_INVOKE_TX_MAX_N_STEPS;
+use starknet_types::chain_id::ChainId;
 
 pub(crate) const CAIRO_0_ACCOUNT_CONTRACT_PATH: &str = concat!(
     env!("CARGO_MANIFEST_DIR"),
@@ -9,6 +9,11 @@ pub(crate) const CAIRO_0_ACCOUNT_CONTRACT_PATH: &str = concat!(
 pub const CAIRO_0_ACCOUNT_CONTRACT_HASH: &str =
     "0x4d07e40e93398ed3c76981e72dd1fd22557a78ce36c0515f679e27f0bb5bc5f";
 
+pub(crate) const CAIRO_1_ACCOUNT_CONTRACT_SIERRA_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/accounts_artifacts/OpenZeppelin/0.7.0/Account.cairo/Account.sierra"
+);
+
 pub(crate) const ERC20_CONTRACT_PATH: &str =
     concat!(env!("CARGO_MANIFEST_DIR"), "/accounts_artifacts/ERC20_Mintable_OZ_0.2.0.json");
 
@@ -18,26 +23,73 @@ pub(crate) const ERC20_CONTRACT_CLASS_HASH: &str =
 pub const ERC20_CONTRACT_ADDRESS: &str =
     "0x49D36570D4E46F48E99674BD3FCC84644DDD6B96F7C741B1562B82F9E004DC7";
 
+pub const STRK_ERC20_CONTRACT_ADDRESS: &str =
+    "0x4718F5A0FC34CC1AF16A1CDEE98FFB20C31F5CD61D6AB07201858F4287C938D";
+
+/// Metadata of the fee tokens on mainnet
+pub const ETH_ERC20_NAME: &str = "Ether";
+pub const ETH_ERC20_SYMBOL: &str = "ETH";
+pub const STRK_ERC20_NAME: &str = "Starknet Token";
+pub const STRK_ERC20_SYMBOL: &str = "STRK";
+pub const ERC20_DECIMALS: u8 = 18;
+
+/// Contract in whose storage the Starknet OS keeps the hashes of past blocks, by block number
+pub const BLOCK_HASH_CONTRACT_ADDRESS: &str = "0x1";
+
+/// Timestamp of the genesis block, unless an initial block timestamp is configured. Unlike later
+/// blocks, the genesis block doesn't take the time of the system clock, so that its hash can be
+/// reproduced
+pub const GENESIS_BLOCK_TIMESTAMP: u64 = 0;
+
+/// Number of most recent blocks whose hashes are not available to the `get_block_hash` syscall
+pub const STORED_BLOCK_HASH_BUFFER: u64 = 10;
+
 pub(crate) const UDC_CONTRACT_PATH: &str =
     concat!(env!("CARGO_MANIFEST_DIR"), "/accounts_artifacts/UDC_OZ_0.5.0.json");
 
 pub(crate) const UDC_CONTRACT_CLASS_HASH: &str =
     "0x7B3E05F48F0C69E4A65CE5E076A66271A527AFF2C34CE1083EC6E1526997A69";
 
-pub(crate) const UDC_CONTRACT_ADDRESS: &str =
+pub const UDC_CONTRACT_ADDRESS: &str =
     "0x41A78E741E5AF2FEC34B695679BC6891742439F7AFB8484ECD7766661AD02BF";
 
+/// Address of the sequencer of Devnet's blocks, to which fees are transferred
+pub const DEVNET_DEFAULT_SEQUENCER_ADDRESS: &str = "0x1000";
+
 pub const DEVNET_DEFAULT_TEST_SEED: u32 = 123;
 pub const DEVNET_DEFAULT_TOTAL_ACCOUNTS: u8 = 10;
 pub const DEVNET_DEFAULT_INITIAL_BALANCE: u128 = 1_000_000_000_000_000_000_000;
 pub const DEVNET_DEFAULT_GAS_PRICE: u64 = 100_000_000_000;
+pub const DEVNET_DEFAULT_DATA_GAS_PRICE: u64 = 100_000_000_000;
+/// Fri a wei is worth, so that gas costs the same amount in both fee tokens by default
+pub const DEVNET_DEFAULT_ETH_STRK_RATE: u64 = 1;
 pub const DEVNET_DEFAULT_HOST: &str = "127.0.0.1";
 pub const DEVNET_DEFAULT_PORT: u16 = 5050;
 pub const DEVNET_DEFAULT_TIMEOUT: u16 = 120;
-pub const DEVNET_DEFAULT_CHAIN_ID: StarknetChainId = StarknetChainId::TestNet;
+/// Path the JSON-RPC handler is mounted at
+pub const DEVNET_DEFAULT_RPC_PATH: &str = "/rpc";
+/// Seconds a subscription to the status of a transaction unknown to Devnet is kept open
+pub const DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL: u64 = 300;
+/// Max size of a request body in bytes, enough for declarations of large contracts
+pub const DEVNET_DEFAULT_REQUEST_BODY_LIMIT: usize = 100 * 1024 * 1024;
+pub const DEVNET_DEFAULT_CHAIN_ID: ChainId = ChainId::TESTNET;
+/// Max number of Cairo steps of a transaction or a call, same as on the Starknet networks
+pub const DEVNET_DEFAULT_MAX_STEPS: u64 = DEFAULT_INVOKE_TX_MAX_N_STEPS;
+/// Max number of elements of the calldata of a submitted transaction, well above what accounts
+/// send in practice
+pub const DEVNET_DEFAULT_MAX_CALLDATA_LEN: usize = 10_000;
+/// Max number of elements of the signature of a submitted transaction
+pub const DEVNET_DEFAULT_MAX_SIGNATURE_LEN: usize = 4_000;
 
 pub const SUPPORTED_TX_VERSION: u32 = 1;
 
+/// Version of the JSON-RPC specification implemented by Devnet
+pub const RPC_SPEC_VERSION: &str = "0.6.0";
+
+/// Versions of Sierra of the classes Devnet is tested with. Classes of other versions may be
+/// translated to CASM differently by the bundled compiler than by the compilers producing them.
+pub const SUPPORTED_SIERRA_VERSIONS: [&str; 2] = ["1.1.0", "1.2.0"];
+
 // chargeable account
 pub const CHARGEABLE_ACCOUNT_PUBLIC_KEY: &str =
     "0x4C37AB4F0994879337BFD4EAD0800776DB57DA382B8ED8EFAA478C5D3B942A4";
@@ -1,6 +1,11 @@
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{ClassHash, Felt, TransactionHash};
+use starknet_types::traits::ToHexString;
 use thiserror::Error;
 use {starknet_in_rust, starknet_types};
 
+use crate::constants::SUPPORTED_SIERRA_VERSIONS;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error(transparent)]
@@ -27,6 +32,90 @@ pub enum Error {
     NoBlock,
     #[error("No state at block {block_number}")]
     NoStateAtBlock { block_number: u64 },
+    #[error("Unsupported action: {msg}")]
+    UnsupportedAction { msg: String },
+    #[error("Dump error: {msg}")]
+    DumpError { msg: String },
+    #[error(
+        "Dump was produced by Devnet version {found}, which is incompatible with the current \
+         version {expected}"
+    )]
+    IncompatibleDumpVersion { found: String, expected: String },
+    #[error("State directory error: {msg}")]
+    StateDirError { msg: String },
+    #[error("Storage proofs are not available for the pending block")]
+    StorageProofForPendingBlock,
+    #[error("Storage proofs are not available in lite mode")]
+    StorageProofInLiteMode,
+    #[error("Invalid continuation token: {token}")]
+    InvalidContinuationToken { token: String },
+    #[error(
+        "Pending block is full: at most {max_pending_transactions} transactions can be pending \
+         until a block is created"
+    )]
+    PendingBlockFull { max_pending_transactions: u64 },
+    #[error("Transaction not found")]
+    TransactionNotFound,
+    #[error("Class hash not found")]
+    ClassHashNotFound,
+    #[error("Transactions of the pending block can't be traced")]
+    TraceForPendingTransaction,
+    #[error("Transaction at index {index} failed: {source}")]
+    TransactionFailed {
+        index: usize,
+        source: starknet_in_rust::transaction::error::TransactionError,
+    },
+    #[error("Message to L2 with nonce {nonce} has already been consumed")]
+    MessageToL2AlreadyConsumed { nonce: String },
+    #[error(
+        "Message to L2 with nonce {nonce} is out of order; the message with nonce \
+         {last_consumed_nonce} has already been consumed"
+    )]
+    MessageToL2OutOfOrder { nonce: String, last_consumed_nonce: String },
+    #[error("Insufficient resources: {msg}")]
+    InsufficientResources { msg: String },
+    #[error("Class with hash {} is already declared", .class_hash.to_prefixed_hex_str())]
+    ClassAlreadyDeclared { class_hash: ClassHash },
+    #[error(
+        "Compiled class hash {} doesn't match the hash {} of the compiled class",
+        .provided.to_prefixed_hex_str(),
+        .expected.to_prefixed_hex_str()
+    )]
+    CompiledClassHashMismatch { expected: ClassHash, provided: ClassHash },
+    #[error(
+        "Sierra version {version} is not supported; supported versions are {}",
+        SUPPORTED_SIERRA_VERSIONS.join(", ")
+    )]
+    UnsupportedSierraVersion { version: String },
+    #[error("Contract at address {} is already deployed", .contract_address.to_prefixed_hex_str())]
+    ContractAlreadyDeployed { contract_address: ContractAddress },
+    #[error(
+        "Invalid nonce {} of transaction sent by {}; expected nonce {}",
+        .provided.to_prefixed_hex_str(),
+        .address.to_prefixed_hex_str(),
+        .expected.to_prefixed_hex_str()
+    )]
+    InvalidTransactionNonce { address: ContractAddress, expected: Felt, provided: Felt },
+    #[error("Predeployed accounts error: {msg}")]
+    PredeployedAccountsError { msg: String },
+    #[error("No snapshot with id {snapshot_id}")]
+    NoSnapshot { snapshot_id: u64 },
+    #[error("Time {time} is out of range")]
+    TimeOutOfRange { time: u64 },
+    #[error("Fee token error: {msg}")]
+    FeeTokenError { msg: String },
+    #[error(
+        "Deployment transaction {} reverted: {revert_error}",
+        .transaction_hash.to_prefixed_hex_str()
+    )]
+    DeploymentReverted { transaction_hash: TransactionHash, revert_error: String },
+    #[error(
+        "Minting transaction {} reverted: {revert_error}",
+        .transaction_hash.to_prefixed_hex_str()
+    )]
+    MintingReverted { transaction_hash: TransactionHash, revert_error: String },
+    #[error(transparent)]
+    ForkError(#[from] crate::starknet::fork::ForkError),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
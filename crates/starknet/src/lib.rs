@@ -4,9 +4,11 @@ pub mod constants;
 pub mod error;
 mod predeployed_accounts;
 pub mod raw_execution;
+mod revert_reason;
 pub mod starknet;
 mod state;
 mod system_contract;
+pub mod traces;
 mod traits;
 pub mod transactions;
 mod utils;
@@ -1,11 +1,17 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde::Deserialize;
 use starknet_rs_ff::FieldElement;
 use starknet_rs_signers::SigningKey;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::ContractClass;
 use starknet_types::felt::{ClassHash, Felt, Key};
+use starknet_types::num_bigint::BigUint;
+use starknet_types::traits::ToHexString;
 
 use crate::account::Account;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::traits::AccountGenerator;
 use crate::utils::generate_u128_random_numbers;
 
@@ -48,6 +54,85 @@ impl PredeployedAccounts {
     pub fn get_accounts(&self) -> &Vec<Account> {
         &self.accounts
     }
+
+    /// Adds an account that was not generated, e.g. one listed in a file
+    pub(crate) fn add_account(&mut self, account: Account) {
+        self.accounts.push(account);
+    }
+}
+
+/// Entry of a file listing accounts to predeploy
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AccountEntry {
+    address: String,
+    public_key: String,
+    /// Decimal amount in the smallest unit of the token
+    balance: String,
+    class_hash: String,
+}
+
+impl AccountEntry {
+    fn into_account(
+        self,
+        account_classes: &HashMap<ClassHash, ContractClass>,
+        fee_token_address: ContractAddress,
+    ) -> Result<Account> {
+        let account_address = ContractAddress::new(parse_felt("address", &self.address)?)
+            .map_err(|_| invalid_entry(format!("Address {} is out of range", self.address)))?;
+        let public_key = parse_felt("public key", &self.public_key)?;
+        let balance = BigUint::from_str(&self.balance)
+            .ok()
+            .and_then(|balance| Felt::try_from(balance).ok())
+            .ok_or_else(|| invalid_entry(format!("Invalid balance {}", self.balance)))?;
+
+        let class_hash = parse_felt("class hash", &self.class_hash)?;
+        let contract_class = account_classes.get(&class_hash).cloned().ok_or_else(|| {
+            invalid_entry(format!(
+                "Class hash {} of account {} is not one of the predeployed account classes",
+                class_hash.to_prefixed_hex_str(),
+                account_address.to_prefixed_hex_str()
+            ))
+        })?;
+
+        Ok(Account::new_at_address(
+            balance,
+            public_key,
+            account_address,
+            class_hash,
+            contract_class,
+            fee_token_address,
+        ))
+    }
+}
+
+fn invalid_entry(msg: String) -> Error {
+    Error::PredeployedAccountsError { msg }
+}
+
+fn parse_felt(field: &str, value: &str) -> Result<Felt> {
+    Felt::from_prefixed_hex_str(value).map_err(|_| {
+        invalid_entry(format!("Invalid {field} {value}: expected a prefixed hex felt"))
+    })
+}
+
+/// Reads the accounts listed in the JSON file at `path`, an array of objects with the prefixed hex
+/// `address`, `public_key` and `class_hash`, and the decimal `balance`. The class hash must be one
+/// of `account_classes`. Addresses are not checked for collisions here.
+pub(crate) fn load_accounts_from_file(
+    path: &str,
+    account_classes: &HashMap<ClassHash, ContractClass>,
+    fee_token_address: ContractAddress,
+) -> Result<Vec<Account>> {
+    let json_str = std::fs::read_to_string(path)
+        .map_err(|err| Error::ReadFileError { source: err, path: path.to_string() })?;
+    let entries: Vec<AccountEntry> = serde_json::from_str(&json_str)
+        .map_err(|err| invalid_entry(format!("Invalid accounts file {path}: {err}")))?;
+
+    entries
+        .into_iter()
+        .map(|entry| entry.into_account(account_classes, fee_token_address))
+        .collect()
 }
 
 impl AccountGenerator for PredeployedAccounts {
@@ -185,7 +270,7 @@ mod tests {
                 json!({
                     "address": acc.account_address.to_prefixed_hex_str(),
                     "initial_balance": acc.initial_balance.to_decimal_string(),
-                    "private_key": acc.private_key.to_prefixed_hex_str(),
+                    "private_key": acc.private_key.unwrap().to_prefixed_hex_str(),
                     "public_key": acc.public_key.to_prefixed_hex_str()
                 })
             })
@@ -59,6 +59,20 @@ impl RawExecution {
         execute_calldata
     }
 
+    /// Calldata of `__execute__` of Cairo 1 accounts, which take the calls as an array of structs,
+    /// with the calldata of each call inlined
+    pub fn raw_calldata_cairo_1(&self) -> Vec<FieldElement> {
+        let mut execute_calldata: Vec<FieldElement> = vec![self.calls.len().into()];
+        for call in self.calls.iter() {
+            execute_calldata.push(call.to); // to
+            execute_calldata.push(call.selector); // selector
+            execute_calldata.push(call.calldata.len().into()); // calldata_len
+            execute_calldata.extend(call.calldata.iter().copied()); // calldata
+        }
+
+        execute_calldata
+    }
+
     pub fn transaction_hash(&self, chain_id: FieldElement, address: FieldElement) -> FieldElement {
         compute_hash_on_elements(&[
             PREFIX_INVOKE,
@@ -0,0 +1,177 @@
+use starknet_in_rust::execution::{CallInfo, TransactionExecutionInfo};
+use starknet_types::felt::Felt;
+use starknet_types::traits::ToHexString;
+
+/// Full reason why the execution of a transaction was reverted, None if it was not reverted.
+/// The error reported by the execution is preceded by the entry point whose execution failed and
+/// its panic data, if known. Felts encoding ASCII text are decoded along the way.
+pub(crate) fn describe(execution_info: &TransactionExecutionInfo) -> Option<String> {
+    let revert_error = execution_info.revert_error.as_ref()?;
+
+    let mut reasons = vec![];
+    if let Some(failed_call) = execution_info.call_info.as_ref().and_then(innermost_failed_call) {
+        let entry_point_selector =
+            failed_call.entry_point_selector.clone().map(Felt::from).unwrap_or_default();
+        let panic_data: Vec<Felt> = failed_call.retdata.iter().cloned().map(Felt::from).collect();
+        reasons.push(format!(
+            "Execution of entry point {} of contract {} failed with: {}",
+            entry_point_selector.to_prefixed_hex_str(),
+            Felt::from(failed_call.contract_address.0.clone()).to_prefixed_hex_str(),
+            decode_panic_data(&panic_data)
+        ));
+    }
+    reasons.push(decode_short_strings_in(revert_error));
+
+    Some(reasons.join("\n"))
+}
+
+/// Messages Cairo 1 entry points panic with if their calldata is too short or too long for their
+/// parameters
+const CALLDATA_MISMATCH_PANICS: [&str; 2] =
+    ["Failed to deserialize param #", "Input too long for arguments"];
+
+const CALLDATA_GUIDANCE: &str = "The calldata doesn't match the parameters of the called entry \
+                                 point. Each felt252, ContractAddress or integer of up to 128 \
+                                 bits takes one felt, a u256 takes two (low, high), and an array \
+                                 or span takes its length followed by its elements. Compare the \
+                                 calldata with the ABI of the contract.";
+
+/// Appends guidance on fixing the calldata to `reason` if it's the failure of an entry point whose
+/// calldata doesn't match its parameters; otherwise `reason` is returned as is
+pub(crate) fn with_calldata_guidance(reason: String) -> String {
+    let decoded_reason = decode_short_strings_in(&reason);
+    if !CALLDATA_MISMATCH_PANICS.iter().any(|panic| decoded_reason.contains(panic)) {
+        return reason;
+    }
+
+    format!("{reason}\n{CALLDATA_GUIDANCE}")
+}
+
+/// The deepest call in the tree of `call_info` that failed, if any
+fn innermost_failed_call(call_info: &CallInfo) -> Option<&CallInfo> {
+    call_info
+        .internal_calls
+        .iter()
+        .find_map(innermost_failed_call)
+        .or_else(|| call_info.failure_flag.then_some(call_info))
+}
+
+/// Panic data as comma separated values, each decoded to text if it encodes ASCII, or as hex
+pub(crate) fn decode_panic_data(panic_data: &[Felt]) -> String {
+    panic_data
+        .iter()
+        .map(|felt| decode_short_string(felt).unwrap_or_else(|| felt.to_prefixed_hex_str()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Text of a Cairo short string, None if `felt` doesn't encode printable ASCII characters
+fn decode_short_string(felt: &Felt) -> Option<String> {
+    let bytes = felt.bytes();
+    let text_bytes: Vec<u8> = bytes.iter().copied().skip_while(|byte| *byte == 0).collect();
+    if text_bytes.is_empty()
+        || !text_bytes.iter().all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+    {
+        return None;
+    }
+
+    String::from_utf8(text_bytes).ok()
+}
+
+/// Appends the decoded text to every hex value in `message` that encodes ASCII
+fn decode_short_strings_in(message: &str) -> String {
+    let mut decoded = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(prefix_start) = rest.find("0x") {
+        let (before, from_prefix) = rest.split_at(prefix_start);
+        let hex_len = from_prefix[2..]
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(from_prefix.len() - 2);
+        let (hex, after) = from_prefix.split_at(2 + hex_len);
+
+        decoded.push_str(before);
+        decoded.push_str(hex);
+        if let Some(text) =
+            Felt::from_prefixed_hex_str(hex).ok().as_ref().and_then(decode_short_string)
+        {
+            decoded.push_str(&format!(" ('{text}')"));
+        }
+        rest = after;
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_in_rust::execution::{CallInfo, TransactionExecutionInfo};
+    use starknet_in_rust::utils::Address;
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::ToHexString;
+
+    use super::{decode_panic_data, describe, with_calldata_guidance, CALLDATA_GUIDANCE};
+
+    /// Felt encoding `text` as a Cairo short string
+    fn short_string(text: &str) -> Felt {
+        let mut bytes = [0u8; 32];
+        bytes[32 - text.len()..].copy_from_slice(text.as_bytes());
+        Felt::new(bytes).unwrap()
+    }
+
+    #[test]
+    fn panic_data_decoded_to_text_with_hex_fallback() {
+        let panic_data = [short_string("Out of gas"), Felt::from(0), Felt::from(0x1234)];
+        assert_eq!(decode_panic_data(&panic_data), "Out of gas, 0x0, 0x1234");
+    }
+
+    #[test]
+    fn no_reason_for_transactions_that_were_not_reverted() {
+        assert_eq!(describe(&TransactionExecutionInfo::default()), None);
+    }
+
+    #[test]
+    fn reason_holds_failed_entry_point_and_decoded_panic_data() {
+        let failed_call = CallInfo {
+            contract_address: Address(Felt::from(0x42).into()),
+            entry_point_selector: Some(Felt::from(0x5).into()),
+            retdata: vec![short_string("Invalid amount").into()],
+            failure_flag: true,
+            ..CallInfo::default()
+        };
+        let execution_info = TransactionExecutionInfo {
+            call_info: Some(CallInfo {
+                contract_address: Address(Felt::from(0x1).into()),
+                internal_calls: vec![failed_call],
+                failure_flag: true,
+                ..CallInfo::default()
+            }),
+            revert_error: Some("Execution failed with 0x496e76616c696420616d6f756e74".to_string()),
+            ..TransactionExecutionInfo::default()
+        };
+
+        assert_eq!(
+            describe(&execution_info).unwrap(),
+            "Execution of entry point 0x5 of contract 0x42 failed with: Invalid amount\nExecution \
+             failed with 0x496e76616c696420616d6f756e74 ('Invalid amount')"
+        );
+    }
+
+    #[test]
+    fn guidance_added_to_failures_caused_by_calldata_length_only() {
+        let too_short = format!(
+            "Execution failed with {}",
+            short_string("Failed to deserialize param #2").to_prefixed_hex_str()
+        );
+        assert_eq!(
+            with_calldata_guidance(too_short.clone()),
+            format!("{too_short}\n{CALLDATA_GUIDANCE}")
+        );
+
+        let too_long = "Execution failed with: Input too long for arguments".to_string();
+        assert!(with_calldata_guidance(too_long).ends_with(CALLDATA_GUIDANCE));
+
+        let unrelated = "Execution failed with: Invalid amount".to_string();
+        assert_eq!(with_calldata_guidance(unrelated.clone()), unrelated);
+    }
+}
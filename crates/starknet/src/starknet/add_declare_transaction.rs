@@ -1,11 +1,12 @@
-use starknet_in_rust::transaction::{verify_version, Declare, DeclareV2};
+use starknet_in_rust::transaction::{verify_version, Transaction as SirTransaction};
 use starknet_types::felt::{ClassHash, TransactionHash};
 use starknet_types::traits::HashProducer;
 
-use crate::error::Result;
-use crate::starknet::Starknet;
+use crate::error::{Error, Result};
+use crate::starknet::{estimations, Starknet};
 use crate::transactions::declare_transaction::DeclareTransactionV1;
 use crate::transactions::declare_transaction_v2::DeclareTransactionV2;
+use crate::transactions::declare_transaction_v3::DeclareTransactionV3;
 use crate::transactions::{StarknetTransaction, Transaction};
 
 pub fn add_declare_transaction_v2(
@@ -14,40 +15,100 @@ pub fn add_declare_transaction_v2(
 ) -> Result<(TransactionHash, ClassHash)> {
     let mut declare_transaction = declare_transaction;
 
-    let transaction = DeclareV2::new(
-        &declare_transaction.sierra_contract_class.clone().try_into()?,
-        None,
-        declare_transaction.compiled_class_hash.into(),
-        declare_transaction.chain_id.into(),
-        declare_transaction.sender_address.try_into()?,
-        declare_transaction.max_fee,
-        declare_transaction.version.into(),
-        declare_transaction.signature.iter().map(|felt| felt.into()).collect(),
-        declare_transaction.nonce.into(),
-    )?;
+    let transaction = declare_transaction.create_sir_declare()?;
     let class_hash = transaction.sierra_class_hash.clone().into();
     let transaction_hash = transaction.hash_value.clone().into();
 
     declare_transaction.class_hash = Some(class_hash);
     declare_transaction.transaction_hash = Some(transaction_hash);
 
-    let state_before_txn = starknet.state.pending_state.clone();
+    assert_class_not_declared(starknet, &class_hash)?;
+
+    let declare_transaction = Transaction::DeclareV2(Box::new(declare_transaction));
+    let mut state_before_txn = starknet.state.pending_state.clone();
+    let block_context = starknet.block_context_for(declare_transaction.fee_unit())?;
+    let sierra_contract_class = transaction.sierra_contract_class.clone();
+
+    let execution_result = starknet
+        .execute_on_pending_state(SirTransaction::DeclareV2(Box::new(transaction)), &block_context)
+        .and_then(|tx_info| starknet.check_actual_fee(&declare_transaction, tx_info));
 
-    match transaction.execute(&mut starknet.state.pending_state, &starknet.block_context) {
+    match execution_result {
         Ok(tx_info) => {
             // Add sierra contract
-            starknet.sierra_contracts.insert(class_hash, transaction.sierra_contract_class);
+            starknet.sierra_contracts.insert(class_hash, sierra_contract_class);
+            let data_gas_consumed = estimations::data_gas_consumed(
+                state_before_txn.cache_mut(),
+                starknet.state.pending_state.cache_mut(),
+            );
             starknet.handle_successful_transaction(
                 &transaction_hash,
-                Transaction::DeclareV2(Box::new(declare_transaction)),
+                declare_transaction,
                 tx_info,
+                data_gas_consumed,
+                false,
             )?;
         }
         Err(tx_err) => {
-            let transaction_to_add = StarknetTransaction::create_rejected(
-                Transaction::DeclareV2(Box::new(declare_transaction)),
-                tx_err,
+            let transaction_to_add =
+                StarknetTransaction::create_rejected(declare_transaction, tx_err);
+
+            starknet.transactions.insert(&transaction_hash, transaction_to_add);
+            // Revert to previous pending state
+            starknet.state.pending_state = state_before_txn;
+        }
+    }
+
+    Ok((transaction_hash, class_hash))
+}
+
+/// Same as [`add_declare_transaction_v2`], but the fee is charged in STRK. The transaction is
+/// rejected if its resource bounds don't cover its estimated cost.
+pub fn add_declare_transaction_v3(
+    starknet: &mut Starknet,
+    declare_transaction: DeclareTransactionV3,
+) -> Result<(TransactionHash, ClassHash)> {
+    let mut declare_transaction = declare_transaction;
+
+    let transaction = declare_transaction.create_sir_declare()?;
+    let class_hash = transaction.sierra_class_hash.clone().into();
+    let transaction_hash = transaction.hash_value.clone().into();
+
+    declare_transaction.class_hash = Some(class_hash);
+    declare_transaction.transaction_hash = Some(transaction_hash);
+
+    assert_class_not_declared(starknet, &class_hash)?;
+
+    let declare_transaction = Transaction::DeclareV3(Box::new(declare_transaction));
+    estimations::check_resource_bounds(starknet, &declare_transaction)?;
+
+    let mut state_before_txn = starknet.state.pending_state.clone();
+    let block_context = starknet.block_context_for(declare_transaction.fee_unit())?;
+    let sierra_contract_class = transaction.sierra_contract_class.clone();
+
+    let execution_result = starknet
+        .execute_on_pending_state(SirTransaction::DeclareV2(Box::new(transaction)), &block_context)
+        .and_then(|tx_info| starknet.check_actual_fee(&declare_transaction, tx_info));
+
+    match execution_result {
+        Ok(tx_info) => {
+            // Add sierra contract
+            starknet.sierra_contracts.insert(class_hash, sierra_contract_class);
+            let data_gas_consumed = estimations::data_gas_consumed(
+                state_before_txn.cache_mut(),
+                starknet.state.pending_state.cache_mut(),
             );
+            starknet.handle_successful_transaction(
+                &transaction_hash,
+                declare_transaction,
+                tx_info,
+                data_gas_consumed,
+                false,
+            )?;
+        }
+        Err(tx_err) => {
+            let transaction_to_add =
+                StarknetTransaction::create_rejected(declare_transaction, tx_err);
 
             starknet.transactions.insert(&transaction_hash, transaction_to_add);
             // Revert to previous pending state
@@ -57,6 +118,7 @@ pub fn add_declare_transaction_v2(
 
     Ok((transaction_hash, class_hash))
 }
+
 pub fn add_declare_transaction_v1(
     starknet: &mut Starknet,
     declare_transaction: DeclareTransactionV1,
@@ -64,26 +126,13 @@ pub fn add_declare_transaction_v1(
     let mut declare_transaction = declare_transaction;
 
     let class_hash = declare_transaction.contract_class.generate_hash()?;
+    assert_class_not_declared(starknet, &class_hash)?;
+
     let transaction_hash = declare_transaction.generate_hash()?;
     declare_transaction.transaction_hash = Some(transaction_hash);
     declare_transaction.class_hash = Some(class_hash);
 
-    let transaction = Declare {
-        class_hash: class_hash.into(),
-        sender_address: declare_transaction.sender_address.try_into()?,
-        tx_type: starknet_in_rust::definitions::transaction_type::TransactionType::Declare,
-        validate_entry_point_selector:
-            starknet_in_rust::definitions::constants::VALIDATE_DECLARE_ENTRY_POINT_SELECTOR.clone(),
-        version: declare_transaction.version.into(),
-        max_fee: declare_transaction.max_fee,
-        signature: declare_transaction.signature.iter().map(|felt| felt.into()).collect(),
-        nonce: declare_transaction.nonce.into(),
-        hash_value: transaction_hash.into(),
-        contract_class: declare_transaction.contract_class.clone().try_into()?,
-        skip_execute: false,
-        skip_fee_transfer: false,
-        skip_validate: false,
-    };
+    let transaction = declare_transaction.create_sir_declare()?;
 
     verify_version(
         &transaction.version,
@@ -92,21 +141,31 @@ pub fn add_declare_transaction_v1(
         &transaction.signature,
     )?;
 
-    let state_before_txn = starknet.state.pending_state.clone();
+    let declare_transaction = Transaction::Declare(Box::new(declare_transaction));
+    let mut state_before_txn = starknet.state.pending_state.clone();
+    let block_context = starknet.block_context_for(declare_transaction.fee_unit())?;
 
-    match transaction.execute(&mut starknet.state.pending_state, &starknet.block_context) {
+    let execution_result = starknet
+        .execute_on_pending_state(SirTransaction::Declare(transaction), &block_context)
+        .and_then(|tx_info| starknet.check_actual_fee(&declare_transaction, tx_info));
+
+    match execution_result {
         Ok(tx_info) => {
+            let data_gas_consumed = estimations::data_gas_consumed(
+                state_before_txn.cache_mut(),
+                starknet.state.pending_state.cache_mut(),
+            );
             starknet.handle_successful_transaction(
                 &transaction_hash,
-                Transaction::Declare(Box::new(declare_transaction)),
+                declare_transaction,
                 tx_info,
+                data_gas_consumed,
+                false,
             )?;
         }
         Err(tx_err) => {
-            let transaction_to_add = StarknetTransaction::create_rejected(
-                Transaction::Declare(Box::new(declare_transaction)),
-                tx_err,
-            );
+            let transaction_to_add =
+                StarknetTransaction::create_rejected(declare_transaction, tx_err);
 
             starknet.transactions.insert(&transaction_hash, transaction_to_add);
             // Revert to previous pending state
@@ -117,24 +176,46 @@ pub fn add_declare_transaction_v1(
     Ok((transaction_hash, class_hash))
 }
 
+/// Declaring a class again would change nothing, so such a declaration is refused before the
+/// sender is charged for it. Classes declared in the pending block count as declared.
+fn assert_class_not_declared(starknet: &mut Starknet, class_hash: &ClassHash) -> Result<()> {
+    let declared_in_pending_block =
+        starknet.pending_block().get_transactions().iter().any(|transaction| match transaction {
+            Transaction::Declare(declare) => declare.class_hash() == Some(class_hash),
+            Transaction::DeclareV2(declare) => declare.class_hash() == Some(class_hash),
+            Transaction::DeclareV3(declare) => declare.class_hash() == Some(class_hash),
+            _ => false,
+        });
+
+    if declared_in_pending_block || starknet.state.is_contract_declared(class_hash) {
+        return Err(Error::ClassAlreadyDeclared { class_hash: *class_hash });
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use starknet_api::block::BlockNumber;
     use starknet_in_rust::core::contract_address::compute_casm_class_hash;
-    use starknet_in_rust::definitions::block_context::StarknetChainId;
     use starknet_in_rust::CasmContractClass;
-    use starknet_rs_core::types::TransactionStatus;
+    use starknet_rs_core::types::{BlockId, BlockTag, TransactionStatus};
     use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_class::ContractClass;
+    use starknet_types::contract_storage_key::ContractStorageKey;
     use starknet_types::felt::Felt;
     use starknet_types::traits::HashProducer;
 
     use crate::account::Account;
-    use crate::constants::{self};
-    use crate::starknet::{predeployed, Starknet};
-    use crate::traits::{Accounted, Deployed, HashIdentifiedMut, StateExtractor};
+    use crate::constants::{self, DEVNET_DEFAULT_CHAIN_ID};
+    use crate::error::Error;
+    use crate::starknet::{predeployed, FeeUnit, Starknet};
+    use crate::traits::{Accounted, Deployed, HashIdentifiedMut, StateChanger, StateExtractor};
     use crate::transactions::declare_transaction::DeclareTransactionV1;
     use crate::transactions::declare_transaction_v2::DeclareTransactionV2;
-    use crate::utils::load_cairo_0_contract_class;
+    use crate::transactions::declare_transaction_v3::DeclareTransactionV3;
+    use crate::transactions::v3::{ResourceBounds, ResourceBoundsMapping, V3Fields};
+    use crate::utils::{get_storage_var_address, load_cairo_0_contract_class};
     use crate::utils::test_utils::{
         dummy_cairo_0_contract_class, dummy_cairo_1_contract_class, dummy_felt,
     };
@@ -153,7 +234,7 @@ mod tests {
             100,
             Vec::new(),
             Felt::from(0),
-            StarknetChainId::TestNet.to_felt().into(),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
         )
         .unwrap()
     }
@@ -167,7 +248,7 @@ mod tests {
             Vec::new(),
             Felt::from(0),
             contract_class,
-            StarknetChainId::TestNet.to_felt().into(),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
         )
         .unwrap()
     }
@@ -205,6 +286,30 @@ mod tests {
         assert!(starknet.sierra_contracts.get(&class_hash).is_some())
     }
 
+    #[test]
+    fn declared_class_available_from_block_of_declaration() {
+        let (mut starknet, sender) = setup(Some(100000000));
+        starknet.create_block().unwrap();
+        let declare_txn = test_declare_transaction_v2(sender);
+        let (_, class_hash) = starknet.add_declare_transaction_v2(declare_txn.clone()).unwrap();
+
+        assert!(matches!(
+            starknet.get_class(BlockId::Number(1), class_hash),
+            Err(Error::ClassHashNotFound)
+        ));
+        for block_id in [BlockId::Number(2), BlockId::Tag(BlockTag::Latest)] {
+            let contract_class = starknet.get_class(block_id, class_hash).unwrap();
+            assert!(matches!(contract_class, ContractClass::Cairo1(_)));
+            assert_eq!(contract_class, declare_txn.sierra_contract_class);
+        }
+
+        assert!(matches!(
+            starknet.get_class(BlockId::Tag(BlockTag::Latest), dummy_felt()),
+            Err(Error::ClassHashNotFound)
+        ));
+        assert!(matches!(starknet.get_class(BlockId::Number(3), class_hash), Err(Error::NoBlock)));
+    }
+
     #[test]
     fn declare_v2_transaction_successful_storage_change() {
         let (mut starknet, sender) = setup(Some(100000000));
@@ -234,6 +339,63 @@ mod tests {
         assert!(starknet.state.is_contract_declared(&expected_class_hash));
     }
 
+    #[test]
+    fn add_declare_v3_transaction_charges_fee_in_strk() {
+        let (mut starknet, sender) = setup(Some(100000000));
+        let strk_fee_token_address = starknet.fee_token_address(FeeUnit::Fri);
+        predeployed::create_strk_erc20(strk_fee_token_address)
+            .unwrap()
+            .deploy(&mut starknet.state)
+            .unwrap();
+        let balance_storage_var_address =
+            get_storage_var_address("ERC20_balances", &[Felt::from(sender)]).unwrap();
+        let strk_balance_key =
+            ContractStorageKey::new(strk_fee_token_address, balance_storage_var_address);
+        let eth_balance_key = ContractStorageKey::new(
+            starknet.fee_token_address(FeeUnit::Wei),
+            balance_storage_var_address,
+        );
+        starknet.state.change_storage(strk_balance_key, Felt::from(100000000)).unwrap();
+        starknet.state.synchronize_states();
+        let eth_balance_before = starknet.state.get_storage(eth_balance_key).unwrap();
+
+        let contract_class = dummy_cairo_1_contract_class();
+        let compiled_class_hash =
+            compute_casm_class_hash(&CasmContractClass::try_from(contract_class.clone()).unwrap())
+                .unwrap();
+        let declare_txn = DeclareTransactionV3::new(
+            contract_class,
+            compiled_class_hash.into(),
+            sender,
+            Vec::new(),
+            Felt::from(0),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+            V3Fields {
+                resource_bounds: ResourceBoundsMapping {
+                    l1_gas: ResourceBounds { max_amount: 100000, max_price_per_unit: 1 },
+                    l2_gas: ResourceBounds::default(),
+                },
+                ..V3Fields::default()
+            },
+            Vec::new(),
+        )
+        .unwrap();
+
+        let (tx_hash, class_hash) = starknet.add_declare_transaction_v3(declare_txn).unwrap();
+        let tx = starknet.transactions.get_by_hash_mut(&tx_hash).unwrap();
+        assert_eq!(tx.status, TransactionStatus::AcceptedOnL2);
+        let actual_fee = tx.actual_fee();
+        assert!(actual_fee > 0);
+
+        assert!(starknet.state.is_contract_declared(&class_hash));
+        assert!(starknet.sierra_contracts.get(&class_hash).is_some());
+        assert_eq!(
+            starknet.state.get_storage(strk_balance_key).unwrap(),
+            Felt::from(100000000 - actual_fee)
+        );
+        assert_eq!(starknet.state.get_storage(eth_balance_key).unwrap(), eth_balance_before);
+    }
+
     #[test]
     fn add_declare_v1_transaction_should_return_rejected_txn_and_not_be_part_of_pending_state() {
         let (mut starknet, sender) = setup(Some(1));
@@ -268,14 +430,14 @@ mod tests {
         assert!(starknet.state.is_contract_declared(&class_hash));
         // check if pending block is resetted
         assert!(starknet.pending_block().get_transactions().is_empty());
-        // check if there is generated block
-        assert_eq!(starknet.blocks.num_to_block.len(), 1);
+        // check if there is generated block after the genesis block
+        assert_eq!(starknet.blocks.num_to_block.len(), 2);
         // check if transaction is in generated block
         assert_eq!(
             starknet
                 .blocks
                 .num_to_block
-                .get(&BlockNumber(0))
+                .get(&BlockNumber(1))
                 .unwrap()
                 .get_transactions()
                 .first()
@@ -305,6 +467,70 @@ mod tests {
         assert!(starknet.state.is_contract_declared(&class_hash));
     }
 
+    fn eth_balance_of(starknet: &Starknet, address: ContractAddress) -> Felt {
+        let balance_key = ContractStorageKey::new(
+            starknet.fee_token_address(FeeUnit::Wei),
+            get_storage_var_address("ERC20_balances", &[Felt::from(address)]).unwrap(),
+        );
+        starknet.state.get_storage(balance_key).unwrap()
+    }
+
+    #[test]
+    fn declare_v1_transaction_of_already_declared_class_is_refused_without_charge() {
+        let (mut starknet, sender) = setup(Some(100000000));
+        let (_, class_hash) =
+            starknet.add_declare_transaction_v1(test_declare_transaction_v1(sender)).unwrap();
+        let balance_after_first_declare = eth_balance_of(&starknet, sender);
+
+        let mut redeclaration = test_declare_transaction_v1(sender);
+        redeclaration.nonce = Felt::from(1);
+        match starknet.add_declare_transaction_v1(redeclaration) {
+            Err(Error::ClassAlreadyDeclared { class_hash: declared_class_hash }) => {
+                assert_eq!(declared_class_hash, class_hash)
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        assert_eq!(eth_balance_of(&starknet, sender), balance_after_first_declare);
+    }
+
+    #[test]
+    fn declare_v2_transaction_of_already_declared_class_is_refused_without_charge() {
+        let (mut starknet, sender) = setup(Some(100000000));
+        let (_, class_hash) =
+            starknet.add_declare_transaction_v2(test_declare_transaction_v2(sender)).unwrap();
+        let balance_after_first_declare = eth_balance_of(&starknet, sender);
+
+        let mut redeclaration = test_declare_transaction_v2(sender);
+        redeclaration.nonce = Felt::from(1);
+        match starknet.add_declare_transaction_v2(redeclaration) {
+            Err(Error::ClassAlreadyDeclared { class_hash: declared_class_hash }) => {
+                assert_eq!(declared_class_hash, class_hash)
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        assert_eq!(eth_balance_of(&starknet, sender), balance_after_first_declare);
+    }
+
+    #[test]
+    fn declare_transactions_not_charged_with_fee_charge_disabled() {
+        let (mut starknet, sender) = setup(Some(100000000));
+        starknet.config.disable_fee_charge = true;
+        let balance_before = eth_balance_of(&starknet, sender);
+
+        let (v1_hash, _) =
+            starknet.add_declare_transaction_v1(test_declare_transaction_v1(sender)).unwrap();
+        let mut declare_v2 = test_declare_transaction_v2(sender);
+        declare_v2.nonce = Felt::from(1);
+        let (v2_hash, _) = starknet.add_declare_transaction_v2(declare_v2).unwrap();
+
+        for tx_hash in [v1_hash, v2_hash] {
+            let tx = starknet.transactions.get_by_hash_mut(&tx_hash).unwrap();
+            assert_eq!(tx.status, TransactionStatus::AcceptedOnL2);
+            assert!(tx.actual_fee() > 0);
+        }
+        assert_eq!(eth_balance_of(&starknet, sender), balance_before);
+    }
+
     /// Initializes starknet with 1 account - account without validations
     fn setup(acc_balance: Option<u128>) -> (Starknet, ContractAddress) {
         let mut starknet = Starknet::default();
@@ -314,7 +540,8 @@ mod tests {
         );
         let contract_class = load_cairo_0_contract_class(account_json_path).unwrap();
 
-        let erc_20_contract = predeployed::create_erc20().unwrap();
+        let erc_20_contract =
+            predeployed::create_erc20(FeeUnit::Wei.default_erc20_address()).unwrap();
         erc_20_contract.deploy(&mut starknet.state).unwrap();
 
         let acc = Account::new(
@@ -334,7 +561,8 @@ mod tests {
         starknet.block_context = Starknet::get_block_context(
             1,
             constants::ERC20_CONTRACT_ADDRESS,
-            StarknetChainId::TestNet,
+            DEVNET_DEFAULT_CHAIN_ID,
+            constants::DEVNET_DEFAULT_MAX_STEPS,
         )
         .unwrap();
 
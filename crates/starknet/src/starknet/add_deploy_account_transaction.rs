@@ -1,46 +1,96 @@
 use starknet_in_rust::core::errors::state_errors::StateError;
+use starknet_in_rust::transaction::error::TransactionError;
+use starknet_in_rust::transaction::{DeployAccount, Transaction as SirTransaction};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::{Felt, TransactionHash};
 use starknet_types::traits::HashProducer;
 
-use super::Starknet;
+use super::{estimations, Starknet};
 use crate::error::{Error, Result};
 use crate::traits::StateExtractor;
 use crate::transactions::deploy_account_transaction::DeployAccountTransaction;
+use crate::transactions::deploy_account_transaction_v3::DeployAccountTransactionV3;
 use crate::transactions::{StarknetTransaction, Transaction};
 
 pub fn add_deploy_account_transaction(
     starknet: &mut Starknet,
     deploy_account_transaction: DeployAccountTransaction,
 ) -> Result<(TransactionHash, ContractAddress)> {
-    if !starknet
-        .state
-        .is_contract_declared(&Felt::new(*deploy_account_transaction.inner.class_hash())?)
-    {
-        return Err(Error::StateError(StateError::MissingClassHash()));
+    // a zero max fee is only acceptable if no fee is going to be charged
+    if deploy_account_transaction.max_fee == 0 && !starknet.config.disable_fee_charge {
+        return Err(Error::TransactionError(TransactionError::FeeError(
+            "For deploy account transaction, max fee cannot be 0".to_string(),
+        )));
     }
 
-    let state_before_txn = starknet.state.pending_state.clone();
     let transaction_hash = deploy_account_transaction.generate_hash()?;
-    let address: ContractAddress =
-        (deploy_account_transaction.inner.contract_address().clone()).try_into()?;
+    let inner = deploy_account_transaction.inner.clone();
+
+    execute_deploy_account(
+        starknet,
+        &inner,
+        transaction_hash,
+        Transaction::DeployAccount(Box::new(deploy_account_transaction)),
+    )
+}
+
+/// Same as [`add_deploy_account_transaction`], but the fee is charged in STRK. The transaction is
+/// rejected if its resource bounds don't cover its estimated cost.
+pub fn add_deploy_account_transaction_v3(
+    starknet: &mut Starknet,
+    deploy_account_transaction: DeployAccountTransactionV3,
+) -> Result<(TransactionHash, ContractAddress)> {
+    let transaction_hash = deploy_account_transaction.generate_hash()?;
+    let inner = deploy_account_transaction.inner.clone();
+    let transaction = Transaction::DeployAccountV3(Box::new(deploy_account_transaction));
+    estimations::check_resource_bounds(starknet, &transaction)?;
+
+    execute_deploy_account(starknet, &inner, transaction_hash, transaction)
+}
+
+fn execute_deploy_account(
+    starknet: &mut Starknet,
+    deploy_account: &DeployAccount,
+    transaction_hash: TransactionHash,
+    transaction: Transaction,
+) -> Result<(TransactionHash, ContractAddress)> {
+    if !starknet.state.is_contract_declared(&Felt::new(*deploy_account.class_hash())?) {
+        return Err(Error::StateError(StateError::MissingClassHash()));
+    }
+
+    // an occupied address is refused before the constructor is executed or any fee is charged
+    let address: ContractAddress = (deploy_account.contract_address().clone()).try_into()?;
+    if starknet.is_contract_deployed(deploy_account.contract_address()) {
+        return Err(Error::ContractAlreadyDeployed { contract_address: address });
+    }
+    starknet.check_nonce(address, *transaction.nonce())?;
+
+    let mut state_before_txn = starknet.state.pending_state.clone();
+    let block_context = starknet.block_context_for(transaction.fee_unit())?;
+
+    let execution_result = starknet
+        .execute_on_pending_state(
+            SirTransaction::DeployAccount(deploy_account.clone()),
+            &block_context,
+        )
+        .and_then(|tx_info| starknet.check_actual_fee(&transaction, tx_info));
 
-    match deploy_account_transaction
-        .inner
-        .execute(&mut starknet.state.pending_state, &starknet.block_context)
-    {
+    match execution_result {
         Ok(tx_info) => {
+            let data_gas_consumed = estimations::data_gas_consumed(
+                state_before_txn.cache_mut(),
+                starknet.state.pending_state.cache_mut(),
+            );
             starknet.handle_successful_transaction(
                 &transaction_hash,
-                Transaction::DeployAccount(Box::new(deploy_account_transaction)),
+                transaction,
                 tx_info,
+                data_gas_consumed,
+                false,
             )?;
         }
         Err(tx_err) => {
-            let transaction_to_add = StarknetTransaction::create_rejected(
-                Transaction::DeployAccount(Box::new(deploy_account_transaction)),
-                tx_err,
-            );
+            let transaction_to_add = StarknetTransaction::create_rejected(transaction, tx_err);
 
             starknet.transactions.insert(&transaction_hash, transaction_to_add);
             // Revert to previous pending state
@@ -53,6 +103,7 @@ pub fn add_deploy_account_transaction(
 
 #[cfg(test)]
 mod tests {
+    use starknet_in_rust::utils::Address;
     use starknet_rs_core::types::TransactionStatus;
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::contract_storage_key::ContractStorageKey;
@@ -60,11 +111,69 @@ mod tests {
     use starknet_types::traits::HashProducer;
 
     use crate::constants::{self, DEVNET_DEFAULT_CHAIN_ID};
-    use crate::starknet::{predeployed, Starknet};
+    use crate::error::Error;
+    use crate::starknet::{predeployed, BlockGenerationOn, FeeUnit, Starknet};
     use crate::traits::{Deployed, HashIdentifiedMut, StateChanger, StateExtractor};
     use crate::transactions::deploy_account_transaction::DeployAccountTransaction;
+    use crate::transactions::deploy_account_transaction_v3::DeployAccountTransactionV3;
+    use crate::transactions::v3::{ResourceBounds, ResourceBoundsMapping, V3Fields};
     use crate::utils::{get_storage_var_address, load_cairo_0_contract_class};
 
+    #[test]
+    fn deploy_account_transaction_with_max_fee_zero_should_return_an_error() {
+        let (mut starknet, account_class_hash, _) = setup();
+
+        let transaction = DeployAccountTransaction::new(
+            vec![],
+            0,
+            vec![],
+            Felt::from(0),
+            account_class_hash,
+            Felt::from(13),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+            Felt::from(0),
+        )
+        .unwrap();
+
+        match starknet.add_deploy_account_transaction(transaction) {
+            Err(crate::error::Error::TransactionError(
+                starknet_in_rust::transaction::error::TransactionError::FeeError(msg),
+            )) => assert_eq!(msg, "For deploy account transaction, max fee cannot be 0"),
+            _ => panic!("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn deploy_account_transaction_with_max_fee_zero_not_charged_with_fee_charge_disabled() {
+        let (mut starknet, account_class_hash, fee_token_address) = setup();
+        starknet.config.disable_fee_charge = true;
+        // the address of the transaction doesn't depend on its max fee
+        let (_, balance_storage_key) =
+            funded_deploy_account_transaction(&mut starknet, account_class_hash, fee_token_address);
+        let balance_before_deployment = starknet.state.get_storage(balance_storage_key).unwrap();
+
+        let transaction = DeployAccountTransaction::new(
+            vec![],
+            0,
+            vec![],
+            Felt::from(0),
+            account_class_hash,
+            Felt::from(13),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+            Felt::from(0),
+        )
+        .unwrap();
+
+        let (txn_hash, _) = starknet.add_deploy_account_transaction(transaction).unwrap();
+        let txn = starknet.transactions.get_by_hash_mut(&txn_hash).unwrap();
+        assert_eq!(txn.status, TransactionStatus::AcceptedOnL2);
+        assert!(txn.actual_fee() > 0);
+        assert_eq!(
+            starknet.state.get_storage(balance_storage_key).unwrap(),
+            balance_before_deployment
+        );
+    }
+
     #[test]
     fn deploy_account_transaction_should_fail_due_to_low_balance() {
         let (mut starknet, account_class_hash, _) = setup();
@@ -76,7 +185,7 @@ mod tests {
             Felt::from(0),
             account_class_hash,
             Felt::from(13),
-            DEVNET_DEFAULT_CHAIN_ID.to_felt().into(),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
             Felt::from(0),
         )
         .unwrap();
@@ -98,7 +207,7 @@ mod tests {
             Felt::from(0),
             account_class_hash,
             Felt::from(13),
-            DEVNET_DEFAULT_CHAIN_ID.to_felt().into(),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
             Felt::from(0),
         )
         .unwrap();
@@ -132,6 +241,133 @@ mod tests {
         assert!(account_balance_before_deployment > account_balance_after_deployment);
     }
 
+    #[test]
+    fn deploy_account_transaction_to_deployed_address_is_refused_without_charge() {
+        let (mut starknet, account_class_hash, fee_token_address) = setup();
+        let (transaction, balance_storage_key) =
+            funded_deploy_account_transaction(&mut starknet, account_class_hash, fee_token_address);
+        let account_address =
+            ContractAddress::try_from(transaction.inner.contract_address().clone()).unwrap();
+
+        starknet.add_deploy_account_transaction(transaction.clone()).unwrap();
+        let balance_after_deployment = starknet.state.get_storage(balance_storage_key).unwrap();
+
+        match starknet.add_deploy_account_transaction(transaction) {
+            Err(Error::ContractAlreadyDeployed { contract_address }) => {
+                assert_eq!(contract_address, account_address)
+            }
+            _ => panic!("Wrong error type"),
+        }
+        assert_eq!(
+            starknet.state.get_storage(balance_storage_key).unwrap(),
+            balance_after_deployment
+        );
+    }
+
+    #[test]
+    fn deploy_account_transaction_to_address_deployed_in_pending_block_is_refused() {
+        let (mut starknet, account_class_hash, fee_token_address) = setup();
+        starknet.config.block_generation_on = BlockGenerationOn::Demand;
+        let (transaction, _) =
+            funded_deploy_account_transaction(&mut starknet, account_class_hash, fee_token_address);
+
+        starknet.add_deploy_account_transaction(transaction.clone()).unwrap();
+        assert!(matches!(
+            starknet.add_deploy_account_transaction(transaction),
+            Err(Error::ContractAlreadyDeployed { .. })
+        ));
+        assert_eq!(starknet.pending_block().get_transactions().len(), 1);
+    }
+
+    #[test]
+    fn deploy_account_transaction_v3_charges_fee_in_strk() {
+        let (mut starknet, account_class_hash, eth_fee_token_address) = setup();
+        let strk_fee_token_address = starknet.fee_token_address(FeeUnit::Fri);
+        predeployed::create_strk_erc20(strk_fee_token_address)
+            .unwrap()
+            .deploy(&mut starknet.state)
+            .unwrap();
+
+        let transaction = DeployAccountTransactionV3::new(
+            vec![],
+            vec![],
+            Felt::from(0),
+            account_class_hash,
+            Felt::from(13),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+            V3Fields {
+                resource_bounds: ResourceBoundsMapping {
+                    l1_gas: ResourceBounds { max_amount: 2000, max_price_per_unit: 1 },
+                    l2_gas: ResourceBounds::default(),
+                },
+                ..V3Fields::default()
+            },
+        )
+        .unwrap();
+
+        let account_address =
+            ContractAddress::try_from(transaction.inner.contract_address().clone()).unwrap();
+        let balance_storage_var_address =
+            get_storage_var_address("ERC20_balances", &[account_address.into()]).unwrap();
+        let strk_balance_storage_key =
+            ContractStorageKey::new(strk_fee_token_address, balance_storage_var_address);
+        let eth_balance_storage_key =
+            ContractStorageKey::new(eth_fee_token_address, balance_storage_var_address);
+
+        let strk_balance_before_deployment = Felt::from(1000000);
+        starknet
+            .state
+            .change_storage(strk_balance_storage_key, strk_balance_before_deployment)
+            .unwrap();
+        starknet.state.synchronize_states();
+
+        let (txn_hash, _) = starknet.add_deploy_account_transaction_v3(transaction).unwrap();
+        let txn = starknet.transactions.get_by_hash_mut(&txn_hash).unwrap();
+        assert_eq!(txn.status, TransactionStatus::AcceptedOnL2);
+        let actual_fee = txn.actual_fee();
+        assert!(actual_fee > 0);
+
+        let address: Address = account_address.try_into().unwrap();
+        assert!(starknet.state.state.address_to_class_hash.contains_key(&address));
+        assert_eq!(
+            starknet.state.get_storage(strk_balance_storage_key).unwrap(),
+            Felt::from(1000000u128 - actual_fee)
+        );
+        assert_eq!(starknet.state.get_storage(eth_balance_storage_key).unwrap(), Felt::from(0));
+    }
+
+    /// A deploy account transaction of `class_hash`, whose counterfactual address is funded with
+    /// enough of the fee token at `fee_token_address` to pay for it, and the storage key of the
+    /// balance of the address
+    fn funded_deploy_account_transaction(
+        starknet: &mut Starknet,
+        class_hash: ClassHash,
+        fee_token_address: ContractAddress,
+    ) -> (DeployAccountTransaction, ContractStorageKey) {
+        let transaction = DeployAccountTransaction::new(
+            vec![],
+            2000,
+            vec![],
+            Felt::from(0),
+            class_hash,
+            Felt::from(13),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+            Felt::from(0),
+        )
+        .unwrap();
+
+        let account_address =
+            ContractAddress::try_from(transaction.inner.contract_address().clone()).unwrap();
+        let balance_storage_var_address =
+            get_storage_var_address("ERC20_balances", &[account_address.into()]).unwrap();
+        let balance_storage_key =
+            ContractStorageKey::new(fee_token_address, balance_storage_var_address);
+        starknet.state.change_storage(balance_storage_key, Felt::from(1000000)).unwrap();
+        starknet.state.synchronize_states();
+
+        (transaction, balance_storage_key)
+    }
+
     /// Initializes starknet with erc20 contract, 1 declared contract class. Gas price is set to 1
     fn setup() -> (Starknet, ClassHash, ContractAddress) {
         let mut starknet = Starknet::default();
@@ -139,7 +375,8 @@ mod tests {
             env!("CARGO_MANIFEST_DIR"),
             "/test_artifacts/account_without_validations/account.json"
         );
-        let erc_20_contract = predeployed::create_erc20().unwrap();
+        let erc_20_contract =
+            predeployed::create_erc20(FeeUnit::Wei.default_erc20_address()).unwrap();
         erc_20_contract.deploy(&mut starknet.state).unwrap();
 
         let contract_class = load_cairo_0_contract_class(account_json_path).unwrap();
@@ -151,6 +388,7 @@ mod tests {
             1,
             constants::ERC20_CONTRACT_ADDRESS,
             DEVNET_DEFAULT_CHAIN_ID,
+            constants::DEVNET_DEFAULT_MAX_STEPS,
         )
         .unwrap();
 
@@ -1,36 +1,126 @@
+use starknet_in_rust::definitions::block_context::BlockContext;
 use starknet_in_rust::definitions::constants::INITIAL_GAS_COST;
+use starknet_in_rust::execution::TransactionExecutionInfo;
+use starknet_in_rust::state::cached_state::CachedState;
+use starknet_in_rust::state::state_api::StateReader;
+use starknet_in_rust::transaction::error::TransactionError;
+use starknet_in_rust::transaction::InvokeFunction;
+use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::TransactionHash;
 use starknet_types::traits::HashProducer;
 
-use super::Starknet;
-use crate::error::Result;
+use super::parallel_execution::QueuedInvoke;
+use super::{estimations, FeeUnit, Starknet};
+use crate::error::{Error, Result};
 use crate::transactions::invoke_transaction::InvokeTransactionV1;
+use crate::transactions::invoke_transaction_v3::InvokeTransactionV3;
 use crate::transactions::{StarknetTransaction, Transaction};
 
 pub fn add_invoke_transcation_v1(
     starknet: &mut Starknet,
     invoke_transaction: InvokeTransactionV1,
 ) -> Result<TransactionHash> {
-    let state_before_txn = starknet.state.pending_state.clone();
+    check_max_fee(starknet, &invoke_transaction)?;
+
     let transaction_hash = invoke_transaction.generate_hash()?;
+    let inner = invoke_transaction.inner.clone();
+
+    execute_invoke(
+        starknet,
+        &inner,
+        transaction_hash,
+        Transaction::Invoke(Box::new(invoke_transaction)),
+    )
+}
+
+/// Same as [`add_invoke_transcation_v1`], but the transaction is only queued. It is executed along
+/// with the other queued transactions when the pending block is committed, with the block context
+/// and impersonation of its sender as of now.
+pub(crate) fn queue_invoke_transaction_v1(
+    starknet: &mut Starknet,
+    invoke_transaction: InvokeTransactionV1,
+) -> Result<TransactionHash> {
+    check_max_fee(starknet, &invoke_transaction)?;
+
+    let transaction_hash = invoke_transaction.generate_hash()?;
+    let invoke = invoke_transaction.inner.clone();
+    let sender_address = ContractAddress::try_from(invoke.contract_address().clone())?;
+    starknet.queued_invokes.push(QueuedInvoke {
+        transaction_hash,
+        skip_validation: starknet.is_account_impersonated(&sender_address),
+        skip_fee_charge: starknet.config.disable_fee_charge,
+        block_context: starknet.block_context_for(FeeUnit::Wei)?,
+        invoke,
+        transaction: Transaction::Invoke(Box::new(invoke_transaction)),
+    });
+
+    Ok(transaction_hash)
+}
+
+/// A zero max fee is only acceptable if no fee is going to be charged
+pub(crate) fn check_max_fee(starknet: &Starknet, invoke_transaction: &InvokeTransactionV1) -> Result<()> {
+    let skip_fee_charge = starknet.config.disable_fee_charge;
+    let gas_price = starknet.block_context.block_info().gas_price;
+    if invoke_transaction.max_fee == 0 && !skip_fee_charge && gas_price != 0 {
+        return Err(Error::TransactionError(TransactionError::FeeError(
+            "For invoke transaction, max fee cannot be 0".to_string(),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Same as [`add_invoke_transcation_v1`], but the fee is charged in STRK. The transaction is
+/// rejected if its resource bounds don't cover its estimated cost.
+pub fn add_invoke_transaction_v3(
+    starknet: &mut Starknet,
+    invoke_transaction: InvokeTransactionV3,
+) -> Result<TransactionHash> {
+    let transaction_hash = invoke_transaction.generate_hash()?;
+    let inner = invoke_transaction.inner.clone();
+    let transaction = Transaction::InvokeV3(Box::new(invoke_transaction));
+    estimations::check_resource_bounds(starknet, &transaction)?;
+
+    execute_invoke(starknet, &inner, transaction_hash, transaction)
+}
 
-    match invoke_transaction.inner.execute(
+fn execute_invoke(
+    starknet: &mut Starknet,
+    invoke: &InvokeFunction,
+    transaction_hash: TransactionHash,
+    transaction: Transaction,
+) -> Result<TransactionHash> {
+    let skip_fee_charge = starknet.config.disable_fee_charge;
+    let mut state_before_txn = starknet.state.pending_state.clone();
+    let block_context = starknet.block_context_for(transaction.fee_unit())?;
+
+    let sender_address = ContractAddress::try_from(invoke.contract_address().clone())?;
+    let skip_validation = starknet.is_account_impersonated(&sender_address);
+    let execution_result = execute_invoke_on(
         &mut starknet.state.pending_state,
-        &starknet.block_context,
-        INITIAL_GAS_COST,
-    ) {
+        invoke,
+        &block_context,
+        skip_validation,
+        skip_fee_charge,
+    )
+    .and_then(|tx_info| starknet.check_actual_fee(&transaction, tx_info));
+
+    match execution_result {
         Ok(tx_info) => {
+            let data_gas_consumed = estimations::data_gas_consumed(
+                state_before_txn.cache_mut(),
+                starknet.state.pending_state.cache_mut(),
+            );
             starknet.handle_successful_transaction(
                 &transaction_hash,
-                Transaction::Invoke(Box::new(invoke_transaction)),
+                transaction,
                 tx_info,
+                data_gas_consumed,
+                skip_validation,
             )?;
         }
         Err(tx_err) => {
-            let transaction_to_add = StarknetTransaction::create_rejected(
-                Transaction::Invoke(Box::new(invoke_transaction)),
-                tx_err,
-            );
+            let transaction_to_add = StarknetTransaction::create_rejected(transaction, tx_err);
 
             starknet.transactions.insert(&transaction_hash, transaction_to_add);
             // Revert to previous pending state
@@ -41,23 +131,46 @@ pub fn add_invoke_transcation_v1(
     Ok(transaction_hash)
 }
 
+/// Executes `invoke` on `state`, without validating it if `skip_validation` is set. With
+/// `skip_fee_charge`, the actual fee is still computed, but it is neither checked against max fee
+/// nor transferred from the sender
+pub(crate) fn execute_invoke_on<S: StateReader>(
+    state: &mut CachedState<S>,
+    invoke: &InvokeFunction,
+    block_context: &BlockContext,
+    skip_validation: bool,
+    skip_fee_charge: bool,
+) -> std::result::Result<TransactionExecutionInfo, TransactionError> {
+    if skip_validation || skip_fee_charge {
+        invoke
+            .create_for_simulation(skip_validation, false, skip_fee_charge, skip_fee_charge)
+            .execute(state, block_context, INITIAL_GAS_COST)
+    } else {
+        invoke.execute(state, block_context, INITIAL_GAS_COST)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use starknet_in_rust::services::api::contract_classes::deprecated_contract_class::ContractClass as StarknetInRustContractClass;
     use starknet_in_rust::EntryPointType;
-    use starknet_rs_core::types::TransactionStatus;
+    use starknet_rs_core::types::{BlockId, BlockTag, TransactionStatus};
     use starknet_rs_core::utils::get_selector_from_name;
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::contract_storage_key::ContractStorageKey;
-    use starknet_types::felt::Felt;
+    use starknet_types::felt::{Felt, TransactionHash};
     use starknet_types::traits::HashProducer;
 
     use crate::account::Account;
-    use crate::constants::{self, DEVNET_DEFAULT_CHAIN_ID};
-    use crate::starknet::{predeployed, Starknet};
+    use crate::constants::{self, CAIRO_0_ACCOUNT_CONTRACT_PATH, DEVNET_DEFAULT_CHAIN_ID};
+    use crate::error::Error;
+    use crate::starknet::{predeployed, BlockGenerationOn, FeeUnit, Starknet};
     use crate::traits::{Accounted, Deployed, HashIdentifiedMut, StateChanger, StateExtractor};
     use crate::transactions::invoke_transaction::InvokeTransactionV1;
-    use crate::utils::get_storage_var_address;
+    use crate::transactions::invoke_transaction_v3::InvokeTransactionV3;
+    use crate::transactions::v3::{ResourceBounds, ResourceBoundsMapping, V3Fields};
+    use crate::transactions::Transaction;
+    use crate::utils::{get_storage_var_address, load_cairo_0_contract_class};
     use crate::utils::test_utils::{
         cairo_0_account_without_validations, dummy_cairo_0_contract_class, dummy_felt,
         get_bytes_from_u32,
@@ -83,7 +196,7 @@ mod tests {
             vec![],
             Felt::from(nonce),
             calldata,
-            DEVNET_DEFAULT_CHAIN_ID.to_felt().into(),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
         )
         .unwrap()
     }
@@ -174,16 +287,624 @@ mod tests {
         let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
         assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
 
+        match starknet.add_invoke_transaction_v1(invoke_transaction) {
+            Err(Error::InvalidTransactionNonce { address, expected, provided }) => {
+                assert_eq!(address, account_address);
+                assert_eq!(expected, Felt::from(1));
+                assert_eq!(provided, Felt::from(0));
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn invoke_transaction_with_nonce_ahead_is_rejected_if_blocks_generated_on_transaction() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+
+        let invoke_transaction = test_invoke_transaction_v1(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            Felt::from(10),
+            1,
+        );
+
+        match starknet.add_invoke_transaction_v1(invoke_transaction) {
+            Err(Error::InvalidTransactionNonce { expected, provided, .. }) => {
+                assert_eq!(expected, Felt::from(0));
+                assert_eq!(provided, Felt::from(1));
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn invoke_transactions_with_nonce_gap_are_added_in_nonce_order_once_gap_fills() {
+        let (
+            mut starknet,
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            balance_var_storage_address,
+        ) = setup();
+        starknet.config.block_generation_on = BlockGenerationOn::Demand;
+        let invoke_transaction = |nonce: u128| {
+            test_invoke_transaction_v1(
+                account_address,
+                contract_address,
+                increase_balance_selector,
+                Felt::from(10),
+                nonce,
+            )
+        };
+
+        let first_hash = starknet.add_invoke_transaction_v1(invoke_transaction(0)).unwrap();
+        let third_hash = starknet.add_invoke_transaction_v1(invoke_transaction(2)).unwrap();
+        let fourth_hash = starknet.add_invoke_transaction_v1(invoke_transaction(3)).unwrap();
+
+        // the transactions after the gap wait without being executed, known as received
+        for transaction_hash in [third_hash, fourth_hash] {
+            let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+            assert_eq!(transaction.status, TransactionStatus::Pending);
+            assert!(transaction.execution_status().is_none());
+        }
+        assert_eq!(starknet.blocks.pending_block.get_transactions().len(), 1);
+        // a nonce that is already awaited can't be sent again
+        assert!(matches!(
+            starknet.add_invoke_transaction_v1(invoke_transaction(2)),
+            Err(Error::InvalidTransactionNonce { .. })
+        ));
+
+        let second_hash = starknet.add_invoke_transaction_v1(invoke_transaction(1)).unwrap();
+        assert_eq!(starknet.blocks.pending_block.get_transactions().len(), 4);
+
+        let block_hash = starknet.create_block().unwrap();
+        assert_eq!(
+            starknet.state.get_storage(balance_var_storage_address).unwrap(),
+            Felt::from(40)
+        );
+        let block = starknet.get_block(BlockId::Hash(block_hash.into())).unwrap();
+        let block_transaction_hashes: Vec<TransactionHash> =
+            block.get_transactions().iter().filter_map(|t| t.get_hash()).collect();
+        assert_eq!(
+            block_transaction_hashes,
+            vec![first_hash, second_hash, third_hash, fourth_hash]
+        );
+        for transaction_hash in block_transaction_hashes {
+            let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+            assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        }
+    }
+
+    #[test]
+    fn awaiting_transaction_failing_to_be_added_is_rejected() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+        starknet.config.block_generation_on = BlockGenerationOn::Demand;
+        starknet.config.gas_price = 1;
+
+        // the zero max fee is checked only once the transaction stops waiting
+        let awaiting_transaction = InvokeTransactionV1::new(
+            account_address,
+            0,
+            vec![],
+            Felt::from(1),
+            vec![
+                Felt::from(contract_address),
+                increase_balance_selector,
+                Felt::from(1),
+                Felt::from(10),
+            ],
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap();
+        let awaiting_hash = starknet.add_invoke_transaction_v1(awaiting_transaction).unwrap();
+        starknet
+            .add_invoke_transaction_v1(test_invoke_transaction_v1(
+                account_address,
+                contract_address,
+                increase_balance_selector,
+                Felt::from(10),
+                0,
+            ))
+            .unwrap();
+
+        let transaction = starknet.transactions.get_by_hash_mut(&awaiting_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Rejected);
+        assert!(matches!(
+            transaction.execution_error,
+            Some(starknet_in_rust::transaction::error::TransactionError::FeeError(_))
+        ));
+        assert_eq!(starknet.blocks.pending_block.get_transactions().len(), 1);
+    }
+
+    #[test]
+    fn pending_transactions_listed_in_demand_mode_until_block_created() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+        let invoke_transaction = |nonce: u128| {
+            test_invoke_transaction_v1(
+                account_address,
+                contract_address,
+                increase_balance_selector,
+                Felt::from(10),
+                nonce,
+            )
+        };
+        let pending_hashes = |starknet: &Starknet| -> Vec<TransactionHash> {
+            starknet.pending_transactions().iter().filter_map(|t| t.get_hash()).collect()
+        };
+
+        // transactions are part of a block as soon as they are added
+        starknet.add_invoke_transaction_v1(invoke_transaction(0)).unwrap();
+        assert!(pending_hashes(&starknet).is_empty());
+
+        starknet.config.block_generation_on = BlockGenerationOn::Demand;
+        let second_hash = starknet.add_invoke_transaction_v1(invoke_transaction(1)).unwrap();
+        let fourth_hash = starknet.add_invoke_transaction_v1(invoke_transaction(3)).unwrap();
+        assert_eq!(pending_hashes(&starknet), vec![second_hash, fourth_hash]);
+
+        let third_hash = starknet.add_invoke_transaction_v1(invoke_transaction(2)).unwrap();
+        assert_eq!(pending_hashes(&starknet), vec![second_hash, third_hash, fourth_hash]);
+
+        starknet.create_block().unwrap();
+        assert!(pending_hashes(&starknet).is_empty());
+    }
+
+    /// Deploys an account of the OpenZeppelin class, which validates signatures. Its private key
+    /// is not used, so that the transactions it sends are signed with garbage.
+    fn deploy_account_validating_signatures(starknet: &mut Starknet) -> Account {
+        let account_contract_class =
+            load_cairo_0_contract_class(CAIRO_0_ACCOUNT_CONTRACT_PATH).unwrap();
+        let account = Account::new(
+            Felt::from(10000),
+            Felt::from(42),
+            Felt::from(43),
+            account_contract_class.generate_hash().unwrap(),
+            account_contract_class,
+            ContractAddress::new(
+                Felt::from_prefixed_hex_str(constants::ERC20_CONTRACT_ADDRESS).unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        account.deploy(&mut starknet.state).unwrap();
+        account.set_initial_balance(&mut starknet.state).unwrap();
+        starknet.state.synchronize_states();
+        starknet.restart_pending_block().unwrap();
+
+        account
+    }
+
+    /// Invoke transaction calling `increase_balance` through `__execute__` of an account that
+    /// expects calls in the multicall format
+    fn multicall_invoke_transaction(
+        account_address: ContractAddress,
+        contract_address: ContractAddress,
+        increase_balance_selector: Felt,
+        signature: Vec<Felt>,
+        nonce: u128,
+    ) -> InvokeTransactionV1 {
+        InvokeTransactionV1::new(
+            account_address,
+            10000,
+            signature,
+            Felt::from(nonce),
+            vec![
+                Felt::from(1),                // number of calls
+                Felt::from(contract_address), // to
+                increase_balance_selector,    // selector
+                Felt::from(0),                // data offset
+                Felt::from(1),                // data length
+                Felt::from(1),                // calldata length
+                Felt::from(10),               // calldata
+            ],
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn invoke_transaction_with_signature_rejected_by_account_is_rejected() {
+        let (mut starknet, _, contract_address, increase_balance_selector, _) = setup();
+        let account = deploy_account_validating_signatures(&mut starknet);
+
+        let transaction_hash = starknet
+            .add_invoke_transaction_v1(multicall_invoke_transaction(
+                account.get_address(),
+                contract_address,
+                increase_balance_selector,
+                vec![Felt::from(1), Felt::from(2)],
+                0,
+            ))
+            .unwrap();
+
+        // __validate__ of the account fails, which is reported instead of aborting Devnet
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Rejected);
+        assert!(transaction.execution_error.is_some());
+        assert!(transaction.execution_info.is_none());
+    }
+
+    #[test]
+    fn invoke_transaction_of_account_accepting_any_signature_is_accepted() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+
+        // the account of the setup doesn't check signatures at all
+        let invoke_transaction = InvokeTransactionV1::new(
+            account_address,
+            10000,
+            vec![Felt::from(1), Felt::from(2)],
+            Felt::from(0),
+            vec![
+                Felt::from(contract_address),
+                increase_balance_selector,
+                Felt::from(1),
+                Felt::from(10),
+            ],
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap();
+
+        let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction).unwrap();
+
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        assert!(transaction.execution_info.as_ref().unwrap().validate_info.is_some());
+    }
+
+    #[test]
+    fn invoke_transaction_of_impersonated_account_skips_validation_and_charges_fee() {
+        let (mut starknet, _, contract_address, increase_balance_selector, _) = setup();
+        let account = deploy_account_validating_signatures(&mut starknet);
+
+        let invoke_transaction = |nonce: u128| {
+            multicall_invoke_transaction(
+                account.get_address(),
+                contract_address,
+                increase_balance_selector,
+                vec![],
+                nonce,
+            )
+        };
+
+        // without a signature, validation fails
+        let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction(0)).unwrap();
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Rejected);
+
+        starknet.impersonate_account(account.get_address());
+        let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction(0)).unwrap();
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        assert!(account.get_balance(&mut starknet.state).unwrap() < Felt::from(10000));
+
+        starknet.stop_impersonating_account(&account.get_address());
+        let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction(1)).unwrap();
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Rejected);
+    }
+
+    #[test]
+    fn invoke_transaction_reverted_by_failing_call_holds_its_error_message() {
+        let (mut starknet, account_address, _, _, _) = setup();
+
+        // the account holds less than it transfers
+        let invoke_transaction = InvokeTransactionV1::new(
+            account_address,
+            10000,
+            vec![],
+            Felt::from(0),
+            vec![
+                Felt::from(FeeUnit::Wei.default_erc20_address()),
+                get_selector_from_name("transfer").unwrap().into(),
+                Felt::from(3),
+                Felt::from(0x42),
+                Felt::from(20000),
+                Felt::from(0),
+            ],
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap();
+        let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction).unwrap();
+
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        let revert_reason = transaction.revert_reason().unwrap();
+        assert!(
+            revert_reason.contains("ERC20: transfer amount exceeds balance"),
+            "Unexpected revert reason: {revert_reason}"
+        );
+    }
+
+    #[test]
+    fn invoke_transaction_exceeding_max_steps_is_reverted() {
+        let (
+            mut starknet,
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            contract_storage_key,
+        ) = setup();
+        // only the steps of the execution itself are counted, not those of validation and fee
+        // transfer
+        starknet.impersonate_account(account_address);
+        starknet.config.disable_fee_charge = true;
+        starknet.block_context = Starknet::get_block_context(
+            1,
+            constants::ERC20_CONTRACT_ADDRESS,
+            DEVNET_DEFAULT_CHAIN_ID,
+            10,
+        )
+        .unwrap();
+
+        let invoke_transaction = invoke_transaction_with_zero_max_fee(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+        );
+        let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction).unwrap();
+
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        let revert_reason = transaction.revert_reason().unwrap();
+        assert!(
+            revert_reason.contains("RunResources has no remaining steps"),
+            "Unexpected revert reason: {revert_reason}"
+        );
+        assert_eq!(starknet.state.get_storage(contract_storage_key).unwrap(), Felt::from(0));
+    }
+
+    fn invoke_transaction_with_zero_max_fee(
+        account_address: ContractAddress,
+        contract_address: ContractAddress,
+        function_selector: Felt,
+    ) -> InvokeTransactionV1 {
+        InvokeTransactionV1::new(
+            account_address,
+            0,
+            vec![],
+            Felt::from(0),
+            vec![Felt::from(contract_address), function_selector, Felt::from(1), Felt::from(10)],
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap()
+    }
+
+    fn fee_token_balance_key(
+        starknet: &Starknet,
+        account_address: ContractAddress,
+        unit: FeeUnit,
+    ) -> ContractStorageKey {
+        let storage_var_address =
+            get_storage_var_address("ERC20_balances", &[Felt::from(account_address)]).unwrap();
+        ContractStorageKey::new(starknet.fee_token_address(unit), storage_var_address)
+    }
+
+    fn get_fee_token_balance(
+        starknet: &mut Starknet,
+        account_address: ContractAddress,
+        unit: FeeUnit,
+    ) -> Felt {
+        let balance_key = fee_token_balance_key(starknet, account_address, unit);
+        starknet.state.get_storage(balance_key).unwrap()
+    }
+
+    #[test]
+    fn invoke_transaction_with_max_fee_zero_should_return_error() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+        starknet.config.gas_price = 1;
+
+        let invoke_transaction = invoke_transaction_with_zero_max_fee(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+        );
+
+        match starknet.add_invoke_transaction_v1(invoke_transaction) {
+            Err(crate::error::Error::TransactionError(
+                starknet_in_rust::transaction::error::TransactionError::FeeError(msg),
+            )) => assert_eq!(msg, "For invoke transaction, max fee cannot be 0"),
+            _ => panic!("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn invoke_transaction_with_disabled_fee_charge_does_not_change_balance() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+        starknet.config.gas_price = 1;
+        starknet.config.disable_fee_charge = true;
+        let balance_before = get_fee_token_balance(&mut starknet, account_address, FeeUnit::Wei);
+
+        let invoke_transaction = invoke_transaction_with_zero_max_fee(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+        );
+        let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction).unwrap();
+
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        // the fee is computed even though it is not charged
+        assert!(transaction.execution_info.as_ref().unwrap().actual_fee > 0);
+        assert_eq!(
+            get_fee_token_balance(&mut starknet, account_address, FeeUnit::Wei),
+            balance_before
+        );
+    }
+
+    fn invoke_transaction_with_max_fee(
+        account_address: ContractAddress,
+        contract_address: ContractAddress,
+        function_selector: Felt,
+        max_fee: u128,
+    ) -> InvokeTransactionV1 {
+        InvokeTransactionV1::new(
+            account_address,
+            max_fee,
+            vec![],
+            Felt::from(0),
+            vec![Felt::from(contract_address), function_selector, Felt::from(1), Felt::from(10)],
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn invoke_transaction_with_max_fee_below_actual_fee_is_rejected() {
+        let (
+            mut starknet,
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            balance_var_storage_address,
+        ) = setup();
+        let balance_before = get_fee_token_balance(&mut starknet, account_address, FeeUnit::Wei);
+
+        let invoke_transaction = invoke_transaction_with_max_fee(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            1,
+        );
         let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction).unwrap();
+
         let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
         assert_eq!(transaction.status, TransactionStatus::Rejected);
+        assert!(transaction.execution_error.is_some());
+        // neither the changes of the transaction nor its fee are committed
+        assert_eq!(starknet.state.get_storage(balance_var_storage_address).unwrap(), Felt::from(0));
+        assert_eq!(
+            get_fee_token_balance(&mut starknet, account_address, FeeUnit::Wei),
+            balance_before
+        );
+    }
+
+    #[test]
+    fn invoke_transaction_with_max_fee_equal_to_actual_fee_is_accepted() {
+        let (
+            mut starknet,
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            balance_var_storage_address,
+        ) = setup();
+
+        let estimated_transaction = Transaction::Invoke(Box::new(invoke_transaction_with_max_fee(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            10000,
+        )));
+        let estimated_fee = starknet
+            .estimate_fee(BlockId::Tag(BlockTag::Pending), &[estimated_transaction], false)
+            .unwrap()[0]
+            .overall_fee;
+
+        let invoke_transaction = invoke_transaction_with_max_fee(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            estimated_fee,
+        );
+        let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction).unwrap();
+
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        assert_eq!(transaction.actual_fee(), estimated_fee);
+        assert_eq!(
+            starknet.state.get_storage(balance_var_storage_address).unwrap(),
+            Felt::from(10)
+        );
+    }
+
+    /// Deploys the STRK fee token and funds `account_address` with STRK only
+    fn fund_with_strk(starknet: &mut Starknet, account_address: ContractAddress, amount: u128) {
+        predeployed::create_strk_erc20(starknet.fee_token_address(FeeUnit::Fri))
+            .unwrap()
+            .deploy(&mut starknet.state)
+            .unwrap();
+        let balance_key = fee_token_balance_key(starknet, account_address, FeeUnit::Fri);
+        starknet.state.change_storage(balance_key, Felt::from(amount)).unwrap();
+        starknet.state.synchronize_states();
+    }
+
+    fn test_invoke_transaction_v3(
+        account_address: ContractAddress,
+        contract_address: ContractAddress,
+        function_selector: Felt,
+        l1_gas_max_amount: u64,
+    ) -> InvokeTransactionV3 {
+        InvokeTransactionV3::new(
+            account_address,
+            vec![],
+            Felt::from(0),
+            vec![Felt::from(contract_address), function_selector, Felt::from(1), Felt::from(10)],
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+            V3Fields {
+                resource_bounds: ResourceBoundsMapping {
+                    l1_gas: ResourceBounds { max_amount: l1_gas_max_amount, max_price_per_unit: 1 },
+                    l2_gas: ResourceBounds::default(),
+                },
+                ..V3Fields::default()
+            },
+            vec![],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn invoke_transaction_v3_charges_fee_in_strk() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+        fund_with_strk(&mut starknet, account_address, 1_000_000);
+        let eth_balance_before =
+            get_fee_token_balance(&mut starknet, account_address, FeeUnit::Wei);
+
+        let invoke_transaction = test_invoke_transaction_v3(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            10000,
+        );
+        let transaction_hash = starknet.add_invoke_transaction_v3(invoke_transaction).unwrap();
+
+        let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        assert_eq!(transaction.get_transaction().fee_unit(), FeeUnit::Fri);
+        let actual_fee = transaction.actual_fee();
+        assert!(actual_fee > 0);
+
+        assert_eq!(
+            get_fee_token_balance(&mut starknet, account_address, FeeUnit::Fri),
+            Felt::from(1_000_000 - actual_fee)
+        );
+        assert_eq!(
+            get_fee_token_balance(&mut starknet, account_address, FeeUnit::Wei),
+            eth_balance_before
+        );
+    }
+
+    #[test]
+    fn invoke_transaction_v3_with_l1_gas_below_estimate_should_return_error() {
+        let (mut starknet, account_address, contract_address, increase_balance_selector, _) =
+            setup();
+        fund_with_strk(&mut starknet, account_address, 1_000_000);
+
+        let invoke_transaction = test_invoke_transaction_v3(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            1,
+        );
 
-        match transaction.execution_error.as_ref().unwrap() {
-            starknet_in_rust::transaction::error::TransactionError::InvalidTransactionNonce(
-                _,
-                _,
-            ) => {}
-            err => panic!("Invalid error type {:?}", err),
+        match starknet.add_invoke_transaction_v3(invoke_transaction) {
+            Err(crate::error::Error::InsufficientResources { msg: _ }) => {}
+            other => panic!("Unexpected result: {other:?}"),
         }
     }
 
@@ -193,7 +914,8 @@ mod tests {
         let mut starknet = Starknet::default();
 
         // deploy erc20 contract
-        let erc_20_contract = predeployed::create_erc20().unwrap();
+        let erc_20_contract =
+            predeployed::create_erc20(FeeUnit::Wei.default_erc20_address()).unwrap();
         erc_20_contract.deploy(&mut starknet.state).unwrap();
 
         // deploy account contract
@@ -249,6 +971,7 @@ mod tests {
             1,
             constants::ERC20_CONTRACT_ADDRESS,
             DEVNET_DEFAULT_CHAIN_ID,
+            constants::DEVNET_DEFAULT_MAX_STEPS,
         )
         .unwrap();
 
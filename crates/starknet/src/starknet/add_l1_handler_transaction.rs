@@ -0,0 +1,70 @@
+use starknet_in_rust::definitions::constants::INITIAL_GAS_COST;
+use starknet_types::felt::TransactionHash;
+use starknet_types::traits::{HashProducer, ToHexString};
+
+use super::{estimations, Starknet};
+use crate::error::{Error, Result};
+use crate::transactions::l1_handler_transaction::L1HandlerTransaction;
+use crate::transactions::{StarknetTransaction, Transaction};
+
+pub fn add_l1_handler_transaction(
+    starknet: &mut Starknet,
+    l1_handler_transaction: L1HandlerTransaction,
+) -> Result<TransactionHash> {
+    // a message can be consumed only once; one whose handler failed can be sent again
+    if starknet.consumed_l1_to_l2_message_nonces.contains(&l1_handler_transaction.nonce) {
+        return Err(Error::MessageToL2AlreadyConsumed {
+            nonce: l1_handler_transaction.nonce.to_prefixed_hex_str(),
+        });
+    }
+    // messages are consumed in the order in which they were sent on L1
+    if let Some(last_consumed_nonce) = starknet
+        .consumed_l1_to_l2_message_nonces
+        .iter()
+        .max()
+        .filter(|last_consumed_nonce| **last_consumed_nonce > l1_handler_transaction.nonce)
+    {
+        return Err(Error::MessageToL2OutOfOrder {
+            nonce: l1_handler_transaction.nonce.to_prefixed_hex_str(),
+            last_consumed_nonce: last_consumed_nonce.to_prefixed_hex_str(),
+        });
+    }
+
+    let mut state_before_txn = starknet.state.pending_state.clone();
+    let transaction_hash = l1_handler_transaction.generate_hash()?;
+
+    match l1_handler_transaction.inner.execute(
+        &mut starknet.state.pending_state,
+        &starknet.block_context,
+        INITIAL_GAS_COST,
+    ) {
+        Ok(mut tx_info) => {
+            // the fee was paid on L1 when sending the message, so nothing is charged on L2
+            tx_info.actual_fee = l1_handler_transaction.paid_fee_on_l1;
+            starknet.consumed_l1_to_l2_message_nonces.insert(l1_handler_transaction.nonce);
+            let data_gas_consumed = estimations::data_gas_consumed(
+                state_before_txn.cache_mut(),
+                starknet.state.pending_state.cache_mut(),
+            );
+            starknet.handle_successful_transaction(
+                &transaction_hash,
+                Transaction::L1Handler(Box::new(l1_handler_transaction)),
+                tx_info,
+                data_gas_consumed,
+                false,
+            )?;
+        }
+        Err(tx_err) => {
+            let transaction_to_add = StarknetTransaction::create_rejected(
+                Transaction::L1Handler(Box::new(l1_handler_transaction)),
+                tx_err,
+            );
+
+            starknet.transactions.insert(&transaction_hash, transaction_to_add);
+            // Revert to previous pending state
+            starknet.state.pending_state = state_before_txn;
+        }
+    }
+
+    Ok(transaction_hash)
+}
@@ -0,0 +1,104 @@
+use starknet_rs_core::crypto::{compute_hash_on_elements, pedersen_hash};
+use starknet_rs_ff::FieldElement;
+use starknet_types::felt::Felt;
+
+use super::events::events_of_block;
+use super::storage_proof::PatriciaTrie;
+use super::Starknet;
+use crate::blocks::StarknetBlock;
+use crate::error::Result;
+use crate::transactions::Transaction;
+
+/// Height of the tries whose roots are the transaction and event commitments of blocks
+const COMMITMENT_TRIE_HEIGHT: usize = 64;
+
+/// Root of the trie holding, under the index of every transaction, the hash of the transaction
+/// combined with the hash of its signature: h(transaction_hash, h(signature)). Zero if there are no
+/// transactions.
+pub(crate) fn transaction_commitment(transactions: &[Transaction]) -> Felt {
+    commitment_of(transactions.iter().map(|transaction| {
+        let transaction_hash = transaction.get_hash().unwrap_or_default();
+        pedersen_hash(&transaction_hash.into(), &hash_of(transaction.signature()))
+    }))
+}
+
+/// Root of the trie holding, under the index of every event emitted by the transactions of
+/// `block`, the hash of the event: h(from_address, h(keys), h(data)). Zero if no events were
+/// emitted.
+pub(crate) fn event_commitment(starknet: &Starknet, block: &StarknetBlock) -> Result<Felt> {
+    Ok(commitment_of(events_of_block(starknet, block)?.into_iter().map(|event| {
+        compute_hash_on_elements(&[
+            Felt::from(event.from_address).into(),
+            hash_of(&event.keys),
+            hash_of(&event.data),
+        ])
+    })))
+}
+
+fn hash_of(felts: &[Felt]) -> FieldElement {
+    let elements: Vec<FieldElement> = felts.iter().map(|felt| (*felt).into()).collect();
+    compute_hash_on_elements(&elements)
+}
+
+fn commitment_of(leaves: impl Iterator<Item = FieldElement>) -> Felt {
+    let leaves =
+        leaves.enumerate().map(|(index, leaf)| (FieldElement::from(index), leaf)).collect();
+    PatriciaTrie::with_height(COMMITMENT_TRIE_HEIGHT, leaves).root_hash().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::crypto::{compute_hash_on_elements, pedersen_hash};
+    use starknet_rs_core::types::BlockId;
+    use starknet_rs_ff::FieldElement;
+    use starknet_types::felt::Felt;
+
+    use crate::starknet::{BlockGenerationOn, FeeUnit, Starknet, StarknetConfig};
+    use crate::utils::test_utils::{dummy_contract_address, starknet_config_for_test};
+
+    #[test]
+    fn commitments_of_empty_block_are_zero() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        starknet.create_block().unwrap();
+
+        let block = starknet.get_block(BlockId::Number(1)).unwrap();
+        assert_eq!(block.transaction_commitment(), Felt::from(0));
+        assert_eq!(block.event_commitment(), Felt::from(0));
+    }
+
+    #[tokio::test]
+    async fn transaction_commitment_of_two_transactions() {
+        let config = StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        starknet.mint(dummy_contract_address(), 1u32.into(), FeeUnit::Wei).await.unwrap();
+        starknet.mint(dummy_contract_address(), 2u32.into(), FeeUnit::Wei).await.unwrap();
+        starknet.create_block().unwrap();
+
+        let block = starknet.get_block(BlockId::Number(1)).unwrap();
+        let leaves: Vec<FieldElement> = block
+            .get_transactions()
+            .iter()
+            .map(|transaction| {
+                let signature: Vec<FieldElement> =
+                    transaction.signature().iter().map(|felt| (*felt).into()).collect();
+                pedersen_hash(
+                    &transaction.get_hash().unwrap().into(),
+                    &compute_hash_on_elements(&signature),
+                )
+            })
+            .collect();
+        assert_eq!(leaves.len(), 2);
+
+        // the keys 0 and 1 share all 63 leading bits, so the root is an edge of length 63 leading
+        // to the binary node of both leaves
+        let expected_root =
+            pedersen_hash(&pedersen_hash(&leaves[0], &leaves[1]), &FieldElement::ZERO)
+                + FieldElement::from(63u8);
+        assert_eq!(block.transaction_commitment(), expected_root.into());
+        // the minting transactions emit transfer events
+        assert_ne!(block.event_commitment(), Felt::from(0));
+    }
+}
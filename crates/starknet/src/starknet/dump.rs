@@ -0,0 +1,1004 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_rs_core::types::{BlockId, BlockTag};
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::{Cairo0ContractClass, ContractClass};
+use starknet_types::felt::Felt;
+use starknet_types::patricia_key::PatriciaKey;
+use starknet_types::traits::ToHexString;
+use tracing::error;
+
+use super::{BlockGenerationOn, Starknet};
+use crate::blocks::{StarknetBlock, StorageWrite};
+use crate::error::{Error, Result};
+use crate::traits::HashIdentified;
+use crate::transactions::declare_transaction::DeclareTransactionV1;
+use crate::transactions::declare_transaction_v2::DeclareTransactionV2;
+use crate::transactions::declare_transaction_v3::DeclareTransactionV3;
+use crate::transactions::deploy_account_transaction::DeployAccountTransaction;
+use crate::transactions::deploy_account_transaction_v3::DeployAccountTransactionV3;
+use crate::transactions::invoke_transaction::InvokeTransactionV1;
+use crate::transactions::invoke_transaction_v3::InvokeTransactionV3;
+use crate::transactions::l1_handler_transaction::L1HandlerTransaction;
+use crate::transactions::v3::{DataAvailabilityMode, ResourceBoundsMapping, V3Fields};
+use crate::transactions::{StarknetTransaction, Transaction};
+
+/// Version of Devnet that produced the dump
+pub const DUMP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// When Devnet automatically dumps its state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpOn {
+    /// On shutdown
+    Exit,
+    /// After every accepted transaction
+    Transaction,
+}
+
+impl FromStr for DumpOn {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "exit" => Ok(DumpOn::Exit),
+            "transaction" => Ok(DumpOn::Transaction),
+            other => Err(format!("Invalid dump mode: {other}; expected one of: exit, transaction")),
+        }
+    }
+}
+
+/// History of blocks and their transactions. State is reconstructed by replaying the transactions
+/// on a Devnet started with the same configuration (seed, accounts, balance) as the dumped one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dump {
+    pub version: String,
+    pub blocks: Vec<DumpedBlock>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DumpedBlock {
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub transactions: Vec<DumpedTransaction>,
+    /// Storage write generating the block, replayed after the transactions
+    #[serde(default)]
+    pub storage_write: Option<DumpedStorageWrite>,
+}
+
+/// Write to the storage of a contract made without a transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DumpedStorageWrite {
+    #[serde(with = "felt_hex")]
+    pub contract_address: Felt,
+    #[serde(with = "felt_hex")]
+    pub key: Felt,
+    #[serde(with = "felt_hex")]
+    pub value: Felt,
+}
+
+impl From<&StorageWrite> for DumpedStorageWrite {
+    fn from(storage_write: &StorageWrite) -> Self {
+        Self {
+            contract_address: Felt::from(storage_write.contract_address),
+            key: storage_write.key.to_felt(),
+            value: storage_write.value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DumpedTransaction {
+    DeclareV1 {
+        #[serde(with = "felt_hex")]
+        sender_address: Felt,
+        max_fee: u128,
+        #[serde(with = "felt_hex_vec")]
+        signature: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        nonce: Felt,
+        contract_class: serde_json::Value,
+        #[serde(with = "felt_hex")]
+        chain_id: Felt,
+    },
+    DeclareV2 {
+        #[serde(with = "felt_hex")]
+        sender_address: Felt,
+        max_fee: u128,
+        #[serde(with = "felt_hex_vec")]
+        signature: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        nonce: Felt,
+        sierra_contract_class: serde_json::Value,
+        #[serde(with = "felt_hex")]
+        compiled_class_hash: Felt,
+        #[serde(with = "felt_hex")]
+        chain_id: Felt,
+    },
+    DeclareV3 {
+        #[serde(with = "felt_hex")]
+        sender_address: Felt,
+        #[serde(with = "felt_hex_vec")]
+        signature: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        nonce: Felt,
+        sierra_contract_class: serde_json::Value,
+        #[serde(with = "felt_hex")]
+        compiled_class_hash: Felt,
+        v3_fields: DumpedV3Fields,
+        #[serde(with = "felt_hex_vec")]
+        account_deployment_data: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        chain_id: Felt,
+    },
+    DeployAccount {
+        max_fee: u128,
+        #[serde(with = "felt_hex_vec")]
+        signature: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        nonce: Felt,
+        #[serde(with = "felt_hex")]
+        class_hash: Felt,
+        #[serde(with = "felt_hex")]
+        contract_address_salt: Felt,
+        #[serde(with = "felt_hex_vec")]
+        constructor_calldata: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        version: Felt,
+        #[serde(with = "felt_hex")]
+        chain_id: Felt,
+    },
+    DeployAccountV3 {
+        #[serde(with = "felt_hex_vec")]
+        signature: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        nonce: Felt,
+        #[serde(with = "felt_hex")]
+        class_hash: Felt,
+        #[serde(with = "felt_hex")]
+        contract_address_salt: Felt,
+        #[serde(with = "felt_hex_vec")]
+        constructor_calldata: Vec<Felt>,
+        v3_fields: DumpedV3Fields,
+        #[serde(with = "felt_hex")]
+        chain_id: Felt,
+    },
+    Invoke {
+        #[serde(with = "felt_hex")]
+        sender_address: Felt,
+        max_fee: u128,
+        #[serde(with = "felt_hex_vec")]
+        signature: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        nonce: Felt,
+        #[serde(with = "felt_hex_vec")]
+        calldata: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        chain_id: Felt,
+        /// Set if the sender was impersonated, in which case the transaction is replayed without
+        /// validation as well
+        #[serde(default)]
+        skip_validation: bool,
+    },
+    InvokeV3 {
+        #[serde(with = "felt_hex")]
+        sender_address: Felt,
+        #[serde(with = "felt_hex_vec")]
+        signature: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        nonce: Felt,
+        #[serde(with = "felt_hex_vec")]
+        calldata: Vec<Felt>,
+        v3_fields: DumpedV3Fields,
+        #[serde(with = "felt_hex_vec")]
+        account_deployment_data: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        chain_id: Felt,
+        #[serde(default)]
+        skip_validation: bool,
+    },
+    L1Handler {
+        #[serde(with = "felt_hex")]
+        contract_address: Felt,
+        #[serde(with = "felt_hex")]
+        entry_point_selector: Felt,
+        #[serde(with = "felt_hex_vec")]
+        calldata: Vec<Felt>,
+        #[serde(with = "felt_hex")]
+        nonce: Felt,
+        paid_fee_on_l1: u128,
+        #[serde(with = "felt_hex")]
+        chain_id: Felt,
+    },
+}
+
+/// Fields of transactions of version 3, in the format in which they are dumped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DumpedV3Fields {
+    resource_bounds: ResourceBoundsMapping,
+    tip: u64,
+    #[serde(with = "felt_hex_vec")]
+    paymaster_data: Vec<Felt>,
+    nonce_data_availability_mode: DataAvailabilityMode,
+    fee_data_availability_mode: DataAvailabilityMode,
+}
+
+impl From<&V3Fields> for DumpedV3Fields {
+    fn from(v3_fields: &V3Fields) -> Self {
+        Self {
+            resource_bounds: v3_fields.resource_bounds,
+            tip: v3_fields.tip,
+            paymaster_data: v3_fields.paymaster_data.clone(),
+            nonce_data_availability_mode: v3_fields.nonce_data_availability_mode,
+            fee_data_availability_mode: v3_fields.fee_data_availability_mode,
+        }
+    }
+}
+
+impl From<DumpedV3Fields> for V3Fields {
+    fn from(dumped: DumpedV3Fields) -> Self {
+        Self {
+            resource_bounds: dumped.resource_bounds,
+            tip: dumped.tip,
+            paymaster_data: dumped.paymaster_data,
+            nonce_data_availability_mode: dumped.nonce_data_availability_mode,
+            fee_data_availability_mode: dumped.fee_data_availability_mode,
+        }
+    }
+}
+
+/// Sierra class of a declare transaction of version 2 or 3, serialized as JSON
+fn dumped_sierra_contract_class(contract_class: &ContractClass) -> Result<serde_json::Value> {
+    match contract_class {
+        ContractClass::Cairo1(sierra) => {
+            serde_json::to_value(sierra).map_err(|err| Error::DumpError { msg: err.to_string() })
+        }
+        ContractClass::Cairo0(_) => Err(Error::DumpError {
+            msg: "Declare transaction v2 or v3 should contain a Sierra contract class".to_string(),
+        }),
+    }
+}
+
+impl TryFrom<&StarknetTransaction> for DumpedTransaction {
+    type Error = Error;
+
+    fn try_from(starknet_transaction: &StarknetTransaction) -> Result<Self> {
+        let skip_validation = starknet_transaction.skip_validation;
+        let dumped = match starknet_transaction.get_transaction() {
+            Transaction::Declare(tx) => DumpedTransaction::DeclareV1 {
+                sender_address: Felt::from(tx.sender_address),
+                max_fee: tx.max_fee,
+                signature: tx.signature.clone(),
+                nonce: tx.nonce,
+                contract_class: match &tx.contract_class {
+                    ContractClass::Cairo0(Cairo0ContractClass::Json(json_value)) => {
+                        json_value.clone()
+                    }
+                    _ => {
+                        return Err(Error::DumpError {
+                            msg: "Only contract classes declared as JSON can be dumped".to_string(),
+                        });
+                    }
+                },
+                chain_id: tx.chain_id,
+            },
+            Transaction::DeclareV2(tx) => DumpedTransaction::DeclareV2 {
+                sender_address: Felt::from(tx.sender_address),
+                max_fee: tx.max_fee,
+                signature: tx.signature.clone(),
+                nonce: tx.nonce,
+                sierra_contract_class: dumped_sierra_contract_class(&tx.sierra_contract_class)?,
+                compiled_class_hash: tx.compiled_class_hash,
+                chain_id: tx.chain_id,
+            },
+            Transaction::DeclareV3(tx) => DumpedTransaction::DeclareV3 {
+                sender_address: Felt::from(tx.sender_address),
+                signature: tx.signature.clone(),
+                nonce: tx.nonce,
+                sierra_contract_class: dumped_sierra_contract_class(&tx.sierra_contract_class)?,
+                compiled_class_hash: tx.compiled_class_hash,
+                v3_fields: (&tx.v3_fields).into(),
+                account_deployment_data: tx.account_deployment_data.clone(),
+                chain_id: tx.chain_id,
+            },
+            Transaction::DeployAccount(tx) => DumpedTransaction::DeployAccount {
+                max_fee: tx.max_fee,
+                signature: tx.signature.clone(),
+                nonce: tx.nonce,
+                class_hash: tx.class_hash()?,
+                contract_address_salt: tx.contract_address_salt(),
+                constructor_calldata: tx.constructor_calldata(),
+                version: tx.version,
+                chain_id: tx.chain_id,
+            },
+            Transaction::DeployAccountV3(tx) => DumpedTransaction::DeployAccountV3 {
+                signature: tx.signature.clone(),
+                nonce: tx.nonce,
+                class_hash: tx.class_hash()?,
+                contract_address_salt: tx.contract_address_salt(),
+                constructor_calldata: tx.constructor_calldata(),
+                v3_fields: (&tx.v3_fields).into(),
+                chain_id: tx.chain_id,
+            },
+            Transaction::Invoke(tx) => DumpedTransaction::Invoke {
+                sender_address: Felt::from(tx.sender_address()?),
+                max_fee: tx.max_fee,
+                signature: tx.signature.clone(),
+                nonce: tx.nonce,
+                calldata: tx.calldata.clone(),
+                chain_id: tx.chain_id,
+                skip_validation,
+            },
+            Transaction::InvokeV3(tx) => DumpedTransaction::InvokeV3 {
+                sender_address: Felt::from(tx.sender_address()?),
+                signature: tx.signature.clone(),
+                nonce: tx.nonce,
+                calldata: tx.calldata.clone(),
+                v3_fields: (&tx.v3_fields).into(),
+                account_deployment_data: tx.account_deployment_data.clone(),
+                chain_id: tx.chain_id,
+                skip_validation,
+            },
+            Transaction::L1Handler(tx) => DumpedTransaction::L1Handler {
+                contract_address: Felt::from(tx.contract_address),
+                entry_point_selector: tx.entry_point_selector,
+                calldata: tx.calldata.clone(),
+                nonce: tx.nonce,
+                paid_fee_on_l1: tx.paid_fee_on_l1,
+                chain_id: tx.chain_id,
+            },
+        };
+
+        Ok(dumped)
+    }
+}
+
+impl Starknet {
+    /// Collects all blocks after the genesis block, which is created on startup, ordered by block
+    /// number, with the transactions needed to reconstruct them
+    pub fn dump(&self) -> Result<Dump> {
+        let mut block_numbers: Vec<&BlockNumber> = self
+            .blocks
+            .num_to_block
+            .keys()
+            .filter(|block_number| block_number.0 != self.config.initial_block_number)
+            .collect();
+        block_numbers.sort();
+
+        let blocks = block_numbers
+            .into_iter()
+            .filter_map(|block_number| self.blocks.num_to_block.get(block_number))
+            .map(|block| self.dumped_block(block))
+            .collect::<Result<_>>()?;
+
+        Ok(Dump { version: DUMP_VERSION.to_string(), blocks })
+    }
+
+    /// Collects the transactions of `block` along with how they were executed
+    pub(crate) fn dumped_block(&self, block: &StarknetBlock) -> Result<DumpedBlock> {
+        let transactions = block
+            .get_transactions()
+            .iter()
+            .map(|transaction| {
+                let transaction_hash = transaction.get_hash().ok_or(Error::TransactionNotFound)?;
+                let starknet_transaction = self
+                    .transactions
+                    .get_by_hash(transaction_hash)
+                    .ok_or(Error::TransactionNotFound)?;
+                DumpedTransaction::try_from(starknet_transaction)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(DumpedBlock {
+            block_number: block.block_number().0,
+            timestamp: block.timestamp().0,
+            transactions,
+            storage_write: block.storage_write().map(DumpedStorageWrite::from),
+        })
+    }
+
+    /// Serializes the dump into a JSON file at `path`
+    pub fn dump_to_file(&self, path: &str) -> Result<()> {
+        let dump = self.dump()?;
+        let file = std::fs::File::create(path)
+            .map_err(|err| Error::DumpError { msg: format!("Cannot create file {path}: {err}") })?;
+        serde_json::to_writer(file, &dump).map_err(|err| Error::DumpError { msg: err.to_string() })
+    }
+
+    /// Dumps to the configured path if dumping after every transaction is enabled. Failures are
+    /// only logged, since the transaction itself has already been accepted.
+    pub(crate) fn dump_on_transaction(&self) {
+        if let (Some(DumpOn::Transaction), Some(path)) =
+            (self.config.dump_on, self.config.dump_path.as_ref())
+        {
+            if let Err(err) = self.dump_to_file(path) {
+                error!("Failed to dump state to {path}: {err}");
+            }
+        }
+    }
+
+    /// Reads a dump from the file at `path`, checking that it was produced by a compatible version
+    pub fn read_dump_file(path: &str) -> Result<Dump> {
+        let dump_str = std::fs::read_to_string(path)
+            .map_err(|err| Error::ReadFileError { source: err, path: path.to_string() })?;
+        let dump_json: serde_json::Value = serde_json::from_str(&dump_str)
+            .map_err(|err| Error::DumpError { msg: format!("Invalid dump file {path}: {err}") })?;
+
+        // the version is checked before the rest, whose format may differ between versions
+        let version =
+            dump_json.get("version").and_then(|version| version.as_str()).unwrap_or("unknown");
+        if version != DUMP_VERSION {
+            return Err(Error::IncompatibleDumpVersion {
+                found: version.to_string(),
+                expected: DUMP_VERSION.to_string(),
+            });
+        }
+
+        serde_json::from_value(dump_json)
+            .map_err(|err| Error::DumpError { msg: format!("Invalid dump file {path}: {err}") })
+    }
+
+    /// Loads the dump from the file at `path`. If Devnet already contains blocks besides the
+    /// genesis block, loading fails, unless `force` is set, in which case Devnet is restarted
+    /// before loading.
+    pub fn load_from_file(&mut self, path: &str, force: bool) -> Result<()> {
+        let dump = Self::read_dump_file(path)?;
+
+        if self.blocks.num_to_block.len() > 1 {
+            if !force {
+                return Err(Error::DumpError {
+                    msg: "Devnet already contains blocks; loading requires the `force` flag"
+                        .to_string(),
+                });
+            }
+
+            self.restart()?;
+        }
+
+        self.load(dump)
+    }
+
+    /// Replays the dumped transactions and storage writes, restoring the timestamp of every block
+    pub fn load(&mut self, dump: Dump) -> Result<()> {
+        for block in dump.blocks {
+            self.set_pending_block_timestamp(block.timestamp)?;
+
+            // in demand mode, replayed transactions are collected until the block is created
+            let create_block = block.transactions.is_empty()
+                || self.config.block_generation_on == BlockGenerationOn::Demand;
+
+            for transaction in block.transactions {
+                self.replay_transaction(transaction)?;
+            }
+
+            match block.storage_write {
+                // the write creates the block
+                Some(storage_write) => {
+                    self.set_storage_at(
+                        ContractAddress::new(storage_write.contract_address)?,
+                        PatriciaKey::new(storage_write.key)?,
+                        storage_write.value,
+                    )?;
+                }
+                None if create_block => {
+                    self.create_block()?;
+                }
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn replay_transaction(&mut self, transaction: DumpedTransaction) -> Result<()> {
+        let transaction_hash = match transaction {
+            DumpedTransaction::DeclareV1 {
+                sender_address,
+                max_fee,
+                signature,
+                nonce,
+                contract_class,
+                chain_id,
+            } => {
+                let contract_class =
+                    ContractClass::cairo_0_from_json_str(&contract_class.to_string())?;
+                self.add_declare_transaction_v1(DeclareTransactionV1::new(
+                    ContractAddress::new(sender_address)?,
+                    max_fee,
+                    signature,
+                    nonce,
+                    contract_class,
+                    chain_id,
+                )?)?
+                .0
+            }
+            DumpedTransaction::DeclareV2 {
+                sender_address,
+                max_fee,
+                signature,
+                nonce,
+                sierra_contract_class,
+                compiled_class_hash,
+                chain_id,
+            } => {
+                let sierra_contract_class = ContractClass::cairo_1_from_sierra_json_str(
+                    &sierra_contract_class.to_string(),
+                )?;
+                self.add_declare_transaction_v2(DeclareTransactionV2::new(
+                    sierra_contract_class,
+                    compiled_class_hash,
+                    ContractAddress::new(sender_address)?,
+                    max_fee,
+                    signature,
+                    nonce,
+                    chain_id,
+                )?)?
+                .0
+            }
+            DumpedTransaction::DeclareV3 {
+                sender_address,
+                signature,
+                nonce,
+                sierra_contract_class,
+                compiled_class_hash,
+                v3_fields,
+                account_deployment_data,
+                chain_id,
+            } => {
+                let sierra_contract_class = ContractClass::cairo_1_from_sierra_json_str(
+                    &sierra_contract_class.to_string(),
+                )?;
+                self.add_declare_transaction_v3(DeclareTransactionV3::new(
+                    sierra_contract_class,
+                    compiled_class_hash,
+                    ContractAddress::new(sender_address)?,
+                    signature,
+                    nonce,
+                    chain_id,
+                    v3_fields.into(),
+                    account_deployment_data,
+                )?)?
+                .0
+            }
+            DumpedTransaction::DeployAccount {
+                max_fee,
+                signature,
+                nonce,
+                class_hash,
+                contract_address_salt,
+                constructor_calldata,
+                version,
+                chain_id,
+            } => {
+                self.add_deploy_account_transaction(DeployAccountTransaction::new(
+                    constructor_calldata,
+                    max_fee,
+                    signature,
+                    nonce,
+                    class_hash,
+                    contract_address_salt,
+                    chain_id,
+                    version,
+                )?)?
+                .0
+            }
+            DumpedTransaction::DeployAccountV3 {
+                signature,
+                nonce,
+                class_hash,
+                contract_address_salt,
+                constructor_calldata,
+                v3_fields,
+                chain_id,
+            } => {
+                self.add_deploy_account_transaction_v3(DeployAccountTransactionV3::new(
+                    constructor_calldata,
+                    signature,
+                    nonce,
+                    class_hash,
+                    contract_address_salt,
+                    chain_id,
+                    v3_fields.into(),
+                )?)?
+                .0
+            }
+            DumpedTransaction::Invoke {
+                sender_address,
+                max_fee,
+                signature,
+                nonce,
+                calldata,
+                chain_id,
+                skip_validation,
+            } => {
+                let sender_address = ContractAddress::new(sender_address)?;
+                let transaction = InvokeTransactionV1::new(
+                    sender_address,
+                    max_fee,
+                    signature,
+                    nonce,
+                    calldata,
+                    chain_id,
+                )?;
+                self.with_impersonation(sender_address, skip_validation, |starknet| {
+                    starknet.add_invoke_transaction_v1(transaction)
+                })?
+            }
+            DumpedTransaction::InvokeV3 {
+                sender_address,
+                signature,
+                nonce,
+                calldata,
+                v3_fields,
+                account_deployment_data,
+                chain_id,
+                skip_validation,
+            } => {
+                let sender_address = ContractAddress::new(sender_address)?;
+                let transaction = InvokeTransactionV3::new(
+                    sender_address,
+                    signature,
+                    nonce,
+                    calldata,
+                    chain_id,
+                    v3_fields.into(),
+                    account_deployment_data,
+                )?;
+                self.with_impersonation(sender_address, skip_validation, |starknet| {
+                    starknet.add_invoke_transaction_v3(transaction)
+                })?
+            }
+            DumpedTransaction::L1Handler {
+                contract_address,
+                entry_point_selector,
+                calldata,
+                nonce,
+                paid_fee_on_l1,
+                chain_id,
+            } => self.add_l1_handler_transaction(L1HandlerTransaction::new(
+                ContractAddress::new(contract_address)?,
+                entry_point_selector,
+                calldata,
+                nonce,
+                paid_fee_on_l1,
+                chain_id,
+            )?)?,
+        };
+
+        // dumped transactions were accepted, so they should be accepted again when replayed
+        let block_tag = match self.config.block_generation_on {
+            BlockGenerationOn::Transaction => BlockTag::Latest,
+            BlockGenerationOn::Demand => BlockTag::Pending,
+        };
+        let block = self.get_block(BlockId::Tag(block_tag))?;
+        if !block.get_transactions().iter().any(|t| t.get_hash() == Some(transaction_hash)) {
+            return Err(Error::DumpError {
+                msg: format!(
+                    "Replayed transaction {} was not accepted",
+                    transaction_hash.to_prefixed_hex_str()
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Runs `replay` with `sender_address` impersonated exactly if `skip_validation` is set, so
+    /// that a replayed invoke is validated only if it was validated originally. Impersonation is
+    /// restored afterwards.
+    fn with_impersonation<T>(
+        &mut self,
+        sender_address: ContractAddress,
+        skip_validation: bool,
+        replay: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let auto_impersonate = std::mem::replace(&mut self.config.auto_impersonate, false);
+        let was_impersonated = if skip_validation {
+            !self.impersonated_accounts.insert(sender_address)
+        } else {
+            self.impersonated_accounts.remove(&sender_address)
+        };
+
+        let result = replay(self);
+
+        self.config.auto_impersonate = auto_impersonate;
+        if was_impersonated {
+            self.impersonated_accounts.insert(sender_address);
+        } else {
+            self.impersonated_accounts.remove(&sender_address);
+        }
+
+        result
+    }
+}
+
+mod felt_hex {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::ToHexString;
+
+    pub(super) fn serialize<S: Serializer>(felt: &Felt, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&felt.to_prefixed_hex_str())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Felt, D::Error> {
+        let hex_str = String::deserialize(deserializer)?;
+        Felt::from_prefixed_hex_str(&hex_str).map_err(serde::de::Error::custom)
+    }
+}
+
+mod felt_hex_vec {
+    use serde::ser::SerializeSeq;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::ToHexString;
+
+    pub(super) fn serialize<S: Serializer>(
+        felts: &[Felt],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(felts.len()))?;
+        for felt in felts {
+            seq.serialize_element(&felt.to_prefixed_hex_str())?;
+        }
+        seq.end()
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Felt>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|hex_str| Felt::from_prefixed_hex_str(hex_str).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_in_rust::definitions::block_context::StarknetChainId;
+    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_storage_key::ContractStorageKey;
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::HashProducer;
+
+    use super::{Dump, DumpOn, DUMP_VERSION};
+    use crate::account::Account;
+    use crate::constants::ERC20_CONTRACT_ADDRESS;
+    use crate::error::Error;
+    use crate::starknet::{FeeUnit, Starknet};
+    use crate::traits::{Accounted, Deployed, StateExtractor};
+    use crate::transactions::declare_transaction::DeclareTransactionV1;
+    use crate::transactions::invoke_transaction::InvokeTransactionV1;
+    use crate::utils::get_storage_var_address;
+    use crate::utils::test_utils::{
+        cairo_0_account_without_validations, dummy_cairo_0_contract_class, dummy_contract_address,
+        dummy_felt, starknet_config_for_test,
+    };
+
+    fn balance_storage_key(address: ContractAddress) -> ContractStorageKey {
+        ContractStorageKey::new(
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap(),
+            get_storage_var_address("ERC20_balances", &[Felt::from(address)]).unwrap(),
+        )
+    }
+
+    #[test]
+    fn dump_mode_is_parsed() {
+        assert_eq!("exit".parse::<DumpOn>().unwrap(), DumpOn::Exit);
+        assert_eq!("transaction".parse::<DumpOn>().unwrap(), DumpOn::Transaction);
+        assert!("block".parse::<DumpOn>().is_err());
+    }
+
+    #[tokio::test]
+    async fn dumped_state_is_restored_after_loading() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+        let address = dummy_contract_address();
+        starknet.mint(address, 42u32.into(), FeeUnit::Wei).await.unwrap();
+        starknet.create_block().unwrap();
+        starknet.mint(address, 100u32.into(), FeeUnit::Wei).await.unwrap();
+
+        let dump = starknet.dump().unwrap();
+        assert_eq!(dump.version, DUMP_VERSION);
+        assert_eq!(dump.blocks.len(), 3);
+        assert!(dump.blocks[1].transactions.is_empty());
+
+        // dump goes through serialization, as it would when written to a file
+        let serialized_dump = serde_json::to_string(&dump).unwrap();
+        let mut loaded_starknet = Starknet::new(&config).unwrap();
+        loaded_starknet.load(serde_json::from_str::<Dump>(&serialized_dump).unwrap()).unwrap();
+
+        let storage_key = balance_storage_key(address);
+        assert_eq!(
+            loaded_starknet.state.get_storage(storage_key).unwrap(),
+            starknet.state.get_storage(storage_key).unwrap()
+        );
+        assert_eq!(loaded_starknet.state.get_storage(storage_key).unwrap(), Felt::from(142));
+
+        let latest = BlockId::Tag(BlockTag::Latest);
+        let original_block = starknet.get_block(latest).unwrap();
+        let loaded_block = loaded_starknet.get_block(latest).unwrap();
+        assert_eq!(loaded_block.block_number(), original_block.block_number());
+        assert_eq!(loaded_block.timestamp(), original_block.timestamp());
+    }
+
+    #[tokio::test]
+    async fn storage_write_is_restored_after_loading() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+        let address = dummy_contract_address();
+        let erc20_address =
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap();
+        let balance_key =
+            get_storage_var_address("ERC20_balances", &[Felt::from(address)]).unwrap();
+        starknet.set_storage_at(erc20_address, balance_key, Felt::from(13)).unwrap();
+        // transactions of later blocks see the written value
+        starknet.mint(address, 100u32.into(), FeeUnit::Wei).await.unwrap();
+
+        let dump = starknet.dump().unwrap();
+        assert!(dump.blocks[0].storage_write.is_some());
+        let serialized_dump = serde_json::to_string(&dump).unwrap();
+        let mut loaded_starknet = Starknet::new(&config).unwrap();
+        loaded_starknet.load(serde_json::from_str::<Dump>(&serialized_dump).unwrap()).unwrap();
+
+        assert_eq!(
+            loaded_starknet.state.get_storage(balance_storage_key(address)).unwrap(),
+            Felt::from(113)
+        );
+        let latest = BlockId::Tag(BlockTag::Latest);
+        assert_eq!(
+            loaded_starknet.get_block(latest).unwrap().block_hash(),
+            starknet.get_block(latest).unwrap().block_hash()
+        );
+    }
+
+    #[test]
+    fn invoke_of_impersonated_account_is_replayed_without_validation() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+        let sender = starknet.get_predeployed_accounts()[0].get_address();
+        let recipient = dummy_contract_address();
+
+        // the transaction is not signed, so it is accepted only without validation
+        starknet.impersonate_account(sender);
+        let transfer = InvokeTransactionV1::new(
+            sender,
+            10000,
+            vec![],
+            Felt::from(0),
+            vec![
+                Felt::from(1), // number of calls
+                Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap(),
+                get_selector_from_name("transfer").unwrap().into(),
+                Felt::from(0), // data offset
+                Felt::from(3), // data length
+                Felt::from(3), // calldata length
+                Felt::from(recipient),
+                Felt::from(7), // amount low
+                Felt::from(0), // amount high
+            ],
+            starknet.chain_id().to_felt(),
+        )
+        .unwrap();
+        starknet.add_invoke_transaction_v1(transfer).unwrap();
+        starknet.stop_impersonating_account(&sender);
+
+        let serialized_dump = serde_json::to_string(&starknet.dump().unwrap()).unwrap();
+        let mut loaded_starknet = Starknet::new(&config).unwrap();
+        loaded_starknet.load(serde_json::from_str::<Dump>(&serialized_dump).unwrap()).unwrap();
+
+        let storage_key = balance_storage_key(recipient);
+        assert_eq!(loaded_starknet.state.get_storage(storage_key).unwrap(), Felt::from(7));
+        // the sender is impersonated only while its transaction is replayed
+        assert!(!loaded_starknet.is_account_impersonated(&sender));
+    }
+
+    fn setup_with_account_without_validations() -> (Starknet, ContractAddress) {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let contract_class = cairo_0_account_without_validations();
+
+        let account = Account::new(
+            Felt::from(1_000_000),
+            dummy_felt(),
+            dummy_felt(),
+            contract_class.generate_hash().unwrap(),
+            contract_class,
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        account.deploy(&mut starknet.state).unwrap();
+        account.set_initial_balance(&mut starknet.state).unwrap();
+        starknet.state.synchronize_states();
+
+        (starknet, account.get_address())
+    }
+
+    #[test]
+    fn declared_class_is_queryable_after_loading_from_file() {
+        let (mut starknet, sender) = setup_with_account_without_validations();
+        let declare_transaction = DeclareTransactionV1::new(
+            sender,
+            10000,
+            vec![],
+            Felt::from(0),
+            dummy_cairo_0_contract_class(),
+            StarknetChainId::TestNet.to_felt().into(),
+        )
+        .unwrap();
+        let (transaction_hash, class_hash) =
+            starknet.add_declare_transaction_v1(declare_transaction).unwrap();
+
+        let path = std::env::temp_dir().join("devnet_declared_class_is_queryable.json");
+        let path = path.to_str().unwrap();
+        starknet.dump_to_file(path).unwrap();
+
+        let (mut loaded_starknet, _) = setup_with_account_without_validations();
+        loaded_starknet.load_from_file(path, false).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert!(loaded_starknet.state.is_contract_declared(&class_hash));
+        let latest = BlockId::Tag(BlockTag::Latest);
+        let original_block = starknet.get_block(latest).unwrap();
+        let loaded_block = loaded_starknet.get_block(latest).unwrap();
+        assert_eq!(loaded_block.block_hash(), original_block.block_hash());
+        assert_eq!(loaded_block.get_transactions()[0].get_hash(), Some(transaction_hash));
+    }
+
+    #[test]
+    fn loading_dump_of_incompatible_version_fails() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let path =
+            concat!(env!("CARGO_MANIFEST_DIR"), "/test_artifacts/dumps/incompatible_version.json");
+
+        match starknet.load_from_file(path, false) {
+            Err(Error::IncompatibleDumpVersion { found, expected }) => {
+                assert_eq!(found, "0.0.1");
+                assert_eq!(expected, DUMP_VERSION);
+            }
+            _ => panic!("Expected failure due to incompatible dump version"),
+        }
+    }
+
+    #[tokio::test]
+    async fn loading_into_devnet_with_blocks_requires_force() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        starknet.mint(dummy_contract_address(), 42u32.into(), FeeUnit::Wei).await.unwrap();
+
+        let path = std::env::temp_dir().join("devnet_loading_requires_force.json");
+        let path = path.to_str().unwrap();
+        starknet.dump_to_file(path).unwrap();
+
+        assert!(matches!(starknet.load_from_file(path, false), Err(Error::DumpError { .. })));
+
+        starknet.load_from_file(path, true).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(starknet.blocks.num_to_block.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn dump_is_written_to_file() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+        starknet.mint(dummy_contract_address(), 42u32.into(), FeeUnit::Wei).await.unwrap();
+
+        let path = std::env::temp_dir().join("devnet_dump_is_written_to_file.json");
+        let path = path.to_str().unwrap();
+        starknet.dump_to_file(path).unwrap();
+
+        let dump: Dump = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(dump.blocks.len(), 1);
+        assert_eq!(dump.blocks[0].transactions.len(), 1);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
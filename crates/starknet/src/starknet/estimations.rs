@@ -0,0 +1,435 @@
+use starknet_in_rust::execution::TransactionExecutionInfo;
+use starknet_in_rust::state::state_cache::StateCache;
+use starknet_in_rust::transaction::error::TransactionError;
+use starknet_rs_core::types::{BlockId, BlockTag};
+
+use super::parallel_execution::StateKeys;
+use super::{simulations, FeeUnit, Starknet};
+use crate::error::{Error, Result};
+use crate::transactions::Transaction;
+
+/// Resource name under which starknet_in_rust reports the L1 gas used by a transaction
+const L1_GAS_USAGE: &str = "l1_gas_usage";
+
+/// Data gas needed to publish a felt of a state diff as part of a blob
+pub(crate) const DATA_GAS_PER_FELT: u128 = 32;
+
+/// Data gas needed to publish the state diff between the caches `previous` and `current` of a state
+pub(crate) fn data_gas_consumed(previous: &mut StateCache, current: &mut StateCache) -> u128 {
+    StateKeys::written_since(previous, current).data_availability_felts() * DATA_GAS_PER_FELT
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeEstimation {
+    /// The actual fee of the transaction expressed in L1 gas, rounded up, so that for fees paid in
+    /// STRK it can be used as the max amount of L1 gas of a transaction of version 3
+    pub gas_consumed: u128,
+    pub gas_price: u128,
+    /// Data gas needed to publish the state diff of the transaction as a blob
+    pub data_gas_consumed: u128,
+    pub data_gas_price: u128,
+    /// Sum of the consumed L1 gas and data gas, weighted by their prices
+    pub overall_fee: u128,
+    /// Unit of the gas prices and of the overall fee
+    pub unit: FeeUnit,
+}
+
+impl FeeEstimation {
+    pub(crate) fn new(
+        execution_info: &TransactionExecutionInfo,
+        data_gas_consumed: u128,
+        gas_price: u128,
+        data_gas_price: u128,
+        unit: FeeUnit,
+    ) -> Self {
+        let gas_consumed = match gas_price {
+            0 => execution_info
+                .actual_resources
+                .get(L1_GAS_USAGE)
+                .map_or(0, |gas_usage| *gas_usage as u128),
+            // rounded up, so that the amount covers the fee at the given price
+            _ => (execution_info.actual_fee + gas_price - 1) / gas_price,
+        };
+
+        Self {
+            gas_consumed,
+            gas_price,
+            data_gas_consumed,
+            data_gas_price,
+            overall_fee: gas_consumed * gas_price + data_gas_consumed * data_gas_price,
+            unit,
+        }
+    }
+}
+
+/// Executes `transactions` one after another on a copy of the state at `block_id`, so that every
+/// transaction sees the changes of the previous ones, without affecting Devnet's state. Fees are
+/// neither checked against the max fee nor transferred. If `skip_validate` is set, the validation
+/// entry point of the sender is not executed.
+pub fn estimate_fee(
+    starknet: &Starknet,
+    block_id: BlockId,
+    transactions: &[Transaction],
+    skip_validate: bool,
+) -> Result<Vec<FeeEstimation>> {
+    simulations::execute_sequentially(starknet, block_id, transactions, skip_validate, true)?
+        .iter()
+        .zip(transactions)
+        .map(|((execution_info, data_gas_consumed), transaction)| {
+            let unit = transaction.fee_unit();
+            let (gas_price, data_gas_price) = starknet.gas_prices_at(&block_id, unit)?;
+            Ok(FeeEstimation::new(
+                execution_info,
+                *data_gas_consumed,
+                gas_price,
+                data_gas_price,
+                unit,
+            ))
+        })
+        .collect()
+}
+
+/// Rejects a transaction of version 3 whose resource bounds don't cover its estimated cost, either
+/// because the max price of L1 gas is below the current one, or because the max amount of it is
+/// below the estimated amount. Transactions whose estimation fails are not rejected here, so that
+/// their execution reports the reason of the failure. Nothing is checked if fee charging is
+/// disabled.
+pub(crate) fn check_resource_bounds(starknet: &Starknet, transaction: &Transaction) -> Result<()> {
+    let l1_gas = match transaction.v3_fields() {
+        Some(v3_fields) if !starknet.config.disable_fee_charge => v3_fields.resource_bounds.l1_gas,
+        _ => return Ok(()),
+    };
+
+    let estimation = match estimate_fee(
+        starknet,
+        BlockId::Tag(BlockTag::Pending),
+        std::slice::from_ref(transaction),
+        false,
+    ) {
+        Ok(mut estimations) => estimations.remove(0),
+        Err(_) => return Ok(()),
+    };
+
+    if estimation.gas_price > l1_gas.max_price_per_unit {
+        return Err(Error::InsufficientResources {
+            msg: format!(
+                "max price per unit of L1 gas {} is below the current gas price {}",
+                l1_gas.max_price_per_unit, estimation.gas_price
+            ),
+        });
+    }
+    if estimation.gas_consumed > l1_gas.max_amount as u128 {
+        return Err(Error::InsufficientResources {
+            msg: format!(
+                "max amount of L1 gas {} is below the estimated amount {}",
+                l1_gas.max_amount, estimation.gas_consumed
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Rejects an executed transaction whose actual fee isn't covered by its max fee or, for a
+/// transaction of version 3, by its resource bounds at `gas_price`. Returns the execution info of
+/// the transaction otherwise, so that the check can be chained to its execution. Nothing is
+/// checked if the fee is not charged, nor for L1 handler transactions, whose fee is paid on L1.
+pub(crate) fn check_actual_fee(
+    transaction: &Transaction,
+    execution_info: TransactionExecutionInfo,
+    gas_price: u128,
+    skip_fee_charge: bool,
+) -> std::result::Result<TransactionExecutionInfo, TransactionError> {
+    if skip_fee_charge || matches!(transaction, Transaction::L1Handler(_)) {
+        return Ok(execution_info);
+    }
+
+    let actual_fee = execution_info.actual_fee;
+    match transaction.v3_fields() {
+        Some(v3_fields) => {
+            let l1_gas = &v3_fields.resource_bounds.l1_gas;
+            if gas_price > l1_gas.max_price_per_unit {
+                return Err(TransactionError::FeeError(format!(
+                    "Insufficient resource bounds: max price per unit of L1 gas {} is below the \
+                     gas price {gas_price}",
+                    l1_gas.max_price_per_unit
+                )));
+            }
+            // rounded up, as in the estimation of the amount
+            let gas_consumed = match gas_price {
+                0 => 0,
+                _ => (actual_fee + gas_price - 1) / gas_price,
+            };
+            if gas_consumed > l1_gas.max_amount as u128 {
+                return Err(TransactionError::FeeError(format!(
+                    "Insufficient resource bounds: max amount of L1 gas {} is below the consumed \
+                     amount {gas_consumed}",
+                    l1_gas.max_amount
+                )));
+            }
+        }
+        None if actual_fee > transaction.max_fee() => {
+            return Err(TransactionError::FeeError(format!(
+                "Insufficient max fee: actual fee {actual_fee} exceeds max fee {}",
+                transaction.max_fee()
+            )));
+        }
+        None => {}
+    }
+
+    Ok(execution_info)
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_in_rust::definitions::block_context::StarknetChainId;
+    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::HashProducer;
+
+    use crate::account::Account;
+    use crate::constants::{ERC20_CONTRACT_ADDRESS, UDC_CONTRACT_ADDRESS};
+    use crate::error::Error;
+    use crate::starknet::Starknet;
+    use crate::traits::{Accounted, Deployed, StateChanger, StateExtractor};
+    use crate::transactions::declare_transaction::DeclareTransactionV1;
+    use crate::transactions::deploy_account_transaction::DeployAccountTransaction;
+    use crate::transactions::invoke_transaction::InvokeTransactionV1;
+    use crate::transactions::Transaction;
+    use crate::utils::test_utils::{
+        cairo_0_account_without_validations, dummy_cairo_0_contract_class, dummy_felt,
+        starknet_config_for_test,
+    };
+
+    fn setup() -> (Starknet, ContractAddress) {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let contract_class = cairo_0_account_without_validations();
+
+        let account = Account::new(
+            Felt::from(1_000_000),
+            dummy_felt(),
+            dummy_felt(),
+            contract_class.generate_hash().unwrap(),
+            contract_class,
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        account.deploy(&mut starknet.state).unwrap();
+        account.set_initial_balance(&mut starknet.state).unwrap();
+        starknet.state.synchronize_states();
+
+        (starknet, account.get_address())
+    }
+
+    /// Invokes `deployContract` of the UDC, deploying an instance of `class_hash`
+    fn deploy_through_udc(sender: ContractAddress, class_hash: Felt, nonce: u128) -> Transaction {
+        let calldata = vec![
+            Felt::from_prefixed_hex_str(UDC_CONTRACT_ADDRESS).unwrap(), // contract address
+            get_selector_from_name("deployContract").unwrap().into(),   // function selector
+            Felt::from(4),                                              // calldata len
+            class_hash,                                                 // class hash
+            Felt::from(42),                                             // salt
+            Felt::from(0),                                              // unique
+            Felt::from(0),                                              // constructor calldata len
+        ];
+
+        Transaction::Invoke(Box::new(
+            InvokeTransactionV1::new(
+                sender,
+                10000,
+                vec![],
+                Felt::from(nonce),
+                calldata,
+                StarknetChainId::TestNet.to_felt().into(),
+            )
+            .unwrap(),
+        ))
+    }
+
+    #[test]
+    fn estimates_see_effects_of_previous_transactions() {
+        let (mut starknet, sender) = setup();
+        let contract_class = dummy_cairo_0_contract_class();
+        let class_hash = contract_class.generate_hash().unwrap();
+
+        let declare_transaction = Transaction::Declare(Box::new(
+            DeclareTransactionV1::new(
+                sender,
+                10000,
+                vec![],
+                Felt::from(0),
+                contract_class,
+                StarknetChainId::TestNet.to_felt().into(),
+            )
+            .unwrap(),
+        ));
+        let transactions = [declare_transaction, deploy_through_udc(sender, class_hash, 1)];
+
+        let estimations =
+            starknet.estimate_fee(BlockId::Tag(BlockTag::Latest), &transactions, false).unwrap();
+        assert_eq!(estimations.len(), 2);
+        for estimation in estimations {
+            assert_eq!(estimation.gas_price, starknet.block_context.block_info().gas_price as u128);
+        }
+
+        // estimation doesn't change the state
+        assert!(!starknet.state.is_contract_declared(&class_hash));
+    }
+
+    #[test]
+    fn estimated_fee_scales_with_gas_price() {
+        let (mut starknet, sender) = setup();
+        let declare_transaction = Transaction::Declare(Box::new(
+            DeclareTransactionV1::new(
+                sender,
+                10000,
+                vec![],
+                Felt::from(0),
+                dummy_cairo_0_contract_class(),
+                StarknetChainId::TestNet.to_felt().into(),
+            )
+            .unwrap(),
+        ));
+        let transactions = [declare_transaction];
+
+        starknet.set_gas_price(1, 1, false).unwrap();
+        let low_estimation = starknet
+            .estimate_fee(BlockId::Tag(BlockTag::Latest), &transactions, false)
+            .unwrap()
+            .remove(0);
+
+        starknet.set_gas_price(1000, 2000, false).unwrap();
+        let high_estimation = starknet
+            .estimate_fee(BlockId::Tag(BlockTag::Latest), &transactions, false)
+            .unwrap()
+            .remove(0);
+
+        assert_eq!(high_estimation.gas_price, 1000);
+        assert_eq!(high_estimation.data_gas_price, 2000);
+        assert_eq!(high_estimation.gas_consumed, low_estimation.gas_consumed);
+        // the fee is proportional to the gas price, up to rounding
+        assert!(low_estimation.overall_fee > 0);
+        assert!(high_estimation.overall_fee >= 100 * low_estimation.overall_fee);
+    }
+
+    /// Invokes `selector` of the fee token with `calldata`
+    fn invoke_fee_token(
+        sender: ContractAddress,
+        selector: &str,
+        calldata: Vec<Felt>,
+        nonce: u128,
+    ) -> Transaction {
+        let mut execute_calldata = vec![
+            Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap(), // contract address
+            get_selector_from_name(selector).unwrap().into(),             // function selector
+            Felt::from(calldata.len() as u128),                           // calldata len
+        ];
+        execute_calldata.extend(calldata);
+
+        Transaction::Invoke(Box::new(
+            InvokeTransactionV1::new(
+                sender,
+                10000,
+                vec![],
+                Felt::from(nonce),
+                execute_calldata,
+                StarknetChainId::TestNet.to_felt().into(),
+            )
+            .unwrap(),
+        ))
+    }
+
+    #[test]
+    fn data_gas_reflects_state_diff_of_each_transaction() {
+        let (mut starknet, sender) = setup();
+        starknet.set_gas_price(10, 20, false).unwrap();
+
+        // only the nonce of the sender changes
+        let balance_query = invoke_fee_token(sender, "balanceOf", vec![sender.into()], 0);
+        // the balances of the sender and of the recipient change as well
+        let transfer = invoke_fee_token(
+            sender,
+            "transfer",
+            vec![Felt::from(0x123), Felt::from(100), Felt::from(0)],
+            1,
+        );
+
+        let estimations = starknet
+            .estimate_fee(BlockId::Tag(BlockTag::Latest), &[balance_query, transfer], false)
+            .unwrap();
+        assert_eq!(estimations.len(), 2);
+
+        // address and nonce word of the sender
+        assert_eq!(estimations[0].data_gas_consumed, 2 * super::DATA_GAS_PER_FELT);
+        assert!(estimations[1].data_gas_consumed > estimations[0].data_gas_consumed);
+        for estimation in estimations {
+            assert_eq!(estimation.data_gas_price, 20);
+            assert_eq!(
+                estimation.overall_fee,
+                estimation.gas_consumed * estimation.gas_price
+                    + estimation.data_gas_consumed * estimation.data_gas_price
+            );
+        }
+    }
+
+    #[test]
+    fn estimate_fails_with_index_of_failed_transaction() {
+        let (starknet, sender) = setup();
+        let class_hash = dummy_cairo_0_contract_class().generate_hash().unwrap();
+
+        // the class is not declared, so the deployment fails
+        match starknet.estimate_fee(
+            BlockId::Tag(BlockTag::Latest),
+            &[deploy_through_udc(sender, class_hash, 0)],
+            false,
+        ) {
+            Err(Error::TransactionFailed { index, source: _ }) => assert_eq!(index, 0),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn deploy_account_to_counterfactual_address_estimated_without_balance() {
+        let (mut starknet, _) = setup();
+        starknet.set_gas_price(1, 1, false).unwrap();
+        let contract_class = cairo_0_account_without_validations();
+        let class_hash = contract_class.generate_hash().unwrap();
+        starknet.state.declare_contract_class(class_hash, contract_class).unwrap();
+        starknet.state.synchronize_states();
+
+        // the fee of an estimate is not charged, so no max fee needs to be set
+        let deploy_account_transaction = DeployAccountTransaction::new(
+            vec![],
+            0,
+            vec![],
+            Felt::from(0),
+            class_hash,
+            Felt::from(13),
+            StarknetChainId::TestNet.to_felt().into(),
+            Felt::from(1),
+        )
+        .unwrap();
+        let address =
+            ContractAddress::try_from(deploy_account_transaction.inner.contract_address().clone())
+                .unwrap();
+
+        let estimation = starknet
+            .estimate_fee(
+                BlockId::Tag(BlockTag::Latest),
+                &[Transaction::DeployAccount(Box::new(deploy_account_transaction))],
+                true,
+            )
+            .unwrap()
+            .remove(0);
+        assert!(estimation.overall_fee > 0);
+
+        // the estimation doesn't deploy the account
+        assert!(matches!(
+            starknet.get_class_hash_at(&BlockId::Tag(BlockTag::Pending), &address),
+            Err(Error::ContractNotFound)
+        ));
+    }
+}
@@ -0,0 +1,380 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use starknet_api::block::BlockNumber;
+use starknet_in_rust::execution::{CallInfo, TransactionExecutionInfo};
+use starknet_rs_core::types::{BlockId, BlockTag};
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{BlockHash, Felt, TransactionHash};
+
+use super::Starknet;
+use crate::blocks::StarknetBlock;
+use crate::error::{Error, Result};
+use crate::traits::HashIdentified;
+
+/// An event together with the transaction and the block it was emitted in. Events of the pending
+/// block have no block hash and number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmittedEvent {
+    pub transaction_hash: TransactionHash,
+    pub block_hash: Option<BlockHash>,
+    pub block_number: Option<BlockNumber>,
+    pub from_address: ContractAddress,
+    pub keys: Vec<Felt>,
+    pub data: Vec<Felt>,
+}
+
+/// Position of an event: the number of its block and its index among all events of the block.
+/// Positions don't change when new blocks are added, so a query can be resumed from one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContinuationToken {
+    pub block_number: u64,
+    pub event_index: usize,
+}
+
+impl Display for ContinuationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.block_number, self.event_index)
+    }
+}
+
+impl FromStr for ContinuationToken {
+    type Err = Error;
+
+    fn from_str(token: &str) -> Result<Self> {
+        let invalid_token = || Error::InvalidContinuationToken { token: token.to_string() };
+
+        let (block_number, event_index) = token.split_once('-').ok_or_else(invalid_token)?;
+        Ok(Self {
+            block_number: block_number.parse().map_err(|_| invalid_token())?,
+            event_index: event_index.parse().map_err(|_| invalid_token())?,
+        })
+    }
+}
+
+/// Criteria that returned events have to satisfy
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub from_block: Option<BlockId>,
+    pub to_block: Option<BlockId>,
+    pub address: Option<ContractAddress>,
+    /// The key of an event at position `i` has to be one of `keys[i]`. An empty list of
+    /// alternatives matches any key.
+    pub keys: Vec<Vec<Felt>>,
+}
+
+impl EventFilter {
+    fn matches(&self, from_address: &ContractAddress, keys: &[Felt]) -> bool {
+        if self.address.map_or(false, |address| address != *from_address) {
+            return false;
+        }
+
+        self.keys.iter().enumerate().all(|(position, alternatives)| {
+            alternatives.is_empty()
+                || keys.get(position).map_or(false, |key| alternatives.contains(key))
+        })
+    }
+}
+
+/// Returns at most `chunk_size` events matching `filter`, starting at `continuation_token` if
+/// given. If there are more matching events, the token of the first one is returned as well.
+pub fn get_events(
+    starknet: &Starknet,
+    filter: &EventFilter,
+    continuation_token: Option<ContinuationToken>,
+    chunk_size: usize,
+) -> Result<(Vec<EmittedEvent>, Option<ContinuationToken>)> {
+    let from_block_number = match filter.from_block {
+        Some(block_id) => block_number_of(starknet, block_id)?,
+        None => 0,
+    };
+    let to_block_number = match filter.to_block {
+        Some(block_id) => block_number_of(starknet, block_id)?,
+        None => starknet.blocks.pending_block.block_number().0,
+    };
+    let start = continuation_token
+        .unwrap_or(ContinuationToken { block_number: from_block_number, event_index: 0 });
+
+    let mut events = vec![];
+    for block_number in start.block_number.max(from_block_number)..=to_block_number {
+        let block = match block_at(starknet, block_number) {
+            Some(block) => block,
+            None => continue,
+        };
+        let is_pending = block_number == starknet.blocks.pending_block.block_number().0;
+
+        for (event_index, event) in events_of_block(starknet, block)?.into_iter().enumerate() {
+            if block_number == start.block_number && event_index < start.event_index {
+                continue;
+            }
+            if !filter.matches(&event.from_address, &event.keys) {
+                continue;
+            }
+            if events.len() == chunk_size {
+                return Ok((events, Some(ContinuationToken { block_number, event_index })));
+            }
+
+            events.push(EmittedEvent {
+                transaction_hash: event.transaction_hash,
+                block_hash: if is_pending { None } else { Some(block.block_hash()) },
+                block_number: if is_pending { None } else { Some(block.block_number()) },
+                from_address: event.from_address,
+                keys: event.keys,
+                data: event.data,
+            });
+        }
+    }
+
+    Ok((events, None))
+}
+
+fn block_number_of(starknet: &Starknet, block_id: BlockId) -> Result<u64> {
+    match block_id {
+        BlockId::Tag(BlockTag::Pending) => Ok(starknet.blocks.pending_block.block_number().0),
+        block_id => starknet
+            .blocks
+            .get_by_block_id(block_id)
+            .map(|block| block.block_number().0)
+            .ok_or(Error::NoBlock),
+    }
+}
+
+fn block_at(starknet: &Starknet, block_number: u64) -> Option<&StarknetBlock> {
+    if block_number == starknet.blocks.pending_block.block_number().0 {
+        Some(&starknet.blocks.pending_block)
+    } else {
+        starknet.blocks.num_to_block.get(&BlockNumber(block_number))
+    }
+}
+
+/// An event of a block, before being filtered
+pub(super) struct BlockEvent {
+    transaction_hash: TransactionHash,
+    order: u64,
+    pub(super) from_address: ContractAddress,
+    pub(super) keys: Vec<Felt>,
+    pub(super) data: Vec<Felt>,
+}
+
+/// Events of all transactions of `block`, in the order of transactions
+pub(super) fn events_of_block(
+    starknet: &Starknet,
+    block: &StarknetBlock,
+) -> Result<Vec<BlockEvent>> {
+    let mut events = vec![];
+    for transaction_hash in block.get_transactions().iter().filter_map(|t| t.get_hash()) {
+        if let Some(execution_info) = starknet
+            .transactions
+            .get_by_hash(transaction_hash)
+            .and_then(|transaction| transaction.execution_info.as_ref())
+        {
+            events.extend(events_of_transaction(transaction_hash, execution_info)?);
+        }
+    }
+
+    Ok(events)
+}
+
+/// Events emitted by a transaction, ordered by validation, execution and fee transfer, and by
+/// emission within each
+fn events_of_transaction(
+    transaction_hash: TransactionHash,
+    execution_info: &TransactionExecutionInfo,
+) -> Result<Vec<BlockEvent>> {
+    let mut events = vec![];
+    for call_info in [
+        &execution_info.validate_info,
+        &execution_info.call_info,
+        &execution_info.fee_transfer_info,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let mut call_events = vec![];
+        collect_events(transaction_hash, call_info, &mut call_events)?;
+        call_events.sort_by_key(|event| event.order);
+        events.extend(call_events);
+    }
+
+    Ok(events)
+}
+
+/// Returns the events emitted by the transaction with `transaction_hash`, in emission order
+pub fn get_transaction_events(
+    starknet: &Starknet,
+    transaction_hash: TransactionHash,
+) -> Result<Vec<EmittedEvent>> {
+    let transaction =
+        starknet.transactions.get_by_hash(transaction_hash).ok_or(Error::TransactionNotFound)?;
+    let execution_info = match transaction.execution_info.as_ref() {
+        Some(execution_info) => execution_info,
+        None => return Ok(vec![]),
+    };
+
+    Ok(events_of_transaction(transaction_hash, execution_info)?
+        .into_iter()
+        .map(|event| EmittedEvent {
+            transaction_hash,
+            block_hash: transaction.block_hash,
+            block_number: transaction.block_number,
+            from_address: event.from_address,
+            keys: event.keys,
+            data: event.data,
+        })
+        .collect())
+}
+
+/// Collects the events emitted by `call_info` and, recursively, by its internal calls. Every event
+/// is attributed to the contract of the call emitting it, not to the contract called by the
+/// transaction
+fn collect_events(
+    transaction_hash: TransactionHash,
+    call_info: &CallInfo,
+    events: &mut Vec<BlockEvent>,
+) -> Result<()> {
+    let from_address: ContractAddress = call_info.contract_address.clone().try_into()?;
+    events.extend(call_info.events.iter().map(|event| BlockEvent {
+        transaction_hash,
+        order: event.order,
+        from_address,
+        keys: event.keys.iter().cloned().map(Felt::from).collect(),
+        data: event.data.iter().cloned().map(Felt::from).collect(),
+    }));
+
+    for internal_call in call_info.internal_calls.iter() {
+        collect_events(transaction_hash, internal_call, events)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+
+    use super::{ContinuationToken, EventFilter};
+    use crate::constants::{CHARGEABLE_ACCOUNT_ADDRESS, ERC20_CONTRACT_ADDRESS};
+    use crate::starknet::{FeeUnit, Starknet};
+    use crate::utils::test_utils::starknet_config_for_test;
+
+    fn transfer_filter() -> EventFilter {
+        EventFilter {
+            address: Some(
+                ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                    .unwrap(),
+            ),
+            keys: vec![vec![get_selector_from_name("Transfer").unwrap().into()]],
+            ..EventFilter::default()
+        }
+    }
+
+    async fn mint(starknet: &mut Starknet, times: u128) {
+        for address in 1..=times {
+            starknet
+                .mint(ContractAddress::new(Felt::from(address)).unwrap(), 1u32.into(), FeeUnit::Wei)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn empty_alternatives_match_any_key() {
+        let address = ContractAddress::new(Felt::from(1)).unwrap();
+        let filter = EventFilter {
+            keys: vec![vec![], vec![Felt::from(2), Felt::from(3)]],
+            ..EventFilter::default()
+        };
+
+        assert!(filter.matches(&address, &[Felt::from(1), Felt::from(3)]));
+        assert!(filter.matches(&address, &[Felt::from(4), Felt::from(2), Felt::from(5)]));
+        assert!(!filter.matches(&address, &[Felt::from(1), Felt::from(4)]));
+        // a missing key doesn't match a non-empty list of alternatives
+        assert!(!filter.matches(&address, &[Felt::from(1)]));
+        assert!(EventFilter::default().matches(&address, &[]));
+    }
+
+    #[test]
+    fn address_has_to_match() {
+        let filter = EventFilter {
+            address: Some(ContractAddress::new(Felt::from(1)).unwrap()),
+            ..EventFilter::default()
+        };
+
+        assert!(filter.matches(&ContractAddress::new(Felt::from(1)).unwrap(), &[]));
+        assert!(!filter.matches(&ContractAddress::new(Felt::from(2)).unwrap(), &[]));
+    }
+
+    #[test]
+    fn continuation_token_is_parsed_back() {
+        let token = ContinuationToken { block_number: 3, event_index: 14 };
+        assert_eq!(token.to_string().parse::<ContinuationToken>().unwrap(), token);
+        assert!("3".parse::<ContinuationToken>().is_err());
+        assert!("a-1".parse::<ContinuationToken>().is_err());
+    }
+
+    #[tokio::test]
+    async fn events_of_inner_calls_carry_address_of_emitting_contract() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        // the chargeable account calls the fee token, which emits the Transfer event
+        let transaction_hash = starknet
+            .mint(ContractAddress::new(Felt::from(1)).unwrap(), 1u32.into(), FeeUnit::Wei)
+            .await
+            .unwrap();
+
+        let events = starknet.get_transaction_events(transaction_hash).unwrap();
+        let transfer_selector = Felt::from(get_selector_from_name("Transfer").unwrap());
+        let transfer_event =
+            events.iter().find(|event| event.keys.first() == Some(&transfer_selector)).unwrap();
+        assert_eq!(
+            Felt::from(transfer_event.from_address),
+            Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap()
+        );
+        assert!(events.iter().all(|event| Felt::from(event.from_address)
+            != Felt::from_prefixed_hex_str(CHARGEABLE_ACCOUNT_ADDRESS).unwrap()));
+        assert_eq!(transfer_event.data[1], Felt::from(1));
+        assert_eq!(transfer_event.transaction_hash, transaction_hash);
+    }
+
+    #[tokio::test]
+    async fn chunks_together_hold_all_events() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        mint(&mut starknet, 3).await;
+        let filter = transfer_filter();
+
+        let (all_events, continuation_token) =
+            starknet.get_events(&filter, None, usize::MAX).unwrap();
+        assert!(all_events.len() >= 3);
+        assert!(continuation_token.is_none());
+
+        let mut chunked_events = vec![];
+        let mut continuation_token = None;
+        loop {
+            let (events, next_token) = starknet.get_events(&filter, continuation_token, 2).unwrap();
+            assert!(events.len() <= 2);
+            chunked_events.extend(events);
+            match next_token {
+                Some(token) => continuation_token = Some(token),
+                None => break,
+            }
+        }
+        assert_eq!(chunked_events, all_events);
+    }
+
+    #[tokio::test]
+    async fn continuation_token_stays_valid_after_new_blocks() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        mint(&mut starknet, 2).await;
+        let filter = transfer_filter();
+        let (all_events, _) = starknet.get_events(&filter, None, usize::MAX).unwrap();
+
+        let (first_chunk, continuation_token) = starknet.get_events(&filter, None, 1).unwrap();
+        assert_eq!(first_chunk, all_events[..1]);
+
+        mint(&mut starknet, 1).await;
+        let (next_chunk, _) =
+            starknet.get_events(&filter, continuation_token, all_events.len() - 1).unwrap();
+        assert_eq!(next_chunk, all_events[1..]);
+    }
+}
@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::ContractClass;
+use starknet_types::felt::{ClassHash, Felt};
+use starknet_types::patricia_key::PatriciaKey;
+use thiserror::Error;
+
+use super::Starknet;
+use crate::state::fork::Fork;
+
+#[derive(Error, Debug)]
+pub enum ForkError {
+    #[error("Request to the forked network timed out")]
+    Timeout,
+    #[error("Contract not found")]
+    ContractNotFound,
+    #[error("Class hash not found")]
+    ClassHashNotFound,
+    #[error("Forked network error: {msg}")]
+    Upstream { msg: String },
+}
+
+/// State of the network Devnet is forked from, at the block it was forked at. Values are `None`
+/// if the contract or the class doesn't exist in the forked network.
+pub trait ForkedState: Send + Sync {
+    fn get_class_hash_at(
+        &self,
+        contract_address: ContractAddress,
+    ) -> Result<Option<ClassHash>, ForkError>;
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> Result<Option<Felt>, ForkError>;
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: PatriciaKey,
+    ) -> Result<Option<Felt>, ForkError>;
+    fn get_class(&self, class_hash: ClassHash) -> Result<Option<ContractClass>, ForkError>;
+}
+
+impl Starknet {
+    /// Makes the contracts and classes of the forked network visible in the current state and in
+    /// the states of all blocks, unless they are shadowed by local ones. Meant to be called on
+    /// startup, before any transaction is added: changes of the pending block are discarded.
+    pub fn set_fork(&mut self, forked_state: Arc<dyn ForkedState>) {
+        self.install_fork(Fork::new(forked_state));
+    }
+
+    pub(crate) fn install_fork(&mut self, fork: Fork) {
+        let states = [&mut self.state, &mut self.genesis_state]
+            .into_iter()
+            .chain(self.blocks.num_to_state.values_mut());
+        for state in states {
+            state.fork = Some(fork.clone());
+            state.synchronize_states();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use starknet_in_rust::core::errors::state_errors::StateError;
+    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_class::ContractClass;
+    use starknet_types::felt::{ClassHash, Felt};
+    use starknet_types::patricia_key::PatriciaKey;
+
+    use super::{ForkError, ForkedState};
+    use crate::error::Error;
+    use crate::starknet::Starknet;
+    use crate::state::state_diff::StateDiff;
+    use crate::traits::StateChanger;
+    use crate::utils::get_storage_var_address;
+    use crate::utils::test_utils::{
+        dummy_cairo_0_contract_class, dummy_contract_address, dummy_felt, starknet_config_for_test,
+    };
+
+    /// Forked network with a single contract at `dummy_contract_address`, whose class is the one
+    /// of the dummy Cairo 0 contract, holding `balance`
+    struct MockForkedState {
+        nonce: Felt,
+        balance: Felt,
+    }
+
+    impl ForkedState for MockForkedState {
+        fn get_class_hash_at(
+            &self,
+            contract_address: ContractAddress,
+        ) -> Result<Option<ClassHash>, ForkError> {
+            Ok((contract_address == dummy_contract_address()).then(dummy_felt))
+        }
+
+        fn get_nonce_at(
+            &self,
+            contract_address: ContractAddress,
+        ) -> Result<Option<Felt>, ForkError> {
+            Ok((contract_address == dummy_contract_address()).then_some(self.nonce))
+        }
+
+        fn get_storage_at(
+            &self,
+            contract_address: ContractAddress,
+            key: PatriciaKey,
+        ) -> Result<Option<Felt>, ForkError> {
+            let value = if key == balance_key() { self.balance } else { Felt::default() };
+            Ok((contract_address == dummy_contract_address()).then_some(value))
+        }
+
+        fn get_class(&self, class_hash: ClassHash) -> Result<Option<ContractClass>, ForkError> {
+            Ok((class_hash == dummy_felt()).then(dummy_cairo_0_contract_class))
+        }
+    }
+
+    fn balance_key() -> PatriciaKey {
+        get_storage_var_address("balance", &[]).unwrap()
+    }
+
+    fn forked_starknet() -> Starknet {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        starknet.generate_new_block(StateDiff::default(), starknet.state.clone()).unwrap();
+        starknet.generate_pending_block().unwrap();
+        starknet
+            .set_fork(Arc::new(MockForkedState { nonce: Felt::from(3), balance: Felt::from(42) }));
+
+        starknet
+    }
+
+    #[test]
+    fn forked_contract_is_visible_in_current_and_past_blocks() {
+        let starknet = forked_starknet();
+
+        for block_id in [BlockId::Tag(BlockTag::Latest), BlockId::Number(0)] {
+            assert_eq!(
+                starknet.get_class_hash_at(&block_id, &dummy_contract_address()).unwrap(),
+                dummy_felt()
+            );
+            assert_eq!(
+                starknet.contract_nonce_at_block(block_id, dummy_contract_address()).unwrap(),
+                Felt::from(3)
+            );
+            assert_eq!(
+                starknet
+                    .contract_storage_at_block(block_id, dummy_contract_address(), balance_key())
+                    .unwrap(),
+                Felt::from(42)
+            );
+            assert_eq!(
+                starknet.get_class(block_id, dummy_felt()).unwrap(),
+                dummy_cairo_0_contract_class()
+            );
+        }
+    }
+
+    #[test]
+    fn forked_contract_can_be_called() {
+        let starknet = forked_starknet();
+
+        let balance = starknet
+            .call(
+                BlockId::Tag(BlockTag::Latest),
+                dummy_contract_address().into(),
+                starknet_rs_core::utils::get_selector_from_name("get_balance").unwrap().into(),
+                vec![],
+            )
+            .unwrap();
+        assert_eq!(balance, vec![Felt::from(42)]);
+    }
+
+    #[test]
+    fn unset_storage_of_local_contract_is_not_read_from_forked_network() {
+        let mut starknet = forked_starknet();
+        // a contract deployed locally at the address of the forked contract shadows it
+        starknet.state.deploy_contract(dummy_contract_address(), dummy_felt()).unwrap();
+
+        assert!(matches!(
+            starknet.contract_storage_at_block(
+                BlockId::Tag(BlockTag::Pending),
+                dummy_contract_address(),
+                balance_key()
+            ),
+            Err(Error::StateError(StateError::NoneStorage((_, _))))
+        ));
+    }
+
+    #[test]
+    fn contract_missing_in_forked_network_is_not_found() {
+        let starknet = forked_starknet();
+        let missing_address = ContractAddress::new(Felt::from(0x789)).unwrap();
+
+        assert!(matches!(
+            starknet.get_class_hash_at(&BlockId::Tag(BlockTag::Latest), &missing_address),
+            Err(Error::ContractNotFound)
+        ));
+        assert!(matches!(
+            starknet.contract_nonce_at_block(BlockId::Tag(BlockTag::Latest), missing_address),
+            Err(Error::ContractNotFound)
+        ));
+    }
+}
@@ -0,0 +1,396 @@
+use starknet_in_rust::execution::{CallInfo, OrderedL2ToL1Message, TransactionExecutionInfo};
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{Felt, TransactionHash};
+
+use super::Starknet;
+use crate::error::{Error, Result};
+use crate::traits::HashIdentified;
+use crate::transactions::l1_handler_transaction::L1HandlerTransaction;
+
+/// A message sent from an L1 contract, consumed by the L1 handler of an L2 contract
+#[derive(Debug, Clone)]
+pub struct MessageToL2 {
+    pub l1_contract_address: Felt,
+    pub l2_contract_address: ContractAddress,
+    /// Selector of the L1 handler consuming the message
+    pub entry_point_selector: Felt,
+    pub payload: Vec<Felt>,
+    /// Nonce assigned to the message on L1, distinguishing messages with the same content
+    pub nonce: Felt,
+    /// Fee paid on L1 for executing the L1 handler, in wei
+    pub paid_fee_on_l1: u128,
+}
+
+/// A message sent from an L2 contract to an L1 contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageToL1 {
+    pub from_address: ContractAddress,
+    pub to_address: Felt,
+    pub payload: Vec<Felt>,
+}
+
+/// Executes the L1 handler transaction that consumes `message`, as if it had been sent from L1.
+/// Returns the hash of the transaction and the hash of the message
+pub fn send_message_to_l2(
+    starknet: &mut Starknet,
+    message: MessageToL2,
+) -> Result<(TransactionHash, String)> {
+    // the handler receives the address of the sender before the payload
+    let mut calldata = vec![message.l1_contract_address];
+    calldata.extend(message.payload);
+
+    let l1_handler_transaction = L1HandlerTransaction::new(
+        message.l2_contract_address,
+        message.entry_point_selector,
+        calldata,
+        message.nonce,
+        message.paid_fee_on_l1,
+        starknet.config.chain_id.to_felt(),
+    )?;
+    let message_hash = l1_handler_transaction.message_hash();
+
+    let transaction_hash = starknet.add_l1_handler_transaction(l1_handler_transaction)?;
+
+    Ok((transaction_hash, message_hash))
+}
+
+/// Returns the messages sent to L1 since the last flush, in the order of their transactions, and
+/// forgets them
+pub fn flush_messages_to_l1(starknet: &mut Starknet) -> Vec<MessageToL1> {
+    std::mem::take(&mut starknet.messages_to_l1)
+}
+
+/// Returns the messages sent to L1 by the transaction of `transaction_hash`, in the order of
+/// sending. A transaction without execution info, e.g. a rejected one, sent no messages.
+pub fn get_transaction_messages_to_l1(
+    starknet: &Starknet,
+    transaction_hash: TransactionHash,
+) -> Result<Vec<MessageToL1>> {
+    let transaction =
+        starknet.transactions.get_by_hash(transaction_hash).ok_or(Error::TransactionNotFound)?;
+    match transaction.execution_info.as_ref() {
+        Some(execution_info) => messages_of_transaction(execution_info),
+        None => Ok(vec![]),
+    }
+}
+
+/// Messages sent to L1 by a transaction, ordered by validation, execution and fee transfer, and by
+/// sending within each
+pub(crate) fn messages_of_transaction(
+    execution_info: &TransactionExecutionInfo,
+) -> Result<Vec<MessageToL1>> {
+    let mut messages = vec![];
+    for call_info in [
+        &execution_info.validate_info,
+        &execution_info.call_info,
+        &execution_info.fee_transfer_info,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let mut call_messages = vec![];
+        collect_messages(call_info, &mut call_messages);
+        call_messages.sort_by_key(|(_, message)| message.order);
+
+        for (call_info, message) in call_messages {
+            messages.push(MessageToL1 {
+                from_address: call_info.contract_address.clone().try_into()?,
+                to_address: message.to_address.0.clone().into(),
+                payload: message.payload.iter().cloned().map(Felt::from).collect(),
+            });
+        }
+    }
+
+    Ok(messages)
+}
+
+fn collect_messages<'a>(
+    call_info: &'a CallInfo,
+    messages: &mut Vec<(&'a CallInfo, &'a OrderedL2ToL1Message)>,
+) {
+    messages.extend(call_info.l2_to_l1_messages.iter().map(|message| (call_info, message)));
+
+    for internal_call in call_info.internal_calls.iter() {
+        collect_messages(internal_call, messages);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::types::{BlockId, BlockTag, TransactionStatus};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::chain_id::ChainId;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::{Felt, TransactionHash};
+    use starknet_types::traits::HashProducer;
+
+    use super::{MessageToL1, MessageToL2};
+    use crate::account::Account;
+    use crate::constants::ERC20_CONTRACT_ADDRESS;
+    use crate::error::Error;
+    use crate::starknet::{FeeUnit, Starknet};
+    use crate::traits::{Accounted, Deployed, HashIdentified, StateChanger};
+    use crate::transactions::invoke_transaction::InvokeTransactionV1;
+    use crate::transactions::Transaction;
+    use crate::utils::test_utils::{
+        cairo_0_account_without_validations, dummy_cairo_1_contract_class, dummy_felt,
+        starknet_config_for_test,
+    };
+
+    /// Deploys the Cairo 1 test contract, whose `l1_handle` handler returns its only argument
+    fn setup() -> (Starknet, ContractAddress) {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let contract_class = dummy_cairo_1_contract_class();
+        let class_hash = contract_class.generate_hash().unwrap();
+        let contract_address = ContractAddress::new(Felt::from(0x1234)).unwrap();
+
+        starknet.state.declare_contract_class(class_hash, contract_class).unwrap();
+        starknet.state.deploy_contract(contract_address, class_hash).unwrap();
+        starknet.state.synchronize_states();
+
+        (starknet, contract_address)
+    }
+
+    fn deploy_account_without_validations(starknet: &mut Starknet) -> ContractAddress {
+        let contract_class = cairo_0_account_without_validations();
+        let account = Account::new(
+            Felt::from(1_000_000),
+            dummy_felt(),
+            dummy_felt(),
+            contract_class.generate_hash().unwrap(),
+            contract_class,
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        account.deploy(&mut starknet.state).unwrap();
+        account.set_initial_balance(&mut starknet.state).unwrap();
+        starknet.state.synchronize_states();
+
+        account.get_address()
+    }
+
+    /// Invokes `test_send_message_to_l1` of the test contract, sending `payload` to `to_address`
+    fn send_message_to_l1(
+        starknet: &mut Starknet,
+        sender: ContractAddress,
+        nonce: u128,
+        contract_address: ContractAddress,
+        to_address: Felt,
+        payload: Felt,
+    ) -> TransactionHash {
+        let calldata = vec![
+            contract_address.into(), // contract address
+            get_selector_from_name("test_send_message_to_l1").unwrap().into(), // function selector
+            Felt::from(3),           // calldata len
+            to_address,              // to address
+            Felt::from(1),           // payload len
+            payload,                 // payload
+        ];
+        let invoke_transaction = InvokeTransactionV1::new(
+            sender,
+            10000,
+            vec![],
+            Felt::from(nonce),
+            calldata,
+            ChainId::TESTNET.to_felt(),
+        )
+        .unwrap();
+
+        let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction).unwrap();
+        let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+
+        transaction_hash
+    }
+
+    fn message_to_l2(l2_contract_address: ContractAddress, nonce: u128) -> MessageToL2 {
+        MessageToL2 {
+            l1_contract_address: Felt::from(0xE7),
+            l2_contract_address,
+            entry_point_selector: get_selector_from_name("l1_handle").unwrap().into(),
+            payload: vec![Felt::from(42)],
+            nonce: Felt::from(nonce),
+            paid_fee_on_l1: 1,
+        }
+    }
+
+    #[test]
+    fn message_is_consumed_by_l1_handler() {
+        let (mut starknet, contract_address) = setup();
+
+        let (transaction_hash, message_hash) =
+            starknet.send_message_to_l2(message_to_l2(contract_address, 0)).unwrap();
+
+        let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        let call_info = transaction.execution_info.as_ref().unwrap().call_info.as_ref().unwrap();
+        assert_eq!(call_info.retdata, vec![Felt::from(42).into()]);
+
+        let block = starknet.get_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+        match block.get_transactions().as_slice() {
+            [Transaction::L1Handler(l1_handler_transaction)] => {
+                assert_eq!(
+                    l1_handler_transaction.calldata(),
+                    &vec![Felt::from(0xE7), Felt::from(42)]
+                );
+                assert_eq!(l1_handler_transaction.message_hash(), message_hash);
+            }
+            _ => panic!("Expected a single L1 handler transaction"),
+        }
+    }
+
+    #[test]
+    fn message_with_consumed_nonce_is_rejected() {
+        let (mut starknet, contract_address) = setup();
+        starknet.send_message_to_l2(message_to_l2(contract_address, 0)).unwrap();
+
+        match starknet.send_message_to_l2(message_to_l2(contract_address, 0)) {
+            Err(Error::MessageToL2AlreadyConsumed { nonce }) => assert_eq!(nonce, "0x0"),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        // a different nonce makes it a new message
+        starknet.send_message_to_l2(message_to_l2(contract_address, 1)).unwrap();
+        assert_eq!(starknet.block_number().0, 3);
+    }
+
+    #[test]
+    fn message_with_nonce_below_consumed_one_is_rejected() {
+        let (mut starknet, contract_address) = setup();
+        starknet.send_message_to_l2(message_to_l2(contract_address, 2)).unwrap();
+
+        match starknet.send_message_to_l2(message_to_l2(contract_address, 1)) {
+            Err(Error::MessageToL2OutOfOrder { nonce, last_consumed_nonce }) => {
+                assert_eq!(nonce, "0x1");
+                assert_eq!(last_consumed_nonce, "0x2");
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        starknet.send_message_to_l2(message_to_l2(contract_address, 3)).unwrap();
+    }
+
+    #[test]
+    fn fee_paid_on_l1_is_actual_fee_and_not_charged_on_l2() {
+        let (mut starknet, contract_address) = setup();
+        // fees charged on L2 are transferred to the sequencer
+        let sequencer_balance = |starknet: &Starknet| {
+            starknet
+                .get_token_balance(
+                    BlockId::Tag(BlockTag::Pending),
+                    starknet.config.sequencer_address,
+                    FeeUnit::Wei,
+                )
+                .unwrap()
+        };
+        let sequencer_balance_before = sequencer_balance(&starknet);
+
+        let mut message = message_to_l2(contract_address, 0);
+        message.paid_fee_on_l1 = 1_000_000;
+        let (transaction_hash, _) = starknet.send_message_to_l2(message).unwrap();
+
+        let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        assert_eq!(transaction.actual_fee(), 1_000_000);
+        assert_eq!(sequencer_balance(&starknet), sequencer_balance_before);
+    }
+
+    #[test]
+    fn nonce_of_message_with_failed_handler_is_not_consumed() {
+        let (mut starknet, contract_address) = setup();
+        let mut message = message_to_l2(contract_address, 0);
+        message.entry_point_selector =
+            get_selector_from_name("nonexistent_handler").unwrap().into();
+
+        let (transaction_hash, _) = starknet.send_message_to_l2(message).unwrap();
+        let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Rejected);
+
+        let (transaction_hash, _) =
+            starknet.send_message_to_l2(message_to_l2(contract_address, 0)).unwrap();
+        let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+    }
+
+    #[test]
+    fn messages_to_l1_are_flushed_in_order_of_sending() {
+        let (mut starknet, contract_address) = setup();
+        let sender = deploy_account_without_validations(&mut starknet);
+
+        send_message_to_l1(
+            &mut starknet,
+            sender,
+            0,
+            contract_address,
+            Felt::from(0xE1),
+            Felt::from(1),
+        );
+        send_message_to_l1(
+            &mut starknet,
+            sender,
+            1,
+            contract_address,
+            Felt::from(0xE2),
+            Felt::from(2),
+        );
+
+        let message = |to_address: u128, payload: u128| MessageToL1 {
+            from_address: contract_address,
+            to_address: Felt::from(to_address),
+            payload: vec![Felt::from(payload)],
+        };
+        assert_eq!(starknet.flush_messages_to_l1(), vec![message(0xE1, 1), message(0xE2, 2)]);
+
+        // flushed messages are not returned again
+        assert_eq!(starknet.flush_messages_to_l1(), vec![]);
+    }
+
+    #[test]
+    fn messages_to_l1_are_returned_per_transaction() {
+        let (mut starknet, contract_address) = setup();
+        let sender = deploy_account_without_validations(&mut starknet);
+
+        let first_hash = send_message_to_l1(
+            &mut starknet,
+            sender,
+            0,
+            contract_address,
+            Felt::from(0xE1),
+            Felt::from(1),
+        );
+        let second_hash = send_message_to_l1(
+            &mut starknet,
+            sender,
+            1,
+            contract_address,
+            Felt::from(0xE2),
+            Felt::from(2),
+        );
+
+        let message = |to_address: u128, payload: u128| MessageToL1 {
+            from_address: contract_address,
+            to_address: Felt::from(to_address),
+            payload: vec![Felt::from(payload)],
+        };
+        assert_eq!(
+            starknet.get_transaction_messages_to_l1(first_hash).unwrap(),
+            vec![message(0xE1, 1)]
+        );
+        assert_eq!(
+            starknet.get_transaction_messages_to_l1(second_hash).unwrap(),
+            vec![message(0xE2, 2)]
+        );
+
+        // messages stay in the receipts after being flushed
+        starknet.flush_messages_to_l1();
+        assert_eq!(
+            starknet.get_transaction_messages_to_l1(first_hash).unwrap(),
+            vec![message(0xE1, 1)]
+        );
+        assert!(matches!(
+            starknet.get_transaction_messages_to_l1(dummy_felt()),
+            Err(Error::TransactionNotFound)
+        ));
+    }
+}
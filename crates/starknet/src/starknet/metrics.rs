@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+use super::Starknet;
+use crate::transactions::Transaction;
+
+/// Upper bounds in seconds of the buckets of the transaction execution histogram
+const EXECUTION_DURATION_BUCKETS: [f64; 11] =
+    [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Metrics collected while transactions are processed, if enabled with `enable_metrics`. Gauges
+/// are read from the state when the metrics are rendered, so only counters are kept here.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Metrics {
+    /// Accepted transactions, by their type
+    transactions_by_type: BTreeMap<&'static str, u64>,
+    /// Number of executions that took at most the bound of each bucket
+    execution_duration_buckets: [u64; EXECUTION_DURATION_BUCKETS.len()],
+    execution_duration_sum: f64,
+    execution_count: u64,
+}
+
+impl Metrics {
+    pub(crate) fn record_accepted_transaction(&mut self, transaction: &Transaction) {
+        *self.transactions_by_type.entry(type_label(transaction)).or_default() += 1;
+    }
+
+    pub(crate) fn record_execution_duration(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        for (bound, count) in
+            EXECUTION_DURATION_BUCKETS.iter().zip(self.execution_duration_buckets.iter_mut())
+        {
+            if seconds <= *bound {
+                *count += 1;
+            }
+        }
+        self.execution_duration_sum += seconds;
+        self.execution_count += 1;
+    }
+}
+
+fn type_label(transaction: &Transaction) -> &'static str {
+    match transaction {
+        Transaction::Declare(_) | Transaction::DeclareV2(_) | Transaction::DeclareV3(_) => {
+            "DECLARE"
+        }
+        Transaction::DeployAccount(_) | Transaction::DeployAccountV3(_) => "DEPLOY_ACCOUNT",
+        Transaction::Invoke(_) | Transaction::InvokeV3(_) => "INVOKE",
+        Transaction::L1Handler(_) => "L1_HANDLER",
+    }
+}
+
+/// Renders the metrics of `starknet` in the Prometheus text exposition format
+pub(crate) fn render(starknet: &Starknet) -> String {
+    let metrics = &starknet.metrics;
+    let mut output = String::new();
+
+    // writing to a String can't fail
+    let _ = writeln!(output, "# HELP devnet_transactions_total Accepted transactions by type");
+    let _ = writeln!(output, "# TYPE devnet_transactions_total counter");
+    for (transaction_type, count) in &metrics.transactions_by_type {
+        let _ =
+            writeln!(output, "devnet_transactions_total{{type=\"{transaction_type}\"}} {count}");
+    }
+
+    let _ = writeln!(output, "# HELP devnet_block_number Number of the latest block");
+    let _ = writeln!(output, "# TYPE devnet_block_number gauge");
+    if let Ok(latest_block) = starknet.get_latest_block() {
+        let _ = writeln!(output, "devnet_block_number {}", latest_block.block_number().0);
+    }
+
+    let _ =
+        writeln!(output, "# HELP devnet_pending_transactions Transactions awaiting the next block");
+    let _ = writeln!(output, "# TYPE devnet_pending_transactions gauge");
+    let _ =
+        writeln!(output, "devnet_pending_transactions {}", starknet.pending_transactions().len());
+
+    let _ = writeln!(
+        output,
+        "# HELP devnet_transaction_execution_seconds Time taken to add a transaction, from its \
+         execution until it is part of the pending block or, if blocks are generated on \
+         transactions, of a new block"
+    );
+    let _ = writeln!(output, "# TYPE devnet_transaction_execution_seconds histogram");
+    for (bound, count) in EXECUTION_DURATION_BUCKETS.iter().zip(metrics.execution_duration_buckets)
+    {
+        let _ = writeln!(
+            output,
+            "devnet_transaction_execution_seconds_bucket{{le=\"{bound}\"}} {count}"
+        );
+    }
+    let _ = writeln!(
+        output,
+        "devnet_transaction_execution_seconds_bucket{{le=\"+Inf\"}} {}",
+        metrics.execution_count
+    );
+    let _ = writeln!(
+        output,
+        "devnet_transaction_execution_seconds_sum {}",
+        metrics.execution_duration_sum
+    );
+    let _ =
+        writeln!(output, "devnet_transaction_execution_seconds_count {}", metrics.execution_count);
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Metrics;
+    use crate::starknet::{FeeUnit, Starknet, StarknetConfig};
+    use crate::utils::test_utils::{dummy_contract_address, starknet_config_for_test};
+
+    #[test]
+    fn execution_durations_counted_in_every_bucket_they_fit() {
+        let mut metrics = Metrics::default();
+        metrics.record_execution_duration(Duration::from_millis(20));
+        metrics.record_execution_duration(Duration::from_secs(20));
+
+        // 0.005 and 0.01 hold none, the buckets from 0.025 to 10 hold the shorter one
+        assert_eq!(metrics.execution_duration_buckets, [0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(metrics.execution_count, 2);
+        assert!((metrics.execution_duration_sum - 20.02).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn accepted_transactions_counted_only_if_enabled() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        starknet.mint(dummy_contract_address(), 1u32.into(), FeeUnit::Wei).await.unwrap();
+        assert_eq!(starknet.metrics, Metrics::default());
+
+        let config = StarknetConfig { enable_metrics: true, ..starknet_config_for_test() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        starknet.mint(dummy_contract_address(), 1u32.into(), FeeUnit::Wei).await.unwrap();
+        starknet.mint(dummy_contract_address(), 1u32.into(), FeeUnit::Wei).await.unwrap();
+
+        let rendered = starknet.render_metrics();
+        assert!(rendered.contains("devnet_transactions_total{type=\"INVOKE\"} 2\n"), "{rendered}");
+        assert!(rendered.contains("devnet_block_number 2\n"), "{rendered}");
+        assert!(rendered.contains("devnet_pending_transactions 0\n"), "{rendered}");
+        assert!(rendered.contains("devnet_transaction_execution_seconds_count 2\n"), "{rendered}");
+    }
+}
@@ -1,71 +1,236 @@
-use std::collections::HashMap;
-use std::time::SystemTime;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 
+use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockNumber, BlockStatus, BlockTimestamp, GasPrice};
+use starknet_api::core::{calculate_contract_address, GlobalRoot};
+use starknet_api::hash::StarkFelt;
+use starknet_api::transaction::{Calldata, ContractAddressSalt};
+use starknet_in_rust::core::contract_address::compute_casm_class_hash;
 use starknet_in_rust::definitions::block_context::{
     BlockContext, StarknetChainId, StarknetOsConfig,
 };
 use starknet_in_rust::definitions::constants::{
     DEFAULT_CAIRO_RESOURCE_FEE_WEIGHTS, DEFAULT_CONTRACT_STORAGE_COMMITMENT_TREE_HEIGHT,
-    DEFAULT_GLOBAL_STATE_COMMITMENT_TREE_HEIGHT, DEFAULT_INVOKE_TX_MAX_N_STEPS,
-    DEFAULT_VALIDATE_MAX_N_STEPS,
+    DEFAULT_GLOBAL_STATE_COMMITMENT_TREE_HEIGHT, DEFAULT_VALIDATE_MAX_N_STEPS, INITIAL_GAS_COST,
 };
 use starknet_in_rust::execution::TransactionExecutionInfo;
 use starknet_in_rust::state::state_api::State;
 use starknet_in_rust::state::BlockInfo;
 use starknet_in_rust::testing::TEST_SEQUENCER_ADDRESS;
+use starknet_in_rust::transaction::error::TransactionError;
+use starknet_in_rust::transaction::Transaction as SirTransaction;
 use starknet_in_rust::utils::Address;
-use starknet_in_rust::{call_contract, SierraContractClass};
-use starknet_rs_core::types::{BlockId, TransactionStatus};
+use starknet_in_rust::{call_contract, CasmContractClass, SierraContractClass};
+use starknet_rs_core::crypto::pedersen_hash;
+use starknet_rs_core::types::{BlockId, BlockTag, TransactionStatus};
 use starknet_rs_core::utils::get_selector_from_name;
 use starknet_rs_ff::FieldElement;
 use starknet_rs_signers::Signer;
+use starknet_types::chain_id::ChainId;
 use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::ContractClass;
 use starknet_types::contract_storage_key::ContractStorageKey;
-use starknet_types::felt::{ClassHash, Felt, TransactionHash};
+use starknet_types::felt::{Balance, ClassHash, Felt, TransactionHash};
+use starknet_types::num_bigint::BigUint;
 use starknet_types::patricia_key::PatriciaKey;
-use starknet_types::traits::HashProducer;
-use tracing::error;
-
+use starknet_types::traits::{HashProducer, ToHexString};
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+
+use self::dump::DumpOn;
+use self::estimations::FeeEstimation;
+use self::events::{ContinuationToken, EmittedEvent, EventFilter};
+use self::messaging::{MessageToL1, MessageToL2};
+use self::metrics::Metrics;
+use self::parallel_execution::{ParallelExecutionStats, QueuedInvoke};
 use self::predeployed::initialize_erc20;
+use self::simulations::SimulatedTransaction;
+use self::snapshots::Snapshot;
+use self::storage_proof::StorageProof;
 use crate::account::Account;
-use crate::blocks::{StarknetBlock, StarknetBlocks};
+use crate::blocks::{NewBlocksNotifier, StarknetBlock, StarknetBlocks, StorageWrite};
 use crate::constants::{
-    CAIRO_0_ACCOUNT_CONTRACT_PATH, CHARGEABLE_ACCOUNT_ADDRESS, CHARGEABLE_ACCOUNT_PRIVATE_KEY,
-    ERC20_CONTRACT_ADDRESS,
+    BLOCK_HASH_CONTRACT_ADDRESS, CAIRO_0_ACCOUNT_CONTRACT_PATH,
+    CAIRO_1_ACCOUNT_CONTRACT_SIERRA_PATH, CHARGEABLE_ACCOUNT_ADDRESS,
+    CHARGEABLE_ACCOUNT_PRIVATE_KEY, DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_ETH_STRK_RATE,
+    DEVNET_DEFAULT_MAX_CALLDATA_LEN, DEVNET_DEFAULT_MAX_SIGNATURE_LEN, DEVNET_DEFAULT_MAX_STEPS,
+    DEVNET_DEFAULT_REQUEST_BODY_LIMIT, DEVNET_DEFAULT_RPC_PATH, DEVNET_DEFAULT_SEQUENCER_ADDRESS,
+    DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL, ERC20_CONTRACT_ADDRESS, ERC20_DECIMALS,
+    ETH_ERC20_NAME, ETH_ERC20_SYMBOL, GENESIS_BLOCK_TIMESTAMP, STORED_BLOCK_HASH_BUFFER,
+    STRK_ERC20_CONTRACT_ADDRESS, STRK_ERC20_NAME, STRK_ERC20_SYMBOL, UDC_CONTRACT_ADDRESS,
 };
 use crate::error::{Error, Result};
-use crate::predeployed_accounts::PredeployedAccounts;
+use crate::predeployed_accounts::{self, PredeployedAccounts};
 use crate::raw_execution::{Call, RawExecution};
 use crate::state::state_diff::StateDiff;
 use crate::state::state_update::StateUpdate;
 use crate::state::StarknetState;
+use crate::traces::TransactionTrace;
 use crate::traits::{
-    AccountGenerator, Accounted, Deployed, HashIdentifiedMut, StateChanger, StateExtractor,
+    AccountGenerator, Accounted, Deployed, HashIdentified, HashIdentifiedMut, StateChanger,
+    StateExtractor,
 };
 use crate::transactions::declare_transaction::DeclareTransactionV1;
 use crate::transactions::declare_transaction_v2::DeclareTransactionV2;
+use crate::transactions::declare_transaction_v3::DeclareTransactionV3;
 use crate::transactions::deploy_account_transaction::DeployAccountTransaction;
+use crate::transactions::deploy_account_transaction_v3::DeployAccountTransactionV3;
 use crate::transactions::invoke_transaction::InvokeTransactionV1;
-use crate::transactions::{StarknetTransaction, StarknetTransactions, Transaction};
-use crate::utils;
+use crate::transactions::invoke_transaction_v3::InvokeTransactionV3;
+use crate::transactions::l1_handler_transaction::L1HandlerTransaction;
+use crate::transactions::{
+    PendingTransactionsNotifier, StarknetTransaction, StarknetTransactions, Transaction,
+};
+use crate::{revert_reason, utils};
 
 mod add_declare_transaction;
 mod add_deploy_account_transaction;
 mod add_invoke_transaction;
+mod add_l1_handler_transaction;
+mod commitments;
+pub mod dump;
+pub mod estimations;
+pub mod events;
+pub mod fork;
+pub mod messaging;
+mod metrics;
+mod nonces;
+mod parallel_execution;
 mod predeployed;
+pub mod simulations;
+mod snapshots;
+pub mod state_dir;
 mod state_update;
+pub mod storage_proof;
+pub mod transaction_trace;
 
 #[derive(Clone, Debug)]
 pub struct StarknetConfig {
     pub seed: u32,
     pub total_accounts: u8,
     pub predeployed_accounts_initial_balance: Felt,
+    /// Path of a JSON file listing accounts predeployed at given addresses, besides the
+    /// generated ones
+    pub predeployed_accounts_path: Option<String>,
+    /// If set, no accounts are generated; only the ones listed in `predeployed_accounts_path`
+    /// are predeployed
+    pub no_auto_accounts: bool,
     pub host: String,
+    /// Port to listen at; if 0, the port assigned by the OS once bound
     pub port: u16,
+    /// Path of the file the port is written to once bound
+    pub port_file: Option<String>,
+    /// Path the JSON-RPC handler is mounted at
+    pub rpc_path: String,
+    /// Prefix of the paths of the Devnet-specific endpoints, such as /mint; empty if mounted at
+    /// the root
+    pub devnet_path: String,
     pub timeout: u16,
+    /// Max size of a request body in bytes
+    pub request_body_limit: usize,
+    /// Max number of requests per second accepted from a single client IP; unlimited if None
+    pub rate_limit: Option<u32>,
+    /// Seconds a subscription to the status of a transaction is kept open while the transaction
+    /// is unknown
+    pub transaction_subscription_ttl: u64,
+    /// Price of L1 gas in wei
     pub gas_price: u64,
-    pub chain_id: StarknetChainId,
+    /// Price of L1 data gas in wei
+    pub data_gas_price: u64,
+    /// Fri a wei is worth; prices of gas in fri are the prices in wei multiplied by it
+    pub eth_strk_rate: u64,
+    /// Max number of Cairo steps of executing a transaction or a call, nested calls included
+    pub max_steps: u64,
+    /// Max number of elements of the calldata of a submitted transaction; longer ones are rejected
+    pub max_calldata_len: usize,
+    /// Max number of elements of the signature of a submitted transaction; longer ones are
+    /// rejected
+    pub max_signature_len: usize,
+    pub chain_id: ChainId,
+    /// Number of the genesis block; subsequent blocks are numbered from it
+    pub initial_block_number: u64,
+    /// Timestamp of the genesis block; subsequent blocks advance from it. If not set, the genesis
+    /// block is timestamped 0 and subsequent blocks take the timestamp of the system clock
+    pub initial_block_timestamp: Option<u64>,
+    /// If set, `starknet_syncing` reports progress starting from this block number
+    pub fake_sync_from: Option<u64>,
+    /// Number of the highest block `starknet_syncing` reports progress until
+    pub fake_sync_until: Option<u64>,
+    /// When to automatically dump the state to `dump_path`
+    pub dump_on: Option<DumpOn>,
+    pub dump_path: Option<String>,
+    /// If set, pending transactions are committed to a new block when Devnet is shut down
+    pub commit_on_shutdown: bool,
+    /// Directory where committed blocks are persisted and loaded from on startup
+    pub state_dir: Option<String>,
+    /// URL of the JSON-RPC node of the network to fork from
+    pub fork_network: Option<String>,
+    /// Block of the forked network whose state is used as the baseline
+    pub fork_block: Option<u64>,
+    /// If set, all accounts are impersonated
+    pub auto_impersonate: bool,
+    /// If set, an impersonated account whose balance in the fee token of its invoke transaction
+    /// is lower than this amount is topped up to it before the transaction is executed
+    pub impersonate_auto_fund: Option<u128>,
+    /// If set, invoke transactions are executed without charging fees to the sender
+    pub disable_fee_charge: bool,
+    /// If set, fee estimations and simulations skip validation unless flags are given explicitly
+    pub estimate_skip_validate: bool,
+    /// If set, classes can be declared through POST /declare without a signed transaction
+    pub allow_dev_declare: bool,
+    /// If set, declared classes can be deployed through POST /deploy without a signed transaction
+    pub allow_dev_deploy: bool,
+    /// If set, contract storage can be written through POST /set_storage_at without a transaction
+    pub allow_dev_state: bool,
+    /// What happens to a storage write of POST /set_storage_at to an undeployed contract
+    pub undeployed_storage_write: UndeployedStorageWrite,
+    /// How failures caused by calldata not matching the parameters of an entry point are reported
+    pub calldata_validation: CalldataValidation,
+    /// Set of standard classes whose class hashes are exposed for deployments without declaring
+    pub predeclare: Option<PredeclaredClassSet>,
+    /// If set, the global state root is not computed when generating blocks and is reported as
+    /// zero; storage proofs are unavailable
+    pub lite_mode: bool,
+    /// Whether a block is generated for every transaction or only on request
+    pub block_generation_on: BlockGenerationOn,
+    /// If positive, a block is generated every this many seconds, regardless of transactions
+    pub block_time_secs: u64,
+    /// If set in demand mode, invoke transactions of version 1 are queued and executed in
+    /// parallel when the block is created
+    pub parallel_execution: bool,
+    /// If set in demand mode, at most this many transactions are pending, queued ones included
+    pub max_pending_transactions: Option<u64>,
+    /// What happens to a new transaction when `max_pending_transactions` are pending
+    pub pending_overflow: PendingOverflow,
+    /// Built-in class of the predeployed accounts
+    pub account_class: AccountClass,
+    /// Path of a Sierra file with the class of the predeployed accounts; takes precedence over
+    /// `account_class`
+    pub account_class_custom: Option<String>,
+    /// If set, private keys of predeployed accounts are neither logged nor returned by the API
+    pub hide_predeployed_keys: bool,
+    /// URL of the L1 node to which messages sent to L1 are submitted when flushed
+    pub l1_node_url: Option<String>,
+    /// Address at which the ETH fee token is deployed, also used for charging fees
+    pub eth_fee_token_address: ContractAddress,
+    /// Address at which the STRK fee token is deployed
+    pub strk_fee_token_address: ContractAddress,
+    /// Name, symbol and decimals stored in the ETH fee token at genesis
+    pub eth_token_name: String,
+    pub eth_token_symbol: String,
+    pub eth_token_decimals: u8,
+    /// Name, symbol and decimals stored in the STRK fee token at genesis
+    pub strk_token_name: String,
+    pub strk_token_symbol: String,
+    pub strk_token_decimals: u8,
+    /// Address of the sequencer in block headers and in the context of executed transactions
+    pub sequencer_address: ContractAddress,
+    /// If set, metrics of the processed transactions are collected and exposed via GET /metrics
+    pub enable_metrics: bool,
 }
 
 impl Default for StarknetConfig {
@@ -74,35 +239,339 @@ impl Default for StarknetConfig {
             seed: u32::default(),
             total_accounts: u8::default(),
             predeployed_accounts_initial_balance: Felt::default(),
+            predeployed_accounts_path: None,
+            no_auto_accounts: false,
             host: String::default(),
             port: u16::default(),
+            port_file: None,
+            rpc_path: DEVNET_DEFAULT_RPC_PATH.to_string(),
+            devnet_path: String::new(),
             timeout: u16::default(),
+            request_body_limit: DEVNET_DEFAULT_REQUEST_BODY_LIMIT,
+            rate_limit: None,
+            transaction_subscription_ttl: DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL,
             gas_price: u64::default(),
-            chain_id: StarknetChainId::TestNet,
+            data_gas_price: u64::default(),
+            eth_strk_rate: DEVNET_DEFAULT_ETH_STRK_RATE,
+            max_steps: DEVNET_DEFAULT_MAX_STEPS,
+            max_calldata_len: DEVNET_DEFAULT_MAX_CALLDATA_LEN,
+            max_signature_len: DEVNET_DEFAULT_MAX_SIGNATURE_LEN,
+            chain_id: DEVNET_DEFAULT_CHAIN_ID,
+            initial_block_number: 0,
+            initial_block_timestamp: None,
+            fake_sync_from: None,
+            fake_sync_until: None,
+            dump_on: None,
+            dump_path: None,
+            commit_on_shutdown: false,
+            state_dir: None,
+            fork_network: None,
+            fork_block: None,
+            auto_impersonate: false,
+            impersonate_auto_fund: None,
+            disable_fee_charge: false,
+            estimate_skip_validate: false,
+            allow_dev_declare: false,
+            allow_dev_deploy: false,
+            allow_dev_state: false,
+            undeployed_storage_write: UndeployedStorageWrite::default(),
+            calldata_validation: CalldataValidation::default(),
+            predeclare: None,
+            lite_mode: false,
+            block_generation_on: BlockGenerationOn::default(),
+            block_time_secs: 0,
+            parallel_execution: false,
+            max_pending_transactions: None,
+            pending_overflow: PendingOverflow::default(),
+            account_class: AccountClass::default(),
+            account_class_custom: None,
+            hide_predeployed_keys: false,
+            l1_node_url: None,
+            eth_fee_token_address: FeeUnit::Wei.default_erc20_address(),
+            strk_fee_token_address: FeeUnit::Fri.default_erc20_address(),
+            eth_token_name: ETH_ERC20_NAME.to_string(),
+            eth_token_symbol: ETH_ERC20_SYMBOL.to_string(),
+            eth_token_decimals: ERC20_DECIMALS,
+            strk_token_name: STRK_ERC20_NAME.to_string(),
+            strk_token_symbol: STRK_ERC20_SYMBOL.to_string(),
+            strk_token_decimals: ERC20_DECIMALS,
+            sequencer_address: default_sequencer_address(),
+            enable_metrics: false,
+        }
+    }
+}
+
+/// Address of the sequencer if not configured otherwise
+pub fn default_sequencer_address() -> ContractAddress {
+    ContractAddress::new(Felt::from_prefixed_hex_str(DEVNET_DEFAULT_SEQUENCER_ADDRESS).unwrap())
+        .unwrap()
+}
+
+/// Address of an instance of `class_hash` deployed with `constructor_calldata` and `salt`, as
+/// computed by `calculate_contract_address`. If deployed through the UDC with `unique` set,
+/// `unique_deployer` is the account calling the UDC: the salt is then hashed with its address and
+/// the UDC is the deployer. Otherwise, e.g. for deploy_account transactions, the deployer is 0.
+pub fn calculate_deployment_address(
+    class_hash: ClassHash,
+    constructor_calldata: &[Felt],
+    salt: Felt,
+    unique_deployer: Option<ContractAddress>,
+) -> Result<ContractAddress> {
+    let (salt, deployer_address) = match unique_deployer {
+        Some(deployer) => (
+            Felt::from(pedersen_hash(&Felt::from(deployer).into(), &salt.into())),
+            starknet_api::core::ContractAddress::try_from(ContractAddress::new(
+                Felt::from_prefixed_hex_str(UDC_CONTRACT_ADDRESS)?,
+            )?)?,
+        ),
+        None => (salt, starknet_api::core::ContractAddress::default()),
+    };
+
+    Ok(ContractAddress::from(calculate_contract_address(
+        ContractAddressSalt(salt.into()),
+        class_hash.into(),
+        &Calldata(Arc::new(constructor_calldata.iter().map(StarkFelt::from).collect())),
+        deployer_address,
+    )?))
+}
+
+/// When Devnet generates new blocks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlockGenerationOn {
+    /// After every accepted transaction
+    #[default]
+    Transaction,
+    /// Only on request; accepted transactions are collected in the pending block until then
+    Demand,
+}
+
+impl FromStr for BlockGenerationOn {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "transaction" => Ok(BlockGenerationOn::Transaction),
+            "demand" => Ok(BlockGenerationOn::Demand),
+            other => Err(format!(
+                "Invalid block generation mode: {other}; expected one of: transaction, demand"
+            )),
+        }
+    }
+}
+
+/// How a full pending block is handled in demand mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PendingOverflow {
+    /// A block is created as soon as the pending block is full
+    #[default]
+    Seal,
+    /// Transactions are rejected until a block is created
+    Reject,
+}
+
+impl FromStr for PendingOverflow {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "seal" => Ok(PendingOverflow::Seal),
+            "reject" => Ok(PendingOverflow::Reject),
+            other => Err(format!(
+                "Invalid pending overflow handling: {other}; expected one of: seal, reject"
+            )),
+        }
+    }
+}
+
+/// Handling of a direct storage write to an address at which no contract is deployed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UndeployedStorageWrite {
+    /// The value is written and a warning is logged
+    #[default]
+    Warn,
+    /// The write is rejected
+    Reject,
+}
+
+impl FromStr for UndeployedStorageWrite {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "warn" => Ok(UndeployedStorageWrite::Warn),
+            "reject" => Ok(UndeployedStorageWrite::Reject),
+            other => Err(format!(
+                "Invalid handling of undeployed storage writes: {other}; expected one of: warn, \
+                 reject"
+            )),
+        }
+    }
+}
+
+/// Reporting of calls and invoke transactions failing because their calldata doesn't match the
+/// parameters of the called entry point
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CalldataValidation {
+    /// The failure is reported as on mainnet
+    #[default]
+    Strict,
+    /// The failure is reported with guidance on how to fix the calldata
+    Permissive,
+}
+
+impl FromStr for CalldataValidation {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(CalldataValidation::Strict),
+            "permissive" => Ok(CalldataValidation::Permissive),
+            other => Err(format!(
+                "Invalid calldata validation: {other}; expected one of: strict, permissive"
+            )),
+        }
+    }
+}
+
+/// Set of classes declared at genesis for clients to deploy without declaring them first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PredeclaredClassSet {
+    /// The classes of the bundled OpenZeppelin ERC20 and of the UDC
+    Standard,
+}
+
+impl FromStr for PredeclaredClassSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(PredeclaredClassSet::Standard),
+            other => {
+                Err(format!("Invalid set of predeclared classes: {other}; expected: standard"))
+            }
+        }
+    }
+}
+
+/// Built-in account contract backing the predeployed accounts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AccountClass {
+    /// OpenZeppelin account written in Cairo 0
+    #[default]
+    Cairo0,
+    /// OpenZeppelin account written in Cairo 1
+    Cairo1,
+}
+
+impl FromStr for AccountClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "cairo0" => Ok(AccountClass::Cairo0),
+            "cairo1" => Ok(AccountClass::Cairo1),
+            other => {
+                Err(format!("Invalid account class: {other}; expected one of: cairo0, cairo1"))
+            }
         }
     }
 }
 
+/// Unit of a fee token amount, which also identifies the token
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum FeeUnit {
+    /// ETH
+    #[default]
+    Wei,
+    /// STRK
+    Fri,
+}
+
+impl FeeUnit {
+    /// Address of the fee token on Starknet mainnet and testnets, where it is deployed by default
+    pub fn default_erc20_address(&self) -> ContractAddress {
+        let address = match self {
+            FeeUnit::Wei => ERC20_CONTRACT_ADDRESS,
+            FeeUnit::Fri => STRK_ERC20_CONTRACT_ADDRESS,
+        };
+
+        ContractAddress::new(Felt::from_prefixed_hex_str(address).unwrap()).unwrap()
+    }
+}
+
 #[derive(Default)]
 pub struct Starknet {
     pub(in crate::starknet) state: StarknetState,
     predeployed_accounts: PredeployedAccounts,
+    /// Class of the generated predeployed accounts
+    account_class_hash: ClassHash,
     pub(in crate::starknet) block_context: BlockContext,
+    /// Price of L1 data gas in wei. Kept apart from the block context, which only knows the price
+    /// of L1 gas
+    data_gas_price: u64,
     blocks: StarknetBlocks,
     transactions: StarknetTransactions,
     pub config: StarknetConfig,
     pub(in crate::starknet) sierra_contracts: HashMap<ClassHash, SierraContractClass>,
+    /// Classes declared before the first block
+    predeployed_classes: HashMap<ClassHash, ContractClass>,
+    /// Hashes of the predeclared standard classes, by class name
+    predeclared_classes: Vec<(&'static str, ClassHash)>,
+    new_blocks_notifier: NewBlocksNotifier,
+    pending_transactions_notifier: PendingTransactionsNotifier,
+    /// Offset in seconds between the system clock and the timestamps of generated blocks
+    pending_block_timestamp_shift: i64,
+    /// Accounts whose transactions are executed without validating their signature
+    impersonated_accounts: HashSet<ContractAddress>,
+    /// State before the first block, used for replaying the transactions of the first block
+    pub(in crate::starknet) genesis_state: StarknetState,
+    /// Address of the L1 contract through which messages are exchanged with L1
+    l1_messaging_contract_address: Option<Felt>,
+    /// Nonces of the messages from L1 whose L1 handler has been executed successfully
+    pub(in crate::starknet) consumed_l1_to_l2_message_nonces: HashSet<Felt>,
+    /// Messages sent to L1 by committed transactions since the last flush
+    pub(in crate::starknet) messages_to_l1: Vec<MessageToL1>,
+    /// Invoke transactions waiting to be executed in parallel, in the order of their arrival
+    pub(in crate::starknet) queued_invokes: Vec<QueuedInvoke>,
+    /// Transactions sent in demand mode with a nonce ahead of the one expected from their sender,
+    /// waiting for the transactions of the nonces in between
+    pub(in crate::starknet) transactions_awaiting_nonce: Vec<Transaction>,
+    pub(in crate::starknet) parallel_execution_stats: ParallelExecutionStats,
+    /// Snapshots that can be reverted to, by their ids
+    pub(in crate::starknet) snapshots: BTreeMap<u64, Snapshot>,
+    /// Id of the next snapshot
+    pub(in crate::starknet) next_snapshot_id: u64,
+    /// Collected only if `enable_metrics` is set
+    pub(in crate::starknet) metrics: Metrics,
 }
 
 impl Starknet {
     pub fn new(config: &StarknetConfig) -> Result<Self> {
         let mut state = StarknetState::default();
         // deploy udc and erc20 contracts
-        let erc20_fee_contract = predeployed::create_erc20()?;
+        let erc20_fee_contract = predeployed::create_erc20(config.eth_fee_token_address)?;
+        let strk_erc20_fee_contract =
+            predeployed::create_strk_erc20(config.strk_fee_token_address)?;
         let udc_contract = predeployed::create_udc()?;
 
         erc20_fee_contract.deploy(&mut state)?;
-        initialize_erc20(&mut state)?;
+        initialize_erc20(
+            &mut state,
+            erc20_fee_contract.get_address(),
+            &config.eth_token_name,
+            &config.eth_token_symbol,
+            config.eth_token_decimals,
+        )?;
+
+        strk_erc20_fee_contract.deploy(&mut state)?;
+        initialize_erc20(
+            &mut state,
+            strk_erc20_fee_contract.get_address(),
+            &config.strk_token_name,
+            &config.strk_token_symbol,
+            config.strk_token_decimals,
+        )?;
 
         udc_contract.deploy(&mut state)?;
 
@@ -111,53 +580,176 @@ impl Starknet {
             config.predeployed_accounts_initial_balance,
             erc20_fee_contract.get_address(),
         );
-        let account_contract_class =
-            utils::load_cairo_0_contract_class(CAIRO_0_ACCOUNT_CONTRACT_PATH)?;
+        let account_contract_class = match (&config.account_class_custom, config.account_class) {
+            (Some(sierra_path), _) => utils::load_cairo_1_contract_class(sierra_path)?,
+            (None, AccountClass::Cairo0) => {
+                utils::load_cairo_0_contract_class(CAIRO_0_ACCOUNT_CONTRACT_PATH)?
+            }
+            (None, AccountClass::Cairo1) => {
+                utils::load_cairo_1_contract_class(CAIRO_1_ACCOUNT_CONTRACT_SIERRA_PATH)?
+            }
+        };
         let class_hash = account_contract_class.generate_hash()?;
 
-        let accounts = predeployed_accounts.generate_accounts(
-            config.total_accounts,
-            class_hash,
-            account_contract_class.clone(),
-        )?;
-        for account in accounts {
-            account.deploy(&mut state)?;
-            account.set_initial_balance(&mut state)?;
+        if !config.no_auto_accounts {
+            let accounts = predeployed_accounts.generate_accounts(
+                config.total_accounts,
+                class_hash,
+                account_contract_class.clone(),
+            )?;
+            for account in accounts {
+                account.deploy(&mut state)?;
+                account.set_initial_balance(&mut state)?;
+            }
         }
 
+        // minting signs transactions in the format of the Cairo 0 account
+        let chargeable_contract_class =
+            utils::load_cairo_0_contract_class(CAIRO_0_ACCOUNT_CONTRACT_PATH)?;
+        let chargeable_class_hash = chargeable_contract_class.generate_hash()?;
         let chargeable_account = Account::new_chargeable(
-            class_hash,
-            account_contract_class,
+            chargeable_class_hash,
+            chargeable_contract_class.clone(),
             erc20_fee_contract.get_address(),
         );
         chargeable_account.deploy(&mut state)?;
 
+        let mut predeployed_classes: HashMap<ClassHash, ContractClass> =
+            [&erc20_fee_contract, &strk_erc20_fee_contract, &udc_contract]
+                .into_iter()
+                .map(|contract| (contract.class_hash(), contract.contract_class().clone()))
+                .collect();
+        predeployed_classes.insert(chargeable_class_hash, chargeable_contract_class.clone());
+        // the standard classes are those of predeployed contracts, so they are declared anyway
+        let predeclared_classes = match config.predeclare {
+            Some(PredeclaredClassSet::Standard) => {
+                vec![("erc20", erc20_fee_contract.class_hash()), ("udc", udc_contract.class_hash())]
+            }
+            None => vec![],
+        };
+        // the class of generated accounts is declared only if some account is deployed with it
+        if !config.no_auto_accounts || config.predeployed_accounts_path.is_some() {
+            predeployed_classes.insert(class_hash, account_contract_class.clone());
+        }
+
+        if let Some(path) = &config.predeployed_accounts_path {
+            // listed accounts can be backed by the class of the generated accounts or by the
+            // Cairo 0 account class
+            let account_classes = HashMap::from([
+                (chargeable_class_hash, chargeable_contract_class),
+                (class_hash, account_contract_class),
+            ]);
+            let accounts = predeployed_accounts::load_accounts_from_file(
+                path,
+                &account_classes,
+                erc20_fee_contract.get_address(),
+            )?;
+            for account in accounts {
+                let is_taken = state
+                    .get_class_hash_at_contract_address(&account.account_address)
+                    .map_or(false, |class_hash| !class_hash.is_zero());
+                if is_taken {
+                    return Err(Error::PredeployedAccountsError {
+                        msg: format!(
+                            "Address {} is already taken",
+                            account.account_address.to_prefixed_hex_str()
+                        ),
+                    });
+                }
+
+                account.deploy(&mut state)?;
+                // listed accounts are funded in both fee tokens
+                account.set_initial_balance(&mut state)?;
+                account
+                    .set_initial_balance_in(&mut state, strk_erc20_fee_contract.get_address())?;
+                predeployed_accounts.add_account(account);
+            }
+        }
+
         // copy already modified state to cached state
         state.synchronize_states();
 
         let mut this = Self {
+            genesis_state: state.clone(),
             state,
             predeployed_accounts,
-            block_context: Self::get_block_context(0, ERC20_CONTRACT_ADDRESS, config.chain_id)?,
+            account_class_hash: class_hash,
+            block_context: Self::get_block_context(
+                config.gas_price,
+                &Felt::from(config.eth_fee_token_address).to_prefixed_hex_str(),
+                config.chain_id,
+                config.max_steps,
+            )?,
+            data_gas_price: config.data_gas_price,
             blocks: StarknetBlocks::default(),
             transactions: StarknetTransactions::default(),
             config: config.clone(),
             sierra_contracts: HashMap::new(),
+            predeployed_classes,
+            predeclared_classes,
+            new_blocks_notifier: NewBlocksNotifier::default(),
+            pending_transactions_notifier: PendingTransactionsNotifier::default(),
+            pending_block_timestamp_shift: 0,
+            impersonated_accounts: HashSet::new(),
+            l1_messaging_contract_address: None,
+            consumed_l1_to_l2_message_nonces: HashSet::new(),
+            messages_to_l1: vec![],
+            queued_invokes: vec![],
+            transactions_awaiting_nonce: vec![],
+            parallel_execution_stats: ParallelExecutionStats::default(),
+            snapshots: BTreeMap::new(),
+            next_snapshot_id: 1,
+            metrics: Metrics::default(),
         };
 
+        this.block_context.block_info_mut().block_number = config.initial_block_number;
+        this.block_context.block_info_mut().sequencer_address =
+            config.sequencer_address.try_into()?;
+        this.block_context.block_info_mut().block_timestamp =
+            config.initial_block_timestamp.unwrap_or(GENESIS_BLOCK_TIMESTAMP);
+        if let Some(timestamp) = config.initial_block_timestamp {
+            // later blocks advance from the initial timestamp, as if it was set with set_time
+            this.pending_block_timestamp_shift =
+                timestamp as i64 - Self::get_unix_timestamp_as_seconds() as i64;
+        }
         this.restart_pending_block()?;
+        // the genesis block holds only the predeployed state; the pending block after it takes the
+        // time of the system clock
+        this.commit_pending_block()?;
 
         Ok(this)
     }
 
+    /// Returns a receiver of every block created after the moment of subscribing
+    pub fn subscribe_to_new_blocks(&self) -> broadcast::Receiver<StarknetBlock> {
+        self.new_blocks_notifier.subscribe()
+    }
+
+    /// Returns a receiver of every transaction added to the pending block after the moment of
+    /// subscribing. A transaction is received before the block including it is created
+    pub fn subscribe_to_pending_transactions(&self) -> broadcast::Receiver<Transaction> {
+        self.pending_transactions_notifier.subscribe()
+    }
+
+    /// Returns a receiver of the hash of every transaction that is accepted, rejected or changes
+    /// its status after the moment of subscribing
+    pub fn subscribe_to_transaction_status_changes(&self) -> broadcast::Receiver<TransactionHash> {
+        self.transactions.subscribe_to_status_changes()
+    }
+
     pub fn get_predeployed_accounts(&self) -> Vec<Account> {
         self.predeployed_accounts.get_accounts().to_vec()
     }
 
+    /// Returns the balance of `account` in WEI at the latest block
+    pub fn get_account_balance(&self, account: &Account) -> Result<Balance> {
+        self.state.get_storage(account.balance_storage_key()?)
+    }
+
     // Update block context
     // Initialize values for new pending block
     pub(crate) fn generate_pending_block(&mut self) -> Result<()> {
-        Self::update_block_context(&mut self.block_context);
+        Self::update_block_context(&mut self.block_context, self.pending_block_timestamp_shift);
         self.restart_pending_block()?;
 
         Ok(())
@@ -172,7 +764,23 @@ impl Starknet {
     ) -> Result<BlockNumber> {
         let mut new_block = self.pending_block().clone();
 
+        // list transactions in the order of their intake, so that blocks collecting several
+        // transactions are reproducible
+        new_block.transactions.sort_by_key(|transaction| {
+            transaction
+                .get_hash()
+                .and_then(|transaction_hash| self.transactions.get_by_hash(transaction_hash))
+                .map_or(u64::MAX, |transaction| transaction.arrival_index())
+        });
+
         // set new block header
+        new_block.transaction_commitment =
+            commitments::transaction_commitment(new_block.get_transactions());
+        new_block.event_commitment = commitments::event_commitment(self, &new_block)?;
+        if !self.config.lite_mode {
+            new_block.header.state_root =
+                GlobalRoot(storage_proof::global_state_root(&state).into());
+        }
         new_block.set_block_hash(new_block.generate_hash()?);
         new_block.status = BlockStatus::AcceptedOnL2;
         let new_block_number = new_block.block_number();
@@ -184,6 +792,7 @@ impl Starknet {
                     tx.block_hash = Some(new_block.header.block_hash.0.into());
                     tx.block_number = Some(new_block_number);
                     tx.status = TransactionStatus::AcceptedOnL2;
+                    self.transactions.notify_status_change(&tx_hash);
                 } else {
                     error!("Transaction is not present in the transactions colletion");
                 }
@@ -192,48 +801,222 @@ impl Starknet {
             }
         });
 
+        // queue messages sent to L1 in the order their transactions are committed
+        for transaction_hash in new_block.get_transactions().iter().filter_map(|t| t.get_hash()) {
+            if let Some(execution_info) = self
+                .transactions
+                .get_by_hash(transaction_hash)
+                .and_then(|transaction| transaction.execution_info.as_ref())
+            {
+                self.messages_to_l1.extend(messaging::messages_of_transaction(execution_info)?);
+            }
+        }
+
         // insert pending block in the blocks collection and connect it to the state diff
         self.blocks.insert(new_block, state_diff);
         // save into blocks state archive
         self.blocks.save_state_at(new_block_number, state);
 
+        if let Some(block) = self.blocks.num_to_block.get(&new_block_number) {
+            self.new_blocks_notifier.notify(block);
+        }
+        self.persist_block(new_block_number);
+
         Ok(new_block_number)
     }
 
+    /// Rejects an executed transaction whose actual fee exceeds what it allows to be charged. Must
+    /// be checked before the transaction is accepted, so that its changes can still be reverted.
+    pub(crate) fn check_actual_fee(
+        &self,
+        transaction: &Transaction,
+        tx_info: TransactionExecutionInfo,
+    ) -> std::result::Result<TransactionExecutionInfo, TransactionError> {
+        estimations::check_actual_fee(
+            transaction,
+            tx_info,
+            self.gas_price_in(transaction.fee_unit()),
+            self.config.disable_fee_charge,
+        )
+    }
+
+    /// Executes a declare or deploy account `transaction` on the pending state. With
+    /// `--disable-fee-charge`, its max fee is not checked and its fee is not transferred.
+    pub(crate) fn execute_on_pending_state(
+        &mut self,
+        transaction: SirTransaction,
+        block_context: &BlockContext,
+    ) -> std::result::Result<TransactionExecutionInfo, TransactionError> {
+        let skip_fee_charge = self.config.disable_fee_charge;
+        let transaction = if skip_fee_charge {
+            transaction.create_for_simulation(false, false, skip_fee_charge, skip_fee_charge)
+        } else {
+            transaction
+        };
+
+        transaction.execute(&mut self.state.pending_state, block_context, INITIAL_GAS_COST)
+    }
+
     pub(crate) fn handle_successful_transaction(
         &mut self,
         transaction_hash: &TransactionHash,
         transaction: Transaction,
         tx_info: TransactionExecutionInfo,
+        data_gas_consumed: u128,
+        skip_validation: bool,
     ) -> Result<()> {
-        let transaction_to_add =
-            StarknetTransaction::create_successful(transaction.clone(), tx_info);
+        let mut transaction_to_add =
+            StarknetTransaction::create_successful(transaction.clone(), tx_info, data_gas_consumed);
+        transaction_to_add.skip_validation = skip_validation;
 
+        if self.config.enable_metrics {
+            self.metrics.record_accepted_transaction(&transaction);
+        }
+        // subscribers learn about the transaction before the block including it is created
+        self.pending_transactions_notifier.notify(&transaction);
         // add accepted transaction to pending block
         self.blocks.pending_block.add_transaction(transaction);
+        let block_number = self.blocks.pending_block.block_number();
 
         self.transactions.insert(transaction_hash, transaction_to_add);
 
+        // in demand mode, the changes stay in the pending state until a block is created
+        if self.config.block_generation_on == BlockGenerationOn::Transaction {
+            self.commit_pending_block()?;
+        }
+
+        info!(
+            transaction_hash = %transaction_hash.to_prefixed_hex_str(),
+            block_number = block_number.0,
+            "Accepted transaction"
+        );
+
+        self.dump_on_transaction();
+
+        Ok(())
+    }
+
+    /// Number of transactions in the pending block, queued ones included
+    fn pending_transactions_count(&self) -> u64 {
+        (self.blocks.pending_block.get_transactions().len() + self.queued_invokes.len()) as u64
+    }
+
+    /// Adds a transaction to the pending block with `add`. In demand mode with
+    /// `max_pending_transactions` set, a full pending block is either committed before and after
+    /// adding or causes the transaction to be rejected, as chosen with `pending_overflow`. The
+    /// check and the addition happen under the same borrow, so concurrent submissions can't
+    /// overshoot the limit.
+    fn add_within_pending_limit<T>(
+        &mut self,
+        add: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        self.handle_full_pending_block()?;
+        let started_at = Instant::now();
+        let added = add(self)?;
+        if self.config.enable_metrics {
+            self.metrics.record_execution_duration(started_at.elapsed());
+        }
+        if self.config.pending_overflow == PendingOverflow::Seal {
+            self.handle_full_pending_block()?;
+        }
+
+        Ok(added)
+    }
+
+    /// Refuses `count` transactions that the pending block can't take without exceeding
+    /// `max_pending_transactions`, if a full pending block causes transactions to be rejected
+    fn check_pending_block_room(&self, count: u64) -> Result<()> {
+        match self.config.max_pending_transactions {
+            Some(max_pending_transactions)
+                if self.config.block_generation_on == BlockGenerationOn::Demand
+                    && self.config.pending_overflow == PendingOverflow::Reject
+                    && self.pending_transactions_count() + count > max_pending_transactions =>
+            {
+                Err(Error::PendingBlockFull { max_pending_transactions })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn handle_full_pending_block(&mut self) -> Result<()> {
+        let max_pending_transactions = match self.config.max_pending_transactions {
+            Some(max) if self.config.block_generation_on == BlockGenerationOn::Demand => max,
+            _ => return Ok(()),
+        };
+        if self.pending_transactions_count() < max_pending_transactions {
+            return Ok(());
+        }
+
+        match self.config.pending_overflow {
+            PendingOverflow::Seal => self.commit_pending_block().map(|_| ()),
+            PendingOverflow::Reject => Err(Error::PendingBlockFull { max_pending_transactions }),
+        }
+    }
+
+    /// Applies the changes of the pending state and generates a new block from the pending one,
+    /// holding all the transactions added since the previous block. Returns the new block number
+    fn commit_pending_block(&mut self) -> Result<BlockNumber> {
+        self.execute_queued_invokes()?;
+
         let state_difference = self.state.extract_state_diff_from_pending_state()?;
         // apply state changes from cached state
         self.state.apply_state_difference(state_difference.clone())?;
         // make cached state part of "persistent" state
         self.state.synchronize_states();
         // create new block from pending one
-        self.generate_new_block(state_difference, self.state.clone())?;
+        let block_number = self.generate_new_block(state_difference, self.state.clone())?;
         // clear pending block information
         self.generate_pending_block()?;
+        self.store_historical_block_hash()?;
+
+        Ok(block_number)
+    }
+
+    /// Stores the hash of the block `STORED_BLOCK_HASH_BUFFER` blocks behind the pending one in
+    /// the pending state, where the `get_block_hash` syscall reads it from, as the Starknet OS does
+    /// at the beginning of every block. Hashes of more recent blocks are never stored, so the
+    /// syscall gets 0 for them. Nothing is stored for blocks that are not known to Devnet, e.g.
+    /// blocks of a forked network.
+    fn store_historical_block_hash(&mut self) -> Result<()> {
+        let pending_block_number = self.block_context.block_info().block_number;
+        let Some(block_number) = pending_block_number.checked_sub(STORED_BLOCK_HASH_BUFFER) else {
+            return Ok(());
+        };
+        let Some(block) = self.blocks.get_by_block_id(BlockId::Number(block_number)) else {
+            return Ok(());
+        };
+
+        let block_hash_contract_address =
+            Address(Felt::from_prefixed_hex_str(BLOCK_HASH_CONTRACT_ADDRESS)?.into());
+        self.state.pending_state.set_storage_at(
+            &(block_hash_contract_address, Felt::from(block_number as u128).bytes()),
+            block.block_hash().into(),
+        );
 
         Ok(())
     }
 
+    /// Chain id seen by executed contracts. The execution environment only knows the networks
+    /// below; on any other one contracts see the chain id of the testnet, while transaction hashes
+    /// are still computed with the configured chain id.
+    fn os_chain_id(chain_id: ChainId) -> StarknetChainId {
+        match chain_id {
+            ChainId::MAINNET => StarknetChainId::MainNet,
+            ChainId::TESTNET2 => StarknetChainId::TestNet2,
+            _ => StarknetChainId::TestNet,
+        }
+    }
+
+    /// Context in which transactions and calls execute at most `max_steps` Cairo steps. The
+    /// validation of a transaction is also limited to the steps allowed on the Starknet networks.
     fn get_block_context(
         gas_price: u64,
         fee_token_address: &str,
-        chain_id: StarknetChainId,
+        chain_id: ChainId,
+        max_steps: u64,
     ) -> Result<BlockContext> {
         let starknet_os_config = StarknetOsConfig::new(
-            chain_id,
+            Self::os_chain_id(chain_id),
             starknet_in_rust::utils::Address(
                 Felt::from_prefixed_hex_str(fee_token_address)?.into(),
             ),
@@ -248,8 +1031,8 @@ impl Starknet {
             DEFAULT_CONTRACT_STORAGE_COMMITMENT_TREE_HEIGHT,
             DEFAULT_GLOBAL_STATE_COMMITMENT_TREE_HEIGHT,
             DEFAULT_CAIRO_RESOURCE_FEE_WEIGHTS.clone(),
-            DEFAULT_INVOKE_TX_MAX_N_STEPS,
-            DEFAULT_VALIDATE_MAX_N_STEPS,
+            max_steps,
+            DEFAULT_VALIDATE_MAX_N_STEPS.min(max_steps),
             block_info,
             HashMap::default(),
             true,
@@ -258,16 +1041,102 @@ impl Starknet {
         Ok(block_context)
     }
 
-    /// Should update block context with new block timestamp
-    /// and pointer to the next block number
-    fn update_block_context(block_context: &mut BlockContext) {
-        let current_timestamp_secs = SystemTime::now()
+    /// Context of the current block in which fees are charged in the token of `unit`, at the
+    /// price of gas in `unit`
+    pub(crate) fn block_context_for(&self, unit: FeeUnit) -> Result<BlockContext> {
+        if unit == FeeUnit::Wei {
+            return Ok(self.block_context.clone());
+        }
+
+        let mut block_context = Self::get_block_context(
+            self.block_context.block_info().gas_price,
+            &Felt::from(self.fee_token_address(unit)).to_prefixed_hex_str(),
+            self.config.chain_id,
+            self.config.max_steps,
+        )?;
+        *block_context.block_info_mut() = self.block_context.block_info().clone();
+        block_context.block_info_mut().gas_price = self.to_fri(self.gas_price());
+
+        Ok(block_context)
+    }
+
+    /// Price of L1 gas of the pending block in `unit`
+    pub(crate) fn gas_price_in(&self, unit: FeeUnit) -> u128 {
+        match unit {
+            FeeUnit::Wei => self.gas_price() as u128,
+            FeeUnit::Fri => self.to_fri(self.gas_price()) as u128,
+        }
+    }
+
+    /// Price of L1 data gas of the pending block in `unit`
+    pub(crate) fn data_gas_price_in(&self, unit: FeeUnit) -> u128 {
+        match unit {
+            FeeUnit::Wei => self.data_gas_price as u128,
+            FeeUnit::Fri => self.to_fri(self.data_gas_price) as u128,
+        }
+    }
+
+    /// Prices of L1 gas and L1 data gas in `unit` at `block_id`. A block that was already created
+    /// keeps the prices it was created with; tags refer to the prices of the pending block.
+    pub(crate) fn gas_prices_at(&self, block_id: &BlockId, unit: FeeUnit) -> Result<(u128, u128)> {
+        if let BlockId::Tag(_) = block_id {
+            return Ok((self.gas_price_in(unit), self.data_gas_price_in(unit)));
+        }
+
+        let block = self.blocks.get_by_block_id(*block_id).ok_or(Error::NoBlock)?;
+        Ok(match unit {
+            FeeUnit::Wei => (block.l1_gas_price().0, block.l1_data_gas_price().0),
+            FeeUnit::Fri => (block.l1_gas_price_in_fri().0, block.l1_data_gas_price_in_fri().0),
+        })
+    }
+
+    /// Same as [`Starknet::block_context_for`], but if `block_id` refers to a block that was
+    /// already created, the number, timestamp and gas price are those of that block, so that
+    /// transactions are executed as if they were part of it
+    pub(crate) fn block_context_at(
+        &self,
+        block_id: &BlockId,
+        unit: FeeUnit,
+    ) -> Result<BlockContext> {
+        let mut block_context = self.block_context_for(unit)?;
+        if let BlockId::Tag(_) = block_id {
+            return Ok(block_context);
+        }
+
+        let block = self.blocks.get_by_block_id(*block_id).ok_or(Error::NoBlock)?;
+        let (gas_price, _) = self.gas_prices_at(block_id, unit)?;
+        let block_info = block_context.block_info_mut();
+        block_info.block_number = block.block_number().0;
+        block_info.block_timestamp = block.timestamp().0;
+        block_info.gas_price = gas_price as u64;
+
+        Ok(block_context)
+    }
+
+    /// Converts a price in wei to fri at the configured rate
+    fn to_fri(&self, price_in_wei: u64) -> u64 {
+        price_in_wei.saturating_mul(self.config.eth_strk_rate)
+    }
+
+    /// Should update block context with new block timestamp, shifted by `timestamp_shift`
+    /// seconds, and pointer to the next block number
+    fn update_block_context(block_context: &mut BlockContext, timestamp_shift: i64) {
+        block_context.block_info_mut().block_number = block_context.block_info().block_number + 1;
+        block_context.block_info_mut().block_timestamp =
+            Self::get_unix_timestamp_as_seconds().saturating_add_signed(timestamp_shift);
+    }
+
+    fn get_unix_timestamp_as_seconds() -> u64 {
+        SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .expect("should get current UNIX timestamp")
-            .as_secs();
+            .as_secs()
+    }
 
-        block_context.block_info_mut().block_number = block_context.block_info().block_number + 1;
-        block_context.block_info_mut().block_timestamp = current_timestamp_secs;
+    /// Sets the timestamp of the pending block and of the block context used for execution
+    fn set_pending_block_timestamp(&mut self, timestamp: u64) -> Result<()> {
+        self.block_context.block_info_mut().block_timestamp = timestamp;
+        self.restart_pending_block()
     }
 
     fn pending_block(&self) -> &StarknetBlock {
@@ -280,18 +1149,40 @@ impl Starknet {
 
         block.header.block_number = BlockNumber(self.block_context.block_info().block_number);
         block.header.gas_price = GasPrice(self.block_context.block_info().gas_price.into());
+        block.l1_data_gas_price = GasPrice(self.data_gas_price.into());
+        block.l1_gas_price_in_fri = GasPrice(self.gas_price_in(FeeUnit::Fri));
+        block.l1_data_gas_price_in_fri = GasPrice(self.data_gas_price_in(FeeUnit::Fri));
         block.header.sequencer =
             ContractAddress::try_from(self.block_context.block_info().sequencer_address.clone())?
                 .try_into()?;
         block.header.timestamp = BlockTimestamp(self.block_context.block_info().block_timestamp);
+        if let Some(last_block_hash) = self.blocks.last_block_hash {
+            block.header.parent_hash = last_block_hash.into();
+        }
 
         self.blocks.pending_block = block;
 
         Ok(())
     }
 
+    /// Returns the state a query at `block_id` is executed against. The current state holds the
+    /// changes of the pending block, which in demand mode are not committed yet, so only pending
+    /// queries see them; latest queries see the state of the last created block instead.
     fn get_state_at(&self, block_id: &BlockId) -> Result<&StarknetState> {
         match block_id {
+            BlockId::Tag(BlockTag::Latest)
+                if self.config.block_generation_on == BlockGenerationOn::Demand =>
+            {
+                match self.blocks.get_by_block_id(*block_id) {
+                    Some(block) => self
+                        .blocks
+                        .num_to_state
+                        .get(&block.block_number())
+                        .ok_or(Error::NoStateAtBlock { block_number: block.block_number().0 }),
+                    // no block was created yet, so nothing is committed beyond genesis
+                    None => Ok(&self.genesis_state),
+                }
+            }
             BlockId::Tag(_) => Ok(&self.state),
             _ => {
                 let block = self.blocks.get_by_block_id(*block_id).ok_or(Error::NoBlock)?;
@@ -312,9 +1203,57 @@ impl Starknet {
     ) -> Result<Felt> {
         let state = self.get_state_at(block_id)?;
         let address: Address = contract_address.try_into()?;
-        match state.state.address_to_class_hash.get(&address) {
-            Some(class_hash) => Ok(Felt::from(*class_hash)),
-            None => Err(Error::ContractNotFound),
+        state.view().get_class_hash_at(&address)?.ok_or(Error::ContractNotFound)
+    }
+
+    /// Hashes of the standard classes declared at genesis, by class name, if Devnet was started
+    /// with a set of predeclared classes
+    pub fn predeclared_classes(&self) -> &[(&'static str, ClassHash)] {
+        &self.predeclared_classes
+    }
+
+    /// Returns the definition of the class with `class_hash`, if the class is declared at
+    /// `block_id`. Besides the predeployed classes, these are the classes declared by transactions
+    /// of the blocks up to `block_id` and, if Devnet is forked, the classes of the forked network.
+    pub fn get_class(&self, block_id: BlockId, class_hash: ClassHash) -> Result<ContractClass> {
+        let last_block_number = match block_id {
+            BlockId::Tag(_) => None,
+            _ => Some(self.blocks.get_by_block_id(block_id).ok_or(Error::NoBlock)?.block_number()),
+        };
+        if let Some(contract_class) = self.predeployed_classes.get(&class_hash) {
+            return Ok(contract_class.clone());
+        }
+
+        // in demand mode, only pending queries see the classes declared in the pending block
+        let pending_block = match block_id {
+            BlockId::Tag(BlockTag::Pending) => Some(&self.blocks.pending_block),
+            _ => None,
+        };
+        let declared_class = self
+            .blocks
+            .num_to_block
+            .values()
+            .filter(|block| last_block_number.map_or(true, |number| block.block_number() <= number))
+            .chain(pending_block)
+            .flat_map(|block| block.get_transactions())
+            .find_map(|transaction| match transaction {
+                Transaction::Declare(declare) if declare.class_hash() == Some(&class_hash) => {
+                    Some(declare.contract_class.clone())
+                }
+                Transaction::DeclareV2(declare) if declare.class_hash() == Some(&class_hash) => {
+                    Some(declare.sierra_contract_class.clone())
+                }
+                Transaction::DeclareV3(declare) if declare.class_hash() == Some(&class_hash) => {
+                    Some(declare.sierra_contract_class.clone())
+                }
+                _ => None,
+            });
+
+        match (declared_class, &self.state.fork) {
+            (Some(contract_class), _) => Ok(contract_class),
+            // the classes of the forked network are declared at every local block
+            (None, Some(fork)) => fork.get_class(class_hash)?.ok_or(Error::ClassHashNotFound),
+            (None, None) => Err(Error::ClassHashNotFound),
         }
     }
 
@@ -340,18 +1279,87 @@ impl Starknet {
         Ok(result.iter().map(|e| Felt::from(e.clone())).collect())
     }
 
+    /// In permissive calldata validation, appends guidance to the `reason` of a failed call or
+    /// transaction if its calldata didn't match the parameters of the called entry point
+    pub fn explain_failure(&self, reason: String) -> String {
+        match self.config.calldata_validation {
+            CalldataValidation::Strict => reason,
+            CalldataValidation::Permissive => revert_reason::with_calldata_guidance(reason),
+        }
+    }
+
     pub fn add_declare_transaction_v1(
         &mut self,
-        declare_transaction: DeclareTransactionV1,
+        mut declare_transaction: DeclareTransactionV1,
     ) -> Result<(TransactionHash, ClassHash)> {
-        add_declare_transaction::add_declare_transaction_v1(self, declare_transaction)
+        self.add_within_pending_limit(|starknet| {
+            starknet.execute_queued_invokes()?;
+            let sender_address = declare_transaction.sender_address;
+            if starknet.awaits_preceding_nonces(sender_address, declare_transaction.nonce)? {
+                let class_hash = declare_transaction.contract_class.generate_hash()?;
+                let transaction_hash = declare_transaction.generate_hash()?;
+                declare_transaction.class_hash = Some(class_hash);
+                declare_transaction.transaction_hash = Some(transaction_hash);
+                starknet
+                    .await_preceding_nonces(Transaction::Declare(Box::new(declare_transaction)));
+                return Ok((transaction_hash, class_hash));
+            }
+
+            let added =
+                add_declare_transaction::add_declare_transaction_v1(starknet, declare_transaction)?;
+            starknet.add_transactions_awaiting_nonce(sender_address)?;
+            Ok(added)
+        })
     }
 
     pub fn add_declare_transaction_v2(
         &mut self,
-        declare_transaction: DeclareTransactionV2,
+        mut declare_transaction: DeclareTransactionV2,
+    ) -> Result<(TransactionHash, ClassHash)> {
+        self.add_within_pending_limit(|starknet| {
+            starknet.execute_queued_invokes()?;
+            let sender_address = declare_transaction.sender_address;
+            if starknet.awaits_preceding_nonces(sender_address, declare_transaction.nonce)? {
+                let sir_declare = declare_transaction.create_sir_declare()?;
+                let class_hash: ClassHash = sir_declare.sierra_class_hash.into();
+                let transaction_hash: TransactionHash = sir_declare.hash_value.into();
+                declare_transaction.class_hash = Some(class_hash);
+                declare_transaction.transaction_hash = Some(transaction_hash);
+                starknet
+                    .await_preceding_nonces(Transaction::DeclareV2(Box::new(declare_transaction)));
+                return Ok((transaction_hash, class_hash));
+            }
+
+            let added =
+                add_declare_transaction::add_declare_transaction_v2(starknet, declare_transaction)?;
+            starknet.add_transactions_awaiting_nonce(sender_address)?;
+            Ok(added)
+        })
+    }
+
+    pub fn add_declare_transaction_v3(
+        &mut self,
+        mut declare_transaction: DeclareTransactionV3,
     ) -> Result<(TransactionHash, ClassHash)> {
-        add_declare_transaction::add_declare_transaction_v2(self, declare_transaction)
+        self.add_within_pending_limit(|starknet| {
+            starknet.execute_queued_invokes()?;
+            let sender_address = declare_transaction.sender_address;
+            if starknet.awaits_preceding_nonces(sender_address, declare_transaction.nonce)? {
+                let sir_declare = declare_transaction.create_sir_declare()?;
+                let class_hash: ClassHash = sir_declare.sierra_class_hash.into();
+                let transaction_hash: TransactionHash = sir_declare.hash_value.into();
+                declare_transaction.class_hash = Some(class_hash);
+                declare_transaction.transaction_hash = Some(transaction_hash);
+                starknet
+                    .await_preceding_nonces(Transaction::DeclareV3(Box::new(declare_transaction)));
+                return Ok((transaction_hash, class_hash));
+            }
+
+            let added =
+                add_declare_transaction::add_declare_transaction_v3(starknet, declare_transaction)?;
+            starknet.add_transactions_awaiting_nonce(sender_address)?;
+            Ok(added)
+        })
     }
 
     /// returning the block number that will be added, ie. the most recent accepted block number
@@ -361,96 +1369,844 @@ impl Starknet {
     }
 
     /// returning the chain id as object
-    pub fn chain_id(&self) -> StarknetChainId {
+    pub fn chain_id(&self) -> ChainId {
         self.config.chain_id
     }
 
+    /// Hash of the class of the generated predeployed accounts, as chosen with `account_class` or
+    /// `account_class_custom`
+    pub fn account_class_hash(&self) -> ClassHash {
+        self.account_class_hash
+    }
+
+    /// Price of L1 gas in wei of the pending block, as set by `gas_price` or changed since
+    pub fn gas_price(&self) -> u64 {
+        self.block_context.block_info().gas_price
+    }
+
     pub fn add_deploy_account_transaction(
         &mut self,
         deploy_account_transaction: DeployAccountTransaction,
     ) -> Result<(TransactionHash, ContractAddress)> {
-        add_deploy_account_transaction::add_deploy_account_transaction(
-            self,
-            deploy_account_transaction,
-        )
+        self.add_within_pending_limit(|starknet| {
+            starknet.execute_queued_invokes()?;
+            add_deploy_account_transaction::add_deploy_account_transaction(
+                starknet,
+                deploy_account_transaction,
+            )
+        })
+    }
+
+    pub fn add_deploy_account_transaction_v3(
+        &mut self,
+        deploy_account_transaction: DeployAccountTransactionV3,
+    ) -> Result<(TransactionHash, ContractAddress)> {
+        self.add_within_pending_limit(|starknet| {
+            starknet.execute_queued_invokes()?;
+            add_deploy_account_transaction::add_deploy_account_transaction_v3(
+                starknet,
+                deploy_account_transaction,
+            )
+        })
     }
 
     pub fn add_invoke_transaction_v1(
         &mut self,
         invoke_transaction: InvokeTransactionV1,
     ) -> Result<TransactionHash> {
-        add_invoke_transaction::add_invoke_transcation_v1(self, invoke_transaction)
+        self.add_within_pending_limit(|starknet| {
+            let sender_address = invoke_transaction.sender_address()?;
+            if starknet.awaits_preceding_nonces(sender_address, invoke_transaction.nonce)? {
+                let transaction_hash = invoke_transaction.generate_hash()?;
+                starknet.await_preceding_nonces(Transaction::Invoke(Box::new(invoke_transaction)));
+                return Ok(transaction_hash);
+            }
+
+            let transaction_hash = if starknet.queues_invokes() {
+                add_invoke_transaction::queue_invoke_transaction_v1(starknet, invoke_transaction)?
+            } else {
+                add_invoke_transaction::add_invoke_transcation_v1(starknet, invoke_transaction)?
+            };
+            starknet.add_transactions_awaiting_nonce(sender_address)?;
+            Ok(transaction_hash)
+        })
     }
 
-    /// Creates an invoke tx for minting, using the chargeable account.
-    pub async fn mint(&mut self, address: ContractAddress, amount: u128) -> Result<Felt> {
-        let sufficiently_big_max_fee: u128 = self.config.gas_price as u128 * 1_000_000;
-        let chargeable_address_felt = Felt::from_prefixed_hex_str(CHARGEABLE_ACCOUNT_ADDRESS)?;
-        let nonce =
-            self.state.pending_state.get_nonce_at(&Address(chargeable_address_felt.into()))?;
+    pub fn add_invoke_transaction_v3(
+        &mut self,
+        invoke_transaction: InvokeTransactionV3,
+    ) -> Result<TransactionHash> {
+        self.add_within_pending_limit(|starknet| {
+            starknet.execute_queued_invokes()?;
+            let sender_address = invoke_transaction.sender_address()?;
+            if starknet.awaits_preceding_nonces(sender_address, invoke_transaction.nonce)? {
+                let transaction_hash = invoke_transaction.generate_hash()?;
+                starknet
+                    .await_preceding_nonces(Transaction::InvokeV3(Box::new(invoke_transaction)));
+                return Ok(transaction_hash);
+            }
 
-        let calldata = vec![
-            Felt::from(address).into(),
-            FieldElement::from(amount), // `low` part of Uint256
-            FieldElement::from(0u32),   // `high` part
-        ];
+            let transaction_hash =
+                add_invoke_transaction::add_invoke_transaction_v3(starknet, invoke_transaction)?;
+            starknet.add_transactions_awaiting_nonce(sender_address)?;
+            Ok(transaction_hash)
+        })
+    }
 
-        let erc20_address_felt = Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS)?;
-        let raw_execution = RawExecution {
-            calls: vec![Call {
-                to: erc20_address_felt.into(),
-                selector: get_selector_from_name("mint").unwrap(),
-                calldata: calldata.clone(),
-            }],
-            nonce: Felt::from(nonce.clone()).into(),
-            max_fee: FieldElement::from(sufficiently_big_max_fee),
-        };
+    pub fn add_l1_handler_transaction(
+        &mut self,
+        l1_handler_transaction: L1HandlerTransaction,
+    ) -> Result<TransactionHash> {
+        self.add_within_pending_limit(|starknet| {
+            starknet.execute_queued_invokes()?;
+            add_l1_handler_transaction::add_l1_handler_transaction(starknet, l1_handler_transaction)
+        })
+    }
 
-        // generate msg hash (not the same as tx hash)
-        let chain_id_felt: Felt = self.config.chain_id.to_felt().into();
-        let msg_hash_felt =
-            raw_execution.transaction_hash(chain_id_felt.into(), chargeable_address_felt.into());
+    /// Registers the address of the L1 contract used for messaging with L1
+    pub fn load_l1_messaging_contract(&mut self, contract_address: Felt) {
+        self.l1_messaging_contract_address = Some(contract_address);
+    }
 
-        // generate signature by signing the msg hash
-        let signer = starknet_rs_signers::LocalWallet::from(
-            starknet_rs_signers::SigningKey::from_secret_scalar(
-                FieldElement::from_hex_be(CHARGEABLE_ACCOUNT_PRIVATE_KEY).unwrap(),
-            ),
-        );
-        let signature = signer.sign_hash(&msg_hash_felt).await?;
+    /// Address of the fee token denominated in `unit`
+    pub fn fee_token_address(&self, unit: FeeUnit) -> ContractAddress {
+        match unit {
+            FeeUnit::Wei => self.config.eth_fee_token_address,
+            FeeUnit::Fri => self.config.strk_fee_token_address,
+        }
+    }
 
-        // apply the invoke tx
-        let invoke_tx = InvokeTransactionV1::new(
-            ContractAddress::new(chargeable_address_felt)?,
-            sufficiently_big_max_fee,
-            vec![signature.r.into(), signature.s.into()],
+    /// Balance of `address` in the fee token denominated in `unit`, as returned by its `balanceOf`
+    pub fn get_token_balance(
+        &self,
+        block_id: BlockId,
+        address: ContractAddress,
+        unit: FeeUnit,
+    ) -> Result<BigUint> {
+        let balance = self.call(
+            block_id,
+            Felt::from(self.fee_token_address(unit)),
+            get_selector_from_name("balanceOf").unwrap().into(),
+            vec![Felt::from(address)],
+        )?;
+
+        // the balance is a Uint256, returned as low and high 128 bits
+        match balance.as_slice() {
+            [low, high] => Ok((BigUint::from(*high) << 128) + BigUint::from(*low)),
+            _ => Err(Error::FeeTokenError {
+                msg: format!("Invalid balance returned by balanceOf: {balance:?}"),
+            }),
+        }
+    }
+
+    pub fn l1_messaging_contract_address(&self) -> Option<Felt> {
+        self.l1_messaging_contract_address
+    }
+
+    /// Executes the L1 handler consuming `message`, as if the message had been sent from L1.
+    /// Returns the hash of the transaction and the hash of the message
+    pub fn send_message_to_l2(
+        &mut self,
+        message: MessageToL2,
+    ) -> Result<(TransactionHash, String)> {
+        messaging::send_message_to_l2(self, message)
+    }
+
+    /// Messages sent to L1 by committed transactions since the last flush
+    pub fn messages_to_l1(&self) -> &[MessageToL1] {
+        &self.messages_to_l1
+    }
+
+    /// Returns the messages sent to L1 since the last flush, in the order of their transactions,
+    /// and forgets them
+    pub fn flush_messages_to_l1(&mut self) -> Vec<MessageToL1> {
+        messaging::flush_messages_to_l1(self)
+    }
+
+    /// Creates an invoke tx for minting, using the chargeable account.
+    /// Mints `amount` of the fee token denominated in `unit` to `address`
+    pub async fn mint(
+        &mut self,
+        address: ContractAddress,
+        amount: BigUint,
+        unit: FeeUnit,
+    ) -> Result<Felt> {
+        if amount.bits() > 256 {
+            return Err(Error::UnsupportedAction {
+                msg: format!("Minted amount {amount} doesn't fit in 256 bits"),
+            });
+        }
+
+        // the mint is never queued, since the minted balance is read right after it, but it is
+        // executed after the queued transactions
+        self.execute_queued_invokes()?;
+
+        let call = self.mint_call(address, &amount, unit)?;
+        self.invoke_with_chargeable_account(vec![call]).await
+    }
+
+    /// Mints each `(address, amount, unit)` of `mints` with a single invoke tx of the chargeable
+    /// account, so that either all or none of them are applied. The batch is rejected before
+    /// anything is executed if it would take a balance beyond 256 bits, and fails if the
+    /// transaction reverts. Returns the hash of the transaction
+    pub async fn mint_batch(
+        &mut self,
+        mints: &[(ContractAddress, BigUint, FeeUnit)],
+    ) -> Result<TransactionHash> {
+        if mints.is_empty() {
+            return Err(Error::UnsupportedAction { msg: "Nothing to mint".to_string() });
+        }
+
+        self.execute_queued_invokes()?;
+
+        let mut new_balances: HashMap<(ContractAddress, FeeUnit), BigUint> = HashMap::new();
+        for (address, amount, unit) in mints {
+            let new_balance = match new_balances.entry((*address, *unit)) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.get_token_balance(
+                    BlockId::Tag(BlockTag::Pending),
+                    *address,
+                    *unit,
+                )?),
+            };
+            *new_balance += amount;
+            if new_balance.bits() > 256 {
+                return Err(Error::UnsupportedAction {
+                    msg: format!(
+                        "Minting {amount} to {} would take its balance beyond 256 bits",
+                        address.to_prefixed_hex_str()
+                    ),
+                });
+            }
+        }
+
+        let calls = mints
+            .iter()
+            .map(|(address, amount, unit)| self.mint_call(*address, amount, *unit))
+            .collect::<Result<Vec<Call>>>()?;
+        let transaction_hash = self.invoke_with_chargeable_account(calls).await?;
+
+        let revert_error = self
+            .transactions
+            .get_by_hash(transaction_hash)
+            .and_then(|transaction| transaction.execution_info.as_ref())
+            .and_then(|execution_info| execution_info.revert_error.clone());
+        if let Some(revert_error) = revert_error {
+            return Err(Error::MintingReverted { transaction_hash, revert_error });
+        }
+
+        Ok(transaction_hash)
+    }
+
+    /// Call of the `mint` function of the fee token denominated in `unit`, which must be given an
+    /// amount of at most 256 bits
+    fn mint_call(&self, address: ContractAddress, amount: &BigUint, unit: FeeUnit) -> Result<Call> {
+        let amount_low = Felt::try_from(amount & BigUint::from(u128::MAX))?;
+        let amount_high = Felt::try_from(amount >> 128)?;
+
+        let erc20_address_felt = Felt::from(self.fee_token_address(unit));
+        Ok(Call {
+            to: erc20_address_felt.into(),
+            selector: get_selector_from_name("mint").unwrap(),
+            calldata: vec![
+                Felt::from(address).into(),
+                amount_low.into(),  // `low` part of Uint256
+                amount_high.into(), // `high` part
+            ],
+        })
+    }
+
+    /// Executes `calls` with an invoke tx signed on behalf of the chargeable account, whose max fee
+    /// covers any number of mints
+    async fn invoke_with_chargeable_account(&mut self, calls: Vec<Call>) -> Result<Felt> {
+        let sufficiently_big_max_fee: u128 =
+            self.block_context.block_info().gas_price as u128 * 1_000_000 * calls.len() as u128;
+        let chargeable_address_felt = Felt::from_prefixed_hex_str(CHARGEABLE_ACCOUNT_ADDRESS)?;
+        let nonce =
+            self.state.pending_state.get_nonce_at(&Address(chargeable_address_felt.into()))?;
+
+        let raw_execution = RawExecution {
+            calls,
+            nonce: Felt::from(nonce.clone()).into(),
+            max_fee: FieldElement::from(sufficiently_big_max_fee),
+        };
+
+        // generate msg hash (not the same as tx hash)
+        let chain_id_felt = self.config.chain_id.to_felt();
+        let msg_hash_felt =
+            raw_execution.transaction_hash(chain_id_felt.into(), chargeable_address_felt.into());
+
+        // generate signature by signing the msg hash
+        let signer = starknet_rs_signers::LocalWallet::from(
+            starknet_rs_signers::SigningKey::from_secret_scalar(
+                FieldElement::from_hex_be(CHARGEABLE_ACCOUNT_PRIVATE_KEY).unwrap(),
+            ),
+        );
+        let signature = signer.sign_hash(&msg_hash_felt).await?;
+
+        // apply the invoke tx
+        let invoke_tx = InvokeTransactionV1::new(
+            ContractAddress::new(chargeable_address_felt)?,
+            sufficiently_big_max_fee,
+            vec![signature.r.into(), signature.s.into()],
             nonce.into(),
             raw_execution.raw_calldata().into_iter().map(|c| c.into()).collect(),
             chain_id_felt,
         )?;
-        self.add_invoke_transaction_v1(invoke_tx)
+        self.add_within_pending_limit(|starknet| {
+            add_invoke_transaction::add_invoke_transcation_v1(starknet, invoke_tx)
+        })
+    }
+
+    /// Declares `sierra_contract_class`, whose compilation is `casm_contract_class`, with a
+    /// declare transaction of version 2 signed on behalf of the predeployed account at
+    /// `account_address`, or of the first predeployed account if no address is given. The max fee
+    /// is the whole WEI balance of the account, so that the declaration fails only if the account
+    /// can't pay for it.
+    pub async fn declare_with_predeployed_account(
+        &mut self,
+        account_address: Option<ContractAddress>,
+        sierra_contract_class: SierraContractClass,
+        casm_contract_class: &CasmContractClass,
+    ) -> Result<(TransactionHash, ClassHash)> {
+        let (account, private_key) =
+            self.predeployed_account_with_private_key(account_address, "Declaring")?;
+
+        // the nonce of the account may be changed by queued transactions
+        self.execute_queued_invokes()?;
+
+        let compiled_class_hash: ClassHash = compute_casm_class_hash(casm_contract_class)
+            .map_err(starknet_types::error::Error::from)?
+            .into();
+        let balance = self.get_token_balance(
+            BlockId::Tag(BlockTag::Pending),
+            account.account_address,
+            FeeUnit::Wei,
+        )?;
+        let nonce = self
+            .state
+            .pending_state
+            .get_nonce_at(&Address(Felt::from(account.account_address).into()))?;
+
+        let mut declare_transaction = DeclareTransactionV2::new(
+            sierra_contract_class.into(),
+            compiled_class_hash,
+            account.account_address,
+            u128::try_from(balance).unwrap_or(u128::MAX),
+            vec![],
+            nonce.into(),
+            self.config.chain_id.to_felt(),
+        )?;
+
+        let signer = starknet_rs_signers::LocalWallet::from(
+            starknet_rs_signers::SigningKey::from_secret_scalar(private_key.into()),
+        );
+        let signature = signer.sign_hash(&declare_transaction.generate_hash()?.into()).await?;
+        declare_transaction.signature = vec![signature.r.into(), signature.s.into()];
+
+        self.add_within_pending_limit(|starknet| {
+            add_declare_transaction::add_declare_transaction_v2(starknet, declare_transaction)
+        })
+    }
+
+    /// Deploys an instance of the declared class `class_hash` through the UDC, with an invoke
+    /// transaction of version 1 signed on behalf of the first predeployed account. The instance is
+    /// not unique, so its address is computed by [`calculate_deployment_address`] from `salt`,
+    /// `class_hash` and `constructor_calldata` with deployer address 0. The max fee is the whole
+    /// WEI balance of the account, as in [`Starknet::declare_with_predeployed_account`].
+    pub async fn deploy_with_predeployed_account(
+        &mut self,
+        class_hash: ClassHash,
+        constructor_calldata: Vec<Felt>,
+        salt: Felt,
+    ) -> Result<(TransactionHash, ContractAddress)> {
+        let (account, private_key) =
+            self.predeployed_account_with_private_key(None, "Deploying")?;
+
+        // the class may be declared and the nonce of the account changed by queued transactions
+        self.execute_queued_invokes()?;
+        self.get_class(BlockId::Tag(BlockTag::Pending), class_hash)?;
+
+        let contract_address =
+            calculate_deployment_address(class_hash, &constructor_calldata, salt, None)?;
+
+        let mut udc_calldata = vec![
+            class_hash.into(),
+            salt.into(),
+            FieldElement::ZERO, // not unique
+            FieldElement::from(constructor_calldata.len()),
+        ];
+        udc_calldata.extend(constructor_calldata.into_iter().map(FieldElement::from));
+
+        let balance = self.get_token_balance(
+            BlockId::Tag(BlockTag::Pending),
+            account.account_address,
+            FeeUnit::Wei,
+        )?;
+        let max_fee = u128::try_from(balance).unwrap_or(u128::MAX);
+        let nonce: Felt = self
+            .state
+            .pending_state
+            .get_nonce_at(&Address(Felt::from(account.account_address).into()))?
+            .into();
+        let raw_execution = RawExecution {
+            calls: vec![Call {
+                to: Felt::from_prefixed_hex_str(UDC_CONTRACT_ADDRESS)?.into(),
+                selector: get_selector_from_name("deployContract").unwrap(),
+                calldata: udc_calldata,
+            }],
+            nonce: nonce.into(),
+            max_fee: FieldElement::from(max_fee),
+        };
+        let calldata: Vec<Felt> = match account.contract_class {
+            ContractClass::Cairo0(_) => raw_execution.raw_calldata(),
+            ContractClass::Cairo1(_) => raw_execution.raw_calldata_cairo_1(),
+        }
+        .into_iter()
+        .map(Felt::from)
+        .collect();
+
+        let unsigned_transaction = InvokeTransactionV1::new(
+            account.account_address,
+            max_fee,
+            vec![],
+            nonce,
+            calldata.clone(),
+            self.config.chain_id.to_felt(),
+        )?;
+        let signer = starknet_rs_signers::LocalWallet::from(
+            starknet_rs_signers::SigningKey::from_secret_scalar(private_key.into()),
+        );
+        let signature = signer.sign_hash(&unsigned_transaction.generate_hash()?.into()).await?;
+        let invoke_transaction = InvokeTransactionV1::new(
+            account.account_address,
+            max_fee,
+            vec![signature.r.into(), signature.s.into()],
+            nonce,
+            calldata,
+            self.config.chain_id.to_felt(),
+        )?;
+
+        // executed right away, not queued, so that a failing constructor is reported
+        let transaction_hash = self.add_within_pending_limit(|starknet| {
+            add_invoke_transaction::add_invoke_transcation_v1(starknet, invoke_transaction)
+        })?;
+        let revert_error = self
+            .transactions
+            .get_by_hash(transaction_hash)
+            .and_then(|transaction| transaction.execution_info.as_ref())
+            .and_then(|execution_info| execution_info.revert_error.clone());
+        if let Some(revert_error) = revert_error {
+            return Err(Error::DeploymentReverted { transaction_hash, revert_error });
+        }
+
+        Ok((transaction_hash, contract_address))
+    }
+
+    /// The predeployed account at `account_address`, or the first predeployed account if no
+    /// address is given, together with its private key for signing transactions on its behalf.
+    /// `action` tells what the account is needed for in errors.
+    fn predeployed_account_with_private_key(
+        &self,
+        account_address: Option<ContractAddress>,
+        action: &str,
+    ) -> Result<(Account, Felt)> {
+        let account = self
+            .predeployed_accounts
+            .get_accounts()
+            .iter()
+            .find(|account| {
+                account_address.map_or(true, |address| account.account_address == address)
+            })
+            .cloned()
+            .ok_or(Error::UnsupportedAction {
+                msg: format!("{action} is only supported on behalf of predeployed accounts"),
+            })?;
+        let private_key = account.private_key.ok_or(Error::UnsupportedAction {
+            msg: format!(
+                "The private key of account {} is not known to Devnet",
+                account.account_address.to_prefixed_hex_str()
+            ),
+        })?;
+
+        Ok((account, private_key))
+    }
+
+    /// Restores the state Devnet was started in: the same predeployed accounts with their initial
+    /// balances, and only the genesis block. Receivers of new blocks are closed.
+    pub fn restart(&mut self) -> Result<()> {
+        let fork = self.state.fork.clone();
+        *self = Starknet::new(&self.config)?;
+        // the forked network stays the baseline
+        if let Some(fork) = fork {
+            self.install_fork(fork);
+        }
+        // the persisted blocks are discarded as well
+        self.persist_block_count();
+
+        Ok(())
+    }
+
+    /// Generates a new block from the pending one. In demand mode, the block holds all the
+    /// transactions accepted since the previous block; otherwise it is empty.
+    /// Returns the hash of the new block
+    pub fn create_block(&mut self) -> Result<Felt> {
+        let block_number = self.commit_pending_block()?;
+
+        let block = self.get_block(BlockId::Number(block_number.0))?;
+        Ok(block.block_hash())
+    }
+
+    /// Transactions accepted in demand mode that are not yet part of a block, in the order they
+    /// were accepted: those of the pending block, then the invoke transactions queued for
+    /// execution, then those awaiting the transactions of preceding nonces. If blocks are
+    /// generated on transactions, every transaction is part of a block, so none are returned.
+    pub fn pending_transactions(&self) -> Vec<&Transaction> {
+        if self.config.block_generation_on != BlockGenerationOn::Demand {
+            return vec![];
+        }
+
+        self.pending_block()
+            .get_transactions()
+            .iter()
+            .chain(self.queued_invokes.iter().map(|queued| &queued.transaction))
+            .chain(self.transactions_awaiting_nonce.iter())
+            .collect()
+    }
+
+    /// Metrics of the processed transactions and of the current state in the Prometheus text
+    /// format. Only the gauges are reported unless `enable_metrics` is set.
+    pub fn render_metrics(&self) -> String {
+        metrics::render(self)
+    }
+
+    /// Generates a block holding the pending transactions, so that they are kept e.g. in a dump.
+    /// Returns the hash of the generated block, or None if there was nothing to commit
+    pub fn commit_pending_transactions(&mut self) -> Result<Option<Felt>> {
+        self.execute_queued_invokes()?;
+        if self.pending_block().get_transactions().is_empty() {
+            return Ok(None);
+        }
+
+        self.create_block().map(Some)
+    }
+
+    /// Writes `value` to the storage of `contract_address` at `key` without a transaction. A new
+    /// block is generated right away, holding the pending transactions, which are executed before
+    /// the write, and recording the write in its state diff. Writing to an address without a
+    /// deployed contract is either rejected or only logged, as chosen with
+    /// `undeployed_storage_write`. Returns the hash of the generated block.
+    pub fn set_storage_at(
+        &mut self,
+        contract_address: ContractAddress,
+        key: PatriciaKey,
+        value: Felt,
+    ) -> Result<Felt> {
+        self.execute_queued_invokes()?;
+
+        if !self.is_contract_deployed(&contract_address.try_into()?) {
+            match self.config.undeployed_storage_write {
+                UndeployedStorageWrite::Warn => warn!(
+                    contract_address = %contract_address.to_prefixed_hex_str(),
+                    "Writing storage of an undeployed contract"
+                ),
+                UndeployedStorageWrite::Reject => return Err(Error::ContractNotFound),
+            }
+        }
+
+        let storage_key = ContractStorageKey::new(contract_address, key);
+        self.state.pending_state.set_storage_at(&storage_key.try_into()?, value.into());
+        // recorded in the block, so that dumps replay the write
+        self.blocks.pending_block.storage_write =
+            Some(StorageWrite { contract_address, key, value });
+
+        self.create_block()
+    }
+
+    /// Whether a contract is deployed at `address`, counting deployments of the pending block
+    pub(crate) fn is_contract_deployed(&mut self, address: &Address) -> bool {
+        self.state.state.address_to_class_hash.contains_key(address)
+            || self.state.pending_state.cache_mut().class_hash_writes_mut().contains_key(address)
+    }
+
+    /// Transactions sent from `account_address` are executed without validating the signature.
+    /// Fees are still charged
+    pub fn impersonate_account(&mut self, account_address: ContractAddress) {
+        self.impersonated_accounts.insert(account_address);
+    }
+
+    pub fn stop_impersonating_account(&mut self, account_address: &ContractAddress) {
+        self.impersonated_accounts.remove(account_address);
+    }
+
+    /// Tops up the balance of the impersonated sender of the invoke `transaction` with
+    /// [`Self::fund_impersonated_account`]. The checks refusing the transaction before its
+    /// execution are made first, so that nothing is minted for a transaction that is refused.
+    /// Meant to be called right before the transaction is added.
+    pub async fn fund_impersonated_sender(&mut self, transaction: &Transaction) -> Result<()> {
+        let sender_address = transaction.sender_address()?;
+        let unit = transaction.fee_unit();
+        if self.auto_fund_amount(sender_address, unit)?.is_none() {
+            return Ok(());
+        }
+
+        // the mint is added to the pending block before the transaction
+        self.check_pending_block_room(2)?;
+        match transaction {
+            Transaction::Invoke(invoke_transaction) => {
+                add_invoke_transaction::check_max_fee(self, invoke_transaction)?
+            }
+            _ => estimations::check_resource_bounds(self, transaction)?,
+        }
+
+        self.fund_impersonated_account(sender_address, unit).await
+    }
+
+    /// Mints to `account_address`, if impersonated, as much of the fee token of `unit` as it takes
+    /// to have a balance of `impersonate_auto_fund`. Nothing is minted if the option is not set or
+    /// the balance is already high enough, so repeated calls don't add up
+    async fn fund_impersonated_account(
+        &mut self,
+        account_address: ContractAddress,
+        unit: FeeUnit,
+    ) -> Result<()> {
+        if let Some(amount) = self.auto_fund_amount(account_address, unit)? {
+            self.mint(account_address, amount, unit).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Amount [`Self::fund_impersonated_account`] mints to `account_address`, None if nothing
+    fn auto_fund_amount(
+        &self,
+        account_address: ContractAddress,
+        unit: FeeUnit,
+    ) -> Result<Option<BigUint>> {
+        let amount = match self.config.impersonate_auto_fund {
+            Some(amount) if self.is_account_impersonated(&account_address) => BigUint::from(amount),
+            _ => return Ok(None),
+        };
+
+        let balance =
+            self.get_token_balance(BlockId::Tag(BlockTag::Pending), account_address, unit)?;
+        Ok((balance < amount).then(|| amount - balance))
+    }
+
+    pub fn is_account_impersonated(&self, account_address: &ContractAddress) -> bool {
+        self.config.auto_impersonate || self.impersonated_accounts.contains(account_address)
+    }
+
+    /// Sets the timestamp of the next block. Blocks generated afterwards keep advancing from the
+    /// adjusted time. If `generate_block` is set, a new empty block is generated with the given
+    /// timestamp and its hash is returned. Timestamps not representable as `i64` are refused.
+    pub fn set_time(&mut self, timestamp: u64, generate_block: bool) -> Result<Option<Felt>> {
+        let signed_timestamp =
+            i64::try_from(timestamp).map_err(|_| Error::TimeOutOfRange { time: timestamp })?;
+        self.pending_block_timestamp_shift =
+            signed_timestamp - Self::get_unix_timestamp_as_seconds() as i64;
+        self.set_pending_block_timestamp(timestamp)?;
+
+        if generate_block { self.create_block().map(Some) } else { Ok(None) }
+    }
+
+    /// Moves the time of the next block, and of all blocks generated afterwards, forward by
+    /// `time_shift` seconds. If `generate_block` is set, a new empty block is generated and its
+    /// hash is returned. A shift moving the time out of range is refused and changes nothing.
+    pub fn increase_time(&mut self, time_shift: u64, generate_block: bool) -> Result<Option<Felt>> {
+        let out_of_range = || Error::TimeOutOfRange { time: time_shift };
+        let timestamp_shift = i64::try_from(time_shift)
+            .ok()
+            .and_then(|time_shift| self.pending_block_timestamp_shift.checked_add(time_shift))
+            .ok_or_else(out_of_range)?;
+        let timestamp = Self::get_unix_timestamp_as_seconds()
+            .checked_add_signed(timestamp_shift)
+            .filter(|timestamp| i64::try_from(*timestamp).is_ok())
+            .ok_or_else(out_of_range)?;
+
+        self.pending_block_timestamp_shift = timestamp_shift;
+        self.set_pending_block_timestamp(timestamp)?;
+
+        if generate_block { self.create_block().map(Some) } else { Ok(None) }
+    }
+
+    /// Sets the prices of L1 gas and L1 data gas in wei, used for fee computation from now on.
+    /// Already generated blocks and receipts are not affected. If `generate_block` is set, a new
+    /// block is generated and its hash is returned
+    pub fn set_gas_price(
+        &mut self,
+        gas_price: u64,
+        data_gas_price: u64,
+        generate_block: bool,
+    ) -> Result<Option<Felt>> {
+        self.block_context.block_info_mut().gas_price = gas_price;
+        self.data_gas_price = data_gas_price;
+
+        // transactions already in the pending block are kept
+        let gas_price_in_fri = GasPrice(self.gas_price_in(FeeUnit::Fri));
+        let data_gas_price_in_fri = GasPrice(self.data_gas_price_in(FeeUnit::Fri));
+        let pending_block = &mut self.blocks.pending_block;
+        pending_block.header.gas_price = GasPrice(gas_price.into());
+        pending_block.l1_data_gas_price = GasPrice(data_gas_price.into());
+        pending_block.l1_gas_price_in_fri = gas_price_in_fri;
+        pending_block.l1_data_gas_price_in_fri = data_gas_price_in_fri;
+
+        if generate_block { self.create_block().map(Some) } else { Ok(None) }
+    }
+
+    /// Aborts the block with the given hash and all blocks after it. The state is reverted to the
+    /// one of the parent block and the transactions of aborted blocks are marked as rejected.
+    /// Returns the hashes of the aborted blocks.
+    pub fn abort_blocks(&mut self, starting_block_hash: Felt) -> Result<Vec<Felt>> {
+        self.execute_queued_invokes()?;
+
+        let starting_block = self
+            .blocks
+            .get_by_block_id(BlockId::Hash(starting_block_hash.into()))
+            .ok_or(Error::NoBlock)?;
+        let starting_block_number = starting_block.block_number();
+
+        if starting_block_number.0 == self.config.initial_block_number {
+            return Err(Error::UnsupportedAction {
+                msg: "Genesis block can't be aborted".to_string(),
+            });
+        }
+
+        let parent_state = self
+            .blocks
+            .num_to_state
+            .get(&BlockNumber(starting_block_number.0 - 1))
+            .cloned()
+            .ok_or(Error::NoStateAtBlock { block_number: starting_block_number.0 - 1 })?;
+
+        let aborted_blocks = self.blocks.remove_blocks_from(starting_block_number);
+        self.invalidate_snapshots_from(starting_block_number);
+        for block in aborted_blocks.iter() {
+            for transaction_hash in block.get_transactions().iter().filter_map(|t| t.get_hash()) {
+                if let Some(transaction) = self.transactions.get_by_hash_mut(&transaction_hash) {
+                    transaction.status = TransactionStatus::Rejected;
+                    transaction.block_hash = None;
+                    transaction.block_number = None;
+                    self.transactions.notify_status_change(&transaction_hash);
+                }
+            }
+        }
+
+        self.state = parent_state;
+        self.state.synchronize_states();
+
+        // the next block will take the place of the first aborted one
+        self.block_context.block_info_mut().block_number = starting_block_number.0;
+        self.restart_pending_block()?;
+        self.persist_block_count();
+
+        Ok(aborted_blocks.iter().map(|block| block.block_hash()).collect())
     }
 
     pub fn block_state_update(&self, block_id: BlockId) -> Result<StateUpdate> {
         state_update::state_update_by_block_id(self, block_id)
     }
 
+    pub fn estimate_fee(
+        &self,
+        block_id: BlockId,
+        transactions: &[Transaction],
+        skip_validate: bool,
+    ) -> Result<Vec<FeeEstimation>> {
+        estimations::estimate_fee(self, block_id, transactions, skip_validate)
+    }
+
+    pub fn get_events(
+        &self,
+        filter: &EventFilter,
+        continuation_token: Option<ContinuationToken>,
+        chunk_size: usize,
+    ) -> Result<(Vec<EmittedEvent>, Option<ContinuationToken>)> {
+        events::get_events(self, filter, continuation_token, chunk_size)
+    }
+
+    pub fn get_transaction_events(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> Result<Vec<EmittedEvent>> {
+        events::get_transaction_events(self, transaction_hash)
+    }
+
+    pub fn get_transaction_messages_to_l1(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> Result<Vec<MessageToL1>> {
+        messaging::get_transaction_messages_to_l1(self, transaction_hash)
+    }
+
+    pub fn get_transaction_by_hash(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> Result<&StarknetTransaction> {
+        self.transactions.get_by_hash(transaction_hash).ok_or(Error::TransactionNotFound)
+    }
+
+    pub fn trace_transaction(
+        &self,
+        transaction_hash: &TransactionHash,
+    ) -> Result<TransactionTrace> {
+        transaction_trace::trace_transaction(self, transaction_hash)
+    }
+
+    pub fn trace_block_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Vec<(TransactionHash, TransactionTrace)>> {
+        transaction_trace::trace_block_transactions(self, block_id)
+    }
+
+    pub fn simulate_transactions(
+        &self,
+        block_id: BlockId,
+        transactions: &[Transaction],
+        skip_validate: bool,
+        skip_fee_charge: bool,
+    ) -> Result<Vec<SimulatedTransaction>> {
+        simulations::simulate_transactions(
+            self,
+            block_id,
+            transactions,
+            skip_validate,
+            skip_fee_charge,
+        )
+    }
+
+    pub fn get_storage_proof(
+        &self,
+        block_id: BlockId,
+        contract_address: ContractAddress,
+        keys: Vec<PatriciaKey>,
+    ) -> Result<StorageProof> {
+        storage_proof::storage_proof_by_block_id(self, block_id, contract_address, keys)
+    }
+
     pub fn get_block_txs_count(&self, block_id: BlockId) -> Result<u64> {
-        let block = self.blocks.get_by_block_id(block_id).ok_or(Error::NoBlock)?;
+        let block = self.get_block(block_id)?;
 
         Ok(block.get_transactions().len() as u64)
     }
 
+    /// Returns the nonce of a contract deployed at `block_id`, which is 0 if it hasn't sent any
+    /// transactions yet
     pub fn contract_nonce_at_block(
         &self,
         block_id: BlockId,
         contract_address: ContractAddress,
     ) -> Result<Felt> {
         let state = self.get_state_at(&block_id)?;
-        match state.state.address_to_nonce.get(&contract_address.try_into()?) {
-            Some(nonce) => Ok(Felt::from(nonce.clone())),
-            None => Err(Error::ContractNotFound),
+        let address: Address = contract_address.try_into()?;
+        let view = state.view();
+        if view.get_class_hash_at(&address)?.is_none() {
+            return Err(Error::ContractNotFound);
         }
+
+        // contracts deployed by transactions get a nonce only once they send a transaction
+        Ok(view.get_nonce_at(&address)?.unwrap_or_default())
     }
 
     pub fn contract_storage_at_block(
@@ -463,7 +2219,13 @@ impl Starknet {
         state.get_storage(ContractStorageKey::new(contract_address, storage_key))
     }
 
+    /// Returns the block identified by `block_id`. The pending tag identifies the block the next
+    /// transactions are added to, which in transaction mode is always empty.
     pub fn get_block(&self, block_id: BlockId) -> Result<StarknetBlock> {
+        if matches!(block_id, BlockId::Tag(BlockTag::Pending)) {
+            return Ok(self.pending_block().clone());
+        }
+
         let block = self.blocks.get_by_block_id(block_id).ok_or(crate::error::Error::NoBlock)?;
         Ok(block.clone())
     }
@@ -480,29 +2242,70 @@ impl Starknet {
 
 #[cfg(test)]
 mod tests {
-    use starknet_api::block::{BlockHash, BlockNumber, BlockStatus, BlockTimestamp, GasPrice};
+    use std::sync::Arc;
+
+    use starknet_api::block::{BlockNumber, BlockStatus, BlockTimestamp, GasPrice};
     use starknet_in_rust::core::errors::state_errors::StateError;
-    use starknet_in_rust::definitions::block_context::StarknetChainId;
     use starknet_in_rust::felt::Felt252;
     use starknet_in_rust::transaction::error::TransactionError;
     use starknet_in_rust::utils::Address;
-    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_rs_core::types::{BlockId, BlockTag, TransactionStatus};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_rs_signers::{LocalWallet, Signer, SigningKey};
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::felt::Felt;
+    use starknet_types::num_bigint::BigUint;
+    use starknet_types::patricia_key::PatriciaKey;
     use starknet_types::traits::HashProducer;
+    use tokio::sync::RwLock;
 
-    use super::Starknet;
+    use super::{
+        calculate_deployment_address, AccountClass, BlockGenerationOn, FeeUnit, PendingOverflow,
+        PredeclaredClassSet, Starknet, StarknetConfig, UndeployedStorageWrite,
+    };
+    use crate::account::{Account, ACCOUNT_CLASS_HASH_HEX_FOR_ADDRESS_COMPUTATION};
     use crate::blocks::StarknetBlock;
     use crate::constants::{
-        DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_INITIAL_BALANCE, ERC20_CONTRACT_ADDRESS,
+        BLOCK_HASH_CONTRACT_ADDRESS, CAIRO_0_ACCOUNT_CONTRACT_HASH, DEVNET_DEFAULT_CHAIN_ID,
+        DEVNET_DEFAULT_INITIAL_BALANCE, DEVNET_DEFAULT_MAX_STEPS, ERC20_CONTRACT_ADDRESS,
+        GENESIS_BLOCK_TIMESTAMP,
     };
     use crate::error::{Error, Result};
     use crate::state::state_diff::StateDiff;
-    use crate::traits::{Accounted, StateChanger, StateExtractor};
+    use crate::traits::{
+        Accounted, Deployed, HashIdentified, HashIdentifiedMut, StateChanger, StateExtractor,
+    };
+    use crate::transactions::invoke_transaction::InvokeTransactionV1;
+    use crate::transactions::Transaction;
+    use crate::utils::get_storage_var_address;
     use crate::utils::test_utils::{
-        dummy_contract_address, dummy_declare_transaction_v1, starknet_config_for_test,
+        cairo_0_account_without_validations, dummy_cairo_1_contract_class, dummy_contract_address,
+        dummy_declare_transaction_v1, dummy_felt, starknet_config_for_test,
     };
 
+    #[test]
+    fn predeployed_account_address_computed_with_deployer_zero() {
+        let starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let account = &starknet.get_predeployed_accounts()[0];
+        let class_hash =
+            Felt::from_prefixed_hex_str(ACCOUNT_CLASS_HASH_HEX_FOR_ADDRESS_COMPUTATION).unwrap();
+        let salt = Felt::from(20u128);
+
+        let address =
+            calculate_deployment_address(class_hash, &[account.public_key], salt, None).unwrap();
+        assert_eq!(address, account.account_address);
+
+        // the UDC is the deployer of unique instances, whose salt depends on the calling account
+        let unique_address = calculate_deployment_address(
+            class_hash,
+            &[account.public_key],
+            salt,
+            Some(account.account_address),
+        )
+        .unwrap();
+        assert_ne!(unique_address, address);
+    }
+
     #[test]
     fn correct_initial_state_with_test_config() {
         let config = starknet_config_for_test();
@@ -520,7 +2323,13 @@ mod tests {
     fn correct_block_context_creation() {
         let fee_token_address =
             ContractAddress::new(Felt::from_prefixed_hex_str("0xAA").unwrap()).unwrap();
-        let block_ctx = Starknet::get_block_context(10, "0xAA", StarknetChainId::TestNet).unwrap();
+        let block_ctx = Starknet::get_block_context(
+            10,
+            "0xAA",
+            DEVNET_DEFAULT_CHAIN_ID,
+            DEVNET_DEFAULT_MAX_STEPS,
+        )
+        .unwrap();
         assert!(block_ctx.block_info().block_number == 0);
         assert!(block_ctx.block_info().block_timestamp == 0);
         assert_eq!(block_ctx.block_info().gas_price, 10);
@@ -543,6 +2352,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pending_block_follows_latest_block_in_transaction_mode() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+        let latest_hash = starknet.create_block().unwrap();
+        let latest_block = starknet.get_latest_block().unwrap();
+
+        let pending_block = starknet.get_block(BlockId::Tag(BlockTag::Pending)).unwrap();
+        assert_eq!(pending_block.status, BlockStatus::Pending);
+        assert!(pending_block.get_transactions().is_empty());
+        assert_eq!(pending_block.parent_hash(), latest_hash);
+        assert_eq!(pending_block.block_number(), BlockNumber(latest_block.block_number().0 + 1));
+        assert!(pending_block.timestamp() >= latest_block.timestamp());
+    }
+
     #[test]
     fn correct_new_block_creation() {
         let config = starknet_config_for_test();
@@ -560,15 +2384,15 @@ mod tests {
 
         // pending block has some transactions
         assert!(!starknet.pending_block().get_transactions().is_empty());
-        // blocks collection is empty
-        assert!(starknet.blocks.num_to_block.is_empty());
+        // blocks collection holds only the genesis block
+        assert_eq!(starknet.blocks.num_to_block.len(), 1);
 
-        starknet.generate_new_block(StateDiff::default(), starknet.state.clone()).unwrap();
-        // blocks collection should not be empty
-        assert!(!starknet.blocks.num_to_block.is_empty());
+        let block_number =
+            starknet.generate_new_block(StateDiff::default(), starknet.state.clone()).unwrap();
+        assert_eq!(starknet.blocks.num_to_block.len(), 2);
 
         // get block by number and check that the transactions in the block are correct
-        let added_block = starknet.blocks.num_to_block.get(&BlockNumber(0)).unwrap();
+        let added_block = starknet.blocks.num_to_block.get(&block_number).unwrap();
 
         assert!(added_block.get_transactions().len() == 1);
         assert_eq!(added_block.get_transactions().first().unwrap().get_hash().unwrap(), tx_hash);
@@ -607,7 +2431,10 @@ mod tests {
             BlockTimestamp(initial_block_timestamp)
         );
         assert_eq!(starknet.pending_block().header.block_number, BlockNumber(initial_block_number));
-        assert_eq!(starknet.pending_block().header.parent_hash, BlockHash::default());
+        assert_eq!(
+            starknet.pending_block().parent_hash(),
+            starknet.get_latest_block().unwrap().block_hash()
+        );
         assert_eq!(starknet.pending_block().header.gas_price, GasPrice(initial_gas_price as u128));
         assert_eq!(
             starknet.pending_block().header.sequencer,
@@ -617,27 +2444,218 @@ mod tests {
 
     #[test]
     fn correct_block_context_update() {
-        let mut block_ctx =
-            Starknet::get_block_context(0, "0x0", StarknetChainId::TestNet).unwrap();
+        let mut block_ctx = Starknet::get_block_context(
+            0,
+            "0x0",
+            DEVNET_DEFAULT_CHAIN_ID,
+            DEVNET_DEFAULT_MAX_STEPS,
+        )
+        .unwrap();
         let initial_block_number = block_ctx.block_info().block_number;
-        Starknet::update_block_context(&mut block_ctx);
+        Starknet::update_block_context(&mut block_ctx, 0);
 
         assert_eq!(block_ctx.block_info().block_number, initial_block_number + 1);
     }
 
     #[test]
-    fn getting_state_of_latest_block() {
-        let config = starknet_config_for_test();
-        let starknet = Starknet::new(&config).unwrap();
-        starknet.get_state_at(&BlockId::Tag(BlockTag::Latest)).expect("Should be OK");
+    fn block_context_update_applies_timestamp_shift() {
+        let mut block_ctx = Starknet::get_block_context(
+            0,
+            "0x0",
+            DEVNET_DEFAULT_CHAIN_ID,
+            DEVNET_DEFAULT_MAX_STEPS,
+        )
+        .unwrap();
+        let shift = 1_000_000;
+        let timestamp_before = Starknet::get_unix_timestamp_as_seconds();
+        Starknet::update_block_context(&mut block_ctx, shift);
+
+        assert!(block_ctx.block_info().block_timestamp >= timestamp_before + shift as u64);
     }
 
     #[test]
-    fn getting_state_of_pending_block() {
+    fn set_time_with_block_generation() {
         let config = starknet_config_for_test();
-        let starknet = Starknet::new(&config).unwrap();
-        starknet.get_state_at(&BlockId::Tag(BlockTag::Pending)).expect("Should be OK");
-    }
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let timestamp = 1_000_000;
+        let block_hash = starknet.set_time(timestamp, true).unwrap().unwrap();
+
+        let block = starknet.get_block(BlockId::Hash(block_hash.into())).unwrap();
+        assert_eq!(block.timestamp(), BlockTimestamp(timestamp));
+        assert_eq!(starknet.block_context.block_info().block_timestamp, timestamp);
+
+        // next blocks keep advancing from the adjusted time, not from the system clock
+        starknet.create_block().unwrap();
+        let next_block = starknet.get_latest_block().unwrap();
+        assert!(next_block.timestamp() >= BlockTimestamp(timestamp));
+        assert!(next_block.timestamp().0 < Starknet::get_unix_timestamp_as_seconds());
+    }
+
+    #[test]
+    fn set_time_without_block_generation() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let timestamp = 1_000_000;
+        assert!(starknet.set_time(timestamp, false).unwrap().is_none());
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+        assert_eq!(starknet.pending_block().timestamp(), BlockTimestamp(timestamp));
+
+        starknet.create_block().unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().timestamp(), BlockTimestamp(timestamp));
+    }
+
+    #[test]
+    fn increase_time_shifts_block_timestamps() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let time_shift = 1_000_000;
+        let timestamp_before = Starknet::get_unix_timestamp_as_seconds();
+        let block_hash = starknet.increase_time(time_shift, true).unwrap().unwrap();
+
+        let block = starknet.get_block(BlockId::Hash(block_hash.into())).unwrap();
+        assert!(block.timestamp().0 >= timestamp_before + time_shift);
+
+        starknet.create_block().unwrap();
+        let next_block = starknet.get_latest_block().unwrap();
+        assert!(next_block.timestamp().0 >= timestamp_before + time_shift);
+    }
+
+    #[test]
+    fn time_out_of_range_refused() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        assert!(matches!(
+            starknet.set_time(u64::MAX, true),
+            Err(Error::TimeOutOfRange { time: u64::MAX })
+        ));
+
+        starknet.increase_time(1_000_000, false).unwrap();
+        let pending_timestamp = starknet.pending_block().timestamp();
+        for time_shift in [u64::MAX, i64::MAX as u64] {
+            assert!(matches!(
+                starknet.increase_time(time_shift, true),
+                Err(Error::TimeOutOfRange { .. })
+            ));
+        }
+        assert_eq!(starknet.pending_block().timestamp(), pending_timestamp);
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+    }
+
+    #[test]
+    fn genesis_block_hash_reproducible_with_same_config() {
+        let config = starknet_config_for_test();
+        let genesis_hash = |starknet: &Starknet| starknet.get_latest_block().unwrap().block_hash();
+
+        let timestamp_before = Starknet::get_unix_timestamp_as_seconds();
+        let mut starknet = Starknet::new(&config).unwrap();
+        let genesis_block = starknet.get_latest_block().unwrap();
+        assert_eq!(genesis_block.block_number(), BlockNumber(0));
+        assert!(genesis_block.get_transactions().is_empty());
+        assert_eq!(genesis_block.timestamp(), BlockTimestamp(GENESIS_BLOCK_TIMESTAMP));
+        // transactions after genesis are executed at the time of the system clock
+        assert!(starknet.pending_block().timestamp().0 >= timestamp_before);
+        assert!(starknet.block_context.block_info().block_timestamp >= timestamp_before);
+
+        let other_starknet = Starknet::new(&config).unwrap();
+        assert_eq!(genesis_hash(&other_starknet), genesis_block.block_hash());
+
+        starknet.restart().unwrap();
+        assert_eq!(genesis_hash(&starknet), genesis_block.block_hash());
+
+        let other_seed_config = StarknetConfig { seed: config.seed + 1, ..config };
+        let other_seed_starknet = Starknet::new(&other_seed_config).unwrap();
+        assert_ne!(genesis_hash(&other_seed_starknet), genesis_block.block_hash());
+    }
+
+    #[test]
+    fn blocks_start_at_initial_block_number_and_timestamp() {
+        let timestamp = 1_000_000;
+        let config = StarknetConfig {
+            initial_block_number: 1000,
+            initial_block_timestamp: Some(timestamp),
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        assert_eq!(starknet.block_number(), BlockNumber(1001));
+
+        let genesis_block = starknet.get_latest_block().unwrap();
+        let genesis_hash = genesis_block.block_hash();
+        assert_eq!(genesis_block.block_number(), BlockNumber(1000));
+        assert_eq!(genesis_hash, genesis_block.generate_hash().unwrap());
+        assert_eq!(genesis_block.timestamp(), BlockTimestamp(timestamp));
+
+        starknet.create_block().unwrap();
+        let next_block = starknet.get_latest_block().unwrap();
+        assert_eq!(next_block.block_number(), BlockNumber(1001));
+        assert_eq!(next_block.parent_hash(), genesis_hash);
+        assert!(next_block.timestamp() >= BlockTimestamp(timestamp));
+        assert!(next_block.timestamp().0 < Starknet::get_unix_timestamp_as_seconds());
+
+        assert!(matches!(
+            starknet.abort_blocks(genesis_hash),
+            Err(Error::UnsupportedAction { .. })
+        ));
+    }
+
+    #[test]
+    fn hashes_of_blocks_at_least_ten_behind_stored_for_get_block_hash_syscall() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        // blocks 1 to 15 follow the genesis block
+        for _ in 1..=15 {
+            starknet.create_block().unwrap();
+        }
+
+        let block_hash_contract_address =
+            ContractAddress::new(Felt::from_prefixed_hex_str(BLOCK_HASH_CONTRACT_ADDRESS).unwrap())
+                .unwrap();
+        let stored_block_hash = |block_number: u128| {
+            starknet
+                .contract_storage_at_block(
+                    BlockId::Tag(BlockTag::Latest),
+                    block_hash_contract_address,
+                    PatriciaKey::new(Felt::from(block_number)).unwrap(),
+                )
+                .unwrap()
+        };
+
+        // block 15 is the latest one
+        let block_3 = starknet.get_block(BlockId::Number(3)).unwrap();
+        assert_eq!(stored_block_hash(3), block_3.block_hash());
+        let block_5 = starknet.get_block(BlockId::Number(5)).unwrap();
+        assert_eq!(stored_block_hash(5), block_5.block_hash());
+        assert_eq!(stored_block_hash(6), Felt::from(0));
+        assert_eq!(stored_block_hash(20), Felt::from(0));
+
+        // the hash of block 6 is available to transactions of the pending block 16
+        let pending_block_hash = starknet
+            .state
+            .pending_state
+            .get_storage_at(&(
+                Address(Felt::from_prefixed_hex_str(BLOCK_HASH_CONTRACT_ADDRESS).unwrap().into()),
+                Felt::from(6).bytes(),
+            ))
+            .unwrap();
+        let block_6 = starknet.get_block(BlockId::Number(6)).unwrap();
+        assert_eq!(Felt::from(pending_block_hash), block_6.block_hash());
+    }
+
+    #[test]
+    fn getting_state_of_latest_block() {
+        let config = starknet_config_for_test();
+        let starknet = Starknet::new(&config).unwrap();
+        starknet.get_state_at(&BlockId::Tag(BlockTag::Latest)).expect("Should be OK");
+    }
+
+    #[test]
+    fn getting_state_of_pending_block() {
+        let config = starknet_config_for_test();
+        let starknet = Starknet::new(&config).unwrap();
+        starknet.get_state_at(&BlockId::Tag(BlockTag::Pending)).expect("Should be OK");
+    }
 
     #[test]
     fn getting_state_at_block_by_nonexistent_hash() {
@@ -710,13 +2728,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn calling_method_exceeding_max_steps() {
+        let config = StarknetConfig { max_steps: 10, ..starknet_config_for_test() };
+        let starknet = Starknet::new(&config).unwrap();
+
+        let predeployed_account = &starknet.predeployed_accounts.get_accounts()[0];
+        let entry_point_selector =
+            starknet_rs_core::utils::get_selector_from_name("balanceOf").unwrap();
+
+        match starknet.call(
+            BlockId::Tag(BlockTag::Latest),
+            Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap(),
+            entry_point_selector.into(),
+            vec![Felt::from(predeployed_account.account_address)],
+        ) {
+            Err(err @ Error::TransactionError(_)) => assert!(
+                err.to_string().contains("RunResources has no remaining steps"),
+                "Unexpected error: {err}"
+            ),
+            unexpected => panic!("Should have failed; got {unexpected:?}"),
+        }
+    }
+
     /// utility method for happy path balance retrieval
     fn get_balance_at(starknet: &Starknet, contract_address: ContractAddress) -> Result<Vec<Felt>> {
+        get_token_balance_at(starknet, contract_address, FeeUnit::Wei)
+    }
+
+    fn get_token_balance_at(
+        starknet: &Starknet,
+        contract_address: ContractAddress,
+        unit: FeeUnit,
+    ) -> Result<Vec<Felt>> {
+        get_token_balance_at_block(starknet, BlockId::Tag(BlockTag::Latest), contract_address, unit)
+    }
+
+    fn get_token_balance_at_block(
+        starknet: &Starknet,
+        block_id: BlockId,
+        contract_address: ContractAddress,
+        unit: FeeUnit,
+    ) -> Result<Vec<Felt>> {
         let entry_point_selector =
             starknet_rs_core::utils::get_selector_from_name("balanceOf").unwrap();
         starknet.call(
-            BlockId::Tag(BlockTag::Latest),
-            Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS)?,
+            block_id,
+            Felt::from(starknet.fee_token_address(unit)),
             entry_point_selector.into(),
             vec![Felt::from(contract_address)],
         )
@@ -750,20 +2808,608 @@ mod tests {
         assert_eq!(result, expected_balance_uint256);
     }
 
+    #[tokio::test]
+    async fn token_balance_combines_low_and_high_bits() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+        let address = ContractAddress::new(Felt::from(0x1234)).unwrap();
+        let amount = (BigUint::from(3u32) << 128) + BigUint::from(42u32);
+
+        starknet.mint(address, amount.clone(), FeeUnit::Fri).await.unwrap();
+
+        let block_id = BlockId::Tag(BlockTag::Latest);
+        assert_eq!(starknet.get_token_balance(block_id, address, FeeUnit::Fri).unwrap(), amount);
+        assert_eq!(
+            starknet.get_token_balance(block_id, address, FeeUnit::Wei).unwrap(),
+            BigUint::from(0u32)
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_of_mints_applied_with_one_transaction() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let mints: Vec<(ContractAddress, BigUint, FeeUnit)> = (1..=5u32)
+            .map(|i| {
+                let unit = if i % 2 == 0 { FeeUnit::Fri } else { FeeUnit::Wei };
+                (ContractAddress::new(Felt::from(0x1000 + i as u128)).unwrap(), i.into(), unit)
+            })
+            .collect();
+
+        starknet.mint_batch(&mints).await.unwrap();
+
+        let latest_block = starknet.get_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(latest_block.block_number().0, 1);
+        assert_eq!(latest_block.get_transactions().len(), 1);
+        for (address, amount, unit) in mints {
+            assert_eq!(
+                starknet.get_token_balance(BlockId::Tag(BlockTag::Latest), address, unit).unwrap(),
+                amount
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn executed_transaction_reports_its_execution_resources() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let transaction_hash = starknet
+            .mint(dummy_contract_address(), BigUint::from(42u32), FeeUnit::Wei)
+            .await
+            .unwrap();
+
+        let resources =
+            starknet.transactions.get_by_hash(transaction_hash).unwrap().execution_resources();
+        assert!(resources.steps > 0);
+        assert!(resources.builtin_instance_counter["range_check_builtin"] > 0);
+        // the balances of the recipient and of the chargeable account are written
+        assert!(resources.data_gas_consumed > 0);
+    }
+
+    #[tokio::test]
+    async fn batch_of_mints_overflowing_balance_applies_nothing() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let address = ContractAddress::new(Felt::from(0x1234)).unwrap();
+        let max_amount = (BigUint::from(1u32) << 256) - BigUint::from(1u32);
+
+        let mints = [
+            (dummy_contract_address(), BigUint::from(1u32), FeeUnit::Wei),
+            (address, max_amount, FeeUnit::Wei),
+            (address, BigUint::from(1u32), FeeUnit::Wei),
+        ];
+        match starknet.mint_batch(&mints).await {
+            Err(Error::UnsupportedAction { msg }) => assert!(msg.contains("beyond 256 bits")),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+        assert_eq!(
+            get_balance_at(&starknet, dummy_contract_address()).unwrap(),
+            vec![Felt::from(0), Felt::from(0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn lite_mode_skips_state_root_but_keeps_storage_and_state_diffs() {
+        let address = ContractAddress::new(Felt::from(0x1234)).unwrap();
+        let erc20_address =
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap();
+        let balance_key =
+            get_storage_var_address("ERC20_balances", &[Felt::from(address)]).unwrap();
+
+        for lite_mode in [false, true] {
+            let config = StarknetConfig { lite_mode, ..starknet_config_for_test() };
+            let mut starknet = Starknet::new(&config).unwrap();
+            starknet.mint(address, 42u32.into(), FeeUnit::Wei).await.unwrap();
+
+            let block_id = BlockId::Tag(BlockTag::Latest);
+            assert_eq!(
+                starknet.contract_storage_at_block(block_id, erc20_address, balance_key).unwrap(),
+                Felt::from(42)
+            );
+
+            let state_update = starknet.block_state_update(block_id).unwrap();
+            assert_eq!(state_update.new_root == Felt::from(0), lite_mode);
+            let (_, erc20_storage_updates) = state_update
+                .storage_updates
+                .iter()
+                .find(|(contract_address, _)| *contract_address == erc20_address)
+                .unwrap();
+            assert!(erc20_storage_updates.contains(&(balance_key, Felt::from(42))));
+        }
+    }
+
+    #[test]
+    fn storage_written_directly_readable_and_recorded_in_state_diff() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let erc20_address =
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap();
+        let key = PatriciaKey::new(Felt::from(0x42)).unwrap();
+
+        let block_hash = starknet.set_storage_at(erc20_address, key, Felt::from(0x123)).unwrap();
+
+        let block_id = BlockId::Tag(BlockTag::Latest);
+        assert_eq!(starknet.get_block(block_id).unwrap().block_hash(), block_hash);
+        assert_eq!(
+            starknet.contract_storage_at_block(block_id, erc20_address, key).unwrap(),
+            Felt::from(0x123)
+        );
+        let state_update = starknet.block_state_update(block_id).unwrap();
+        assert_eq!(
+            state_update.storage_updates,
+            vec![(erc20_address, vec![(key, Felt::from(0x123))])]
+        );
+    }
+
+    #[test]
+    fn storage_write_to_undeployed_contract_handled_as_configured() {
+        let undeployed_address = ContractAddress::new(Felt::from(0x1234)).unwrap();
+        let key = PatriciaKey::new(Felt::from(0x42)).unwrap();
+
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        starknet.set_storage_at(undeployed_address, key, Felt::from(1)).unwrap();
+        assert_eq!(
+            starknet
+                .contract_storage_at_block(BlockId::Tag(BlockTag::Latest), undeployed_address, key)
+                .unwrap(),
+            Felt::from(1)
+        );
+
+        let config = StarknetConfig {
+            undeployed_storage_write: UndeployedStorageWrite::Reject,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        match starknet.set_storage_at(undeployed_address, key, Felt::from(1)) {
+            Err(Error::ContractNotFound) => {}
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+    }
+
+    #[test]
+    fn standard_classes_predeclared_only_if_configured() {
+        let starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        assert!(starknet.predeclared_classes().is_empty());
+
+        let config = StarknetConfig {
+            predeclare: Some(PredeclaredClassSet::Standard),
+            ..starknet_config_for_test()
+        };
+        let starknet = Starknet::new(&config).unwrap();
+        let names: Vec<&str> =
+            starknet.predeclared_classes().iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, vec!["erc20", "udc"]);
+        for (_, class_hash) in starknet.predeclared_classes() {
+            assert!(starknet.get_class(BlockId::Tag(BlockTag::Latest), *class_hash).is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn impersonated_sender_not_funded_for_refused_transaction() {
+        let config = StarknetConfig {
+            impersonate_auto_fund: Some(1000),
+            auto_impersonate: true,
+            block_generation_on: BlockGenerationOn::Demand,
+            max_pending_transactions: Some(2),
+            pending_overflow: PendingOverflow::Reject,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let address = dummy_contract_address();
+        starknet.mint(address, 400u32.into(), FeeUnit::Wei).await.unwrap();
+
+        // the top-up and the transaction don't both fit in the pending block
+        let transaction = Transaction::Invoke(Box::new(
+            InvokeTransactionV1::new(
+                address,
+                100,
+                vec![],
+                Felt::from(0),
+                vec![],
+                config.chain_id.to_felt(),
+            )
+            .unwrap(),
+        ));
+        assert!(matches!(
+            starknet.fund_impersonated_sender(&transaction).await,
+            Err(Error::PendingBlockFull { max_pending_transactions: 2 })
+        ));
+
+        assert_eq!(starknet.pending_transactions_count(), 1);
+        assert_eq!(
+            starknet
+                .get_token_balance(BlockId::Tag(BlockTag::Pending), address, FeeUnit::Wei)
+                .unwrap(),
+            BigUint::from(400u32)
+        );
+    }
+
+    #[tokio::test]
+    async fn impersonated_account_funded_only_up_to_configured_amount() {
+        let config =
+            StarknetConfig { impersonate_auto_fund: Some(1000), ..starknet_config_for_test() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let address = dummy_contract_address();
+
+        // not impersonated, so not funded
+        starknet.fund_impersonated_account(address, FeeUnit::Fri).await.unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+
+        starknet.impersonate_account(address);
+        starknet.mint(address, 400u32.into(), FeeUnit::Fri).await.unwrap();
+        starknet.fund_impersonated_account(address, FeeUnit::Fri).await.unwrap();
+        starknet.fund_impersonated_account(address, FeeUnit::Fri).await.unwrap();
+
+        assert_eq!(
+            get_token_balance_at(&starknet, address, FeeUnit::Fri).unwrap(),
+            vec![Felt::from(1000), Felt::from(0)]
+        );
+        // a mint of the initial 400 and a single top-up
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(2));
+        assert_eq!(get_balance_at(&starknet, address).unwrap(), vec![Felt::from(0), Felt::from(0)]);
+    }
+
+    #[tokio::test]
+    async fn minting_strk_does_not_change_eth_balance() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let predeployed_account = starknet.predeployed_accounts.get_accounts()[0].clone();
+        let eth_balance_before = get_balance_at(&starknet, predeployed_account.account_address);
+
+        starknet
+            .mint(predeployed_account.account_address, 42u32.into(), FeeUnit::Fri)
+            .await
+            .unwrap();
+
+        let strk_balance =
+            get_token_balance_at(&starknet, predeployed_account.account_address, FeeUnit::Fri)
+                .unwrap();
+        assert_eq!(strk_balance, vec![Felt::from(42), Felt::from(0)]);
+        assert_eq!(
+            get_balance_at(&starknet, predeployed_account.account_address).unwrap(),
+            eth_balance_before.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn eth_fee_token_deployed_and_minted_at_custom_address() {
+        let eth_fee_token_address = ContractAddress::new(Felt::from(0xE7)).unwrap();
+        let config = StarknetConfig { eth_fee_token_address, ..starknet_config_for_test() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let account_address = starknet.predeployed_accounts.get_accounts()[0].account_address;
+
+        starknet.mint(account_address, 42u32.into(), FeeUnit::Wei).await.unwrap();
+
+        let balance = get_balance_at(&starknet, account_address).unwrap();
+        assert_eq!(balance, vec![Felt::from(DEVNET_DEFAULT_INITIAL_BALANCE + 42), Felt::from(0)]);
+        // nothing is deployed at the default address
+        assert!(
+            starknet
+                .call(
+                    BlockId::Tag(BlockTag::Latest),
+                    Felt::from(FeeUnit::Wei.default_erc20_address()),
+                    get_selector_from_name("balanceOf").unwrap().into(),
+                    vec![Felt::from(account_address)],
+                )
+                .is_err()
+        );
+        // fees are charged in the token at the custom address
+        assert_eq!(
+            starknet.block_context.starknet_os_config().fee_token_address().clone(),
+            eth_fee_token_address.try_into().unwrap()
+        );
+    }
+
+    #[test]
+    fn blocks_sequenced_by_configured_sequencer() {
+        let sequencer_address = ContractAddress::new(Felt::from(0x5E9)).unwrap();
+        let config = StarknetConfig { sequencer_address, ..starknet_config_for_test() };
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        assert_eq!(
+            starknet.block_context.block_info().sequencer_address,
+            sequencer_address.try_into().unwrap()
+        );
+        assert_eq!(
+            starknet.block_context_for(FeeUnit::Fri).unwrap().block_info().sequencer_address,
+            sequencer_address.try_into().unwrap()
+        );
+
+        starknet.create_block().unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().sequencer_address(), sequencer_address);
+        assert_eq!(
+            starknet.pending_block().header.sequencer,
+            sequencer_address.try_into().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn transactions_are_collected_into_one_block_in_demand_mode() {
+        let config = StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let address = dummy_contract_address();
+
+        let mut transaction_hashes = vec![];
+        for _ in 0..3 {
+            transaction_hashes
+                .push(starknet.mint(address, 1u32.into(), FeeUnit::Wei).await.unwrap());
+        }
+
+        // no block is generated until requested, so the latest block is still the genesis block
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+        let pending_block = starknet.get_block(BlockId::Tag(BlockTag::Pending)).unwrap();
+        assert_eq!(pending_block.get_transactions().len(), 3);
+        // the changes are visible to subsequent transactions and to pending queries, but not to
+        // latest ones until the block is created
+        let pending = BlockId::Tag(BlockTag::Pending);
+        assert_eq!(
+            get_token_balance_at_block(&starknet, pending, address, FeeUnit::Wei).unwrap(),
+            vec![Felt::from(3), Felt::from(0)]
+        );
+        assert_eq!(get_balance_at(&starknet, address).unwrap(), vec![Felt::from(0), Felt::from(0)]);
+
+        let block_hash = starknet.create_block().unwrap();
+        let block = starknet.get_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(block.block_hash(), block_hash);
+        assert_eq!(block.block_number(), BlockNumber(1));
+        assert_eq!(
+            block.get_transactions().iter().map(|t| t.get_hash().unwrap()).collect::<Vec<_>>(),
+            transaction_hashes
+        );
+        assert_eq!(get_balance_at(&starknet, address).unwrap(), vec![Felt::from(3), Felt::from(0)]);
+        for transaction_hash in transaction_hashes {
+            let transaction = starknet.transactions.get_by_hash_mut(&transaction_hash).unwrap();
+            assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        }
+        assert!(starknet.pending_block().get_transactions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn full_pending_block_sealed_in_demand_mode() {
+        let config = StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            max_pending_transactions: Some(2),
+            pending_overflow: PendingOverflow::Seal,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let address = dummy_contract_address();
+
+        starknet.mint(address, 1u32.into(), FeeUnit::Wei).await.unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+
+        // reaching the limit creates a block right away
+        starknet.mint(address, 1u32.into(), FeeUnit::Wei).await.unwrap();
+        let latest_block = starknet.get_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(latest_block.get_transactions().len(), 2);
+        assert!(starknet.pending_block().get_transactions().is_empty());
+
+        starknet.mint(address, 1u32.into(), FeeUnit::Wei).await.unwrap();
+        assert_eq!(starknet.pending_block().get_transactions().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn transactions_rejected_while_pending_block_full_in_demand_mode() {
+        let config = StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            max_pending_transactions: Some(2),
+            pending_overflow: PendingOverflow::Reject,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let address = dummy_contract_address();
+
+        for _ in 0..2 {
+            starknet.mint(address, 1u32.into(), FeeUnit::Wei).await.unwrap();
+        }
+        match starknet.mint(address, 1u32.into(), FeeUnit::Wei).await {
+            Err(Error::PendingBlockFull { max_pending_transactions: 2 }) => {}
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        assert_eq!(starknet.pending_block().get_transactions().len(), 2);
+
+        // creating a block makes room for new transactions
+        starknet.create_block().unwrap();
+        starknet.mint(address, 1u32.into(), FeeUnit::Wei).await.unwrap();
+        assert_eq!(starknet.pending_block().get_transactions().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn transaction_status_changes_notified_on_acceptance_and_block_creation() {
+        let config = StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let mut receiver = starknet.subscribe_to_transaction_status_changes();
+
+        let transaction_hash =
+            starknet.mint(dummy_contract_address(), 1u32.into(), FeeUnit::Wei).await.unwrap();
+        assert_eq!(receiver.try_recv().unwrap(), transaction_hash);
+        assert!(receiver.try_recv().is_err());
+
+        starknet.create_block().unwrap();
+        assert_eq!(receiver.try_recv().unwrap(), transaction_hash);
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn pending_transaction_notified_before_block_including_it() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let mut transactions = starknet.subscribe_to_pending_transactions();
+        let mut blocks = starknet.subscribe_to_new_blocks();
+
+        let transaction_hash =
+            starknet.mint(dummy_contract_address(), 1u32.into(), FeeUnit::Wei).await.unwrap();
+
+        let transaction = transactions.try_recv().unwrap();
+        assert_eq!(transaction.get_hash(), Some(transaction_hash));
+        assert!(transaction.sender_address().is_ok());
+        assert!(transactions.try_recv().is_err());
+
+        let block = blocks.try_recv().unwrap();
+        assert_eq!(
+            block.get_transactions().iter().filter_map(|t| t.get_hash()).collect::<Vec<_>>(),
+            vec![transaction_hash]
+        );
+    }
+
+    #[tokio::test]
+    async fn pending_transactions_committed_only_if_present() {
+        let config = StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        assert!(starknet.commit_pending_transactions().unwrap().is_none());
+
+        let mint_hash =
+            starknet.mint(dummy_contract_address(), 1u32.into(), FeeUnit::Wei).await.unwrap();
+        let block_hash = starknet.commit_pending_transactions().unwrap().unwrap();
+        let block = starknet.get_block(BlockId::Hash(block_hash.into())).unwrap();
+        assert_eq!(
+            block.get_transactions().iter().filter_map(|t| t.get_hash()).collect::<Vec<_>>(),
+            vec![mint_hash]
+        );
+
+        assert!(starknet.commit_pending_transactions().unwrap().is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrently_submitted_transactions_are_listed_in_arrival_order() {
+        let config = StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..starknet_config_for_test()
+        };
+        let starknet = Arc::new(RwLock::new(Starknet::new(&config).unwrap()));
+
+        let tasks: Vec<_> = (1..=5)
+            .map(|amount| {
+                let starknet = starknet.clone();
+                tokio::spawn(async move {
+                    let mut starknet = starknet.write().await;
+                    let transaction_hash = starknet
+                        .mint(dummy_contract_address(), amount.into(), FeeUnit::Wei)
+                        .await
+                        .unwrap();
+                    let arrival_index =
+                        starknet.get_transaction_by_hash(transaction_hash).unwrap().arrival_index();
+                    (arrival_index, transaction_hash)
+                })
+            })
+            .collect();
+
+        let mut submitted = vec![];
+        for task in tasks {
+            submitted.push(task.await.unwrap());
+        }
+        submitted.sort_by_key(|(arrival_index, _)| *arrival_index);
+        // every transaction got its own index, without gaps
+        assert!(submitted.windows(2).all(|pair| pair[1].0 == pair[0].0 + 1));
+
+        let mut starknet = starknet.write().await;
+        starknet.create_block().unwrap();
+        let block = starknet.get_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(
+            block.get_transactions().iter().map(|t| t.get_hash().unwrap()).collect::<Vec<_>>(),
+            submitted.into_iter().map(|(_, transaction_hash)| transaction_hash).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn restart_reverts_to_genesis_state() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let predeployed_account = starknet.predeployed_accounts.get_accounts()[0].clone();
+        let initial_balance = starknet.get_account_balance(&predeployed_account).unwrap();
+        let mint_hash = starknet
+            .mint(predeployed_account.account_address, 42u32.into(), FeeUnit::Wei)
+            .await
+            .unwrap();
+        assert_ne!(starknet.get_account_balance(&predeployed_account).unwrap(), initial_balance);
+
+        starknet.restart().unwrap();
+
+        assert_eq!(starknet.get_account_balance(&predeployed_account).unwrap(), initial_balance);
+        assert_eq!(starknet.block_number(), BlockNumber(1));
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+        assert!(starknet.transactions.get_by_hash_mut(&mint_hash).is_none());
+        assert_eq!(starknet.get_predeployed_accounts().len(), config.total_accounts as usize);
+    }
+
+    #[tokio::test]
+    async fn gas_price_change_applies_to_new_blocks_only() {
+        let config =
+            StarknetConfig { gas_price: 10, data_gas_price: 20, ..starknet_config_for_test() };
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let mint_hash =
+            starknet.mint(dummy_contract_address(), 1u32.into(), FeeUnit::Wei).await.unwrap();
+        let mint_fee = starknet
+            .transactions
+            .get_by_hash_mut(&mint_hash)
+            .unwrap()
+            .execution_info
+            .as_ref()
+            .unwrap()
+            .actual_fee;
+
+        let block_hash = starknet.set_gas_price(30, 40, true).unwrap().unwrap();
+        let new_block = starknet.get_block(BlockId::Hash(block_hash.into())).unwrap();
+        assert_eq!(new_block.l1_gas_price(), GasPrice(30));
+        assert_eq!(new_block.l1_data_gas_price(), GasPrice(40));
+
+        let mint_block = starknet.get_block(BlockId::Number(1)).unwrap();
+        assert_eq!(mint_block.l1_gas_price(), GasPrice(10));
+        assert_eq!(mint_block.l1_data_gas_price(), GasPrice(20));
+        let mint_transaction = starknet.transactions.get_by_hash_mut(&mint_hash).unwrap();
+        assert_eq!(mint_transaction.execution_info.as_ref().unwrap().actual_fee, mint_fee);
+    }
+
+    #[tokio::test]
+    async fn block_gas_prices_in_fri_derived_from_eth_strk_rate() {
+        let config = StarknetConfig {
+            gas_price: 10,
+            data_gas_price: 20,
+            eth_strk_rate: 1000,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        starknet.mint(dummy_contract_address(), 1u32.into(), FeeUnit::Wei).await.unwrap();
+        let block = starknet.get_block(BlockId::Number(1)).unwrap();
+        assert_eq!(block.l1_gas_price(), GasPrice(10));
+        assert_eq!(block.l1_gas_price_in_fri(), GasPrice(10_000));
+        assert_eq!(block.l1_data_gas_price(), GasPrice(20));
+        assert_eq!(block.l1_data_gas_price_in_fri(), GasPrice(20_000));
+
+        let block_hash = starknet.set_gas_price(30, 40, true).unwrap().unwrap();
+        let new_block = starknet.get_block(BlockId::Hash(block_hash.into())).unwrap();
+        assert_eq!(new_block.l1_gas_price_in_fri(), GasPrice(30_000));
+        assert_eq!(new_block.l1_data_gas_price_in_fri(), GasPrice(40_000));
+    }
+
     #[test]
     fn returns_block_number() {
         let config = starknet_config_for_test();
         let mut starknet = Starknet::new(&config).unwrap();
 
-        let block_number_no_blocks = starknet.block_number();
-        assert_eq!(block_number_no_blocks.0, 0);
+        // only the genesis block exists
+        let block_number_genesis_only = starknet.block_number();
+        assert_eq!(block_number_genesis_only.0, 1);
 
         starknet.generate_new_block(StateDiff::default(), starknet.state.clone()).unwrap();
         starknet.generate_pending_block().unwrap();
 
-        // last added block number -> 0
-        let added_block = starknet.blocks.num_to_block.get(&BlockNumber(0)).unwrap();
-        // number of the accepted block -> 1
+        // last added block number -> 1
+        let added_block = starknet.blocks.num_to_block.get(&BlockNumber(1)).unwrap();
+        // number of the accepted block -> 2
         let block_number = starknet.block_number();
 
         assert_eq!(block_number.0 - 1, added_block.header.block_number.0);
@@ -771,7 +3417,7 @@ mod tests {
         starknet.generate_new_block(StateDiff::default(), starknet.state.clone()).unwrap();
         starknet.generate_pending_block().unwrap();
 
-        let added_block2 = starknet.blocks.num_to_block.get(&BlockNumber(1)).unwrap();
+        let added_block2 = starknet.blocks.num_to_block.get(&BlockNumber(2)).unwrap();
         let block_number2 = starknet.block_number();
 
         assert_eq!(block_number2.0 - 1, added_block2.header.block_number.0);
@@ -785,7 +3431,7 @@ mod tests {
         starknet.generate_new_block(StateDiff::default(), starknet.state.clone()).unwrap();
         starknet.generate_pending_block().unwrap();
 
-        let num_no_transactions = starknet.get_block_txs_count(BlockId::Number(0));
+        let num_no_transactions = starknet.get_block_txs_count(BlockId::Number(1));
 
         assert_eq!(num_no_transactions.unwrap(), 0);
 
@@ -801,7 +3447,7 @@ mod tests {
 
         starknet.generate_new_block(StateDiff::default(), starknet.state.clone()).unwrap();
 
-        let num_one_transaction = starknet.get_block_txs_count(BlockId::Number(1));
+        let num_one_transaction = starknet.get_block_txs_count(BlockId::Number(2));
 
         assert_eq!(num_one_transaction.unwrap(), 1);
     }
@@ -815,6 +3461,190 @@ mod tests {
         assert_eq!(chain_id.to_string(), DEVNET_DEFAULT_CHAIN_ID.to_string());
     }
 
+    /// Sends a fee token transfer from `account_address`, signed with `private_key`, and asserts
+    /// that it's executed successfully. Calls are encoded in the format expected by `__execute__`
+    /// of the given account class.
+    async fn assert_signed_transfer_succeeds(
+        starknet: &mut Starknet,
+        account_address: ContractAddress,
+        private_key: Felt,
+        account_class: AccountClass,
+    ) {
+        let transfer_call = vec![
+            Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap(),
+            get_selector_from_name("transfer").unwrap().into(),
+        ];
+        let transfer_calldata = vec![Felt::from(0x42), Felt::from(1), Felt::from(0)];
+        let calldata = match account_class {
+            // call array followed by the concatenated calldata of all calls
+            AccountClass::Cairo0 => [
+                vec![Felt::from(1)],
+                transfer_call,
+                vec![Felt::from(0), Felt::from(3), Felt::from(3)],
+                transfer_calldata,
+            ]
+            .concat(),
+            // calls with their calldata inlined
+            AccountClass::Cairo1 => {
+                [vec![Felt::from(1)], transfer_call, vec![Felt::from(3)], transfer_calldata]
+                    .concat()
+            }
+        };
+
+        let max_fee = starknet.config.gas_price as u128 * 1_000_000;
+        let chain_id = starknet.config.chain_id.to_felt();
+        let invoke_transaction = |signature: Vec<Felt>| {
+            InvokeTransactionV1::new(
+                account_address,
+                max_fee,
+                signature,
+                Felt::from(0),
+                calldata.clone(),
+                chain_id,
+            )
+            .unwrap()
+        };
+        let transaction_hash = invoke_transaction(vec![]).generate_hash().unwrap();
+        let signature = LocalWallet::from(SigningKey::from_secret_scalar(private_key.into()))
+            .sign_hash(&transaction_hash.into())
+            .await
+            .unwrap();
+
+        starknet
+            .add_invoke_transaction_v1(invoke_transaction(vec![
+                signature.r.into(),
+                signature.s.into(),
+            ]))
+            .unwrap();
+        let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        assert!(transaction.execution_info.as_ref().unwrap().revert_error.is_none());
+    }
+
+    #[tokio::test]
+    async fn predeployed_accounts_backed_by_cairo_0_class_by_default() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let account = starknet.get_predeployed_accounts()[0].clone();
+        assert_eq!(
+            account.class_hash,
+            Felt::from_prefixed_hex_str(CAIRO_0_ACCOUNT_CONTRACT_HASH).unwrap()
+        );
+        assert!(starknet.state.is_contract_declared(&account.class_hash));
+
+        assert_signed_transfer_succeeds(
+            &mut starknet,
+            account.account_address,
+            account.private_key.unwrap(),
+            AccountClass::Cairo0,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "the Cairo 1 account artifact is not part of the repository yet"]
+    async fn predeployed_accounts_backed_by_cairo_1_class() {
+        let config =
+            StarknetConfig { account_class: AccountClass::Cairo1, ..starknet_config_for_test() };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let account = starknet.get_predeployed_accounts()[0].clone();
+        assert_ne!(
+            account.class_hash,
+            Felt::from_prefixed_hex_str(CAIRO_0_ACCOUNT_CONTRACT_HASH).unwrap()
+        );
+        assert!(starknet.state.is_contract_declared(&account.class_hash));
+
+        assert_signed_transfer_succeeds(
+            &mut starknet,
+            account.account_address,
+            account.private_key.unwrap(),
+            AccountClass::Cairo1,
+        )
+        .await;
+        // minting is still possible, since the chargeable account stays on the Cairo 0 class
+        starknet.mint(dummy_contract_address(), 1u32.into(), FeeUnit::Wei).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn accounts_listed_in_file_predeployed_with_given_keys_and_balances() {
+        let config = StarknetConfig {
+            predeployed_accounts_path: Some(
+                concat!(env!("CARGO_MANIFEST_DIR"), "/test_artifacts/predeployed_accounts.json")
+                    .to_string(),
+            ),
+            no_auto_accounts: true,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let accounts = starknet.get_predeployed_accounts();
+        let expected_accounts = [
+            ("0x1234", "0x60dea6c1228f1db4ca1f9db11c01b6e9cce5e627f7181dcaa27d69cbdbe57b5", 1000),
+            ("0x5678", "0x5a91f0ea25312accb20d8041b12260bff31a3490e5730a690b0ec8fe10ffbb", 42),
+        ];
+        assert_eq!(accounts.len(), expected_accounts.len());
+        for (account, (address, public_key, balance)) in accounts.iter().zip(expected_accounts) {
+            let address = ContractAddress::new(Felt::from_prefixed_hex_str(address).unwrap());
+            assert_eq!(account.account_address, address.unwrap());
+            assert_eq!(account.public_key, Felt::from_prefixed_hex_str(public_key).unwrap());
+            assert_eq!(account.private_key, None);
+            for unit in [FeeUnit::Wei, FeeUnit::Fri] {
+                assert_eq!(
+                    get_token_balance_at(&starknet, account.account_address, unit).unwrap(),
+                    vec![Felt::from(balance), Felt::from(0)]
+                );
+            }
+        }
+
+        // signatures made with the private key of the listed public key are valid
+        let private_key =
+            Felt::from_prefixed_hex_str("0xc4da537c1651ddae44867db30d67b366").unwrap();
+        let account_address = accounts[0].account_address;
+        assert_signed_transfer_succeeds(
+            &mut starknet,
+            account_address,
+            private_key,
+            AccountClass::Cairo0,
+        )
+        .await;
+    }
+
+    #[test]
+    fn listed_account_at_taken_address_rejected() {
+        let accounts_path =
+            std::env::temp_dir().join("devnet_listed_account_at_taken_address_rejected.json");
+        let accounts = serde_json::json!([{
+            "address": ERC20_CONTRACT_ADDRESS,
+            "public_key": "0x1",
+            "balance": "1",
+            "class_hash": CAIRO_0_ACCOUNT_CONTRACT_HASH,
+        }]);
+        std::fs::write(&accounts_path, accounts.to_string()).unwrap();
+
+        let config = StarknetConfig {
+            predeployed_accounts_path: Some(accounts_path.display().to_string()),
+            ..starknet_config_for_test()
+        };
+        match Starknet::new(&config) {
+            Err(Error::PredeployedAccountsError { msg }) => assert!(msg.contains("already taken")),
+            Err(other) => panic!("Unexpected error: {other}"),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn missing_custom_account_class_fails() {
+        let config = StarknetConfig {
+            account_class_custom: Some("missing.sierra".to_string()),
+            ..starknet_config_for_test()
+        };
+
+        match Starknet::new(&config) {
+            Err(Error::ReadFileError { path, .. }) => assert_eq!(path, "missing.sierra"),
+            Err(other) => panic!("Unexpected error: {other}"),
+            Ok(_) => panic!("Should have failed"),
+        }
+    }
+
     #[test]
     fn correct_state_at_specific_block() {
         let mut starknet = Starknet::default();
@@ -880,6 +3710,149 @@ mod tests {
         assert_eq!(third_block_expected_address_nonce, *third_block_address_nonce);
     }
 
+    #[test]
+    fn nonce_of_deployed_contract_without_transactions_is_zero() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let latest = BlockId::Tag(BlockTag::Latest);
+        let address = dummy_contract_address();
+
+        match starknet.contract_nonce_at_block(latest, address) {
+            Err(Error::ContractNotFound) => (),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        // as after deployment by a transaction, only the class hash of the contract is known
+        starknet
+            .state
+            .state
+            .address_to_class_hash_mut()
+            .insert(address.try_into().unwrap(), dummy_felt().bytes());
+        assert_eq!(starknet.contract_nonce_at_block(latest, address).unwrap(), Felt::from(0));
+    }
+
+    #[test]
+    fn class_hash_at_block_reflects_replaced_class() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+
+        let account_class = cairo_0_account_without_validations();
+        let account_class_hash = account_class.generate_hash().unwrap();
+        let account = Account::new(
+            Felt::from(1_000_000),
+            dummy_felt(),
+            dummy_felt(),
+            account_class_hash,
+            account_class,
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        account.deploy(&mut starknet.state).unwrap();
+        account.set_initial_balance(&mut starknet.state).unwrap();
+
+        // the test contract replaces its class with the one given to `test_replace_class`
+        let contract_class = dummy_cairo_1_contract_class();
+        let old_class_hash = contract_class.generate_hash().unwrap();
+        let contract_address = ContractAddress::new(Felt::from(0x1234)).unwrap();
+        starknet.state.declare_contract_class(old_class_hash, contract_class).unwrap();
+        starknet.state.deploy_contract(contract_address, old_class_hash).unwrap();
+        starknet.state.synchronize_states();
+
+        // block 1 precedes the replacement, which happens in block 2
+        starknet.create_block().unwrap();
+        let calldata = vec![
+            contract_address.into(), // contract address
+            get_selector_from_name("test_replace_class").unwrap().into(), // function selector
+            Felt::from(1),           // calldata len
+            account_class_hash,      // new class hash
+        ];
+        let invoke_transaction = InvokeTransactionV1::new(
+            account.get_address(),
+            10000,
+            vec![],
+            Felt::from(0),
+            calldata,
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap();
+        let transaction_hash = starknet.add_invoke_transaction_v1(invoke_transaction).unwrap();
+        let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        assert!(transaction.execution_info.as_ref().unwrap().revert_error.is_none());
+
+        for (block_id, expected_class_hash) in [
+            (BlockId::Number(1), old_class_hash),
+            (BlockId::Number(2), account_class_hash),
+            (BlockId::Tag(BlockTag::Latest), account_class_hash),
+        ] {
+            assert_eq!(
+                starknet.get_class_hash_at(&block_id, &contract_address).unwrap(),
+                expected_class_hash
+            );
+
+            match starknet.get_class_hash_at(&block_id, &dummy_contract_address()) {
+                Err(Error::ContractNotFound) => (),
+                other => panic!("Unexpected result: {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn subscribers_are_notified_about_new_blocks() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+        let mut receiver = starknet.subscribe_to_new_blocks();
+
+        starknet.generate_new_block(StateDiff::default(), starknet.state.clone()).unwrap();
+
+        let notified_block = receiver.try_recv().unwrap();
+        assert_eq!(notified_block.block_number(), BlockNumber(1));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn aborts_blocks_starting_from_given_one() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let mut block_hashes = vec![];
+        for _ in 0..3 {
+            let block_number =
+                starknet.generate_new_block(StateDiff::default(), starknet.state.clone()).unwrap();
+            starknet.generate_pending_block().unwrap();
+            block_hashes
+                .push(starknet.get_block(BlockId::Number(block_number.0)).unwrap().block_hash());
+        }
+
+        let aborted_hashes = starknet.abort_blocks(block_hashes[1]).unwrap();
+        assert_eq!(aborted_hashes, vec![block_hashes[1], block_hashes[2]]);
+
+        assert_eq!(starknet.get_latest_block().unwrap().block_hash(), block_hashes[0]);
+        assert!(starknet.get_block(BlockId::Hash(block_hashes[1].into())).is_err());
+        assert!(starknet.get_block(BlockId::Number(3)).is_err());
+        assert_eq!(starknet.block_number(), BlockNumber(2));
+
+        // next block takes the place of the first aborted one
+        let new_block_number =
+            starknet.generate_new_block(StateDiff::default(), starknet.state.clone()).unwrap();
+        assert_eq!(new_block_number, BlockNumber(2));
+        assert_eq!(starknet.get_block(BlockId::Number(2)).unwrap().parent_hash(), block_hashes[0]);
+    }
+
+    #[test]
+    fn aborting_genesis_block_fails() {
+        let config = starknet_config_for_test();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let genesis_hash = starknet.get_latest_block().unwrap().block_hash();
+
+        match starknet.abort_blocks(genesis_hash) {
+            Err(Error::UnsupportedAction { msg }) => {
+                assert_eq!(msg, "Genesis block can't be aborted")
+            }
+            _ => panic!("Expected failure when aborting genesis block"),
+        }
+    }
+
     #[test]
     fn gets_latest_block() {
         let config = starknet_config_for_test();
@@ -893,6 +3866,6 @@ mod tests {
 
         let latest_block = starknet.get_latest_block();
 
-        assert_eq!(latest_block.unwrap().block_number(), BlockNumber(2));
+        assert_eq!(latest_block.unwrap().block_number(), BlockNumber(3));
     }
 }
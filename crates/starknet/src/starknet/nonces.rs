@@ -0,0 +1,167 @@
+use starknet_in_rust::state::state_api::State;
+use starknet_in_rust::transaction::error::TransactionError;
+use starknet_in_rust::utils::Address;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::Felt;
+use starknet_types::num_bigint::BigUint;
+use starknet_types::traits::ToHexString;
+use tracing::{error, info};
+
+use super::{add_declare_transaction, add_invoke_transaction, BlockGenerationOn, Starknet};
+use crate::error::{Error, Result};
+use crate::transactions::{StarknetTransaction, Transaction};
+
+/// Errors of transactions rejected before their execution, in the form kept for rejected
+/// transactions
+fn to_transaction_error(err: Error) -> TransactionError {
+    match err {
+        Error::TransactionError(err) => err,
+        other => TransactionError::CustomError(other.to_string()),
+    }
+}
+
+impl Starknet {
+    /// Nonce the next transaction of `sender` has to carry: its nonce in the pending state,
+    /// advanced past the invoke transactions it sent that are queued for execution
+    pub(crate) fn expected_nonce(&mut self, sender: ContractAddress) -> Result<Felt> {
+        let mut expected_nonce =
+            Felt::from(self.state.pending_state.get_nonce_at(&Address(Felt::from(sender).into()))?);
+        for queued in &self.queued_invokes {
+            if queued.transaction.sender_address()? == sender
+                && *queued.transaction.nonce() == expected_nonce
+            {
+                expected_nonce = Felt::try_from(BigUint::from(expected_nonce) + 1u8)?;
+            }
+        }
+
+        Ok(expected_nonce)
+    }
+
+    /// Rejects a transaction of `sender` with a nonce other than the expected one
+    pub(crate) fn check_nonce(&mut self, sender: ContractAddress, nonce: Felt) -> Result<()> {
+        let expected = self.expected_nonce(sender)?;
+        if nonce != expected {
+            return Err(Error::InvalidTransactionNonce {
+                address: sender,
+                expected,
+                provided: nonce,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Same as [`Starknet::check_nonce`], but if blocks are generated on demand, a nonce ahead of
+    /// the expected one is accepted for the transaction to wait until the transactions of the
+    /// nonces in between are added. Returns true if the transaction has to wait.
+    pub(crate) fn awaits_preceding_nonces(
+        &mut self,
+        sender: ContractAddress,
+        nonce: Felt,
+    ) -> Result<bool> {
+        let expected = self.expected_nonce(sender)?;
+        if nonce == expected {
+            return Ok(false);
+        }
+
+        let may_await = nonce > expected
+            && self.config.block_generation_on == BlockGenerationOn::Demand
+            && !self.transactions_awaiting_nonce.iter().any(|awaiting| {
+                awaiting.sender_address().ok() == Some(sender) && *awaiting.nonce() == nonce
+            });
+        if !may_await {
+            return Err(Error::InvalidTransactionNonce {
+                address: sender,
+                expected,
+                provided: nonce,
+            });
+        }
+
+        Ok(true)
+    }
+
+    /// Keeps `transaction`, whose nonce is ahead of the nonce expected from its sender, until the
+    /// transactions of the nonces in between are added. Meanwhile, it is known by its hash as a
+    /// received transaction.
+    pub(crate) fn await_preceding_nonces(&mut self, transaction: Transaction) {
+        info!(
+            transaction_hash = ?transaction.get_hash().map(|hash| hash.to_prefixed_hex_str()),
+            nonce = %transaction.nonce().to_prefixed_hex_str(),
+            "Transaction awaits the transactions of preceding nonces"
+        );
+        if let Some(transaction_hash) = transaction.get_hash() {
+            self.transactions.insert(
+                &transaction_hash,
+                StarknetTransaction::create_received(transaction.clone()),
+            );
+        }
+        self.transactions_awaiting_nonce.push(transaction);
+    }
+
+    /// Adds the awaiting transactions of `sender` in the order of their nonces, for as long as
+    /// the nonce of one of them is the expected one. A transaction that fails to be added is
+    /// rejected with the error, and the ones following it keep waiting.
+    pub(crate) fn add_transactions_awaiting_nonce(
+        &mut self,
+        sender: ContractAddress,
+    ) -> Result<()> {
+        loop {
+            let expected = self.expected_nonce(sender)?;
+            let Some(index) = self.transactions_awaiting_nonce.iter().position(|awaiting| {
+                awaiting.sender_address().ok() == Some(sender) && *awaiting.nonce() == expected
+            }) else {
+                return Ok(());
+            };
+
+            let transaction = self.transactions_awaiting_nonce.remove(index);
+            if let Err(err) = self.add_awaited_transaction(transaction.clone()) {
+                let Some(transaction_hash) = transaction.get_hash() else {
+                    continue;
+                };
+                error!(
+                    transaction_hash = %transaction_hash.to_prefixed_hex_str(),
+                    "Transaction awaiting preceding nonces could not be added: {err}"
+                );
+                self.transactions.insert(
+                    &transaction_hash,
+                    StarknetTransaction::create_rejected(transaction, to_transaction_error(err)),
+                );
+            }
+        }
+    }
+
+    fn add_awaited_transaction(&mut self, transaction: Transaction) -> Result<()> {
+        match transaction {
+            Transaction::Invoke(invoke_transaction) if self.queues_invokes() => {
+                add_invoke_transaction::queue_invoke_transaction_v1(self, *invoke_transaction)?;
+            }
+            Transaction::Invoke(invoke_transaction) => {
+                add_invoke_transaction::add_invoke_transcation_v1(self, *invoke_transaction)?;
+            }
+            Transaction::InvokeV3(invoke_transaction) => {
+                self.execute_queued_invokes()?;
+                add_invoke_transaction::add_invoke_transaction_v3(self, *invoke_transaction)?;
+            }
+            Transaction::Declare(declare_transaction) => {
+                self.execute_queued_invokes()?;
+                add_declare_transaction::add_declare_transaction_v1(self, *declare_transaction)?;
+            }
+            Transaction::DeclareV2(declare_transaction) => {
+                self.execute_queued_invokes()?;
+                add_declare_transaction::add_declare_transaction_v2(self, *declare_transaction)?;
+            }
+            Transaction::DeclareV3(declare_transaction) => {
+                self.execute_queued_invokes()?;
+                add_declare_transaction::add_declare_transaction_v3(self, *declare_transaction)?;
+            }
+            _ => {
+                return Err(Error::UnsupportedAction {
+                    msg: "only invoke and declare transactions can await preceding nonces"
+                        .to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
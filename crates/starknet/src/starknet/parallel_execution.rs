@@ -0,0 +1,709 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use starknet_in_rust::core::errors::state_errors::StateError;
+use starknet_in_rust::definitions::block_context::BlockContext;
+use starknet_in_rust::execution::{CallInfo, TransactionExecutionInfo};
+use starknet_in_rust::services::api::contract_classes::compiled_class::CompiledClass;
+use starknet_in_rust::state::cached_state::CachedState;
+use starknet_in_rust::state::state_api::StateReader;
+use starknet_in_rust::state::state_cache::{StateCache, StorageEntry};
+use starknet_in_rust::transaction::error::TransactionError;
+use starknet_in_rust::transaction::InvokeFunction;
+use starknet_in_rust::utils::Address;
+use starknet_types::cairo_felt::Felt252;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{Felt, TransactionHash};
+use starknet_types::num_bigint::BigUint;
+use tracing::info;
+
+use super::add_invoke_transaction::execute_invoke_on;
+use super::{estimations, FeeUnit, Starknet};
+use crate::error::Result;
+use crate::state::fork::ForkingStateReader;
+use crate::traits::{StateChanger, StateExtractor};
+use crate::transactions::{StarknetTransaction, Transaction};
+use crate::utils::get_storage_var_address;
+
+/// Invoke transaction waiting to be executed when the pending block is committed
+pub(crate) struct QueuedInvoke {
+    pub(crate) transaction_hash: TransactionHash,
+    pub(crate) invoke: InvokeFunction,
+    pub(crate) transaction: Transaction,
+    pub(crate) block_context: BlockContext,
+    pub(crate) skip_validation: bool,
+    pub(crate) skip_fee_charge: bool,
+}
+
+/// How the queued transactions were executed since Devnet was started
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ParallelExecutionStats {
+    /// Transactions whose concurrent execution was committed as is
+    pub(crate) executed_in_parallel: u64,
+    /// Transactions executed again after the others, because they read values changed by
+    /// transactions queued before them
+    pub(crate) executed_sequentially: u64,
+}
+
+/// Keys of the state read or written by a transaction
+#[derive(Debug, Default)]
+pub(super) struct StateKeys {
+    storage: HashSet<(Address, [u8; 32])>,
+    nonces: HashSet<Address>,
+    class_hashes: HashSet<Address>,
+    compiled_class_hashes: HashSet<[u8; 32]>,
+}
+
+impl StateKeys {
+    fn is_disjoint(&self, other: &StateKeys) -> bool {
+        self.storage.is_disjoint(&other.storage)
+            && self.nonces.is_disjoint(&other.nonces)
+            && self.class_hashes.is_disjoint(&other.class_hashes)
+            && self.compiled_class_hashes.is_disjoint(&other.compiled_class_hashes)
+    }
+
+    fn extend(&mut self, other: StateKeys) {
+        self.storage.extend(other.storage);
+        self.nonces.extend(other.nonces);
+        self.class_hashes.extend(other.class_hashes);
+        self.compiled_class_hashes.extend(other.compiled_class_hashes);
+    }
+
+    /// Keys written in `current` with values other than the ones written in `previous`
+    pub(super) fn written_since(previous: &mut StateCache, current: &mut StateCache) -> Self {
+        Self {
+            storage: changed_keys(previous.storage_writes_mut(), current.storage_writes_mut()),
+            nonces: changed_keys(previous.nonce_writes_mut(), current.nonce_writes_mut()),
+            class_hashes: changed_keys(
+                previous.class_hash_writes_mut(),
+                current.class_hash_writes_mut(),
+            ),
+            compiled_class_hashes: changed_keys(
+                previous.compiled_class_hash_writes_mut(),
+                current.compiled_class_hash_writes_mut(),
+            ),
+        }
+    }
+
+    /// Number of felts the state diff of these keys takes when published on L1. Every modified
+    /// contract takes its address and a word packing its nonce and number of storage updates,
+    /// followed by its new class hash, if any, and a key and a value per storage update. Every
+    /// declared class takes its class hash and compiled class hash.
+    pub(super) fn data_availability_felts(&self) -> u128 {
+        let modified_contracts: HashSet<&Address> = self
+            .storage
+            .iter()
+            .map(|(address, _)| address)
+            .chain(&self.nonces)
+            .chain(&self.class_hashes)
+            .collect();
+
+        (2 * modified_contracts.len()
+            + self.class_hashes.len()
+            + 2 * self.storage.len()
+            + 2 * self.compiled_class_hashes.len()) as u128
+    }
+}
+
+fn changed_keys<K: Clone + Eq + Hash, V: PartialEq>(
+    previous: &HashMap<K, V>,
+    current: &HashMap<K, V>,
+) -> HashSet<K> {
+    current
+        .iter()
+        .filter(|(key, value)| previous.get(key) != Some(value))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// Reads the state from `state`, recording the keys of all values read. Classes are not recorded,
+/// since declared classes can't change.
+struct RecordingStateReader {
+    state: Arc<ForkingStateReader>,
+    read_keys: Mutex<StateKeys>,
+}
+
+impl RecordingStateReader {
+    fn new(state: Arc<ForkingStateReader>) -> Self {
+        Self { state, read_keys: Mutex::new(StateKeys::default()) }
+    }
+
+    fn record(&self, record: impl FnOnce(&mut StateKeys)) {
+        record(&mut self.read_keys.lock().expect("Read keys should not be poisoned"));
+    }
+}
+
+impl StateReader for RecordingStateReader {
+    fn get_contract_class(&self, class_hash: &[u8; 32]) -> Result<CompiledClass, StateError> {
+        self.state.get_contract_class(class_hash)
+    }
+
+    fn get_class_hash_at(&self, contract_address: &Address) -> Result<[u8; 32], StateError> {
+        self.record(|keys| {
+            keys.class_hashes.insert(contract_address.clone());
+        });
+        self.state.get_class_hash_at(contract_address)
+    }
+
+    fn get_nonce_at(&self, contract_address: &Address) -> Result<Felt252, StateError> {
+        self.record(|keys| {
+            keys.nonces.insert(contract_address.clone());
+        });
+        self.state.get_nonce_at(contract_address)
+    }
+
+    fn get_storage_at(&self, storage_entry: &(Address, [u8; 32])) -> Result<Felt252, StateError> {
+        self.record(|keys| {
+            keys.storage.insert(storage_entry.clone());
+        });
+        self.state.get_storage_at(storage_entry)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: &[u8; 32]) -> Result<[u8; 32], StateError> {
+        self.record(|keys| {
+            keys.compiled_class_hashes.insert(*class_hash);
+        });
+        self.state.get_compiled_class_hash(class_hash)
+    }
+}
+
+/// Storage entries of the low and high 128 bits of a balance held in a fee token
+type BalanceEntries = [StorageEntry; 2];
+
+fn read_balance(
+    state: &impl StateReader,
+    entries: &BalanceEntries,
+) -> std::result::Result<BigUint, StateError> {
+    let [low, high] = entries;
+    let low = BigUint::from(Felt::from(state.get_storage_at(low)?));
+    let high = BigUint::from(Felt::from(state.get_storage_at(high)?));
+    Ok((high << 128) + low)
+}
+
+fn write_balance(cache: &mut StateCache, entries: &BalanceEntries, balance: BigUint) -> Result<()> {
+    let [low, high] = entries;
+    let low_mask = (BigUint::from(1u32) << 128) - 1u32;
+    let writes = cache.storage_writes_mut();
+    writes.insert(low.clone(), Felt252::from(Felt::try_from(&balance & low_mask)?));
+    writes.insert(high.clone(), Felt252::from(Felt::try_from(balance >> 128)?));
+    Ok(())
+}
+
+/// Whether `call_info` or any of its internal calls accessed one of `entries`
+fn accesses_storage(call_info: &CallInfo, entries: &HashSet<StorageEntry>) -> bool {
+    call_info
+        .accessed_storage_keys
+        .iter()
+        .any(|key| entries.contains(&(call_info.contract_address.clone(), *key)))
+        || call_info.internal_calls.iter().any(|call| accesses_storage(call, entries))
+}
+
+/// Amounts the balances of the sequencer were increased by in a speculative execution, by the
+/// fee transfer of the transaction. Such increases commute, so reading the balances doesn't make
+/// the transaction conflict with the ones preceding it in the queue. None if the balances were
+/// accessed by the transaction itself, or decreased.
+fn fee_transfers(
+    tx_info: &TransactionExecutionInfo,
+    cache: &mut StateCache,
+    preceding_state: &ForkingStateReader,
+    sequencer_balances: &[BalanceEntries],
+) -> Result<Option<Vec<(BalanceEntries, BigUint)>>> {
+    let entries: HashSet<StorageEntry> = sequencer_balances.iter().flatten().cloned().collect();
+    let accessed_by_transaction = [&tx_info.validate_info, &tx_info.call_info]
+        .into_iter()
+        .flatten()
+        .any(|call_info| accesses_storage(call_info, &entries));
+    if accessed_by_transaction {
+        return Ok(None);
+    }
+
+    let mut transfers = vec![];
+    for balance_entries in sequencer_balances {
+        let writes = cache.storage_writes_mut();
+        if !balance_entries.iter().any(|entry| writes.contains_key(entry)) {
+            continue;
+        }
+
+        let initial = read_balance(preceding_state, balance_entries)?;
+        let [low, high] = balance_entries;
+        let written_value = |entry: &StorageEntry| match writes.get(entry) {
+            Some(value) => Ok(value.clone()),
+            None => preceding_state.get_storage_at(entry),
+        };
+        let written = (BigUint::from(Felt::from(written_value(high)?)) << 128)
+            + BigUint::from(Felt::from(written_value(low)?));
+        if written < initial {
+            return Ok(None);
+        }
+        transfers.push((balance_entries.clone(), written - initial));
+    }
+
+    Ok(Some(transfers))
+}
+
+/// Outcome of executing a queued transaction on its own, on top of the state preceding the queue
+struct SpeculativeExecution {
+    result: std::result::Result<TransactionExecutionInfo, TransactionError>,
+    read_keys: StateKeys,
+    cache: StateCache,
+}
+
+fn execute_speculatively(
+    state: &Arc<ForkingStateReader>,
+    queued: &QueuedInvoke,
+) -> SpeculativeExecution {
+    let reader = Arc::new(RecordingStateReader::new(state.clone()));
+    let mut speculative_state = CachedState::new(reader.clone(), None, None);
+    let result = execute_invoke_on(
+        &mut speculative_state,
+        &queued.invoke,
+        &queued.block_context,
+        queued.skip_validation,
+        queued.skip_fee_charge,
+    );
+
+    let read_keys =
+        std::mem::take(&mut *reader.read_keys.lock().expect("Read keys should not be poisoned"));
+    SpeculativeExecution { result, read_keys, cache: speculative_state.cache_mut().clone() }
+}
+
+/// Executes all `queued` transactions concurrently, each on top of `state` only. The results are
+/// in the order of `queued`.
+fn execute_all_speculatively(
+    state: &Arc<ForkingStateReader>,
+    queued: &[QueuedInvoke],
+) -> Vec<SpeculativeExecution> {
+    let threads = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk_size = (queued.len() + threads - 1) / threads;
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = queued
+            .chunks(chunk_size.max(1))
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|queued| execute_speculatively(state, queued))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Speculative execution should not panic"))
+            .collect()
+    })
+}
+
+/// Values read by a speculative execution become initial values of the pending state, unless the
+/// pending state already knows them
+fn unknown_entries<K: Eq + Hash, V>(
+    read_values: &mut HashMap<K, V>,
+    known_values: &HashMap<K, V>,
+) -> Vec<(K, V)> {
+    read_values.drain().filter(|(key, _)| !known_values.contains_key(key)).collect()
+}
+
+/// Applies the changes of a speculative execution to `pending_state`, leaving it as if the
+/// transaction had been executed on it
+fn merge_into_pending_state(
+    pending_state: &mut CachedState<ForkingStateReader>,
+    mut executed: StateCache,
+) {
+    let pending = pending_state.cache_mut();
+
+    for (key, value) in
+        unknown_entries(executed.storage_initial_values_mut(), pending.storage_writes_mut())
+    {
+        pending.storage_initial_values_mut().entry(key).or_insert(value);
+    }
+    for (key, value) in
+        unknown_entries(executed.nonce_initial_values_mut(), pending.nonce_writes_mut())
+    {
+        pending.nonce_initial_values_mut().entry(key).or_insert(value);
+    }
+    for (key, value) in
+        unknown_entries(executed.class_hash_initial_values_mut(), pending.class_hash_writes_mut())
+    {
+        pending.class_hash_initial_values_mut().entry(key).or_insert(value);
+    }
+    for (key, value) in unknown_entries(
+        executed.compiled_class_hash_initial_values_mut(),
+        pending.compiled_class_hash_writes_mut(),
+    ) {
+        pending.compiled_class_hash_initial_values_mut().entry(key).or_insert(value);
+    }
+
+    pending.storage_writes_mut().extend(executed.storage_writes_mut().drain());
+    pending.nonce_writes_mut().extend(executed.nonce_writes_mut().drain());
+    pending.class_hash_writes_mut().extend(executed.class_hash_writes_mut().drain());
+    pending
+        .compiled_class_hash_writes_mut()
+        .extend(executed.compiled_class_hash_writes_mut().drain());
+    pending
+        .class_hash_to_compiled_class_hash_mut()
+        .extend(executed.class_hash_to_compiled_class_hash_mut().drain());
+}
+
+impl Starknet {
+    /// Whether invoke transactions are queued and executed in parallel when the pending block is
+    /// committed
+    pub(crate) fn queues_invokes(&self) -> bool {
+        self.config.parallel_execution
+            && self.config.block_generation_on == super::BlockGenerationOn::Demand
+    }
+
+    /// Storage entries of the balances of the sequencer in the ETH and STRK fee tokens, written
+    /// by the fee transfer of every transaction
+    fn sequencer_balance_entries(&self) -> Result<Vec<BalanceEntries>> {
+        let sequencer_address =
+            ContractAddress::try_from(self.block_context.block_info().sequencer_address.clone())?;
+        let low_key =
+            get_storage_var_address("ERC20_balances", &[Felt::from(sequencer_address)])?.to_felt();
+        let high_key = Felt::try_from(BigUint::from(low_key) + 1u32)?;
+
+        [FeeUnit::Wei, FeeUnit::Fri]
+            .into_iter()
+            .map(|unit| {
+                let token_address = Address::try_from(self.fee_token_address(unit))?;
+                Ok([(token_address.clone(), low_key.bytes()), (token_address, high_key.bytes())])
+            })
+            .collect()
+    }
+
+    /// Executes the queued invoke transactions, leaving the same state, transactions and pending
+    /// block as if they had been executed one by one in the order of their arrival.
+    ///
+    /// All transactions are first executed concurrently, each on top of the pending state
+    /// preceding the queue. Their results are then committed in the order of arrival. A
+    /// transaction that read a value changed by a transaction committed before it would have
+    /// seen a different state if executed in order, so it is executed again on the pending state,
+    /// after all transactions preceding it. The balances of the sequencer, which every fee
+    /// transfer reads, are the exception: the amount a transaction transferred is added to the
+    /// pending balances when the transaction is committed.
+    pub(crate) fn execute_queued_invokes(&mut self) -> Result<()> {
+        if self.queued_invokes.is_empty() {
+            return Ok(());
+        }
+        let queued = std::mem::take(&mut self.queued_invokes);
+        let queued_count = queued.len() as u64;
+        let sequencer_balances = self.sequencer_balance_entries()?;
+
+        let mut state_preceding_queue = self.state.clone();
+        state_preceding_queue.apply_state_difference(
+            state_preceding_queue.extract_state_diff_from_pending_state()?,
+        )?;
+        let state_preceding_queue = Arc::new(ForkingStateReader::new(
+            state_preceding_queue.state,
+            state_preceding_queue.fork,
+        ));
+        let speculative_executions = execute_all_speculatively(&state_preceding_queue, &queued);
+
+        let mut written_keys = StateKeys::default();
+        let mut executed_sequentially = 0;
+        for (queued, speculative) in queued.into_iter().zip(speculative_executions) {
+            let SpeculativeExecution { result, mut read_keys, mut cache } = speculative;
+            let transfers = match &result {
+                Ok(tx_info) => {
+                    fee_transfers(tx_info, &mut cache, &state_preceding_queue, &sequencer_balances)?
+                }
+                Err(_) => None,
+            };
+            if transfers.is_some() {
+                for entry in sequencer_balances.iter().flatten() {
+                    read_keys.storage.remove(entry);
+                }
+            }
+            let check_actual_fee = |tx_info| {
+                estimations::check_actual_fee(
+                    &queued.transaction,
+                    tx_info,
+                    queued.block_context.block_info().gas_price as u128,
+                    queued.skip_fee_charge,
+                )
+            };
+
+            // keys written by the transaction, which determine the data gas it consumes
+            let mut transaction_keys = StateKeys::default();
+            let result = if read_keys.is_disjoint(&written_keys) {
+                let result = result.and_then(check_actual_fee);
+                if result.is_ok() {
+                    for (balance_entries, amount) in transfers.unwrap_or_default() {
+                        let pending_balance =
+                            read_balance(&self.state.pending_state, &balance_entries)?;
+                        write_balance(&mut cache, &balance_entries, pending_balance + amount)?;
+                    }
+                    transaction_keys =
+                        StateKeys::written_since(&mut StateCache::default(), &mut cache);
+                    merge_into_pending_state(&mut self.state.pending_state, cache);
+                }
+                result
+            } else {
+                executed_sequentially += 1;
+                let mut state_before_txn = self.state.pending_state.clone();
+                let result = execute_invoke_on(
+                    &mut self.state.pending_state,
+                    &queued.invoke,
+                    &queued.block_context,
+                    queued.skip_validation,
+                    queued.skip_fee_charge,
+                )
+                .and_then(check_actual_fee);
+                if result.is_ok() {
+                    transaction_keys = StateKeys::written_since(
+                        state_before_txn.cache_mut(),
+                        self.state.pending_state.cache_mut(),
+                    );
+                } else {
+                    self.state.pending_state = state_before_txn;
+                }
+                result
+            };
+
+            match result {
+                Ok(tx_info) => {
+                    let data_gas_consumed =
+                        transaction_keys.data_availability_felts() * estimations::DATA_GAS_PER_FELT;
+                    written_keys.extend(transaction_keys);
+                    self.handle_successful_transaction(
+                        &queued.transaction_hash,
+                        queued.transaction,
+                        tx_info,
+                        data_gas_consumed,
+                        queued.skip_validation,
+                    )?;
+                }
+                Err(tx_err) => {
+                    let transaction_to_add =
+                        StarknetTransaction::create_rejected(queued.transaction, tx_err);
+                    self.transactions.insert(&queued.transaction_hash, transaction_to_add);
+                }
+            }
+        }
+
+        let executed_in_parallel = queued_count - executed_sequentially;
+        self.parallel_execution_stats.executed_in_parallel += executed_in_parallel;
+        self.parallel_execution_stats.executed_sequentially += executed_sequentially;
+        info!(executed_in_parallel, executed_sequentially, "Executed queued invoke transactions");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::types::TransactionStatus;
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_storage_key::ContractStorageKey;
+    use starknet_types::felt::{Felt, TransactionHash};
+    use starknet_types::traits::HashProducer;
+
+    use super::ParallelExecutionStats;
+    use crate::account::Account;
+    use crate::constants::{DEVNET_DEFAULT_CHAIN_ID, ERC20_CONTRACT_ADDRESS};
+    use crate::error::Error;
+    use crate::starknet::{BlockGenerationOn, Starknet, StarknetConfig};
+    use crate::state::state_diff::StateDiff;
+    use crate::traits::{Accounted, Deployed, HashIdentified, StateChanger, StateExtractor};
+    use crate::transactions::invoke_transaction::InvokeTransactionV1;
+    use crate::utils::get_storage_var_address;
+    use crate::utils::test_utils::{
+        cairo_0_account_without_validations, dummy_cairo_0_contract_class, dummy_felt,
+        starknet_config_for_test,
+    };
+
+    /// Deploys `count` accounts without validations, each with its own instance of the dummy
+    /// contract. Returns the pairs of account and contract addresses.
+    fn setup(
+        parallel_execution: bool,
+        count: u32,
+    ) -> (Starknet, Vec<(ContractAddress, ContractAddress)>) {
+        let config = StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            parallel_execution,
+            // fees are charged, so that every transaction transfers its fee to the sequencer
+            gas_price: 1,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        let account_class = cairo_0_account_without_validations();
+        let account_class_hash = account_class.generate_hash().unwrap();
+        let dummy_contract = dummy_cairo_0_contract_class();
+        let dummy_contract_class_hash = dummy_contract.generate_hash().unwrap();
+        starknet.state.declare_contract_class(dummy_contract_class_hash, dummy_contract).unwrap();
+
+        let mut addresses = vec![];
+        for i in 1..=count {
+            let account = Account::new(
+                Felt::from(1_000_000_000),
+                Felt::from(i as u128),
+                dummy_felt(),
+                account_class_hash,
+                account_class.clone(),
+                ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                    .unwrap(),
+            )
+            .unwrap();
+            account.deploy(&mut starknet.state).unwrap();
+            account.set_initial_balance(&mut starknet.state).unwrap();
+
+            let contract_address = ContractAddress::new(Felt::from(0x1000 + i as u128)).unwrap();
+            starknet.state.deploy_contract(contract_address, dummy_contract_class_hash).unwrap();
+
+            addresses.push((account.account_address, contract_address));
+        }
+        starknet.state.synchronize_states();
+
+        (starknet, addresses)
+    }
+
+    fn increase_balance_transaction(
+        account_address: ContractAddress,
+        contract_address: ContractAddress,
+        amount: u128,
+        nonce: u128,
+    ) -> InvokeTransactionV1 {
+        let calldata = vec![
+            contract_address.into(), // contract address
+            get_selector_from_name("increase_balance").unwrap().into(), // function selector
+            Felt::from(1),           // calldata len
+            Felt::from(amount),      // calldata
+        ];
+        InvokeTransactionV1::new(
+            account_address,
+            1_000_000,
+            vec![],
+            Felt::from(nonce),
+            calldata,
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap()
+    }
+
+    fn increase_balance(
+        starknet: &mut Starknet,
+        account_address: ContractAddress,
+        contract_address: ContractAddress,
+        amount: u128,
+        nonce: u128,
+    ) -> TransactionHash {
+        let invoke_transaction =
+            increase_balance_transaction(account_address, contract_address, amount, nonce);
+        starknet.add_invoke_transaction_v1(invoke_transaction).unwrap()
+    }
+
+    fn balance_storage_key(contract_address: ContractAddress) -> ContractStorageKey {
+        ContractStorageKey::new(contract_address, get_storage_var_address("balance", &[]).unwrap())
+    }
+
+    /// Sends one transaction per account, executes the queued ones and creates a block. Returns
+    /// the state diff of the block.
+    fn send_independent_invokes(
+        starknet: &mut Starknet,
+        addresses: &[(ContractAddress, ContractAddress)],
+    ) -> StateDiff {
+        let transaction_hashes: Vec<_> = addresses
+            .iter()
+            .enumerate()
+            .map(|(i, (account_address, contract_address))| {
+                increase_balance(starknet, *account_address, *contract_address, i as u128 + 1, 0)
+            })
+            .collect();
+
+        starknet.execute_queued_invokes().unwrap();
+        let state_diff = starknet.state.extract_state_diff_from_pending_state().unwrap();
+        starknet.create_block().unwrap();
+
+        for transaction_hash in transaction_hashes {
+            let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+            assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        }
+
+        state_diff
+    }
+
+    #[test]
+    fn independent_invokes_executed_in_parallel_with_same_result_as_sequentially() {
+        let (mut sequential, addresses) = setup(false, 100);
+        let sequential_state_diff = send_independent_invokes(&mut sequential, &addresses);
+
+        let (mut parallel, addresses) = setup(true, 100);
+        let parallel_state_diff = send_independent_invokes(&mut parallel, &addresses);
+
+        assert_eq!(parallel_state_diff, sequential_state_diff);
+        assert_eq!(
+            parallel.parallel_execution_stats,
+            ParallelExecutionStats { executed_in_parallel: 100, executed_sequentially: 0 }
+        );
+        assert_eq!(sequential.parallel_execution_stats, ParallelExecutionStats::default());
+
+        // the fees of all transactions were transferred to the sequencer
+        let sequencer_balance_key = ContractStorageKey::new(
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap(),
+            get_storage_var_address(
+                "ERC20_balances",
+                &[Felt::from(parallel.config.sequencer_address)],
+            )
+            .unwrap(),
+        );
+        let sequencer_balance = parallel.state.get_storage(sequencer_balance_key).unwrap();
+        assert_ne!(sequencer_balance, Felt::from(0));
+        assert_eq!(sequencer_balance, sequential.state.get_storage(sequencer_balance_key).unwrap());
+
+        for (i, (_, contract_address)) in addresses.into_iter().enumerate() {
+            assert_eq!(
+                parallel.state.get_storage(balance_storage_key(contract_address)).unwrap(),
+                Felt::from(i as u128 + 1)
+            );
+        }
+        let block_transaction_hashes = |starknet: &Starknet| {
+            let block = starknet.get_latest_block().unwrap();
+            block.get_transactions().iter().map(|t| t.get_hash().unwrap()).collect::<Vec<_>>()
+        };
+        assert_eq!(block_transaction_hashes(&parallel), block_transaction_hashes(&sequential));
+    }
+
+    #[test]
+    fn conflicting_invokes_executed_sequentially() {
+        let (mut starknet, addresses) = setup(true, 2);
+        let (first_account, first_contract) = addresses[0];
+        let (second_account, second_contract) = addresses[1];
+
+        // same sender, so the second transaction reads the nonce written by the first one
+        increase_balance(&mut starknet, first_account, first_contract, 10, 0);
+        increase_balance(&mut starknet, first_account, first_contract, 15, 1);
+        // same contract storage, written by another sender
+        increase_balance(&mut starknet, second_account, first_contract, 5, 0);
+        // independent of the others
+        increase_balance(&mut starknet, second_account, second_contract, 7, 1);
+        // the nonce was already used, so the transaction is refused instead of being queued
+        let reused_nonce = increase_balance_transaction(first_account, second_contract, 1, 0);
+        assert!(matches!(
+            starknet.add_invoke_transaction_v1(reused_nonce),
+            Err(Error::InvalidTransactionNonce { .. })
+        ));
+
+        starknet.create_block().unwrap();
+
+        assert_eq!(starknet.get_latest_block().unwrap().get_transactions().len(), 4);
+        assert_eq!(
+            starknet.state.get_storage(balance_storage_key(first_contract)).unwrap(),
+            Felt::from(30)
+        );
+        assert_eq!(
+            starknet.state.get_storage(balance_storage_key(second_contract)).unwrap(),
+            Felt::from(7)
+        );
+        // only the first transaction saw the state it would have seen if executed in order
+        assert_eq!(
+            starknet.parallel_execution_stats,
+            ParallelExecutionStats { executed_in_parallel: 1, executed_sequentially: 3 }
+        );
+    }
+}
@@ -1,11 +1,13 @@
-use starknet_rs_core::utils::get_selector_from_name;
+use starknet_rs_core::utils::cairo_short_string_to_felt;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_storage_key::ContractStorageKey;
 use starknet_types::felt::Felt;
 
+use starknet_types::traits::ToHexString;
+
 use crate::constants::{
-    CHARGEABLE_ACCOUNT_ADDRESS, ERC20_CONTRACT_ADDRESS, ERC20_CONTRACT_CLASS_HASH,
-    ERC20_CONTRACT_PATH, UDC_CONTRACT_ADDRESS, UDC_CONTRACT_CLASS_HASH, UDC_CONTRACT_PATH,
+    CHARGEABLE_ACCOUNT_ADDRESS, ERC20_CONTRACT_CLASS_HASH, ERC20_CONTRACT_PATH,
+    UDC_CONTRACT_ADDRESS, UDC_CONTRACT_CLASS_HASH, UDC_CONTRACT_PATH,
 };
 use crate::error::{Error, Result};
 use crate::state::StarknetState;
@@ -13,30 +15,41 @@ use crate::system_contract::SystemContract;
 use crate::traits::StateChanger;
 use crate::utils::get_storage_var_address;
 
-pub(crate) fn create_erc20() -> Result<SystemContract> {
+/// ETH fee token
+pub(crate) fn create_erc20(address: ContractAddress) -> Result<SystemContract> {
+    create_erc20_at_address(&Felt::from(address).to_prefixed_hex_str())
+}
+
+/// STRK fee token
+pub(crate) fn create_strk_erc20(address: ContractAddress) -> Result<SystemContract> {
+    create_erc20_at_address(&Felt::from(address).to_prefixed_hex_str())
+}
+
+fn create_erc20_at_address(address: &str) -> Result<SystemContract> {
     let erc20_contract_class_json_str =
         std::fs::read_to_string(ERC20_CONTRACT_PATH).map_err(|err| Error::ReadFileError {
             source: err,
             path: ERC20_CONTRACT_PATH.to_string(),
         })?;
-    let erc20_fee_contract = SystemContract::new(
-        ERC20_CONTRACT_CLASS_HASH,
-        ERC20_CONTRACT_ADDRESS,
-        &erc20_contract_class_json_str,
-    )?;
+    let erc20_fee_contract =
+        SystemContract::new(ERC20_CONTRACT_CLASS_HASH, address, &erc20_contract_class_json_str)?;
 
     Ok(erc20_fee_contract)
 }
 
-/// Set initial values of ERC20 contract storage
-pub(crate) fn initialize_erc20(state: &mut StarknetState) -> Result<()> {
-    let contract_address =
-        ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS)?)?;
-
+/// Set initial values of ERC20 contract storage. The name and symbol are stored as Cairo short
+/// strings, so they can't be longer than 31 ASCII characters.
+pub(crate) fn initialize_erc20(
+    state: &mut StarknetState,
+    contract_address: ContractAddress,
+    name: &str,
+    symbol: &str,
+    decimals: u8,
+) -> Result<()> {
     for (storage_var_name, storage_value) in [
-        ("ERC20_name", get_selector_from_name("ether").unwrap().into()),
-        ("ERC20_symbol", get_selector_from_name("ETH").unwrap().into()),
-        ("ERC20_decimals", 18.into()),
+        ("ERC20_name", short_string_to_felt(name)?),
+        ("ERC20_symbol", short_string_to_felt(symbol)?),
+        ("ERC20_decimals", Felt::from(decimals as u128)),
         // necessary to set - otherwise minting txs cannot be executed
         ("Ownable_owner", Felt::from_prefixed_hex_str(CHARGEABLE_ACCOUNT_ADDRESS)?),
     ] {
@@ -48,6 +61,12 @@ pub(crate) fn initialize_erc20(state: &mut StarknetState) -> Result<()> {
     Ok(())
 }
 
+fn short_string_to_felt(value: &str) -> Result<Felt> {
+    cairo_short_string_to_felt(value).map(Felt::from).map_err(|err| Error::FeeTokenError {
+        msg: format!("Invalid short string {value:?}: {err}"),
+    })
+}
+
 pub(crate) fn create_udc() -> Result<SystemContract> {
     let udc_contract_class_json_str = std::fs::read_to_string(UDC_CONTRACT_PATH)
         .map_err(|err| Error::ReadFileError { source: err, path: UDC_CONTRACT_PATH.to_string() })?;
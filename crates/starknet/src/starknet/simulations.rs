@@ -0,0 +1,224 @@
+use starknet_in_rust::definitions::constants::INITIAL_GAS_COST;
+use starknet_in_rust::execution::TransactionExecutionInfo;
+use starknet_in_rust::state::state_api::StateReader;
+use starknet_in_rust::utils::Address;
+use starknet_rs_core::types::BlockId;
+
+use super::estimations::{self, FeeEstimation};
+use super::Starknet;
+use crate::error::{Error, Result};
+use crate::traces::TransactionTrace;
+use crate::transactions::Transaction;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedTransaction {
+    pub transaction_trace: TransactionTrace,
+    pub fee_estimation: FeeEstimation,
+}
+
+/// Executes `transactions` one after another on a copy of the state at `block_id`, so that every
+/// transaction sees the changes of the previous ones, without affecting Devnet's state. If
+/// `skip_fee_charge` is set, the max fee is not checked and the fee is not transferred. A block
+/// that was already created is reproduced with the state, number, timestamp and gas prices it had,
+/// so a transaction sent from an account that was not deployed yet at that block fails with
+/// [`Error::ContractNotFound`]. Every execution info is returned together with the data gas
+/// consumed by the state diff of the transaction.
+pub(crate) fn execute_sequentially(
+    starknet: &Starknet,
+    block_id: BlockId,
+    transactions: &[Transaction],
+    skip_validate: bool,
+    skip_fee_charge: bool,
+) -> Result<Vec<(TransactionExecutionInfo, u128)>> {
+    let mut state = starknet.get_state_at(&block_id)?.pending_state.clone();
+
+    transactions
+        .iter()
+        .enumerate()
+        .map(|(index, transaction)| {
+            if !matches!(
+                transaction,
+                Transaction::DeployAccount(_) | Transaction::DeployAccountV3(_)
+            ) {
+                let sender = Address(transaction.sender_address()?.into());
+                // the sender may also have been deployed by one of the preceding transactions
+                if state.get_class_hash_at(&sender).is_err() {
+                    return Err(Error::ContractNotFound);
+                }
+            }
+
+            let block_context = starknet.block_context_at(&block_id, transaction.fee_unit())?;
+            let mut cache_before_txn = state.cache_mut().clone();
+            let execution_info = transaction
+                .create_sir_transaction()?
+                .create_for_simulation(skip_validate, false, skip_fee_charge, skip_fee_charge)
+                .execute(&mut state, &block_context, INITIAL_GAS_COST)
+                .map_err(|source| Error::TransactionFailed { index, source })?;
+
+            let data_gas_consumed =
+                estimations::data_gas_consumed(&mut cache_before_txn, state.cache_mut());
+            Ok((execution_info, data_gas_consumed))
+        })
+        .collect()
+}
+
+/// Same as [`execute_sequentially`], but returns the trace of each transaction together with its
+/// fee estimation. Reverted transactions are not treated as failures; their trace holds the
+/// reason of the revert.
+pub fn simulate_transactions(
+    starknet: &Starknet,
+    block_id: BlockId,
+    transactions: &[Transaction],
+    skip_validate: bool,
+    skip_fee_charge: bool,
+) -> Result<Vec<SimulatedTransaction>> {
+    execute_sequentially(starknet, block_id, transactions, skip_validate, skip_fee_charge)?
+        .iter()
+        .zip(transactions)
+        .map(|((execution_info, data_gas_consumed), transaction)| {
+            let unit = transaction.fee_unit();
+            let (gas_price, data_gas_price) = starknet.gas_prices_at(&block_id, unit)?;
+            Ok(SimulatedTransaction {
+                transaction_trace: TransactionTrace::new(transaction, execution_info)?,
+                fee_estimation: FeeEstimation::new(
+                    execution_info,
+                    *data_gas_consumed,
+                    gas_price,
+                    data_gas_price,
+                    unit,
+                ),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_in_rust::definitions::block_context::StarknetChainId;
+    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::HashProducer;
+
+    use crate::account::Account;
+    use crate::constants::ERC20_CONTRACT_ADDRESS;
+    use crate::error::Error;
+    use crate::starknet::Starknet;
+    use crate::traits::{Accounted, Deployed};
+    use crate::transactions::invoke_transaction::InvokeTransactionV1;
+    use crate::transactions::Transaction;
+    use crate::utils::test_utils::{
+        cairo_0_account_without_validations, dummy_felt, starknet_config_for_test,
+    };
+
+    fn deploy_account_without_validations(starknet: &mut Starknet, public_key: Felt) -> Account {
+        let contract_class = cairo_0_account_without_validations();
+        let account = Account::new(
+            Felt::from(1_000_000),
+            public_key,
+            dummy_felt(),
+            contract_class.generate_hash().unwrap(),
+            contract_class,
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        account.deploy(&mut starknet.state).unwrap();
+        account.set_initial_balance(&mut starknet.state).unwrap();
+
+        account
+    }
+
+    /// Invoke of `outer` which forwards a `balanceOf` call through `inner`
+    fn nested_invoke(outer: ContractAddress, inner: ContractAddress) -> Transaction {
+        let calldata = vec![
+            inner.into(),                                                 // contract address
+            get_selector_from_name("__execute__").unwrap().into(),        // function selector
+            Felt::from(4),                                                // calldata len
+            Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap(), // contract address
+            get_selector_from_name("balanceOf").unwrap().into(),          // function selector
+            Felt::from(1),                                                // calldata len
+            outer.into(),                                                 // account
+        ];
+
+        Transaction::Invoke(Box::new(
+            InvokeTransactionV1::new(
+                outer,
+                10000,
+                vec![],
+                Felt::from(0),
+                calldata,
+                StarknetChainId::TestNet.to_felt().into(),
+            )
+            .unwrap(),
+        ))
+    }
+
+    #[test]
+    fn trace_contains_nested_calls() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let outer = deploy_account_without_validations(&mut starknet, Felt::from(1));
+        let inner = deploy_account_without_validations(&mut starknet, Felt::from(2));
+        starknet.state.synchronize_states();
+
+        let simulations = starknet
+            .simulate_transactions(
+                BlockId::Tag(BlockTag::Latest),
+                &[nested_invoke(outer.get_address(), inner.get_address())],
+                false,
+                true,
+            )
+            .unwrap();
+        assert_eq!(simulations.len(), 1);
+
+        let trace = &simulations[0].transaction_trace;
+        assert!(trace.validate_invocation.is_some());
+        assert!(trace.fee_transfer_invocation.is_none());
+        assert!(trace.revert_reason.is_none());
+
+        // outer __execute__ -> inner __execute__ -> balanceOf
+        let execute_invocation = trace.execute_invocation.as_ref().unwrap();
+        assert_eq!(execute_invocation.depth(), 3);
+        assert_eq!(execute_invocation.contract_address, outer.get_address());
+        let inner_invocation = &execute_invocation.calls[0];
+        assert_eq!(inner_invocation.contract_address, inner.get_address());
+        assert_eq!(
+            inner_invocation.calls[0].entry_point_selector,
+            get_selector_from_name("balanceOf").unwrap().into()
+        );
+    }
+
+    #[test]
+    fn simulation_at_block_preceding_deployment_of_sender_fails() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let inner = deploy_account_without_validations(&mut starknet, Felt::from(2));
+        starknet.state.synchronize_states();
+        let block_before_deployment = starknet.create_block().unwrap();
+
+        let outer = deploy_account_without_validations(&mut starknet, Felt::from(1));
+        starknet.state.synchronize_states();
+        let block_of_deployment = starknet.create_block().unwrap();
+
+        let transactions = [nested_invoke(outer.get_address(), inner.get_address())];
+        assert!(matches!(
+            starknet.simulate_transactions(
+                BlockId::Hash(block_before_deployment.into()),
+                &transactions,
+                false,
+                true,
+            ),
+            Err(Error::ContractNotFound)
+        ));
+
+        let simulations = starknet
+            .simulate_transactions(
+                BlockId::Hash(block_of_deployment.into()),
+                &transactions,
+                false,
+                true,
+            )
+            .unwrap();
+        assert!(simulations[0].transaction_trace.revert_reason.is_none());
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::HashSet;
+
+use starknet_api::block::BlockNumber;
+use starknet_in_rust::definitions::block_context::BlockContext;
+use starknet_in_rust::state::cached_state::CachedState;
+use starknet_rs_core::types::{BlockId, BlockTag, TransactionStatus};
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{ClassHash, Felt};
+
+use super::messaging::MessageToL1;
+use super::Starknet;
+use crate::blocks::StarknetBlock;
+use crate::error::{Error, Result};
+use crate::state::fork::ForkingStateReader;
+use crate::state::StarknetState;
+use crate::traits::HashIdentifiedMut;
+
+/// Point Devnet can be reverted to. The committed state is not copied: it is the state of the
+/// last block at the time of the snapshot, which is kept in the state archive of blocks. Only the
+/// changes of the pending block are held by the snapshot.
+#[derive(Clone)]
+pub(crate) struct Snapshot {
+    /// Last created block, None if no block was created yet
+    block_number: Option<BlockNumber>,
+    pending_block: StarknetBlock,
+    pending_state: CachedState<ForkingStateReader>,
+    /// Arrival index of the first transaction added after the snapshot
+    next_arrival_index: u64,
+    block_context: BlockContext,
+    data_gas_price: u64,
+    pending_block_timestamp_shift: i64,
+    impersonated_accounts: HashSet<ContractAddress>,
+    l1_messaging_contract_address: Option<Felt>,
+    consumed_l1_to_l2_message_nonces: HashSet<Felt>,
+    messages_to_l1: Vec<MessageToL1>,
+    sierra_class_hashes: HashSet<ClassHash>,
+}
+
+impl Starknet {
+    /// Captures the current state and block height. Returns the id of the snapshot, which is
+    /// greater than the ids of all snapshots taken before.
+    pub fn take_snapshot(&mut self) -> Result<u64> {
+        self.execute_queued_invokes()?;
+
+        let snapshot = Snapshot {
+            block_number: self.last_block_number(),
+            pending_block: self.blocks.pending_block.clone(),
+            pending_state: self.state.pending_state.clone(),
+            next_arrival_index: self.transactions.next_arrival_index(),
+            block_context: self.block_context.clone(),
+            data_gas_price: self.data_gas_price,
+            pending_block_timestamp_shift: self.pending_block_timestamp_shift,
+            impersonated_accounts: self.impersonated_accounts.clone(),
+            l1_messaging_contract_address: self.l1_messaging_contract_address,
+            consumed_l1_to_l2_message_nonces: self.consumed_l1_to_l2_message_nonces.clone(),
+            messages_to_l1: self.messages_to_l1.clone(),
+            sierra_class_hashes: self.sierra_contracts.keys().copied().collect(),
+        };
+
+        let snapshot_id = self.next_snapshot_id;
+        self.next_snapshot_id += 1;
+        self.snapshots.insert(snapshot_id, snapshot);
+
+        Ok(snapshot_id)
+    }
+
+    /// Restores the state, blocks and transactions captured by the snapshot with the given id.
+    /// Blocks created and transactions added after the snapshot are discarded, and so are the
+    /// snapshots taken after it. The snapshot itself stays valid, so it can be reverted to again.
+    pub fn revert_to_snapshot(&mut self, snapshot_id: u64) -> Result<()> {
+        let snapshot =
+            self.snapshots.get(&snapshot_id).cloned().ok_or(Error::NoSnapshot { snapshot_id })?;
+
+        let committed_state = match snapshot.block_number {
+            Some(block_number) => self
+                .blocks
+                .num_to_state
+                .get(&block_number)
+                .map(|state| state.state.clone())
+                .ok_or(Error::NoStateAtBlock { block_number: block_number.0 })?,
+            None => self.genesis_state.state.clone(),
+        };
+
+        self.snapshots.retain(|id, _| *id <= snapshot_id);
+        // transactions queued or awaiting preceding nonces were not executed yet; the awaiting
+        // ones received before the snapshot are dropped as rejected
+        self.queued_invokes.clear();
+        for transaction_hash in
+            self.transactions_awaiting_nonce.drain(..).filter_map(|t| t.get_hash())
+        {
+            if let Some(transaction) = self.transactions.get_by_hash_mut(&transaction_hash) {
+                transaction.status = TransactionStatus::Rejected;
+                self.transactions.notify_status_change(&transaction_hash);
+            }
+        }
+
+        let first_discarded_block = snapshot
+            .block_number
+            .map_or(self.config.initial_block_number, |block_number| block_number.0 + 1);
+        self.blocks.remove_blocks_from(BlockNumber(first_discarded_block));
+        self.transactions.remove_from_arrival_index(snapshot.next_arrival_index);
+
+        // transactions of the restored pending block may have been committed after the snapshot
+        for transaction_hash in
+            snapshot.pending_block.get_transactions().iter().filter_map(|t| t.get_hash())
+        {
+            if let Some(transaction) = self.transactions.get_by_hash_mut(&transaction_hash) {
+                transaction.status = TransactionStatus::Pending;
+                transaction.block_hash = None;
+                transaction.block_number = None;
+                self.transactions.notify_status_change(&transaction_hash);
+            }
+        }
+        self.blocks.pending_block = snapshot.pending_block;
+
+        self.state = StarknetState {
+            state: committed_state,
+            pending_state: snapshot.pending_state,
+            fork: self.state.fork.clone(),
+        };
+        self.block_context = snapshot.block_context;
+        self.data_gas_price = snapshot.data_gas_price;
+        self.pending_block_timestamp_shift = snapshot.pending_block_timestamp_shift;
+        self.impersonated_accounts = snapshot.impersonated_accounts;
+        self.l1_messaging_contract_address = snapshot.l1_messaging_contract_address;
+        self.consumed_l1_to_l2_message_nonces = snapshot.consumed_l1_to_l2_message_nonces;
+        self.messages_to_l1 = snapshot.messages_to_l1;
+        self.sierra_contracts
+            .retain(|class_hash, _| snapshot.sierra_class_hashes.contains(class_hash));
+
+        self.persist_block_count();
+
+        Ok(())
+    }
+
+    /// Discards the snapshots whose last block is `block_number` or a later one, e.g. because
+    /// the block was aborted
+    pub(crate) fn invalidate_snapshots_from(&mut self, block_number: BlockNumber) {
+        self.snapshots.retain(|_, snapshot| {
+            snapshot.block_number.map_or(true, |snapshot_block| snapshot_block < block_number)
+        });
+    }
+
+    fn last_block_number(&self) -> Option<BlockNumber> {
+        self.blocks
+            .get_by_block_id(BlockId::Tag(BlockTag::Latest))
+            .map(|block| block.block_number())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_api::block::BlockNumber;
+    use starknet_rs_core::types::{BlockId, BlockTag, TransactionStatus};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_storage_key::ContractStorageKey;
+    use starknet_types::felt::{Felt, TransactionHash};
+    use starknet_types::traits::HashProducer;
+
+    use crate::account::Account;
+    use crate::constants::{DEVNET_DEFAULT_CHAIN_ID, ERC20_CONTRACT_ADDRESS};
+    use crate::error::Error;
+    use crate::starknet::{BlockGenerationOn, Starknet, StarknetConfig};
+    use crate::traits::{Accounted, Deployed, HashIdentified, StateChanger, StateExtractor};
+    use crate::transactions::invoke_transaction::InvokeTransactionV1;
+    use crate::utils::get_storage_var_address;
+    use crate::utils::test_utils::{
+        cairo_0_account_without_validations, dummy_cairo_0_contract_class, dummy_felt,
+        starknet_config_for_test,
+    };
+
+    /// Deploys an account without validations and an instance of the dummy contract. Returns
+    /// their addresses.
+    fn setup(config: &StarknetConfig) -> (Starknet, ContractAddress, ContractAddress) {
+        let mut starknet = Starknet::new(config).unwrap();
+
+        let account_class = cairo_0_account_without_validations();
+        let account = Account::new(
+            Felt::from(1_000_000),
+            dummy_felt(),
+            dummy_felt(),
+            account_class.generate_hash().unwrap(),
+            account_class,
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        account.deploy(&mut starknet.state).unwrap();
+        account.set_initial_balance(&mut starknet.state).unwrap();
+
+        let dummy_contract = dummy_cairo_0_contract_class();
+        let dummy_contract_class_hash = dummy_contract.generate_hash().unwrap();
+        let contract_address = ContractAddress::new(Felt::from(0x1234)).unwrap();
+        starknet.state.declare_contract_class(dummy_contract_class_hash, dummy_contract).unwrap();
+        starknet.state.deploy_contract(contract_address, dummy_contract_class_hash).unwrap();
+        starknet.state.synchronize_states();
+        // snapshots taken before the first block after genesis are reverted to the genesis state
+        starknet.genesis_state = starknet.state.clone();
+        starknet.blocks.save_state_at(BlockNumber(0), starknet.state.clone());
+
+        (starknet, account.account_address, contract_address)
+    }
+
+    fn increase_balance(
+        starknet: &mut Starknet,
+        account_address: ContractAddress,
+        contract_address: ContractAddress,
+        amount: u128,
+        nonce: u128,
+    ) -> TransactionHash {
+        let calldata = vec![
+            contract_address.into(), // contract address
+            get_selector_from_name("increase_balance").unwrap().into(), // function selector
+            Felt::from(1),           // calldata len
+            Felt::from(amount),      // calldata
+        ];
+        let invoke_transaction = InvokeTransactionV1::new(
+            account_address,
+            10000,
+            vec![],
+            Felt::from(nonce),
+            calldata,
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap();
+
+        starknet.add_invoke_transaction_v1(invoke_transaction).unwrap()
+    }
+
+    fn balance_at(starknet: &Starknet, contract_address: ContractAddress) -> Felt {
+        let storage_key = ContractStorageKey::new(
+            contract_address,
+            get_storage_var_address("balance", &[]).unwrap(),
+        );
+        starknet.state.get_storage(storage_key).unwrap()
+    }
+
+    #[test]
+    fn revert_restores_storage_and_block_height() {
+        let (mut starknet, account_address, contract_address) = setup(&starknet_config_for_test());
+
+        increase_balance(&mut starknet, account_address, contract_address, 10, 0);
+        let snapshot_id = starknet.take_snapshot().unwrap();
+        let latest_block = starknet.get_latest_block().unwrap();
+
+        let transaction_hash =
+            increase_balance(&mut starknet, account_address, contract_address, 15, 1);
+        assert_eq!(balance_at(&starknet, contract_address), Felt::from(25));
+
+        starknet.revert_to_snapshot(snapshot_id).unwrap();
+        assert_eq!(balance_at(&starknet, contract_address), Felt::from(10));
+        assert_eq!(starknet.get_latest_block().unwrap().block_hash(), latest_block.block_hash());
+        assert!(starknet.transactions.get_by_hash(transaction_hash).is_none());
+
+        // the nonce is restored as well, so the reverted transaction can be sent again
+        let transaction_hash =
+            increase_balance(&mut starknet, account_address, contract_address, 15, 1);
+        let transaction = starknet.transactions.get_by_hash(transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::AcceptedOnL2);
+        assert_eq!(balance_at(&starknet, contract_address), Felt::from(25));
+        assert_eq!(
+            starknet.get_latest_block().unwrap().block_number().0,
+            latest_block.block_number().0 + 1
+        );
+    }
+
+    #[test]
+    fn revert_restores_pending_block_in_demand_mode() {
+        let config = StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..starknet_config_for_test()
+        };
+        let (mut starknet, account_address, contract_address) = setup(&config);
+
+        let pending_transaction_hash =
+            increase_balance(&mut starknet, account_address, contract_address, 10, 0);
+        let snapshot_id = starknet.take_snapshot().unwrap();
+
+        increase_balance(&mut starknet, account_address, contract_address, 15, 1);
+        starknet.create_block().unwrap();
+
+        starknet.revert_to_snapshot(snapshot_id).unwrap();
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+        let pending_block = starknet.get_block(BlockId::Tag(BlockTag::Pending)).unwrap();
+        assert_eq!(
+            pending_block
+                .get_transactions()
+                .iter()
+                .map(|t| t.get_hash().unwrap())
+                .collect::<Vec<_>>(),
+            vec![pending_transaction_hash]
+        );
+        let transaction = starknet.transactions.get_by_hash(pending_transaction_hash).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Pending);
+        assert_eq!(transaction.block_hash(), None);
+
+        // the pending changes are committed with the next block
+        starknet.create_block().unwrap();
+        assert_eq!(balance_at(&starknet, contract_address), Felt::from(10));
+    }
+
+    #[test]
+    fn revert_invalidates_later_snapshots() {
+        let (mut starknet, account_address, contract_address) = setup(&starknet_config_for_test());
+
+        let first_snapshot_id = starknet.take_snapshot().unwrap();
+        increase_balance(&mut starknet, account_address, contract_address, 10, 0);
+        let second_snapshot_id = starknet.take_snapshot().unwrap();
+        assert!(second_snapshot_id > first_snapshot_id);
+
+        starknet.revert_to_snapshot(first_snapshot_id).unwrap();
+        assert_eq!(balance_at(&starknet, contract_address), Felt::from(0));
+        match starknet.revert_to_snapshot(second_snapshot_id) {
+            Err(Error::NoSnapshot { snapshot_id }) => assert_eq!(snapshot_id, second_snapshot_id),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        // the reverted snapshot can be reverted to again
+        increase_balance(&mut starknet, account_address, contract_address, 5, 0);
+        starknet.revert_to_snapshot(first_snapshot_id).unwrap();
+        assert_eq!(balance_at(&starknet, contract_address), Felt::from(0));
+    }
+
+    #[test]
+    fn aborting_blocks_invalidates_snapshots_taken_after_them() {
+        let (mut starknet, account_address, contract_address) = setup(&starknet_config_for_test());
+
+        starknet.create_block().unwrap();
+        let kept_snapshot_id = starknet.take_snapshot().unwrap();
+        increase_balance(&mut starknet, account_address, contract_address, 10, 0);
+        let aborted_block_hash = starknet.get_latest_block().unwrap().block_hash();
+        let invalidated_snapshot_id = starknet.take_snapshot().unwrap();
+
+        starknet.abort_blocks(aborted_block_hash).unwrap();
+        assert!(matches!(
+            starknet.revert_to_snapshot(invalidated_snapshot_id),
+            Err(Error::NoSnapshot { .. })
+        ));
+        starknet.revert_to_snapshot(kept_snapshot_id).unwrap();
+    }
+}
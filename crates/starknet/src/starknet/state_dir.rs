@@ -0,0 +1,226 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use tracing::error;
+
+use super::dump::{Dump, DumpedBlock, DUMP_VERSION};
+use super::Starknet;
+use crate::error::{Error, Result};
+
+const INDEX_FILE_NAME: &str = "index.json";
+
+/// Lists the blocks persisted in the state directory. Only the blocks listed here are loaded, so a
+/// block file written right before a crash, but not indexed yet, is ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StateIndex {
+    version: String,
+    /// The `block_count` blocks after the genesis block are persisted, one file per block
+    block_count: u64,
+}
+
+fn block_file_path(state_dir: &str, block_number: u64) -> PathBuf {
+    Path::new(state_dir).join(format!("block_{block_number}.json"))
+}
+
+/// Writes `value` as JSON to a temporary file, which is then renamed to `path`. The rename is
+/// atomic, so a crash leaves either the previous file or the new one at `path`, never a partially
+/// written one.
+fn write_atomically<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let to_state_dir_error = |err: std::io::Error| Error::StateDirError {
+        msg: format!("Cannot write {}: {err}", path.display()),
+    };
+
+    let tmp_path = path.with_extension("json.tmp");
+    let contents =
+        serde_json::to_vec(value).map_err(|err| Error::StateDirError { msg: err.to_string() })?;
+    fs::write(&tmp_path, contents).map_err(to_state_dir_error)?;
+    fs::rename(&tmp_path, path).map_err(to_state_dir_error)
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| Error::ReadFileError { source: err, path: path.display().to_string() })?;
+    serde_json::from_str(&contents).map_err(|err| Error::StateDirError {
+        msg: format!("Invalid file {}: {err}", path.display()),
+    })
+}
+
+impl Starknet {
+    /// Loads the blocks persisted in the configured state directory by replaying their
+    /// transactions. Does nothing if no state directory is configured or nothing was persisted
+    /// there yet.
+    pub fn load_state_dir(&mut self) -> Result<()> {
+        let state_dir = match self.config.state_dir.clone() {
+            Some(state_dir) => state_dir,
+            None => return Ok(()),
+        };
+
+        let index_path = Path::new(&state_dir).join(INDEX_FILE_NAME);
+        if !index_path.exists() {
+            return Ok(());
+        }
+
+        let index: StateIndex = read_json(&index_path)?;
+        if index.version != DUMP_VERSION {
+            return Err(Error::IncompatibleDumpVersion {
+                found: index.version,
+                expected: DUMP_VERSION.to_string(),
+            });
+        }
+
+        // the genesis block is created on startup, so it's not persisted
+        let first_block_number = self.config.initial_block_number + 1;
+        let blocks = (first_block_number..first_block_number + index.block_count)
+            .map(|block_number| {
+                read_json::<DumpedBlock>(&block_file_path(&state_dir, block_number))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // replayed blocks are already persisted, so they are not written again
+        self.config.state_dir = None;
+        let loaded = self.load(Dump { version: DUMP_VERSION.to_string(), blocks });
+        self.config.state_dir = Some(state_dir);
+
+        loaded
+    }
+
+    /// Persists the committed block with `block_number` if a state directory is configured.
+    /// Failures are only logged, since the block has already been committed.
+    pub(crate) fn persist_block(&self, block_number: BlockNumber) {
+        if block_number.0 == self.config.initial_block_number {
+            return;
+        }
+        if let Some(state_dir) = self.config.state_dir.as_ref() {
+            if let Err(err) = self.write_block(state_dir, block_number) {
+                error!("Failed to persist block {} to {state_dir}: {err}", block_number.0);
+            }
+        }
+    }
+
+    /// Updates the index of the state directory to the blocks Devnet currently holds, e.g. after
+    /// blocks were aborted. Failures are only logged.
+    pub(crate) fn persist_block_count(&self) {
+        if let Some(state_dir) = self.config.state_dir.as_ref() {
+            if let Err(err) = self.write_index(state_dir) {
+                error!("Failed to update the index of {state_dir}: {err}");
+            }
+        }
+    }
+
+    /// Writes the block file before the index listing it, so that the index never refers to a
+    /// missing block
+    fn write_block(&self, state_dir: &str, block_number: BlockNumber) -> Result<()> {
+        let block = self.blocks.num_to_block.get(&block_number).ok_or(Error::NoBlock)?;
+
+        fs::create_dir_all(state_dir).map_err(|err| Error::StateDirError {
+            msg: format!("Cannot create directory {state_dir}: {err}"),
+        })?;
+        write_atomically(&block_file_path(state_dir, block_number.0), &self.dumped_block(block)?)?;
+
+        self.write_index(state_dir)
+    }
+
+    fn write_index(&self, state_dir: &str) -> Result<()> {
+        let index = StateIndex {
+            version: DUMP_VERSION.to_string(),
+            block_count: self.blocks.num_to_block.len().saturating_sub(1) as u64,
+        };
+        write_atomically(&Path::new(state_dir).join(INDEX_FILE_NAME), &index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_api::block::BlockNumber;
+    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::contract_storage_key::ContractStorageKey;
+    use starknet_types::felt::Felt;
+
+    use super::{block_file_path, INDEX_FILE_NAME};
+    use crate::constants::ERC20_CONTRACT_ADDRESS;
+    use crate::starknet::{FeeUnit, Starknet, StarknetConfig};
+    use crate::traits::StateExtractor;
+    use crate::utils::get_storage_var_address;
+    use crate::utils::test_utils::{dummy_contract_address, starknet_config_for_test};
+
+    fn config_with_state_dir(name: &str) -> StarknetConfig {
+        let state_dir = std::env::temp_dir().join(name);
+        // leftovers of previous runs would be loaded
+        let _ = std::fs::remove_dir_all(&state_dir);
+
+        StarknetConfig {
+            state_dir: Some(state_dir.to_str().unwrap().to_string()),
+            ..starknet_config_for_test()
+        }
+    }
+
+    fn balance_storage_key(address: ContractAddress) -> ContractStorageKey {
+        ContractStorageKey::new(
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap(),
+            get_storage_var_address("ERC20_balances", &[Felt::from(address)]).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn persisted_blocks_survive_restart() {
+        let config = config_with_state_dir("devnet_persisted_blocks_survive_restart");
+        let mut starknet = Starknet::new(&config).unwrap();
+        let address = dummy_contract_address();
+        starknet.mint(address, 42u32.into(), FeeUnit::Wei).await.unwrap();
+        starknet.create_block().unwrap();
+        starknet.mint(address, 100u32.into(), FeeUnit::Wei).await.unwrap();
+        let latest_block = starknet.get_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+
+        // a new instance on the same directory stands for Devnet started again after a crash
+        let mut restarted_starknet = Starknet::new(&config).unwrap();
+        restarted_starknet.load_state_dir().unwrap();
+
+        let restarted_latest_block =
+            restarted_starknet.get_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(restarted_latest_block.block_number(), latest_block.block_number());
+        assert_eq!(restarted_latest_block.block_number(), BlockNumber(3));
+        assert_eq!(
+            restarted_starknet.state.get_storage(balance_storage_key(address)).unwrap(),
+            Felt::from(142)
+        );
+
+        // blocks committed after loading are persisted as well
+        restarted_starknet.create_block().unwrap();
+        let mut again_restarted_starknet = Starknet::new(&config).unwrap();
+        again_restarted_starknet.load_state_dir().unwrap();
+        assert_eq!(again_restarted_starknet.block_number(), restarted_starknet.block_number());
+    }
+
+    #[tokio::test]
+    async fn unindexed_block_files_are_ignored() {
+        let config = config_with_state_dir("devnet_unindexed_block_files_are_ignored");
+        let state_dir = config.state_dir.clone().unwrap();
+        let mut starknet = Starknet::new(&config).unwrap();
+        starknet.mint(dummy_contract_address(), 42u32.into(), FeeUnit::Wei).await.unwrap();
+
+        // as if Devnet crashed while writing the next block
+        std::fs::write(block_file_path(&state_dir, 2).with_extension("json.tmp"), "{").unwrap();
+        std::fs::write(block_file_path(&state_dir, 2), "{").unwrap();
+
+        let mut restarted_starknet = Starknet::new(&config).unwrap();
+        restarted_starknet.load_state_dir().unwrap();
+        let latest_block = restarted_starknet.get_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(latest_block.block_number(), BlockNumber(1));
+    }
+
+    #[test]
+    fn nothing_loaded_from_empty_state_dir() {
+        let config = config_with_state_dir("devnet_nothing_loaded_from_empty_state_dir");
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        starknet.load_state_dir().unwrap();
+
+        // only the genesis block, created on startup, exists
+        assert_eq!(starknet.get_latest_block().unwrap().block_number(), BlockNumber(0));
+        assert!(!std::path::Path::new(&config.state_dir.unwrap()).join(INDEX_FILE_NAME).exists());
+    }
+}
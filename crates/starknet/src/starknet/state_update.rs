@@ -1,36 +1,62 @@
-use starknet_rs_core::types::BlockId;
+use starknet_api::block::BlockNumber;
+use starknet_rs_core::types::{BlockId, BlockTag};
+use starknet_types::felt::Felt;
 
 use super::Starknet;
 use crate::error::Result;
 use crate::state::state_update::StateUpdate;
+use crate::traits::StateExtractor;
 
+/// Changes introduced by the block with `block_id` only. The changes of the pending block are
+/// not committed yet, so its state update has no block hash and no roots; they are left zero.
 pub fn state_update_by_block_id(starknet: &Starknet, block_id: BlockId) -> Result<StateUpdate> {
+    if let BlockId::Tag(BlockTag::Pending) = block_id {
+        let state_diff = starknet.state.extract_state_diff_from_pending_state()?;
+        return StateUpdate::new(Felt::default(), state_diff);
+    }
+
     let block = starknet.blocks.get_by_block_id(block_id).ok_or(crate::error::Error::NoBlock)?;
     let state_diff =
         starknet.blocks.num_to_state_diff.get(&block.block_number()).cloned().unwrap_or_default();
+    let old_root = block
+        .block_number()
+        .0
+        .checked_sub(1)
+        .and_then(|parent_number| starknet.blocks.num_to_block.get(&BlockNumber(parent_number)))
+        .map(|parent| parent.new_root())
+        .unwrap_or_default();
 
-    StateUpdate::new(block.block_hash(), state_diff)
+    Ok(StateUpdate {
+        new_root: block.new_root(),
+        old_root,
+        ..StateUpdate::new(block.block_hash(), state_diff)?
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use starknet_in_rust::core::contract_address::compute_casm_class_hash;
-    use starknet_in_rust::definitions::block_context::StarknetChainId;
     use starknet_in_rust::CasmContractClass;
-    use starknet_rs_core::types::TransactionStatus;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, TransactionStatus};
+    use starknet_rs_core::utils::{get_contract_address, get_selector_from_name};
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::felt::Felt;
     use starknet_types::traits::HashProducer;
 
     use crate::account::Account;
-    use crate::constants;
-    use crate::starknet::{predeployed, Starknet};
+    use crate::constants::{self, DEVNET_DEFAULT_CHAIN_ID, UDC_CONTRACT_ADDRESS};
+    use crate::starknet::{predeployed, BlockGenerationOn, FeeUnit, Starknet, StarknetConfig};
     use crate::state::state_diff::StateDiff;
     use crate::state::state_update::StateUpdate;
-    use crate::traits::{Accounted, Deployed, HashIdentifiedMut};
+    use crate::traits::{Accounted, Deployed, HashIdentifiedMut, StateExtractor};
+    use crate::transactions::declare_transaction::DeclareTransactionV1;
     use crate::transactions::declare_transaction_v2::DeclareTransactionV2;
-    use crate::utils::load_cairo_0_contract_class;
-    use crate::utils::test_utils::{dummy_cairo_1_contract_class, dummy_felt};
+    use crate::transactions::invoke_transaction::InvokeTransactionV1;
+    use crate::utils::test_utils::{
+        cairo_0_account_without_validations, dummy_cairo_0_contract_class,
+        dummy_cairo_1_contract_class, dummy_felt, starknet_config_for_test,
+    };
+    use crate::utils::{get_storage_var_address, load_cairo_0_contract_class};
 
     #[test]
     /// This test checks that the state update is correct after a declare transaction v2.
@@ -50,7 +76,7 @@ mod tests {
             100,
             Vec::new(),
             Felt::from(0),
-            StarknetChainId::TestNet.to_felt().into(),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
         )
         .unwrap();
 
@@ -109,6 +135,146 @@ mod tests {
         assert!(state_update.cairo_0_declared_classes.is_empty());
     }
 
+    /// Invoke transaction of an account without validations, calling `selector` of
+    /// `contract_address` with `calldata`
+    fn invoke_transaction(
+        sender: ContractAddress,
+        contract_address: Felt,
+        selector: &str,
+        calldata: Vec<Felt>,
+        nonce: u128,
+    ) -> InvokeTransactionV1 {
+        let call = vec![
+            contract_address,
+            get_selector_from_name(selector).unwrap().into(),
+            Felt::from(calldata.len() as u128),
+        ];
+        InvokeTransactionV1::new(
+            sender,
+            10000,
+            vec![],
+            Felt::from(nonce),
+            [call, calldata].concat(),
+            DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn state_update_holds_only_changes_of_its_block() {
+        let config = StarknetConfig {
+            block_generation_on: BlockGenerationOn::Demand,
+            ..starknet_config_for_test()
+        };
+        let mut starknet = Starknet::new(&config).unwrap();
+        let account_class = cairo_0_account_without_validations();
+        let account = Account::new(
+            Felt::from(1_000_000),
+            dummy_felt(),
+            dummy_felt(),
+            account_class.generate_hash().unwrap(),
+            account_class,
+            config.eth_fee_token_address,
+        )
+        .unwrap();
+        account.deploy(&mut starknet.state).unwrap();
+        account.set_initial_balance(&mut starknet.state).unwrap();
+        starknet.state.synchronize_states();
+        let sender = account.get_address();
+
+        // declare, deploy through the UDC and write storage of the deployed contract
+        let contract_class = dummy_cairo_0_contract_class();
+        let class_hash = contract_class.generate_hash().unwrap();
+        starknet
+            .add_declare_transaction_v1(
+                DeclareTransactionV1::new(
+                    sender,
+                    10000,
+                    vec![],
+                    Felt::from(0),
+                    contract_class,
+                    DEVNET_DEFAULT_CHAIN_ID.to_felt(),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let salt = Felt::from(42);
+        let udc_calldata = vec![class_hash, salt, Felt::from(0), Felt::from(0)];
+        let udc_address = Felt::from_prefixed_hex_str(UDC_CONTRACT_ADDRESS).unwrap();
+        starknet
+            .add_invoke_transaction_v1(invoke_transaction(
+                sender,
+                udc_address,
+                "deployContract",
+                udc_calldata,
+                1,
+            ))
+            .unwrap();
+        let contract_address = Felt::from(get_contract_address(
+            salt.into(),
+            class_hash.into(),
+            &[],
+            FieldElement::ZERO,
+        ));
+        starknet
+            .add_invoke_transaction_v1(invoke_transaction(
+                sender,
+                contract_address,
+                "increase_balance",
+                vec![Felt::from(10)],
+                2,
+            ))
+            .unwrap();
+
+        let contract_address = ContractAddress::new(contract_address).unwrap();
+        let balance_key = get_storage_var_address("balance", &[]).unwrap();
+        let assert_lists_changes = |state_update: StateUpdate| {
+            assert_eq!(state_update.cairo_0_declared_classes, vec![class_hash]);
+            assert!(state_update.deployed_contracts.contains(&(contract_address, class_hash)));
+            assert!(state_update.replaced_classes.is_empty());
+            let (_, storage_entries) = state_update
+                .storage_updates
+                .into_iter()
+                .find(|(address, _)| *address == contract_address)
+                .unwrap();
+            assert_eq!(storage_entries, vec![(balance_key, Felt::from(10))]);
+            assert!(state_update.nonces.contains(&(sender, Felt::from(3))));
+        };
+
+        assert_lists_changes(starknet.block_state_update(BlockId::Tag(BlockTag::Pending)).unwrap());
+
+        let block_hash = starknet.create_block().unwrap();
+        let state_update = starknet.block_state_update(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert_eq!(state_update.block_hash, block_hash);
+        assert_lists_changes(state_update);
+
+        // the changes of the previous block are not repeated
+        starknet.create_block().unwrap();
+        let state_update = starknet.block_state_update(BlockId::Tag(BlockTag::Latest)).unwrap();
+        assert!(state_update.cairo_0_declared_classes.is_empty());
+        assert!(state_update.deployed_contracts.is_empty());
+        assert!(state_update.storage_updates.is_empty());
+        assert!(state_update.nonces.is_empty());
+    }
+
+    #[test]
+    fn replaced_class_is_not_listed_as_deployed_contract() {
+        let (mut starknet, sender_address) = setup();
+        let new_class_hash = dummy_felt();
+        starknet
+            .state
+            .pending_state
+            .cache_mut()
+            .class_hash_writes_mut()
+            .insert(sender_address.try_into().unwrap(), new_class_hash.bytes());
+
+        let state_diff = starknet.state.extract_state_diff_from_pending_state().unwrap();
+        let state_update = StateUpdate::new(Felt::default(), state_diff).unwrap();
+
+        assert!(state_update.deployed_contracts.is_empty());
+        assert_eq!(state_update.replaced_classes, vec![(sender_address, new_class_hash)]);
+    }
+
     /// Initializes starknet with account_without_validations
     /// deploys ERC20 contract
     fn setup() -> (Starknet, ContractAddress) {
@@ -119,7 +285,8 @@ mod tests {
         );
         let contract_class = load_cairo_0_contract_class(account_json_path).unwrap();
 
-        let erc_20_contract = predeployed::create_erc20().unwrap();
+        let erc_20_contract =
+            predeployed::create_erc20(FeeUnit::Wei.default_erc20_address()).unwrap();
         erc_20_contract.deploy(&mut starknet.state).unwrap();
 
         let acc = Account::new(
@@ -139,7 +306,8 @@ mod tests {
         starknet.block_context = Starknet::get_block_context(
             1,
             constants::ERC20_CONTRACT_ADDRESS,
-            StarknetChainId::TestNet,
+            DEVNET_DEFAULT_CHAIN_ID,
+            constants::DEVNET_DEFAULT_MAX_STEPS,
         )
         .unwrap();
 
@@ -0,0 +1,435 @@
+use starknet_in_rust::utils::Address;
+use starknet_rs_core::crypto::pedersen_hash;
+use starknet_rs_core::types::{BlockId, BlockTag};
+use starknet_rs_ff::FieldElement;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::Felt;
+use starknet_types::patricia_key::PatriciaKey;
+
+use super::Starknet;
+use crate::error::{Error, Result};
+use crate::state::StarknetState;
+
+/// Height of both the contracts trie and the contract storage tries
+const TRIE_HEIGHT: usize = 251;
+
+/// A node on the path from the root of a Merkle-Patricia trie towards a leaf.
+/// Values of `left`, `right` and `child` are hashes of the referenced nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofNode {
+    Binary { left: Felt, right: Felt },
+    Edge { child: Felt, path: Felt, length: u8 },
+}
+
+impl ProofNode {
+    /// Hash of the node, as used by its parent
+    pub fn hash(&self) -> Felt {
+        match self {
+            ProofNode::Binary { left, right } => {
+                pedersen_hash(&(*left).into(), &(*right).into()).into()
+            }
+            ProofNode::Edge { child, path, length } => {
+                (pedersen_hash(&(*child).into(), &(*path).into()) + FieldElement::from(*length))
+                    .into()
+            }
+        }
+    }
+}
+
+/// Proof of a contract's presence in the contracts trie and of the values of the requested keys
+/// in its storage trie. For absent contracts or keys the path ends before reaching a leaf, which
+/// proves non-membership.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageProof {
+    pub contracts_tree_root: Felt,
+    pub contract_proof: Vec<ProofNode>,
+    pub class_hash: Felt,
+    pub nonce: Felt,
+    pub contract_storage_root: Felt,
+    pub storage_proofs: Vec<Vec<ProofNode>>,
+}
+
+enum TrieNode {
+    Leaf(FieldElement),
+    Binary { left: Box<TrieNode>, right: Box<TrieNode>, hash: FieldElement },
+    Edge { path: Vec<bool>, child: Box<TrieNode>, hash: FieldElement },
+}
+
+impl TrieNode {
+    fn hash(&self) -> FieldElement {
+        match self {
+            TrieNode::Leaf(value) => *value,
+            TrieNode::Binary { hash, .. } | TrieNode::Edge { hash, .. } => *hash,
+        }
+    }
+
+    fn binary(left: TrieNode, right: TrieNode) -> Self {
+        let hash = pedersen_hash(&left.hash(), &right.hash());
+        TrieNode::Binary { left: Box::new(left), right: Box::new(right), hash }
+    }
+
+    fn edge(path: Vec<bool>, child: TrieNode) -> Self {
+        let hash = pedersen_hash(&child.hash(), &path_to_field_element(&path))
+            + FieldElement::from(path.len());
+        TrieNode::Edge { path, child: Box::new(child), hash }
+    }
+
+    fn to_proof_node(&self) -> Option<ProofNode> {
+        match self {
+            TrieNode::Leaf(_) => None,
+            TrieNode::Binary { left, right, .. } => {
+                Some(ProofNode::Binary { left: left.hash().into(), right: right.hash().into() })
+            }
+            TrieNode::Edge { path, child, .. } => Some(ProofNode::Edge {
+                child: child.hash().into(),
+                path: path_to_field_element(path).into(),
+                length: path.len() as u8,
+            }),
+        }
+    }
+}
+
+/// Binary Merkle-Patricia trie, of height 251 unless built with another height, built from the
+/// non-zero leaves only
+pub(super) struct PatriciaTrie {
+    height: usize,
+    root: Option<TrieNode>,
+}
+
+impl PatriciaTrie {
+    fn new(leaves: Vec<(FieldElement, FieldElement)>) -> Self {
+        Self::with_height(TRIE_HEIGHT, leaves)
+    }
+
+    pub(super) fn with_height(height: usize, leaves: Vec<(FieldElement, FieldElement)>) -> Self {
+        let mut leaves: Vec<(Vec<bool>, FieldElement)> = leaves
+            .into_iter()
+            .filter(|(_, value)| *value != FieldElement::ZERO)
+            .map(|(key, value)| (key_to_bits(key, height), value))
+            .collect();
+        leaves.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+        if leaves.is_empty() {
+            Self { height, root: None }
+        } else {
+            Self { height, root: Some(Self::build(&leaves, 0, height)) }
+        }
+    }
+
+    /// `leaves` are sorted and non-empty, and all of them share the same first `depth` bits
+    fn build(leaves: &[(Vec<bool>, FieldElement)], depth: usize, height: usize) -> TrieNode {
+        if depth == height {
+            return TrieNode::Leaf(leaves[0].1);
+        }
+
+        // leaves are sorted, so the common prefix of all of them is the common prefix of the first
+        // and the last one
+        let first = &leaves[0].0;
+        let last = &leaves[leaves.len() - 1].0;
+        let mut common_length = 0;
+        while depth + common_length < height
+            && first[depth + common_length] == last[depth + common_length]
+        {
+            common_length += 1;
+        }
+
+        if common_length > 0 {
+            let child = Self::build(leaves, depth + common_length, height);
+            return TrieNode::edge(first[depth..depth + common_length].to_vec(), child);
+        }
+
+        let split_idx = leaves.partition_point(|(bits, _)| !bits[depth]);
+        TrieNode::binary(
+            Self::build(&leaves[..split_idx], depth + 1, height),
+            Self::build(&leaves[split_idx..], depth + 1, height),
+        )
+    }
+
+    pub(super) fn root_hash(&self) -> FieldElement {
+        self.root.as_ref().map(TrieNode::hash).unwrap_or(FieldElement::ZERO)
+    }
+
+    /// Collects the nodes from the root towards the leaf of `key`. The path stops early if the key
+    /// is not part of the trie.
+    fn proof(&self, key: FieldElement) -> Vec<ProofNode> {
+        let bits = key_to_bits(key, self.height);
+        let mut nodes = vec![];
+        let mut depth = 0;
+        let mut current = self.root.as_ref();
+
+        while let Some(node) = current {
+            if let Some(proof_node) = node.to_proof_node() {
+                nodes.push(proof_node);
+            }
+
+            current = match node {
+                TrieNode::Leaf(_) => None,
+                TrieNode::Binary { left, right, .. } => {
+                    let next = if bits[depth] { right } else { left };
+                    depth += 1;
+                    Some(next.as_ref())
+                }
+                TrieNode::Edge { path, child, .. } => {
+                    if bits[depth..depth + path.len()] == path[..] {
+                        depth += path.len();
+                        Some(child.as_ref())
+                    } else {
+                        None
+                    }
+                }
+            };
+        }
+
+        nodes
+    }
+}
+
+/// Returns the lowest `height` bits of the key, starting from the most significant one
+fn key_to_bits(key: FieldElement, height: usize) -> Vec<bool> {
+    key.to_bits_le()[..height].iter().rev().copied().collect()
+}
+
+fn path_to_field_element(path: &[bool]) -> FieldElement {
+    path.iter().fold(FieldElement::ZERO, |acc, bit| {
+        acc.double() + if *bit { FieldElement::ONE } else { FieldElement::ZERO }
+    })
+}
+
+fn contract_storage_trie(state: &StarknetState, address: &Address) -> PatriciaTrie {
+    let leaves = state
+        .state
+        .address_to_storage
+        .iter()
+        .filter(|((storage_address, _), _)| storage_address == address)
+        .map(|((_, key), value)| {
+            (FieldElement::from_bytes_be(key).unwrap_or_default(), Felt::from(value.clone()).into())
+        })
+        .collect();
+
+    PatriciaTrie::new(leaves)
+}
+
+/// Hash of the contract's leaf in the contracts trie: h(h(h(class_hash, storage_root), nonce), 0)
+fn contract_state_hash(
+    class_hash: FieldElement,
+    storage_root: FieldElement,
+    nonce: FieldElement,
+) -> FieldElement {
+    pedersen_hash(
+        &pedersen_hash(&pedersen_hash(&class_hash, &storage_root), &nonce),
+        &FieldElement::ZERO,
+    )
+}
+
+fn contracts_trie(state: &StarknetState) -> PatriciaTrie {
+    let contract_leaves = state
+        .state
+        .address_to_class_hash
+        .iter()
+        .map(|(address, class_hash)| {
+            let storage_root = contract_storage_trie(state, address).root_hash();
+            let nonce = state.state.address_to_nonce.get(address).cloned().unwrap_or_default();
+            let leaf = contract_state_hash(
+                Felt::from(*class_hash).into(),
+                storage_root,
+                Felt::from(nonce).into(),
+            );
+
+            (Felt::from(address.0.clone()).into(), leaf)
+        })
+        .collect();
+
+    PatriciaTrie::new(contract_leaves)
+}
+
+/// Root of the contracts trie of `state`, reported as the global state root of blocks
+pub(crate) fn global_state_root(state: &StarknetState) -> Felt {
+    contracts_trie(state).root_hash().into()
+}
+
+pub fn storage_proof_by_block_id(
+    starknet: &Starknet,
+    block_id: BlockId,
+    contract_address: ContractAddress,
+    keys: Vec<PatriciaKey>,
+) -> Result<StorageProof> {
+    if starknet.config.lite_mode {
+        return Err(Error::StorageProofInLiteMode);
+    }
+    if let BlockId::Tag(BlockTag::Pending) = block_id {
+        return Err(Error::StorageProofForPendingBlock);
+    }
+
+    let state = starknet.get_state_at(&block_id)?;
+    let contracts_trie = contracts_trie(state);
+
+    let address: Address = contract_address.try_into()?;
+    let storage_trie = contract_storage_trie(state, &address);
+
+    let class_hash = state
+        .state
+        .address_to_class_hash
+        .get(&address)
+        .map(|class_hash| Felt::from(*class_hash))
+        .unwrap_or_default();
+    let nonce =
+        state.state.address_to_nonce.get(&address).cloned().map(Felt::from).unwrap_or_default();
+
+    Ok(StorageProof {
+        contracts_tree_root: contracts_trie.root_hash().into(),
+        contract_proof: contracts_trie.proof(Felt::from(contract_address).into()),
+        class_hash,
+        nonce,
+        contract_storage_root: storage_trie.root_hash().into(),
+        storage_proofs: keys.iter().map(|key| storage_trie.proof(key.to_felt().into())).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_rs_ff::FieldElement;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+    use starknet_types::patricia_key::PatriciaKey;
+
+    use super::{PatriciaTrie, ProofNode};
+    use crate::constants::ERC20_CONTRACT_ADDRESS;
+    use crate::error::Error;
+    use crate::starknet::{Starknet, StarknetConfig};
+    use crate::utils::get_storage_var_address;
+    use crate::utils::test_utils::starknet_config_for_test;
+
+    /// Recomputes the root from the proof, returning the value at the end of the path if the key
+    /// is present or `None` if the proof shows that it is absent
+    fn verify(root: Felt, key: Felt, proof: &[ProofNode]) -> Option<Felt> {
+        let bits = super::key_to_bits(key.into(), super::TRIE_HEIGHT);
+        let mut depth = 0;
+        let mut expected_hash = root;
+        for node in proof {
+            assert_eq!(node.hash(), expected_hash);
+            match node {
+                ProofNode::Binary { left, right } => {
+                    expected_hash = if bits[depth] { *right } else { *left };
+                    depth += 1;
+                }
+                ProofNode::Edge { child, path, length } => {
+                    let length = *length as usize;
+                    if super::path_to_field_element(&bits[depth..depth + length])
+                        != FieldElement::from(*path)
+                    {
+                        return None;
+                    }
+                    expected_hash = *child;
+                    depth += length;
+                }
+            }
+        }
+
+        if depth == 251 { Some(expected_hash) } else { None }
+    }
+
+    #[test]
+    fn empty_trie_has_zero_root_and_no_proof() {
+        let trie = PatriciaTrie::new(vec![]);
+        assert_eq!(trie.root_hash(), FieldElement::ZERO);
+        assert!(trie.proof(FieldElement::ONE).is_empty());
+    }
+
+    #[test]
+    fn proof_of_existing_storage_key() {
+        let starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let erc20_address = Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap();
+        let key = get_storage_var_address("ERC20_name", &[]).unwrap();
+
+        let proof = starknet
+            .get_storage_proof(
+                BlockId::Tag(BlockTag::Latest),
+                ContractAddress::new(erc20_address).unwrap(),
+                vec![key],
+            )
+            .unwrap();
+
+        let expected_value = starknet
+            .contract_storage_at_block(
+                BlockId::Tag(BlockTag::Latest),
+                ContractAddress::new(erc20_address).unwrap(),
+                key,
+            )
+            .unwrap();
+        assert_eq!(
+            verify(proof.contract_storage_root, key.to_felt(), &proof.storage_proofs[0]),
+            Some(expected_value)
+        );
+        assert!(verify(proof.contracts_tree_root, erc20_address, &proof.contract_proof).is_some());
+    }
+
+    #[test]
+    fn proof_of_absent_storage_key() {
+        let starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        let erc20_address = Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap();
+        let key = PatriciaKey::new(Felt::from(123456789)).unwrap();
+
+        let proof = starknet
+            .get_storage_proof(
+                BlockId::Tag(BlockTag::Latest),
+                ContractAddress::new(erc20_address).unwrap(),
+                vec![key],
+            )
+            .unwrap();
+
+        assert!(!proof.storage_proofs[0].is_empty());
+        assert_eq!(
+            verify(proof.contract_storage_root, key.to_felt(), &proof.storage_proofs[0]),
+            None
+        );
+    }
+
+    #[test]
+    fn proof_for_pending_block_is_rejected() {
+        let starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+
+        match starknet.get_storage_proof(
+            BlockId::Tag(BlockTag::Pending),
+            ContractAddress::zero(),
+            vec![],
+        ) {
+            Err(Error::StorageProofForPendingBlock) => {}
+            _ => panic!("Expected error for pending block"),
+        }
+    }
+
+    #[test]
+    fn proof_in_lite_mode_is_rejected() {
+        let config = StarknetConfig { lite_mode: true, ..starknet_config_for_test() };
+        let starknet = Starknet::new(&config).unwrap();
+
+        match starknet.get_storage_proof(
+            BlockId::Tag(BlockTag::Latest),
+            ContractAddress::zero(),
+            vec![],
+        ) {
+            Err(Error::StorageProofInLiteMode) => {}
+            _ => panic!("Expected error in lite mode"),
+        }
+    }
+
+    #[test]
+    fn state_root_of_block_matches_contracts_tree_root() {
+        let mut starknet = Starknet::new(&starknet_config_for_test()).unwrap();
+        starknet.create_block().unwrap();
+        let erc20_address = Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap();
+
+        let proof = starknet
+            .get_storage_proof(
+                BlockId::Tag(BlockTag::Latest),
+                ContractAddress::new(erc20_address).unwrap(),
+                vec![],
+            )
+            .unwrap();
+        let block = starknet.get_block(BlockId::Tag(BlockTag::Latest)).unwrap();
+
+        assert_ne!(block.new_root(), Felt::from(0));
+        assert_eq!(block.new_root(), proof.contracts_tree_root);
+    }
+}
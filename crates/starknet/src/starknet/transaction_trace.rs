@@ -0,0 +1,263 @@
+use starknet_api::block::BlockNumber;
+use starknet_in_rust::definitions::constants::INITIAL_GAS_COST;
+use starknet_rs_core::types::{BlockId, TransactionStatus};
+use starknet_types::felt::TransactionHash;
+
+use super::Starknet;
+use crate::blocks::StarknetBlock;
+use crate::error::{Error, Result};
+use crate::traces::TransactionTrace;
+use crate::traits::HashIdentified;
+use crate::transactions::Transaction;
+
+/// Replays the transaction with the given hash on the state at the start of its block, after
+/// replaying all transactions that precede it in the block
+pub fn trace_transaction(
+    starknet: &Starknet,
+    transaction_hash: &TransactionHash,
+) -> Result<TransactionTrace> {
+    let transaction =
+        starknet.transactions.get_by_hash(*transaction_hash).ok_or(Error::TransactionNotFound)?;
+    if transaction.status == TransactionStatus::Pending {
+        return Err(Error::TraceForPendingTransaction);
+    }
+
+    let block = transaction
+        .block_number
+        .and_then(|block_number| starknet.blocks.num_to_block.get(&block_number))
+        .ok_or(Error::TransactionNotFound)?;
+
+    trace_block_until(starknet, block, Some(transaction_hash))?
+        .pop()
+        .map(|(_, trace)| trace)
+        .ok_or(Error::TransactionNotFound)
+}
+
+/// Replays all transactions of the block with the given id in order, each one on the state left by
+/// the previous one, and returns their traces together with their hashes
+pub fn trace_block_transactions(
+    starknet: &Starknet,
+    block_id: BlockId,
+) -> Result<Vec<(TransactionHash, TransactionTrace)>> {
+    let block = starknet.blocks.get_by_block_id(block_id).ok_or(Error::NoBlock)?;
+
+    trace_block_until(starknet, block, None)
+}
+
+/// Replays the transactions of `block` in order, on top of the state at the start of the block.
+/// Stops after the transaction with hash `last`, if given. Returns the traces of the replayed
+/// transactions.
+fn trace_block_until(
+    starknet: &Starknet,
+    block: &StarknetBlock,
+    last: Option<&TransactionHash>,
+) -> Result<Vec<(TransactionHash, TransactionTrace)>> {
+    let block_number = block.block_number();
+    // the first block is executed on top of the genesis state
+    let parent_number = block_number
+        .0
+        .checked_sub(1)
+        .filter(|parent_number| *parent_number >= starknet.config.initial_block_number);
+    let mut state = match parent_number {
+        Some(parent_number) => starknet
+            .blocks
+            .num_to_state
+            .get(&BlockNumber(parent_number))
+            .ok_or(Error::NoStateAtBlock { block_number: parent_number })?,
+        None => &starknet.genesis_state,
+    }
+    .pending_state
+    .clone();
+
+    let mut block_context = starknet.block_context.clone();
+    block_context.block_info_mut().block_number = block_number.0;
+    block_context.block_info_mut().block_timestamp = block.timestamp().0;
+
+    let mut traces = vec![];
+    for transaction in block.get_transactions() {
+        let transaction_hash = transaction.get_hash().ok_or(Error::TransactionNotFound)?;
+
+        // impersonation and fee charge settings are those currently in effect
+        let (skip_validate, skip_fee_charge) = match transaction {
+            Transaction::Invoke(invoke_transaction) => (
+                starknet.is_account_impersonated(&invoke_transaction.sender_address()?),
+                starknet.config.disable_fee_charge,
+            ),
+            _ => (false, starknet.config.disable_fee_charge),
+        };
+        let execution_info = transaction
+            .create_sir_transaction()?
+            .create_for_simulation(skip_validate, false, skip_fee_charge, skip_fee_charge)
+            .execute(&mut state, &block_context, INITIAL_GAS_COST)?;
+
+        traces.push((transaction_hash, TransactionTrace::new(transaction, &execution_info)?));
+        if Some(&transaction_hash) == last {
+            break;
+        }
+    }
+
+    Ok(traces)
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_api::block::BlockNumber;
+    use starknet_in_rust::definitions::block_context::StarknetChainId;
+    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::HashProducer;
+
+    use crate::account::Account;
+    use crate::constants::ERC20_CONTRACT_ADDRESS;
+    use crate::error::Error;
+    use crate::starknet::{BlockGenerationOn, Starknet};
+    use crate::traits::{Accounted, Deployed};
+    use crate::transactions::declare_transaction::DeclareTransactionV1;
+    use crate::transactions::invoke_transaction::InvokeTransactionV1;
+    use crate::utils::test_utils::{
+        cairo_0_account_without_validations, dummy_cairo_0_contract_class, dummy_felt,
+        starknet_config_for_test,
+    };
+
+    fn setup(block_generation_on: BlockGenerationOn) -> (Starknet, ContractAddress) {
+        let mut config = starknet_config_for_test();
+        config.block_generation_on = block_generation_on;
+        let mut starknet = Starknet::new(&config).unwrap();
+        let contract_class = cairo_0_account_without_validations();
+
+        let account = Account::new(
+            Felt::from(1_000_000),
+            dummy_felt(),
+            dummy_felt(),
+            contract_class.generate_hash().unwrap(),
+            contract_class,
+            ContractAddress::new(Felt::from_prefixed_hex_str(ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        account.deploy(&mut starknet.state).unwrap();
+        account.set_initial_balance(&mut starknet.state).unwrap();
+        starknet.state.synchronize_states();
+        starknet.genesis_state = starknet.state.clone();
+        starknet.blocks.save_state_at(BlockNumber(0), starknet.state.clone());
+
+        (starknet, account.get_address())
+    }
+
+    /// Declares the dummy class and invokes `deploy_contract` of `sender`, which emits an event.
+    /// Returns the hashes of both transactions.
+    fn declare_and_deploy(starknet: &mut Starknet, sender: ContractAddress) -> (Felt, Felt) {
+        let contract_class = dummy_cairo_0_contract_class();
+        let class_hash = contract_class.generate_hash().unwrap();
+        let (declare_transaction_hash, _) = starknet
+            .add_declare_transaction_v1(
+                DeclareTransactionV1::new(
+                    sender,
+                    10000,
+                    vec![],
+                    Felt::from(0),
+                    contract_class,
+                    StarknetChainId::TestNet.to_felt().into(),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let calldata = vec![
+            sender.into(),                                             // contract address
+            get_selector_from_name("deploy_contract").unwrap().into(), // function selector
+            Felt::from(3),                                             // calldata len
+            class_hash,                                                // class hash
+            Felt::from(42),                                            // salt
+            Felt::from(0),                                             // constructor calldata len
+        ];
+        let invoke_transaction_hash = starknet
+            .add_invoke_transaction_v1(
+                InvokeTransactionV1::new(
+                    sender,
+                    10000,
+                    vec![],
+                    Felt::from(1),
+                    calldata,
+                    StarknetChainId::TestNet.to_felt().into(),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        (declare_transaction_hash, invoke_transaction_hash)
+    }
+
+    #[test]
+    fn trace_of_invoke_contains_emitted_event() {
+        let (mut starknet, sender) = setup(BlockGenerationOn::Transaction);
+        let (_, transaction_hash) = declare_and_deploy(&mut starknet, sender);
+
+        let trace = starknet.trace_transaction(&transaction_hash).unwrap();
+        let execute_invocation = trace.execute_invocation.unwrap();
+        assert_eq!(execute_invocation.calls[0].events.len(), 1);
+        assert!(trace.revert_reason.is_none());
+    }
+
+    #[test]
+    fn trace_of_declare_contains_validate_invocation() {
+        let (mut starknet, sender) = setup(BlockGenerationOn::Transaction);
+        let (transaction_hash, _) = declare_and_deploy(&mut starknet, sender);
+
+        let trace = starknet.trace_transaction(&transaction_hash).unwrap();
+        assert_eq!(
+            trace.validate_invocation.unwrap().entry_point_selector,
+            get_selector_from_name("__validate_declare__").unwrap().into()
+        );
+        assert!(trace.execute_invocation.is_none());
+    }
+
+    #[test]
+    fn trace_of_transaction_in_pending_block_fails() {
+        let (mut starknet, sender) = setup(BlockGenerationOn::Demand);
+        let (_, transaction_hash) = declare_and_deploy(&mut starknet, sender);
+
+        match starknet.trace_transaction(&transaction_hash) {
+            Err(Error::TraceForPendingTransaction) => {}
+            other => panic!("Unexpected result: {other:?}"),
+        }
+
+        // once the block is created, the transaction is replayed after the declaration
+        starknet.create_block().unwrap();
+        assert!(starknet.trace_transaction(&transaction_hash).is_ok());
+    }
+
+    #[test]
+    fn traces_of_block_transactions_are_in_order() {
+        let (mut starknet, sender) = setup(BlockGenerationOn::Demand);
+        let (declare_transaction_hash, invoke_transaction_hash) =
+            declare_and_deploy(&mut starknet, sender);
+        starknet.create_block().unwrap();
+
+        let traces = starknet.trace_block_transactions(BlockId::Tag(BlockTag::Latest)).unwrap();
+        let transaction_hashes: Vec<Felt> = traces.iter().map(|(hash, _)| *hash).collect();
+        assert_eq!(transaction_hashes, vec![declare_transaction_hash, invoke_transaction_hash]);
+        // the deployment only succeeds if the declaration was replayed before it
+        assert!(traces[1].1.revert_reason.is_none());
+    }
+
+    #[test]
+    fn traces_of_empty_block_are_empty() {
+        let (mut starknet, _) = setup(BlockGenerationOn::Transaction);
+        starknet.create_block().unwrap();
+
+        assert!(starknet.trace_block_transactions(BlockId::Number(1)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn trace_of_unknown_transaction_fails() {
+        let (starknet, _) = setup(BlockGenerationOn::Transaction);
+
+        match starknet.trace_transaction(&dummy_felt()) {
+            Err(Error::TransactionNotFound) => {}
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+}
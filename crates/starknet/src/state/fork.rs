@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use starknet_in_rust::core::errors::state_errors::StateError;
+use starknet_in_rust::services::api::contract_classes::compiled_class::CompiledClass;
+use starknet_in_rust::services::api::contract_classes::deprecated_contract_class::ContractClass as StarknetInRustContractClass;
+use starknet_in_rust::state::in_memory_state_reader::InMemoryStateReader;
+use starknet_in_rust::state::state_api::StateReader;
+use starknet_in_rust::state::state_cache::StorageEntry;
+use starknet_in_rust::utils::Address;
+use starknet_in_rust::CasmContractClass;
+use starknet_types::cairo_felt::Felt252;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::ContractClass;
+use starknet_types::felt::{ClassHash, Felt};
+use starknet_types::patricia_key::PatriciaKey;
+
+use crate::error::{Error, Result};
+use crate::starknet::fork::ForkedState;
+
+/// The network Devnet is forked from, with the classes fetched from it compiled for execution.
+/// Clones share the compiled classes.
+#[derive(Clone)]
+pub(crate) struct Fork {
+    forked_state: Arc<dyn ForkedState>,
+    compiled_classes: Arc<RwLock<HashMap<ClassHash, CompiledClass>>>,
+}
+
+impl std::fmt::Debug for Fork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Fork").finish_non_exhaustive()
+    }
+}
+
+impl Fork {
+    pub(crate) fn new(forked_state: Arc<dyn ForkedState>) -> Self {
+        Self { forked_state, compiled_classes: Arc::new(RwLock::new(HashMap::new())) }
+    }
+
+    pub(crate) fn get_class(&self, class_hash: ClassHash) -> Result<Option<ContractClass>> {
+        Ok(self.forked_state.get_class(class_hash)?)
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> Result<Option<CompiledClass>> {
+        let compiled_classes = &self.compiled_classes;
+        if let Some(compiled_class) = compiled_classes
+            .read()
+            .expect("Compiled classes should not be poisoned")
+            .get(&class_hash)
+        {
+            return Ok(Some(compiled_class.clone()));
+        }
+
+        let compiled_class = match self.get_class(class_hash)? {
+            Some(contract_class @ ContractClass::Cairo0(_)) => CompiledClass::Deprecated(Arc::new(
+                StarknetInRustContractClass::try_from(contract_class)?,
+            )),
+            Some(contract_class @ ContractClass::Cairo1(_)) => {
+                CompiledClass::Casm(Arc::new(CasmContractClass::try_from(contract_class)?))
+            }
+            None => return Ok(None),
+        };
+        compiled_classes
+            .write()
+            .expect("Compiled classes should not be poisoned")
+            .insert(class_hash, compiled_class.clone());
+
+        Ok(Some(compiled_class))
+    }
+}
+
+/// Reads the committed local state, falling back to the forked network for contracts that are
+/// not deployed locally and for classes that are not declared locally. Values of contracts
+/// deployed locally are never read from the forked network.
+#[derive(Clone, Copy)]
+pub(crate) struct ForkingView<'a> {
+    local: &'a InMemoryStateReader,
+    fork: Option<&'a Fork>,
+}
+
+impl<'a> ForkingView<'a> {
+    pub(crate) fn new(local: &'a InMemoryStateReader, fork: Option<&'a Fork>) -> Self {
+        Self { local, fork }
+    }
+
+    /// The forked network, if the contract at `address` is read from it
+    fn fork_of(&self, address: &Address) -> Option<&'a Fork> {
+        self.fork.filter(|_| !self.local.address_to_class_hash.contains_key(address))
+    }
+
+    /// None if the contract is not deployed
+    pub(crate) fn get_class_hash_at(&self, address: &Address) -> Result<Option<ClassHash>> {
+        if let Some(class_hash) = self.local.address_to_class_hash.get(address) {
+            return Ok(Some(Felt::new(*class_hash)?));
+        }
+
+        match self.fork_of(address) {
+            Some(fork) => Ok(fork
+                .forked_state
+                .get_class_hash_at(ContractAddress::try_from(address.clone())?)?),
+            None => Ok(None),
+        }
+    }
+
+    /// None if the nonce is not known locally and the contract is not read from the forked
+    /// network
+    pub(crate) fn get_nonce_at(&self, address: &Address) -> Result<Option<Felt>> {
+        if let Some(nonce) = self.local.address_to_nonce.get(address) {
+            return Ok(Some(Felt::from(nonce.clone())));
+        }
+
+        match self.fork_of(address) {
+            Some(fork) => {
+                Ok(fork.forked_state.get_nonce_at(ContractAddress::try_from(address.clone())?)?)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// None if the value is not set locally and the contract is not read from the forked network
+    pub(crate) fn get_storage_at(&self, storage_entry: &StorageEntry) -> Result<Option<Felt>> {
+        if let Some(value) = self.local.address_to_storage.get(storage_entry) {
+            return Ok(Some(Felt::from(value.clone())));
+        }
+
+        let (address, key) = storage_entry;
+        match self.fork_of(address) {
+            Some(fork) => Ok(fork.forked_state.get_storage_at(
+                ContractAddress::try_from(address.clone())?,
+                PatriciaKey::new(Felt::new(*key)?)?,
+            )?),
+            None => Ok(None),
+        }
+    }
+
+    /// None if the class is declared neither locally nor in the forked network
+    pub(crate) fn get_contract_class(
+        &self,
+        class_hash: &[u8; 32],
+    ) -> Result<Option<CompiledClass>> {
+        if let Ok(compiled_class) = self.local.get_contract_class(class_hash) {
+            return Ok(Some(compiled_class));
+        }
+
+        match self.fork {
+            Some(fork) => fork.get_compiled_class(Felt::new(*class_hash)?),
+            None => Ok(None),
+        }
+    }
+}
+
+/// State reader of the pending state: the committed local state, read through a
+/// [`ForkingView`]. Values missing both locally and in the forked network are reported with the
+/// errors of the local state.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ForkingStateReader {
+    pub(crate) local: InMemoryStateReader,
+    pub(crate) fork: Option<Fork>,
+}
+
+impl ForkingStateReader {
+    pub(crate) fn new(local: InMemoryStateReader, fork: Option<Fork>) -> Self {
+        Self { local, fork }
+    }
+
+    pub(crate) fn view(&self) -> ForkingView<'_> {
+        ForkingView::new(&self.local, self.fork.as_ref())
+    }
+}
+
+/// Failures of reading the forked network abort the execution reading the value
+fn to_state_error(err: Error) -> StateError {
+    StateError::CustomError(err.to_string())
+}
+
+impl StateReader for ForkingStateReader {
+    fn get_contract_class(&self, class_hash: &[u8; 32]) -> Result<CompiledClass, StateError> {
+        match self.view().get_contract_class(class_hash).map_err(to_state_error)? {
+            Some(compiled_class) => Ok(compiled_class),
+            None => self.local.get_contract_class(class_hash),
+        }
+    }
+
+    fn get_class_hash_at(&self, contract_address: &Address) -> Result<[u8; 32], StateError> {
+        match self.view().get_class_hash_at(contract_address).map_err(to_state_error)? {
+            Some(class_hash) => Ok(class_hash.bytes()),
+            None => self.local.get_class_hash_at(contract_address),
+        }
+    }
+
+    fn get_nonce_at(&self, contract_address: &Address) -> Result<Felt252, StateError> {
+        match self.view().get_nonce_at(contract_address).map_err(to_state_error)? {
+            Some(nonce) => Ok(nonce.into()),
+            None => self.local.get_nonce_at(contract_address),
+        }
+    }
+
+    fn get_storage_at(&self, storage_entry: &StorageEntry) -> Result<Felt252, StateError> {
+        match self.view().get_storage_at(storage_entry).map_err(to_state_error)? {
+            Some(value) => Ok(value.into()),
+            None => self.local.get_storage_at(storage_entry),
+        }
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: &[u8; 32]) -> Result<[u8; 32], StateError> {
+        self.local.get_compiled_class_hash(class_hash)
+    }
+}
@@ -4,6 +4,7 @@ use starknet_in_rust::services::api::contract_classes::deprecated_contract_class
 use starknet_in_rust::state::cached_state::CachedState;
 use starknet_in_rust::state::in_memory_state_reader::InMemoryStateReader;
 use starknet_in_rust::state::state_api::StateReader;
+use starknet_in_rust::state::state_cache::StorageEntry;
 use starknet_in_rust::utils::Address;
 use starknet_in_rust::CasmContractClass;
 use starknet_types::cairo_felt::Felt252;
@@ -12,17 +13,21 @@ use starknet_types::contract_class::ContractClass;
 use starknet_types::contract_storage_key::ContractStorageKey;
 use starknet_types::felt::{ClassHash, Felt};
 
+use self::fork::{Fork, ForkingStateReader, ForkingView};
 use self::state_diff::StateDiff;
 use crate::error::Result;
 use crate::traits::{StateChanger, StateExtractor};
 
+pub(crate) mod fork;
 pub(crate) mod state_diff;
 pub mod state_update;
 
 #[derive(Debug, Clone)]
 pub(crate) struct StarknetState {
     pub state: InMemoryStateReader,
-    pub pending_state: CachedState<InMemoryStateReader>,
+    pub pending_state: CachedState<ForkingStateReader>,
+    /// Network whose state is read where the committed state lacks it, if Devnet is forked
+    pub fork: Option<Fork>,
 }
 
 impl StarknetState {
@@ -31,19 +36,29 @@ impl StarknetState {
     // but rather a copy.
     pub(crate) fn synchronize_states(&mut self) {
         self.pending_state = CachedState::new(
-            Arc::new(self.state.clone()),
+            Arc::new(self.reader()),
             Some(self.state.class_hash_to_contract_class.clone()),
             Some(self.state.casm_contract_classes_mut().clone()),
         );
     }
+
+    /// Reader of the committed state, through which the pending state reads it
+    pub(crate) fn reader(&self) -> ForkingStateReader {
+        ForkingStateReader::new(self.state.clone(), self.fork.clone())
+    }
+
+    /// Reads the committed state without copying it
+    pub(crate) fn view(&self) -> ForkingView<'_> {
+        ForkingView::new(&self.state, self.fork.as_ref())
+    }
 }
 
 impl Default for StarknetState {
     fn default() -> Self {
-        let in_memory_state = InMemoryStateReader::default();
         Self {
-            state: in_memory_state.clone(),
-            pending_state: CachedState::new(Arc::new(in_memory_state), None, None),
+            state: InMemoryStateReader::default(),
+            pending_state: CachedState::new(Arc::new(ForkingStateReader::default()), None, None),
+            fork: None,
         }
     }
 }
@@ -144,19 +159,28 @@ impl StateChanger for StarknetState {
 
 impl StateExtractor for StarknetState {
     fn get_storage(&self, storage_key: ContractStorageKey) -> Result<Felt> {
-        Ok(self.state.get_storage_at(&storage_key.try_into()?).map(Felt::from)?)
+        let storage_entry: StorageEntry = storage_key.try_into()?;
+        match self.view().get_storage_at(&storage_entry)? {
+            Some(value) => Ok(value),
+            None => Ok(self.state.get_storage_at(&storage_entry).map(Felt::from)?),
+        }
     }
 
     fn is_contract_declared(&mut self, class_hash: &ClassHash) -> bool {
         self.state.class_hash_to_compiled_class_hash_mut().contains_key(&class_hash.bytes())
             || self.state.class_hash_to_contract_class.contains_key(&(class_hash.bytes()))
+            || self.state.casm_contract_classes_mut().contains_key(&class_hash.bytes())
     }
 
     fn get_class_hash_at_contract_address(
         &mut self,
         contract_address: &ContractAddress,
     ) -> Result<ClassHash> {
-        Ok(self.state.get_class_hash_at(&contract_address.try_into()?).map(Felt::new)??)
+        let address: Address = contract_address.try_into()?;
+        match self.view().get_class_hash_at(&address)? {
+            Some(class_hash) => Ok(class_hash),
+            None => Ok(self.state.get_class_hash_at(&address).map(Felt::new)??),
+        }
     }
 
     fn extract_state_diff_from_pending_state(&self) -> Result<StateDiff> {
@@ -255,14 +279,12 @@ mod tests {
             dummy_contract_address().try_into().unwrap();
 
         // check if current nonce is 0
-        assert!(
-            state
-                .state
-                .address_to_nonce
-                .get(&starknet_in_rust_address)
-                .unwrap()
-                .eq(&Felt252::from(0))
-        );
+        assert!(state
+            .state
+            .address_to_nonce
+            .get(&starknet_in_rust_address)
+            .unwrap()
+            .eq(&Felt252::from(0)));
 
         state.synchronize_states();
         state.pending_state.increment_nonce(&starknet_in_rust_address).unwrap();
@@ -271,14 +293,12 @@ mod tests {
             .unwrap();
 
         // check if nonce update was correct
-        assert!(
-            state
-                .state
-                .address_to_nonce
-                .get(&starknet_in_rust_address)
-                .unwrap()
-                .eq(&Felt252::from(1))
-        );
+        assert!(state
+            .state
+            .address_to_nonce
+            .get(&starknet_in_rust_address)
+            .unwrap()
+            .eq(&Felt252::from(1)));
     }
 
     #[test]
@@ -301,14 +321,12 @@ mod tests {
         assert!(state.deploy_contract(address, felt).is_ok());
         assert!(state.state.address_to_class_hash.len() == 1);
         assert!(state.state.address_to_class_hash.contains_key(&(address.try_into().unwrap())));
-        assert!(
-            state
-                .state
-                .address_to_nonce
-                .get(&(address.try_into().unwrap()))
-                .unwrap()
-                .eq(&Felt252::from(0))
-        );
+        assert!(state
+            .state
+            .address_to_nonce
+            .get(&(address.try_into().unwrap()))
+            .unwrap()
+            .eq(&Felt252::from(0)));
     }
 
     #[test]
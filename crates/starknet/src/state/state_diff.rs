@@ -2,9 +2,11 @@ use std::collections::HashMap;
 
 use starknet_in_rust::state::cached_state::CachedState;
 use starknet_in_rust::state::in_memory_state_reader::InMemoryStateReader;
+use starknet_in_rust::state::state_api::StateReader;
 use starknet_in_rust::state::StateDiff as StarknetInRustStateDiff;
 use starknet_in_rust::utils::subtract_mappings;
 use starknet_in_rust::CasmContractClass;
+use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::ContractClass;
 use starknet_types::felt::{ClassHash, Felt};
 
@@ -22,14 +24,17 @@ pub struct StateDiff {
     pub(crate) declared_contracts: HashMap<ClassHash, CasmContractClass>,
     // cairo 0 declared contracts
     pub(crate) cairo_0_declared_contracts: HashMap<ClassHash, ContractClass>,
+    // contracts whose class was replaced, with their new class hash; the class hashes in `inner`
+    // of all other contracts are of newly deployed contracts
+    pub(crate) replaced_classes: HashMap<ContractAddress, ClassHash>,
 }
 
 impl Eq for StateDiff {}
 
 impl StateDiff {
-    pub(crate) fn difference_between_old_and_new_state(
+    pub(crate) fn difference_between_old_and_new_state<S: StateReader>(
         mut old_state: InMemoryStateReader,
-        mut new_state: CachedState<InMemoryStateReader>,
+        mut new_state: CachedState<S>,
     ) -> Result<Self> {
         let mut class_hash_to_compiled_class_hash = HashMap::<ClassHash, ClassHash>::new();
         let mut declared_contracts = HashMap::<ClassHash, CasmContractClass>::new();
@@ -80,11 +85,28 @@ impl StateDiff {
 
         let diff = StarknetInRustStateDiff::from_cached_state(new_state)?;
 
+        // a contract that already had a class is not deployed, its class is replaced
+        let mut replaced_classes = HashMap::<ContractAddress, ClassHash>::new();
+        for (address, class_hash) in diff.address_to_class_hash() {
+            let had_class = old_state
+                .address_to_class_hash
+                .get(address)
+                .map_or(false, |old_class_hash| *old_class_hash != [0; 32]);
+            if had_class {
+                replaced_classes.insert(
+                    ContractAddress::try_from(address.clone())
+                        .map_err(crate::error::Error::from)?,
+                    Felt::new(*class_hash).map_err(crate::error::Error::from)?,
+                );
+            }
+        }
+
         Ok(StateDiff {
             inner: diff,
             class_hash_to_compiled_class_hash,
             cairo_0_declared_contracts,
             declared_contracts,
+            replaced_classes,
         })
     }
 }
@@ -228,6 +250,34 @@ mod tests {
         assert_eq!(generated_diff, expected_diff);
     }
 
+    #[test]
+    fn class_of_already_deployed_contract_is_replaced() {
+        let (mut old_state, _) = setup();
+        let contract_address = dummy_contract_address();
+        old_state
+            .address_to_class_hash_mut()
+            .insert(contract_address.try_into().unwrap(), Felt::from(1).bytes());
+        let mut new_state = CachedState::new(
+            Arc::new(old_state.clone()),
+            Some(HashMap::new()),
+            Some(HashMap::new()),
+        );
+
+        let new_class_hash = dummy_felt();
+        new_state
+            .cache_mut()
+            .class_hash_writes_mut()
+            .insert(contract_address.try_into().unwrap(), new_class_hash.bytes());
+
+        let generated_diff =
+            super::StateDiff::difference_between_old_and_new_state(old_state, new_state).unwrap();
+
+        assert_eq!(
+            generated_diff.replaced_classes,
+            HashMap::from([(contract_address, new_class_hash)])
+        );
+    }
+
     fn setup() -> (InMemoryStateReader, CachedState<InMemoryStateReader>) {
         let state = InMemoryStateReader::default();
         let cached_state =
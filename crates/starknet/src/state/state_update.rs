@@ -14,6 +14,7 @@ pub struct StateUpdate {
     pub storage_updates: Vec<(ContractAddress, Vec<(PatriciaKey, Felt)>)>,
     pub nonces: Vec<(ContractAddress, Felt)>,
     pub deployed_contracts: Vec<(ContractAddress, ClassHash)>,
+    pub replaced_classes: Vec<(ContractAddress, ClassHash)>,
 }
 
 impl StateUpdate {
@@ -54,15 +55,21 @@ impl StateUpdate {
             nonces.push((contract_address, Felt::from(nonce.clone())));
         }
 
-        // deployed contracts (address -> class hash)
+        // deployed contracts (address -> class hash), without those whose class was replaced
         let mut deployed_contracts = Vec::new();
         for (address, class_hash) in state_diff.inner.address_to_class_hash() {
             let contract_address =
                 ContractAddress::try_from(address.clone()).map_err(crate::error::Error::from)?;
+            if state_diff.replaced_classes.contains_key(&contract_address) {
+                continue;
+            }
             let class_hash = Felt::new(*class_hash).map_err(crate::error::Error::from)?;
             deployed_contracts.push((contract_address, class_hash));
         }
 
+        // replaced classes (address -> new class hash)
+        let replaced_classes = state_diff.replaced_classes.into_iter().collect();
+
         // TODO new and old root are not computed, they are not part of the MVP
         Ok(Self {
             block_hash,
@@ -73,6 +80,7 @@ impl StateUpdate {
             storage_updates,
             nonces,
             deployed_contracts,
+            replaced_classes,
         })
     }
 }
@@ -23,6 +23,14 @@ impl SystemContract {
             contract_class: ContractClass::cairo_0_from_json_str(contract_class_json_str)?,
         })
     }
+
+    pub(crate) fn class_hash(&self) -> ClassHash {
+        self.class_hash
+    }
+
+    pub(crate) fn contract_class(&self) -> &ContractClass {
+        &self.contract_class
+    }
 }
 
 impl Deployed for SystemContract {
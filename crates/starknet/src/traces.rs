@@ -0,0 +1,126 @@
+use starknet_in_rust::execution::{CallInfo, CallType as SirCallType, TransactionExecutionInfo};
+use starknet_in_rust::EntryPointType;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::Felt;
+
+use crate::error::Result;
+use crate::revert_reason;
+use crate::transactions::Transaction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallType {
+    Call,
+    LibraryCall,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderedEvent {
+    pub order: u64,
+    pub keys: Vec<Felt>,
+    pub data: Vec<Felt>,
+}
+
+/// A call to an entry point, together with all the calls it made
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionInvocation {
+    pub contract_address: ContractAddress,
+    pub entry_point_selector: Felt,
+    pub calldata: Vec<Felt>,
+    pub caller_address: ContractAddress,
+    pub class_hash: Felt,
+    pub entry_point_type: EntryPointType,
+    pub call_type: CallType,
+    pub result: Vec<Felt>,
+    pub calls: Vec<FunctionInvocation>,
+    pub events: Vec<OrderedEvent>,
+}
+
+impl FunctionInvocation {
+    /// Number of nested levels of calls, including this one
+    pub fn depth(&self) -> usize {
+        1 + self.calls.iter().map(FunctionInvocation::depth).max().unwrap_or(0)
+    }
+}
+
+impl TryFrom<&CallInfo> for FunctionInvocation {
+    type Error = crate::error::Error;
+
+    fn try_from(call_info: &CallInfo) -> Result<Self> {
+        Ok(Self {
+            contract_address: call_info.contract_address.clone().try_into()?,
+            entry_point_selector: call_info
+                .entry_point_selector
+                .clone()
+                .map(Felt::from)
+                .unwrap_or_default(),
+            calldata: call_info.calldata.iter().cloned().map(Felt::from).collect(),
+            caller_address: call_info.caller_address.clone().try_into()?,
+            class_hash: call_info.class_hash.map(Felt::from).unwrap_or_default(),
+            entry_point_type: call_info.entry_point_type.unwrap_or(EntryPointType::External),
+            call_type: match call_info.call_type {
+                Some(SirCallType::Delegate) => CallType::LibraryCall,
+                Some(SirCallType::Call) | None => CallType::Call,
+            },
+            result: call_info.retdata.iter().cloned().map(Felt::from).collect(),
+            calls: call_info
+                .internal_calls
+                .iter()
+                .map(FunctionInvocation::try_from)
+                .collect::<Result<_>>()?,
+            events: call_info
+                .events
+                .iter()
+                .map(|event| OrderedEvent {
+                    order: event.order,
+                    keys: event.keys.iter().cloned().map(Felt::from).collect(),
+                    data: event.data.iter().cloned().map(Felt::from).collect(),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Invocations made during the execution of a transaction. Which of them are present depends on
+/// the type of the transaction: declare transactions have no execution, deploy account
+/// transactions execute the constructor instead of `__execute__`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionTrace {
+    pub validate_invocation: Option<FunctionInvocation>,
+    pub execute_invocation: Option<FunctionInvocation>,
+    pub constructor_invocation: Option<FunctionInvocation>,
+    pub fee_transfer_invocation: Option<FunctionInvocation>,
+    /// Set if the execution of the transaction was reverted
+    pub revert_reason: Option<String>,
+}
+
+impl TransactionTrace {
+    pub fn new(
+        transaction: &Transaction,
+        execution_info: &TransactionExecutionInfo,
+    ) -> Result<Self> {
+        let to_invocation = |call_info: &Option<CallInfo>| {
+            call_info.as_ref().map(FunctionInvocation::try_from).transpose()
+        };
+
+        let call_invocation = to_invocation(&execution_info.call_info)?;
+        let (execute_invocation, constructor_invocation) = match transaction {
+            Transaction::Declare(_) | Transaction::DeclareV2(_) | Transaction::DeclareV3(_) => {
+                (None, None)
+            }
+            Transaction::DeployAccount(_) | Transaction::DeployAccountV3(_) => {
+                (None, call_invocation)
+            }
+            Transaction::Invoke(_) | Transaction::InvokeV3(_) | Transaction::L1Handler(_) => {
+                (call_invocation, None)
+            }
+        };
+
+        Ok(Self {
+            validate_invocation: to_invocation(&execution_info.validate_info)?,
+            execute_invocation,
+            constructor_invocation,
+            fee_transfer_invocation: to_invocation(&execution_info.fee_transfer_info)?,
+            revert_reason: revert_reason::describe(execution_info),
+        })
+    }
+}
@@ -2,6 +2,8 @@ use starknet_in_rust::core::transaction_hash::{
     calculate_transaction_hash_common, TransactionHashPrefix,
 };
 use starknet_in_rust::definitions::constants::VALIDATE_DECLARE_ENTRY_POINT_SELECTOR;
+use starknet_in_rust::definitions::transaction_type::TransactionType;
+use starknet_in_rust::transaction::Declare;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::ContractClass;
 use starknet_types::felt::{ClassHash, Felt, TransactionHash};
@@ -60,6 +62,34 @@ impl DeclareTransactionV1 {
     pub fn class_hash(&self) -> Option<&ClassHash> {
         self.class_hash.as_ref()
     }
+
+    /// Creates the transaction executed by starknet_in_rust
+    pub(crate) fn create_sir_declare(&self) -> Result<Declare> {
+        let class_hash = match self.class_hash {
+            Some(class_hash) => class_hash,
+            None => self.contract_class.generate_hash()?,
+        };
+        let transaction_hash = match self.transaction_hash {
+            Some(transaction_hash) => transaction_hash,
+            None => self.generate_hash()?,
+        };
+
+        Ok(Declare {
+            class_hash: class_hash.into(),
+            sender_address: self.sender_address.try_into()?,
+            tx_type: TransactionType::Declare,
+            validate_entry_point_selector: VALIDATE_DECLARE_ENTRY_POINT_SELECTOR.clone(),
+            version: self.version.into(),
+            max_fee: self.max_fee,
+            signature: self.signature.iter().map(|felt| felt.into()).collect(),
+            nonce: self.nonce.into(),
+            hash_value: transaction_hash.into(),
+            contract_class: self.contract_class.clone().try_into()?,
+            skip_execute: false,
+            skip_fee_transfer: false,
+            skip_validate: false,
+        })
+    }
 }
 
 impl HashProducer for DeclareTransactionV1 {
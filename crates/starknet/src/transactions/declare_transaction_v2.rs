@@ -1,12 +1,16 @@
+use starknet_in_rust::core::contract_address::compute_casm_class_hash;
 use starknet_in_rust::core::transaction_hash::{
     calculate_transaction_hash_common, TransactionHashPrefix,
 };
+use starknet_in_rust::transaction::DeclareV2;
+use starknet_in_rust::CasmContractClass;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::contract_class::ContractClass;
 use starknet_types::felt::{ClassHash, Felt, TransactionHash};
 use starknet_types::traits::HashProducer;
 use starknet_types::DevnetResult;
 
+use crate::constants::SUPPORTED_SIERRA_VERSIONS;
 use crate::error::{Error, Result};
 
 #[derive(Clone, PartialEq, Eq)]
@@ -66,6 +70,73 @@ impl DeclareTransactionV2 {
     pub fn compiled_class_hash(&self) -> &ClassHash {
         &self.compiled_class_hash
     }
+
+    /// Creates the transaction executed by starknet_in_rust
+    pub(crate) fn create_sir_declare(&self) -> Result<DeclareV2> {
+        let casm_contract_class =
+            compile_sierra_contract_class(&self.sierra_contract_class, self.compiled_class_hash)?;
+
+        Ok(DeclareV2::new(
+            &self.sierra_contract_class.clone().try_into()?,
+            Some(casm_contract_class),
+            self.compiled_class_hash.into(),
+            self.chain_id.into(),
+            self.sender_address.try_into()?,
+            self.max_fee,
+            self.version.into(),
+            self.signature.iter().map(|felt| felt.into()).collect(),
+            self.nonce.into(),
+        )?)
+    }
+}
+
+/// Version of Sierra of `sierra_contract_class`, which its program starts with as three felts:
+/// major, minor and patch. Programs compiled before the version was encoded have none.
+pub(crate) fn sierra_version(sierra_contract_class: &ContractClass) -> Option<String> {
+    let ContractClass::Cairo1(sierra_contract_class) = sierra_contract_class else {
+        return None;
+    };
+
+    let version = sierra_contract_class
+        .sierra_program
+        .iter()
+        .take(3)
+        .map(|felt| u32::try_from(&felt.value).ok())
+        .collect::<Option<Vec<u32>>>()?;
+    match version.as_slice() {
+        [major, minor, patch] => Some(format!("{major}.{minor}.{patch}")),
+        _ => None,
+    }
+}
+
+/// Compiles the Sierra class to CASM and checks that the hash of the compiled class is the
+/// `compiled_class_hash` given by the declaration. Classes of Sierra versions that are not
+/// supported are rejected, since the bundled compiler may compile them differently than their own
+/// compiler. Classes without a version are compiled as before versions were checked. The compiled
+/// class is handed over to starknet_in_rust, which then doesn't compile the Sierra class again.
+pub(crate) fn compile_sierra_contract_class(
+    sierra_contract_class: &ContractClass,
+    compiled_class_hash: ClassHash,
+) -> Result<CasmContractClass> {
+    if let Some(version) = sierra_version(sierra_contract_class) {
+        if !SUPPORTED_SIERRA_VERSIONS.contains(&version.as_str()) {
+            return Err(Error::UnsupportedSierraVersion { version });
+        }
+    }
+
+    let casm_contract_class = CasmContractClass::try_from(sierra_contract_class.clone())?;
+    let expected_compiled_class_hash: ClassHash = compute_casm_class_hash(&casm_contract_class)
+        .map_err(starknet_types::error::Error::from)?
+        .into();
+
+    if expected_compiled_class_hash != compiled_class_hash {
+        return Err(Error::CompiledClassHashMismatch {
+            expected: expected_compiled_class_hash,
+            provided: compiled_class_hash,
+        });
+    }
+
+    Ok(casm_contract_class)
 }
 
 impl HashProducer for DeclareTransactionV2 {
@@ -99,10 +170,52 @@ impl HashProducer for DeclareTransactionV2 {
 #[cfg(test)]
 mod tests {
 
+    use starknet_in_rust::core::contract_address::compute_casm_class_hash;
+    use starknet_in_rust::CasmContractClass;
+    use starknet_types::contract_class::ContractClass;
+    use starknet_types::felt::Felt;
+    use starknet_types::num_bigint::BigUint;
+
+    use super::{compile_sierra_contract_class, sierra_version};
+    use crate::error::Error;
     use crate::utils::test_utils::{
         dummy_cairo_1_contract_class, dummy_contract_address, dummy_felt,
     };
 
+    /// Sierra class compiled to Sierra 1.1.0, whose ABI is serialized as a string
+    fn cairo_1_contract_class_of_sierra_1_1_0() -> ContractClass {
+        let mut sierra: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../starknet-server/test_data/sierra_contract_class_with_abi_as_string.json"
+            ))
+            .unwrap(),
+        )
+        .unwrap();
+        sierra["abi"] = serde_json::from_str(sierra["abi"].as_str().unwrap()).unwrap();
+
+        ContractClass::Cairo1(serde_json::from_value(sierra).unwrap())
+    }
+
+    /// The dummy Sierra class with the felt at `index` of its program replaced by `value`
+    fn dummy_cairo_1_contract_class_with_program_felt(
+        index: usize,
+        value: BigUint,
+    ) -> ContractClass {
+        let ContractClass::Cairo1(mut sierra_contract_class) = dummy_cairo_1_contract_class()
+        else {
+            panic!("Expected a Sierra class");
+        };
+        sierra_contract_class.sierra_program[index].value = value;
+        ContractClass::Cairo1(sierra_contract_class)
+    }
+
+    fn compiled_class_hash_of(contract_class: &ContractClass) -> Felt {
+        compute_casm_class_hash(&CasmContractClass::try_from(contract_class.clone()).unwrap())
+            .unwrap()
+            .into()
+    }
+
     #[ignore]
     #[test]
     fn correct_declare_transaction_hash_computation() {
@@ -129,4 +242,78 @@ mod tests {
             _ => panic!("Wrong error type"),
         }
     }
+
+    #[test]
+    fn declare_transaction_v2_with_wrong_compiled_class_hash_should_return_an_error() {
+        let contract_class = dummy_cairo_1_contract_class();
+        let compiled_class_hash: Felt =
+            compute_casm_class_hash(&CasmContractClass::try_from(contract_class.clone()).unwrap())
+                .unwrap()
+                .into();
+        let wrong_compiled_class_hash = Felt::from(42);
+
+        let declare_transaction = super::DeclareTransactionV2::new(
+            contract_class,
+            wrong_compiled_class_hash,
+            dummy_contract_address(),
+            100,
+            vec![],
+            dummy_felt(),
+            dummy_felt(),
+        )
+        .unwrap();
+
+        match declare_transaction.create_sir_declare() {
+            Err(crate::error::Error::CompiledClassHashMismatch { expected, provided }) => {
+                assert_eq!(expected, compiled_class_hash);
+                assert_eq!(provided, wrong_compiled_class_hash);
+            }
+            _ => panic!("Wrong result"),
+        }
+    }
+
+    #[test]
+    fn classes_of_supported_sierra_versions_compiled() {
+        for (contract_class, expected_version) in [
+            (dummy_cairo_1_contract_class(), "1.2.0"),
+            (cairo_1_contract_class_of_sierra_1_1_0(), "1.1.0"),
+        ] {
+            assert_eq!(sierra_version(&contract_class).unwrap(), expected_version);
+
+            let compiled_class_hash = compiled_class_hash_of(&contract_class);
+            let casm_contract_class =
+                compile_sierra_contract_class(&contract_class, compiled_class_hash).unwrap();
+            assert_eq!(
+                Felt::from(compute_casm_class_hash(&casm_contract_class).unwrap()),
+                compiled_class_hash
+            );
+        }
+    }
+
+    #[test]
+    fn class_without_sierra_version_compiled() {
+        // a felt not fitting in 32 bits is not part of a version
+        let contract_class =
+            dummy_cairo_1_contract_class_with_program_felt(0, BigUint::from(u64::MAX));
+        assert!(sierra_version(&contract_class).is_none());
+
+        let compiled_class_hash = compiled_class_hash_of(&contract_class);
+        compile_sierra_contract_class(&contract_class, compiled_class_hash).unwrap();
+    }
+
+    #[test]
+    fn class_of_unsupported_sierra_version_rejected_with_supported_versions() {
+        let contract_class =
+            dummy_cairo_1_contract_class_with_program_felt(1, BigUint::from(99u32));
+
+        match compile_sierra_contract_class(&contract_class, dummy_felt()) {
+            Err(err @ Error::UnsupportedSierraVersion { .. }) => {
+                assert_eq!(
+                    err.to_string(),
+                    "Sierra version 1.99.0 is not supported; supported versions are 1.1.0, 1.2.0"
+                );
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,185 @@
+use starknet_in_rust::transaction::DeclareV2;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::ContractClass;
+use starknet_types::felt::{ClassHash, Felt, TransactionHash};
+use starknet_types::traits::HashProducer;
+use starknet_types::DevnetResult;
+
+use super::declare_transaction_v2::compile_sierra_contract_class;
+use super::v3::{poseidon_hash_of, V3Fields};
+use crate::error::Result;
+
+/// Declare transaction of version 3, whose fee is paid in STRK
+#[derive(Clone, PartialEq, Eq)]
+pub struct DeclareTransactionV3 {
+    pub(crate) sierra_contract_class: ContractClass,
+    pub(crate) compiled_class_hash: ClassHash,
+    pub(crate) sender_address: ContractAddress,
+    pub(crate) signature: Vec<Felt>,
+    pub(crate) nonce: Felt,
+    pub(crate) class_hash: Option<ClassHash>,
+    pub(crate) transaction_hash: Option<TransactionHash>,
+    pub(crate) chain_id: Felt,
+    pub(crate) version: Felt,
+    pub(crate) v3_fields: V3Fields,
+    pub(crate) account_deployment_data: Vec<Felt>,
+}
+
+impl DeclareTransactionV3 {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sierra_contract_class: ContractClass,
+        compiled_class_hash: ClassHash,
+        sender_address: ContractAddress,
+        signature: Vec<Felt>,
+        nonce: Felt,
+        chain_id: Felt,
+        v3_fields: V3Fields,
+        account_deployment_data: Vec<Felt>,
+    ) -> Result<Self> {
+        v3_fields.validate_l1_gas_bounds("declare")?;
+
+        Ok(Self {
+            sierra_contract_class,
+            compiled_class_hash,
+            sender_address,
+            signature,
+            nonce,
+            class_hash: None,
+            transaction_hash: None,
+            chain_id,
+            version: Felt::from(3),
+            v3_fields,
+            account_deployment_data,
+        })
+    }
+
+    pub fn sender_address(&self) -> &ContractAddress {
+        &self.sender_address
+    }
+
+    pub fn class_hash(&self) -> Option<&ClassHash> {
+        self.class_hash.as_ref()
+    }
+
+    pub fn compiled_class_hash(&self) -> &ClassHash {
+        &self.compiled_class_hash
+    }
+
+    pub fn max_fee(&self) -> u128 {
+        self.v3_fields.max_fee()
+    }
+
+    pub fn v3_fields(&self) -> &V3Fields {
+        &self.v3_fields
+    }
+
+    pub fn account_deployment_data(&self) -> &[Felt] {
+        &self.account_deployment_data
+    }
+
+    /// Creates the transaction executed by starknet_in_rust: a declaration of version 2 with the
+    /// hash of version 3
+    pub(crate) fn create_sir_declare(&self) -> Result<DeclareV2> {
+        let casm_contract_class =
+            compile_sierra_contract_class(&self.sierra_contract_class, self.compiled_class_hash)?;
+
+        let mut declare = DeclareV2::new(
+            &self.sierra_contract_class.clone().try_into()?,
+            Some(casm_contract_class),
+            self.compiled_class_hash.into(),
+            self.chain_id.into(),
+            self.sender_address.try_into()?,
+            self.max_fee(),
+            Felt::from(2).into(),
+            self.signature.iter().map(|felt| felt.into()).collect(),
+            self.nonce.into(),
+        )?;
+
+        let transaction_hash = match self.transaction_hash {
+            Some(transaction_hash) => transaction_hash,
+            None => self.generate_hash()?,
+        };
+        declare.hash_value = transaction_hash.into();
+
+        Ok(declare)
+    }
+}
+
+impl HashProducer for DeclareTransactionV3 {
+    fn generate_hash(&self) -> DevnetResult<Felt> {
+        let class_hash = self.class_hash.unwrap_or(self.sierra_contract_class.generate_hash()?);
+
+        Ok(self.v3_fields.transaction_hash(
+            b"declare",
+            self.sender_address.into(),
+            self.chain_id,
+            self.nonce,
+            &[
+                poseidon_hash_of(&self.account_deployment_data),
+                class_hash.into(),
+                self.compiled_class_hash.into(),
+            ],
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_in_rust::core::contract_address::compute_casm_class_hash;
+    use starknet_in_rust::CasmContractClass;
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::HashProducer;
+
+    use super::DeclareTransactionV3;
+    use crate::transactions::v3::{ResourceBounds, ResourceBoundsMapping, V3Fields};
+    use crate::utils::test_utils::{
+        dummy_cairo_1_contract_class, dummy_contract_address, dummy_felt,
+    };
+
+    fn declare_transaction_v3(max_amount: u64) -> crate::error::Result<DeclareTransactionV3> {
+        let contract_class = dummy_cairo_1_contract_class();
+        let compiled_class_hash =
+            compute_casm_class_hash(&CasmContractClass::try_from(contract_class.clone()).unwrap())
+                .unwrap();
+
+        DeclareTransactionV3::new(
+            contract_class,
+            compiled_class_hash.into(),
+            dummy_contract_address(),
+            vec![],
+            dummy_felt(),
+            dummy_felt(),
+            V3Fields {
+                resource_bounds: ResourceBoundsMapping {
+                    l1_gas: ResourceBounds { max_amount, max_price_per_unit: 1 },
+                    l2_gas: ResourceBounds::default(),
+                },
+                ..V3Fields::default()
+            },
+            vec![],
+        )
+    }
+
+    #[test]
+    fn declare_transaction_v3_with_zero_l1_gas_bounds_should_return_an_error() {
+        match declare_transaction_v3(0) {
+            Err(crate::error::Error::TransactionError(
+                starknet_in_rust::transaction::error::TransactionError::FeeError(msg),
+            )) => assert_eq!(msg, "For declare transaction version 3, L1 gas bounds cannot be 0"),
+            _ => panic!("Wrong result"),
+        }
+    }
+
+    #[test]
+    fn executed_declaration_carries_hash_of_version_3() {
+        let declare_transaction = declare_transaction_v3(1000).unwrap();
+        let sir_declare = declare_transaction.create_sir_declare().unwrap();
+
+        assert_eq!(
+            Felt::from(sir_declare.hash_value.clone()),
+            declare_transaction.generate_hash().unwrap()
+        );
+        assert_eq!(sir_declare.max_fee, 1000);
+    }
+}
@@ -0,0 +1,171 @@
+use starknet_in_rust::transaction::error::TransactionError;
+use starknet_in_rust::transaction::DeployAccount;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::error::Error;
+use starknet_types::felt::{ClassHash, Felt};
+use starknet_types::traits::HashProducer;
+
+use super::v3::{poseidon_hash_of, V3Fields};
+use crate::error::{self, Result};
+
+/// Deploy account transaction of version 3, whose fee is paid in STRK
+#[derive(Clone)]
+pub struct DeployAccountTransactionV3 {
+    pub(crate) inner: DeployAccount,
+    pub(crate) chain_id: Felt,
+    pub(crate) signature: Vec<Felt>,
+    pub(crate) nonce: Felt,
+    pub(crate) version: Felt,
+    pub(crate) v3_fields: V3Fields,
+}
+
+impl Eq for DeployAccountTransactionV3 {}
+
+impl PartialEq for DeployAccountTransactionV3 {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.hash_value() == other.inner.hash_value()
+            && self.inner.signature() == other.inner.signature()
+    }
+}
+
+impl DeployAccountTransactionV3 {
+    pub fn new(
+        constructor_calldata: Vec<Felt>,
+        signature: Vec<Felt>,
+        nonce: Felt,
+        class_hash: ClassHash,
+        contract_address_salt: Felt,
+        chain_id: Felt,
+        v3_fields: V3Fields,
+    ) -> Result<Self> {
+        v3_fields.validate_l1_gas_bounds("deploy account")?;
+
+        // executed in place of the transaction until its hash is known, which requires the
+        // address of the account derived by it
+        let address_deriving_deploy_account = DeployAccount::new(
+            class_hash.bytes(),
+            0,
+            Felt::from(1).into(),
+            nonce.into(),
+            constructor_calldata.iter().map(|felt| felt.into()).collect(),
+            signature.iter().map(|felt| felt.into()).collect(),
+            contract_address_salt.into(),
+            chain_id.into(),
+        )
+        .map_err(|err| Error::TransactionError(TransactionError::Syscall(err)))?;
+        let contract_address = Felt::from(ContractAddress::try_from(
+            address_deriving_deploy_account.contract_address().clone(),
+        )?);
+
+        let transaction_hash = v3_fields.transaction_hash(
+            b"deploy_account",
+            contract_address,
+            chain_id,
+            nonce,
+            &[
+                poseidon_hash_of(&constructor_calldata),
+                class_hash.into(),
+                contract_address_salt.into(),
+            ],
+        );
+
+        let inner = DeployAccount::new_with_tx_hash(
+            class_hash.bytes(),
+            v3_fields.max_fee(),
+            Felt::from(1).into(),
+            nonce.into(),
+            constructor_calldata.iter().map(|felt| felt.into()).collect(),
+            signature.iter().map(|felt| felt.into()).collect(),
+            contract_address_salt.into(),
+            transaction_hash.into(),
+        )
+        .map_err(|err| Error::TransactionError(TransactionError::Syscall(err)))?;
+
+        Ok(Self { inner, chain_id, signature, nonce, version: Felt::from(3), v3_fields })
+    }
+
+    pub fn max_fee(&self) -> u128 {
+        self.v3_fields.max_fee()
+    }
+
+    pub fn class_hash(&self) -> Result<Felt> {
+        Felt::new(*self.inner.class_hash()).map_err(error::Error::from)
+    }
+
+    pub fn contract_address_salt(&self) -> Felt {
+        (self.inner.contract_address_salt().clone()).into()
+    }
+
+    pub fn constructor_calldata(&self) -> Vec<Felt> {
+        self.inner.constructor_calldata().clone().into_iter().map(|felt| felt.into()).collect()
+    }
+
+    pub fn v3_fields(&self) -> &V3Fields {
+        &self.v3_fields
+    }
+}
+
+impl HashProducer for DeployAccountTransactionV3 {
+    fn generate_hash(&self) -> starknet_types::DevnetResult<Felt> {
+        Ok(self.inner.hash_value().clone().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::HashProducer;
+
+    use super::DeployAccountTransactionV3;
+    use crate::transactions::v3::{ResourceBounds, ResourceBoundsMapping, V3Fields};
+    use crate::utils::test_utils::dummy_felt;
+
+    fn deploy_account_transaction_v3(
+        max_amount: u64,
+        max_price_per_unit: u128,
+    ) -> crate::error::Result<DeployAccountTransactionV3> {
+        DeployAccountTransactionV3::new(
+            vec![Felt::from(1)],
+            vec![],
+            Felt::from(0),
+            dummy_felt(),
+            Felt::from(13),
+            dummy_felt(),
+            V3Fields {
+                resource_bounds: ResourceBoundsMapping {
+                    l1_gas: ResourceBounds { max_amount, max_price_per_unit },
+                    l2_gas: ResourceBounds::default(),
+                },
+                ..V3Fields::default()
+            },
+        )
+    }
+
+    #[test]
+    fn deploy_account_transaction_v3_with_zero_l1_gas_bounds_should_return_an_error() {
+        match deploy_account_transaction_v3(0, 1) {
+            Err(crate::error::Error::TransactionError(
+                starknet_in_rust::transaction::error::TransactionError::FeeError(msg),
+            )) => assert_eq!(
+                msg,
+                "For deploy account transaction version 3, L1 gas bounds cannot be 0"
+            ),
+            _ => panic!("Wrong result"),
+        }
+    }
+
+    #[test]
+    fn max_fee_and_hash_depend_on_resource_bounds() {
+        let transaction = deploy_account_transaction_v3(1000, 3).unwrap();
+        assert_eq!(transaction.max_fee(), 3000);
+        assert_eq!(transaction.version, Felt::from(3));
+
+        let same_transaction = deploy_account_transaction_v3(1000, 3).unwrap();
+        let other_transaction = deploy_account_transaction_v3(1000, 4).unwrap();
+        assert_eq!(transaction.generate_hash().unwrap(), same_transaction.generate_hash().unwrap());
+        assert_ne!(
+            transaction.generate_hash().unwrap(),
+            other_transaction.generate_hash().unwrap()
+        );
+    }
+}
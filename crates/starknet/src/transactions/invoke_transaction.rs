@@ -1,5 +1,4 @@
 use starknet_in_rust::definitions::constants::EXECUTE_ENTRY_POINT_SELECTOR;
-use starknet_in_rust::transaction::error::TransactionError;
 use starknet_in_rust::transaction::InvokeFunction;
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::Felt;
@@ -37,12 +36,6 @@ impl InvokeTransactionV1 {
         calldata: Vec<Felt>,
         chain_id: Felt,
     ) -> Result<Self> {
-        if max_fee == 0 {
-            return Err(error::Error::TransactionError(TransactionError::FeeError(
-                "For invoke transaction, max fee cannot be 0".to_string(),
-            )));
-        }
-
         Ok(Self {
             inner: starknet_in_rust::transaction::InvokeFunction::new(
                 sender_address.try_into()?,
@@ -80,7 +73,12 @@ impl HashProducer for InvokeTransactionV1 {
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::test_utils::{dummy_contract_address, dummy_felt};
+    use starknet_types::chain_id::ChainId;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::HashProducer;
+
+    use super::InvokeTransactionV1;
 
     #[test]
     #[ignore]
@@ -89,22 +87,22 @@ mod tests {
     }
 
     #[test]
-    fn invoke_transaction_with_max_fee_zero_should_return_error() {
-        let result = super::InvokeTransactionV1::new(
-            dummy_contract_address(),
-            0,
-            vec![],
-            dummy_felt(),
-            vec![],
-            dummy_felt(),
-        );
+    fn transaction_hash_depends_on_chain_id() {
+        let transaction_on = |chain_id: ChainId| {
+            InvokeTransactionV1::new(
+                ContractAddress::new(Felt::from(1)).unwrap(),
+                100,
+                vec![],
+                Felt::from(0),
+                vec![Felt::from(2)],
+                chain_id.to_felt(),
+            )
+            .unwrap()
+        };
 
-        assert!(result.is_err());
-        match result.err().unwrap() {
-            crate::error::Error::TransactionError(
-                starknet_in_rust::transaction::error::TransactionError::FeeError(msg),
-            ) => assert_eq!(msg, "For invoke transaction, max fee cannot be 0"),
-            _ => panic!("Wrong error type"),
-        }
+        assert_ne!(
+            transaction_on(ChainId::SEPOLIA).generate_hash().unwrap(),
+            transaction_on(ChainId::MAINNET).generate_hash().unwrap()
+        );
     }
 }
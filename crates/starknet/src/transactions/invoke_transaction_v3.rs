@@ -0,0 +1,170 @@
+use starknet_in_rust::definitions::constants::EXECUTE_ENTRY_POINT_SELECTOR;
+use starknet_in_rust::transaction::InvokeFunction;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::Felt;
+use starknet_types::traits::HashProducer;
+
+use super::v3::{poseidon_hash_of, V3Fields};
+use crate::error::{self, Result};
+
+/// Invoke transaction of version 3, whose fee is paid in STRK
+#[derive(Clone)]
+pub struct InvokeTransactionV3 {
+    pub(crate) inner: InvokeFunction,
+    pub(crate) chain_id: Felt,
+    pub(crate) signature: Vec<Felt>,
+    pub(crate) nonce: Felt,
+    pub(crate) calldata: Vec<Felt>,
+    pub(crate) version: Felt,
+    pub(crate) v3_fields: V3Fields,
+    pub(crate) account_deployment_data: Vec<Felt>,
+}
+
+impl Eq for InvokeTransactionV3 {}
+
+impl PartialEq for InvokeTransactionV3 {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.signature() == other.inner.signature()
+            && self.inner.contract_address() == other.inner.contract_address()
+            && self.inner.hash_value() == other.inner.hash_value()
+    }
+}
+
+impl InvokeTransactionV3 {
+    pub fn new(
+        sender_address: ContractAddress,
+        signature: Vec<Felt>,
+        nonce: Felt,
+        calldata: Vec<Felt>,
+        chain_id: Felt,
+        v3_fields: V3Fields,
+        account_deployment_data: Vec<Felt>,
+    ) -> Result<Self> {
+        v3_fields.validate_l1_gas_bounds("invoke")?;
+
+        let transaction_hash = v3_fields.transaction_hash(
+            b"invoke",
+            sender_address.into(),
+            chain_id,
+            nonce,
+            &[poseidon_hash_of(&account_deployment_data), poseidon_hash_of(&calldata)],
+        );
+
+        Ok(Self {
+            inner: InvokeFunction::new_with_tx_hash(
+                sender_address.try_into()?,
+                EXECUTE_ENTRY_POINT_SELECTOR.clone(),
+                v3_fields.max_fee(),
+                Felt::from(1).into(),
+                calldata.iter().map(|f| f.into()).collect(),
+                signature.iter().map(|f| f.into()).collect(),
+                Some(nonce.into()),
+                transaction_hash.into(),
+            )?,
+            chain_id,
+            signature,
+            nonce,
+            calldata,
+            version: Felt::from(3),
+            v3_fields,
+            account_deployment_data,
+        })
+    }
+
+    pub fn sender_address(&self) -> Result<ContractAddress> {
+        self.inner.contract_address().clone().try_into().map_err(error::Error::from)
+    }
+
+    pub fn calldata(&self) -> &Vec<Felt> {
+        &self.calldata
+    }
+
+    pub fn max_fee(&self) -> u128 {
+        self.v3_fields.max_fee()
+    }
+
+    pub fn v3_fields(&self) -> &V3Fields {
+        &self.v3_fields
+    }
+
+    pub fn account_deployment_data(&self) -> &[Felt] {
+        &self.account_deployment_data
+    }
+}
+
+impl HashProducer for InvokeTransactionV3 {
+    fn generate_hash(&self) -> starknet_types::DevnetResult<Felt> {
+        Ok(self.inner.hash_value().clone().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_types::chain_id::ChainId;
+    use starknet_types::contract_address::ContractAddress;
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::HashProducer;
+
+    use super::InvokeTransactionV3;
+    use crate::transactions::invoke_transaction::InvokeTransactionV1;
+    use crate::transactions::v3::{ResourceBounds, ResourceBoundsMapping, V3Fields};
+
+    fn v3_fields() -> V3Fields {
+        V3Fields {
+            resource_bounds: ResourceBoundsMapping {
+                l1_gas: ResourceBounds { max_amount: 100, max_price_per_unit: 1 },
+                l2_gas: ResourceBounds::default(),
+            },
+            ..V3Fields::default()
+        }
+    }
+
+    #[test]
+    fn hash_differs_from_version_1_with_same_max_fee() {
+        let sender_address = ContractAddress::new(Felt::from(1)).unwrap();
+        let chain_id = ChainId::TESTNET.to_felt();
+
+        let invoke_v3 = InvokeTransactionV3::new(
+            sender_address,
+            vec![],
+            Felt::from(0),
+            vec![Felt::from(2)],
+            chain_id,
+            v3_fields(),
+            vec![],
+        )
+        .unwrap();
+        let invoke_v1 = InvokeTransactionV1::new(
+            sender_address,
+            invoke_v3.max_fee(),
+            vec![],
+            Felt::from(0),
+            vec![Felt::from(2)],
+            chain_id,
+        )
+        .unwrap();
+
+        assert_eq!(invoke_v3.max_fee(), 100);
+        assert_ne!(invoke_v3.generate_hash().unwrap(), invoke_v1.generate_hash().unwrap());
+    }
+
+    #[test]
+    fn hash_depends_on_account_deployment_data() {
+        let invoke_with = |account_deployment_data: Vec<Felt>| {
+            InvokeTransactionV3::new(
+                ContractAddress::new(Felt::from(1)).unwrap(),
+                vec![],
+                Felt::from(0),
+                vec![],
+                ChainId::TESTNET.to_felt(),
+                v3_fields(),
+                account_deployment_data,
+            )
+            .unwrap()
+            .generate_hash()
+            .unwrap()
+        };
+
+        assert_ne!(invoke_with(vec![]), invoke_with(vec![Felt::from(1)]));
+    }
+}
@@ -0,0 +1,145 @@
+use sha3::{Digest, Keccak256};
+use starknet_in_rust::felt::Felt252;
+use starknet_in_rust::transaction::L1Handler;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::Felt;
+use starknet_types::traits::HashProducer;
+
+use crate::error::Result;
+
+/// Transaction executing the L1 handler of a contract, triggered by a message sent from L1
+#[derive(Clone)]
+pub struct L1HandlerTransaction {
+    pub(crate) inner: L1Handler,
+    pub(crate) contract_address: ContractAddress,
+    pub(crate) entry_point_selector: Felt,
+    /// Address of the L1 sender of the message, followed by the payload
+    pub(crate) calldata: Vec<Felt>,
+    pub(crate) nonce: Felt,
+    pub(crate) paid_fee_on_l1: u128,
+    pub(crate) chain_id: Felt,
+    pub(crate) version: Felt,
+}
+
+impl Eq for L1HandlerTransaction {}
+
+impl PartialEq for L1HandlerTransaction {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.hash_value() == other.inner.hash_value()
+    }
+}
+
+impl L1HandlerTransaction {
+    pub fn new(
+        contract_address: ContractAddress,
+        entry_point_selector: Felt,
+        calldata: Vec<Felt>,
+        nonce: Felt,
+        paid_fee_on_l1: u128,
+        chain_id: Felt,
+    ) -> Result<Self> {
+        Ok(Self {
+            inner: L1Handler::new(
+                contract_address.try_into()?,
+                entry_point_selector.into(),
+                calldata.iter().map(|f| f.into()).collect(),
+                nonce.into(),
+                chain_id.into(),
+                Some(Felt252::from(paid_fee_on_l1)),
+            )?,
+            contract_address,
+            entry_point_selector,
+            calldata,
+            nonce,
+            paid_fee_on_l1,
+            chain_id,
+            version: Felt::from(0),
+        })
+    }
+
+    pub fn contract_address(&self) -> ContractAddress {
+        self.contract_address
+    }
+
+    pub fn entry_point_selector(&self) -> &Felt {
+        &self.entry_point_selector
+    }
+
+    pub fn calldata(&self) -> &Vec<Felt> {
+        &self.calldata
+    }
+
+    /// Hash of the consumed message, computed the same way as by the Starknet core contract on L1:
+    /// keccak256 of the sender, the recipient, the nonce, the selector, the payload length and the
+    /// payload, each encoded as 32 bytes. Returned as a 0x-prefixed hex string, since it doesn't
+    /// necessarily fit into a felt.
+    pub fn message_hash(&self) -> String {
+        let (from_address, payload) = match self.calldata.split_first() {
+            Some((from_address, payload)) => (*from_address, payload),
+            None => (Felt::default(), &[][..]),
+        };
+
+        let mut hasher = Keccak256::new();
+        hasher.update(from_address.bytes());
+        hasher.update(Felt::from(self.contract_address).bytes());
+        hasher.update(self.nonce.bytes());
+        hasher.update(self.entry_point_selector.bytes());
+        hasher.update(Felt::from(payload.len() as u128).bytes());
+        for felt in payload {
+            hasher.update(felt.bytes());
+        }
+
+        let hash: String = hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect();
+        format!("0x{hash}")
+    }
+}
+
+impl HashProducer for L1HandlerTransaction {
+    fn generate_hash(&self) -> starknet_types::DevnetResult<Felt> {
+        Ok(self.inner.hash_value().clone().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_types::chain_id::ChainId;
+    use starknet_types::felt::Felt;
+    use starknet_types::traits::HashProducer;
+
+    use super::L1HandlerTransaction;
+    use crate::utils::test_utils::dummy_contract_address;
+
+    fn l1_handler_transaction(nonce: u128, payload: Vec<Felt>) -> L1HandlerTransaction {
+        let mut calldata = vec![Felt::from(0x1234)];
+        calldata.extend(payload);
+
+        L1HandlerTransaction::new(
+            dummy_contract_address(),
+            Felt::from(1),
+            calldata,
+            Felt::from(nonce),
+            1,
+            ChainId::TESTNET.to_felt(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn message_hash_depends_on_nonce_and_payload() {
+        let message_hash = l1_handler_transaction(0, vec![Felt::from(1)]).message_hash();
+        assert_eq!(message_hash.len(), 66);
+        assert!(message_hash.starts_with("0x"));
+
+        assert_eq!(l1_handler_transaction(0, vec![Felt::from(1)]).message_hash(), message_hash);
+        assert_ne!(l1_handler_transaction(1, vec![Felt::from(1)]).message_hash(), message_hash);
+        assert_ne!(l1_handler_transaction(0, vec![Felt::from(2)]).message_hash(), message_hash);
+    }
+
+    #[test]
+    fn transaction_hash_depends_on_nonce() {
+        assert_ne!(
+            l1_handler_transaction(0, vec![]).generate_hash().unwrap(),
+            l1_handler_transaction(1, vec![]).generate_hash().unwrap()
+        );
+    }
+}
@@ -1,28 +1,137 @@
 pub mod declare_transaction;
 pub mod declare_transaction_v2;
+pub mod declare_transaction_v3;
 pub mod deploy_account_transaction;
+pub mod deploy_account_transaction_v3;
 pub mod invoke_transaction;
+pub mod invoke_transaction_v3;
+pub mod l1_handler_transaction;
+pub mod v3;
 
 use std::collections::HashMap;
 
 use starknet_api::block::BlockNumber;
-use starknet_in_rust::execution::TransactionExecutionInfo;
+use starknet_in_rust::execution::{CallInfo, TransactionExecutionInfo};
 use starknet_in_rust::transaction::error::TransactionError;
+use starknet_in_rust::transaction::Transaction as SirTransaction;
 use starknet_rs_core::types::TransactionStatus;
+use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::{BlockHash, Felt, TransactionHash};
+use tokio::sync::broadcast;
 
 use self::declare_transaction::DeclareTransactionV1;
 use self::declare_transaction_v2::DeclareTransactionV2;
+use self::declare_transaction_v3::DeclareTransactionV3;
 use self::deploy_account_transaction::DeployAccountTransaction;
+use self::deploy_account_transaction_v3::DeployAccountTransactionV3;
 use self::invoke_transaction::InvokeTransactionV1;
-use crate::traits::HashIdentifiedMut;
+use self::invoke_transaction_v3::InvokeTransactionV3;
+use self::v3::V3Fields;
+use self::l1_handler_transaction::L1HandlerTransaction;
+use crate::error::Result;
+use crate::revert_reason;
+use crate::starknet::FeeUnit;
+use crate::traits::{HashIdentified, HashIdentifiedMut};
 
-#[derive(Default)]
-pub struct StarknetTransactions(HashMap<TransactionHash, StarknetTransaction>);
+/// Number of status changes kept for subscribers that have not received them yet
+const STATUS_CHANGES_CHANNEL_CAPACITY: usize = 1000;
+
+/// Number of pending transactions kept for subscribers that have not received them yet
+const PENDING_TRANSACTIONS_CHANNEL_CAPACITY: usize = 1000;
+
+/// Resource name under which starknet_in_rust reports the Cairo steps of a transaction
+const N_STEPS: &str = "n_steps";
+
+/// Suffix of the resource names under which starknet_in_rust reports builtin usages
+const BUILTIN_SUFFIX: &str = "_builtin";
+
+/// Notifies all subscribers about every transaction added to the pending block
+pub(crate) struct PendingTransactionsNotifier {
+    sender: broadcast::Sender<Transaction>,
+}
+
+impl Default for PendingTransactionsNotifier {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(PENDING_TRANSACTIONS_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+}
+
+impl PendingTransactionsNotifier {
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<Transaction> {
+        self.sender.subscribe()
+    }
+
+    pub(crate) fn notify(&self, transaction: &Transaction) {
+        // sending fails only if there are no subscribers, which is not an error
+        let _ = self.sender.send(transaction.clone());
+    }
+}
+
+pub struct StarknetTransactions {
+    transactions: HashMap<TransactionHash, StarknetTransaction>,
+    /// Arrival index assigned to the next inserted transaction
+    next_arrival_index: u64,
+    /// Hashes of transactions whose status changed, including newly inserted ones
+    status_changes: broadcast::Sender<TransactionHash>,
+}
+
+impl Default for StarknetTransactions {
+    fn default() -> Self {
+        let (status_changes, _) = broadcast::channel(STATUS_CHANGES_CHANNEL_CAPACITY);
+        Self { transactions: HashMap::new(), next_arrival_index: 0, status_changes }
+    }
+}
 
 impl StarknetTransactions {
-    pub fn insert(&mut self, transaction_hash: &TransactionHash, transaction: StarknetTransaction) {
-        self.0.insert(*transaction_hash, transaction);
+    /// Inserts the transaction and assigns it the next arrival index, which is returned.
+    /// Transactions are inserted while holding exclusive access to Starknet, so concurrently
+    /// submitted transactions get distinct indices in the order of their intake.
+    pub fn insert(
+        &mut self,
+        transaction_hash: &TransactionHash,
+        mut transaction: StarknetTransaction,
+    ) -> u64 {
+        let arrival_index = self.next_arrival_index;
+        self.next_arrival_index += 1;
+
+        transaction.arrival_index = arrival_index;
+        self.transactions.insert(*transaction_hash, transaction);
+        self.notify_status_change(transaction_hash);
+
+        arrival_index
+    }
+
+    /// Returns a receiver of the hash of every transaction inserted or changing its status after
+    /// the moment of subscribing
+    pub(crate) fn subscribe_to_status_changes(&self) -> broadcast::Receiver<TransactionHash> {
+        self.status_changes.subscribe()
+    }
+
+    /// Has to be called after changing the status of the transaction with `transaction_hash`
+    pub(crate) fn notify_status_change(&self, transaction_hash: &TransactionHash) {
+        // sending fails only if there are no subscribers, which is not an error
+        let _ = self.status_changes.send(*transaction_hash);
+    }
+
+    /// Arrival index that the next inserted transaction will get
+    pub(crate) fn next_arrival_index(&self) -> u64 {
+        self.next_arrival_index
+    }
+
+    /// Removes the transactions that arrived at `arrival_index` or later; the next inserted
+    /// transaction gets `arrival_index`
+    pub(crate) fn remove_from_arrival_index(&mut self, arrival_index: u64) {
+        self.transactions.retain(|_, transaction| transaction.arrival_index < arrival_index);
+        self.next_arrival_index = arrival_index;
+    }
+}
+
+impl HashIdentified for StarknetTransactions {
+    type Hash = TransactionHash;
+    type Element = StarknetTransaction;
+    fn get_by_hash(&self, hash: Self::Hash) -> Option<&StarknetTransaction> {
+        self.transactions.get(&hash)
     }
 }
 
@@ -30,10 +139,31 @@ impl HashIdentifiedMut for StarknetTransactions {
     type Hash = TransactionHash;
     type Element = StarknetTransaction;
     fn get_by_hash_mut(&mut self, hash: &Self::Hash) -> Option<&mut StarknetTransaction> {
-        self.0.get_mut(hash)
+        self.transactions.get_mut(hash)
     }
 }
 
+/// Outcome of the execution of an accepted transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionStatus {
+    Succeeded,
+    /// The transaction was executed, but its changes were reverted; it is still included in a
+    /// block and charged a fee
+    Reverted,
+}
+
+/// Resources consumed by the execution of a transaction, summed over all of its calls
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutionResources {
+    /// Cairo steps, including the ones executed before a revert
+    pub steps: u64,
+    pub memory_holes: u64,
+    /// Number of instances of every used builtin, by builtin name, e.g. `range_check_builtin`
+    pub builtin_instance_counter: HashMap<String, u64>,
+    /// Data gas needed to publish the state diff of the transaction as a blob
+    pub data_gas_consumed: u128,
+}
+
 #[allow(unused)]
 pub struct StarknetTransaction {
     pub(crate) status: TransactionStatus,
@@ -42,6 +172,13 @@ pub struct StarknetTransaction {
     pub(crate) block_number: Option<BlockNumber>,
     pub(crate) execution_info: Option<starknet_in_rust::execution::TransactionExecutionInfo>,
     pub(crate) execution_error: Option<TransactionError>,
+    /// Data gas needed to publish the state diff of the transaction, 0 if it was rejected
+    pub(crate) data_gas_consumed: u128,
+    /// Position of the transaction in the order of intake, assigned on insertion
+    pub(crate) arrival_index: u64,
+    /// Whether the transaction was executed without validation, since its sender was
+    /// impersonated
+    pub(crate) skip_validation: bool,
 }
 
 impl StarknetTransaction {
@@ -51,32 +188,137 @@ impl StarknetTransaction {
             inner: transaction,
             execution_info: None,
             execution_error: Some(execution_error),
+            data_gas_consumed: 0,
+            block_hash: None,
+            block_number: None,
+            arrival_index: 0,
+            skip_validation: false,
+        }
+    }
+
+    /// Transaction that was received, but is not executed yet
+    pub fn create_received(transaction: Transaction) -> Self {
+        Self {
+            status: TransactionStatus::Pending,
+            inner: transaction,
+            execution_info: None,
+            execution_error: None,
+            data_gas_consumed: 0,
             block_hash: None,
             block_number: None,
+            arrival_index: 0,
+            skip_validation: false,
         }
     }
 
     pub fn create_successful(
         transaction: Transaction,
         execution_info: TransactionExecutionInfo,
+        data_gas_consumed: u128,
     ) -> Self {
         Self {
             status: TransactionStatus::Pending,
             inner: transaction,
             execution_info: Some(execution_info),
             execution_error: None,
+            data_gas_consumed,
             block_hash: None,
             block_number: None,
+            arrival_index: 0,
+            skip_validation: false,
         }
     }
+
+    pub fn get_transaction(&self) -> &Transaction {
+        &self.inner
+    }
+
+    pub fn status(&self) -> &TransactionStatus {
+        &self.status
+    }
+
+    pub fn block_hash(&self) -> Option<BlockHash> {
+        self.block_hash
+    }
+
+    pub fn block_number(&self) -> Option<BlockNumber> {
+        self.block_number
+    }
+
+    pub fn arrival_index(&self) -> u64 {
+        self.arrival_index
+    }
+
+    /// Outcome of the execution, None if the transaction was rejected before being executed
+    pub fn execution_status(&self) -> Option<ExecutionStatus> {
+        self.execution_info.as_ref().map(|execution_info| match execution_info.revert_error {
+            Some(_) => ExecutionStatus::Reverted,
+            None => ExecutionStatus::Succeeded,
+        })
+    }
+
+    /// Reason why the execution of the transaction was reverted, including the failed entry point
+    /// and its decoded panic data
+    pub fn revert_reason(&self) -> Option<String> {
+        self.execution_info.as_ref().and_then(revert_reason::describe)
+    }
+
+    /// Fee charged for the execution, 0 if the transaction was rejected
+    pub fn actual_fee(&self) -> u128 {
+        self.execution_info.as_ref().map_or(0, |execution_info| execution_info.actual_fee)
+    }
+
+    /// Resources consumed by the execution, including the ones of nested calls and, for reverted
+    /// transactions, the ones consumed up to the revert. Empty if the transaction was rejected.
+    pub fn execution_resources(&self) -> ExecutionResources {
+        let execution_info = match &self.execution_info {
+            Some(execution_info) => execution_info,
+            None => return ExecutionResources::default(),
+        };
+
+        let mut resources =
+            ExecutionResources { data_gas_consumed: self.data_gas_consumed, ..Default::default() };
+        for (name, amount) in &execution_info.actual_resources {
+            if name == N_STEPS {
+                resources.steps = *amount as u64;
+            } else if name.ends_with(BUILTIN_SUFFIX) && *amount > 0 {
+                resources.builtin_instance_counter.insert(name.clone(), *amount as u64);
+            }
+        }
+        // starknet_in_rust doesn't report memory holes among the resources of the transaction
+        resources.memory_holes = [
+            &execution_info.validate_info,
+            &execution_info.call_info,
+            &execution_info.fee_transfer_info,
+        ]
+        .into_iter()
+        .flatten()
+        .map(memory_holes)
+        .sum();
+
+        resources
+    }
+}
+
+/// Memory holes left by the execution of the call and of all calls nested in it
+fn memory_holes(call_info: &CallInfo) -> u64 {
+    call_info
+        .execution_resources
+        .as_ref()
+        .map_or(0, |execution_resources| execution_resources.n_memory_holes as u64)
+        + call_info.internal_calls.iter().map(memory_holes).sum::<u64>()
 }
 
 #[derive(Clone, PartialEq, Eq)]
 pub enum Transaction {
     Declare(Box<DeclareTransactionV1>),
     DeclareV2(Box<DeclareTransactionV2>),
+    DeclareV3(Box<DeclareTransactionV3>),
     DeployAccount(Box<DeployAccountTransaction>),
+    DeployAccountV3(Box<DeployAccountTransactionV3>),
     Invoke(Box<InvokeTransactionV1>),
+    InvokeV3(Box<InvokeTransactionV3>),
+    L1Handler(Box<L1HandlerTransaction>),
 }
 
 impl Transaction {
@@ -84,8 +326,12 @@ impl Transaction {
         match self {
             Transaction::Declare(tx) => tx.transaction_hash,
             Transaction::DeclareV2(tx) => tx.transaction_hash,
+            Transaction::DeclareV3(tx) => tx.transaction_hash,
             Transaction::DeployAccount(tx) => Some(tx.inner.hash_value().clone().into()),
+            Transaction::DeployAccountV3(tx) => Some(tx.inner.hash_value().clone().into()),
             Transaction::Invoke(tx) => Some(tx.inner.hash_value().clone().into()),
+            Transaction::InvokeV3(tx) => Some(tx.inner.hash_value().clone().into()),
+            Transaction::L1Handler(tx) => Some(tx.inner.hash_value().clone().into()),
         }
     }
 
@@ -93,8 +339,12 @@ impl Transaction {
         match self {
             Transaction::Declare(txn) => &txn.chain_id,
             Transaction::DeclareV2(txn) => &txn.chain_id,
+            Transaction::DeclareV3(txn) => &txn.chain_id,
             Transaction::DeployAccount(txn) => &txn.chain_id,
+            Transaction::DeployAccountV3(txn) => &txn.chain_id,
             Transaction::Invoke(txn) => &txn.chain_id,
+            Transaction::InvokeV3(txn) => &txn.chain_id,
+            Transaction::L1Handler(txn) => &txn.chain_id,
         }
     }
 
@@ -102,17 +352,26 @@ impl Transaction {
         match self {
             Transaction::Declare(txn) => txn.max_fee,
             Transaction::DeclareV2(txn) => txn.max_fee,
+            Transaction::DeclareV3(txn) => txn.max_fee(),
             Transaction::DeployAccount(txn) => txn.max_fee,
+            Transaction::DeployAccountV3(txn) => txn.max_fee(),
             Transaction::Invoke(txn) => txn.max_fee,
+            Transaction::InvokeV3(txn) => txn.max_fee(),
+            // the fee of an L1 handler transaction is paid on L1
+            Transaction::L1Handler(_) => 0,
         }
     }
 
-    pub fn signature(&self) -> &Vec<Felt> {
+    pub fn signature(&self) -> &[Felt] {
         match self {
             Transaction::Declare(txn) => &txn.signature,
             Transaction::DeclareV2(txn) => &txn.signature,
+            Transaction::DeclareV3(txn) => &txn.signature,
             Transaction::DeployAccount(txn) => &txn.signature,
+            Transaction::DeployAccountV3(txn) => &txn.signature,
             Transaction::Invoke(txn) => &txn.signature,
+            Transaction::InvokeV3(txn) => &txn.signature,
+            Transaction::L1Handler(_) => &[],
         }
     }
 
@@ -120,8 +379,12 @@ impl Transaction {
         match self {
             Transaction::Declare(txn) => &txn.nonce,
             Transaction::DeclareV2(txn) => &txn.nonce,
+            Transaction::DeclareV3(txn) => &txn.nonce,
             Transaction::DeployAccount(txn) => &txn.nonce,
+            Transaction::DeployAccountV3(txn) => &txn.nonce,
             Transaction::Invoke(txn) => &txn.nonce,
+            Transaction::InvokeV3(txn) => &txn.nonce,
+            Transaction::L1Handler(txn) => &txn.nonce,
         }
     }
 
@@ -129,20 +392,85 @@ impl Transaction {
         match self {
             Transaction::Declare(txn) => &txn.version,
             Transaction::DeclareV2(txn) => &txn.version,
+            Transaction::DeclareV3(txn) => &txn.version,
             Transaction::DeployAccount(txn) => &txn.version,
+            Transaction::DeployAccountV3(txn) => &txn.version,
             Transaction::Invoke(txn) => &txn.version,
+            Transaction::InvokeV3(txn) => &txn.version,
+            Transaction::L1Handler(txn) => &txn.version,
+        }
+    }
+
+    /// Fields of transactions of version 3, `None` for transactions of earlier versions
+    pub fn v3_fields(&self) -> Option<&V3Fields> {
+        match self {
+            Transaction::DeclareV3(txn) => Some(&txn.v3_fields),
+            Transaction::DeployAccountV3(txn) => Some(&txn.v3_fields),
+            Transaction::InvokeV3(txn) => Some(&txn.v3_fields),
+            _ => None,
+        }
+    }
+
+    /// Address of the account sending the transaction. For deploy account transactions it is the
+    /// deployed account, for L1 handler transactions the contract handling the message
+    pub fn sender_address(&self) -> Result<ContractAddress> {
+        match self {
+            Transaction::Declare(txn) => Ok(*txn.sender_address()),
+            Transaction::DeclareV2(txn) => Ok(*txn.sender_address()),
+            Transaction::DeclareV3(txn) => Ok(*txn.sender_address()),
+            Transaction::DeployAccount(txn) => {
+                Ok(txn.inner.contract_address().clone().try_into()?)
+            }
+            Transaction::DeployAccountV3(txn) => {
+                Ok(txn.inner.contract_address().clone().try_into()?)
+            }
+            Transaction::Invoke(txn) => txn.sender_address(),
+            Transaction::InvokeV3(txn) => txn.sender_address(),
+            Transaction::L1Handler(txn) => Ok(txn.contract_address()),
+        }
+    }
+
+    /// Unit of the fee charged for the transaction, identifying the token it is paid with.
+    /// Transactions of version 3 pay in STRK
+    pub fn fee_unit(&self) -> FeeUnit {
+        match self.v3_fields() {
+            Some(_) => FeeUnit::Fri,
+            None => FeeUnit::Wei,
         }
     }
+
+    /// Creates the transaction executed by starknet_in_rust
+    pub(crate) fn create_sir_transaction(&self) -> Result<SirTransaction> {
+        Ok(match self {
+            Transaction::Declare(txn) => SirTransaction::Declare(txn.create_sir_declare()?),
+            Transaction::DeclareV2(txn) => {
+                SirTransaction::DeclareV2(Box::new(txn.create_sir_declare()?))
+            }
+            Transaction::DeclareV3(txn) => {
+                SirTransaction::DeclareV2(Box::new(txn.create_sir_declare()?))
+            }
+            Transaction::DeployAccount(txn) => SirTransaction::DeployAccount(txn.inner.clone()),
+            Transaction::DeployAccountV3(txn) => SirTransaction::DeployAccount(txn.inner.clone()),
+            Transaction::Invoke(txn) => SirTransaction::InvokeFunction(txn.inner.clone()),
+            Transaction::InvokeV3(txn) => SirTransaction::InvokeFunction(txn.inner.clone()),
+            Transaction::L1Handler(txn) => SirTransaction::L1Handler(txn.inner.clone()),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use starknet_in_rust::execution::TransactionExecutionInfo;
     use starknet_rs_core::types::TransactionStatus;
+    use starknet_types::felt::Felt;
     use starknet_types::traits::HashProducer;
 
-    use super::{StarknetTransaction, StarknetTransactions, Transaction};
-    use crate::traits::HashIdentifiedMut;
+    use super::{
+        ExecutionResources, ExecutionStatus, StarknetTransaction, StarknetTransactions, Transaction,
+    };
+    use crate::traits::{HashIdentified, HashIdentifiedMut};
     use crate::utils::test_utils::dummy_declare_transaction_v1;
 
     #[test]
@@ -151,6 +479,7 @@ mod tests {
         let sn_tx = StarknetTransaction::create_successful(
             Transaction::Declare(dummy_declare_transaction_v1()),
             TransactionExecutionInfo::default(),
+            0,
         );
         let mut sn_txs = StarknetTransactions::default();
         sn_txs.insert(
@@ -158,6 +487,7 @@ mod tests {
             StarknetTransaction::create_successful(
                 Transaction::Declare(dummy_declare_transaction_v1()),
                 TransactionExecutionInfo::default(),
+                0,
             ),
         );
 
@@ -171,6 +501,126 @@ mod tests {
         assert_eq!(sn_tx.execution_info.is_some(), extracted_tran.execution_info.is_some());
     }
 
+    #[test]
+    fn inserted_transactions_get_consecutive_arrival_indices() {
+        let mut sn_txs = StarknetTransactions::default();
+        let arrival_indices: Vec<u64> = [Felt::from(1), Felt::from(2), Felt::from(3)]
+            .iter()
+            .map(|hash| {
+                sn_txs.insert(
+                    hash,
+                    StarknetTransaction::create_successful(
+                        Transaction::Declare(dummy_declare_transaction_v1()),
+                        TransactionExecutionInfo::default(),
+                        0,
+                    ),
+                )
+            })
+            .collect();
+
+        assert_eq!(arrival_indices, vec![0, 1, 2]);
+        assert_eq!(sn_txs.get_by_hash(Felt::from(2)).unwrap().arrival_index(), 1);
+    }
+
+    #[test]
+    fn transactions_removed_from_arrival_index_are_replaced_by_next_inserted() {
+        let mut sn_txs = StarknetTransactions::default();
+        let create_transaction = || {
+            StarknetTransaction::create_successful(
+                Transaction::Declare(dummy_declare_transaction_v1()),
+                TransactionExecutionInfo::default(),
+                0,
+            )
+        };
+        for hash in [Felt::from(1), Felt::from(2), Felt::from(3)] {
+            sn_txs.insert(&hash, create_transaction());
+        }
+
+        sn_txs.remove_from_arrival_index(1);
+        assert!(sn_txs.get_by_hash(Felt::from(1)).is_some());
+        assert!(sn_txs.get_by_hash(Felt::from(2)).is_none());
+        assert!(sn_txs.get_by_hash(Felt::from(3)).is_none());
+        assert_eq!(sn_txs.insert(&Felt::from(4), create_transaction()), 1);
+    }
+
+    #[test]
+    fn execution_status_distinguishes_reverted_from_rejected_transactions() {
+        let succeeded = StarknetTransaction::create_successful(
+            Transaction::Declare(dummy_declare_transaction_v1()),
+            TransactionExecutionInfo::default(),
+            0,
+        );
+        assert_eq!(succeeded.execution_status(), Some(ExecutionStatus::Succeeded));
+        assert_eq!(succeeded.revert_reason(), None);
+
+        let reverted = StarknetTransaction::create_successful(
+            Transaction::Declare(dummy_declare_transaction_v1()),
+            TransactionExecutionInfo {
+                revert_error: Some("Insufficient balance".to_string()),
+                ..TransactionExecutionInfo::default()
+            },
+            0,
+        );
+        assert_eq!(reverted.status, TransactionStatus::Pending);
+        assert_eq!(reverted.execution_status(), Some(ExecutionStatus::Reverted));
+        assert_eq!(reverted.revert_reason().as_deref(), Some("Insufficient balance"));
+
+        let rejected = StarknetTransaction::create_rejected(
+            Transaction::Declare(dummy_declare_transaction_v1()),
+            starknet_in_rust::transaction::error::TransactionError::AttempToUseNoneCodeAddress,
+        );
+        assert_eq!(rejected.status, TransactionStatus::Rejected);
+        assert_eq!(rejected.execution_status(), None);
+    }
+
+    #[test]
+    fn execution_resources_taken_from_actual_resources_without_unused_builtins() {
+        let transaction = StarknetTransaction::create_successful(
+            Transaction::Declare(dummy_declare_transaction_v1()),
+            TransactionExecutionInfo {
+                actual_resources: HashMap::from([
+                    ("n_steps".to_string(), 1234),
+                    ("l1_gas_usage".to_string(), 5678),
+                    ("range_check_builtin".to_string(), 12),
+                    ("pedersen_builtin".to_string(), 0),
+                ]),
+                ..TransactionExecutionInfo::default()
+            },
+            64,
+        );
+
+        let resources = transaction.execution_resources();
+        assert_eq!(resources.steps, 1234);
+        assert_eq!(
+            resources.builtin_instance_counter,
+            HashMap::from([("range_check_builtin".to_string(), 12)])
+        );
+        assert_eq!(resources.data_gas_consumed, 64);
+
+        let rejected = StarknetTransaction::create_rejected(
+            Transaction::Declare(dummy_declare_transaction_v1()),
+            starknet_in_rust::transaction::error::TransactionError::AttempToUseNoneCodeAddress,
+        );
+        assert_eq!(rejected.execution_resources(), ExecutionResources::default());
+    }
+
+    #[test]
+    fn reverted_transaction_reports_resources_consumed_up_to_revert() {
+        // starknet_in_rust counts the steps executed before the revert among the steps
+        let reverted = StarknetTransaction::create_successful(
+            Transaction::Declare(dummy_declare_transaction_v1()),
+            TransactionExecutionInfo {
+                revert_error: Some("Insufficient balance".to_string()),
+                actual_resources: HashMap::from([("n_steps".to_string(), 321)]),
+                ..TransactionExecutionInfo::default()
+            },
+            0,
+        );
+
+        assert_eq!(reverted.execution_status(), Some(ExecutionStatus::Reverted));
+        assert_eq!(reverted.execution_resources().steps, 321);
+    }
+
     #[test]
     fn check_correct_rejected_transaction_creation() {
         check_correct_transaction_properties(
@@ -192,6 +642,7 @@ mod tests {
             StarknetTransaction::create_successful(
                 tran.clone(),
                 TransactionExecutionInfo::default(),
+                0,
             )
         } else {
             StarknetTransaction::create_rejected(
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use starknet_in_rust::transaction::error::TransactionError;
+use starknet_rs_crypto::poseidon_hash_many;
+use starknet_rs_ff::FieldElement;
+use starknet_types::felt::Felt;
+
+use crate::error::{Error, Result};
+
+const L1_GAS_RESOURCE_NAME: &[u8] = b"L1_GAS";
+const L2_GAS_RESOURCE_NAME: &[u8] = b"L2_GAS";
+
+/// Max amount of a resource a transaction can use, and the max price paid per unit of it, in fri
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceBounds {
+    pub max_amount: u64,
+    pub max_price_per_unit: u128,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceBoundsMapping {
+    pub l1_gas: ResourceBounds,
+    pub l2_gas: ResourceBounds,
+}
+
+/// Layer on which the data of a transaction is published
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DataAvailabilityMode {
+    #[default]
+    L1,
+    L2,
+}
+
+impl DataAvailabilityMode {
+    fn as_u64(&self) -> u64 {
+        match self {
+            DataAvailabilityMode::L1 => 0,
+            DataAvailabilityMode::L2 => 1,
+        }
+    }
+}
+
+/// Fields shared by transactions of version 3, whose fee is paid in STRK. Such transactions are
+/// executed as their predecessors paying in ETH, with the hash of version 3 and limited by the fee
+/// implied by the L1 gas bounds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct V3Fields {
+    pub resource_bounds: ResourceBoundsMapping,
+    pub tip: u64,
+    pub paymaster_data: Vec<Felt>,
+    pub nonce_data_availability_mode: DataAvailabilityMode,
+    pub fee_data_availability_mode: DataAvailabilityMode,
+}
+
+impl V3Fields {
+    /// Fee in fri the transaction can be charged at most: the max amount of L1 gas at its max price
+    pub fn max_fee(&self) -> u128 {
+        let l1_gas = self.resource_bounds.l1_gas;
+        (l1_gas.max_amount as u128).saturating_mul(l1_gas.max_price_per_unit)
+    }
+
+    /// Checks that the L1 gas bounds allow paying any fee. `transaction_name` identifies the
+    /// transaction in the error message
+    pub(crate) fn validate_l1_gas_bounds(&self, transaction_name: &str) -> Result<()> {
+        let l1_gas = self.resource_bounds.l1_gas;
+        if l1_gas.max_amount == 0 || l1_gas.max_price_per_unit == 0 {
+            return Err(Error::TransactionError(TransactionError::FeeError(format!(
+                "For {transaction_name} transaction version 3, L1 gas bounds cannot be 0"
+            ))));
+        }
+
+        Ok(())
+    }
+
+    /// Hash of a transaction of version 3: the poseidon hash of the fields common to all
+    /// transactions, prefixed by `prefix`, followed by `specific_fields` of the transaction type
+    pub(crate) fn transaction_hash(
+        &self,
+        prefix: &[u8],
+        sender_address: Felt,
+        chain_id: Felt,
+        nonce: Felt,
+        specific_fields: &[FieldElement],
+    ) -> Felt {
+        let fee_fields_hash = poseidon_hash_many(&[
+            FieldElement::from(self.tip),
+            resource_bounds_field_element(L1_GAS_RESOURCE_NAME, &self.resource_bounds.l1_gas),
+            resource_bounds_field_element(L2_GAS_RESOURCE_NAME, &self.resource_bounds.l2_gas),
+        ]);
+        let data_availability_modes = (self.nonce_data_availability_mode.as_u64() << 32)
+            + self.fee_data_availability_mode.as_u64();
+
+        let mut elements = vec![
+            short_string_field_element(prefix),
+            // version
+            FieldElement::from(3u64),
+            sender_address.into(),
+            fee_fields_hash,
+            poseidon_hash_of(&self.paymaster_data),
+            chain_id.into(),
+            nonce.into(),
+            FieldElement::from(data_availability_modes),
+        ];
+        elements.extend_from_slice(specific_fields);
+
+        poseidon_hash_many(&elements).into()
+    }
+}
+
+pub(crate) fn poseidon_hash_of(felts: &[Felt]) -> FieldElement {
+    poseidon_hash_many(&felts.iter().map(|felt| FieldElement::from(*felt)).collect::<Vec<_>>())
+}
+
+/// Bounds of a resource packed in a single field element: the resource name in the 64 highest
+/// bits, followed by the max amount in 64 bits and the max price per unit in 128 bits
+fn resource_bounds_field_element(resource_name: &[u8], bounds: &ResourceBounds) -> FieldElement {
+    let mut bytes = [0u8; 32];
+    bytes[8 - resource_name.len()..8].copy_from_slice(resource_name);
+    bytes[8..16].copy_from_slice(&bounds.max_amount.to_be_bytes());
+    bytes[16..].copy_from_slice(&bounds.max_price_per_unit.to_be_bytes());
+
+    FieldElement::from_bytes_be(&bytes).expect("Resource bounds should fit in a field element")
+}
+
+fn short_string_field_element(short_string: &[u8]) -> FieldElement {
+    let mut bytes = [0u8; 32];
+    bytes[32 - short_string.len()..].copy_from_slice(short_string);
+
+    FieldElement::from_bytes_be(&bytes).expect("Short string should fit in a field element")
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_types::felt::Felt;
+
+    use super::{
+        resource_bounds_field_element, ResourceBounds, ResourceBoundsMapping, V3Fields,
+        L1_GAS_RESOURCE_NAME,
+    };
+
+    fn v3_fields(max_amount: u64, max_price_per_unit: u128) -> V3Fields {
+        V3Fields {
+            resource_bounds: ResourceBoundsMapping {
+                l1_gas: ResourceBounds { max_amount, max_price_per_unit },
+                l2_gas: ResourceBounds::default(),
+            },
+            ..V3Fields::default()
+        }
+    }
+
+    #[test]
+    fn max_fee_is_implied_by_l1_gas_bounds() {
+        assert_eq!(v3_fields(1000, 3).max_fee(), 3000);
+        assert_eq!(v3_fields(u64::MAX, u128::MAX).max_fee(), u128::MAX);
+    }
+
+    #[test]
+    fn zero_l1_gas_bounds_rejected() {
+        match v3_fields(0, 1).validate_l1_gas_bounds("invoke") {
+            Err(crate::error::Error::TransactionError(
+                starknet_in_rust::transaction::error::TransactionError::FeeError(msg),
+            )) => assert_eq!(msg, "For invoke transaction version 3, L1 gas bounds cannot be 0"),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        assert!(v3_fields(1, 1).validate_l1_gas_bounds("invoke").is_ok());
+    }
+
+    #[test]
+    fn hash_depends_on_resource_bounds() {
+        let hash_of = |fields: V3Fields| {
+            fields.transaction_hash(b"invoke", Felt::from(1), Felt::from(2), Felt::from(0), &[])
+        };
+
+        assert_eq!(hash_of(v3_fields(1000, 3)), hash_of(v3_fields(1000, 3)));
+        assert_ne!(hash_of(v3_fields(1000, 3)), hash_of(v3_fields(1000, 4)));
+    }
+
+    #[test]
+    fn resource_bounds_packed_with_resource_name_in_highest_bits() {
+        let packed = resource_bounds_field_element(
+            L1_GAS_RESOURCE_NAME,
+            &ResourceBounds { max_amount: 0x10, max_price_per_unit: 0x20 },
+        );
+
+        let resource_name_hex: String =
+            L1_GAS_RESOURCE_NAME.iter().map(|byte| format!("{byte:02x}")).collect();
+        assert_eq!(
+            format!("{packed:#x}"),
+            format!("0x{resource_name_hex}{:016x}{:032x}", 0x10, 0x20)
+        );
+    }
+}
@@ -23,6 +23,12 @@ pub(crate) fn load_cairo_0_contract_class(path: &str) -> Result<ContractClass> {
     Ok(ContractClass::cairo_0_from_json_str(&contract_class_str)?)
 }
 
+pub(crate) fn load_cairo_1_contract_class(sierra_path: &str) -> Result<ContractClass> {
+    let contract_class_str = fs::read_to_string(sierra_path)
+        .map_err(|err| Error::ReadFileError { source: err, path: sierra_path.to_string() })?;
+    Ok(ContractClass::cairo_1_from_sierra_json_str(&contract_class_str)?)
+}
+
 /// Returns the storage address of a Starknet storage variable given its name and arguments.
 pub(crate) fn get_storage_var_address(storage_var_name: &str, args: &[Felt]) -> Result<StorageKey> {
     let storage_var_name_hash = calculate_sn_keccak(storage_var_name.as_bytes());
@@ -50,11 +56,18 @@ pub(crate) mod test_utils {
 
     use super::load_cairo_0_contract_class;
     use crate::constants::{
-        DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_GAS_PRICE, DEVNET_DEFAULT_HOST,
-        DEVNET_DEFAULT_INITIAL_BALANCE, DEVNET_DEFAULT_PORT, DEVNET_DEFAULT_TEST_SEED,
+        DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_ETH_STRK_RATE, DEVNET_DEFAULT_HOST,
+        DEVNET_DEFAULT_INITIAL_BALANCE, DEVNET_DEFAULT_MAX_CALLDATA_LEN,
+        DEVNET_DEFAULT_MAX_SIGNATURE_LEN, DEVNET_DEFAULT_MAX_STEPS, DEVNET_DEFAULT_PORT,
+        DEVNET_DEFAULT_REQUEST_BODY_LIMIT, DEVNET_DEFAULT_RPC_PATH, DEVNET_DEFAULT_TEST_SEED,
         DEVNET_DEFAULT_TIMEOUT, DEVNET_DEFAULT_TOTAL_ACCOUNTS,
+        DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL, ERC20_DECIMALS, ETH_ERC20_NAME,
+        ETH_ERC20_SYMBOL, STRK_ERC20_NAME, STRK_ERC20_SYMBOL,
+    };
+    use crate::starknet::{
+        default_sequencer_address, AccountClass, BlockGenerationOn, CalldataValidation, FeeUnit,
+        PendingOverflow, StarknetConfig, UndeployedStorageWrite,
     };
-    use crate::starknet::StarknetConfig;
     use crate::transactions::declare_transaction::DeclareTransactionV1;
 
     pub fn starknet_config_for_test() -> StarknetConfig {
@@ -62,11 +75,65 @@ pub(crate) mod test_utils {
             seed: DEVNET_DEFAULT_TEST_SEED,
             total_accounts: DEVNET_DEFAULT_TOTAL_ACCOUNTS,
             predeployed_accounts_initial_balance: DEVNET_DEFAULT_INITIAL_BALANCE.into(),
+            predeployed_accounts_path: None,
+            no_auto_accounts: false,
             host: DEVNET_DEFAULT_HOST.to_string(),
             port: DEVNET_DEFAULT_PORT,
+            port_file: None,
+            rpc_path: DEVNET_DEFAULT_RPC_PATH.to_string(),
+            devnet_path: String::new(),
             timeout: DEVNET_DEFAULT_TIMEOUT,
-            gas_price: DEVNET_DEFAULT_GAS_PRICE,
+            request_body_limit: DEVNET_DEFAULT_REQUEST_BODY_LIMIT,
+            rate_limit: None,
+            transaction_subscription_ttl: DEVNET_DEFAULT_TRANSACTION_SUBSCRIPTION_TTL,
+            // dummy transactions are sent with low max fees by accounts with little or no funds
+            gas_price: 0,
+            data_gas_price: 0,
+            eth_strk_rate: DEVNET_DEFAULT_ETH_STRK_RATE,
+            max_steps: DEVNET_DEFAULT_MAX_STEPS,
+            max_calldata_len: DEVNET_DEFAULT_MAX_CALLDATA_LEN,
+            max_signature_len: DEVNET_DEFAULT_MAX_SIGNATURE_LEN,
             chain_id: DEVNET_DEFAULT_CHAIN_ID,
+            initial_block_number: 0,
+            initial_block_timestamp: None,
+            fake_sync_from: None,
+            fake_sync_until: None,
+            dump_on: None,
+            dump_path: None,
+            commit_on_shutdown: false,
+            state_dir: None,
+            fork_network: None,
+            fork_block: None,
+            auto_impersonate: false,
+            impersonate_auto_fund: None,
+            disable_fee_charge: false,
+            estimate_skip_validate: false,
+            allow_dev_declare: false,
+            allow_dev_deploy: false,
+            allow_dev_state: false,
+            undeployed_storage_write: UndeployedStorageWrite::Warn,
+            calldata_validation: CalldataValidation::Strict,
+            predeclare: None,
+            lite_mode: false,
+            block_generation_on: BlockGenerationOn::Transaction,
+            block_time_secs: 0,
+            parallel_execution: false,
+            max_pending_transactions: None,
+            pending_overflow: PendingOverflow::Seal,
+            account_class: AccountClass::Cairo0,
+            account_class_custom: None,
+            hide_predeployed_keys: false,
+            l1_node_url: None,
+            eth_fee_token_address: FeeUnit::Wei.default_erc20_address(),
+            strk_fee_token_address: FeeUnit::Fri.default_erc20_address(),
+            eth_token_name: ETH_ERC20_NAME.to_string(),
+            eth_token_symbol: ETH_ERC20_SYMBOL.to_string(),
+            eth_token_decimals: ERC20_DECIMALS,
+            strk_token_name: STRK_ERC20_NAME.to_string(),
+            strk_token_symbol: STRK_ERC20_SYMBOL.to_string(),
+            strk_token_decimals: ERC20_DECIMALS,
+            sequencer_address: default_sequencer_address(),
+            enable_metrics: false,
         }
     }
 
@@ -0,0 +1,130 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::felt::Felt;
+use crate::traits::ToHexString;
+
+/// Identifier of a Starknet network. It is part of the preimage of transaction hashes, so the same
+/// transaction has a different hash on each network.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ChainId(Felt);
+
+impl ChainId {
+    pub const MAINNET: ChainId = ChainId(Felt(short_string_bytes("SN_MAIN")));
+    pub const TESTNET: ChainId = ChainId(Felt(short_string_bytes("SN_GOERLI")));
+    pub const TESTNET2: ChainId = ChainId(Felt(short_string_bytes("SN_GOERLI2")));
+    pub const SEPOLIA: ChainId = ChainId(Felt(short_string_bytes("SN_SEPOLIA")));
+    pub const SEPOLIA_INTEGRATION: ChainId =
+        ChainId(Felt(short_string_bytes("SN_INTEGRATION_SEPOLIA")));
+
+    /// Names of the known networks, as accepted by `from_str`
+    const NAMED: [(&'static str, ChainId); 5] = [
+        ("MAINNET", Self::MAINNET),
+        ("TESTNET", Self::TESTNET),
+        ("TESTNET2", Self::TESTNET2),
+        ("SEPOLIA", Self::SEPOLIA),
+        ("SEPOLIA_INTEGRATION", Self::SEPOLIA_INTEGRATION),
+    ];
+
+    pub fn to_felt(&self) -> Felt {
+        self.0
+    }
+}
+
+/// Encodes an ASCII string of at most 31 characters as a big-endian felt
+const fn short_string_bytes(s: &str) -> [u8; 32] {
+    let s = s.as_bytes();
+    let mut bytes = [0u8; 32];
+    let mut i = 0;
+    while i < s.len() {
+        bytes[32 - s.len() + i] = s[i];
+        i += 1;
+    }
+
+    bytes
+}
+
+impl From<Felt> for ChainId {
+    fn from(felt: Felt) -> Self {
+        Self(felt)
+    }
+}
+
+impl Display for ChainId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.to_prefixed_hex_str())
+    }
+}
+
+/// Accepts the name of a known network (case insensitive), a `0x`-prefixed hex value or an ASCII
+/// string of at most 31 characters. Strings consisting only of hex digits are rejected, since they
+/// could be meant as either.
+impl FromStr for ChainId {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("Chain id can't be empty".to_string());
+        }
+
+        if let Some((_, chain_id)) =
+            Self::NAMED.iter().find(|(name, _)| name.eq_ignore_ascii_case(s))
+        {
+            return Ok(*chain_id);
+        }
+
+        if s.starts_with("0x") {
+            return Felt::from_prefixed_hex_str(s)
+                .map(Self)
+                .map_err(|_| format!("Invalid hex value for chain id: {s}"));
+        }
+
+        if s.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!(
+                "Ambiguous chain id: {s}; prefix it with 0x if it's meant as a hex value"
+            ));
+        }
+
+        if !s.is_ascii() || s.len() > 31 {
+            return Err(format!(
+                "Chain id has to be an ASCII string of at most 31 characters: {s}"
+            ));
+        }
+
+        Ok(Self(Felt(short_string_bytes(s))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChainId;
+    use crate::felt::Felt;
+
+    #[test]
+    fn known_networks_parsed_by_name() {
+        assert_eq!("MAINNET".parse::<ChainId>().unwrap(), ChainId::MAINNET);
+        assert_eq!("sepolia".parse::<ChainId>().unwrap(), ChainId::SEPOLIA);
+        assert_eq!("SEPOLIA_INTEGRATION".parse::<ChainId>().unwrap(), ChainId::SEPOLIA_INTEGRATION);
+        assert_eq!(
+            ChainId::MAINNET.to_felt(),
+            Felt::from_prefixed_hex_str("0x534e5f4d41494e").unwrap()
+        );
+    }
+
+    #[test]
+    fn custom_values_parsed_as_hex_or_ascii() {
+        assert_eq!("0x534e5f4d41494e".parse::<ChainId>().unwrap(), ChainId::MAINNET);
+        assert_eq!("SN_MAIN".parse::<ChainId>().unwrap(), ChainId::MAINNET);
+        assert_eq!("0x1".parse::<ChainId>().unwrap(), ChainId::from(Felt::from(1)));
+    }
+
+    #[test]
+    fn empty_invalid_and_ambiguous_values_rejected() {
+        assert!("".parse::<ChainId>().is_err());
+        assert!("0xZZ".parse::<ChainId>().is_err());
+        assert!("1234".parse::<ChainId>().is_err());
+        assert!("CAFE".parse::<ChainId>().is_err());
+        assert!("A_VERY_LONG_CHAIN_ID_OVER_31_CHARS".parse::<ChainId>().is_err());
+        assert!("ČAIN".parse::<ChainId>().is_err());
+    }
+}
@@ -6,7 +6,7 @@ use crate::patricia_key::PatriciaKey;
 use crate::traits::ToHexString;
 use crate::DevnetResult;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ContractAddress(pub(crate) PatriciaKey);
 
 impl ContractAddress {
@@ -1,3 +1,4 @@
+pub mod chain_id;
 pub mod contract_address;
 pub mod contract_class;
 pub mod contract_storage_key;